@@ -0,0 +1,43 @@
+// Pit two engines against each other using only the stable prelude:
+//
+//   cargo run --release -p catan-core --example arena -- 20
+//
+// Seats alternate between the engines, and each game gets its own seed.
+
+use catan_core::prelude::*;
+
+fn main() {
+    let games: u64 = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(10);
+    let config = GameConfiguration::default();
+
+    let mut wins = [0u32; 2];
+    for seed in 0..games {
+        let bots: Vec<SeatedBot> = (0..config.num_players)
+            .map(|seat| -> SeatedBot {
+                let engine: Box<dyn BotPlayer> = if seat % 2 == 0 {
+                    Box::new(ValueFunctionPlayer::new())
+                } else {
+                    Box::new(WeightedRandomPlayer::new())
+                };
+                SeatedBot::new(seat, engine)
+            })
+            .collect();
+        let record = run_game(&bots, config.clone(), seed);
+        if let Some(winner) = record.winner {
+            wins[winner as usize % 2] += 1;
+        }
+        println!(
+            "game {seed}: {:?} after {} turns, final points {:?}",
+            record.termination,
+            record.turns(),
+            record.final_vps
+        );
+    }
+    println!(
+        "ValueFunction {} - {} WeightedRandom over {games} games",
+        wins[0], wins[1]
+    );
+}
@@ -0,0 +1,608 @@
+use crate::enums::{FreqDeck, Resource};
+use crate::map_instance::{EdgeId, NodeId};
+use crate::map_template::Coordinate;
+use serde::{Deserialize, Serialize};
+
+/// Convert u8 resource index to Resource enum
+fn u8_to_resource(index: u8) -> Resource {
+    match index {
+        0 => Resource::Wood,
+        1 => Resource::Brick,
+        2 => Resource::Sheep,
+        3 => Resource::Wheat,
+        4 => Resource::Ore,
+        _ => Resource::Wood, // Default fallback
+    }
+}
+
+/// Convert Resource enum to u8 index
+pub fn resource_to_u8(resource: Resource) -> u8 {
+    match resource {
+        Resource::Wood => 0,
+        Resource::Brick => 1,
+        Resource::Sheep => 2,
+        Resource::Wheat => 3,
+        Resource::Ore => 4,
+    }
+}
+
+/// Cards of a trade side, one entry per card in resource order
+pub fn freqdeck_to_resources(deck: FreqDeck) -> Vec<Resource> {
+    (0..5u8)
+        .flat_map(|resource| {
+            std::iter::repeat_n(u8_to_resource(resource), deck[resource as usize] as usize)
+        })
+        .collect()
+}
+
+/// Card counts per resource of a trade side
+pub fn resources_to_freqdeck(resources: &[Resource]) -> FreqDeck {
+    let mut deck = [0; 5];
+    for &resource in resources {
+        deck[resource_to_u8(resource) as usize] += 1;
+    }
+    deck
+}
+
+/// Unique identifier for players
+pub type PlayerId = String;
+
+/// Unique identifier for games  
+pub type GameId = String;
+
+/// Core player actions that can be taken in the game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum PlayerAction {
+    // Basic actions
+    Roll,
+    EndTurn,
+    PassBuildPhase,
+
+    // Building actions
+    BuildRoad {
+        edge_id: EdgeId,
+    },
+    BuildSettlement {
+        node_id: NodeId,
+    },
+    BuildCity {
+        node_id: NodeId,
+    },
+    /// Seafarers: a ship on a sea edge
+    BuildShip {
+        edge_id: EdgeId,
+    },
+    /// Seafarers: relocate the ship at the open end of a shipping route
+    MoveShip {
+        from: EdgeId,
+        to: EdgeId,
+    },
+
+    // Development cards
+    BuyDevelopmentCard,
+    PlayKnight,
+    PlayYearOfPlenty {
+        resources: (Resource, Option<Resource>),
+    },
+    PlayMonopoly {
+        resource: Resource,
+    },
+    PlayRoadBuilding,
+
+    // Trading
+    MaritimeTrade {
+        give: Resource,
+        take: Resource,
+        ratio: u8,
+    },
+    /// Cards for cards with the other players, one entry per card
+    OfferTrade {
+        give: Vec<Resource>,
+        take: Vec<Resource>,
+    },
+    /// Answers to the offer on the table
+    AcceptTrade,
+    RejectTrade,
+    /// Other terms for the offer on the table, from the offerer's side like the offer
+    CounterTrade {
+        give: Vec<Resource>,
+        take: Vec<Resource>,
+    },
+    /// The offerer trades with one of the players who accepted
+    ConfirmTrade {
+        with: PlayerId,
+    },
+    CancelTrade,
+    GiftResource {
+        teammate: PlayerId,
+        resource: Resource,
+    },
+
+    // Special actions
+    MoveRobber {
+        coordinate: Coordinate,
+        victim: Option<PlayerId>,
+    },
+    /// Cards to give up; left empty, the server discards the suggested cards
+    Discard {
+        resources: Vec<Resource>,
+    },
+    /// Seafarers: one card of choice owed from a rolled gold field
+    ChooseGold {
+        resource: Resource,
+    },
+}
+
+/// High-level commands that can be sent to the game system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GameCommand {
+    /// Player wants to perform an action
+    PlayerAction {
+        player_id: PlayerId,
+        action: PlayerAction,
+    },
+
+    /// System-initiated actions
+    StartGame { game_id: GameId },
+    AddPlayer {
+        game_id: GameId,
+        player_id: PlayerId,
+        name: String,
+    },
+    RemovePlayer {
+        game_id: GameId,
+        player_id: PlayerId,
+    },
+
+    /// Bot actions
+    RequestBotAction {
+        game_id: GameId,
+        player_id: PlayerId,
+    },
+}
+
+/// Events that occur as a result of commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// Game lifecycle events
+    GameCreated {
+        game_id: GameId,
+    },
+    GameStarted {
+        game_id: GameId,
+    },
+    GameEnded {
+        game_id: GameId,
+        winner: Option<PlayerId>,
+    },
+
+    /// Player events
+    PlayerJoined {
+        game_id: GameId,
+        player_id: PlayerId,
+        name: String,
+    },
+    PlayerLeft {
+        game_id: GameId,
+        player_id: PlayerId,
+    },
+
+    /// Action events
+    ActionExecuted {
+        game_id: GameId,
+        player_id: PlayerId,
+        action: PlayerAction,
+        success: bool,
+        message: String,
+    },
+
+    /// State changes
+    GameStateChanged {
+        game_id: GameId,
+        new_state: crate::game::GameState,
+    },
+
+    /// Turn management
+    TurnChanged {
+        game_id: GameId,
+        current_player: PlayerId,
+    },
+
+    /// Dice events
+    DiceRolled {
+        game_id: GameId,
+        player_id: PlayerId,
+        dice: [u8; 2],
+    },
+
+    /// Diagnostics
+    LoopDetected {
+        game_id: GameId,
+        player_id: PlayerId,
+        repetitions: u8,
+        forced_end_turn: bool,
+    },
+
+    /// Error events
+    Error {
+        game_id: GameId,
+        player_id: Option<PlayerId>,
+        error: String,
+    },
+}
+
+/// Result of executing an action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionResult {
+    pub success: bool,
+    pub message: String,
+    pub events: Vec<GameEvent>,
+}
+
+impl ActionResult {
+    pub fn success(message: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            message: message.into(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn failure(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            message: message.into(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn with_events(mut self, events: Vec<GameEvent>) -> Self {
+        self.events = events;
+        self
+    }
+}
+
+impl PlayerAction {
+    /// Variant name, as it appears in the action's JSON (e.g. "BuildRoad")
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PlayerAction::Roll => "Roll",
+            PlayerAction::EndTurn => "EndTurn",
+            PlayerAction::PassBuildPhase => "PassBuildPhase",
+            PlayerAction::BuildRoad { .. } => "BuildRoad",
+            PlayerAction::BuildSettlement { .. } => "BuildSettlement",
+            PlayerAction::BuildCity { .. } => "BuildCity",
+            PlayerAction::BuildShip { .. } => "BuildShip",
+            PlayerAction::MoveShip { .. } => "MoveShip",
+            PlayerAction::BuyDevelopmentCard => "BuyDevelopmentCard",
+            PlayerAction::PlayKnight => "PlayKnight",
+            PlayerAction::PlayYearOfPlenty { .. } => "PlayYearOfPlenty",
+            PlayerAction::PlayMonopoly { .. } => "PlayMonopoly",
+            PlayerAction::PlayRoadBuilding => "PlayRoadBuilding",
+            PlayerAction::MaritimeTrade { .. } => "MaritimeTrade",
+            PlayerAction::OfferTrade { .. } => "OfferTrade",
+            PlayerAction::AcceptTrade => "AcceptTrade",
+            PlayerAction::RejectTrade => "RejectTrade",
+            PlayerAction::CounterTrade { .. } => "CounterTrade",
+            PlayerAction::ConfirmTrade { .. } => "ConfirmTrade",
+            PlayerAction::CancelTrade => "CancelTrade",
+            PlayerAction::GiftResource { .. } => "GiftResource",
+            PlayerAction::MoveRobber { .. } => "MoveRobber",
+            PlayerAction::Discard { .. } => "Discard",
+            PlayerAction::ChooseGold { .. } => "ChooseGold",
+        }
+    }
+}
+
+/// An action with some of its details left out, e.g. "BuildRoad touching node 12" or
+/// "MaritimeTrade giving Wood". Every field is optional; unset fields match anything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartialAction {
+    /// Action kind, the `PlayerAction` variant name (e.g. "BuildRoad")
+    pub kind: Option<String>,
+    /// Settlement or city node, or either end of a road or ship
+    pub node_id: Option<NodeId>,
+    /// Road or ship edge, in either node order
+    pub edge_id: Option<EdgeId>,
+    /// Resource given away in a maritime trade or trade offer
+    pub give: Option<Resource>,
+    /// Resource received in a maritime trade or trade offer
+    pub take: Option<Resource>,
+    /// Cards given per card received in a maritime trade
+    pub ratio: Option<u8>,
+    /// Monopolized, gifted, gold, or one of the Year of Plenty resources
+    pub resource: Option<Resource>,
+    /// Both Year of Plenty resources, in either order
+    pub resources: Option<(Resource, Option<Resource>)>,
+    /// Robber destination tile
+    pub coordinate: Option<Coordinate>,
+    /// Robbed player
+    pub victim: Option<PlayerId>,
+}
+
+impl PartialAction {
+    /// Whether `action` fills in this partial action
+    pub fn matches(&self, action: &PlayerAction) -> bool {
+        if self
+            .kind
+            .as_deref()
+            .is_some_and(|kind| kind != action.kind())
+        {
+            return false;
+        }
+        let node_id = match action {
+            PlayerAction::BuildSettlement { node_id } | PlayerAction::BuildCity { node_id } => {
+                self.node_id.is_none_or(|wanted| wanted == *node_id)
+            }
+            PlayerAction::BuildRoad { edge_id } | PlayerAction::BuildShip { edge_id } => self
+                .node_id
+                .is_none_or(|wanted| wanted == edge_id.0 || wanted == edge_id.1),
+            _ => self.node_id.is_none(),
+        };
+        let edge_id = match action {
+            PlayerAction::BuildRoad { edge_id } | PlayerAction::BuildShip { edge_id } => self
+                .edge_id
+                .is_none_or(|(a, b)| (a, b) == *edge_id || (b, a) == *edge_id),
+            _ => self.edge_id.is_none(),
+        };
+        let trade = match action {
+            PlayerAction::MaritimeTrade { give, take, ratio } => {
+                self.give.is_none_or(|wanted| wanted == *give)
+                    && self.take.is_none_or(|wanted| wanted == *take)
+                    && self.ratio.is_none_or(|wanted| wanted == *ratio)
+            }
+            PlayerAction::OfferTrade { give, take } | PlayerAction::CounterTrade { give, take } => {
+                self.give.is_none_or(|wanted| give.contains(&wanted))
+                    && self.take.is_none_or(|wanted| take.contains(&wanted))
+                    && self.ratio.is_none()
+            }
+            _ => self.give.is_none() && self.take.is_none() && self.ratio.is_none(),
+        };
+        let resource = match action {
+            PlayerAction::PlayMonopoly { resource }
+            | PlayerAction::GiftResource { resource, .. }
+            | PlayerAction::ChooseGold { resource } => {
+                self.resource.is_none_or(|wanted| wanted == *resource)
+            }
+            PlayerAction::PlayYearOfPlenty { resources } => {
+                self.resource
+                    .is_none_or(|wanted| wanted == resources.0 || Some(wanted) == resources.1)
+                    && self.resources.is_none_or(|(a, b)| {
+                        (a, b) == *resources || b.is_some_and(|b| (b, Some(a)) == *resources)
+                    })
+            }
+            _ => self.resource.is_none() && self.resources.is_none(),
+        };
+        let robber = match action {
+            PlayerAction::MoveRobber { coordinate, victim } => {
+                self.coordinate.is_none_or(|wanted| wanted == *coordinate)
+                    && self
+                        .victim
+                        .as_ref()
+                        .is_none_or(|wanted| victim.as_ref() == Some(wanted))
+            }
+            _ => self.coordinate.is_none() && self.victim.is_none(),
+        };
+        node_id && edge_id && trade && resource && robber
+    }
+
+    /// The actions in `offered` that complete this partial action, in offered order
+    pub fn completions(&self, offered: &[PlayerAction]) -> Vec<PlayerAction> {
+        offered
+            .iter()
+            .filter(|action| self.matches(action))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Convert from the internal Action enum to our PlayerAction
+impl From<crate::enums::Action> for PlayerAction {
+    fn from(action: crate::enums::Action) -> Self {
+        use crate::enums::Action as EnumAction;
+
+        match action {
+            EnumAction::Roll { .. } => PlayerAction::Roll,
+            EnumAction::BuildRoad { edge_id, .. } => PlayerAction::BuildRoad { edge_id },
+            EnumAction::BuildSettlement { node_id, .. } => {
+                PlayerAction::BuildSettlement { node_id }
+            }
+            EnumAction::BuildCity { node_id, .. } => PlayerAction::BuildCity { node_id },
+            EnumAction::BuildShip { edge_id, .. } => PlayerAction::BuildShip { edge_id },
+            EnumAction::MoveShip { from, to, .. } => PlayerAction::MoveShip { from, to },
+            EnumAction::BuyDevelopmentCard { .. } => PlayerAction::BuyDevelopmentCard,
+            EnumAction::PlayKnight { .. } => PlayerAction::PlayKnight,
+            EnumAction::PlayYearOfPlenty { resources, .. } => PlayerAction::PlayYearOfPlenty {
+                resources: (u8_to_resource(resources.0), resources.1.map(u8_to_resource)),
+            },
+            EnumAction::PlayMonopoly { resource, .. } => PlayerAction::PlayMonopoly {
+                resource: u8_to_resource(resource),
+            },
+            EnumAction::PlayRoadBuilding { .. } => PlayerAction::PlayRoadBuilding,
+            EnumAction::MaritimeTrade {
+                give, take, ratio, ..
+            } => PlayerAction::MaritimeTrade {
+                give: u8_to_resource(give),
+                take: u8_to_resource(take),
+                ratio,
+            },
+            EnumAction::EndTurn { .. } => PlayerAction::EndTurn,
+            EnumAction::PassBuildPhase { .. } => PlayerAction::PassBuildPhase,
+            EnumAction::MoveRobber {
+                coordinate,
+                victim_opt,
+                ..
+            } => PlayerAction::MoveRobber {
+                coordinate,
+                victim: victim_opt.map(|c| format!("player_{c}")),
+            },
+            EnumAction::Discard { resources, .. } => PlayerAction::Discard {
+                resources: freqdeck_to_resources(resources),
+            },
+            EnumAction::ChooseGold { resource, .. } => PlayerAction::ChooseGold {
+                resource: u8_to_resource(resource),
+            },
+            EnumAction::GiftResource {
+                teammate, resource, ..
+            } => PlayerAction::GiftResource {
+                teammate: format!("player_{teammate}"),
+                resource: u8_to_resource(resource),
+            },
+            EnumAction::OfferTrade {
+                trade: (give, take),
+                ..
+            } => PlayerAction::OfferTrade {
+                give: freqdeck_to_resources(give),
+                take: freqdeck_to_resources(take),
+            },
+            EnumAction::AcceptTrade { .. } => PlayerAction::AcceptTrade,
+            EnumAction::RejectTrade { .. } => PlayerAction::RejectTrade,
+            EnumAction::CounterTrade {
+                trade: (give, take),
+                ..
+            } => PlayerAction::CounterTrade {
+                give: freqdeck_to_resources(give),
+                take: freqdeck_to_resources(take),
+            },
+            EnumAction::ConfirmTrade {
+                trade: (_, _, acceptee),
+                ..
+            } => PlayerAction::ConfirmTrade {
+                with: format!("player_{acceptee}"),
+            },
+            EnumAction::CancelTrade { .. } => PlayerAction::CancelTrade,
+            #[cfg(any(test, feature = "debug-actions"))]
+            _ => PlayerAction::EndTurn, // Debug actions have no client form
+        }
+    }
+}
+
+/// Convert from PlayerAction to internal Action enum
+impl From<PlayerAction> for crate::enums::Action {
+    fn from(action: PlayerAction) -> Self {
+        use crate::enums::Action as EnumAction;
+
+        match action {
+            PlayerAction::Roll => EnumAction::Roll {
+                color: 0,
+                dice_opt: None,
+            },
+            PlayerAction::BuildRoad { edge_id } => EnumAction::BuildRoad { color: 0, edge_id },
+            PlayerAction::BuildSettlement { node_id } => {
+                EnumAction::BuildSettlement { color: 0, node_id }
+            }
+            PlayerAction::BuildCity { node_id } => EnumAction::BuildCity { color: 0, node_id },
+            PlayerAction::BuildShip { edge_id } => EnumAction::BuildShip { color: 0, edge_id },
+            PlayerAction::MoveShip { from, to } => EnumAction::MoveShip { color: 0, from, to },
+            PlayerAction::BuyDevelopmentCard => EnumAction::BuyDevelopmentCard { color: 0 },
+            PlayerAction::PlayKnight => EnumAction::PlayKnight { color: 0 },
+            PlayerAction::PlayYearOfPlenty { resources } => EnumAction::PlayYearOfPlenty {
+                color: 0,
+                resources: (resource_to_u8(resources.0), resources.1.map(resource_to_u8)),
+            },
+            PlayerAction::PlayMonopoly { resource } => EnumAction::PlayMonopoly {
+                color: 0,
+                resource: resource_to_u8(resource),
+            },
+            PlayerAction::PlayRoadBuilding => EnumAction::PlayRoadBuilding { color: 0 },
+            PlayerAction::EndTurn => EnumAction::EndTurn { color: 0 },
+            PlayerAction::PassBuildPhase => EnumAction::PassBuildPhase { color: 0 },
+            PlayerAction::MoveRobber { coordinate, .. } => EnumAction::MoveRobber {
+                color: 0,
+                coordinate,
+                victim_opt: None,
+            },
+            // An empty discard is filled in by `State::canonical_form`
+            PlayerAction::Discard { resources } => EnumAction::Discard {
+                color: 0,
+                resources: resources_to_freqdeck(&resources),
+            },
+            PlayerAction::ChooseGold { resource } => EnumAction::ChooseGold {
+                color: 0,
+                resource: resource_to_u8(resource),
+            },
+            PlayerAction::OfferTrade { give, take } => EnumAction::OfferTrade {
+                color: 0,
+                trade: (resources_to_freqdeck(&give), resources_to_freqdeck(&take)),
+            },
+            // The terms of the offer on the table are filled in by `State::canonical_form`
+            PlayerAction::AcceptTrade => EnumAction::AcceptTrade {
+                color: 0,
+                trade: ([0; 5], [0; 5]),
+            },
+            PlayerAction::RejectTrade => EnumAction::RejectTrade { color: 0 },
+            PlayerAction::CancelTrade => EnumAction::CancelTrade { color: 0 },
+            _ => EnumAction::EndTurn { color: 0 }, // Default for unhandled actions
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offered() -> Vec<PlayerAction> {
+        vec![
+            PlayerAction::BuildRoad { edge_id: (3, 12) },
+            PlayerAction::BuildRoad { edge_id: (12, 13) },
+            PlayerAction::BuildRoad { edge_id: (4, 5) },
+            PlayerAction::BuildSettlement { node_id: 12 },
+            PlayerAction::MaritimeTrade {
+                give: Resource::Wood,
+                take: Resource::Ore,
+                ratio: 4,
+            },
+            PlayerAction::MaritimeTrade {
+                give: Resource::Brick,
+                take: Resource::Ore,
+                ratio: 4,
+            },
+            PlayerAction::EndTurn,
+        ]
+    }
+
+    #[test]
+    fn road_through_a_node_completes_to_its_edges() {
+        let partial = PartialAction {
+            kind: Some("BuildRoad".to_string()),
+            node_id: Some(12),
+            ..Default::default()
+        };
+        assert_eq!(
+            partial.completions(&offered()),
+            vec![
+                PlayerAction::BuildRoad { edge_id: (3, 12) },
+                PlayerAction::BuildRoad { edge_id: (12, 13) },
+            ]
+        );
+
+        // Edges match in either node order
+        let partial = PartialAction {
+            edge_id: Some((5, 4)),
+            ..Default::default()
+        };
+        assert_eq!(
+            partial.completions(&offered()),
+            vec![PlayerAction::BuildRoad { edge_id: (4, 5) }]
+        );
+    }
+
+    #[test]
+    fn unset_fields_match_anything_and_set_fields_exclude_other_kinds() {
+        let partial = PartialAction {
+            kind: Some("MaritimeTrade".to_string()),
+            give: Some(Resource::Wood),
+            ..Default::default()
+        };
+        assert_eq!(partial.completions(&offered()).len(), 1);
+
+        // A node narrows to node-bearing actions only
+        let partial = PartialAction {
+            node_id: Some(12),
+            ..Default::default()
+        };
+        assert_eq!(partial.completions(&offered()).len(), 3);
+
+        assert_eq!(PartialAction::default().completions(&offered()), offered());
+    }
+}
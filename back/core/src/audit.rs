@@ -0,0 +1,181 @@
+// Fair-play audit - flags human seats whose play looks engine-assisted: answers that
+// match the analysis engine's recommendation, given faster than a person could work them
+// out, with a consistency people rarely keep up over a whole game. Built from the think
+// time recorded for every action and the same analyst search as the hint endpoint. A
+// flag is a lead for an admin to review, not proof.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::players::best_actions;
+use crate::replay::Replay;
+
+/// Search limits of the audit and the thresholds a seat is flagged at
+#[derive(Debug, Clone)]
+pub struct AuditSettings {
+    pub depth: i32,
+    pub time_budget: Duration,
+    /// Answers at most this fast count as instant
+    pub instant_ms: u64,
+    /// Fewer timed decisions than this are too few to judge
+    pub min_decisions: u32,
+    /// Share of decisions matching the engine, and of instant matches, at which a seat
+    /// is flagged; both must be reached
+    pub match_rate: f64,
+    pub instant_match_rate: f64,
+}
+
+impl Default for AuditSettings {
+    fn default() -> Self {
+        Self {
+            depth: 4,
+            time_budget: Duration::from_millis(500),
+            instant_ms: 2_000,
+            min_decisions: 20,
+            match_rate: 0.85,
+            instant_match_rate: 0.6,
+        }
+    }
+}
+
+/// How one human seat's decisions compare to the engine's
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatAudit {
+    pub color: String,
+    /// Timed decisions where the engine rated some legal actions above others
+    pub decisions: u32,
+    /// Decisions that were the engine's recommendation, or tied with it
+    pub engine_matches: u32,
+    /// Engine matches answered within `instant_ms`
+    pub instant_engine_matches: u32,
+    pub median_think_ms: Option<u64>,
+    pub flagged: bool,
+}
+
+impl SeatAudit {
+    pub fn match_rate(&self) -> f64 {
+        self.engine_matches as f64 / self.decisions.max(1) as f64
+    }
+
+    pub fn instant_match_rate(&self) -> f64 {
+        self.instant_engine_matches as f64 / self.decisions.max(1) as f64
+    }
+}
+
+/// Audit of every human seat of one game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub game_id: String,
+    pub seats: Vec<SeatAudit>,
+}
+
+/// Replay `replay` and compare the decisions of `seats` (color index and name) against
+/// the engine. `think_ms` holds the think time of each recorded action; untimed actions,
+/// such as those replayed after a restore, are skipped.
+pub fn audit(
+    game_id: String,
+    replay: &Replay,
+    think_ms: &[Option<u64>],
+    seats: &[(u8, String)],
+    settings: &AuditSettings,
+) -> AuditReport {
+    let mut think_times = vec![Vec::new(); seats.len()];
+    let mut audits: Vec<SeatAudit> = seats
+        .iter()
+        .map(|(_, color)| SeatAudit {
+            color: color.clone(),
+            decisions: 0,
+            engine_matches: 0,
+            instant_engine_matches: 0,
+            median_think_ms: None,
+            flagged: false,
+        })
+        .collect();
+
+    let mut state = replay.initial_state();
+    for (i, &action) in replay.actions.iter().enumerate() {
+        let seat = seats.iter().position(|&(color, _)| color == action.color());
+        if let (Some(seat), Some(Some(think))) = (seat, think_ms.get(i)) {
+            let recommended = best_actions(&state, settings.depth, settings.time_budget);
+            if recommended.len() < state.generate_playable_actions().len() {
+                let audit = &mut audits[seat];
+                audit.decisions += 1;
+                think_times[seat].push(*think);
+                if recommended.contains(&action) {
+                    audit.engine_matches += 1;
+                    if *think <= settings.instant_ms {
+                        audit.instant_engine_matches += 1;
+                    }
+                }
+            }
+        }
+        state.apply_action(action);
+    }
+
+    for (audit, mut times) in audits.iter_mut().zip(think_times) {
+        times.sort_unstable();
+        audit.median_think_ms = times.get(times.len() / 2).copied();
+        audit.flagged = audit.decisions >= settings.min_decisions
+            && audit.match_rate() >= settings.match_rate
+            && audit.instant_match_rate() >= settings.instant_match_rate;
+    }
+    AuditReport {
+        game_id,
+        seats: audits,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::analyze;
+    use crate::state::State;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_instant_engine_moves_are_flagged_and_slow_random_ones_are_not() {
+        let settings = AuditSettings {
+            depth: 1,
+            time_budget: Duration::from_millis(50),
+            min_decisions: 10,
+            ..AuditSettings::default()
+        };
+        let mut state = State::new_base();
+        let mut replay = Replay::record_start(&state, 0);
+        let mut think_ms = Vec::new();
+        let mut rng = XorShiftRng::seed_from_u64(3);
+        let (engine_seat, random_seat) =
+            (state.get_seating_order()[0], state.get_seating_order()[1]);
+        for _ in 0..400 {
+            if state.winner().is_some() {
+                break;
+            }
+            let actions = state.generate_playable_actions();
+            let color = state.get_current_color();
+            let (action, think) = if color == engine_seat {
+                let best = analyze(&state, settings.depth, settings.time_budget)
+                    .map_or(actions[0], |analysis| analysis.best_action);
+                (best, 300)
+            } else {
+                (*actions.choose(&mut rng).unwrap(), 9_000)
+            };
+            state.apply_action(action);
+            replay.push(action, &state);
+            think_ms.push(Some(think));
+        }
+
+        let seats = [engine_seat, random_seat].map(|color| (color, format!("seat {color}")));
+        let report = audit("g".into(), &replay, &think_ms, &seats, &settings);
+        let [engine, random] = &report.seats[..] else {
+            panic!("one audit per seat");
+        };
+        assert!(engine.decisions >= settings.min_decisions);
+        assert!(engine.match_rate() > 0.9, "{engine:?}");
+        assert!(engine.flagged);
+        assert_eq!(engine.median_think_ms, Some(300));
+        assert_eq!(random.instant_engine_matches, 0);
+        assert!(!random.flagged, "{random:?}");
+    }
+}
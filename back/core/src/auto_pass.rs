@@ -0,0 +1,153 @@
+// Opt-in auto-pass: a seat's turn ends by itself once nothing worth doing is left, after a
+// short pause so the player sees the roll. Offers to other players never hold it up. A
+// Special Build Phase with nothing to build is passed the same way.
+// Without `skip_dominated_trades` that means EndTurn is the only other legal action. With it, maritime trades are ignored as long as no
+// single trade would let the seat build or buy anything, and its hand is small enough
+// not to risk a discard on the next 7.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::enums::{Action, ActionPrompt};
+use crate::state::State;
+
+/// Hand size from which maritime trades always count, since a 7 would cost half of it
+const DISCARD_HAND_SIZE: u8 = 8;
+
+/// One seat's auto-pass setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoPass {
+    /// Pause before the turn is ended
+    pub delay_ms: u64,
+    /// Also pass when the only other options are maritime trades that lead nowhere
+    pub skip_dominated_trades: bool,
+    /// Trades are only skipped while the hand holds fewer cards than this
+    pub trade_hand_limit: u8,
+}
+
+impl Default for AutoPass {
+    fn default() -> Self {
+        Self {
+            delay_ms: 800,
+            skip_dominated_trades: false,
+            trade_hand_limit: DISCARD_HAND_SIZE,
+        }
+    }
+}
+
+impl AutoPass {
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    /// Whether `color`'s turn should end on its own in `state`
+    pub fn should_pass(&self, state: &State, color: u8) -> bool {
+        if state.winner().is_some()
+            || state.is_initial_build_phase()
+            || state.get_current_color() != color
+        {
+            return false;
+        }
+        let actions = state.generate_playable_actions();
+        if state.get_action_prompt() == ActionPrompt::SpecialBuild {
+            return actions.len() == 1;
+        }
+        if !actions
+            .iter()
+            .any(|action| matches!(action, Action::EndTurn { .. }))
+        {
+            return false;
+        }
+        let mut others = actions
+            .iter()
+            .filter(|action| !matches!(action, Action::EndTurn { .. } | Action::OfferTrade { .. }))
+            .peekable();
+        if others.peek().is_none() {
+            return true;
+        }
+
+        let hand_size: u8 = state.get_player_hand(color).iter().sum();
+        self.skip_dominated_trades
+            && hand_size < self.trade_hand_limit
+            && others.all(|action| {
+                matches!(action, Action::MaritimeTrade { .. }) && !enables_build(state, *action)
+            })
+    }
+}
+
+/// Whether `trade` leaves the trader able to build or buy something
+fn enables_build(state: &State, trade: Action) -> bool {
+    let mut after = state.clone();
+    after.apply_action(trade);
+    after.generate_playable_actions().iter().any(|action| {
+        matches!(
+            action,
+            Action::BuildRoad { .. }
+                | Action::BuildShip { .. }
+                | Action::BuildSettlement { .. }
+                | Action::BuildCity { .. }
+                | Action::BuyDevelopmentCard { .. }
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::StateEdit;
+
+    fn rolled_state(hand: [u8; 5]) -> State {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::HasRolled(true),
+            StateEdit::Hand(hand),
+        ] {
+            state.apply_action(Action::DebugSetState { color, edit });
+        }
+        state
+    }
+
+    #[test]
+    fn test_passes_when_only_end_turn_is_left() {
+        let state = rolled_state([0, 0, 0, 0, 0]);
+        let color = state.get_current_color();
+        assert!(AutoPass::default().should_pass(&state, color));
+
+        let other = (color + 1) % state.get_num_players();
+        assert!(!AutoPass::default().should_pass(&state, other));
+    }
+
+    #[test]
+    fn test_dominated_trades_only_skipped_when_asked() {
+        // Four wood trade for anything, but one card is still far from any build
+        let state = rolled_state([4, 0, 0, 0, 0]);
+        let color = state.get_current_color();
+        assert!(!AutoPass::default().should_pass(&state, color));
+
+        let skipping = AutoPass {
+            skip_dominated_trades: true,
+            ..Default::default()
+        };
+        assert!(skipping.should_pass(&state, color));
+        let tight_limit = AutoPass {
+            trade_hand_limit: 4,
+            ..skipping
+        };
+        assert!(!tight_limit.should_pass(&state, color));
+    }
+
+    #[test]
+    fn test_trade_into_a_build_is_not_dominated() {
+        // Trading four ore for a wheat completes a development card
+        let state = rolled_state([0, 0, 1, 0, 5]);
+        let color = state.get_current_color();
+        let skipping = AutoPass {
+            skip_dominated_trades: true,
+            ..Default::default()
+        };
+        assert!(!skipping.should_pass(&state, color));
+    }
+}
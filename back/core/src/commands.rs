@@ -0,0 +1,275 @@
+// Text commands for chat-driven play: "roll", "build settlement at 23",
+// "trade 3 wood for 1 ore". A command parses into a PartialAction and resolves to the
+// single offered action it describes, so it can never produce an illegal move.
+
+use crate::actions::{PartialAction, PlayerAction};
+use crate::enums::Resource;
+
+/// Words that carry no meaning in a command ("build a road from 3 to 12")
+const FILLER: [&str; 9] = ["a", "an", "the", "at", "on", "to", "from", "with", "and"];
+
+/// Parse a text command into the action it describes. Details the command leaves out
+/// stay unset, e.g. "build road at 12" matches every offered road touching node 12.
+pub fn parse_command(text: &str) -> Result<PartialAction, String> {
+    let text = text.to_lowercase().replace([',', '(', ')'], " ");
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .filter(|word| !FILLER.contains(word))
+        .collect();
+    let kind = |kind: &str| Some(kind.to_string());
+
+    let partial = match words.as_slice() {
+        ["roll" | "r"] | ["roll", "dice"] => PartialAction {
+            kind: kind("Roll"),
+            ..Default::default()
+        },
+        ["end" | "pass" | "done"] | ["end", "turn"] => PartialAction {
+            kind: kind("EndTurn"),
+            ..Default::default()
+        },
+        ["build" | "place", "settlement", rest @ ..] => PartialAction {
+            kind: kind("BuildSettlement"),
+            node_id: node(rest)?,
+            ..Default::default()
+        },
+        ["build" | "place", "city", rest @ ..] | ["upgrade", rest @ ..] => PartialAction {
+            kind: kind("BuildCity"),
+            node_id: node(rest)?,
+            ..Default::default()
+        },
+        ["build" | "place", "road", rest @ ..] => {
+            let nodes = numbers(&rest.join(" ").replace('-', " "))?;
+            match nodes.as_slice() {
+                [] => PartialAction {
+                    kind: kind("BuildRoad"),
+                    ..Default::default()
+                },
+                [node_id] => PartialAction {
+                    kind: kind("BuildRoad"),
+                    node_id: Some(to_node(*node_id)?),
+                    ..Default::default()
+                },
+                [a, b] => PartialAction {
+                    kind: kind("BuildRoad"),
+                    edge_id: Some((to_node(*a)?, to_node(*b)?)),
+                    ..Default::default()
+                },
+                _ => return Err("a road joins two nodes".to_string()),
+            }
+        }
+        ["buy", "dev" | "development", "card"] | ["buy", "card"] => PartialAction {
+            kind: kind("BuyDevelopmentCard"),
+            ..Default::default()
+        },
+        ["play", "knight"] | ["knight"] => PartialAction {
+            kind: kind("PlayKnight"),
+            ..Default::default()
+        },
+        ["play", "road", "building"] => PartialAction {
+            kind: kind("PlayRoadBuilding"),
+            ..Default::default()
+        },
+        ["play", "monopoly", rest @ ..] | ["monopoly", rest @ ..] => PartialAction {
+            kind: kind("PlayMonopoly"),
+            resource: single_resource(rest)?,
+            ..Default::default()
+        },
+        ["play", "year", "of", "plenty", rest @ ..] | ["year", "of", "plenty", rest @ ..] => {
+            let picked = resources(rest)?;
+            PartialAction {
+                kind: kind("PlayYearOfPlenty"),
+                resources: match picked.as_slice() {
+                    [] => None,
+                    [a] => Some((*a, None)),
+                    [a, b] => Some((*a, Some(*b))),
+                    _ => return Err("year of plenty takes at most two resources".to_string()),
+                },
+                ..Default::default()
+            }
+        }
+        ["trade", rest @ ..] => {
+            let (give, take) = match rest.iter().position(|word| *word == "for") {
+                Some(split) => (&rest[..split], &rest[split + 1..]),
+                None => (rest, &[][..]),
+            };
+            let ratio = numbers(&give.join(" "))?;
+            PartialAction {
+                kind: kind("MaritimeTrade"),
+                give: single_resource(give)?,
+                take: single_resource(take)?,
+                ratio: match ratio.as_slice() {
+                    [] => None,
+                    [ratio] => Some(u8::try_from(*ratio).map_err(|_| "bad trade ratio")?),
+                    _ => return Err("give one amount of one resource".to_string()),
+                },
+                ..Default::default()
+            }
+        }
+        ["move", "robber", rest @ ..] | ["robber", rest @ ..] => {
+            let (tile, victim) = match rest.iter().position(|word| *word == "steal") {
+                Some(split) => (&rest[..split], Some(&rest[split + 1..])),
+                None => (rest, None),
+            };
+            let coordinate = match numbers(&tile.join(" "))?.as_slice() {
+                [] => None,
+                [x, y, z] => Some((to_axis(*x)?, to_axis(*y)?, to_axis(*z)?)),
+                _ => return Err("a tile is three cube coordinates, e.g. 0 -1 1".to_string()),
+            };
+            PartialAction {
+                kind: kind("MoveRobber"),
+                coordinate,
+                victim: victim.map(player).transpose()?,
+                ..Default::default()
+            }
+        }
+        ["discard", ..] => PartialAction {
+            kind: kind("Discard"),
+            ..Default::default()
+        },
+        ["take" | "choose", "gold", rest @ ..] | ["gold", rest @ ..] => PartialAction {
+            kind: kind("ChooseGold"),
+            resource: single_resource(rest)?,
+            ..Default::default()
+        },
+        _ => return Err(format!("unknown command '{}'", text.trim())),
+    };
+    Ok(partial)
+}
+
+/// Resolve a text command to the one offered action it describes
+pub fn resolve_command(text: &str, offered: &[PlayerAction]) -> Result<PlayerAction, String> {
+    let partial = parse_command(text)?;
+    let mut completions = partial.completions(offered);
+    match completions.len() {
+        0 => Err(format!("'{text}' is not a legal action right now")),
+        1 => Ok(completions.remove(0)),
+        n => Err(format!("'{text}' is ambiguous: {n} legal actions match")),
+    }
+}
+
+fn numbers(text: &str) -> Result<Vec<i64>, String> {
+    text.split_whitespace()
+        .filter(|word| word.starts_with(|c: char| c.is_ascii_digit() || c == '-'))
+        .map(|word| {
+            word.parse()
+                .map_err(|_| format!("'{word}' is not a number"))
+        })
+        .collect()
+}
+
+fn node(words: &[&str]) -> Result<Option<u8>, String> {
+    match numbers(&words.join(" "))?.as_slice() {
+        [] => Ok(None),
+        [node_id] => to_node(*node_id).map(Some),
+        _ => Err("expected one node".to_string()),
+    }
+}
+
+fn to_node(number: i64) -> Result<u8, String> {
+    u8::try_from(number).map_err(|_| format!("no node {number}"))
+}
+
+fn to_axis(number: i64) -> Result<i8, String> {
+    i8::try_from(number).map_err(|_| format!("no tile coordinate {number}"))
+}
+
+fn resources(words: &[&str]) -> Result<Vec<Resource>, String> {
+    words
+        .iter()
+        .filter(|word| !word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|word| resource(word))
+        .collect()
+}
+
+fn single_resource(words: &[&str]) -> Result<Option<Resource>, String> {
+    match resources(words)?.as_slice() {
+        [] => Ok(None),
+        [resource] => Ok(Some(*resource)),
+        _ => Err("expected one resource".to_string()),
+    }
+}
+
+fn resource(word: &str) -> Result<Resource, String> {
+    match word.trim_end_matches('s') {
+        "wood" | "lumber" | "log" => Ok(Resource::Wood),
+        "brick" | "clay" => Ok(Resource::Brick),
+        "sheep" | "wool" => Ok(Resource::Sheep),
+        "wheat" | "grain" => Ok(Resource::Wheat),
+        "ore" | "stone" | "rock" => Ok(Resource::Ore),
+        _ => Err(format!("unknown resource '{word}'")),
+    }
+}
+
+/// A victim by player id ("player_2") or seat number ("2")
+fn player(words: &[&str]) -> Result<String, String> {
+    match words {
+        [id] if id.starts_with("player_") => Ok(id.to_string()),
+        [seat] if seat.parse::<u8>().is_ok() => Ok(format!("player_{seat}")),
+        ["player", seat] if seat.parse::<u8>().is_ok() => Ok(format!("player_{seat}")),
+        _ => Err(format!("unknown player '{}'", words.join(" "))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_common_commands() {
+        assert_eq!(parse_command("Roll").unwrap().kind.as_deref(), Some("Roll"));
+        assert_eq!(
+            parse_command("build settlement at 23").unwrap().node_id,
+            Some(23)
+        );
+        assert_eq!(
+            parse_command("build a road from 3 to 12").unwrap().edge_id,
+            Some((3, 12))
+        );
+        assert_eq!(
+            parse_command("build road 3-12").unwrap().edge_id,
+            Some((3, 12))
+        );
+
+        let trade = parse_command("trade 3 wood for 1 ore").unwrap();
+        assert_eq!(
+            (trade.give, trade.take, trade.ratio),
+            (Some(Resource::Wood), Some(Resource::Ore), Some(3))
+        );
+
+        let robber = parse_command("move robber to 0 -1 1 steal from player_2").unwrap();
+        assert_eq!(robber.coordinate, Some((0, -1, 1)));
+        assert_eq!(robber.victim.as_deref(), Some("player_2"));
+
+        assert!(parse_command("build castle").is_err());
+        assert!(parse_command("trade 4 gold for ore").is_err());
+    }
+
+    #[test]
+    fn resolves_only_unique_legal_actions() {
+        let offered = vec![
+            PlayerAction::BuildSettlement { node_id: 23 },
+            PlayerAction::BuildRoad { edge_id: (3, 12) },
+            PlayerAction::BuildRoad { edge_id: (12, 13) },
+            PlayerAction::MaritimeTrade {
+                give: Resource::Wood,
+                take: Resource::Ore,
+                ratio: 3,
+            },
+            PlayerAction::EndTurn,
+        ];
+
+        assert_eq!(
+            resolve_command("build settlement at 23", &offered),
+            Ok(PlayerAction::BuildSettlement { node_id: 23 })
+        );
+        assert_eq!(
+            resolve_command("trade 3 wood for 1 ore", &offered),
+            Ok(offered[3].clone())
+        );
+        // Ratio must be the one the player is offered
+        assert!(resolve_command("trade 4 wood for ore", &offered).is_err());
+        // Two roads touch node 12
+        assert!(resolve_command("build road at 12", &offered).is_err());
+        assert!(resolve_command("roll", &offered).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+// Compact playable actions for clients on thin connections. Instead of a PlayerAction per
+// legal move, a snapshot carries positions in a per-game action table, either as a sorted
+// index list or as a bitmask. The table only depends on the map and the seat count, so a
+// client fetches it once per game.
+//
+// Table layout, in order (N = land nodes by id, E = land edges by (low, high) node id,
+// T = land tiles in board `tiles` order, P = seats, S = sea edges by (low, high) node id,
+// none off Seafarers boards, G = 5 on boards with gold fields, else 0):
+//
+// | Section          | Size      | Entry                                                |
+// |------------------|-----------|------------------------------------------------------|
+// | Basic            | 7         | Roll, EndTurn, PassBuildPhase, BuyDevelopmentCard,   |
+// |                  |           | PlayKnight, PlayRoadBuilding, Discard                |
+// | BuildSettlement  | N         | per node                                             |
+// | BuildCity        | N         | per node                                             |
+// | BuildRoad        | E         | per edge                                             |
+// | PlayMonopoly     | 5         | per resource                                         |
+// | PlayYearOfPlenty | 20        | per resource alone, then per pair (a <= b)           |
+// | MaritimeTrade    | 60        | per ratio (4, 3, 2), give, then take (take != give)  |
+// | MoveRobber       | T * (P+1) | per tile: no victim, then each seat as victim        |
+// | GiftResource     | P * 5     | per teammate seat, then resource                     |
+// | DomesticTrade    | 23 + P    | AcceptTrade, RejectTrade, CancelTrade, one-for-one   |
+// |                  |           | OfferTrade per give then take (take != give),        |
+// |                  |           | ConfirmTrade per seat                                |
+// | BuildShip        | S         | per sea edge                                         |
+// | ChooseGold       | G         | per resource                                         |
+//
+// Resources go Wood, Brick, Sheep, Wheat, Ore. Which sections can hold legal actions
+// depends on the prompt: BUILD_INITIAL_SETTLEMENT only BuildSettlement, DISCARD only
+// Discard, MOVE_ROBBER only MoveRobber, and so on. Clients answer with the table entry
+// itself as their player_action. Larger trade offers are legal but never listed, so
+// clients send them in full. Ship moves are listed in full too.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::actions::PlayerAction;
+use crate::enums::Resource;
+use crate::map_instance::MapInstance;
+
+const RESOURCES: [Resource; 5] = [
+    Resource::Wood,
+    Resource::Brick,
+    Resource::Sheep,
+    Resource::Wheat,
+    Resource::Ore,
+];
+/// Maritime trade ratios with a table section, in table order
+const TRADE_RATIOS: [u8; 3] = [4, 3, 2];
+
+/// How a connection wants playable actions sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionsFormat {
+    /// Every action in full, in `current_playable_actions`
+    #[default]
+    Full,
+    /// Sorted table indices
+    Indices,
+    /// One bit per table entry
+    Mask,
+}
+
+/// Playable actions as table positions. Legal actions without a table entry (none in
+/// the standard rules, ship moves in Seafarers) are listed in full in `unmapped`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompactActions {
+    /// Number of table entries, so clients can tell a stale table
+    pub table_len: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indices: Option<Vec<u16>>,
+    /// Hex, two digits per byte; entry `i` is bit `i % 8` (least significant first) of
+    /// byte `i / 8`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmapped: Vec<PlayerAction>,
+}
+
+/// Every action a game can offer, at fixed positions; see the module comment
+#[derive(Debug)]
+pub struct ActionTable {
+    entries: Vec<PlayerAction>,
+    positions: HashMap<PlayerAction, u16>,
+}
+
+impl ActionTable {
+    pub fn new(map: &MapInstance, num_players: u8) -> Self {
+        let mut node_ids: Vec<_> = map.land_nodes.iter().copied().collect();
+        node_ids.sort_unstable();
+        let mut edge_ids: Vec<_> = map
+            .land_edges
+            .iter()
+            .map(|&(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        edge_ids.sort_unstable();
+        edge_ids.dedup();
+        let mut sea_edge_ids: Vec<_> = map.sea_edges.iter().copied().collect();
+        sea_edge_ids.sort_unstable();
+        let mut tiles: Vec<_> = map.land_tiles.keys().copied().collect();
+        tiles.sort_unstable();
+        let seats: Vec<String> = (0..num_players).map(|c| format!("player_{c}")).collect();
+
+        let mut entries = vec![
+            PlayerAction::Roll,
+            PlayerAction::EndTurn,
+            PlayerAction::PassBuildPhase,
+            PlayerAction::BuyDevelopmentCard,
+            PlayerAction::PlayKnight,
+            PlayerAction::PlayRoadBuilding,
+            PlayerAction::Discard { resources: vec![] },
+        ];
+        entries.extend(
+            node_ids
+                .iter()
+                .map(|&node_id| PlayerAction::BuildSettlement { node_id }),
+        );
+        entries.extend(
+            node_ids
+                .iter()
+                .map(|&node_id| PlayerAction::BuildCity { node_id }),
+        );
+        entries.extend(
+            edge_ids
+                .iter()
+                .map(|&edge_id| PlayerAction::BuildRoad { edge_id }),
+        );
+        entries.extend(
+            RESOURCES
+                .iter()
+                .map(|&resource| PlayerAction::PlayMonopoly { resource }),
+        );
+        entries.extend(RESOURCES.iter().map(|&a| PlayerAction::PlayYearOfPlenty {
+            resources: (a, None),
+        }));
+        for (i, &a) in RESOURCES.iter().enumerate() {
+            entries.extend(
+                RESOURCES[i..]
+                    .iter()
+                    .map(|&b| PlayerAction::PlayYearOfPlenty {
+                        resources: (a, Some(b)),
+                    }),
+            );
+        }
+        for ratio in TRADE_RATIOS {
+            for give in RESOURCES {
+                entries.extend(
+                    RESOURCES
+                        .iter()
+                        .filter(|&&take| take != give)
+                        .map(|&take| PlayerAction::MaritimeTrade { give, take, ratio }),
+                );
+            }
+        }
+        for &coordinate in &tiles {
+            entries.push(PlayerAction::MoveRobber {
+                coordinate,
+                victim: None,
+            });
+            entries.extend(seats.iter().map(|seat| PlayerAction::MoveRobber {
+                coordinate,
+                victim: Some(seat.clone()),
+            }));
+        }
+        for seat in &seats {
+            entries.extend(
+                RESOURCES
+                    .iter()
+                    .map(|&resource| PlayerAction::GiftResource {
+                        teammate: seat.clone(),
+                        resource,
+                    }),
+            );
+        }
+        entries.extend([
+            PlayerAction::AcceptTrade,
+            PlayerAction::RejectTrade,
+            PlayerAction::CancelTrade,
+        ]);
+        for give in RESOURCES {
+            entries.extend(RESOURCES.iter().filter(|&&take| take != give).map(|&take| {
+                PlayerAction::OfferTrade {
+                    give: vec![give],
+                    take: vec![take],
+                }
+            }));
+        }
+        entries.extend(
+            seats
+                .iter()
+                .map(|seat| PlayerAction::ConfirmTrade { with: seat.clone() }),
+        );
+        entries.extend(
+            sea_edge_ids
+                .iter()
+                .map(|&edge_id| PlayerAction::BuildShip { edge_id }),
+        );
+        if map.land_tiles.values().any(|tile| tile.gold) {
+            entries.extend(
+                RESOURCES
+                    .iter()
+                    .map(|&resource| PlayerAction::ChooseGold { resource }),
+            );
+        }
+
+        let positions = entries
+            .iter()
+            .enumerate()
+            .map(|(i, action)| (action.clone(), i as u16))
+            .collect();
+        Self { entries, positions }
+    }
+
+    /// The table, indexed by position
+    pub fn entries(&self) -> &[PlayerAction] {
+        &self.entries
+    }
+
+    /// Position of `action`, taking roads, ships and Year of Plenty pairs in either order
+    pub fn position(&self, action: &PlayerAction) -> Option<u16> {
+        let canonical = match action {
+            PlayerAction::BuildRoad { edge_id: (a, b) } => PlayerAction::BuildRoad {
+                edge_id: (*a.min(b), *a.max(b)),
+            },
+            PlayerAction::BuildShip { edge_id: (a, b) } => PlayerAction::BuildShip {
+                edge_id: (*a.min(b), *a.max(b)),
+            },
+            PlayerAction::PlayYearOfPlenty {
+                resources: (a, Some(b)),
+            } if resource_rank(*b) < resource_rank(*a) => PlayerAction::PlayYearOfPlenty {
+                resources: (*b, Some(*a)),
+            },
+            PlayerAction::Discard { .. } => PlayerAction::Discard { resources: vec![] },
+            _ => return self.positions.get(action).copied(),
+        };
+        self.positions.get(&canonical).copied()
+    }
+
+    /// `actions` in the given format; `None` for `ActionsFormat::Full`
+    pub fn encode(
+        &self,
+        actions: &[PlayerAction],
+        format: ActionsFormat,
+    ) -> Option<CompactActions> {
+        if format == ActionsFormat::Full {
+            return None;
+        }
+        let mut indices = Vec::with_capacity(actions.len());
+        let mut unmapped = Vec::new();
+        for action in actions {
+            match self.position(action) {
+                Some(index) => indices.push(index),
+                None => unmapped.push(action.clone()),
+            }
+        }
+        indices.sort_unstable();
+        indices.dedup();
+
+        let mut compact = CompactActions {
+            table_len: self.entries.len(),
+            unmapped,
+            ..Default::default()
+        };
+        if format == ActionsFormat::Mask {
+            let mut bytes = vec![0u8; self.entries.len().div_ceil(8)];
+            for &index in &indices {
+                bytes[index as usize / 8] |= 1 << (index % 8);
+            }
+            compact.mask = Some(bytes.iter().map(|byte| format!("{byte:02x}")).collect());
+        } else {
+            compact.indices = Some(indices);
+        }
+        Some(compact)
+    }
+}
+
+fn resource_rank(resource: Resource) -> usize {
+    RESOURCES.iter().position(|&r| r == resource).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_every_legal_action_has_a_table_entry() {
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("compact".into(), names);
+        let table = game.action_table().unwrap();
+        // 7 basic, 54 nodes twice, 72 edges, 5 + 20 + 60 cards and trades,
+        // 19 tiles * 5 robber placements, 4 seats * 5 gifts, 3 + 20 + 4 domestic trades
+        assert_eq!(table.entries().len(), 7 + 108 + 72 + 85 + 95 + 20 + 27);
+
+        let mut rng = XorShiftRng::seed_from_u64(7);
+        for _ in 0..400 {
+            let compact = game.compact_actions(ActionsFormat::Indices).unwrap();
+            assert!(compact.unmapped.is_empty(), "{:?}", compact.unmapped);
+            let indices = compact.indices.unwrap();
+            assert_eq!(indices.len(), game.current_playable_actions.len());
+            for action in &game.current_playable_actions {
+                let index = table.position(action).unwrap();
+                assert_eq!(
+                    table.position(&table.entries()[index as usize]),
+                    Some(index)
+                );
+            }
+
+            let state = game.state.as_ref().unwrap();
+            if state.winner().is_some() {
+                break;
+            }
+            let action = *state.generate_playable_actions().choose(&mut rng).unwrap();
+            let player_id = game.players[state.get_current_color() as usize].id.clone();
+            game.process_action(&player_id, action).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_mask_sets_the_bits_of_the_listed_indices() {
+        let names: Vec<String> = (0..2).map(|i| format!("Bot {}", i + 1)).collect();
+        let game = Game::new("mask".into(), names);
+        let table = game.action_table().unwrap();
+        let actions = [
+            PlayerAction::EndTurn,
+            PlayerAction::BuildRoad { edge_id: (1, 0) },
+            PlayerAction::PlayYearOfPlenty {
+                resources: (Resource::Ore, Some(Resource::Wood)),
+            },
+        ];
+
+        let indices = table
+            .encode(&actions, ActionsFormat::Indices)
+            .unwrap()
+            .indices
+            .unwrap();
+        let mask = table
+            .encode(&actions, ActionsFormat::Mask)
+            .unwrap()
+            .mask
+            .unwrap();
+        assert_eq!(mask.len(), table.entries().len().div_ceil(8) * 2);
+        let bytes: Vec<u8> = (0..mask.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&mask[i..i + 2], 16).unwrap())
+            .collect();
+        let set: Vec<u16> = (0..table.entries().len() as u16)
+            .filter(|&i| bytes[i as usize / 8] & (1 << (i % 8)) != 0)
+            .collect();
+        assert_eq!(set, indices);
+        assert_eq!(indices[0], 1);
+        assert_eq!(
+            table.entries()[indices[2] as usize],
+            PlayerAction::PlayYearOfPlenty {
+                resources: (Resource::Wood, Some(Resource::Ore)),
+            }
+        );
+        assert!(table.encode(&actions, ActionsFormat::Full).is_none());
+    }
+}
@@ -0,0 +1,199 @@
+// Conformance cases - action sequences with expected outcomes ported from the Python
+// catanatron test suite, so games migrated between the two engines play out the same way.
+//
+// The corpus is a JSON list of cases. Each starts from a fresh game (`num_players` seats
+// on the base map of `map_seed`) or from a `position` in `State::to_fen` notation, then
+// runs its steps in order:
+//
+//   {"apply": <Action>}      applied after checking it is legal; a `Roll` may fix its dice,
+//                            and roads and Year of Plenty pairs may come in either order
+//   {"expect": {...}}        any of `hands`, `bank`, `victory_points`, `current_color`,
+//                            `prompt` and `awards` (`longest_road`, `largest_army`: the
+//                            holder's color, or null for nobody)
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::enums::{Action, ActionPrompt, FreqDeck, GameConfiguration, MapType};
+use crate::errors::CatanResult;
+use crate::map_instance::MapInstance;
+use crate::replay::persistence_error;
+use crate::state::State;
+
+/// Corpus shipped with the repository, relative to the back directory
+pub const DEFAULT_CORPUS_PATH: &str = "conformance/catanatron.json";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConformanceCase {
+    pub name: String,
+    /// The catanatron test, or test module, the case was ported from
+    pub source: String,
+    #[serde(default)]
+    pub position: Option<String>,
+    #[serde(default = "default_num_players")]
+    pub num_players: u8,
+    #[serde(default)]
+    pub map_seed: u64,
+    pub steps: Vec<Step>,
+}
+
+fn default_num_players() -> u8 {
+    4
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    Apply(Action),
+    Expect(Expectation),
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Expectation {
+    /// Resource cards per color
+    pub hands: Option<Vec<FreqDeck>>,
+    pub bank: Option<FreqDeck>,
+    /// Actual victory points per color, hidden cards included
+    pub victory_points: Option<Vec<u8>>,
+    pub current_color: Option<u8>,
+    pub prompt: Option<ActionPrompt>,
+    /// Holder per award; only the awards named are checked
+    pub awards: BTreeMap<String, Option<u8>>,
+}
+
+impl ConformanceCase {
+    pub fn initial_state(&self) -> CatanResult<State> {
+        match &self.position {
+            Some(fen) => State::from_fen(fen),
+            None => {
+                let config = GameConfiguration::builder()
+                    .map_type(MapType::Base)
+                    .num_players(self.num_players)
+                    .build()?;
+                let map_instance = MapInstance::shared(&config.map_type, self.map_seed);
+                Ok(State::new_with_seed(Arc::new(config), map_instance, 0))
+            }
+        }
+    }
+
+    /// Play the case through, describing the first step that goes differently
+    pub fn run(&self) -> Result<(), String> {
+        let mut state = self.initial_state().map_err(|e| e.to_string())?;
+        for (index, step) in self.steps.iter().enumerate() {
+            let failed = |details: String| format!("step {}: {details}", index + 1);
+            match step {
+                Step::Apply(action) => {
+                    // Catanatron may list a road's nodes the other way around
+                    let action = state.canonical_form(*action);
+                    // Fixed dice are how the cases stand in for catanatron's rigged rolls
+                    let checked = match action {
+                        Action::Roll { color, .. } => Action::Roll {
+                            color,
+                            dice_opt: None,
+                        },
+                        action => action,
+                    };
+                    state
+                        .validate_action(&checked)
+                        .map_err(|e| failed(format!("{action:?} is illegal: {e}")))?;
+                    state.apply_action(action);
+                }
+                Step::Expect(expectation) => expectation.check(&state).map_err(failed)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Expectation {
+    fn check(&self, state: &State) -> Result<(), String> {
+        let colors = 0..state.get_num_players();
+        let mismatch =
+            |what: &str, expected: &dyn std::fmt::Debug, actual: &dyn std::fmt::Debug| {
+                Err(format!("{what}: expected {expected:?}, got {actual:?}"))
+            };
+        if let Some(hands) = &self.hands {
+            let actual: Vec<&[u8]> = colors.clone().map(|c| state.get_player_hand(c)).collect();
+            if !hands
+                .iter()
+                .map(|hand| &hand[..])
+                .eq(actual.iter().copied())
+            {
+                return mismatch("hands", hands, &actual);
+            }
+        }
+        if let Some(bank) = &self.bank {
+            if state.get_bank_resources() != bank {
+                return mismatch("bank", bank, &state.get_bank_resources());
+            }
+        }
+        if let Some(points) = &self.victory_points {
+            let actual: Vec<u8> = colors.map(|c| state.get_actual_victory_points(c)).collect();
+            if *points != actual {
+                return mismatch("victory points", points, &actual);
+            }
+        }
+        if let Some(color) = self.current_color {
+            if state.get_current_color() != color {
+                return mismatch("current color", &color, &state.get_current_color());
+            }
+        }
+        if let Some(prompt) = &self.prompt {
+            if state.get_action_prompt() != *prompt {
+                return mismatch("prompt", prompt, &state.get_action_prompt());
+            }
+        }
+        for (award, holder) in &self.awards {
+            let actual = match award.as_str() {
+                "longest_road" => state.get_longest_road_color(),
+                "largest_army" => state.get_largest_army_color(),
+                _ => return Err(format!("unknown award '{award}'")),
+            };
+            if actual != *holder {
+                return mismatch(award, holder, &actual);
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn load_corpus(path: &Path) -> CatanResult<Vec<ConformanceCase>> {
+    let text = std::fs::read_to_string(path).map_err(|e| persistence_error(path, e))?;
+    serde_json::from_str(&text).map_err(|e| persistence_error(path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_matches_catanatron_corpus() {
+        // The corpus ships with the server crate, one level up
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(DEFAULT_CORPUS_PATH);
+        let corpus = load_corpus(&path).unwrap();
+        assert!(corpus.len() >= 10);
+        let failures: Vec<String> = corpus
+            .iter()
+            .filter_map(|case| case.run().err().map(|e| format!("{}: {e}", case.name)))
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_a_wrong_expectation_names_its_step() {
+        let case: ConformanceCase = serde_json::from_str(
+            r#"{"name": "wrong", "source": "-", "steps": [
+                {"apply": {"BuildSettlement": {"color": 0, "node_id": 0}}},
+                {"expect": {"prompt": "PlayTurn"}}
+            ]}"#,
+        )
+        .unwrap();
+        let error = case.run().unwrap_err();
+        assert!(error.starts_with("step 2: prompt"), "{error}");
+    }
+}
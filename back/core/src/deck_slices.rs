@@ -2,6 +2,7 @@ pub type FreqDeck = [u8; 5];
 
 pub const SETTLEMENT_COST: FreqDeck = [1, 1, 1, 1, 0];
 pub const ROAD_COST: FreqDeck = [1, 1, 0, 0, 0];
+pub const SHIP_COST: FreqDeck = [1, 0, 1, 0, 0];
 pub const CITY_COST: FreqDeck = [0, 0, 0, 2, 3];
 pub const DEVCARD_COST: FreqDeck = [0, 0, 1, 1, 1];
 
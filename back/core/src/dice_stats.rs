@@ -0,0 +1,135 @@
+// Dice fairness - how often each total came up against the 1-2-3-4-5-6-5-4-3-2-1 odds
+// of two fair dice. The chi-square statistic has 10 degrees of freedom, and for an even
+// number of degrees its tail probability has a closed form, so no stats crate is needed.
+
+use serde::{Deserialize, Serialize};
+
+/// Ways to roll each total from 2 to 12 out of 36
+const WAYS: [u32; 11] = [1, 2, 3, 4, 5, 6, 5, 4, 3, 2, 1];
+const DEGREES_OF_FREEDOM: u32 = 10;
+
+/// Rolls per total, `counts[0]` being 2 and `counts[10]` being 12
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiceHistogram {
+    pub counts: [u32; 11],
+}
+
+impl DiceHistogram {
+    pub fn record(&mut self, (die1, die2): (u8, u8)) {
+        let total = (die1 + die2) as usize;
+        if (2..=12).contains(&total) {
+            self.counts[total - 2] += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: &DiceHistogram) {
+        for (count, other) in self.counts.iter_mut().zip(other.counts) {
+            *count += other;
+        }
+    }
+
+    pub fn rolls(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// Pearson's chi-square against fair dice (0 when nothing was rolled)
+    pub fn chi_square(&self) -> f64 {
+        let rolls = self.rolls() as f64;
+        if rolls == 0.0 {
+            return 0.0;
+        }
+        self.counts
+            .iter()
+            .zip(WAYS)
+            .map(|(&count, ways)| {
+                let expected = rolls * ways as f64 / 36.0;
+                (count as f64 - expected).powi(2) / expected
+            })
+            .sum()
+    }
+
+    pub fn fairness(&self) -> DiceFairness {
+        let chi_square = self.chi_square();
+        DiceFairness {
+            rolls: self.rolls(),
+            counts: self.counts,
+            chi_square,
+            p_value: chi_square_tail(chi_square),
+        }
+    }
+}
+
+/// Histogram plus the fit to fair dice. A small `p_value` (say below 0.01 over a few
+/// hundred rolls) means the rolls are unlikely to come from fair dice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiceFairness {
+    pub rolls: u32,
+    pub counts: [u32; 11],
+    pub chi_square: f64,
+    pub p_value: f64,
+}
+
+impl std::fmt::Display for DiceFairness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} rolls, chi-square {:.2} (df {}), p = {:.3}",
+            self.rolls, self.chi_square, DEGREES_OF_FREEDOM, self.p_value
+        )
+    }
+}
+
+/// P(X >= x) for chi-square with `DEGREES_OF_FREEDOM` (even) degrees of freedom:
+/// e^(-x/2) * sum over i < df/2 of (x/2)^i / i!
+fn chi_square_tail(x: f64) -> f64 {
+    let half = x / 2.0;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for i in 1..DEGREES_OF_FREEDOM / 2 {
+        term *= half / i as f64;
+        sum += term;
+    }
+    (-half).exp() * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_odds_are_perfectly_fair() {
+        let mut histogram = DiceHistogram::default();
+        for die1 in 1..=6 {
+            for die2 in 1..=6 {
+                histogram.record((die1, die2));
+            }
+        }
+        let fairness = histogram.fairness();
+        assert_eq!(fairness.rolls, 36);
+        assert_eq!(fairness.counts[5], 6);
+        assert_eq!(fairness.chi_square, 0.0);
+        assert!((fairness.p_value - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_loaded_dice_are_flagged() {
+        let mut histogram = DiceHistogram::default();
+        for _ in 0..200 {
+            histogram.record((6, 6));
+        }
+        let mut other = DiceHistogram::default();
+        other.record((3, 4));
+        histogram.merge(&other);
+
+        let fairness = histogram.fairness();
+        assert_eq!(fairness.rolls, 201);
+        assert!(fairness.p_value < 1e-6);
+    }
+
+    #[test]
+    fn test_tail_matches_table() {
+        // Critical values for 10 degrees of freedom
+        assert!((chi_square_tail(18.307) - 0.05).abs() < 1e-4);
+        assert!((chi_square_tail(23.209) - 0.01).abs() < 1e-4);
+    }
+}
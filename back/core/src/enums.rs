@@ -0,0 +1,872 @@
+use crate::{
+    map_instance::{EdgeId, NodeId},
+    map_template::Coordinate,
+};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use crate::errors::{CatanError, CatanResult, GameError};
+
+// Define FreqDeck type directly in this module
+pub type FreqDeck = [u8; 5]; // 5 resources
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Color {
+    Red = 0,
+    Blue = 1,
+    Orange = 2,
+    White = 3,
+    Green = 4,
+    Brown = 5,
+}
+
+pub const COLORS: [Color; 6] = [
+    Color::Red,
+    Color::Blue,
+    Color::Orange,
+    Color::White,
+    Color::Green,
+    Color::Brown,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Resource {
+    Wood,
+    Brick,
+    Sheep,
+    Wheat,
+    Ore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DevCard {
+    Knight,
+    YearOfPlenty,
+    Monopoly,
+    RoadBuilding,
+    VictoryPoint,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum BuildingType {
+    Settlement,
+    City,
+    Road,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum NodeRef {
+    North,
+    Northeast,
+    Southeast,
+    South,
+    Southwest,
+    Northwest,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum EdgeRef {
+    East,
+    Southeast,
+    Southwest,
+    West,
+    Northwest,
+    Northeast,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionPrompt {
+    BuildInitialSettlement,
+    BuildInitialRoad,
+    PlayTurn,
+    Discard,
+    MoveRobber,
+    DecideTrade,
+    DecideAcceptees,
+    /// 5–6 players: a seat building between two turns, see `State::special_build_seat`
+    SpecialBuild,
+    /// Seafarers: a seat picking the cards its buildings on a rolled gold field earned
+    ChooseGold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Roll {
+        color: u8,
+        dice_opt: Option<(u8, u8)>,
+    },
+    MoveRobber {
+        color: u8,
+        coordinate: Coordinate,
+        victim_opt: Option<u8>,
+    },
+    // Cards given up on a 7, as a count per resource
+    Discard {
+        color: u8,
+        resources: FreqDeck,
+    },
+    BuildRoad {
+        color: u8,
+        edge_id: EdgeId,
+    },
+    // Seafarers: a ship on a sea edge, see `MapInstance::sea_edges`
+    BuildShip {
+        color: u8,
+        edge_id: EdgeId,
+    },
+    // Seafarers: once per turn, relocate the ship at the open end of a shipping route
+    MoveShip {
+        color: u8,
+        from: EdgeId,
+        to: EdgeId,
+    },
+    BuildSettlement {
+        color: u8,
+        node_id: NodeId,
+    },
+    BuildCity {
+        color: u8,
+        node_id: NodeId,
+    },
+    BuyDevelopmentCard {
+        color: u8,
+    },
+    PlayKnight {
+        color: u8,
+    },
+    PlayYearOfPlenty {
+        color: u8,
+        resources: (u8, Option<u8>),
+    },
+    PlayMonopoly {
+        color: u8,
+        resource: u8,
+    },
+    PlayRoadBuilding {
+        color: u8,
+    },
+    MaritimeTrade {
+        color: u8,
+        give: u8,
+        take: u8,
+        ratio: u8,
+    },
+    OfferTrade {
+        color: u8,
+        trade: (FreqDeck, FreqDeck),
+    },
+    AcceptTrade {
+        color: u8,
+        trade: (FreqDeck, FreqDeck),
+    },
+    RejectTrade {
+        color: u8,
+    },
+    // Other terms for the offer on the table, written from the offerer's side like the
+    // offer itself
+    CounterTrade {
+        color: u8,
+        trade: (FreqDeck, FreqDeck),
+    },
+    ConfirmTrade {
+        color: u8,
+        trade: (FreqDeck, FreqDeck, u8),
+    },
+    CancelTrade {
+        color: u8,
+    },
+    // Seafarers: one card of any resource for a building on a rolled gold field
+    ChooseGold {
+        color: u8,
+        resource: u8,
+    },
+    // Team variant: hand one resource card to a teammate (once per turn)
+    GiftResource {
+        color: u8,
+        teammate: u8,
+        resource: u8,
+    },
+    EndTurn {
+        color: u8,
+    },
+    // Speed rounds: hand the rest of the build phase to the next seat, without a new roll
+    PassBuildPhase {
+        color: u8,
+    },
+    // Test-only: no rule handles it, so applying it panics. Exercises panic isolation.
+    #[cfg(test)]
+    DebugPanic {
+        color: u8,
+    },
+    // Test setup: edits the position through `apply_action`, then checks it still
+    // holds together (see state/move_application/debug.rs)
+    #[cfg(any(test, feature = "debug-actions"))]
+    DebugSetState {
+        color: u8,
+        edit: StateEdit,
+    },
+}
+
+/// One change made by `Action::DebugSetState`. Flags apply to the whole game; hands
+/// belong to the action's color.
+#[cfg(any(test, feature = "debug-actions"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StateEdit {
+    /// Make the hand exactly this, trading the difference with the bank
+    Hand(FreqDeck),
+    Bank(FreqDeck),
+    Robber(Coordinate),
+    HasRolled(bool),
+    InitialBuildPhase(bool),
+    MovingRobber(bool),
+    PlayedDevCard(bool),
+    FreeRoads(u8),
+}
+
+impl Action {
+    /// Variant name without payload, e.g. "BuildRoad". Useful for grouping actions in stats.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Action::Roll { .. } => "Roll",
+            Action::MoveRobber { .. } => "MoveRobber",
+            Action::Discard { .. } => "Discard",
+            Action::ChooseGold { .. } => "ChooseGold",
+            Action::BuildRoad { .. } => "BuildRoad",
+            Action::BuildShip { .. } => "BuildShip",
+            Action::MoveShip { .. } => "MoveShip",
+            Action::BuildSettlement { .. } => "BuildSettlement",
+            Action::BuildCity { .. } => "BuildCity",
+            Action::BuyDevelopmentCard { .. } => "BuyDevelopmentCard",
+            Action::PlayKnight { .. } => "PlayKnight",
+            Action::PlayYearOfPlenty { .. } => "PlayYearOfPlenty",
+            Action::PlayMonopoly { .. } => "PlayMonopoly",
+            Action::PlayRoadBuilding { .. } => "PlayRoadBuilding",
+            Action::MaritimeTrade { .. } => "MaritimeTrade",
+            Action::OfferTrade { .. } => "OfferTrade",
+            Action::AcceptTrade { .. } => "AcceptTrade",
+            Action::RejectTrade { .. } => "RejectTrade",
+            Action::CounterTrade { .. } => "CounterTrade",
+            Action::ConfirmTrade { .. } => "ConfirmTrade",
+            Action::CancelTrade { .. } => "CancelTrade",
+            Action::GiftResource { .. } => "GiftResource",
+            Action::EndTurn { .. } => "EndTurn",
+            Action::PassBuildPhase { .. } => "PassBuildPhase",
+            #[cfg(test)]
+            Action::DebugPanic { .. } => "DebugPanic",
+            #[cfg(any(test, feature = "debug-actions"))]
+            Action::DebugSetState { .. } => "DebugSetState",
+        }
+    }
+
+    /// Seat performing the action
+    pub fn color(&self) -> u8 {
+        match *self {
+            Action::Roll { color, .. }
+            | Action::MoveRobber { color, .. }
+            | Action::Discard { color, .. }
+            | Action::ChooseGold { color, .. }
+            | Action::BuildRoad { color, .. }
+            | Action::BuildShip { color, .. }
+            | Action::MoveShip { color, .. }
+            | Action::BuildSettlement { color, .. }
+            | Action::BuildCity { color, .. }
+            | Action::BuyDevelopmentCard { color }
+            | Action::PlayKnight { color }
+            | Action::PlayYearOfPlenty { color, .. }
+            | Action::PlayMonopoly { color, .. }
+            | Action::PlayRoadBuilding { color }
+            | Action::MaritimeTrade { color, .. }
+            | Action::OfferTrade { color, .. }
+            | Action::AcceptTrade { color, .. }
+            | Action::RejectTrade { color }
+            | Action::CounterTrade { color, .. }
+            | Action::ConfirmTrade { color, .. }
+            | Action::CancelTrade { color }
+            | Action::GiftResource { color, .. }
+            | Action::EndTurn { color }
+            | Action::PassBuildPhase { color } => color,
+            #[cfg(test)]
+            Action::DebugPanic { color } => color,
+            #[cfg(any(test, feature = "debug-actions"))]
+            Action::DebugSetState { color, .. } => color,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MapType {
+    Mini,
+    Base,
+    Tournament,
+    /// The 5–6 player board: 30 tiles with two deserts, and 11 harbors
+    Extended,
+    /// Seafarers "Heading for New Shores" (3–4 players): the base island and two small
+    /// islands across a strait, reached by ships, with two gold fields
+    Seafarers,
+}
+
+pub const MIN_PLAYERS: u8 = 2;
+pub const MAX_PLAYERS: u8 = 6;
+/// Player count from which the 5–6 player extension rules apply
+pub const EXTENSION_MIN_PLAYERS: u8 = 5;
+pub const DEFAULT_DISCARD_LIMIT: u8 = 7;
+pub const EXTENSION_DISCARD_LIMIT: u8 = 9;
+pub const VPS_TO_WIN_RANGE: RangeInclusive<u8> = 3..=20;
+pub const DISCARD_LIMIT_RANGE: RangeInclusive<u8> = 5..=20;
+pub const DEFAULT_MAX_TICKS: u32 = 100;
+
+/// Rules of one game. Deserializing goes through `GameConfigBuilder`, so configs from
+/// request bodies and files get the same defaults and validation as ones built in code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "GameConfigBuilder")]
+pub struct GameConfiguration {
+    pub discard_limit: u8,
+    pub vps_to_win: u8,
+    pub map_type: MapType,
+    pub num_players: u8,
+    pub max_ticks: u32,
+    pub teams: Option<TeamConfig>,
+    pub robber: RobberRules,
+    pub variants: VariantConfig,
+    pub rules: RulesVersion,
+    pub seating: Seating,
+    /// Head starts per color, for balancing mixed-skill tables. Empty for an even game.
+    pub handicaps: Vec<SeatHandicap>,
+}
+
+/// How the turn order is decided when a game starts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Seating {
+    /// Colors play in color order, red first
+    #[default]
+    Fixed,
+    /// A seeded shuffle of the colors
+    Shuffled,
+    /// The given colors in turn order, e.g. [2, 0, 1, 3] starts with white
+    Explicit(Vec<u8>),
+    /// Everyone rolls two dice, ties among the highest roll again; the winner starts and
+    /// play continues in color order from there
+    RollForFirst,
+}
+
+/// Which behavior to apply where a rules fix changed how games play out. New games use
+/// `Strict`; the older variants keep replays recorded before the fixes verifying.
+/// Variants are in revision order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RulesVersion {
+    /// Rules revision 1: longest road only counts trails that start away from
+    /// opponents' buildings, development cards can be played the turn they are bought,
+    /// and a 7 takes the larger half of an odd hand
+    Legacy,
+    /// Rules revision 2: the longest road fix, but development cards can still be played
+    /// the turn they are bought and a 7 takes the larger half of an odd hand
+    Revision2,
+    /// The current `RULES_REVISION`
+    #[default]
+    Strict,
+}
+
+impl RulesVersion {
+    pub fn for_revision(revision: u32) -> Self {
+        match revision {
+            0 | 1 => RulesVersion::Legacy,
+            2 => RulesVersion::Revision2,
+            _ => RulesVersion::Strict,
+        }
+    }
+
+    pub fn revision(self) -> u32 {
+        match self {
+            RulesVersion::Legacy => 1,
+            RulesVersion::Revision2 => 2,
+            RulesVersion::Strict => crate::RULES_REVISION,
+        }
+    }
+
+    /// Longest road counts a road running between two opponents' buildings
+    pub fn roads_between_opponents_count(self) -> bool {
+        self >= RulesVersion::Revision2
+    }
+
+    /// A development card cannot be played the turn it was bought
+    pub fn bought_dev_cards_wait_a_turn(self) -> bool {
+        self >= RulesVersion::Strict
+    }
+
+    /// A 7 takes half of an odd hand rounded down, e.g. 4 of 9 cards
+    pub fn discards_round_down(self) -> bool {
+        self >= RulesVersion::Strict
+    }
+
+    /// A player reaching the target on someone else's turn, e.g. by taking over Longest
+    /// Road, wins at once rather than on their own next turn. Every revision so far
+    /// plays it this way, as catanatron does.
+    pub fn wins_on_any_turn(self) -> bool {
+        true
+    }
+}
+
+/// The standard four-player game on the base map
+impl Default for GameConfiguration {
+    fn default() -> Self {
+        Self {
+            discard_limit: DEFAULT_DISCARD_LIMIT,
+            vps_to_win: 10,
+            map_type: MapType::Base,
+            num_players: 4,
+            max_ticks: DEFAULT_MAX_TICKS,
+            teams: None,
+            robber: RobberRules::default(),
+            variants: VariantConfig::default(),
+            rules: RulesVersion::default(),
+            seating: Seating::default(),
+            handicaps: Vec::new(),
+        }
+    }
+}
+
+impl GameConfiguration {
+    pub fn builder() -> GameConfigBuilder {
+        GameConfigBuilder::default()
+    }
+
+    /// 5–6 player games: the bigger bank and deck, and a Special Build Phase after every
+    /// turn
+    pub fn uses_extension(&self) -> bool {
+        self.num_players >= EXTENSION_MIN_PLAYERS
+    }
+
+    pub fn validate(&self) -> CatanResult<()> {
+        if self.num_players < MIN_PLAYERS {
+            return Err(GameError::MinPlayersNotMet {
+                min_players: MIN_PLAYERS,
+            }
+            .into());
+        }
+        if self.num_players > MAX_PLAYERS {
+            return Err(GameError::MaxPlayersReached {
+                max_players: MAX_PLAYERS,
+            }
+            .into());
+        }
+        let invalid = |details: String| Err(CatanError::Validation(details));
+        if self.uses_extension() && !matches!(self.map_type, MapType::Extended) {
+            return invalid(format!(
+                "{} players need the Extended map, not {:?}",
+                self.num_players, self.map_type
+            ));
+        }
+        if !VPS_TO_WIN_RANGE.contains(&self.vps_to_win) {
+            return invalid(format!(
+                "vps_to_win {} outside {VPS_TO_WIN_RANGE:?}",
+                self.vps_to_win
+            ));
+        }
+        if !DISCARD_LIMIT_RANGE.contains(&self.discard_limit) {
+            return invalid(format!(
+                "discard_limit {} outside {DISCARD_LIMIT_RANGE:?}",
+                self.discard_limit
+            ));
+        }
+        if self.max_ticks == 0 {
+            return invalid("max_ticks must be positive".to_string());
+        }
+        if let Seating::Explicit(order) = &self.seating {
+            let mut sorted = order.clone();
+            sorted.sort_unstable();
+            if !sorted.iter().copied().eq(0..self.num_players) {
+                return invalid(format!(
+                    "seating {order:?} is not an order of all {} colors",
+                    self.num_players
+                ));
+            }
+        }
+        if let Some(teams) = &self.teams {
+            if teams.team_of_seat.len() != self.num_players as usize {
+                return invalid(format!(
+                    "{} team seats for {} players",
+                    teams.team_of_seat.len(),
+                    self.num_players
+                ));
+            }
+        }
+        if self.variants.catch_up.robber_immunity_gap == Some(0) {
+            return invalid("robber_immunity_gap must be positive".to_string());
+        }
+        if !self.handicaps.is_empty() {
+            if self.handicaps.len() != self.num_players as usize {
+                return invalid(format!(
+                    "{} handicaps for {} players",
+                    self.handicaps.len(),
+                    self.num_players
+                ));
+            }
+            if let Some(handicap) = self
+                .handicaps
+                .iter()
+                .find(|handicap| handicap.vp_offset >= self.vps_to_win)
+            {
+                return invalid(format!(
+                    "vp_offset {} reaches vps_to_win {}",
+                    handicap.vp_offset, self.vps_to_win
+                ));
+            }
+            let mut dealt = [0u32; 5];
+            for handicap in &self.handicaps {
+                for (total, &count) in dealt.iter_mut().zip(&handicap.resources) {
+                    *total += count as u32;
+                }
+            }
+            let bank = crate::state_vector::bank_resource_count(self.num_players);
+            if dealt.iter().any(|&total| total > bank as u32) {
+                return invalid(format!(
+                    "handicaps deal {dealt:?}, more than the bank holds"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a JSON config file; missing fields take their defaults
+    pub fn load(path: &Path) -> CatanResult<Self> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| crate::replay::persistence_error(path, e))?;
+        serde_json::from_str(&text).map_err(|e| crate::replay::persistence_error(path, e))
+    }
+}
+
+/// Builds a validated `GameConfiguration`, starting from the defaults. The discard limit
+/// and map, when not given, follow the player count.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GameConfigBuilder {
+    discard_limit: Option<u8>,
+    vps_to_win: u8,
+    map_type: Option<MapType>,
+    num_players: u8,
+    max_ticks: u32,
+    teams: Option<TeamConfig>,
+    robber: RobberRules,
+    variants: VariantConfig,
+    rules: RulesVersion,
+    seating: Seating,
+    handicaps: Vec<SeatHandicap>,
+}
+
+impl Default for GameConfigBuilder {
+    fn default() -> Self {
+        let GameConfiguration {
+            vps_to_win,
+            num_players,
+            max_ticks,
+            teams,
+            robber,
+            variants,
+            rules,
+            seating,
+            handicaps,
+            ..
+        } = GameConfiguration::default();
+        Self {
+            discard_limit: None,
+            vps_to_win,
+            map_type: None,
+            num_players,
+            max_ticks,
+            teams,
+            robber,
+            variants,
+            rules,
+            seating,
+            handicaps,
+        }
+    }
+}
+
+impl GameConfigBuilder {
+    pub fn discard_limit(mut self, discard_limit: u8) -> Self {
+        self.discard_limit = Some(discard_limit);
+        self
+    }
+
+    pub fn vps_to_win(mut self, vps_to_win: u8) -> Self {
+        self.vps_to_win = vps_to_win;
+        self
+    }
+
+    pub fn map_type(mut self, map_type: MapType) -> Self {
+        self.map_type = Some(map_type);
+        self
+    }
+
+    pub fn num_players(mut self, num_players: u8) -> Self {
+        self.num_players = num_players;
+        self
+    }
+
+    pub fn max_ticks(mut self, max_ticks: u32) -> Self {
+        self.max_ticks = max_ticks;
+        self
+    }
+
+    pub fn teams(mut self, teams: Option<TeamConfig>) -> Self {
+        self.teams = teams;
+        self
+    }
+
+    pub fn robber(mut self, robber: RobberRules) -> Self {
+        self.robber = robber;
+        self
+    }
+
+    pub fn variants(mut self, variants: VariantConfig) -> Self {
+        self.variants = variants;
+        self
+    }
+
+    pub fn rules(mut self, rules: RulesVersion) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn seating(mut self, seating: Seating) -> Self {
+        self.seating = seating;
+        self
+    }
+
+    pub fn handicaps(mut self, handicaps: Vec<SeatHandicap>) -> Self {
+        self.handicaps = handicaps;
+        self
+    }
+
+    pub fn build(self) -> CatanResult<GameConfiguration> {
+        let extension = self.num_players >= EXTENSION_MIN_PLAYERS;
+        let config = GameConfiguration {
+            discard_limit: self.discard_limit.unwrap_or(if extension {
+                EXTENSION_DISCARD_LIMIT
+            } else {
+                DEFAULT_DISCARD_LIMIT
+            }),
+            vps_to_win: self.vps_to_win,
+            map_type: self.map_type.unwrap_or(if extension {
+                MapType::Extended
+            } else {
+                MapType::Base
+            }),
+            num_players: self.num_players,
+            max_ticks: self.max_ticks,
+            teams: self.teams,
+            robber: self.robber,
+            variants: self.variants,
+            rules: self.rules,
+            seating: self.seating,
+            handicaps: self.handicaps,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+impl TryFrom<GameConfigBuilder> for GameConfiguration {
+    type Error = CatanError;
+
+    fn try_from(builder: GameConfigBuilder) -> CatanResult<Self> {
+        builder.build()
+    }
+}
+
+/// Optional official mini-variants. All off by default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VariantConfig {
+    /// Harbormaster: 2 VP for the most harbor points (settlement 1, city 2; at least 3)
+    pub harbormaster: bool,
+    /// Where roll results come from
+    pub dice: DiceMode,
+    /// Anti-leader rules that help trailing players
+    #[serde(default)]
+    pub catch_up: CatchUpRules,
+    /// Experimental speed rounds: after the roll, every seat in turn gets a build phase
+    /// on it (`Action::PassBuildPhase`) before the turn ends
+    #[serde(default)]
+    pub speed_rounds: bool,
+}
+
+/// Catch-up rules. The leaders are the players with the most victory points; everyone
+/// else trails, unless all are tied. All off by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatchUpRules {
+    /// A trailing player whose roll pays a leader also takes one card of the resource
+    /// that leader collects most of
+    pub leader_roll_bonus: bool,
+    /// Players at least this many points behind the leaders cannot be robbed, nor have
+    /// their tiles blocked while other tiles are open
+    pub robber_immunity_gap: Option<u8>,
+}
+
+/// Source of roll results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiceMode {
+    /// Two independent d6
+    #[default]
+    Standard,
+    /// "Catan Event Cards": a shuffled deck with one card per 2d6 outcome. The New Year
+    /// card sits among the bottom five and reshuffles the deck when drawn.
+    EventCards,
+    /// Draws from a shuffled bag of the 36 two-dice outcomes, refilled when empty
+    Balanced,
+}
+
+/// Robber house rules ("friendly robber" family). The default is the standard game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RobberRules {
+    /// Friendly robber: players with at most this many public victory points cannot be
+    /// targeted, neither by placing the robber on their tiles nor by stealing
+    pub protect_up_to_vps: Option<u8>,
+    /// Whether the robber may be moved (back) to the desert
+    pub allow_desert: bool,
+    /// When false the robber only blocks production and never steals
+    pub allow_steal: bool,
+}
+
+impl Default for RobberRules {
+    fn default() -> Self {
+        Self {
+            protect_up_to_vps: None,
+            allow_desert: true,
+            allow_steal: true,
+        }
+    }
+}
+
+impl RobberRules {
+    /// Common friendly robber: players at 2 VP or less are off limits
+    pub fn friendly() -> Self {
+        Self {
+            protect_up_to_vps: Some(2),
+            ..Self::default()
+        }
+    }
+}
+
+/// How a team wins in the team variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TeamVictory {
+    /// Teammates' victory points are summed and checked against `vps_to_win`
+    CombinedPoints,
+    /// The team wins as soon as any teammate reaches `vps_to_win`
+    FirstTeammate,
+}
+
+/// Team variant (e.g. 2v2): seats grouped into teams sharing one victory condition.
+/// Teammates cannot be robbed by each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamConfig {
+    /// Team index per seat, e.g. [0, 1, 0, 1] seats partners across the table
+    pub team_of_seat: Vec<u8>,
+    pub victory: TeamVictory,
+    /// Allow handing one resource card to a teammate once per turn
+    pub allow_gifts: bool,
+}
+
+impl TeamConfig {
+    /// Two teams of two with partners sitting opposite each other
+    pub fn two_vs_two(victory: TeamVictory) -> Self {
+        Self {
+            team_of_seat: vec![0, 1, 0, 1],
+            victory,
+            allow_gifts: true,
+        }
+    }
+}
+
+/// Head start for one seat. The default is no handicap.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeatHandicap {
+    /// Cards dealt from the bank before the first placement, in resource order
+    pub resources: FreqDeck,
+    /// Victory points the seat starts with
+    pub vp_offset: u8,
+    /// Place a third road right after the second initial road
+    pub extra_road: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_validates() {
+        let config = GameConfiguration::builder().num_players(3).build().unwrap();
+        assert_eq!(config.num_players, 3);
+        assert_eq!(config.vps_to_win, 10);
+        assert_eq!(config.max_ticks, DEFAULT_MAX_TICKS);
+
+        let too_many = GameConfiguration::builder().num_players(7).build();
+        assert!(matches!(
+            too_many,
+            Err(CatanError::Game(GameError::MaxPlayersReached { .. }))
+        ));
+        assert!(GameConfiguration::builder().vps_to_win(0).build().is_err());
+        assert!(GameConfiguration::builder()
+            .discard_limit(2)
+            .build()
+            .is_err());
+        // Teams must cover every seat
+        let teams = TeamConfig::two_vs_two(TeamVictory::FirstTeammate);
+        assert!(GameConfiguration::builder()
+            .num_players(3)
+            .teams(Some(teams))
+            .build()
+            .is_err());
+        // An explicit seating must name every color once
+        let seating = |order: Vec<u8>| {
+            GameConfiguration::builder()
+                .num_players(3)
+                .seating(Seating::Explicit(order))
+                .build()
+        };
+        assert!(seating(vec![2, 0, 1]).is_ok());
+        assert!(seating(vec![2, 0, 0]).is_err());
+        assert!(seating(vec![2, 0, 1, 3]).is_err());
+    }
+
+    #[test]
+    fn test_extension_defaults_follow_the_player_count() {
+        let config = GameConfiguration::builder().num_players(6).build().unwrap();
+        assert!(config.uses_extension());
+        assert!(matches!(config.map_type, MapType::Extended));
+        assert_eq!(config.discard_limit, EXTENSION_DISCARD_LIMIT);
+
+        let config = GameConfiguration::builder()
+            .num_players(5)
+            .discard_limit(7)
+            .build()
+            .unwrap();
+        assert_eq!(config.discard_limit, 7);
+        // The base board is too small for five
+        assert!(GameConfiguration::builder()
+            .num_players(5)
+            .map_type(MapType::Base)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_deserialize_fills_defaults_and_validates() {
+        let config: GameConfiguration =
+            serde_json::from_str(r#"{"num_players": 2, "vps_to_win": 12}"#).unwrap();
+        assert_eq!((config.num_players, config.vps_to_win), (2, 12));
+        assert_eq!(config.discard_limit, 7);
+        assert!(matches!(config.map_type, MapType::Base));
+
+        let round_trip: GameConfiguration =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+        assert_eq!(round_trip.vps_to_win, 12);
+
+        assert!(serde_json::from_str::<GameConfiguration>(r#"{"num_players": 1}"#).is_err());
+    }
+}
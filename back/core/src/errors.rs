@@ -31,6 +31,9 @@ pub enum GameError {
     #[error("Game not found: {game_id}")]
     GameNotFound { game_id: GameId },
 
+    #[error("Campaign not found: {campaign_id}")]
+    CampaignNotFound { campaign_id: String },
+
     #[error("Game already exists: {game_id}")]
     GameAlreadyExists { game_id: GameId },
 
@@ -57,6 +60,9 @@ pub enum GameError {
 
     #[error("Minimum players not met: {min_players}")]
     MinPlayersNotMet { min_players: u8 },
+
+    #[error("Action made against game version {submitted}, but the game is at {current}")]
+    StaleVersion { submitted: u64, current: u64 },
 }
 
 /// Player-specific errors
@@ -187,3 +193,100 @@ impl From<&str> for CatanError {
         CatanError::Internal(msg.to_string())
     }
 }
+
+/// Text of a caught panic's payload
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Error category sent to WebSocket clients, so they can branch on (and localize) errors
+/// without parsing messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    GameNotFound,
+    NotYourTurn,
+    IllegalAction,
+    RateLimited,
+    VersionConflict,
+    Internal,
+}
+
+impl CatanError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            CatanError::Game(GameError::GameNotFound { .. })
+            | CatanError::Game(GameError::CampaignNotFound { .. }) => ErrorCode::GameNotFound,
+            CatanError::Game(GameError::NotPlayerTurn { .. }) => ErrorCode::NotYourTurn,
+            CatanError::Game(GameError::StaleVersion { .. }) => ErrorCode::VersionConflict,
+            CatanError::Game(_) | CatanError::Player(_) | CatanError::Validation(_) => {
+                ErrorCode::IllegalAction
+            }
+            CatanError::Infrastructure(InfrastructureError::ResourceExhausted { .. }) => {
+                ErrorCode::RateLimited
+            }
+            CatanError::Network(_) | CatanError::Infrastructure(_) | CatanError::Internal(_) => {
+                ErrorCode::Internal
+            }
+        }
+    }
+
+    /// The error's fields for clients that act on them, e.g. the current version after a
+    /// version conflict
+    pub fn details(&self) -> Option<serde_json::Value> {
+        let details = match self {
+            CatanError::Game(GameError::GameNotFound { game_id }) => {
+                serde_json::json!({ "game_id": game_id })
+            }
+            CatanError::Game(GameError::CampaignNotFound { campaign_id }) => {
+                serde_json::json!({ "campaign_id": campaign_id })
+            }
+            CatanError::Game(GameError::InvalidAction { action, player_id }) => {
+                serde_json::json!({ "action": action, "player_id": player_id })
+            }
+            CatanError::Game(GameError::NotPlayerTurn {
+                current_player,
+                attempted_player,
+            }) => serde_json::json!({
+                "current_player": current_player,
+                "attempted_player": attempted_player,
+            }),
+            CatanError::Game(GameError::StaleVersion { submitted, current }) => {
+                serde_json::json!({ "submitted": submitted, "current": current })
+            }
+            _ => return None,
+        };
+        Some(details)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_and_details() {
+        let stale = CatanError::Game(GameError::StaleVersion {
+            submitted: 3,
+            current: 5,
+        });
+        assert_eq!(stale.code(), ErrorCode::VersionConflict);
+        assert_eq!(stale.details().unwrap()["current"], 5);
+
+        let turn = CatanError::Game(GameError::not_player_turn("player_1", "player_0"));
+        assert_eq!(turn.code(), ErrorCode::NotYourTurn);
+        assert_eq!(
+            serde_json::to_value(turn.code()).unwrap(),
+            serde_json::json!("NOT_YOUR_TURN")
+        );
+        assert_eq!(
+            CatanError::from("lock poisoned").code(),
+            ErrorCode::Internal
+        );
+        assert!(CatanError::Validation("bad".into()).details().is_none());
+    }
+}
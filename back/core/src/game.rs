@@ -0,0 +1,2184 @@
+use crate::audit::{self, AuditReport, AuditSettings};
+use crate::compact_actions::{ActionTable, ActionsFormat, CompactActions};
+use crate::dice_stats::{DiceFairness, DiceHistogram};
+use crate::enums::{
+    Action as EnumAction, DevCard, GameConfiguration, Resource as EnumResource, Seating,
+};
+use crate::errors::{panic_message, CatanResult};
+use crate::map_instance::{Direction, EdgeRef, LandTile, MapInstance, NodeRef, PortTile, Tile};
+use crate::map_template::Coordinate as CubeCoordinate;
+use crate::pace::Pace;
+use crate::palette::Palette;
+use crate::players::opponent_model::OpponentModel;
+use crate::players::win_model::FITTED_WIN_MODEL;
+use crate::replay::Replay;
+// REMOVED: NodeDirection import - no longer needed
+use crate::state::debug_dump::StateDump;
+use crate::state::loop_detection::{LoopBreak, LoopDetector};
+use crate::state::validation::Illegal;
+use crate::state::{BuildingType, State};
+use crate::think_time::{SeatThinkTime, ThinkClock};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::collections::HashMap;
+
+// (tile_id, resource_name, number)
+type NodeTileAdjacency = (u8, Option<String>, Option<u8>);
+type NodeAdjacencyMap = HashMap<u8, Vec<NodeTileAdjacency>>;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid;
+
+// Use EnumAction instead of defining GameAction
+pub type GameAction = EnumAction;
+
+// Game state enum to track the current state of the game
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum GameState {
+    Setup,
+    Active,
+    Finished { winner: String },
+}
+
+// A serializable coordinate for frontend use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Coordinate {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+// A serializable tile for frontend use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TileData {
+    pub resource: Option<String>,
+    pub number: Option<u8>,
+}
+
+// A tile with its position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TilePosition {
+    pub coordinate: Coordinate,
+    pub tile: TileData,
+}
+
+// A port for trading resources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Port {
+    pub resource: Option<String>,
+    pub ratio: u8,
+    pub direction: String,
+}
+
+// A port with its position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortPosition {
+    pub coordinate: Coordinate,
+    pub port: Port,
+}
+
+// A node (intersection) on the board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Node {
+    pub building: Option<String>,
+    pub color: Option<String>,
+    pub tile_coordinate: Coordinate,
+    pub direction: String,
+}
+
+// REMOVED: NodeAbsoluteCoordinate struct - no longer needed
+
+// An edge (path) on the board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Edge {
+    pub color: Option<String>,
+    pub node1_id: u8,
+    pub node2_id: u8,
+    pub tile_coordinate: Coordinate,
+    pub direction: String,
+}
+
+// The game board representation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameBoard {
+    pub tiles: Vec<TilePosition>,
+    pub ports: Vec<PortPosition>,
+    pub nodes: HashMap<String, Node>,
+    pub edges: HashMap<String, Edge>,
+    pub robber_coordinate: Option<Coordinate>,
+}
+
+// Player information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Player {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+    pub resources: HashMap<EnumResource, u32>,
+    pub dev_cards: Vec<DevCard>,
+    // Development cards held but not shown to this viewer, e.g. an opponent's
+    #[serde(default)]
+    pub hidden_dev_cards: u32,
+    pub knights_played: u32,
+    pub victory_points: u32, // Public score; includes victory point cards once the game is over
+    pub longest_road_length: u32, // Longest continuous road, held award or not
+    pub ports: Vec<Option<EnumResource>>, // Port types built on; None is the 3:1 port
+    pub longest_road: bool,
+    pub largest_army: bool,
+    // Pieces still in the player's supply
+    #[serde(default)]
+    pub settlements_left: u32,
+    #[serde(default)]
+    pub cities_left: u32,
+    #[serde(default)]
+    pub roads_left: u32,
+    /// Seafarers only; 0 on boards without sea
+    #[serde(default)]
+    pub ships_left: u32,
+}
+
+// Action tracking for the game log - format: [player_color, action_type, action_data]
+pub type ActionLog = Vec<serde_json::Value>;
+
+// The unified Game struct that replaces both Game enum and GameView
+#[derive(Debug, Clone, Deserialize)]
+pub struct Game {
+    pub id: String,
+    pub players: Vec<Player>,
+    pub game_state: GameState,
+    pub current_player_index: usize,
+    pub dice_rolled: bool,
+    pub turns: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_dice_roll: Option<[u8; 2]>,
+    pub actions: ActionLog, // Track all actions for the game log
+    // Frontend compatibility fields
+    pub current_playable_actions: Vec<crate::actions::PlayerAction>,
+    pub is_initial_build_phase: bool,
+    pub current_color: Option<String>,
+    pub current_prompt: Option<String>,
+    pub bot_colors: Vec<String>, // Colors of bot players for frontend identification
+    #[serde(default)]
+    pub forked_from: Option<ForkOrigin>, // Set on sandbox games branched off another game
+    #[serde(default)]
+    pub pace: Pace, // Speed preset picked at creation
+    #[serde(default)]
+    pub palette: Palette, // How seats are colored on screen, picked at creation
+    #[serde(default)]
+    pub version: u64, // Bumped by every applied action, so clients can tell stale views
+    #[serde(default)]
+    pub last_action_seq: u64, // Highest client sequence number accepted, for deduplication
+    #[serde(default)]
+    pub fault: Option<GameFault>, // Set when applying an action panicked; no actions after
+    #[serde(skip)]
+    pub state: Option<State>, // Internal game logic state, skipped in serialization
+    #[serde(skip)]
+    loop_detector: LoopDetector,
+    #[serde(skip)]
+    last_loop_break: Option<LoopBreak>,
+    #[serde(skip)]
+    replay: Option<Replay>, // Every action applied since creation, for export
+    #[serde(skip)]
+    opponent_model: OpponentModel, // Habits observed for each seat during this game
+    #[serde(skip)]
+    vp_history: Vec<Vec<u8>>, // Shown VPs per seat after every completed turn
+    #[serde(skip)]
+    dice: DiceHistogram, // Totals rolled so far
+    #[serde(skip)]
+    action_deltas: Vec<ActionDelta>, // What every applied action changed, for replay viewers
+    #[serde(skip)]
+    think_clock: ThinkClock, // Time each seat takes to answer its prompts
+    #[serde(skip)]
+    board: Option<GameBoard>, // Built once; only buildings, roads and robber are refreshed
+    #[serde(skip)]
+    board_topology: Option<BoardTopology>, // Tiles and ports as JSON, spliced into snapshots
+    #[serde(skip)]
+    action_table: Option<Arc<ActionTable>>, // Positions for compact playable actions
+}
+
+/// Tiles and ports of a board, serialized once per game since they never change.
+/// Shared between clones of the game.
+#[derive(Debug, Clone)]
+struct BoardTopology {
+    tiles: Arc<RawValue>,
+    ports: Arc<RawValue>,
+}
+
+impl BoardTopology {
+    fn new(board: &GameBoard) -> Option<Self> {
+        let raw = |value: String| RawValue::from_string(value).ok().map(Arc::from);
+        Some(Self {
+            tiles: raw(serde_json::to_string(&board.tiles).ok()?)?,
+            ports: raw(serde_json::to_string(&board.ports).ok()?)?,
+        })
+    }
+}
+
+/// The board as sent to clients, with the pre-serialized topology in place of tiles and
+/// ports. Same JSON as GameBoard.
+#[derive(Serialize)]
+struct BoardSnapshot<'a> {
+    tiles: &'a RawValue,
+    ports: &'a RawValue,
+    nodes: &'a HashMap<String, Node>,
+    edges: &'a HashMap<String, Edge>,
+    robber_coordinate: &'a Option<Coordinate>,
+}
+
+/// Actual victory points per seat after every completed turn, for VP-over-time charts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VpHistory {
+    /// Seat colors, in the order of each turn's points
+    pub colors: Vec<String>,
+    pub turns: Vec<Vec<u8>>,
+}
+
+/// Per-game statistics served at `/games/{id}/stats`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameStats {
+    /// Completed turns
+    pub turns: usize,
+    pub dice: DiceFairness,
+    /// Time each seat took to answer its prompts
+    pub think_time: Vec<SeatThinkTime>,
+    /// Win probability per seat from `FITTED_WIN_MODEL`, in color order; empty before
+    /// the game starts
+    #[serde(default)]
+    pub win_probabilities: Vec<f64>,
+}
+
+/// Everything an operator needs to look into a misbehaving game, served at
+/// `/games/{id}/debug` to admins
+#[derive(Debug, Clone, Serialize)]
+pub struct GameDump {
+    pub id: String,
+    pub version: u64,
+    pub game_state: GameState,
+    pub fault: Option<GameFault>,
+    /// The last entries of the action log, oldest first
+    pub recent_actions: ActionLog,
+    pub state: Option<StateDump>,
+}
+
+/// One applied action and what it changed, so a replay viewer can step through a game
+/// without fetching a board snapshot per action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionDelta {
+    /// Position in the game's action history
+    pub index: usize,
+    pub action: GameAction,
+    /// Dice shown by a roll
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dice: Option<(u8, u8)>,
+    /// Resource cards gained or lost per seat, indexed like the resource enum
+    pub resources: Vec<[i8; 5]>,
+    /// Change in victory points per seat as everyone sees them: victory point cards count
+    /// only once the game is over
+    pub victory_points: Vec<i8>,
+    /// Time the seat took to choose the action. Forced actions and those replayed into an
+    /// imported or forked game have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub think_ms: Option<u64>,
+}
+
+/// A page of a game's action history, served at `/games/{id}/actions`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionPage {
+    /// Actions applied over the whole game
+    pub total: usize,
+    pub actions: Vec<ActionDelta>,
+}
+
+/// Where a sandbox ("what if") game branched off: the original game and how many of its
+/// actions were replayed before play diverged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkOrigin {
+    pub game_id: String,
+    pub at: usize,
+}
+
+/// Why a game stopped taking actions: applying one panicked, which may have left the
+/// position half-updated. Holds what is needed to reproduce the failure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameFault {
+    pub message: String,
+    /// The action whose application panicked
+    pub action: String,
+    /// Game version before that action
+    pub version: u64,
+    pub prompt: Option<String>,
+    /// The last few entries of the action log leading up to the failure
+    pub recent_actions: ActionLog,
+}
+
+/// How the turn order was decided, served with every game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatingInfo {
+    pub method: Seating,
+    /// Colors in turn order
+    pub order: Vec<String>,
+    /// Rolls that decided who goes first, in the order they were rolled
+    pub opening_rolls: Vec<OpeningRollInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpeningRollInfo {
+    pub color: String,
+    pub dice: (u8, u8),
+}
+
+/// Where the current player may place a settlement or road right now, going by the board
+/// alone (cost is not checked), so clients can highlight spots without scanning actions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildableMasks {
+    pub color: String, // Same form as `current_color`
+    pub node_ids: Vec<u8>,
+    pub edge_ids: Vec<(u8, u8)>,
+}
+
+/// Bump when the savegame layout changes incompatibly
+pub const SAVEGAME_VERSION: u32 = 1;
+
+/// Self-contained savegame: the seats plus a replay of everything applied so far.
+/// Re-applying the replay restores the exact position, including the dice and deck
+/// order still to come, since the replay carries the game's random seed. The clock
+/// state rides along so a game moved to another server keeps its pace and timings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub player_names: Vec<String>,
+    pub bot_colors: Vec<String>,
+    pub bot_mode: String,
+    pub replay: Replay,
+    #[serde(default)]
+    pub pace: Pace,
+    /// Think time of every recorded action, in order
+    #[serde(default)]
+    pub think_ms: Vec<Option<u64>>,
+    /// Time already spent on the prompt that was open when the game was saved
+    #[serde(default)]
+    pub open_prompt_ms: u64,
+}
+
+// Resource cards held by every seat
+fn hands(state: &State) -> Vec<[u8; 5]> {
+    (0..state.get_num_players())
+        .map(|color| std::array::from_fn(|i| state.get_player_resource_count(color, i as u8)))
+        .collect()
+}
+
+// Victory points of every seat as shown to all players, hidden cards left out until
+// somebody has won
+fn shown_vps(state: &State) -> Vec<u8> {
+    let finished = state.winner().is_some();
+    (0..state.get_num_players())
+        .map(|color| {
+            if finished {
+                state.get_actual_victory_points(color)
+            } else {
+                state.get_public_victory_points(color)
+            }
+        })
+        .collect()
+}
+
+// Helper function to convert from template coordinate to serializable coordinate
+fn convert_coordinate(coord: CubeCoordinate) -> Coordinate {
+    Coordinate {
+        x: coord.0 as i32,
+        y: coord.1 as i32,
+        z: coord.2 as i32,
+    }
+}
+
+// REMOVED: node_ref_to_direction function - no longer needed
+
+// REMOVED: node_coordinate_to_absolute helper - no longer needed
+
+// REMOVED: find_node_absolute_coordinate function - no longer needed
+
+// Helper function to convert from map_instance::Tile to TilePosition
+fn convert_land_tile(coord: CubeCoordinate, land_tile: &LandTile) -> TilePosition {
+    let resource = match land_tile.resource {
+        Some(EnumResource::Wood) => Some("wood".to_string()),
+        Some(EnumResource::Brick) => Some("brick".to_string()),
+        Some(EnumResource::Sheep) => Some("sheep".to_string()),
+        Some(EnumResource::Wheat) => Some("wheat".to_string()),
+        Some(EnumResource::Ore) => Some("ore".to_string()),
+        None if land_tile.gold => Some("gold".to_string()),
+        None => None,
+    };
+
+    TilePosition {
+        coordinate: convert_coordinate(coord),
+        tile: TileData {
+            resource,
+            number: land_tile.number,
+        },
+    }
+}
+
+// Helper function to convert from map_instance::PortTile to PortPosition
+fn convert_port_tile(coord: CubeCoordinate, port_tile: &PortTile) -> PortPosition {
+    let resource = match port_tile.resource {
+        Some(EnumResource::Wood) => Some("wood".to_string()),
+        Some(EnumResource::Brick) => Some("brick".to_string()),
+        Some(EnumResource::Sheep) => Some("sheep".to_string()),
+        Some(EnumResource::Wheat) => Some("wheat".to_string()),
+        Some(EnumResource::Ore) => Some("ore".to_string()),
+        None => None,
+    };
+
+    let direction = match port_tile.direction {
+        Direction::NorthWest => "NW",
+        Direction::NorthEast => "NE",
+        Direction::East => "E",
+        Direction::SouthEast => "SE",
+        Direction::SouthWest => "SW",
+        Direction::West => "W",
+        Direction::North => "N",
+        Direction::South => "S",
+    };
+
+    // Check if it's a resource port before using the resource value
+    let is_resource_port = resource.is_some();
+
+    PortPosition {
+        coordinate: convert_coordinate(coord),
+        port: Port {
+            resource,
+            ratio: if is_resource_port { 2 } else { 3 },
+            direction: direction.to_string(),
+        },
+    }
+}
+
+// Create a new Player struct from the given information
+pub fn create_player(id: String, name: String, color: String) -> Player {
+    Player {
+        id,
+        name,
+        color,
+        resources: HashMap::new(),
+        dev_cards: Vec::new(), // Will be populated from internal state via update_players_from_state
+        hidden_dev_cards: 0,
+        knights_played: 0,
+        victory_points: 0,
+        longest_road_length: 0,
+        ports: Vec::new(),
+        longest_road: false,
+        largest_army: false,
+        settlements_left: 0,
+        cities_left: 0,
+        roads_left: 0,
+        ships_left: 0,
+    }
+}
+
+// Create a new Game with default settings
+pub fn create_game(id: String, player_names: Vec<String>) -> Game {
+    // Create configuration for the game state
+    let config = GameConfiguration {
+        num_players: player_names.len() as u8,
+        ..Default::default()
+    };
+
+    // Shared board for the game, fixed seed for predictable board generation
+    let map_instance = MapInstance::shared(&config.map_type, 0);
+
+    // Create the State object first (it owns the canonical map)
+    let state = State::new(Arc::new(config), map_instance);
+
+    Game::from_state(id, &player_names, state, 0)
+}
+
+// Game simulation for bot play
+pub fn simulate_bot_game(num_players: u8) -> Game {
+    let player_names = (0..num_players).map(|i| format!("Bot {}", i + 1)).collect();
+    let game_id = format!("sim_{}", uuid::Uuid::new_v4());
+    Game::new(game_id, player_names)
+}
+
+// Initial setup for a game against Catanatron; every seat but the first is a bot
+pub fn start_human_vs_catanatron(
+    human_name: String,
+    config: GameConfiguration,
+    map_seed: u64,
+) -> Game {
+    let num_bots = config.num_players - 1;
+    log::debug!("🎮 DEBUG start_human_vs_catanatron:");
+    log::debug!("  - Human name: {human_name}");
+    log::debug!("  - Number of bots: {num_bots}");
+
+    let mut player_names = vec![human_name.clone()];
+
+    for i in 0..num_bots {
+        player_names.push(format!("Bot {}", i + 1));
+    }
+
+    log::debug!("  - Player names: {player_names:?}");
+
+    let game_id = format!("hvs_{}", uuid::Uuid::new_v4());
+    log::debug!("  - Game ID: {game_id}");
+
+    let mut game = Game::new_on_map(game_id, player_names, config, map_seed);
+
+    // Set bot_colors - all players except the first one (human) are bots
+    game.bot_colors = game
+        .players
+        .iter()
+        .skip(1) // Skip the first player (human)
+        .map(|p| p.color.clone())
+        .collect();
+
+    log::debug!("  - Bot colors: {:?}", game.bot_colors);
+    log::debug!("  - All players:");
+    for (i, player) in game.players.iter().enumerate() {
+        let is_bot = game.bot_colors.contains(&player.color);
+        log::debug!(
+            "    - Player {}: {} (color: {}, is_bot: {})",
+            i,
+            player.name,
+            player.color,
+            is_bot
+        );
+    }
+
+    // Update metadata from state
+    log::debug!("  - Calling update_metadata_from_state...");
+    game.update_metadata_from_state();
+
+    log::debug!("🎮 END start_human_vs_catanatron debug\n");
+
+    game
+}
+
+impl Game {
+    pub fn new(id: String, player_names: Vec<String>) -> Self {
+        // Create configuration for the game state
+        Self::new_with_config(id, player_names, GameConfiguration::default())
+    }
+
+    /// Like `new`, but with a caller-provided configuration (e.g. a team variant).
+    /// `num_players` is taken from `player_names`.
+    pub fn new_with_config(
+        id: String,
+        player_names: Vec<String>,
+        config: GameConfiguration,
+    ) -> Self {
+        // Fixed seed for predictable board generation
+        Self::new_on_map(id, player_names, config, 0)
+    }
+
+    /// Like `new_with_config`, on the board generated from `map_seed`
+    pub fn new_on_map(
+        id: String,
+        player_names: Vec<String>,
+        mut config: GameConfiguration,
+        map_seed: u64,
+    ) -> Self {
+        config.num_players = player_names.len() as u8;
+
+        // Shared board for the game
+        let map_instance = MapInstance::shared(&config.map_type, map_seed);
+
+        // Create the State object first (it owns the canonical map)
+        let mut state = State::new(Arc::new(config), map_instance);
+
+        // For testing: Add dev cards to each player in the internal state
+        for player_idx in 0..player_names.len() {
+            let color = player_idx as u8;
+            // Add 2 of each dev card type to the internal state
+            state.add_dev_card(color, DevCard::Knight as usize);
+            state.add_dev_card(color, DevCard::Knight as usize);
+            state.add_dev_card(color, DevCard::Monopoly as usize);
+            state.add_dev_card(color, DevCard::Monopoly as usize);
+            state.add_dev_card(color, DevCard::YearOfPlenty as usize);
+            state.add_dev_card(color, DevCard::YearOfPlenty as usize);
+            state.add_dev_card(color, DevCard::RoadBuilding as usize);
+            state.add_dev_card(color, DevCard::RoadBuilding as usize);
+        }
+
+        Self::from_state(id, &player_names, state, map_seed)
+    }
+
+    /// Wrap a fresh engine state (no actions applied yet) in a Game.
+    /// `map_seed` is the seed the state's map was generated from; it is kept for export.
+    pub fn from_state(id: String, player_names: &[String], state: State, map_seed: u64) -> Self {
+        let colors = ["red", "blue", "white", "orange", "green", "brown"];
+
+        let players = player_names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let player_id = format!("player_{i}");
+                let color = colors[i % colors.len()].to_string();
+                create_player(player_id, name.clone(), color)
+            })
+            .collect();
+
+        let replay = Replay::record_start(&state, map_seed);
+
+        // Create the Game object (the board is built by update_metadata_from_state)
+        let mut game = Game {
+            id,
+            players,
+            game_state: GameState::Setup,
+            current_player_index: 0,
+            dice_rolled: false,
+            turns: 0,
+            current_dice_roll: None,
+            actions: Vec::new(), // Initialize empty actions log
+            current_playable_actions: Vec::new(),
+            is_initial_build_phase: true,
+            current_color: None,
+            current_prompt: None,
+            bot_colors: Vec::new(),
+            forked_from: None,
+            fault: None,
+            pace: Pace::default(),
+            palette: Palette::default(),
+            version: 0,
+            last_action_seq: 0,
+            state: Some(state),
+            loop_detector: LoopDetector::new(),
+            last_loop_break: None,
+            replay: Some(replay),
+            opponent_model: OpponentModel::new(),
+            vp_history: Vec::new(),
+            dice: DiceHistogram::default(),
+            action_deltas: Vec::new(),
+            think_clock: ThinkClock::default(),
+            board: None,
+            board_topology: None,
+            action_table: None,
+        };
+
+        // Update metadata from the initial state, which need not be empty (e.g. from FEN)
+        if let Some(state) = &game.state {
+            update_players_from_state(&mut game.players, state);
+            game.action_table = Some(Arc::new(ActionTable::new(
+                state.get_map_instance(),
+                state.get_config().num_players,
+            )));
+        }
+        game.update_metadata_from_state();
+        game.think_clock.start();
+
+        game
+    }
+
+    /// Restore a game from a savegame by re-applying its recorded actions.
+    /// Fails if any action is not legal where it was recorded.
+    pub fn from_savegame(id: String, save: &SaveGame) -> Result<Self, String> {
+        if save.version > SAVEGAME_VERSION {
+            return Err(format!("Unsupported savegame version {}", save.version));
+        }
+        if save.player_names.len() != save.replay.config.num_players as usize {
+            return Err("Savegame seats do not match its configuration".into());
+        }
+        let state = save.replay.initial_state();
+        let mut game = Self::from_state(id, &save.player_names, state, save.replay.map_seed);
+        game.bot_colors = save.bot_colors.clone();
+        game.pace = save.pace;
+        game.apply_recorded(&save.replay.actions)?;
+        game.restore_clock(&save.think_ms, Duration::from_millis(save.open_prompt_ms));
+
+        if let Some(state) = &game.state {
+            if state.compute_hash64() != save.replay.final_hash {
+                return Err("Restored position does not match the savegame".into());
+            }
+        }
+        game.update_metadata_from_state();
+        Ok(game)
+    }
+
+    /// A new game that replays this one's first `at` actions, so another line can be
+    /// explored from that point. Seats and bot colors carry over.
+    pub fn fork(&self, id: String, at: usize) -> Result<Self, String> {
+        let replay = self
+            .replay
+            .as_ref()
+            .ok_or("Game has no recorded history to fork")?;
+        if at > replay.actions.len() {
+            return Err(format!(
+                "Cannot fork at action {at}: the game has {} actions",
+                replay.actions.len()
+            ));
+        }
+        let player_names: Vec<String> = self.players.iter().map(|p| p.name.clone()).collect();
+        let state = replay.initial_state();
+        let mut game = Self::from_state(id, &player_names, state, replay.map_seed);
+        game.bot_colors = self.bot_colors.clone();
+        game.palette = self.palette;
+        game.apply_recorded(&replay.actions[..at])?;
+        game.forked_from = Some(ForkOrigin {
+            game_id: self.id.clone(),
+            at,
+        });
+        game.update_metadata_from_state();
+        Ok(game)
+    }
+
+    /// Re-apply recorded actions in order, failing on the first one that is not legal
+    fn apply_recorded(&mut self, actions: &[GameAction]) -> Result<(), String> {
+        for (i, &action) in actions.iter().enumerate() {
+            let state = self.state.as_ref().ok_or("Game state is missing")?;
+            // Rule-checked rather than looked up among the generated actions, since custom
+            // discards, offers and counter-offers are never generated
+            if let Err(rule) = state.validate_action(&action) {
+                return Err(format!(
+                    "Action #{i} {action:?} is not legal at that point: {rule}"
+                ));
+            }
+            let player_index = state.get_current_color() as usize;
+            self.apply_and_record(player_index, action)?;
+        }
+        // Replaying took no one's time
+        self.think_clock.start();
+        Ok(())
+    }
+
+    /// Export everything needed to restore this game elsewhere
+    pub fn to_savegame(&self, bot_mode: String) -> Result<SaveGame, String> {
+        let replay = self
+            .replay
+            .clone()
+            .ok_or("Game has no recorded history to export")?;
+        Ok(SaveGame {
+            version: SAVEGAME_VERSION,
+            player_names: self.players.iter().map(|p| p.name.clone()).collect(),
+            bot_colors: self.bot_colors.clone(),
+            bot_mode,
+            replay,
+            pace: self.pace,
+            think_ms: self.think_times(),
+            open_prompt_ms: self.think_clock.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Think time of every applied action, in order
+    pub fn think_times(&self) -> Vec<Option<u64>> {
+        self.action_deltas.iter().map(|d| d.think_ms).collect()
+    }
+
+    /// Put back think times saved with `think_times` after replaying the same actions,
+    /// charging them to the seats again, with `open` spent on the prompt still open
+    fn restore_clock(&mut self, think_ms: &[Option<u64>], open: Duration) {
+        for (delta, &think_ms) in self.action_deltas.iter_mut().zip(think_ms) {
+            delta.think_ms = think_ms;
+        }
+        let charges = self.action_deltas.iter().filter_map(|delta| {
+            let think_ms = delta.think_ms?;
+            Some((
+                delta.action.color() as usize,
+                Duration::from_millis(think_ms),
+            ))
+        });
+        self.think_clock.restore(charges, open);
+    }
+
+    /// Fair-play audit of the human seats' timed decisions
+    pub fn audit(&self, settings: &AuditSettings) -> Result<AuditReport, String> {
+        let replay = self
+            .replay
+            .as_ref()
+            .ok_or("Game has no recorded history to audit")?;
+        let humans: Vec<(u8, String)> = (0..self.players.len())
+            .filter(|&i| !self.bot_colors.contains(&self.players[i].color))
+            .map(|i| (i as u8, self.players[i].color.clone()))
+            .collect();
+        Ok(audit::audit(
+            self.id.clone(),
+            replay,
+            &self.think_times(),
+            &humans,
+            settings,
+        ))
+    }
+
+    /// Board data for the current state, from the cache when it has been built
+    pub fn get_board(&self) -> GameBoard {
+        if let Some(board) = &self.board {
+            board.clone()
+        } else if let Some(state) = &self.state {
+            generate_board_from_state(state, state.get_map_instance())
+        } else {
+            // Fallback empty board if no state (shouldn't happen)
+            GameBoard {
+                tiles: Vec::new(),
+                ports: Vec::new(),
+                nodes: HashMap::new(),
+                edges: HashMap::new(),
+                robber_coordinate: None,
+            }
+        }
+    }
+
+    // Process an action on the game
+    pub fn process_action(&mut self, player_id: &str, action: GameAction) -> Result<(), String> {
+        // Check if the game is in a valid state for actions
+        match self.game_state {
+            GameState::Finished { .. } => return Err("Game already finished".into()),
+            GameState::Setup | GameState::Active => {} // Allow actions in both Setup and Active phases
+        }
+
+        // Find the player's color index first
+        let player_index = self.players.iter().position(|p| p.id == player_id);
+
+        if player_index.is_none() {
+            return Err("Player not found".into());
+        }
+
+        let player_index = player_index.unwrap();
+
+        if let Some(fault) = &self.fault {
+            return Err(format!(
+                "Game is halted after an internal error: {}",
+                fault.message
+            ));
+        }
+
+        // Answers to a trade offer may leave its terms out
+        let action = match &self.state {
+            Some(state) => state.canonical_form(action),
+            None => action,
+        };
+
+        if let Some(violation) = self
+            .state
+            .as_ref()
+            .and_then(|state| state.initial_placement_violation(&action))
+        {
+            return Err(format!("Illegal initial placement: {violation}"));
+        }
+
+        let index = self.action_deltas.len();
+        self.apply_isolated(player_index, action)?;
+        let think_time = self.think_clock.record(player_index);
+        if let (Some(think_time), Some(delta)) = (think_time, self.action_deltas.get_mut(index)) {
+            delta.think_ms = Some(think_time.as_millis() as u64);
+        }
+
+        // Break out of repeated positions; the forced EndTurn is logged like any other action
+        if !matches!(self.game_state, GameState::Finished { .. }) {
+            if let Some(state) = &self.state {
+                self.last_loop_break = self.loop_detector.check(state, action);
+            }
+            if let Some(LoopBreak {
+                forced_end_turn: true,
+                color,
+                ..
+            }) = self.last_loop_break
+            {
+                self.apply_isolated(player_index, EnumAction::EndTurn { color })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `apply_and_record`, but a panic while applying halts this game with a `GameFault`
+    /// instead of unwinding into the caller, which may be holding locks other games need
+    fn apply_isolated(&mut self, player_index: usize, action: GameAction) -> Result<(), String> {
+        let version = self.version;
+        let applied = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            self.apply_and_record(player_index, action)
+        }));
+        applied.unwrap_or_else(|payload| {
+            let message = panic_message(payload.as_ref());
+            let fault = GameFault {
+                message: message.clone(),
+                action: format!("{action:?}"),
+                version,
+                prompt: self.current_prompt.clone(),
+                recent_actions: self.actions[self.actions.len().saturating_sub(10)..].to_vec(),
+            };
+            log::error!(
+                "💥 Game {} halted: applying {action:?} panicked: {message}\n{}",
+                self.id,
+                serde_json::to_string_pretty(&fault).unwrap_or_default()
+            );
+            self.fault = Some(fault);
+            Err(format!("Internal error applying {action:?}: {message}"))
+        })
+    }
+
+    /// Apply an action for the player at `player_index` and record it in the action log
+    /// and replay, without any turn-order or loop checks
+    fn apply_and_record(&mut self, player_index: usize, action: GameAction) -> Result<(), String> {
+        // Get player color for logging (clone to avoid borrowing issues)
+        let player_color = self.players[player_index].color.clone();
+
+        // Apply the action and get updated state info
+        let (new_current_player, new_dice_rolled) = {
+            // Scope the mutable borrow of state
+            let state = match &mut self.state {
+                Some(state) => state,
+                None => return Err("Game state is missing".into()),
+            };
+
+            // Observe before applying: the model judges the action against the position
+            // it was chosen in
+            self.opponent_model.observe(state, action);
+
+            let hands_before = hands(state);
+            let vps_before = shown_vps(state);
+
+            // Apply the action directly since GameAction is now an alias for EnumAction
+            state.apply_action(action);
+            self.action_deltas.push(ActionDelta {
+                index: self.action_deltas.len(),
+                action,
+                dice: match action {
+                    EnumAction::Roll { .. } => state.get_last_dice_roll(),
+                    _ => None,
+                },
+                resources: hands_before
+                    .iter()
+                    .zip(hands(state))
+                    .map(|(before, after)| {
+                        std::array::from_fn(|i| after[i] as i8 - before[i] as i8)
+                    })
+                    .collect(),
+                victory_points: vps_before
+                    .iter()
+                    .zip(shown_vps(state))
+                    .map(|(&before, after)| after as i8 - before as i8)
+                    .collect(),
+                think_ms: None,
+            });
+            if let Some(replay) = &mut self.replay {
+                replay.push(action, state);
+                replay.finish(state);
+            }
+            match action {
+                EnumAction::EndTurn { .. } => self.vp_history.push(shown_vps(state)),
+                EnumAction::Roll { .. } => {
+                    if let Some(dice) = state.get_last_dice_roll() {
+                        self.dice.record(dice);
+                    }
+                }
+                _ => {}
+            }
+
+            // Update frontend players from the state
+            update_players_from_state(&mut self.players, state);
+
+            // Get current player and dice rolled status
+            (
+                state.get_current_color() as usize,
+                state.current_player_rolled(),
+            )
+        };
+
+        // Now we can safely update other fields
+        self.version += 1;
+        self.current_player_index = new_current_player;
+        self.dice_rolled = new_dice_rolled;
+
+        self.refresh_board();
+
+        // Log the action for the game log - format: [player_color, action_type, action_data]
+        let action_log_entry = {
+            let (action_type, action_data) = match &action {
+                EnumAction::BuildSettlement { node_id, .. } => {
+                    ("BuildSettlement", serde_json::json!(node_id))
+                }
+                EnumAction::BuildCity { node_id, .. } => ("BuildCity", serde_json::json!(node_id)),
+                EnumAction::BuildRoad { edge_id, .. } => ("BuildRoad", serde_json::json!(edge_id)),
+                EnumAction::BuildShip { edge_id, .. } => ("BuildShip", serde_json::json!(edge_id)),
+                EnumAction::MoveShip { from, to, .. } => {
+                    ("MoveShip", serde_json::json!([from, to]))
+                }
+                EnumAction::BuyDevelopmentCard { .. } => {
+                    ("BuyDevelopmentCard", serde_json::Value::Null)
+                }
+                EnumAction::PlayKnight { .. } => ("PlayKnight", serde_json::Value::Null),
+                EnumAction::PlayMonopoly { resource, .. } => {
+                    ("PlayMonopoly", serde_json::json!(resource))
+                }
+                EnumAction::PlayYearOfPlenty { resources, .. } => {
+                    ("PlayYearOfPlenty", serde_json::json!(resources))
+                }
+                EnumAction::PlayRoadBuilding { .. } => {
+                    ("PlayRoadBuilding", serde_json::Value::Null)
+                }
+                EnumAction::MoveRobber {
+                    coordinate,
+                    victim_opt,
+                    ..
+                } => {
+                    let mut data = serde_json::json!([coordinate.0, coordinate.1, coordinate.2]);
+                    if let Some(victim) = victim_opt {
+                        if let serde_json::Value::Array(ref mut arr) = data {
+                            arr.push(serde_json::json!(victim));
+                        }
+                    }
+                    ("MoveRobber", data)
+                }
+                EnumAction::MaritimeTrade {
+                    give, take, ratio, ..
+                } => ("MaritimeTrade", serde_json::json!([give, take, ratio])),
+                EnumAction::EndTurn { .. } => ("EndTurn", serde_json::Value::Null),
+                EnumAction::PassBuildPhase { .. } => ("PassBuildPhase", serde_json::Value::Null),
+                EnumAction::Roll { .. } => {
+                    // Simple approach: get dice from state after action is applied
+                    let dice_data = if let Some(ref state) = self.state {
+                        if let Some((die1, die2)) = state.get_last_dice_roll() {
+                            let total = die1 + die2;
+                            serde_json::json!(total)
+                        } else {
+                            serde_json::Value::Null
+                        }
+                    } else {
+                        serde_json::Value::Null
+                    };
+                    ("Roll", dice_data)
+                }
+                EnumAction::Discard { resources, .. } => ("Discard", serde_json::json!(resources)),
+                EnumAction::ChooseGold { resource, .. } => {
+                    ("ChooseGold", serde_json::json!(resource))
+                }
+                EnumAction::GiftResource {
+                    teammate, resource, ..
+                } => ("GiftResource", serde_json::json!([teammate, resource])),
+                EnumAction::OfferTrade { trade, .. } => ("OfferTrade", serde_json::json!(trade)),
+                EnumAction::AcceptTrade { trade, .. } => ("AcceptTrade", serde_json::json!(trade)),
+                EnumAction::RejectTrade { .. } => ("RejectTrade", serde_json::Value::Null),
+                EnumAction::CounterTrade { trade, .. } => {
+                    ("CounterTrade", serde_json::json!(trade))
+                }
+                EnumAction::ConfirmTrade { trade, .. } => {
+                    ("ConfirmTrade", serde_json::json!(trade))
+                }
+                EnumAction::CancelTrade { .. } => ("CancelTrade", serde_json::Value::Null),
+                #[cfg(any(test, feature = "debug-actions"))]
+                _ => ("Unknown", serde_json::Value::Null),
+            };
+
+            serde_json::json!([player_color.to_uppercase(), action_type, action_data])
+        };
+
+        self.actions.push(action_log_entry);
+
+        // Sync frontend game_state with internal state phase transitions
+        // Check if we should transition from Setup to Active phase
+        if self.game_state == GameState::Setup {
+            if let Some(ref state) = self.state {
+                if !state.is_initial_build_phase() {
+                    log::info!("🎮 Game {}: Transitioning from Setup to Active phase - initial build phase completed", self.id);
+                    self.game_state = GameState::Active;
+                }
+            }
+        }
+
+        // Check if the game is finished
+        let winner_color = self.state.as_ref().and_then(|s| s.winner());
+        if let Some(winner_color) = winner_color {
+            let winner_name = if (winner_color as usize) < self.players.len() {
+                self.players[winner_color as usize].name.clone()
+            } else {
+                "Unknown".to_string()
+            };
+
+            // Update the game state to finished
+            self.game_state = GameState::Finished {
+                winner: winner_name,
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Habits observed for each seat so far, for bots that adapt to their opponents
+    pub fn opponent_model(&self) -> &OpponentModel {
+        &self.opponent_model
+    }
+
+    /// Victory points after every completed turn so far
+    pub fn vp_history(&self) -> VpHistory {
+        VpHistory {
+            colors: self.players.iter().map(|p| p.color.clone()).collect(),
+            turns: self.vp_history.clone(),
+        }
+    }
+
+    /// Up to `limit` applied actions starting at index `from`, with what each changed
+    pub fn action_page(&self, from: usize, limit: usize) -> ActionPage {
+        ActionPage {
+            total: self.action_deltas.len(),
+            actions: self
+                .action_deltas
+                .iter()
+                .skip(from)
+                .take(limit)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Players as seen from the seats in `viewers`: until the game is over, other seats'
+    /// development cards are counted in `hidden_dev_cards` rather than listed
+    pub fn players_seen_by(&self, viewers: &[String]) -> Vec<Player> {
+        let finished = matches!(self.game_state, GameState::Finished { .. });
+        self.players
+            .iter()
+            .map(|player| {
+                let mut player = player.clone();
+                if !finished && !viewers.contains(&player.color) {
+                    player.hidden_dev_cards = player.dev_cards.len() as u32;
+                    player.dev_cards.clear();
+                }
+                player
+            })
+            .collect()
+    }
+
+    /// Turn order and how it was decided
+    pub fn seating(&self) -> Option<SeatingInfo> {
+        let state = self.state.as_ref()?;
+        let color = |color: u8| self.players[color as usize].color.clone();
+        Some(SeatingInfo {
+            method: state.get_config().seating.clone(),
+            order: state
+                .get_seating_order()
+                .iter()
+                .map(|&c| color(c))
+                .collect(),
+            opening_rolls: state
+                .get_opening_rolls()
+                .iter()
+                .map(|&(c, dice)| OpeningRollInfo {
+                    color: color(c),
+                    dice,
+                })
+                .collect(),
+        })
+    }
+
+    /// Buildable nodes and edges for the player to move; `None` once the game is over
+    pub fn buildable_masks(&self) -> Option<BuildableMasks> {
+        use crate::enums::ActionPrompt;
+
+        let state = self.state.as_ref()?;
+        if state.winner().is_some() {
+            return None;
+        }
+        let color = state.get_current_color();
+        let place = |actions: Vec<EnumAction>| -> (Vec<u8>, Vec<(u8, u8)>) {
+            let mut node_ids = Vec::new();
+            let mut edge_ids = Vec::new();
+            for action in actions {
+                match action {
+                    EnumAction::BuildSettlement { node_id, .. } => node_ids.push(node_id),
+                    EnumAction::BuildRoad { edge_id, .. } => edge_ids.push(edge_id),
+                    _ => {}
+                }
+            }
+            (node_ids, edge_ids)
+        };
+        let (mut node_ids, mut edge_ids) = match state.get_action_prompt() {
+            ActionPrompt::BuildInitialSettlement => {
+                place(state.settlement_possibilities(color, true))
+            }
+            ActionPrompt::BuildInitialRoad => place(state.initial_road_possibilities(color)),
+            ActionPrompt::PlayTurn => (
+                state.buildable_node_ids(color),
+                state.board_buildable_edges(color),
+            ),
+            _ => (Vec::new(), Vec::new()),
+        };
+        node_ids.sort_unstable();
+        edge_ids.sort_unstable();
+        Some(BuildableMasks {
+            color: self.current_color.clone()?,
+            node_ids,
+            edge_ids,
+        })
+    }
+
+    /// Whether `action` could be applied now, and if not, the rule it breaks. Roads and
+    /// Year of Plenty pairs may be written in either order.
+    pub fn check_action(&self, action: EnumAction) -> Result<(), Illegal> {
+        let state = match &self.state {
+            Some(state) if self.fault.is_none() && state.winner().is_none() => state,
+            _ => return Err(Illegal::GameOver),
+        };
+        state.validate_action(&state.canonical_form(action))
+    }
+
+    /// Table behind compact playable actions; built on demand for games restored from JSON
+    pub fn action_table(&self) -> Option<Arc<ActionTable>> {
+        self.action_table.clone().or_else(|| {
+            let state = self.state.as_ref()?;
+            Some(Arc::new(ActionTable::new(
+                state.get_map_instance(),
+                state.get_config().num_players,
+            )))
+        })
+    }
+
+    /// `current_playable_actions` as positions in `action_table()`
+    pub fn compact_actions(&self, format: ActionsFormat) -> Option<CompactActions> {
+        self.action_table()?
+            .encode(&self.current_playable_actions, format)
+    }
+
+    /// Raw position and caches plus the latest actions, for debugging
+    pub fn debug_dump(&self) -> GameDump {
+        GameDump {
+            id: self.id.clone(),
+            version: self.version,
+            game_state: self.game_state.clone(),
+            fault: self.fault.clone(),
+            recent_actions: self.actions[self.actions.len().saturating_sub(20)..].to_vec(),
+            state: self.state.as_ref().map(State::debug_dump),
+        }
+    }
+
+    /// Where the board clients are sent differs from one rebuilt from scratch by
+    /// replaying every recorded action on a fresh state, as JSON paths such as
+    /// `nodes.n12.color`. Empty when they agree; `None` for games without a replay.
+    pub fn board_divergence(&self) -> CatanResult<Option<Vec<String>>> {
+        let Some(replay) = &self.replay else {
+            return Ok(None);
+        };
+        let rebuilt = replay.resimulate()?;
+        let expected = generate_board_from_state(&rebuilt, rebuilt.get_map_instance());
+
+        let sent = serde_json::to_value(self)
+            .ok()
+            .and_then(|mut game| game.get_mut("board").map(serde_json::Value::take))
+            .unwrap_or_default();
+        let expected = serde_json::to_value(&expected).unwrap_or_default();
+        let mut differences = Vec::new();
+        json_differences("", &sent, &expected, &mut differences);
+        Ok(Some(differences))
+    }
+
+    pub fn stats(&self) -> GameStats {
+        let colors: Vec<String> = self.players.iter().map(|p| p.color.clone()).collect();
+        GameStats {
+            turns: self.vp_history.len(),
+            dice: self.dice.fairness(),
+            think_time: self.think_clock.summary(&colors),
+            win_probabilities: self
+                .state
+                .as_ref()
+                .map(|state| FITTED_WIN_MODEL.win_probabilities(state))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Take the loop diagnostic produced by the last processed action, if any
+    pub fn take_loop_break(&mut self) -> Option<LoopBreak> {
+        self.last_loop_break.take()
+    }
+
+    /// Check if the game is in the initial build phase from the internal state
+    pub fn is_initial_build_phase(&self) -> bool {
+        self.state
+            .as_ref()
+            .map(|s| s.is_initial_build_phase())
+            .unwrap_or(true) // Default to true if no state available
+    }
+
+    /// Update game metadata from the internal state
+    pub fn update_metadata_from_state(&mut self) {
+        if let Some(state) = &self.state {
+            // DEBUG: Log essential information about the current state
+            let current_color_index = state.get_current_color();
+            let is_initial_phase = state.is_initial_build_phase();
+            let action_prompt = state.get_action_prompt();
+
+            log::debug!("🔍 DEBUG update_metadata_from_state:");
+            log::debug!("  - Current color index: {current_color_index}");
+            log::debug!("  - Is initial build phase: {is_initial_phase}");
+            log::debug!("  - Action prompt: {action_prompt:?}");
+
+            // Update current_playable_actions
+            let playable_actions = state.generate_playable_actions();
+            log::debug!("  - Generated {} playable actions", playable_actions.len());
+
+            // Debug: Log just the first action for verification
+            if !playable_actions.is_empty() {
+                log::debug!("    - First action: {:?}", playable_actions[0]);
+            }
+
+            self.current_playable_actions = playable_actions
+                .iter()
+                .map(|action| crate::actions::PlayerAction::from(*action))
+                .collect();
+
+            log::debug!(
+                "  - Converted to {} PlayerActions",
+                self.current_playable_actions.len()
+            );
+
+            // Update current_color
+            self.current_color = Some(match current_color_index {
+                0 => "RED".to_string(),
+                1 => "BLUE".to_string(),
+                2 => "WHITE".to_string(),
+                3 => "ORANGE".to_string(),
+                4 => "GREEN".to_string(),
+                5 => "BROWN".to_string(),
+                _ => format!("PLAYER_{current_color_index}"),
+            });
+
+            // Update is_initial_build_phase
+            self.is_initial_build_phase = is_initial_phase;
+
+            self.refresh_board();
+
+            // Update current_prompt based on action prompt
+            use crate::enums::ActionPrompt;
+            self.current_prompt = Some(match action_prompt {
+                ActionPrompt::BuildInitialSettlement => "BUILD_INITIAL_SETTLEMENT".to_string(),
+                ActionPrompt::BuildInitialRoad => "BUILD_INITIAL_ROAD".to_string(),
+                ActionPrompt::PlayTurn => "PLAY_TURN".to_string(),
+                ActionPrompt::Discard => "DISCARD".to_string(),
+                ActionPrompt::MoveRobber => "MOVE_ROBBER".to_string(),
+                ActionPrompt::DecideTrade => "DECIDE_TRADE".to_string(),
+                ActionPrompt::DecideAcceptees => "DECIDE_ACCEPTEES".to_string(),
+                ActionPrompt::SpecialBuild => "SPECIAL_BUILD".to_string(),
+                ActionPrompt::ChooseGold => "CHOOSE_GOLD".to_string(),
+            });
+
+            log::debug!(
+                "  - Current color: {:?}, Current prompt: {:?}",
+                self.current_color,
+                self.current_prompt
+            );
+            log::debug!("🔍 END update_metadata_from_state debug\n");
+        } else {
+            log::error!("❌ update_metadata_from_state: No internal state available!");
+        }
+    }
+
+    /// Bring the cached board in line with the state, building it on first use. Tiles,
+    /// ports and topology never change, so only buildings, roads and the robber are
+    /// compared, and fields are written only when they differ.
+    fn refresh_board(&mut self) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let Some(board) = &mut self.board else {
+            let board = generate_board_from_state(state, state.get_map_instance());
+            self.board_topology = BoardTopology::new(&board);
+            self.board = Some(board);
+            return;
+        };
+
+        for (key, node) in board.nodes.iter_mut() {
+            let Ok(node_id) = key[1..].parse::<u8>() else {
+                continue;
+            };
+            let building = state.get_building_type(node_id).map(building_name);
+            if node.building.as_deref() != building {
+                node.building = building.map(str::to_string);
+            }
+            let color = state.get_node_color(node_id).map(color_name);
+            if node.color.as_deref() != color {
+                node.color = color.map(str::to_string);
+            }
+        }
+        for edge in board.edges.values_mut() {
+            let color = state
+                .get_edge_owner((edge.node1_id, edge.node2_id))
+                .map(color_name);
+            if edge.color.as_deref() != color {
+                edge.color = color.map(str::to_string);
+            }
+        }
+
+        let robber_tile_id = state.get_robber_tile();
+        board.robber_coordinate = state
+            .get_map_instance()
+            .land_tiles
+            .iter()
+            .find(|(_, tile)| tile.id == robber_tile_id)
+            .map(|(&coordinate, _)| convert_coordinate(coordinate));
+    }
+
+    /// Helper method to verify frontend and backend state consistency
+    pub fn verify_state_consistency(&self) -> bool {
+        match (&self.game_state, self.state.as_ref()) {
+            (GameState::Setup, Some(state)) => state.is_initial_build_phase(),
+            (GameState::Active, Some(state)) => {
+                !state.is_initial_build_phase() && state.winner().is_none()
+            }
+            (GameState::Finished { .. }, Some(state)) => state.winner().is_some(),
+            _ => false, // Inconsistent if no internal state
+        }
+    }
+
+    /// Get all adjacent tiles for a specific node ID using backend's authoritative adjacency calculation
+    pub fn get_node_adjacent_tiles(&self, node_id: u8) -> Option<Vec<NodeTileAdjacency>> {
+        if let Some(state) = &self.state {
+            let map_instance = state.get_map_instance();
+            if let Some(adjacent_tiles) = map_instance.get_adjacent_tiles(node_id) {
+                let result: Vec<NodeTileAdjacency> = adjacent_tiles
+                    .iter()
+                    .map(|tile| {
+                        let resource_str = tile.resource.map(|r| match r {
+                            crate::enums::Resource::Wood => "wood".to_string(),
+                            crate::enums::Resource::Brick => "brick".to_string(),
+                            crate::enums::Resource::Sheep => "sheep".to_string(),
+                            crate::enums::Resource::Wheat => "wheat".to_string(),
+                            crate::enums::Resource::Ore => "ore".to_string(),
+                        });
+                        let resource_str =
+                            resource_str.or_else(|| tile.gold.then(|| "gold".to_string()));
+                        (tile.id, resource_str, tile.number)
+                    })
+                    .collect();
+                Some(result)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get complete node-to-tile adjacency mapping for all nodes using backend's authoritative calculations
+    /// Returns HashMap<NodeId, Vec<(TileId, Resource, Number)>>
+    pub fn get_all_node_tile_adjacencies(&self) -> NodeAdjacencyMap {
+        let mut adjacencies: NodeAdjacencyMap = HashMap::new();
+
+        if let Some(state) = &self.state {
+            let map_instance = state.get_map_instance();
+            // Iterate through all land nodes to build complete mapping
+            for &node_id in map_instance.land_nodes() {
+                if let Some(adjacent_tiles) = self.get_node_adjacent_tiles(node_id) {
+                    adjacencies.insert(node_id, adjacent_tiles);
+                }
+            }
+        }
+
+        adjacencies
+    }
+}
+
+// Custom Serialize implementation to include board field generated on-demand
+impl Serialize for Game {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Game", 27)?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("engine_version", crate::VERSION)?;
+        state.serialize_field("rules_revision", &crate::RULES_REVISION)?;
+        // Clients play the human seats, so bots' development cards are only counted
+        let humans: Vec<String> = self
+            .players
+            .iter()
+            .filter(|p| !self.bot_colors.contains(&p.color))
+            .map(|p| p.color.clone())
+            .collect();
+        state.serialize_field("players", &self.players_seen_by(&humans))?;
+        state.serialize_field("game_state", &self.game_state)?;
+        state.serialize_field("current_player_index", &self.current_player_index)?;
+        state.serialize_field("dice_rolled", &self.dice_rolled)?;
+        state.serialize_field("turns", &self.turns)?;
+        state.serialize_field("current_dice_roll", &self.current_dice_roll)?;
+        state.serialize_field("actions", &self.actions)?;
+        state.serialize_field("current_playable_actions", &self.current_playable_actions)?;
+        state.serialize_field("is_initial_build_phase", &self.is_initial_build_phase)?;
+        state.serialize_field("current_color", &self.current_color)?;
+        state.serialize_field("current_prompt", &self.current_prompt)?;
+        state.serialize_field("bot_colors", &self.bot_colors)?;
+        state.serialize_field("forked_from", &self.forked_from)?;
+        state.serialize_field("version", &self.version)?;
+        state.serialize_field("last_action_seq", &self.last_action_seq)?;
+        state.serialize_field("fault", &self.fault)?;
+        // Live timer for the open prompt; stops once the game is over
+        let prompt_elapsed_ms = match self.game_state {
+            GameState::Finished { .. } => None,
+            _ => Some(self.think_clock.elapsed().as_millis() as u64),
+        };
+        state.serialize_field("prompt_elapsed_ms", &prompt_elapsed_ms)?;
+        state.serialize_field("seating", &self.seating())?;
+        state.serialize_field("pace", &self.pace)?;
+        state.serialize_field("pace_settings", &self.pace.settings())?;
+        state.serialize_field("palette", &self.palette)?;
+        let seats: Vec<String> = self.players.iter().map(|p| p.color.clone()).collect();
+        state.serialize_field("seat_colors", &self.palette.seat_colors(&seats))?;
+        state.serialize_field(
+            "dice_mode",
+            &self.state.as_ref().map(|s| s.get_config().variants.dice),
+        )?;
+
+        // Serialize the cached board in place, splicing in the topology JSON; games
+        // restored from JSON have neither yet
+        match (&self.board, &self.board_topology) {
+            (Some(board), Some(topology)) => state.serialize_field(
+                "board",
+                &BoardSnapshot {
+                    tiles: &topology.tiles,
+                    ports: &topology.ports,
+                    nodes: &board.nodes,
+                    edges: &board.edges,
+                    robber_coordinate: &board.robber_coordinate,
+                },
+            )?,
+            (Some(board), None) => state.serialize_field("board", board)?,
+            (None, _) => state.serialize_field("board", &self.get_board())?,
+        }
+
+        state.end()
+    }
+}
+
+/// Collect the paths under `path` where `sent` and `expected` differ. Lists are compared
+/// element by element and objects key by key.
+fn json_differences(
+    path: &str,
+    sent: &serde_json::Value,
+    expected: &serde_json::Value,
+    out: &mut Vec<String>,
+) {
+    use serde_json::Value;
+
+    let child = |key: &dyn std::fmt::Display| match path {
+        "" => key.to_string(),
+        _ => format!("{path}.{key}"),
+    };
+    match (sent, expected) {
+        (Value::Object(sent), Value::Object(expected)) => {
+            let mut keys: Vec<&String> = sent.keys().chain(expected.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let missing = Value::Null;
+                let (a, b) = (
+                    sent.get(key).unwrap_or(&missing),
+                    expected.get(key).unwrap_or(&missing),
+                );
+                json_differences(&child(key), a, b, out);
+            }
+        }
+        (Value::Array(sent), Value::Array(expected)) if sent.len() == expected.len() => {
+            for (i, (a, b)) in sent.iter().zip(expected).enumerate() {
+                json_differences(&child(&i), a, b, out);
+            }
+        }
+        _ if sent != expected => out.push(format!("{path}: sent {sent}, rebuilt {expected}")),
+        _ => {}
+    }
+}
+
+// Generate a serializable game board from the State and MapInstance
+fn generate_board_from_state(state: &State, map_instance: &MapInstance) -> GameBoard {
+    let mut tiles = Vec::new();
+    let mut ports = Vec::new();
+    let mut nodes = HashMap::new();
+    let mut edges = HashMap::new();
+    let mut robber_coordinate = None;
+
+    // Collect all node information in a deterministic way
+    let mut node_info: std::collections::BTreeMap<u8, (Coordinate, String)> =
+        std::collections::BTreeMap::new();
+
+    // Get robber position from state
+    let robber_tile_id = state.get_robber_tile();
+
+    // Sort tiles by coordinate for deterministic iteration
+    let mut sorted_tiles: Vec<_> = map_instance.tiles.iter().collect();
+    sorted_tiles.sort_by_key(|(coord, _)| (coord.0, coord.1, coord.2));
+
+    // Process all tiles in deterministic order
+    for (&coordinate, tile) in sorted_tiles {
+        match tile {
+            Tile::Land(land_tile) => {
+                // Convert land tile to frontend format
+                tiles.push(convert_land_tile(coordinate, land_tile));
+
+                // Track the robber location
+                if land_tile.id == robber_tile_id {
+                    robber_coordinate = Some(convert_coordinate(coordinate));
+                }
+
+                // Collect node information (store the first occurrence for deterministic positioning)
+                for (&node_ref, &node_id) in &land_tile.hexagon.nodes {
+                    // Only store if not already stored (ensures deterministic positioning)
+                    node_info.entry(node_id).or_insert_with(|| {
+                        let direction = match node_ref {
+                            NodeRef::North => "N",
+                            NodeRef::NorthEast => "NE",
+                            NodeRef::SouthEast => "SE",
+                            NodeRef::South => "S",
+                            NodeRef::SouthWest => "SW",
+                            NodeRef::NorthWest => "NW",
+                        };
+
+                        (convert_coordinate(coordinate), direction.to_string())
+                    });
+                }
+
+                // Generate edges for this tile
+                for (&edge_ref, &(node1, node2)) in &land_tile.hexagon.edges {
+                    let direction = match edge_ref {
+                        EdgeRef::North => "N",
+                        EdgeRef::SouthEast => "SE",
+                        EdgeRef::SouthWest => "SW",
+                        EdgeRef::South => "S",
+                        EdgeRef::NorthWest => "NW",
+                        EdgeRef::NorthEast => "NE",
+                    };
+
+                    // Use edge ID format that shows both connected nodes
+                    let edge_id_str = format!("e{}_{}", node1.min(node2), node1.max(node2));
+
+                    // Get road owner using order-agnostic helper
+                    let edge_color_idx = state.get_edge_owner((node1, node2));
+                    let edge_color =
+                        edge_color_idx.map(|color_idx| color_name(color_idx).to_string());
+
+                    edges.insert(
+                        edge_id_str,
+                        Edge {
+                            color: edge_color,
+                            node1_id: node1,
+                            node2_id: node2,
+                            tile_coordinate: convert_coordinate(coordinate),
+                            direction: direction.to_string(),
+                        },
+                    );
+                }
+            }
+            Tile::Port(port_tile) => {
+                // Convert port tile to frontend format
+                ports.push(convert_port_tile(coordinate, port_tile));
+            }
+            Tile::Water(_) => {
+                // Skip water tiles - we don't need to send them to the frontend
+            }
+        }
+    }
+
+    // Now process nodes in deterministic order (sorted by node_id)
+    for (node_id, (tile_coordinate, direction)) in node_info {
+        // Use just the node ID as the key to ensure uniqueness
+        let node_id_str = format!("n{node_id}");
+
+        // Get building info using public state methods
+        let building_type_opt = state.get_building_type(node_id);
+        let building_color_idx_opt = state.get_node_color(node_id);
+
+        let building_type = building_type_opt.map(|building| building_name(building).to_string());
+        let building_color =
+            building_color_idx_opt.map(|color_idx| color_name(color_idx).to_string());
+
+        nodes.insert(
+            node_id_str,
+            Node {
+                building: building_type,
+                color: building_color,
+                tile_coordinate,
+                direction,
+            },
+        );
+    }
+
+    GameBoard {
+        tiles,
+        ports,
+        nodes,
+        edges,
+        robber_coordinate,
+    }
+}
+
+// Board name of a building type
+fn building_name(building: BuildingType) -> &'static str {
+    match building {
+        BuildingType::Settlement => "settlement",
+        BuildingType::City => "city",
+    }
+}
+
+// Board name of a color index
+fn color_name(color_idx: u8) -> &'static str {
+    match color_idx {
+        0 => "red",
+        1 => "blue",
+        2 => "white",
+        3 => "orange",
+        4 => "green",
+        5 => "brown",
+        _ => "unknown", // Handle unexpected color index
+    }
+}
+
+// Update frontend players from the State
+fn update_players_from_state(players: &mut [Player], state: &State) {
+    for (i, player) in players.iter_mut().enumerate() {
+        let color_idx = i as u8;
+
+        // Get resources
+        let player_hand = state.get_player_hand(color_idx);
+        if player_hand.len() >= 5 {
+            player.resources.clear();
+            player
+                .resources
+                .insert(EnumResource::Wood, player_hand[0] as u32);
+            player
+                .resources
+                .insert(EnumResource::Brick, player_hand[1] as u32);
+            player
+                .resources
+                .insert(EnumResource::Sheep, player_hand[2] as u32);
+            player
+                .resources
+                .insert(EnumResource::Wheat, player_hand[3] as u32);
+            player
+                .resources
+                .insert(EnumResource::Ore, player_hand[4] as u32);
+        }
+
+        // Get development cards (indices: 0=Knight, 1=YearOfPlenty, 2=Monopoly, 3=RoadBuilding, 4=VictoryPoint)
+        player.dev_cards.clear();
+        let player_dev_hand = state.get_player_devhand(color_idx);
+        if player_dev_hand.len() >= 5 {
+            for _ in 0..(player_dev_hand[0]) {
+                player.dev_cards.push(DevCard::Knight);
+            }
+            for _ in 0..(player_dev_hand[1]) {
+                player.dev_cards.push(DevCard::YearOfPlenty);
+            }
+            for _ in 0..(player_dev_hand[2]) {
+                player.dev_cards.push(DevCard::Monopoly);
+            }
+            for _ in 0..(player_dev_hand[3]) {
+                player.dev_cards.push(DevCard::RoadBuilding);
+            }
+        }
+        // Victory point cards score on purchase instead of sitting in the dev hand
+        for _ in 0..state.get_victory_point_cards(color_idx) {
+            player.dev_cards.push(DevCard::VictoryPoint);
+        }
+
+        // Update player stats
+        player.knights_played = state.get_knights_played(color_idx) as u32;
+        player.longest_road_length = state.get_road_length(color_idx) as u32;
+        player.ports = state.get_owned_ports(color_idx);
+        player.settlements_left = state.get_settlements_left(color_idx) as u32;
+        player.cities_left = state.get_cities_left(color_idx) as u32;
+        player.roads_left = state.get_roads_left(color_idx) as u32;
+        player.ships_left = if state.has_ships() {
+            state.get_ships_left(color_idx) as u32
+        } else {
+            0
+        };
+        // Victory point cards stay hidden from the public score until the game ends
+        player.victory_points = if state.winner().is_some() {
+            state.get_actual_victory_points(color_idx)
+        } else {
+            state.get_public_victory_points(color_idx)
+        } as u32;
+
+        player.longest_road = state.get_longest_road_color() == Some(color_idx);
+        player.largest_army = state.get_largest_army_color() == Some(color_idx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_savegame_roundtrip_restores_position() {
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("original".into(), names);
+        game.pace = Pace::Blitz;
+        let mut rng = XorShiftRng::seed_from_u64(11);
+        for _ in 0..300 {
+            let state = game.state.as_ref().unwrap();
+            if state.winner().is_some() {
+                break;
+            }
+            let action = *state.generate_playable_actions().choose(&mut rng).unwrap();
+            let player_id = game.players[state.get_current_color() as usize].id.clone();
+            game.process_action(&player_id, action).unwrap();
+        }
+
+        let save = game.to_savegame("random".into()).unwrap();
+        let json = serde_json::to_string(&save).unwrap();
+        let loaded: SaveGame = serde_json::from_str(&json).unwrap();
+        let restored = Game::from_savegame("restored".into(), &loaded).unwrap();
+
+        let original = game.state.as_ref().unwrap();
+        let copy = restored.state.as_ref().unwrap();
+        assert_eq!(original.compute_hash64(), copy.compute_hash64());
+        assert_eq!(restored.actions, game.actions);
+        let end_turns = save
+            .replay
+            .actions
+            .iter()
+            .filter(|a| matches!(a, EnumAction::EndTurn { .. }))
+            .count();
+        assert!(end_turns > 0);
+        assert_eq!(game.vp_history().turns.len(), end_turns);
+        assert_eq!(restored.vp_history(), game.vp_history());
+        assert!(game.stats().dice.rolls > 0);
+        assert_eq!(restored.stats().dice, game.stats().dice);
+        let win_probabilities = game.stats().win_probabilities;
+        assert_eq!(win_probabilities.len(), game.players.len());
+        assert_eq!(restored.stats().win_probabilities, win_probabilities);
+        // The clock state comes back with the savegame, not from timing the replay
+        assert!(game.stats().think_time.iter().any(|seat| seat.prompts > 0));
+        // Saved to the millisecond, so totals may lose the fractions of live timing
+        let seats = |game: &Game| -> Vec<(u32, u64)> {
+            game.stats()
+                .think_time
+                .iter()
+                .map(|seat| (seat.prompts, seat.longest_ms))
+                .collect()
+        };
+        assert_eq!(seats(&restored), seats(&game));
+        let page = game.action_page(0, usize::MAX);
+        assert!(page.actions.iter().all(|delta| delta.think_ms.is_some()));
+        assert_eq!(restored.action_page(0, usize::MAX), page);
+        assert_eq!(restored.pace, game.pace);
+        // Compared as sets: robber moves are generated in map iteration order
+        let restored_actions: std::collections::HashSet<_> =
+            copy.generate_playable_actions().into_iter().collect();
+        let original_actions: std::collections::HashSet<_> =
+            original.generate_playable_actions().into_iter().collect();
+        assert_eq!(restored_actions, original_actions);
+    }
+
+    #[test]
+    fn test_board_divergence_spots_state_changed_outside_the_log() {
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("diverged".into(), names);
+        for _ in 0..12 {
+            let state = game.state.as_ref().unwrap();
+            let action = state.generate_playable_actions()[0];
+            let player_id = game.players[state.get_current_color() as usize].id.clone();
+            game.process_action(&player_id, action).unwrap();
+        }
+        assert_eq!(game.board_divergence().unwrap(), Some(Vec::new()));
+
+        // Move the robber behind the action log's back
+        let state = game.state.as_mut().unwrap();
+        let desert = state.get_robber_tile();
+        let coordinate = *state
+            .get_map_instance()
+            .land_tiles
+            .iter()
+            .find(|(_, tile)| tile.id != desert)
+            .unwrap()
+            .0;
+        state.apply_action(EnumAction::DebugSetState {
+            color: 0,
+            edit: crate::enums::StateEdit::Robber(coordinate),
+        });
+        // GameService refreshes metadata, and with it the board, before every send
+        game.update_metadata_from_state();
+        let differences = game.board_divergence().unwrap().unwrap();
+        assert!(!differences.is_empty());
+        assert!(differences
+            .iter()
+            .all(|path| path.starts_with("robber_coordinate")));
+    }
+
+    #[test]
+    fn test_cached_board_matches_a_fresh_one() {
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("cached".into(), names);
+        let mut rng = XorShiftRng::seed_from_u64(11);
+        for _ in 0..300 {
+            let state = game.state.as_ref().unwrap();
+            if state.winner().is_some() {
+                break;
+            }
+            let actions = state.generate_playable_actions();
+            let action = *actions.choose(&mut rng).unwrap();
+            let player_id = game.players[state.get_current_color() as usize].id.clone();
+            game.process_action(&player_id, action).unwrap();
+        }
+
+        let state = game.state.as_ref().unwrap();
+        let fresh =
+            serde_json::to_value(generate_board_from_state(state, state.get_map_instance()))
+                .unwrap();
+        assert_eq!(serde_json::to_value(game.get_board()).unwrap(), fresh);
+        // Snapshots splice in the pre-serialized tiles and ports
+        assert_eq!(serde_json::to_value(&game).unwrap()["board"], fresh);
+    }
+
+    #[test]
+    fn test_check_action_takes_roads_in_either_order() {
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("oracle".into(), names);
+        let state = game.state.as_ref().unwrap();
+        let settlement = state.generate_playable_actions()[0];
+        let player_id = game.players[settlement.color() as usize].id.clone();
+        game.process_action(&player_id, settlement).unwrap();
+
+        let EnumAction::BuildRoad { color, edge_id } =
+            game.state.as_ref().unwrap().generate_playable_actions()[0]
+        else {
+            panic!("an initial road is due");
+        };
+        let reversed = EnumAction::BuildRoad {
+            color,
+            edge_id: (edge_id.1, edge_id.0),
+        };
+        assert_eq!(game.check_action(reversed), Ok(()));
+        assert_eq!(
+            game.check_action(EnumAction::EndTurn { color }),
+            Err(Illegal::WrongPrompt {
+                prompt: crate::enums::ActionPrompt::BuildInitialRoad
+            })
+        );
+    }
+
+    #[test]
+    fn test_action_page_tracks_resource_and_vp_changes() {
+        let names: Vec<String> = (0..2).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("paged".into(), names);
+        let mut rng = XorShiftRng::seed_from_u64(5);
+        for _ in 0..200 {
+            let state = game.state.as_ref().unwrap();
+            if state.winner().is_some() {
+                break;
+            }
+            let action = *state.generate_playable_actions().choose(&mut rng).unwrap();
+            let player_id = game.players[state.get_current_color() as usize].id.clone();
+            game.process_action(&player_id, action).unwrap();
+        }
+
+        let all = game.action_page(0, usize::MAX);
+        assert_eq!(all.total, game.replay.as_ref().unwrap().actions.len());
+        let page = game.action_page(120, 50);
+        assert_eq!(page.total, all.total);
+        assert_eq!(page.actions, all.actions[120..170]);
+        assert_eq!(page.actions[0].index, 120);
+        assert!(game.action_page(all.total, 50).actions.is_empty());
+
+        // Summing the deltas lands on the final hands and scores
+        let state = game.state.as_ref().unwrap();
+        for color in 0..2u8 {
+            let seat = color as usize;
+            let vps: i32 = all
+                .actions
+                .iter()
+                .map(|d| d.victory_points[seat] as i32)
+                .sum();
+            assert_eq!(vps, shown_vps(state)[seat] as i32);
+            for resource in 0..5u8 {
+                let held: i32 = all
+                    .actions
+                    .iter()
+                    .map(|d| d.resources[seat][resource as usize] as i32)
+                    .sum();
+                assert_eq!(
+                    held,
+                    state.get_player_resource_count(color, resource) as i32
+                );
+            }
+        }
+        assert!(all.actions.iter().any(|d| d.dice.is_some()));
+    }
+
+    #[test]
+    fn test_fork_replays_the_actions_before_the_fork_point() {
+        let names: Vec<String> = (0..3).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("original".into(), names);
+        game.bot_colors = vec!["blue".into()];
+        let mut rng = XorShiftRng::seed_from_u64(3);
+        let mut hashes = vec![game.state.as_ref().unwrap().compute_hash64()];
+        for _ in 0..120 {
+            let state = game.state.as_ref().unwrap();
+            let action = *state.generate_playable_actions().choose(&mut rng).unwrap();
+            let player_id = game.players[state.get_current_color() as usize].id.clone();
+            game.process_action(&player_id, action).unwrap();
+            hashes.push(game.state.as_ref().unwrap().compute_hash64());
+        }
+
+        let fork = game.fork("fork".into(), 87).unwrap();
+        assert_eq!(fork.state.as_ref().unwrap().compute_hash64(), hashes[87]);
+        assert_eq!(fork.action_page(0, usize::MAX).total, 87);
+        assert_eq!(fork.bot_colors, game.bot_colors);
+        assert_eq!(
+            fork.forked_from,
+            Some(ForkOrigin {
+                game_id: "original".into(),
+                at: 87
+            })
+        );
+        // The fork is a game of its own: it can be exported and forked again
+        assert!(fork.to_savegame("random".into()).is_ok());
+        assert!(fork.fork("again".into(), 10).is_ok());
+
+        assert!(game.fork("late".into(), 121).is_err());
+    }
+
+    #[test]
+    fn test_victory_point_cards_stay_off_the_public_score() {
+        // Seat 0 holds a settlement and a victory point card
+        let position = "B:3 1p s0@0,c1@10 0:0-1,0:1-2,1:10-11 1.0.2.1.3/0.2.0.2.3/0.0.0.0.0/0.0.0.0.0 1.0.0.0.1/0.0.0.0.0/0.0.0.0.0/0.0.0.0.0 2.0.0.0/0.0.0.0/0.0.0.0/0.0.0.0 4";
+        let state = State::from_fen(position).unwrap();
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let game = Game::from_state("hidden".into(), &names, state, 3);
+
+        let seat = &game.players[0];
+        assert_eq!(seat.victory_points, 1);
+        assert_eq!(game.players[1].victory_points, 2);
+
+        // Only seat 0 itself sees the card; an opponent only sees how many cards it holds
+        let (own, opponent) = (game.players[0].color.clone(), game.players[1].color.clone());
+        let own_view = &game.players_seen_by(&[own])[0];
+        assert!(own_view.dev_cards.contains(&DevCard::VictoryPoint));
+        let held = own_view.dev_cards.len() as u32;
+        let opponent_view = &game.players_seen_by(&[opponent])[0];
+        assert!(opponent_view.dev_cards.is_empty());
+        assert_eq!(opponent_view.hidden_dev_cards, held);
+
+        // Clients see the human seats' cards, so a bot's cards are only counted
+        let mut game = game;
+        game.bot_colors = vec![game.players[0].color.clone()];
+        let snapshot = serde_json::to_value(&game).unwrap();
+        assert_eq!(snapshot["players"][0]["dev_cards"], serde_json::json!([]));
+        assert_eq!(snapshot["players"][0]["hidden_dev_cards"], held);
+        assert_eq!(snapshot["players"][0]["victory_points"], 1);
+    }
+
+    #[test]
+    fn test_players_report_the_pieces_left_in_their_supply() {
+        // Seat 0 has a settlement and two roads, seat 1 a city and a road
+        let position = "B:3 1p s0@0,c1@10 0:0-1,0:1-2,1:10-11 1.0.2.1.3/0.2.0.2.3/0.0.0.0.0/0.0.0.0.0 1.0.0.0.1/0.0.0.0.0/0.0.0.0.0/0.0.0.0.0 2.0.0.0/0.0.0.0/0.0.0.0/0.0.0.0 4";
+        let state = State::from_fen(position).unwrap();
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let game = Game::from_state("supply".into(), &names, state, 3);
+
+        let pieces = |seat: &Player| (seat.settlements_left, seat.cities_left, seat.roads_left);
+        assert_eq!(pieces(&game.players[0]), (4, 4, 13));
+        assert_eq!(pieces(&game.players[1]), (5, 3, 14));
+        assert_eq!(pieces(&game.players[2]), (5, 4, 15));
+        assert!(game.players.iter().all(|seat| seat.ships_left == 0));
+    }
+
+    #[test]
+    fn test_buildable_masks_follow_the_initial_placement() {
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("masks".into(), names);
+        let masks = game.buildable_masks().unwrap();
+        assert_eq!(Some(masks.color), game.current_color);
+        assert_eq!(masks.node_ids.len(), 54);
+        assert!(masks.edge_ids.is_empty());
+
+        let player_id = game.players[0].id.clone();
+        let off_board = EnumAction::BuildSettlement {
+            color: 0,
+            node_id: 200,
+        };
+        assert!(game.process_action(&player_id, off_board).is_err());
+        let node_id = masks.node_ids[0];
+        game.process_action(
+            &player_id,
+            EnumAction::BuildSettlement { color: 0, node_id },
+        )
+        .unwrap();
+        let masks = game.buildable_masks().unwrap();
+        assert!(masks.node_ids.is_empty());
+        assert!(!masks.edge_ids.is_empty());
+        assert!(masks
+            .edge_ids
+            .iter()
+            .all(|&(a, b)| a == node_id || b == node_id));
+    }
+
+    #[test]
+    fn test_panicking_action_halts_only_its_game() {
+        let position = "B:3 1p s0@0,c1@10 0:0-1,0:1-2,1:10-11 1.0.2.1.3/0.2.0.2.3/0.0.0.0.0/0.0.0.0.0 1.0.0.0.1/0.0.0.0.0/0.0.0.0.0/0.0.0.0.0 2.0.0.0/0.0.0.0/0.0.0.0/0.0.0.0 4";
+        let names: Vec<String> = (0..4).map(|i| format!("Bot {}", i + 1)).collect();
+        let game =
+            |id: &str| Game::from_state(id.into(), &names, State::from_fen(position).unwrap(), 3);
+        let mut broken = game("broken");
+        let mut healthy = game("healthy");
+        let player_id = broken.players[0].id.clone();
+        let first = broken.state.as_ref().unwrap().generate_playable_actions()[0];
+        broken.process_action(&player_id, first).unwrap();
+
+        let error = broken
+            .process_action(&player_id, EnumAction::DebugPanic { color: 0 })
+            .unwrap_err();
+        assert!(error.contains("DebugPanic"));
+        let fault = broken.fault.clone().unwrap();
+        assert_eq!(fault.version, 1);
+        assert_eq!(fault.recent_actions.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&broken).unwrap()["fault"]["action"],
+            "DebugPanic { color: 0 }"
+        );
+
+        // Nothing more is applied to the halted game, while others carry on
+        let next = broken.state.as_ref().unwrap().generate_playable_actions()[0];
+        assert!(broken.process_action(&player_id, next).is_err());
+        assert_eq!(broken.version, 1);
+        healthy.process_action(&player_id, first).unwrap();
+        assert!(healthy.fault.is_none());
+    }
+
+    #[test]
+    fn test_savegame_restores_offers_move_generation_does_not_list() {
+        let names: Vec<String> = (0..2).map(|i| format!("Bot {}", i + 1)).collect();
+        let mut game = Game::new("original".into(), names);
+        let mut rng = XorShiftRng::seed_from_u64(5);
+        let offer = loop {
+            let state = game.state.as_ref().unwrap();
+            let actions = state.generate_playable_actions();
+            if let Some(&EnumAction::OfferTrade {
+                color,
+                trade: (give, _),
+            }) = actions
+                .iter()
+                .find(|a| matches!(a, EnumAction::OfferTrade { .. }))
+            {
+                // Two cards of whatever is not offered, where generated offers ask for one
+                let wanted = (give.iter().position(|&n| n > 0).unwrap() + 1) % 5;
+                let mut take = [0; 5];
+                take[wanted] = 2;
+                break EnumAction::OfferTrade {
+                    color,
+                    trade: (give, take),
+                };
+            }
+            let action = *actions.choose(&mut rng).unwrap();
+            let player_id = game.players[state.get_current_color() as usize].id.clone();
+            game.process_action(&player_id, action).unwrap();
+        };
+        let state = game.state.as_ref().unwrap();
+        assert!(!state.generate_playable_actions().contains(&offer));
+        let player_id = game.players[offer.color() as usize].id.clone();
+        game.process_action(&player_id, offer).unwrap();
+
+        let save = game.to_savegame("random".into()).unwrap();
+        let restored = Game::from_savegame("restored".into(), &save).unwrap();
+        assert_eq!(
+            restored.state.as_ref().unwrap().compute_hash64(),
+            game.state.as_ref().unwrap().compute_hash64()
+        );
+    }
+
+    #[test]
+    fn test_savegame_with_illegal_action_is_rejected() {
+        let names: Vec<String> = (0..2).map(|i| format!("Bot {}", i + 1)).collect();
+        let game = Game::new("original".into(), names);
+        let mut save = game.to_savegame("random".into()).unwrap();
+        save.replay.actions.push(EnumAction::EndTurn { color: 1 });
+        assert!(Game::from_savegame("restored".into(), &save).is_err());
+    }
+}
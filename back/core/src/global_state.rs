@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::{
+    enums::{MapType, Resource},
+    map_template::{MapTemplate, TileSlot},
+    ordered_hashmap::OrderedHashMap,
+};
+
+#[derive(Debug)]
+pub struct GlobalState {
+    pub mini_map_template: MapTemplate,
+    pub base_map_template: MapTemplate,
+    pub extended_map_template: MapTemplate,
+    pub seafarers_map_template: MapTemplate,
+    pub dice_probas: HashMap<u8, f64>,
+}
+
+fn build_dice_probas() -> HashMap<u8, f64> {
+    let mut probas: HashMap<u8, f64> = HashMap::new();
+
+    // Iterate over two dice rolls
+    for i in 1..=6 {
+        for j in 1..=6 {
+            let sum = i + j;
+            let counter = probas.entry(sum).or_insert(0.0);
+            *counter += 1.0 / 36.0;
+        }
+    }
+
+    probas
+}
+
+impl Default for GlobalState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static SHARED: OnceLock<GlobalState> = OnceLock::new();
+
+impl GlobalState {
+    /// Process-wide instance; the templates never change, so there is no need to build
+    /// them per game
+    pub fn shared() -> &'static GlobalState {
+        SHARED.get_or_init(GlobalState::new)
+    }
+
+    pub fn map_template(&self, map_type: &MapType) -> &MapTemplate {
+        match map_type {
+            MapType::Mini => &self.mini_map_template,
+            MapType::Base | MapType::Tournament => &self.base_map_template,
+            MapType::Extended => &self.extended_map_template,
+            MapType::Seafarers => &self.seafarers_map_template,
+        }
+    }
+
+    pub fn new() -> Self {
+        // Mini Map Template
+        let mut topology = OrderedHashMap::new();
+        // center
+        topology.insert((0, 0, 0), TileSlot::Land);
+        // first layer
+        topology.insert((1, -1, 0), TileSlot::Land);
+        topology.insert((0, -1, 1), TileSlot::Land);
+        topology.insert((-1, 0, 1), TileSlot::Land);
+        topology.insert((-1, 1, 0), TileSlot::Land);
+        topology.insert((0, 1, -1), TileSlot::Land);
+        topology.insert((1, 0, -1), TileSlot::Land);
+        // second layer
+        topology.insert((2, -2, 0), TileSlot::Water);
+        topology.insert((1, -2, 1), TileSlot::Water);
+        topology.insert((0, -2, 2), TileSlot::Water);
+        topology.insert((-1, -1, 2), TileSlot::Water);
+        topology.insert((-2, 0, 2), TileSlot::Water);
+        topology.insert((-2, 1, 1), TileSlot::Water);
+        topology.insert((-2, 2, 0), TileSlot::Water);
+        topology.insert((-1, 2, -1), TileSlot::Water);
+        topology.insert((0, 2, -2), TileSlot::Water);
+        topology.insert((1, 1, -2), TileSlot::Water);
+        topology.insert((2, 0, -2), TileSlot::Water);
+        topology.insert((2, -1, -1), TileSlot::Water);
+        let mini_map_template = MapTemplate {
+            numbers: vec![3, 4, 5, 6, 8, 9, 10],
+            ports: vec![],
+            tiles: vec![
+                Some(Resource::Wood),
+                None,
+                Some(Resource::Brick),
+                Some(Resource::Sheep),
+                Some(Resource::Wheat),
+                Some(Resource::Wheat),
+                Some(Resource::Ore),
+            ],
+            ships: false,
+            gold_fields: 0,
+            topology,
+        };
+        // Base Map Template
+        let mut topology = OrderedHashMap::new();
+        // center
+        topology.insert((0, 0, 0), TileSlot::Land);
+        // first layer
+        topology.insert((1, -1, 0), TileSlot::Land);
+        topology.insert((0, -1, 1), TileSlot::Land);
+        topology.insert((-1, 0, 1), TileSlot::Land);
+        topology.insert((-1, 1, 0), TileSlot::Land);
+        topology.insert((0, 1, -1), TileSlot::Land);
+        topology.insert((1, 0, -1), TileSlot::Land);
+        // second layer
+        topology.insert((2, -2, 0), TileSlot::Land);
+        topology.insert((1, -2, 1), TileSlot::Land);
+        topology.insert((0, -2, 2), TileSlot::Land);
+        topology.insert((-1, -1, 2), TileSlot::Land);
+        topology.insert((-2, 0, 2), TileSlot::Land);
+        topology.insert((-2, 1, 1), TileSlot::Land);
+        topology.insert((-2, 2, 0), TileSlot::Land);
+        topology.insert((-1, 2, -1), TileSlot::Land);
+        topology.insert((0, 2, -2), TileSlot::Land);
+        topology.insert((1, 1, -2), TileSlot::Land);
+        topology.insert((2, 0, -2), TileSlot::Land);
+        topology.insert((2, -1, -1), TileSlot::Land);
+        // third layer (outer water/port ring)
+        topology.insert((3, -3, 0), TileSlot::WPort);
+        topology.insert((2, -3, 1), TileSlot::Water);
+        topology.insert((1, -3, 2), TileSlot::WPort);
+        topology.insert((0, -3, 3), TileSlot::Water);
+        topology.insert((-1, -2, 3), TileSlot::NWPort);
+        topology.insert((-2, -1, 3), TileSlot::Water);
+        topology.insert((-3, 0, 3), TileSlot::NEPort);
+        topology.insert((-3, 1, 2), TileSlot::Water);
+        topology.insert((-3, 2, 1), TileSlot::EPort);
+        topology.insert((-3, 3, 0), TileSlot::Water);
+        topology.insert((-2, 3, -1), TileSlot::EPort);
+        topology.insert((-1, 3, -2), TileSlot::Water);
+        topology.insert((0, 3, -3), TileSlot::SEPort);
+        topology.insert((1, 2, -3), TileSlot::Water);
+        topology.insert((2, 1, -3), TileSlot::SWPort);
+        topology.insert((3, 0, -3), TileSlot::Water);
+        topology.insert((3, -1, -2), TileSlot::SWPort);
+        topology.insert((3, -2, -1), TileSlot::Water);
+
+        let base_map_template = MapTemplate {
+            numbers: vec![2, 3, 3, 4, 4, 5, 5, 6, 6, 8, 8, 9, 9, 10, 10, 11, 11, 12],
+            ports: vec![
+                Some(Resource::Wood),
+                None,
+                Some(Resource::Sheep),
+                Some(Resource::Wheat),
+                Some(Resource::Ore),
+                Some(Resource::Brick),
+                None,
+                None,
+                None,
+            ],
+            tiles: vec![
+                // Four wood tiles
+                Some(Resource::Wood),
+                Some(Resource::Wood),
+                Some(Resource::Wood),
+                Some(Resource::Wood),
+                // Three brick tiles
+                Some(Resource::Brick),
+                Some(Resource::Brick),
+                Some(Resource::Brick),
+                // Four sheep tiles
+                Some(Resource::Sheep),
+                Some(Resource::Sheep),
+                Some(Resource::Sheep),
+                Some(Resource::Sheep),
+                // Four wheat tiles
+                Some(Resource::Wheat),
+                Some(Resource::Wheat),
+                Some(Resource::Wheat),
+                Some(Resource::Wheat),
+                // Three ore tiles
+                Some(Resource::Ore),
+                Some(Resource::Ore),
+                Some(Resource::Ore),
+                // One desert
+                None,
+            ],
+            ships: false,
+            gold_fields: 0,
+            topology,
+        };
+
+        // Extended Map Template (5–6 players): land rows of 3, 4, 5, 6, 5, 4 and 3 tiles
+        let mut topology = OrderedHashMap::new();
+        // row 1
+        topology.insert((0, 3, -3), TileSlot::Land);
+        topology.insert((1, 2, -3), TileSlot::Land);
+        topology.insert((2, 1, -3), TileSlot::Land);
+        // row 2
+        topology.insert((-1, 3, -2), TileSlot::Land);
+        topology.insert((0, 2, -2), TileSlot::Land);
+        topology.insert((1, 1, -2), TileSlot::Land);
+        topology.insert((2, 0, -2), TileSlot::Land);
+        // row 3
+        topology.insert((-2, 3, -1), TileSlot::Land);
+        topology.insert((-1, 2, -1), TileSlot::Land);
+        topology.insert((0, 1, -1), TileSlot::Land);
+        topology.insert((1, 0, -1), TileSlot::Land);
+        topology.insert((2, -1, -1), TileSlot::Land);
+        // row 4
+        topology.insert((-3, 3, 0), TileSlot::Land);
+        topology.insert((-2, 2, 0), TileSlot::Land);
+        topology.insert((-1, 1, 0), TileSlot::Land);
+        topology.insert((0, 0, 0), TileSlot::Land);
+        topology.insert((1, -1, 0), TileSlot::Land);
+        topology.insert((2, -2, 0), TileSlot::Land);
+        // row 5
+        topology.insert((-3, 2, 1), TileSlot::Land);
+        topology.insert((-2, 1, 1), TileSlot::Land);
+        topology.insert((-1, 0, 1), TileSlot::Land);
+        topology.insert((0, -1, 1), TileSlot::Land);
+        topology.insert((1, -2, 1), TileSlot::Land);
+        // row 6
+        topology.insert((-3, 1, 2), TileSlot::Land);
+        topology.insert((-2, 0, 2), TileSlot::Land);
+        topology.insert((-1, -1, 2), TileSlot::Land);
+        topology.insert((0, -2, 2), TileSlot::Land);
+        // row 7
+        topology.insert((-3, 0, 3), TileSlot::Land);
+        topology.insert((-2, -1, 3), TileSlot::Land);
+        topology.insert((-1, -2, 3), TileSlot::Land);
+        // outer water/port ring
+        topology.insert((-4, 4, 0), TileSlot::Water);
+        topology.insert((-3, 4, -1), TileSlot::SEPort);
+        topology.insert((-2, 4, -2), TileSlot::Water);
+        topology.insert((-1, 4, -3), TileSlot::SEPort);
+        topology.insert((0, 4, -4), TileSlot::Water);
+        topology.insert((1, 3, -4), TileSlot::SWPort);
+        topology.insert((2, 2, -4), TileSlot::Water);
+        topology.insert((3, 1, -4), TileSlot::SWPort);
+        topology.insert((3, 0, -3), TileSlot::Water);
+        topology.insert((3, -1, -2), TileSlot::WPort);
+        topology.insert((3, -2, -1), TileSlot::Water);
+        topology.insert((3, -3, 0), TileSlot::WPort);
+        topology.insert((2, -3, 1), TileSlot::Water);
+        topology.insert((1, -3, 2), TileSlot::NWPort);
+        topology.insert((0, -3, 3), TileSlot::Water);
+        topology.insert((-1, -3, 4), TileSlot::NWPort);
+        topology.insert((-2, -2, 4), TileSlot::Water);
+        topology.insert((-3, -1, 4), TileSlot::NEPort);
+        topology.insert((-4, 0, 4), TileSlot::Water);
+        topology.insert((-4, 1, 3), TileSlot::EPort);
+        topology.insert((-4, 2, 2), TileSlot::Water);
+        topology.insert((-4, 3, 1), TileSlot::EPort);
+
+        let extended_map_template = MapTemplate {
+            numbers: vec![
+                2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 6, 8, 8, 8, 9, 9, 9, 10, 10, 10, 11, 11, 11,
+                12, 12,
+            ],
+            ports: vec![
+                Some(Resource::Wood),
+                None,
+                Some(Resource::Sheep),
+                Some(Resource::Wheat),
+                Some(Resource::Ore),
+                Some(Resource::Brick),
+                None,
+                Some(Resource::Sheep),
+                None,
+                None,
+                None,
+            ],
+            tiles: [
+                (Some(Resource::Wood), 6),
+                (Some(Resource::Brick), 5),
+                (Some(Resource::Sheep), 6),
+                (Some(Resource::Wheat), 6),
+                (Some(Resource::Ore), 5),
+                (None, 2),
+            ]
+            .into_iter()
+            .flat_map(|(resource, count)| std::iter::repeat_n(resource, count))
+            .collect(),
+            ships: false,
+            gold_fields: 0,
+            topology,
+        };
+
+        // Seafarers "Heading for New Shores" (3–4 players): the base island and harbors,
+        // then two four-tile islands east of a strait, in a frame of 7 rows of 11 hexes.
+        // Land goes first so that land node ids stay low (the base island keeps its ids).
+        let mut topology = OrderedHashMap::new();
+        for (&coordinate, &slot) in base_map_template.topology.iter() {
+            if slot == TileSlot::Land {
+                topology.insert(coordinate, slot);
+            }
+        }
+        // north island
+        topology.insert((5, -3, -2), TileSlot::Land);
+        topology.insert((6, -4, -2), TileSlot::Land);
+        topology.insert((5, -4, -1), TileSlot::Land);
+        topology.insert((6, -5, -1), TileSlot::Land);
+        // south island
+        topology.insert((4, -5, 1), TileSlot::Land);
+        topology.insert((5, -6, 1), TileSlot::Land);
+        topology.insert((3, -5, 2), TileSlot::Land);
+        topology.insert((4, -6, 2), TileSlot::Land);
+        for (&coordinate, &slot) in base_map_template.topology.iter() {
+            if slot != TileSlot::Land {
+                topology.insert(coordinate, slot);
+            }
+        }
+        // open sea filling the rest of the frame, row by row from the top
+        for z in -3i8..=3 {
+            let first_x = -2 - (z + 3) / 2;
+            for x in first_x..first_x + 11 {
+                let coordinate = (x, -x - z, z);
+                if !topology.contains_key(&coordinate) {
+                    topology.insert(coordinate, TileSlot::Water);
+                }
+            }
+        }
+
+        let seafarers_map_template = MapTemplate {
+            numbers: [
+                base_map_template.numbers.clone(),
+                vec![3, 4, 5, 6, 8, 9, 10, 11],
+            ]
+            .concat(),
+            ports: base_map_template.ports.clone(),
+            tiles: [
+                base_map_template.tiles.clone(),
+                vec![
+                    Some(Resource::Wood),
+                    Some(Resource::Brick),
+                    Some(Resource::Sheep),
+                    Some(Resource::Wheat),
+                    Some(Resource::Ore),
+                    Some(Resource::Ore),
+                ],
+            ]
+            .concat(),
+            ships: true,
+            gold_fields: 2,
+            topology,
+        };
+
+        let dice_probas = build_dice_probas();
+
+        Self {
+            mini_map_template,
+            base_map_template,
+            extended_map_template,
+            seafarers_map_template,
+            dice_probas,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_state() {
+        let global_state = GlobalState::new();
+        assert_eq!(global_state.mini_map_template.numbers.len(), 7);
+        assert_eq!(global_state.base_map_template.numbers.len(), 18);
+        assert_eq!(global_state.mini_map_template.topology.len(), 19);
+
+        let extended = &global_state.extended_map_template;
+        let land = extended
+            .topology
+            .iter()
+            .filter(|(_, &slot)| slot == TileSlot::Land)
+            .count();
+        assert_eq!(land, extended.tiles.len());
+        assert_eq!(extended.numbers.len(), land - 2);
+        assert_eq!(extended.topology.len() - land, 22);
+        assert_eq!(
+            extended.ports.len(),
+            extended
+                .topology
+                .iter()
+                .filter(|(_, &slot)| !matches!(slot, TileSlot::Land | TileSlot::Water))
+                .count()
+        );
+    }
+}
@@ -0,0 +1,76 @@
+// Catan engine - rules, board, bots and simulation, with no web server attached.
+//
+// Bot authors, tools and the WASM build depend on this crate alone; catan-server adds
+// the HTTP/WebSocket layer on top and re-exports these modules under the same paths.
+// Only `prelude` is a stable API. The modules below it stay public for catan-server and
+// the bundled tools but are hidden from the docs and may change in any release.
+
+pub mod prelude;
+
+// Core game data structures and enums
+#[doc(hidden)]
+pub mod actions;
+#[doc(hidden)]
+pub mod commands;
+#[doc(hidden)]
+pub mod compact_actions;
+#[doc(hidden)]
+pub mod enums;
+#[doc(hidden)]
+pub mod errors;
+#[doc(hidden)]
+pub mod game;
+#[doc(hidden)]
+pub mod state;
+#[doc(hidden)]
+pub mod state_vector;
+
+// Game logic implementation
+#[doc(hidden)]
+pub mod audit;
+#[doc(hidden)]
+pub mod auto_pass;
+#[doc(hidden)]
+pub mod conformance;
+#[doc(hidden)]
+pub mod deck_slices;
+#[doc(hidden)]
+pub mod dice_stats;
+#[doc(hidden)]
+pub mod global_state;
+#[doc(hidden)]
+pub mod map_instance;
+#[doc(hidden)]
+pub mod map_template;
+
+#[doc(hidden)]
+pub mod ordered_hashmap;
+#[doc(hidden)]
+pub mod pace;
+#[doc(hidden)]
+pub mod palette;
+#[doc(hidden)]
+pub mod placement_stats;
+#[doc(hidden)]
+pub mod player;
+#[doc(hidden)]
+pub mod players;
+#[doc(hidden)]
+pub mod replay;
+#[doc(hidden)]
+pub mod simulation;
+#[doc(hidden)]
+pub mod think_time;
+
+// Version information
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Revision of the game rules the engine plays by. Bump it whenever a rules fix can
+/// change how a recorded game plays out, so replays and clients can tell which
+/// behavior they were produced under.
+///
+/// 1. Original rules
+/// 2. Longest road counts roads that end at opponents' buildings on both sides
+/// 3. A development card cannot be played the turn it was bought, and a 7 takes half of an
+///    odd hand rounded down
+pub const RULES_REVISION: u32 = 3;
@@ -1,9 +1,12 @@
-use crate::enums::Resource;
+use crate::enums::{MapType, Resource};
+use crate::global_state::GlobalState;
 use crate::map_template::{add_coordinates, Coordinate, MapTemplate, TileSlot};
+use crate::state_vector::NUM_RESOURCES;
 use rand::rngs::StdRng;
 use rand::{seq::SliceRandom, SeedableRng};
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
 pub type NodeId = u8;
 pub type EdgeId = (NodeId, NodeId);
@@ -93,6 +96,14 @@ pub struct LandTile {
     pub(crate) hexagon: Hexagon,
     pub(crate) resource: Option<Resource>,
     pub(crate) number: Option<u8>,
+    /// Seafarers gold field: no resource of its own, pays what the owner picks
+    pub(crate) gold: bool,
+}
+
+impl LandTile {
+    pub(crate) fn is_desert(&self) -> bool {
+        self.resource.is_none() && !self.gold
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -140,9 +151,55 @@ pub struct MapInstance {
     pub land_edges: HashSet<EdgeId>,
     pub node_neighbors: HashMap<NodeId, Vec<NodeId>>,
     pub edge_neighbors: HashMap<NodeId, Vec<EdgeId>>,
+    /// Edges ships can sail, as (low, high): between two hexes, at least one of them sea.
+    /// Coastal edges are both land and sea edges. Empty on boards without ships.
+    pub sea_edges: HashSet<EdgeId>,
+
+    // Lookup tables indexed by node id, built once from the maps above so the accessors
+    // used in search and longest-road are a bounds check and a slice
+    neighbor_table: Vec<Adjacency<NodeId>>,
+    edge_table: Vec<Adjacency<EdgeId>>,
+    sea_edge_table: Vec<Adjacency<EdgeId>>,
+    tile_table: Vec<Vec<LandTile>>,
+    // Dice probability of each resource per node, as one flat [node][resource] matrix
+    production_table: Vec<f64>,
+    seed: u64,
+}
+
+/// Neighbors or edges of one node (never more than three), stored inline
+#[derive(Debug, Clone, Copy)]
+struct Adjacency<T: Copy> {
+    items: [T; 3],
+    len: u8,
+}
+
+impl<T: Copy + Ord> Adjacency<T> {
+    fn collect(items: &[T], fill: T) -> Self {
+        let mut adjacency = Self {
+            items: [fill; 3],
+            len: 0,
+        };
+        for &item in items {
+            if !adjacency.as_slice().contains(&item) {
+                adjacency.items[adjacency.len as usize] = item;
+                adjacency.len += 1;
+            }
+        }
+        adjacency.items[..adjacency.len as usize].sort();
+        adjacency
+    }
+
+    fn as_slice(&self) -> &[T] {
+        &self.items[..self.len as usize]
+    }
 }
 
 impl MapInstance {
+    /// Seed the tile and number layout was shuffled with
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
     pub fn get_tiles(&self) -> &HashMap<Coordinate, Tile> {
         &self.tiles
     }
@@ -167,6 +224,21 @@ impl MapInstance {
         &self.node_production
     }
 
+    /// Dice probability of each resource, in `Resource` order, paying out at the node;
+    /// all zero where nothing is produced
+    pub fn get_node_production_row(&self, node_id: NodeId) -> &[f64] {
+        let start = node_id as usize * NUM_RESOURCES;
+        self.production_table
+            .get(start..start + NUM_RESOURCES)
+            .unwrap_or(&[0.0; NUM_RESOURCES])
+    }
+
+    /// Production rows of all nodes back to back: resource `r` of node `n` is at
+    /// `n * NUM_RESOURCES + r`
+    pub fn get_production_table(&self) -> &[f64] {
+        &self.production_table
+    }
+
     pub fn get_tile(&self, coordinate: Coordinate) -> Option<&Tile> {
         self.tiles.get(&coordinate)
     }
@@ -175,22 +247,35 @@ impl MapInstance {
         self.land_tiles.get(&coordinate)
     }
 
-    pub fn get_neighbor_nodes(&self, node_id: NodeId) -> Vec<NodeId> {
-        self.node_neighbors
-            .get(&node_id)
-            .cloned()
+    /// Adjacent nodes, in ascending id order
+    pub fn get_neighbor_nodes(&self, node_id: NodeId) -> &[NodeId] {
+        self.neighbor_table
+            .get(node_id as usize)
+            .map(Adjacency::as_slice)
             .unwrap_or_else(|| panic!("Unknown node_id {node_id} in get_neighbor_nodes"))
     }
 
-    pub fn get_neighbor_edges(&self, node_id: NodeId) -> Vec<EdgeId> {
-        self.edge_neighbors
-            .get(&node_id)
-            .cloned()
+    /// Edges touching the node, in ascending order
+    pub fn get_neighbor_edges(&self, node_id: NodeId) -> &[EdgeId] {
+        self.edge_table
+            .get(node_id as usize)
+            .map(Adjacency::as_slice)
             .unwrap_or_else(|| panic!("Unknown node_id {node_id} in get_neighbor_edges"))
     }
 
-    pub fn get_adjacent_tiles(&self, node_id: NodeId) -> Option<&Vec<LandTile>> {
-        self.adjacent_land_tiles.get(&node_id)
+    /// Sea edges touching the node, in ascending order; empty away from the sea
+    pub fn get_sea_edges(&self, node_id: NodeId) -> &[EdgeId] {
+        self.sea_edge_table
+            .get(node_id as usize)
+            .map_or(&[], Adjacency::as_slice)
+    }
+
+    /// Land tiles around the node, in tile id order; None off the land
+    pub fn get_adjacent_tiles(&self, node_id: NodeId) -> Option<&[LandTile]> {
+        self.tile_table
+            .get(node_id as usize)
+            .filter(|tiles| !tiles.is_empty())
+            .map(Vec::as_slice)
     }
 
     pub fn get_tiles_by_number(&self, number: u8) -> Vec<&LandTile> {
@@ -201,10 +286,42 @@ impl MapInstance {
     }
 }
 
+/// Boards `MapInstance::shared` keeps before dropping the ones no game uses any more
+const SHARED_MAP_CAPACITY: usize = 256;
+
+/// Interned boards keyed by (template address, seed); Base and Tournament share one
+type SharedMaps = HashMap<(usize, u64), Arc<MapInstance>>;
+
+static SHARED_MAPS: OnceLock<Mutex<SharedMaps>> = OnceLock::new();
+
 impl MapInstance {
+    /// Board for `map_type` shuffled with `seed`, built once per process and shared by
+    /// every game (and every state clone) that uses it
+    pub fn shared(map_type: &MapType, seed: u64) -> Arc<MapInstance> {
+        let global_state = GlobalState::shared();
+        let template = global_state.map_template(map_type);
+        let key = (std::ptr::from_ref(template) as usize, seed);
+        let mut maps = SHARED_MAPS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(map) = maps.get(&key) {
+            return Arc::clone(map);
+        }
+        if maps.len() >= SHARED_MAP_CAPACITY {
+            maps.retain(|_, map| Arc::strong_count(map) > 1);
+        }
+        let map = Arc::new(MapInstance::new(template, &global_state.dice_probas, seed));
+        maps.insert(key, Arc::clone(&map));
+        map
+    }
+
     pub fn new(map_template: &MapTemplate, dice_probas: &HashMap<u8, f64>, seed: u64) -> Self {
         let tiles = Self::initialize_tiles(map_template, seed);
-        Self::from_tiles(tiles, dice_probas)
+        Self {
+            seed,
+            ..Self::from_tiles(tiles, dice_probas, map_template.ships)
+        }
     }
 
     fn initialize_tiles(map_template: &MapTemplate, seed: u64) -> HashMap<Coordinate, Tile> {
@@ -213,7 +330,13 @@ impl MapInstance {
         // Shuffle the numbers, tiles, and ports
         let mut shuffled_numbers = map_template.numbers.clone();
         shuffled_numbers.shuffle(&mut rng);
-        let mut shuffled_tiles = map_template.tiles.clone();
+        // Gold fields go last, so that maps without them shuffle as before
+        let mut shuffled_tiles: Vec<(Option<Resource>, bool)> = map_template
+            .tiles
+            .iter()
+            .map(|&resource| (resource, false))
+            .chain(std::iter::repeat_n((None, true), map_template.gold_fields))
+            .collect();
         shuffled_tiles.shuffle(&mut rng);
         let mut shuffled_ports = map_template.ports.clone();
         shuffled_ports.shuffle(&mut rng);
@@ -231,13 +354,14 @@ impl MapInstance {
             let hexagon = Hexagon { nodes, edges };
 
             if tile_slot == TileSlot::Land {
-                let resource = shuffled_tiles.pop().unwrap();
-                if resource.is_none() {
+                let (resource, gold) = shuffled_tiles.pop().unwrap();
+                if resource.is_none() && !gold {
                     let land_tile = LandTile {
                         id: tile_autoinc,
                         hexagon: hexagon.clone(),
                         resource,
                         number: None,
+                        gold,
                     };
                     tiles.insert(coordinate, Tile::Land(land_tile));
                 } else {
@@ -247,6 +371,7 @@ impl MapInstance {
                         hexagon: hexagon.clone(),
                         resource,
                         number: Some(number),
+                        gold,
                     };
                     tiles.insert(coordinate, Tile::Land(land_tile));
                 }
@@ -283,7 +408,11 @@ impl MapInstance {
         tiles
     }
 
-    fn from_tiles(tiles: HashMap<Coordinate, Tile>, dice_probas: &HashMap<u8, f64>) -> Self {
+    fn from_tiles(
+        tiles: HashMap<Coordinate, Tile>,
+        dice_probas: &HashMap<u8, f64>,
+        ships: bool,
+    ) -> Self {
         let mut land_tiles: HashMap<Coordinate, LandTile> = HashMap::new();
         let mut port_nodes: HashMap<NodeId, Option<Resource>> = HashMap::new();
         let mut adjacent_land_tiles: HashMap<NodeId, Vec<LandTile>> = HashMap::new();
@@ -297,6 +426,7 @@ impl MapInstance {
         for (&coordinate, tile) in tiles.iter() {
             if let Tile::Land(land_tile) = tile {
                 land_tiles.insert(coordinate, land_tile.clone());
+                // Gold fields pay no fixed resource, so they add no production
                 let is_desert = land_tile.resource.is_none();
 
                 land_tile.hexagon.nodes.values().for_each(|&node_id| {
@@ -349,6 +479,52 @@ impl MapInstance {
             }
         }
 
+        let sea_edges = if ships {
+            Self::find_sea_edges(&tiles)
+        } else {
+            HashSet::new()
+        };
+        let mut sea_edge_neighbors: HashMap<NodeId, Vec<EdgeId>> = HashMap::new();
+        for &edge_id in &sea_edges {
+            sea_edge_neighbors
+                .entry(edge_id.0)
+                .or_default()
+                .push(edge_id);
+            sea_edge_neighbors
+                .entry(edge_id.1)
+                .or_default()
+                .push(edge_id);
+        }
+
+        // Open-sea nodes get (empty) land entries too, so walks over ships can ask
+        let table_len = land_nodes
+            .iter()
+            .chain(sea_edge_neighbors.keys())
+            .max()
+            .map_or(0, |&max| max as usize + 1);
+        let mut neighbor_table = vec![Adjacency::collect(&[], 0); table_len];
+        let mut edge_table = vec![Adjacency::collect(&[], (0, 0)); table_len];
+        let mut sea_edge_table = vec![Adjacency::collect(&[], (0, 0)); table_len];
+        let mut tile_table = vec![Vec::new(); table_len];
+        let mut production_table = vec![0.0; table_len * NUM_RESOURCES];
+        for (&node_id, production) in &node_production {
+            let row = node_id as usize * NUM_RESOURCES;
+            for (&resource, &proba) in production {
+                production_table[row + resource as usize] = proba;
+            }
+        }
+        for (&node_id, edges) in &sea_edge_neighbors {
+            sea_edge_table[node_id as usize] = Adjacency::collect(edges, (0, 0));
+        }
+        for &node_id in &land_nodes {
+            let index = node_id as usize;
+            neighbor_table[index] = Adjacency::collect(&node_neighbors[&node_id], 0);
+            edge_table[index] = Adjacency::collect(&edge_neighbors[&node_id], (0, 0));
+            let mut tiles = adjacent_land_tiles[&node_id].clone();
+            tiles.sort_by_key(|tile| tile.id);
+            tile_table[index] = tiles;
+        }
+
         Self {
             tiles,
             land_tiles,
@@ -360,8 +536,37 @@ impl MapInstance {
             land_edges,
             node_neighbors,
             edge_neighbors,
+            sea_edges,
+            neighbor_table,
+            edge_table,
+            sea_edge_table,
+            tile_table,
+            production_table,
+            seed: 0,
         }
     }
+
+    /// Edges shared by two hexes of the board where one of them is water or a harbor
+    fn find_sea_edges(tiles: &HashMap<Coordinate, Tile>) -> HashSet<EdgeId> {
+        let mut sides: HashMap<EdgeId, (u8, bool)> = HashMap::new();
+        for tile in tiles.values() {
+            let (hexagon, is_sea) = match tile {
+                Tile::Land(land_tile) => (&land_tile.hexagon, false),
+                Tile::Port(port_tile) => (&port_tile.hexagon, true),
+                Tile::Water(water_tile) => (&water_tile.hexagon, true),
+            };
+            for &(a, b) in hexagon.edges.values() {
+                let side = sides.entry((a.min(b), a.max(b))).or_default();
+                side.0 += 1;
+                side.1 |= is_sea;
+            }
+        }
+        sides
+            .into_iter()
+            .filter(|&(_, (hexes, touches_sea))| hexes == 2 && touches_sea)
+            .map(|(edge, _)| edge)
+            .collect()
+    }
 }
 
 fn get_noderefs_from_port_direction(direction: Direction) -> (NodeRef, NodeRef) {
@@ -509,6 +714,13 @@ impl Clone for MapInstance {
             land_edges: self.land_edges.clone(),
             node_neighbors: self.node_neighbors.clone(),
             edge_neighbors: self.edge_neighbors.clone(),
+            sea_edges: self.sea_edges.clone(),
+            neighbor_table: self.neighbor_table.clone(),
+            edge_table: self.edge_table.clone(),
+            sea_edge_table: self.sea_edge_table.clone(),
+            tile_table: self.tile_table.clone(),
+            production_table: self.production_table.clone(),
+            seed: self.seed,
         }
     }
 }
@@ -635,6 +847,134 @@ mod tests {
         assert_node_value(&map_instance, (-1, 0, 1), NodeRef::South, 13);
     }
 
+    #[test]
+    fn test_adjacency_tables_match_maps() {
+        let map_instance = MapInstance::shared(&MapType::Base, 0);
+        for &node_id in map_instance.land_nodes() {
+            let neighbors = map_instance.get_neighbor_nodes(node_id);
+            assert!((2..=3).contains(&neighbors.len()));
+            assert!(neighbors.windows(2).all(|pair| pair[0] < pair[1]));
+            let mut expected = map_instance.node_neighbors[&node_id].clone();
+            expected.sort();
+            expected.dedup();
+            assert_eq!(neighbors, &expected[..]);
+
+            let edges = map_instance.get_neighbor_edges(node_id);
+            assert_eq!(edges.len(), neighbors.len());
+            assert!(edges.iter().all(|&(a, b)| a == node_id || b == node_id));
+
+            let tiles = map_instance.get_adjacent_tiles(node_id).unwrap();
+            assert_eq!(
+                tiles.len(),
+                map_instance.adjacent_land_tiles[&node_id].len()
+            );
+
+            let row = map_instance.get_node_production_row(node_id);
+            for resource in [
+                Resource::Wood,
+                Resource::Brick,
+                Resource::Sheep,
+                Resource::Wheat,
+                Resource::Ore,
+            ] {
+                let expected = map_instance.node_production[&node_id]
+                    .get(&resource)
+                    .copied()
+                    .unwrap_or(0.0);
+                assert_eq!(row[resource as usize], expected);
+            }
+        }
+        assert_eq!(map_instance.get_production_table().len() % NUM_RESOURCES, 0);
+        assert_eq!(map_instance.get_node_production_row(u8::MAX), [0.0; 5]);
+    }
+
+    #[test]
+    fn test_extended_map() {
+        let map_instance = MapInstance::shared(&MapType::Extended, 3);
+        assert_eq!(map_instance.land_tiles.len(), 30);
+        let deserts = map_instance
+            .land_tiles
+            .values()
+            .filter(|tile| tile.resource.is_none())
+            .count();
+        assert_eq!(deserts, 2);
+        // Eleven harbors, none sharing a node
+        assert_eq!(map_instance.get_port_nodes().len(), 22);
+        assert!(map_instance
+            .get_port_nodes()
+            .keys()
+            .all(|node| map_instance.land_nodes().contains(node)));
+        for &node_id in map_instance.land_nodes() {
+            assert!((2..=3).contains(&map_instance.get_neighbor_nodes(node_id).len()));
+        }
+        assert!(!Arc::ptr_eq(
+            &map_instance,
+            &MapInstance::shared(&MapType::Base, 3)
+        ));
+    }
+
+    #[test]
+    fn test_seafarers_map() {
+        let map_instance = MapInstance::shared(&MapType::Seafarers, 3);
+        // The base island and two islands of four hexes
+        assert_eq!(map_instance.land_tiles.len(), 27);
+        assert_eq!(map_instance.get_port_nodes().len(), 18);
+        // Every sea edge touches water, and coastal ones are land edges too
+        assert!(!map_instance.sea_edges.is_empty());
+        assert!(map_instance.sea_edges.iter().any(|&(a, b)| {
+            map_instance.land_edges.contains(&(a, b)) || map_instance.land_edges.contains(&(b, a))
+        }));
+        for &(a, b) in &map_instance.sea_edges {
+            assert!(a < b);
+            assert!(map_instance.get_sea_edges(a).contains(&(a, b)));
+            assert!(map_instance.get_sea_edges(b).contains(&(a, b)));
+        }
+        // Two numbered gold fields and the one desert
+        let gold: Vec<_> = map_instance
+            .land_tiles
+            .values()
+            .filter(|tile| tile.gold)
+            .collect();
+        assert_eq!(gold.len(), 2);
+        assert!(gold
+            .iter()
+            .all(|tile| tile.resource.is_none() && tile.number.is_some()));
+        assert_eq!(
+            map_instance
+                .land_tiles
+                .values()
+                .filter(|tile| tile.is_desert())
+                .count(),
+            1
+        );
+        // No sea lanes on boards without ships
+        assert!(MapInstance::shared(&MapType::Base, 3).sea_edges.is_empty());
+    }
+
+    #[test]
+    fn test_shared_maps_are_interned() {
+        let first = MapInstance::shared(&MapType::Base, 11);
+        let again = MapInstance::shared(&MapType::Tournament, 11);
+        assert!(Arc::ptr_eq(&first, &again));
+        assert!(!Arc::ptr_eq(
+            &first,
+            &MapInstance::shared(&MapType::Mini, 11)
+        ));
+        assert!(!Arc::ptr_eq(
+            &first,
+            &MapInstance::shared(&MapType::Base, 12)
+        ));
+
+        let global_state = GlobalState::new();
+        let built = MapInstance::new(
+            &global_state.base_map_template,
+            &global_state.dice_probas,
+            11,
+        );
+        assert_eq!(first.tiles, built.tiles);
+        assert_eq!(first.get_seed(), 11);
+    }
+
     #[test]
     fn test_map_instance() {
         let global_state = GlobalState::new();
@@ -674,7 +1014,8 @@ mod tests {
                     ])
                 },
                 resource: Some(Resource::Wood),
-                number: Some(10)
+                number: Some(10),
+                gold: false
             }))
         );
         assert_eq!(
@@ -700,7 +1041,8 @@ mod tests {
                     ])
                 },
                 resource: Some(Resource::Ore),
-                number: Some(9)
+                number: Some(9),
+                gold: false
             })
         );
 
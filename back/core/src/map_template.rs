@@ -25,6 +25,11 @@ pub struct MapTemplate {
     pub(crate) numbers: Vec<u8>,
     pub(crate) ports: Vec<Option<Resource>>,
     pub(crate) tiles: Vec<Option<Resource>>,
+    /// Seafarers: ships sail between the hexes (see `MapInstance::sea_edges`)
+    pub(crate) ships: bool,
+    /// Seafarers: gold fields, shuffled in with `tiles`; they take a number and pay any
+    /// resource the owner picks
+    pub(crate) gold_fields: usize,
 
     // Ordered, so that when map is built, we keep the same node-id, edge-id, and tile-id.
     //  that original catanatron uses.
@@ -27,6 +27,10 @@ impl<K: std::hash::Hash + Eq + Clone, V> OrderedHashMap<K, V> {
         self.map.insert(key, value);
     }
 
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
         self.keys
             .iter()
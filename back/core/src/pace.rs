@@ -0,0 +1,85 @@
+// Game-speed presets. Picking one at creation sets the pacing of every subsystem at once:
+// how long humans get per prompt, how long bots may think, how long the server pauses
+// between bot moves, and how fast clients should animate.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Pace {
+    /// Quick games: short timers, shallow bot searches, barely any pauses
+    Blitz,
+    #[default]
+    Standard,
+    /// No timer and unhurried bot moves
+    Relaxed,
+    /// Bot showcases: long pauses so spectators can follow every move
+    Demo,
+}
+
+/// What a preset means for each subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaceSettings {
+    /// Seconds a human gets per prompt (advisory until time controls are enforced)
+    pub turn_timer_secs: Option<u32>,
+    /// Search budget per decision for alpha-beta bots; `None` keeps the bot's own
+    pub bot_search_ms: Option<u64>,
+    /// Longest any other bot may take to decide
+    pub bot_timeout_ms: u64,
+    /// Pause after each bot move so people can see it
+    pub bot_delay_ms: u64,
+    /// Suggested length of board animations on clients
+    pub animation_ms: u32,
+}
+
+impl Pace {
+    pub fn settings(self) -> PaceSettings {
+        match self {
+            Pace::Blitz => PaceSettings {
+                turn_timer_secs: Some(30),
+                bot_search_ms: Some(60),
+                bot_timeout_ms: 2_000,
+                bot_delay_ms: 150,
+                animation_ms: 150,
+            },
+            Pace::Standard => PaceSettings {
+                turn_timer_secs: Some(120),
+                bot_search_ms: None,
+                bot_timeout_ms: 5_000,
+                bot_delay_ms: 500,
+                animation_ms: 300,
+            },
+            Pace::Relaxed => PaceSettings {
+                turn_timer_secs: None,
+                bot_search_ms: None,
+                bot_timeout_ms: 10_000,
+                bot_delay_ms: 1_000,
+                animation_ms: 500,
+            },
+            Pace::Demo => PaceSettings {
+                turn_timer_secs: None,
+                bot_search_ms: None,
+                bot_timeout_ms: 10_000,
+                bot_delay_ms: 2_000,
+                animation_ms: 800,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_slow_down_in_order() {
+        let delays: Vec<u64> = [Pace::Blitz, Pace::Standard, Pace::Relaxed, Pace::Demo]
+            .map(|pace| pace.settings().bot_delay_ms)
+            .to_vec();
+        assert!(delays.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(
+            serde_json::from_str::<Pace>(r#""blitz""#).unwrap(),
+            Pace::Blitz
+        );
+    }
+}
@@ -0,0 +1,96 @@
+// Seat color palettes. Seats keep their ids (RED, BLUE, ...) everywhere in the API and
+// the action log; a palette only decides how each seat is drawn and named on screen. The
+// server sends the display colors with every snapshot, so every client and anything the
+// server renders show a seat the same way without remapping it.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Palette {
+    /// The seat ids' own colors
+    #[default]
+    Classic,
+    /// Okabe-Ito colors, which stay apart under the common forms of color blindness
+    ColorblindSafe,
+}
+
+/// How one seat is shown under a palette
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SeatColor {
+    /// Seat id used by the rest of the API
+    pub seat: String,
+    /// Name to call the seat by, e.g. in a game log
+    pub name: &'static str,
+    /// CSS color to draw the seat's pieces with
+    pub hex: &'static str,
+}
+
+impl Palette {
+    /// Display name and color of the seat at `index`
+    pub fn display(self, index: usize) -> (&'static str, &'static str) {
+        const CLASSIC: [(&str, &str); 6] = [
+            ("red", "#f44336"),
+            ("blue", "#2196f3"),
+            ("white", "#ffffff"),
+            ("orange", "#ff9800"),
+            ("green", "#4caf50"),
+            ("brown", "#795548"),
+        ];
+        const COLORBLIND_SAFE: [(&str, &str); 6] = [
+            ("vermillion", "#d55e00"),
+            ("blue", "#0072b2"),
+            ("yellow", "#f0e442"),
+            ("orange", "#e69f00"),
+            ("bluish green", "#009e73"),
+            ("reddish purple", "#cc79a7"),
+        ];
+        let colors = match self {
+            Palette::Classic => &CLASSIC,
+            Palette::ColorblindSafe => &COLORBLIND_SAFE,
+        };
+        colors[index % colors.len()]
+    }
+
+    /// Display colors of `seats`, given in seat order
+    pub fn seat_colors(self, seats: &[String]) -> Vec<SeatColor> {
+        seats
+            .iter()
+            .enumerate()
+            .map(|(index, seat)| {
+                let (name, hex) = self.display(index);
+                SeatColor {
+                    seat: seat.clone(),
+                    name,
+                    hex,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palettes_keep_seat_ids_and_give_distinct_colors() {
+        let seats = ["RED", "BLUE", "WHITE", "ORANGE", "GREEN", "BROWN"].map(String::from);
+        for palette in [Palette::Classic, Palette::ColorblindSafe] {
+            let colors = palette.seat_colors(&seats);
+            let ids: Vec<&str> = colors.iter().map(|c| c.seat.as_str()).collect();
+            assert_eq!(ids, seats);
+            let mut hexes: Vec<&str> = colors.iter().map(|c| c.hex).collect();
+            hexes.sort_unstable();
+            hexes.dedup();
+            assert_eq!(hexes.len(), seats.len(), "{palette:?}");
+        }
+        // The classic names are the seat ids themselves
+        assert_eq!(Palette::Classic.display(2), ("white", "#ffffff"));
+        assert_eq!(Palette::ColorblindSafe.display(0).0, "vermillion");
+        assert_eq!(
+            serde_json::to_string(&Palette::ColorblindSafe).unwrap(),
+            "\"colorblind_safe\""
+        );
+    }
+}
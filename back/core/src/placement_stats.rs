@@ -0,0 +1,148 @@
+// Opening placement heatmap - where each bot puts its first and second settlement on
+// each map, and how often those games were won. Aggregated from simulation records and
+// exported as JSON keyed by map seed, then node id, as input for the opening book.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::enums::Action;
+use crate::errors::CatanResult;
+use crate::map_instance::NodeId;
+use crate::replay::persistence_error;
+use crate::simulation::GameRecord;
+
+/// Games counted and games won
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WinCount {
+    pub games: u32,
+    pub wins: u32,
+}
+
+impl WinCount {
+    fn add(&mut self, won: bool) {
+        self.games += 1;
+        self.wins += won as u32;
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.games as f64
+    }
+}
+
+/// Picks of one node, per bot
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NodePlacements {
+    /// Games where the bot's first settlement went here
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub first: BTreeMap<String, WinCount>,
+    /// Games where the bot's second settlement went here
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub second: BTreeMap<String, WinCount>,
+}
+
+/// Placements on the map generated from one seed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MapPlacements {
+    /// Every seat a bot played on this map; the baseline a node's win rate is
+    /// compared against
+    pub bots: BTreeMap<String, WinCount>,
+    pub nodes: BTreeMap<NodeId, NodePlacements>,
+}
+
+/// Opening placements of many games, per map seed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlacementHeatmap {
+    pub maps: BTreeMap<u64, MapPlacements>,
+}
+
+impl PlacementHeatmap {
+    /// Count the opening settlements of `game`, credited to the bots in its lineup.
+    /// Games without a winner count as losses for everyone.
+    pub fn record(&mut self, game: &GameRecord) {
+        let map = self.maps.entry(game.seed).or_default();
+        let won = |color: u8| game.winner == Some(color);
+        for (color, bot) in game.lineup.iter().enumerate() {
+            map.bots
+                .entry(bot.clone())
+                .or_default()
+                .add(won(color as u8));
+        }
+
+        let mut placed = vec![0u8; game.lineup.len()];
+        for action in &game.actions {
+            let Action::BuildSettlement { color, node_id } = *action else {
+                continue;
+            };
+            let (Some(count), Some(bot)) = (
+                placed.get_mut(color as usize),
+                game.lineup.get(color as usize),
+            ) else {
+                continue;
+            };
+            let node = map.nodes.entry(node_id).or_default();
+            let picks = match count {
+                0 => &mut node.first,
+                1 => &mut node.second,
+                _ => continue,
+            };
+            picks.entry(bot.clone()).or_default().add(won(color));
+            *count += 1;
+            if placed.iter().all(|&count| count >= 2) {
+                break;
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> CatanResult<()> {
+        let text = serde_json::to_string_pretty(self).map_err(|e| persistence_error(path, e))?;
+        std::fs::write(path, text).map_err(|e| persistence_error(path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::GameConfiguration;
+    use crate::players::{RandomPlayer, SeatedBot};
+    use crate::simulation::run_game;
+
+    #[test]
+    fn test_heatmap_counts_two_openings_per_seat() {
+        let bots: Vec<SeatedBot> = (0..3)
+            .map(|seat| SeatedBot::new(seat, Box::new(RandomPlayer::new())))
+            .collect();
+        let config = GameConfiguration::builder().num_players(3).build().unwrap();
+        let mut heatmap = PlacementHeatmap::default();
+        let mut game = run_game(&bots, config, 21);
+        game.lineup = vec!["A".into(), "B".into(), "A".into()];
+        game.winner = Some(1);
+        heatmap.record(&game);
+        heatmap.record(&game);
+
+        let map = &heatmap.maps[&21];
+        assert_eq!(map.bots["A"], WinCount { games: 4, wins: 0 });
+        assert_eq!(map.bots["B"], WinCount { games: 2, wins: 2 });
+        let total = |pick: fn(&NodePlacements) -> &BTreeMap<String, WinCount>, bot: &str| {
+            map.nodes
+                .values()
+                .filter_map(|node| pick(node).get(bot))
+                .map(|count| count.games)
+                .sum::<u32>()
+        };
+        assert_eq!(total(|node| &node.first, "A"), 4);
+        assert_eq!(total(|node| &node.second, "B"), 2);
+
+        let Action::BuildSettlement { node_id, .. } = game.actions[0] else {
+            panic!("the game should open with a settlement");
+        };
+        let opening = &map.nodes[&node_id].first["A"];
+        assert_eq!(opening.win_rate(), 0.0);
+
+        let json = serde_json::to_value(&heatmap).unwrap();
+        assert!(json["maps"]["21"]["nodes"][node_id.to_string()]["first"]["A"].is_object());
+    }
+}
@@ -0,0 +1,95 @@
+// Position analysis - the recommended action for the player to move plus the
+// probability-weighted outcomes behind it, for hint and analysis endpoints.
+
+use serde::Serialize;
+use std::time::Duration;
+
+use super::minimax::AlphaBetaPlayer;
+use super::BotPlayer;
+use crate::enums::Action;
+use crate::state::State;
+
+/// One way a stochastic action can resolve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChanceOutcome {
+    /// Dice total after a roll
+    DiceSum { sum: u8 },
+    /// Development card drawn (DevCard index)
+    DevCard { card: u8 },
+    /// Resource stolen by the robber (None when the victim had no cards)
+    Steal { resource: Option<u8> },
+}
+
+/// A chance outcome of the recommended action, annotated with its probability
+#[derive(Debug, Clone, Serialize)]
+pub struct OutcomeAnnotation {
+    pub outcome: ChanceOutcome,
+    pub probability: f64,
+    /// Search value of the position after this outcome, from the analyzing color's view
+    pub value: f64,
+    /// Change of the acting player's hand, by resource
+    pub resource_delta: [i8; 5],
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisResult {
+    pub color: u8,
+    pub best_action: Action,
+    pub value: f64,
+    /// Empty when the recommended action is deterministic
+    pub outcomes: Vec<OutcomeAnnotation>,
+    /// Probability-weighted hand change of the acting player over `outcomes`
+    pub expected_resources: [f64; 5],
+}
+
+/// Analyze the position for the player to move with the expectiminimax search, limited
+/// to `depth` plies. `time_budget` bounds the root search and, separately, the valuation
+/// of the recommended action's chance outcomes. Returns None when nothing is playable.
+pub fn analyze(state: &State, depth: i32, time_budget: Duration) -> Option<AnalysisResult> {
+    AlphaBetaPlayer::analyst(depth, time_budget).analyze(state)
+}
+
+/// Every action `analyze` could recommend for the player to move: the best-valued one
+/// and those tied with it, which the search picks between at random
+pub fn best_actions(state: &State, depth: i32, time_budget: Duration) -> Vec<Action> {
+    let actions = state.generate_playable_actions();
+    if actions.len() < 2 {
+        return actions;
+    }
+    let deliberation = AlphaBetaPlayer::analyst(depth, time_budget).deliberate(state, &actions);
+    let Some(best) = deliberation.candidates.first().map(|c| c.score) else {
+        return vec![deliberation.chosen];
+    };
+    let mut tied: Vec<Action> = deliberation
+        .candidates
+        .iter()
+        .take_while(|c| (c.score - best).abs() <= 1e-6)
+        .map(|c| c.action)
+        .collect();
+    if !tied.contains(&deliberation.chosen) {
+        tied.push(deliberation.chosen);
+    }
+    tied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_annotates_opening_roll() {
+        let mut state = State::new_base();
+        while state.is_initial_build_phase() {
+            let action = state.generate_playable_actions()[0];
+            state.apply_action(action);
+        }
+
+        let result = analyze(&state, 2, Duration::from_millis(50)).expect("roll is playable");
+        assert_eq!(result.color, state.get_current_color());
+        assert!(matches!(result.best_action, Action::Roll { .. }));
+        let total: f64 = result.outcomes.iter().map(|o| o.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(result.expected_resources.iter().all(|&r| r >= 0.0));
+    }
+}
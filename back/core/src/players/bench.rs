@@ -0,0 +1,212 @@
+// Benchmark positions - a suite of positions with annotated best moves, scored by the
+// bench_positions binary as a fast proxy for engine strength between full arena runs.
+//
+// One position per line, `#` starts a comment:
+//
+//   <tag> | <expected moves> | <position in State::to_fen notation>
+//
+// Expected moves are space separated alternatives, any of which counts as solved:
+// `city`, `city@<node>`, `settlement`, `settlement@<node>`, `road`, `road@<a>-<b>`,
+// `dev`, `knight`, `robber`, `robber@<tile>`, `robber>victim`, `roll`, `end`.
+
+use std::path::Path;
+
+use crate::enums::Action;
+use crate::errors::CatanResult;
+use crate::map_instance::{EdgeId, NodeId};
+use crate::replay::persistence_error;
+use crate::state::State;
+
+/// Suite shipped with the repository, relative to the back directory
+pub const DEFAULT_SUITE_PATH: &str = "bench/positions.fen";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveSpec {
+    City(Option<NodeId>),
+    Settlement(Option<NodeId>),
+    Road(Option<EdgeId>),
+    DevCard,
+    Knight,
+    Robber {
+        tile: Option<u8>,
+        victim: Option<u8>,
+    },
+    Roll,
+    EndTurn,
+}
+
+impl MoveSpec {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let bad = || format!("unknown move '{text}'");
+        let node = |n: &str| n.parse::<NodeId>().map_err(|_| bad());
+        Ok(match text {
+            "city" => MoveSpec::City(None),
+            "settlement" => MoveSpec::Settlement(None),
+            "road" => MoveSpec::Road(None),
+            "dev" => MoveSpec::DevCard,
+            "knight" => MoveSpec::Knight,
+            "robber" => MoveSpec::Robber {
+                tile: None,
+                victim: None,
+            },
+            "roll" => MoveSpec::Roll,
+            "end" => MoveSpec::EndTurn,
+            _ => {
+                if let Some(n) = text.strip_prefix("city@") {
+                    MoveSpec::City(Some(node(n)?))
+                } else if let Some(n) = text.strip_prefix("settlement@") {
+                    MoveSpec::Settlement(Some(node(n)?))
+                } else if let Some(edge) = text.strip_prefix("road@") {
+                    let (a, b) = edge.split_once('-').ok_or_else(bad)?;
+                    let (a, b) = (node(a)?, node(b)?);
+                    MoveSpec::Road(Some((a.min(b), a.max(b))))
+                } else if let Some(tile) = text.strip_prefix("robber@") {
+                    MoveSpec::Robber {
+                        tile: Some(tile.parse().map_err(|_| bad())?),
+                        victim: None,
+                    }
+                } else if let Some(victim) = text.strip_prefix("robber>") {
+                    MoveSpec::Robber {
+                        tile: None,
+                        victim: Some(victim.parse().map_err(|_| bad())?),
+                    }
+                } else {
+                    return Err(bad());
+                }
+            }
+        })
+    }
+
+    pub fn matches(&self, state: &State, action: Action) -> bool {
+        match (*self, action) {
+            (MoveSpec::City(node), Action::BuildCity { node_id, .. }) => {
+                node.is_none_or(|n| n == node_id)
+            }
+            (MoveSpec::Settlement(node), Action::BuildSettlement { node_id, .. }) => {
+                node.is_none_or(|n| n == node_id)
+            }
+            (MoveSpec::Road(edge), Action::BuildRoad { edge_id, .. }) => {
+                let (a, b) = edge_id;
+                edge.is_none_or(|e| e == (a.min(b), a.max(b)))
+            }
+            (MoveSpec::DevCard, Action::BuyDevelopmentCard { .. }) => true,
+            (MoveSpec::Knight, Action::PlayKnight { .. }) => true,
+            (
+                MoveSpec::Robber { tile, victim },
+                Action::MoveRobber {
+                    coordinate,
+                    victim_opt,
+                    ..
+                },
+            ) => {
+                let tile_id = state
+                    .get_map_instance()
+                    .get_land_tile(coordinate)
+                    .map(|t| t.id);
+                tile.is_none_or(|t| Some(t) == tile_id)
+                    && victim.is_none_or(|v| Some(v) == victim_opt)
+            }
+            (MoveSpec::Roll, Action::Roll { .. }) => true,
+            (MoveSpec::EndTurn, Action::EndTurn { .. }) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchPosition {
+    pub tag: String,
+    pub expected: Vec<MoveSpec>,
+    pub fen: String,
+    /// 1-based line in the suite file
+    pub line: usize,
+}
+
+impl BenchPosition {
+    pub fn state(&self) -> CatanResult<State> {
+        State::from_fen(&self.fen)
+    }
+
+    pub fn is_solved_by(&self, state: &State, action: Action) -> bool {
+        self.expected.iter().any(|spec| spec.matches(state, action))
+    }
+}
+
+pub fn parse_suite(text: &str) -> Result<Vec<BenchPosition>, String> {
+    let mut positions = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = line.split('|').map(str::trim).collect();
+        let [tag, expected, fen] = parts[..] else {
+            return Err(format!(
+                "line {}: expected 'tag | moves | position'",
+                index + 1
+            ));
+        };
+        let expected = expected
+            .split_whitespace()
+            .map(MoveSpec::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("line {}: {e}", index + 1))?;
+        positions.push(BenchPosition {
+            tag: tag.to_string(),
+            expected,
+            fen: fen.to_string(),
+            line: index + 1,
+        });
+    }
+    Ok(positions)
+}
+
+pub fn load_suite(path: &Path) -> CatanResult<Vec<BenchPosition>> {
+    let text = std::fs::read_to_string(path).map_err(|e| persistence_error(path, e))?;
+    parse_suite(&text).map_err(|e| persistence_error(path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_suite_is_solvable() {
+        // The suite ships with the server crate, one level up
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(DEFAULT_SUITE_PATH);
+        let suite = load_suite(&path).unwrap();
+        assert!((50..=100).contains(&suite.len()));
+        for position in &suite {
+            let state = position
+                .state()
+                .unwrap_or_else(|e| panic!("line {}: {e}", position.line));
+            assert_eq!(state.to_fen(), position.fen, "line {}", position.line);
+            assert!(
+                state
+                    .generate_playable_actions()
+                    .into_iter()
+                    .any(|action| position.is_solved_by(&state, action)),
+                "line {}: no legal move matches the annotation",
+                position.line
+            );
+        }
+    }
+
+    #[test]
+    fn test_move_specs() {
+        assert_eq!(
+            MoveSpec::parse("road@5-4"),
+            Ok(MoveSpec::Road(Some((4, 5))))
+        );
+        assert_eq!(
+            MoveSpec::parse("robber>2"),
+            Ok(MoveSpec::Robber {
+                tile: None,
+                victim: Some(2)
+            })
+        );
+        assert!(MoveSpec::parse("castle").is_err());
+    }
+}
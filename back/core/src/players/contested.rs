@@ -0,0 +1,147 @@
+// Contested settlement spots - open nodes that several players can reach within a
+// couple of roads. Bots that ignore them routinely lose obvious spots to a faster
+// neighbour, so evaluation rewards being first in line and the opening takes a spot
+// now when it will not survive until the next pick.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use crate::map_instance::NodeId;
+use crate::state::{Building, State};
+
+/// Roads a player may still need for a spot to count as within reach
+pub const CONTESTED_ROAD_RADIUS: u8 = 2;
+
+/// Roads `color` still needs to reach each open spot within `CONTESTED_ROAD_RADIUS`.
+/// Paths may not use other players' roads or pass through their buildings.
+pub fn spot_distances(state: &State, color: u8) -> HashMap<NodeId, u8> {
+    let mut distances: HashMap<NodeId, u8> = HashMap::new();
+    let mut frontier: Vec<NodeId> = Vec::new();
+    let mut reach = |node: NodeId, frontier: &mut Vec<NodeId>| {
+        if let Entry::Vacant(entry) = distances.entry(node) {
+            entry.insert(0);
+            frontier.push(node);
+        }
+    };
+    for (a, b) in state.get_roads_for_color(color) {
+        reach(a, &mut frontier);
+        reach(b, &mut frontier);
+    }
+    for building in state
+        .get_settlements(color)
+        .into_iter()
+        .chain(state.get_cities(color))
+    {
+        let (Building::Settlement(_, node) | Building::City(_, node)) = building;
+        reach(node, &mut frontier);
+    }
+
+    let map = state.get_map_instance();
+    for depth in 1..=CONTESTED_ROAD_RADIUS {
+        let mut next = Vec::new();
+        for node in frontier {
+            if state
+                .get_node_color(node)
+                .is_some_and(|owner| owner != color)
+            {
+                continue;
+            }
+            for &neighbor in map.get_neighbor_nodes(node) {
+                let edge = (node.min(neighbor), node.max(neighbor));
+                let blocked = state
+                    .get_edge_owner(edge)
+                    .is_some_and(|owner| owner != color);
+                if !blocked && !distances.contains_key(&neighbor) {
+                    distances.insert(neighbor, depth);
+                    next.push(neighbor);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    distances.retain(|&node, _| state.is_board_buildable(node));
+    distances
+}
+
+/// Production of the contested spots `color` is first in line for (a tie with the
+/// closest opponent counts half). A spot is contested when an opponent can also
+/// reach it within `CONTESTED_ROAD_RADIUS` roads.
+pub fn contested_spot_lead(state: &State, color: u8) -> f64 {
+    let mine = spot_distances(state, color);
+    if mine.is_empty() {
+        return 0.0;
+    }
+    let theirs: Vec<HashMap<NodeId, u8>> = (0..state.get_num_players())
+        .filter(|&other| !state.are_teammates(other, color))
+        .map(|other| spot_distances(state, other))
+        .collect();
+
+    let map = state.get_map_instance();
+    let mut lead = 0.0;
+    for (node, &my_distance) in &mine {
+        let Some(their_distance) = theirs.iter().filter_map(|d| d.get(node)).min() else {
+            continue;
+        };
+        let share = match my_distance.cmp(their_distance) {
+            std::cmp::Ordering::Less => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Greater => continue,
+        };
+        let production: f64 = map.get_node_production_row(*node).iter().sum();
+        lead += share * production;
+    }
+    lead
+}
+
+/// Opponent settlements placed between the current initial placement and the current
+/// player's next one (snake order). Zero in the second round.
+pub fn opening_picks_before_next(state: &State) -> usize {
+    let num_players = state.get_num_players() as usize;
+    let placed: usize = (0..state.get_num_players())
+        .map(|color| state.get_settlements(color).len() + state.get_cities(color).len())
+        .sum();
+    if placed >= num_players {
+        return 0;
+    }
+    2 * (num_players - 1 - placed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::Action;
+
+    #[test]
+    fn test_racing_road_takes_the_lead() {
+        let mut state = State::new_base();
+        assert_eq!(opening_picks_before_next(&state), 6);
+
+        // Seats 0 and 1 each two roads away from the same open spot
+        let map = state.get_map_instance().clone();
+        let target = *map.land_nodes().iter().min().unwrap();
+        let approach = |via: NodeId| {
+            map.get_neighbor_nodes(via)
+                .iter()
+                .copied()
+                .find(|&n| n != target)
+                .unwrap()
+        };
+        let neighbors = map.get_neighbor_nodes(target);
+        let (via0, via1) = (neighbors[0], neighbors[1]);
+        for (color, node_id) in [(0, approach(via0)), (1, approach(via1))] {
+            state.apply_action(Action::BuildSettlement { color, node_id });
+        }
+        assert_eq!(spot_distances(&state, 0).get(&target), Some(&2));
+        assert_eq!(spot_distances(&state, 1).get(&target), Some(&2));
+        let tied = contested_spot_lead(&state, 0);
+
+        let from = approach(via0);
+        state.apply_action(Action::BuildRoad {
+            color: 0,
+            edge_id: (from.min(via0), from.max(via0)),
+        });
+        assert_eq!(spot_distances(&state, 0).get(&target), Some(&1));
+        assert!(contested_spot_lead(&state, 0) > tied);
+    }
+}
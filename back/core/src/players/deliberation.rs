@@ -0,0 +1,193 @@
+// Bot deliberation logs - for every decision a bot makes in a served game, what it was
+// asked (prompt), the best candidates it scored, what it chose and how long it took.
+// Written as one NDJSON file per game when CATAN_DELIBERATION_DIR is set, so a single
+// game can be debugged offline without turning up the global log level.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::BotPlayer;
+use crate::enums::Action;
+use crate::errors::{CatanError, CatanResult};
+use crate::replay::persistence_error;
+use crate::state::State;
+
+/// Directory for per-game deliberation logs; logging is off when unset
+pub const DELIBERATION_DIR_ENV: &str = "CATAN_DELIBERATION_DIR";
+/// Candidates kept per logged decision
+pub const LOGGED_CANDIDATES: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoredAction {
+    pub action: Action,
+    /// Engine-specific value, higher is better for the side to move
+    pub score: f64,
+}
+
+/// A decision together with the candidates the engine scored on the way
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deliberation {
+    pub chosen: Action,
+    /// Best first; empty for engines that do not score actions
+    pub candidates: Vec<ScoredAction>,
+}
+
+impl Deliberation {
+    /// A decision without scores
+    pub fn unscored(chosen: Action) -> Self {
+        Self {
+            chosen,
+            candidates: Vec::new(),
+        }
+    }
+
+    pub fn scored(chosen: Action, mut candidates: Vec<ScoredAction>) -> Self {
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Self { chosen, candidates }
+    }
+}
+
+/// One line of a deliberation log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliberationEntry {
+    pub color: u8,
+    pub bot: String,
+    pub prompt: String,
+    pub candidates: Vec<ScoredAction>,
+    pub chosen: Action,
+    pub time_ms: f64,
+}
+
+impl DeliberationEntry {
+    pub fn new(
+        state: &State,
+        bot: &dyn BotPlayer,
+        deliberation: Deliberation,
+        elapsed: Duration,
+    ) -> Self {
+        let mut candidates = deliberation.candidates;
+        candidates.truncate(LOGGED_CANDIDATES);
+        Self {
+            color: state.get_current_color(),
+            bot: bot.name(),
+            prompt: format!("{:?}", state.get_action_prompt()),
+            candidates,
+            chosen: deliberation.chosen,
+            time_ms: elapsed.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Per-game NDJSON files under one directory
+#[derive(Debug, Clone)]
+pub struct DeliberationLog {
+    dir: PathBuf,
+}
+
+impl DeliberationLog {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Log in the directory named by `CATAN_DELIBERATION_DIR`, if the variable is set
+    pub fn from_env() -> Option<Self> {
+        std::env::var(DELIBERATION_DIR_ENV).ok().map(Self::new)
+    }
+
+    /// File for a game. Game ids are generated names; anything that could leave the
+    /// directory is rejected.
+    fn path(&self, game_id: &str) -> CatanResult<PathBuf> {
+        let valid = !game_id.is_empty()
+            && game_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(CatanError::Validation(format!(
+                "invalid game id '{game_id}'"
+            )));
+        }
+        Ok(self.dir.join(format!("{game_id}.ndjson")))
+    }
+
+    pub fn append(&self, game_id: &str, entry: &DeliberationEntry) -> CatanResult<()> {
+        let path = self.path(game_id)?;
+        let line = serde_json::to_string(entry).map_err(|e| persistence_error(&path, e))?;
+        std::fs::create_dir_all(&self.dir).map_err(|e| persistence_error(&self.dir, e))?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| persistence_error(&path, e))?;
+        writeln!(file, "{line}").map_err(|e| persistence_error(&path, e))
+    }
+
+    /// The whole log of a game, as written
+    pub fn read(&self, game_id: &str) -> CatanResult<String> {
+        let path = self.path(game_id)?;
+        std::fs::read_to_string(&path).map_err(|e| persistence_error(&path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::{SeatedBot, ValueFunctionPlayer};
+
+    #[test]
+    fn test_value_player_reports_scored_candidates() {
+        let state = State::new_base();
+        let actions = state.generate_playable_actions();
+        let bot = SeatedBot::new(
+            state.get_current_color(),
+            Box::new(ValueFunctionPlayer::new()),
+        );
+        let deliberation = bot.deliberate(&state, &actions);
+
+        assert_eq!(deliberation.candidates.len(), actions.len());
+        assert_eq!(deliberation.candidates[0].action, deliberation.chosen);
+        assert!(deliberation
+            .candidates
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+    }
+
+    #[test]
+    fn test_log_appends_one_line_per_decision() {
+        let dir = std::env::temp_dir().join(format!("deliberation_{}", std::process::id()));
+        let log = DeliberationLog::new(&dir);
+        let state = State::new_base();
+        let actions = state.generate_playable_actions();
+        let bot = ValueFunctionPlayer::new();
+        for _ in 0..2 {
+            let entry = DeliberationEntry::new(
+                &state,
+                &bot,
+                bot.deliberate(&state, &actions),
+                Duration::from_millis(3),
+            );
+            log.append("game_1", &entry).unwrap();
+        }
+
+        let text = log.read("game_1").unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let entries: Vec<DeliberationEntry> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].bot, "ValueFunctionPlayer");
+        assert_eq!(entries[0].candidates.len(), LOGGED_CANDIDATES);
+        assert_eq!(entries[0].time_ms, 3.0);
+    }
+
+    #[test]
+    fn test_log_rejects_paths_outside_its_directory() {
+        let log = DeliberationLog::new("logs");
+        assert!(matches!(
+            log.read("../secrets"),
+            Err(CatanError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,93 @@
+// Production-shape evaluation features shared by the value function and alpha-beta:
+// how many resources a player really produces, whether their 2:1 ports match what
+// they overproduce, and whether they have committed to a build archetype.
+
+use crate::state::State;
+
+/// Production (probability per roll) a resource needs to count towards diversity: a
+/// single 4 or 10
+pub const DIVERSITY_MIN_PRODUCTION: f64 = 3.0 / 36.0;
+
+/// How much one resource pair has to outproduce the other to define an archetype
+pub const ARCHETYPE_RATIO: f64 = 1.5;
+
+/// Build plans a production base naturally supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Archetype {
+    /// Cities and development cards
+    OreWheat,
+    /// Roads and settlements
+    WoodBrick,
+}
+
+/// Distinct resources produced at `DIVERSITY_MIN_PRODUCTION` or better
+pub fn production_diversity(production: &[f64]) -> f64 {
+    production
+        .iter()
+        .filter(|&&p| p >= DIVERSITY_MIN_PRODUCTION)
+        .count() as f64
+}
+
+/// Surplus production (above the average of the other resources) of each resource
+/// `color` holds a 2:1 port for
+pub fn port_synergy(state: &State, color: u8, production: &[f64]) -> f64 {
+    let rates = state.calculate_port_rates(color);
+    let total: f64 = production.iter().sum();
+    let others = (production.len() - 1) as f64;
+    rates
+        .iter()
+        .zip(production)
+        .filter(|(&rate, _)| rate == 2)
+        .map(|(_, &p)| (p - (total - p) / others).max(0.0))
+        .sum()
+}
+
+/// Archetype the production base leans towards, if either pair clearly dominates
+pub fn detect_archetype(production: &[f64]) -> Option<Archetype> {
+    let (wood_brick, ore_wheat) = archetype_pairs(production);
+    if ore_wheat > 0.0 && ore_wheat >= wood_brick * ARCHETYPE_RATIO {
+        Some(Archetype::OreWheat)
+    } else if wood_brick > 0.0 && wood_brick >= ore_wheat * ARCHETYPE_RATIO {
+        Some(Archetype::WoodBrick)
+    } else {
+        None
+    }
+}
+
+/// Share of the dominant pair within the two pairs when an archetype is detected, 0
+/// otherwise
+pub fn archetype_focus(production: &[f64]) -> f64 {
+    let (wood_brick, ore_wheat) = archetype_pairs(production);
+    match detect_archetype(production) {
+        Some(Archetype::OreWheat) => ore_wheat / (ore_wheat + wood_brick),
+        Some(Archetype::WoodBrick) => wood_brick / (ore_wheat + wood_brick),
+        None => 0.0,
+    }
+}
+
+fn archetype_pairs(production: &[f64]) -> (f64, f64) {
+    (production[0] + production[1], production[3] + production[4])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archetypes_and_diversity() {
+        let city_builder = [0.0, 0.03, 0.08, 0.14, 0.11];
+        assert_eq!(detect_archetype(&city_builder), Some(Archetype::OreWheat));
+        assert!((archetype_focus(&city_builder) - 0.25 / 0.28).abs() < 1e-9);
+        assert_eq!(production_diversity(&city_builder), 2.0);
+
+        let balanced = [0.1, 0.1, 0.1, 0.1, 0.1];
+        assert_eq!(detect_archetype(&balanced), None);
+        assert_eq!(archetype_focus(&balanced), 0.0);
+        assert_eq!(production_diversity(&balanced), 5.0);
+
+        assert_eq!(
+            detect_archetype(&[0.11, 0.08, 0.0, 0.03, 0.0]),
+            Some(Archetype::WoodBrick)
+        );
+    }
+}
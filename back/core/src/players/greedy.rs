@@ -1,5 +1,6 @@
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::time::Instant;
@@ -7,41 +8,27 @@ use std::time::Instant;
 use crate::enums::Action;
 use crate::state::State;
 
-const SIMULATIONS_PER_ACTION: usize = 3;
+pub(crate) const SIMULATIONS_PER_ACTION: usize = 3;
 
 use super::BotPlayer;
 
 /// Greedy Monte Carlo Player
 /// Evaluates each action by running random playouts and choosing the one with the highest win rate
 pub struct GreedyPlayer {
-    pub id: String,
-    pub name: String,
-    pub color: String,
     num_simulations_per_action: usize,
     use_parallel: bool,
 }
 
 impl GreedyPlayer {
-    pub fn new(id: String, name: String, color: String) -> Self {
+    pub fn new() -> Self {
         GreedyPlayer {
-            id,
-            name,
-            color,
             num_simulations_per_action: SIMULATIONS_PER_ACTION,
             use_parallel: true,
         }
     }
 
-    pub fn with_simulations(
-        id: String,
-        name: String,
-        color: String,
-        num_simulations_per_action: usize,
-    ) -> Self {
+    pub fn with_simulations(num_simulations_per_action: usize) -> Self {
         GreedyPlayer {
-            id,
-            name,
-            color,
             num_simulations_per_action,
             use_parallel: true,
         }
@@ -90,7 +77,7 @@ impl GreedyPlayer {
                 if let Some(winner) = Self::playout(state_copy) {
                     // Count the win
                     let win_count = action_wins.entry(*action).or_insert(0);
-                    if winner == my_color {
+                    if state.are_teammates(winner, my_color) {
                         *win_count += 1;
                     }
                 }
@@ -133,8 +120,11 @@ impl GreedyPlayer {
         let my_color = state.get_current_color();
 
         // Use parallel iterator to evaluate actions
-        let results: Vec<(Action, f64)> = playable_actions
-            .par_iter()
+        #[cfg(feature = "parallel")]
+        let actions = playable_actions.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let actions = playable_actions.iter();
+        let results: Vec<(Action, f64)> = actions
             .map(|action| {
                 let mut wins = 0;
 
@@ -144,7 +134,7 @@ impl GreedyPlayer {
                     state_copy.apply_action(*action);
 
                     if let Some(winner) = Self::playout(state_copy) {
-                        if winner == my_color {
+                        if state.are_teammates(winner, my_color) {
                             wins += 1;
                         }
                     }
@@ -194,10 +184,6 @@ impl BotPlayer for GreedyPlayer {
 
 impl Default for GreedyPlayer {
     fn default() -> Self {
-        Self::new(
-            "default".to_string(),
-            "Greedy Player".to_string(),
-            "red".to_string(),
-        )
+        Self::new()
     }
 }
@@ -0,0 +1,187 @@
+// Strategy ladder - versioned ValueWeights checkpoints. A candidate only replaces the
+// champion after beating it in an arena by a configurable margin, so the lineage file
+// always ends with the strongest known weights (served as the "hard" bot).
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::value::{ValueFunctionPlayer, ValueWeights};
+use super::SeatedBot;
+use crate::enums::GameConfiguration;
+use crate::errors::CatanResult;
+use crate::replay::persistence_error;
+use crate::simulation::{new_game_state, run_from, SimulationSettings};
+
+/// Where the server looks for the champion lineage unless CATAN_LADDER_PATH is set
+pub const DEFAULT_LADDER_PATH: &str = "models/ladder.json";
+/// Win rate above 50% a candidate needs to be promoted
+pub const DEFAULT_PROMOTION_MARGIN: f64 = 0.05;
+pub const DEFAULT_ARENA_GAMES: usize = 40;
+const ARENA_MAX_STEPS: usize = 5000;
+
+/// Arena record of a candidate against the champion
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArenaResult {
+    pub candidate_wins: usize,
+    pub champion_wins: usize,
+    pub unfinished: usize,
+}
+
+impl ArenaResult {
+    pub fn games(&self) -> usize {
+        self.candidate_wins + self.champion_wins + self.unfinished
+    }
+
+    /// Share of all arena games the candidate won (unfinished games count against it)
+    pub fn candidate_win_rate(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.0;
+        }
+        self.candidate_wins as f64 / self.games() as f64
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Version tag: v1, v2, ...
+    pub tag: String,
+    pub weights: ValueWeights,
+    /// Match that promoted this checkpoint (None for the first one)
+    pub arena: Option<ArenaResult>,
+}
+
+/// Champion lineage, oldest first. The last checkpoint is the current champion.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ladder {
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+/// Outcome of challenging the champion
+#[derive(Debug, Clone, PartialEq)]
+pub struct Challenge {
+    /// None when there was no champion to play against
+    pub arena: Option<ArenaResult>,
+    /// Tag given to the candidate if it was promoted
+    pub promoted_as: Option<String>,
+}
+
+impl Ladder {
+    /// Load a lineage file; a missing file is an empty ladder
+    pub fn load(path: &Path) -> CatanResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path).map_err(|e| persistence_error(path, e))?;
+        serde_json::from_str(&text).map_err(|e| persistence_error(path, e))
+    }
+
+    pub fn save(&self, path: &Path) -> CatanResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| persistence_error(path, e))?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(|e| persistence_error(path, e))?;
+        std::fs::write(path, text).map_err(|e| persistence_error(path, e))
+    }
+
+    pub fn champion(&self) -> Option<&Checkpoint> {
+        self.checkpoints.last()
+    }
+
+    /// Play `candidate` against the champion and promote it if its win rate is at least
+    /// 50% + `margin`. The first candidate of an empty ladder is promoted without a match.
+    pub fn challenge(
+        &mut self,
+        candidate: ValueWeights,
+        games: usize,
+        margin: f64,
+        seed: u64,
+    ) -> Challenge {
+        let arena = self
+            .champion()
+            .map(|champion| run_arena(&candidate, &champion.weights, games, seed));
+        let promote = arena.is_none_or(|result| result.candidate_win_rate() >= 0.5 + margin);
+
+        let promoted_as = promote.then(|| {
+            let tag = format!("v{}", self.checkpoints.len() + 1);
+            self.checkpoints.push(Checkpoint {
+                tag: tag.clone(),
+                weights: candidate,
+                arena,
+            });
+            tag
+        });
+        Challenge { arena, promoted_as }
+    }
+}
+
+/// Head-to-head two-player games between value-function bots using each weight set.
+/// Seats alternate every game so neither side keeps the first-move advantage.
+pub fn run_arena(
+    candidate: &ValueWeights,
+    champion: &ValueWeights,
+    games: usize,
+    seed: u64,
+) -> ArenaResult {
+    let mut result = ArenaResult::default();
+    for game in 0..games {
+        let candidate_seat = (game % 2) as u8;
+        let mut bots = vec![
+            SeatedBot::new(
+                0,
+                Box::new(ValueFunctionPlayer::with_weights(champion.clone())),
+            ),
+            SeatedBot::new(
+                1,
+                Box::new(ValueFunctionPlayer::with_weights(champion.clone())),
+            ),
+        ];
+        bots[candidate_seat as usize].engine =
+            Box::new(ValueFunctionPlayer::with_weights(candidate.clone()));
+
+        match play_arena_game(&bots, seed.wrapping_add(game as u64)) {
+            Some(winner) if winner == candidate_seat => result.candidate_wins += 1,
+            Some(_) => result.champion_wins += 1,
+            None => result.unfinished += 1,
+        }
+    }
+    result
+}
+
+fn play_arena_game(bots: &[SeatedBot], seed: u64) -> Option<u8> {
+    let config = GameConfiguration {
+        num_players: bots.len() as u8,
+        max_ticks: ARENA_MAX_STEPS as u32,
+        ..Default::default()
+    };
+    let settings = SimulationSettings {
+        max_actions: ARENA_MAX_STEPS,
+        ..Default::default()
+    };
+    run_from(new_game_state(config, seed), bots, &settings, &mut ()).winner
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_candidate_is_promoted_without_a_match() {
+        let mut ladder = Ladder::default();
+        let challenge = ladder.challenge(ValueWeights::default(), 10, 0.05, 1);
+        assert_eq!(challenge.arena, None);
+        assert_eq!(challenge.promoted_as.as_deref(), Some("v1"));
+        assert_eq!(ladder.champion().unwrap().tag, "v1");
+    }
+
+    #[test]
+    fn test_candidate_must_beat_champion_by_margin() {
+        let mut ladder = Ladder::default();
+        ladder.challenge(ValueWeights::default(), 0, 0.0, 1);
+
+        // An identical candidate cannot clear a margin above 50%
+        let challenge = ladder.challenge(ValueWeights::default(), 4, 0.6, 7);
+        assert_eq!(challenge.arena.map(|a| a.games()), Some(4));
+        assert_eq!(challenge.promoted_as, None);
+        assert_eq!(ladder.checkpoints.len(), 1);
+    }
+}
@@ -0,0 +1,79 @@
+// Players module - Contains all player implementations
+//
+// This module organizes various player implementations
+
+// Import necessary types
+use crate::enums::{Action, FreqDeck};
+use crate::state::State;
+
+// Define the Player trait for bot players (separate from the main Player trait)
+pub trait BotPlayer {
+    fn decide(&self, state: &State, playable_actions: &[Action]) -> Action;
+
+    /// `decide`, plus the scores of the candidates considered. Engines that do not score
+    /// actions report none.
+    fn deliberate(&self, state: &State, playable_actions: &[Action]) -> Deliberation {
+        Deliberation::unscored(self.decide(state, playable_actions))
+    }
+
+    /// Cards `color` gives up on a 7. Move generation only offers the suggested discard,
+    /// so engines with their own preference pick here instead of in `decide`.
+    fn choose_discard(&self, state: &State, color: u8) -> FreqDeck {
+        state.suggested_discard(color)
+    }
+
+    /// Terms `color` counters the trade on the table with instead of turning it down, or
+    /// `None` to let the refusal stand. Move generation lists no counter-offers.
+    fn counter_offer(&self, _state: &State, _color: u8) -> Option<(FreqDeck, FreqDeck)> {
+        None
+    }
+
+    /// Name recorded in game records; the engine's type name unless overridden
+    fn name(&self) -> String {
+        let path = std::any::type_name::<Self>();
+        path.rsplit("::").next().unwrap_or(path).to_string()
+    }
+}
+
+// Declare the player implementation modules
+pub mod analysis;
+pub mod bench;
+pub mod contested;
+pub mod deliberation;
+pub mod features;
+pub mod greedy;
+pub mod human;
+pub mod ladder;
+pub mod minimax;
+#[cfg(feature = "nn")]
+pub mod nn;
+pub mod opponent_model;
+pub mod playout;
+pub mod profile;
+pub mod random;
+pub mod robber_denial;
+pub mod seated;
+pub mod value;
+pub mod weighted_random;
+pub mod win_model;
+#[cfg(feature = "nn")]
+pub mod zero;
+// pub mod mcts;  // Keep disabled for now - may need fixes
+
+// Re-export player implementations for ease of use
+pub use self::analysis::{analyze, best_actions, AnalysisResult, ChanceOutcome, OutcomeAnnotation};
+pub use self::deliberation::{Deliberation, DeliberationEntry, DeliberationLog, ScoredAction};
+pub use self::greedy::GreedyPlayer;
+pub use self::human::HumanPlayer;
+pub use self::minimax::{AlphaBetaPlayer, SearchMode};
+pub use self::profile::EvalProfile;
+pub use self::random::RandomPlayer;
+pub use self::seated::SeatedBot;
+pub use self::value::ValueFunctionPlayer;
+pub use self::weighted_random::WeightedRandomPlayer;
+pub use self::win_model::{WinModel, FITTED_WIN_MODEL};
+// nn module exposes helper to get a default net (feature-gated)
+#[cfg(feature = "nn")]
+pub use self::zero::AlphaZeroPlayer;
+
+// BotPlayer trait is defined above
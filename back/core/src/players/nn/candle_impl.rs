@@ -95,7 +95,7 @@ impl PolicyValueNet for CandleNet {
         let act_feats = action_features(legal_actions);
         let k = act_feats.len();
         let state_broadcast = state_embed
-            .repeat((k as usize, 1))
+            .repeat((k, 1))
             .unwrap_or_else(|_| state_embed.clone());
         let act_tensor = candle::Tensor::from_vec(
             act_feats.iter().flatten().copied().collect::<Vec<f32>>(),
@@ -113,17 +113,13 @@ impl PolicyValueNet for CandleNet {
             .to_vec1()
             .unwrap_or(vec![0.0; k]);
         let probs = masked_softmax(&logits, &mask);
-        let priors = legal_actions
-            .iter()
-            .copied()
-            .zip(probs.into_iter())
-            .collect();
+        let priors = legal_actions.iter().copied().zip(probs).collect();
         let value_vec: Vec<f32> = value_tensor
             .flatten_all()
             .expect("value flatten")
             .to_vec1()
             .unwrap_or(vec![0.0]);
-        let value: f32 = *value_vec.get(0).unwrap_or(&0.0);
+        let value: f32 = *value_vec.first().unwrap_or(&0.0);
         PolicyValue { priors, value }
     }
 }
@@ -134,10 +130,8 @@ fn masked_softmax(logits: &[f32], mask: &[f32]) -> Vec<f32> {
     }
     let mut max_logit = f32::NEG_INFINITY;
     for (&l, &m) in logits.iter().zip(mask.iter()) {
-        if m > 0.0 {
-            if l > max_logit {
-                max_logit = l;
-            }
+        if m > 0.0 && l > max_logit {
+            max_logit = l;
         }
     }
     let mut exps = vec![0f32; logits.len()];
@@ -177,7 +177,7 @@ pub fn action_features(
             Action::BuildCity { .. } => {
                 f[1] = 1.0;
             }
-            Action::BuildRoad { .. } => {
+            Action::BuildRoad { .. } | Action::BuildShip { .. } => {
                 f[2] = 1.0;
             }
             Action::BuyDevelopmentCard { .. } => {
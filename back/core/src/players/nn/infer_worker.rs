@@ -96,8 +96,7 @@ fn worker_loop(device: Device, rx: Receiver<InferenceRequest>, flush_ms: u64) {
             let logits = net.policy_logits(&fused).unwrap().squeeze(1).unwrap();
             let probs =
                 softmax_logits_masked_host(logits).unwrap_or_else(|| vec![1.0 / k as f32; k]);
-            let priors: Vec<(Action, f32)> =
-                req.legal.iter().copied().zip(probs.into_iter()).collect();
+            let priors: Vec<(Action, f32)> = req.legal.iter().copied().zip(probs).collect();
             let _ = req.reply.send((priors, values[i]));
         }
     }
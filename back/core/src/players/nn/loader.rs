@@ -26,10 +26,7 @@ pub fn try_load(varmap: &mut VarMap, path: &str) -> bool {
     if !p.exists() {
         return false;
     }
-    match varmap.load(p) {
-        Ok(_) => true,
-        Err(_) => false,
-    }
+    varmap.load(p).is_ok()
 }
 
 pub fn try_save(varmap: &VarMap, path: &str) -> bool {
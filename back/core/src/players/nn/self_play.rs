@@ -21,9 +21,6 @@ pub fn generate_self_play_game() -> Vec<Experience> {
     // Use a single AlphaZeroPlayer for the current player decisions; for other prompts
     // its decide() already short-circuits.
     let az = AlphaZeroPlayer::with_parameters_full(
-        "z".to_string(),
-        "AlphaZero".to_string(),
-        "Z".to_string(),
         TRAINING_MCTS_SIMULATIONS,
         TRAINING_EXPLORATION,
         TRAINING_DECIDE_BUDGET_MS,
@@ -60,7 +57,11 @@ pub fn generate_self_play_game() -> Vec<Experience> {
     if let Some(winner) = state.winner() {
         for exp in experiences.iter_mut() {
             let me = exp.state.get_current_color();
-            exp.value_target = if winner == me { 1.0 } else { -1.0 };
+            exp.value_target = if state.are_teammates(winner, me) {
+                1.0
+            } else {
+                -1.0
+            };
         }
     }
 
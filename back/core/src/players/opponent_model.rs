@@ -0,0 +1,177 @@
+// Opponent modeling - simple per-seat habits observed during one game (who they rob,
+// how often they take trades, what they like to build). Bots use it to lean into
+// predictable patterns. A model lives with a single game and is never carried over.
+
+use serde::Serialize;
+
+use crate::deck_slices::{FreqDeck, CITY_COST, DEVCARD_COST, ROAD_COST, SETTLEMENT_COST};
+use crate::enums::Action;
+use crate::state::State;
+
+/// Observations needed before a habit is trusted
+const MIN_SAMPLES: u16 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BuildKind {
+    Road,
+    Settlement,
+    City,
+    DevelopmentCard,
+}
+
+impl BuildKind {
+    pub fn cost(self) -> FreqDeck {
+        match self {
+            BuildKind::Road => ROAD_COST,
+            BuildKind::Settlement => SETTLEMENT_COST,
+            BuildKind::City => CITY_COST,
+            BuildKind::DevelopmentCard => DEVCARD_COST,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct OpponentProfile {
+    pub robber_moves: u16,
+    /// Robber moves that stole from the player leading at the time (other than the mover)
+    pub robber_moves_on_leader: u16,
+    pub trade_responses: u16,
+    pub trades_accepted: u16,
+    pub roads: u16,
+    pub settlements: u16,
+    pub cities: u16,
+    pub dev_cards: u16,
+}
+
+impl OpponentProfile {
+    /// Share of robber moves aimed at the leader
+    pub fn leader_aggression(&self) -> Option<f64> {
+        (self.robber_moves >= MIN_SAMPLES)
+            .then(|| self.robber_moves_on_leader as f64 / self.robber_moves as f64)
+    }
+
+    /// Share of trade offers this player accepted
+    pub fn trade_acceptance(&self) -> Option<f64> {
+        (self.trade_responses >= MIN_SAMPLES)
+            .then(|| self.trades_accepted as f64 / self.trade_responses as f64)
+    }
+
+    /// What this player buys most often (roads excluded: everyone needs them)
+    pub fn preferred_build(&self) -> Option<BuildKind> {
+        let counts = [
+            (BuildKind::Settlement, self.settlements),
+            (BuildKind::City, self.cities),
+            (BuildKind::DevelopmentCard, self.dev_cards),
+        ];
+        let total: u16 = counts.iter().map(|(_, n)| n).sum();
+        if total < MIN_SAMPLES {
+            return None;
+        }
+        counts.iter().max_by_key(|(_, n)| *n).map(|(kind, _)| *kind)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OpponentModel {
+    profiles: Vec<OpponentProfile>,
+}
+
+impl OpponentModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `action`, observed in `state` just before it is applied
+    pub fn observe(&mut self, state: &State, action: Action) {
+        let color = action.color();
+        if self.profiles.len() <= color as usize {
+            self.profiles
+                .resize(color as usize + 1, OpponentProfile::default());
+        }
+        let leader = leader_excluding(state, color);
+        let profile = &mut self.profiles[color as usize];
+        match action {
+            Action::MoveRobber { victim_opt, .. } => {
+                profile.robber_moves += 1;
+                if victim_opt.is_some() && victim_opt == leader {
+                    profile.robber_moves_on_leader += 1;
+                }
+            }
+            Action::AcceptTrade { .. } => {
+                profile.trade_responses += 1;
+                profile.trades_accepted += 1;
+            }
+            Action::RejectTrade { .. } => profile.trade_responses += 1,
+            // A counter from a responder turns the offer down as made
+            Action::CounterTrade { .. }
+                if state
+                    .get_domestic_trade()
+                    .is_some_and(|t| t.offerer != color) =>
+            {
+                profile.trade_responses += 1
+            }
+            Action::BuildRoad { .. } | Action::BuildShip { .. }
+                if !state.is_initial_build_phase() =>
+            {
+                profile.roads += 1
+            }
+            Action::BuildSettlement { .. } if !state.is_initial_build_phase() => {
+                profile.settlements += 1
+            }
+            Action::BuildCity { .. } => profile.cities += 1,
+            Action::BuyDevelopmentCard { .. } => profile.dev_cards += 1,
+            _ => {}
+        }
+    }
+
+    pub fn profile(&self, color: u8) -> Option<&OpponentProfile> {
+        self.profiles.get(color as usize)
+    }
+}
+
+/// Player with the most victory points other than `color`, as `color` sees them
+fn leader_excluding(state: &State, color: u8) -> Option<u8> {
+    (0..state.get_num_players())
+        .filter(|&other| other != color)
+        .max_by_key(|&other| state.get_visible_victory_points(color, other))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_habits_need_enough_samples() {
+        let state = State::new_base();
+        let mut model = OpponentModel::new();
+        let accept = Action::AcceptTrade {
+            color: 2,
+            trade: ([1, 0, 0, 0, 0], [0, 1, 0, 0, 0]),
+        };
+
+        model.observe(&state, accept);
+        model.observe(&state, Action::RejectTrade { color: 2 });
+        assert_eq!(model.profile(2).unwrap().trade_acceptance(), None);
+
+        model.observe(&state, accept);
+        assert_eq!(
+            model.profile(2).unwrap().trade_acceptance(),
+            Some(2.0 / 3.0)
+        );
+        assert!(model.profile(3).is_none());
+    }
+
+    #[test]
+    fn test_preferred_build() {
+        let state = State::new_base();
+        let mut model = OpponentModel::new();
+        for node_id in [1, 2] {
+            model.observe(&state, Action::BuildCity { color: 0, node_id });
+        }
+        model.observe(&state, Action::BuyDevelopmentCard { color: 0 });
+        assert_eq!(
+            model.profile(0).unwrap().preferred_build(),
+            Some(BuildKind::City)
+        );
+    }
+}
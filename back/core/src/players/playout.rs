@@ -0,0 +1,167 @@
+// Playout estimates - plays each candidate action out to the end many times with
+// configured bots, in parallel with the `parallel` feature, and reports empirical win
+// rates. Slow but unbiased by any evaluation function, so it serves as ground truth for
+// benchmark positions and as a sanity check on evaluation changes.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::{BotPlayer, SeatedBot};
+use crate::enums::Action;
+use crate::simulation::{run_from, SimulationSettings};
+use crate::state::State;
+
+/// Builds the bot for a color; called once per seat per game so bots need not be shared
+/// across threads
+pub type BotFactory = dyn Fn(u8) -> Box<dyn BotPlayer> + Sync;
+
+#[derive(Debug, Clone)]
+pub struct PlayoutSettings {
+    pub games_per_action: usize,
+    /// Games still running after this many actions count as unfinished
+    pub max_actions: usize,
+    /// Game `i` of every action uses seed `seed + i`, so actions are compared on the same
+    /// dice
+    pub seed: u64,
+}
+
+impl Default for PlayoutSettings {
+    fn default() -> Self {
+        Self {
+            games_per_action: 100,
+            max_actions: 2000,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionWinRate {
+    pub action: Action,
+    pub games: usize,
+    /// Games won by the side to move (or a teammate)
+    pub wins: usize,
+    pub unfinished: usize,
+    /// Actions played after the candidate, summed over won games
+    pub win_length: usize,
+}
+
+impl ActionWinRate {
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games.max(1) as f64
+    }
+
+    /// Standard error of `win_rate`
+    pub fn std_error(&self) -> f64 {
+        let p = self.win_rate();
+        (p * (1.0 - p) / self.games.max(1) as f64).sqrt()
+    }
+
+    /// Average number of actions a won game took to finish
+    pub fn mean_win_length(&self) -> f64 {
+        self.win_length as f64 / self.wins.max(1) as f64
+    }
+}
+
+/// Win rate of every playable action in `state` for the side to move, best first. Equal
+/// win rates are ordered by how quickly the wins came.
+pub fn playout_win_rates(
+    state: &State,
+    bots: &BotFactory,
+    settings: &PlayoutSettings,
+) -> Vec<ActionWinRate> {
+    let me = state.get_current_color();
+    let actions = state.generate_playable_actions();
+    let games = settings.games_per_action;
+
+    #[cfg(feature = "parallel")]
+    let jobs = (0..actions.len() * games).into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let jobs = 0..actions.len() * games;
+    let outcomes: Vec<(usize, Option<u8>, usize)> = jobs
+        .map(|job| {
+            let (index, game) = (job / games, job % games);
+            let mut copy = state.clone();
+            copy.reseed(settings.seed.wrapping_add(game as u64));
+            copy.apply_action(actions[index]);
+            let (winner, length) = play_out(copy, bots, settings.max_actions);
+            (index, winner, length)
+        })
+        .collect();
+
+    let mut rates: Vec<ActionWinRate> = actions
+        .iter()
+        .map(|&action| ActionWinRate {
+            action,
+            games,
+            wins: 0,
+            unfinished: 0,
+            win_length: 0,
+        })
+        .collect();
+    for (index, winner, length) in outcomes {
+        match winner {
+            Some(winner) if state.are_teammates(winner, me) => {
+                rates[index].wins += 1;
+                rates[index].win_length += length;
+            }
+            Some(_) => {}
+            None => rates[index].unfinished += 1,
+        }
+    }
+    rates.sort_by(|a, b| {
+        b.win_rate()
+            .total_cmp(&a.win_rate())
+            .then(a.mean_win_length().total_cmp(&b.mean_win_length()))
+    });
+    rates
+}
+
+/// Winner (if any) and the number of actions played
+fn play_out(state: State, bots: &BotFactory, max_actions: usize) -> (Option<u8>, usize) {
+    let seats: Vec<SeatedBot> = (0..state.get_num_players())
+        .map(|color| SeatedBot::new(color, bots(color)))
+        .collect();
+    let settings = SimulationSettings {
+        max_actions,
+        break_loops: false,
+    };
+    let record = run_from(state, &seats, &settings, &mut ());
+    (record.winner, record.actions.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::RandomPlayer;
+
+    #[test]
+    fn test_winning_city_plays_out_to_certain_win() {
+        // Seat 1 is on 9 VP with exactly a city in hand
+        let state = State::from_fen(
+            "B:1 1p s1@1,s2@7,s0@9,s0@14,c1@16,s3@17,s2@20,c1@23,c1@27,s3@49 \
+             1:1-2,0:2-9,1:6-23,2:7-24,1:8-27,0:14-37,3:15-17,1:16-18,2:19-20,3:22-49 \
+             1.0.0.0.0/0.0.1.2.3/1.0.1.0.0/0.1.1.0.0 0.0.0.0.0/0.0.0.0.2/0.0.0.0.0/0.0.0.0.0 \
+             0.0.0.0/0.0.0.0/0.0.0.0/0.0.0.0 13",
+        )
+        .unwrap();
+        let settings = PlayoutSettings {
+            games_per_action: 4,
+            max_actions: 200,
+            seed: 7,
+        };
+        let rates = playout_win_rates(&state, &|_| Box::new(RandomPlayer::new()), &settings);
+
+        assert_eq!(rates.len(), state.generate_playable_actions().len());
+        let city = rates
+            .iter()
+            .find(|r| matches!(r.action, Action::BuildCity { .. }))
+            .unwrap();
+        assert_eq!(city.win_rate(), 1.0);
+        assert_eq!(city.std_error(), 0.0);
+        assert_eq!(city.mean_win_length(), 0.0);
+        // Winning on the spot sorts ahead of everything that wins later
+        assert_eq!(rates[0].mean_win_length(), 0.0);
+        assert!(rates.iter().all(|r| r.games == 4 && r.wins <= 4));
+    }
+}
@@ -0,0 +1,205 @@
+// Evaluation profiles - bot weights and search settings read from a TOML file, so a
+// deployment can change bot strength and style without recompiling and simulate can
+// seat differently tuned bots against each other.
+//
+// Every section and key is optional; anything left out keeps the built-in default:
+//
+//   [weights]          # ValueWeights, shared by the value function and alpha-beta
+//   public_vps = 120.0
+//   [alphabeta]
+//   depth = 4
+//   epsilon = 0.05
+//   fast_ms = 60       # search time profile, all three or none
+//   slow_ms = 100
+//   slow_branch_threshold = 12
+//   late_game_extensions = true    # deeper, wider search near the finish (default off)
+//   contempt = 0.5     # >0: gamble when behind, consolidate when ahead (default 0)
+//   search_mode = "max_n"   # or "paranoid" (default)
+//   [value]
+//   epsilon = 0.1
+//   [greedy]
+//   simulations = 10
+
+use serde::Deserialize;
+use std::path::Path;
+
+use super::greedy::SIMULATIONS_PER_ACTION;
+use super::minimax::{SearchMode, DEFAULT_DEPTH};
+use super::value::ValueWeights;
+use super::{AlphaBetaPlayer, GreedyPlayer, ValueFunctionPlayer};
+use crate::errors::CatanResult;
+use crate::replay::persistence_error;
+
+/// Environment variable naming the profile the server's bots use
+pub const EVAL_PROFILE_ENV: &str = "CATAN_EVAL_PROFILE";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvalProfile {
+    pub weights: ValueWeights,
+    pub alphabeta: AlphaBetaSettings,
+    pub value: ValueSettings,
+    pub greedy: GreedySettings,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlphaBetaSettings {
+    pub depth: i32,
+    pub epsilon: Option<f64>,
+    pub fast_ms: Option<u64>,
+    pub slow_ms: Option<u64>,
+    pub slow_branch_threshold: Option<usize>,
+    pub late_game_extensions: bool,
+    pub contempt: f64,
+    pub search_mode: SearchMode,
+}
+
+impl Default for AlphaBetaSettings {
+    fn default() -> Self {
+        Self {
+            depth: DEFAULT_DEPTH,
+            epsilon: None,
+            fast_ms: None,
+            slow_ms: None,
+            slow_branch_threshold: None,
+            late_game_extensions: false,
+            contempt: 0.0,
+            search_mode: SearchMode::Paranoid,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValueSettings {
+    pub epsilon: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GreedySettings {
+    pub simulations: usize,
+}
+
+impl Default for GreedySettings {
+    fn default() -> Self {
+        Self {
+            simulations: SIMULATIONS_PER_ACTION,
+        }
+    }
+}
+
+impl EvalProfile {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let document: toml_edit::DocumentMut = text.parse().map_err(|e| format!("{e}"))?;
+        let json = table_to_json(document.as_table())?;
+        serde_json::from_value(json).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: &Path) -> CatanResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| persistence_error(path, e))?;
+        Self::parse(&text).map_err(|e| persistence_error(path, e))
+    }
+
+    /// Profile named by `CATAN_EVAL_PROFILE`, if the variable is set
+    pub fn from_env() -> CatanResult<Option<Self>> {
+        match std::env::var(EVAL_PROFILE_ENV) {
+            Ok(path) => Self::load(Path::new(&path)).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn alpha_beta_player(&self) -> AlphaBetaPlayer {
+        let settings = &self.alphabeta;
+        let mut player =
+            AlphaBetaPlayer::with_config(settings.depth, self.weights.clone(), settings.epsilon);
+        if let (Some(fast_ms), Some(slow_ms), Some(threshold)) = (
+            settings.fast_ms,
+            settings.slow_ms,
+            settings.slow_branch_threshold,
+        ) {
+            player.set_time_profile(fast_ms, slow_ms, threshold);
+        }
+        player.set_late_game_extensions(settings.late_game_extensions);
+        player.set_contempt(settings.contempt);
+        player.set_search_mode(settings.search_mode);
+        player
+    }
+
+    pub fn value_player(&self) -> ValueFunctionPlayer {
+        let mut player = ValueFunctionPlayer::with_weights(self.weights.clone());
+        player.epsilon = self.value.epsilon;
+        player
+    }
+
+    pub fn greedy_player(&self) -> GreedyPlayer {
+        GreedyPlayer::with_simulations(self.greedy.simulations)
+    }
+}
+
+fn table_to_json(table: &toml_edit::Table) -> Result<serde_json::Value, String> {
+    let mut object = serde_json::Map::new();
+    for (key, item) in table.iter() {
+        let value = match item {
+            toml_edit::Item::Value(value) => value_to_json(value)?,
+            toml_edit::Item::Table(table) => table_to_json(table)?,
+            toml_edit::Item::ArrayOfTables(_) => {
+                return Err(format!("`{key}`: arrays of tables are not supported"))
+            }
+            toml_edit::Item::None => continue,
+        };
+        object.insert(key.to_string(), value);
+    }
+    Ok(serde_json::Value::Object(object))
+}
+
+fn value_to_json(value: &toml_edit::Value) -> Result<serde_json::Value, String> {
+    use toml_edit::Value;
+    Ok(match value {
+        Value::String(s) => serde_json::Value::from(s.value().as_str()),
+        Value::Integer(i) => serde_json::Value::from(*i.value()),
+        Value::Float(f) => serde_json::Value::from(*f.value()),
+        Value::Boolean(b) => serde_json::Value::from(*b.value()),
+        Value::Array(array) => {
+            serde_json::Value::Array(array.iter().map(value_to_json).collect::<Result<_, _>>()?)
+        }
+        Value::InlineTable(table) => {
+            let mut object = serde_json::Map::new();
+            for (key, value) in table.iter() {
+                object.insert(key.to_string(), value_to_json(value)?);
+            }
+            serde_json::Value::Object(object)
+        }
+        Value::Datetime(_) => return Err("datetimes are not supported".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_profile_keeps_defaults() {
+        let profile = EvalProfile::parse(
+            "[weights]\npublic_vps = 200\narmy_size = 1.5\n\n[alphabeta]\ndepth = 3\n",
+        )
+        .unwrap();
+        assert_eq!(profile.weights.public_vps, 200.0);
+        assert_eq!(profile.weights.army_size, 1.5);
+        assert_eq!(
+            profile.weights.production,
+            ValueWeights::default().production
+        );
+        assert_eq!(profile.alphabeta.depth, 3);
+        assert!(!profile.alphabeta.late_game_extensions);
+        assert_eq!(profile.alphabeta.contempt, 0.0);
+        assert_eq!(profile.greedy.simulations, SIMULATIONS_PER_ACTION);
+
+        assert!(EvalProfile::parse("[alphabeta]\ndepht = 3\n").is_err());
+
+        let max_n = EvalProfile::parse("[alphabeta]\nsearch_mode = \"max_n\"\n").unwrap();
+        assert_eq!(max_n.alphabeta.search_mode, SearchMode::MaxN);
+        assert!(EvalProfile::parse("[alphabeta]\nsearch_mode = \"maxn\"\n").is_err());
+    }
+}
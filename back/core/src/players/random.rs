@@ -3,15 +3,12 @@ use rand::prelude::*;
 use crate::enums::Action;
 use crate::state::State;
 
-pub struct RandomPlayer {
-    pub id: String,
-    pub name: String,
-    pub color: String,
-}
+#[derive(Default)]
+pub struct RandomPlayer;
 
 impl RandomPlayer {
-    pub fn new(id: String, name: String, color: String) -> Self {
-        RandomPlayer { id, name, color }
+    pub fn new() -> Self {
+        RandomPlayer
     }
 }
 
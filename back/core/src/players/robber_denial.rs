@@ -0,0 +1,97 @@
+// Robber denial planner - guesses the victim's next build from their hand and the
+// spots still open to them, then rates robber tiles by how much of the missing
+// resources they would cut off. Backs the "spiteful" robber heuristic.
+
+use super::opponent_model::BuildKind;
+use crate::deck_slices::FreqDeck;
+use crate::map_template::Coordinate;
+use crate::state::State;
+
+/// Tie-break order when several builds are equally close: more points first
+const PLAN_PRIORITY: [BuildKind; 4] = [
+    BuildKind::City,
+    BuildKind::Settlement,
+    BuildKind::DevelopmentCard,
+    BuildKind::Road,
+];
+
+/// The build a player is closest to affording, with the cards still missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedBuild {
+    pub kind: BuildKind,
+    pub missing: FreqDeck,
+}
+
+impl PlannedBuild {
+    pub fn missing_cards(&self) -> u8 {
+        self.missing.iter().sum()
+    }
+}
+
+/// Most likely next build for `color`: among builds with a legal spot, the one
+/// needing the fewest extra cards
+pub fn likely_next_build(state: &State, color: u8) -> Option<PlannedBuild> {
+    let hand = state.get_player_hand(color);
+    PLAN_PRIORITY
+        .iter()
+        .copied()
+        .filter(|&kind| has_spot(state, color, kind))
+        .map(|kind| {
+            let cost = kind.cost();
+            let mut missing = [0u8; 5];
+            for (i, slot) in missing.iter_mut().enumerate() {
+                *slot = cost[i].saturating_sub(hand[i]);
+            }
+            PlannedBuild { kind, missing }
+        })
+        .min_by_key(PlannedBuild::missing_cards)
+}
+
+/// Production `victim` would lose toward `plan` with the robber on `coordinate`,
+/// each resource weighted by how many of it are still missing. Negative when the
+/// move frees a tile the plan depends on.
+pub fn denial_value(state: &State, victim: u8, plan: &PlannedBuild, coordinate: Coordinate) -> f64 {
+    let Some(tile) = state.get_map_instance().get_land_tile(coordinate) else {
+        return 0.0;
+    };
+    let before = state.get_effective_production(victim);
+    let mut ns = state.clone();
+    ns.set_robber_tile(tile.id);
+    let after = ns.get_effective_production(victim);
+    plan.missing
+        .iter()
+        .zip(before.iter().zip(&after))
+        .map(|(&count, (b, a))| count as f64 * (b - a))
+        .sum()
+}
+
+fn has_spot(state: &State, color: u8, kind: BuildKind) -> bool {
+    match kind {
+        BuildKind::City => !state.get_settlements(color).is_empty(),
+        BuildKind::Settlement => !state.buildable_node_ids(color).is_empty(),
+        BuildKind::Road => !state.board_buildable_edges(color).is_empty(),
+        BuildKind::DevelopmentCard => state.get_remaining_dev_counts().iter().any(|&c| c > 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, StateEdit};
+
+    #[test]
+    fn test_plan_follows_hand() {
+        let mut state = State::new_base();
+        // Seat 0 has nothing on the board yet, so only a dev card has a spot
+        let plan = likely_next_build(&state, 0).unwrap();
+        assert_eq!(plan.kind, BuildKind::DevelopmentCard);
+        assert_eq!(plan.missing_cards(), 3);
+
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::Hand([0, 0, 1, 1, 0]),
+        });
+        let plan = likely_next_build(&state, 0).unwrap();
+        assert_eq!(plan.missing, [0, 0, 0, 0, 1]);
+    }
+}
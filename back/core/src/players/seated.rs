@@ -0,0 +1,52 @@
+use crate::enums::{Action, FreqDeck};
+use crate::state::State;
+
+use super::{BotPlayer, Deliberation};
+
+/// A bot engine playing a particular seat.
+///
+/// Engines only carry search parameters (weights, depth, time profile); which seat they
+/// play lives here, so the same engine types can be built, pooled and reused without
+/// any per-player identity.
+pub struct SeatedBot {
+    pub seat: u8,
+    pub engine: Box<dyn BotPlayer>,
+}
+
+impl SeatedBot {
+    pub fn new(seat: u8, engine: Box<dyn BotPlayer>) -> Self {
+        Self { seat, engine }
+    }
+}
+
+impl BotPlayer for SeatedBot {
+    fn decide(&self, state: &State, playable_actions: &[Action]) -> Action {
+        debug_assert_eq!(
+            state.get_current_color(),
+            self.seat,
+            "bot asked to move for another seat"
+        );
+        self.engine.decide(state, playable_actions)
+    }
+
+    fn deliberate(&self, state: &State, playable_actions: &[Action]) -> Deliberation {
+        debug_assert_eq!(
+            state.get_current_color(),
+            self.seat,
+            "bot asked to move for another seat"
+        );
+        self.engine.deliberate(state, playable_actions)
+    }
+
+    fn choose_discard(&self, state: &State, color: u8) -> FreqDeck {
+        self.engine.choose_discard(state, color)
+    }
+
+    fn counter_offer(&self, state: &State, color: u8) -> Option<(FreqDeck, FreqDeck)> {
+        self.engine.counter_offer(state, color)
+    }
+
+    fn name(&self) -> String {
+        self.engine.name()
+    }
+}
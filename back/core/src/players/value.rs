@@ -1,15 +1,20 @@
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-use crate::enums::{Action, DevCard};
+use crate::deck_slices::{freqdeck_add, freqdeck_sub};
+use crate::enums::{Action, DevCard, FreqDeck, TeamVictory};
 use crate::map_instance::NodeId;
 use crate::state::{Building, State};
 
-use super::BotPlayer;
+use super::contested::contested_spot_lead;
+use super::features::{archetype_focus, port_synergy, production_diversity};
+use super::{BotPlayer, Deliberation, ScoredAction};
 
 const TRANSLATE_VARIETY: f64 = 4.0; // each new resource is like 4 production points
 const PROBA_POINT: f64 = 2.778 / 100.0; // probability point used in Python value_production
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ValueWeights {
     pub public_vps: f64,
     pub production: f64,
@@ -24,6 +29,14 @@ pub struct ValueWeights {
     pub discard_penalty: f64,
     pub hand_devs: f64,
     pub army_size: f64,
+    /// Production of contested spots the player is first in line for
+    pub contested_spots: f64,
+    /// Distinct resources produced at a useful rate
+    pub production_diversity: f64,
+    /// Surplus production of resources with an owned 2:1 port
+    pub port_synergy: f64,
+    /// Commitment to an ore-wheat or wood-brick production base
+    pub archetype_focus: f64,
 }
 
 impl Default for ValueWeights {
@@ -42,6 +55,10 @@ impl Default for ValueWeights {
             discard_penalty: -5.0,
             hand_devs: 1.2,
             army_size: 6.0,
+            contested_spots: 5.0,
+            production_diversity: 1.0,
+            port_synergy: 10.0,
+            archetype_focus: 2.0,
         }
     }
 }
@@ -50,38 +67,23 @@ impl ValueWeights {}
 
 #[derive(Debug, Clone)]
 pub struct ValueFunctionPlayer {
-    pub id: String,
-    pub name: String,
-    pub color: String,
-    pub my_color: u8,
     pub weights: ValueWeights,
     pub epsilon: Option<f64>,
 }
 
+impl Default for ValueFunctionPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ValueFunctionPlayer {
-    pub fn new(id: String, name: String, color_name: String, my_color: u8) -> Self {
-        Self {
-            id,
-            name,
-            color: color_name,
-            my_color,
-            weights: ValueWeights::default(),
-            epsilon: None,
-        }
+    pub fn new() -> Self {
+        Self::with_weights(ValueWeights::default())
     }
 
-    pub fn with_weights(
-        id: String,
-        name: String,
-        color_name: String,
-        my_color: u8,
-        weights: ValueWeights,
-    ) -> Self {
+    pub fn with_weights(weights: ValueWeights) -> Self {
         Self {
-            id,
-            name,
-            color: color_name,
-            my_color,
             weights,
             epsilon: None,
         }
@@ -141,8 +143,11 @@ impl ValueFunctionPlayer {
     fn evaluate_state(&self, state: &State, p0_color: u8) -> f64 {
         let w = &self.weights;
 
-        // Public/actual VPs
-        let vps = state.get_actual_victory_points(p0_color) as f64;
+        // Public/actual VPs (the whole team's when teammates' points are combined)
+        let vps = match state.get_team_config().map(|teams| teams.victory) {
+            Some(TeamVictory::CombinedPoints) => state.get_team_victory_points(p0_color) as f64,
+            _ => state.get_actual_victory_points(p0_color) as f64,
+        };
 
         // Production (effective, considering robber)
         let my_prod = state.get_effective_production(p0_color);
@@ -152,7 +157,7 @@ impl ValueFunctionPlayer {
         let mut enemy_acc = 0.0;
         let mut enemy_cnt = 0.0;
         for color in 0..state.get_num_players() {
-            if color == p0_color {
+            if state.are_teammates(color, p0_color) {
                 continue;
             }
             let p = state.get_effective_production(color);
@@ -186,8 +191,14 @@ impl ValueFunctionPlayer {
         let hand_synergy = self.hand_synergy(state, p0_color);
 
         // Board features
-        let num_buildable_nodes = state.buildable_node_ids(p0_color).len() as f64;
+        let num_buildable_nodes = state.buildable_nodes(p0_color).count() as f64;
         let num_tiles = self.count_my_owned_tiles(state, p0_color) as f64;
+        let contested_lead = contested_spot_lead(state, p0_color);
+
+        // Production shape
+        let diversity = production_diversity(&my_prod);
+        let synergy = port_synergy(state, p0_color, &my_prod);
+        let focus = archetype_focus(&my_prod);
 
         // Longest road factor: if cannot build more, weight longest road bonus; else small
         let longest_road_factor = if num_buildable_nodes == 0.0 {
@@ -210,34 +221,68 @@ impl ValueFunctionPlayer {
             + longest_road_length * longest_road_factor
             + hand_devs * w.hand_devs
             + army_size * w.army_size
+            + contested_lead * w.contested_spots
+            + diversity * w.production_diversity
+            + synergy * w.port_synergy
+            + focus * w.archetype_focus
     }
 }
 
 impl BotPlayer for ValueFunctionPlayer {
     fn decide(&self, state: &State, playable_actions: &[Action]) -> Action {
+        self.deliberate(state, playable_actions).chosen
+    }
+
+    fn deliberate(&self, state: &State, playable_actions: &[Action]) -> Deliberation {
         if playable_actions.len() == 1 {
-            return playable_actions[0];
+            return Deliberation::unscored(playable_actions[0]);
         }
 
         if let Some(eps) = self.epsilon {
             let mut rng = rand::thread_rng();
             if rng.gen_range(0.0..1.0) < eps {
                 let idx = rng.gen_range(0..playable_actions.len());
-                return playable_actions[idx];
+                return Deliberation::unscored(playable_actions[idx]);
             }
         }
 
+        let my_color = state.get_current_color();
         let mut best_action = playable_actions[0];
         let mut best_value = f64::NEG_INFINITY;
+        let mut candidates = Vec::with_capacity(playable_actions.len());
         for &action in playable_actions.iter() {
             let mut next_state = state.clone();
             next_state.apply_action(action);
-            let value = self.evaluate_state(&next_state, self.my_color);
+            let value = self.evaluate_state(&next_state, my_color);
             if value > best_value {
                 best_value = value;
                 best_action = action;
             }
+            candidates.push(ScoredAction {
+                action,
+                score: value,
+            });
+        }
+        Deliberation::scored(best_action, candidates)
+    }
+
+    /// The candidate counter worth most once traded through, if any beats keeping the hand
+    fn counter_offer(&self, state: &State, color: u8) -> Option<(FreqDeck, FreqDeck)> {
+        let (offerer, responder) = state.trade_sides(color)?;
+        let mut best = None;
+        let mut best_value = self.evaluate_state(state, color);
+        for (give, take) in state.counter_offer_candidates(color) {
+            let mut settled = state.clone();
+            freqdeck_sub(settled.get_mut_player_hand(offerer), give);
+            freqdeck_add(settled.get_mut_player_hand(responder), give);
+            freqdeck_sub(settled.get_mut_player_hand(responder), take);
+            freqdeck_add(settled.get_mut_player_hand(offerer), take);
+            let value = self.evaluate_state(&settled, color);
+            if value > best_value {
+                best_value = value;
+                best = Some((give, take));
+            }
         }
-        best_action
+        best
     }
 }
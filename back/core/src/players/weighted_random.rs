@@ -12,15 +12,11 @@ use super::BotPlayer;
 /// - Building settlements
 /// - Buying development cards
 ///   Other actions have a default weight of 1.
-pub struct WeightedRandomPlayer {
-    pub id: String,
-    pub name: String,
-    pub color: String,
-}
+pub struct WeightedRandomPlayer;
 
 impl WeightedRandomPlayer {
-    pub fn new(id: String, name: String, color: String) -> Self {
-        WeightedRandomPlayer { id, name, color }
+    pub fn new() -> Self {
+        WeightedRandomPlayer
     }
 
     /// Creates action weight map similar to Python version
@@ -77,10 +73,6 @@ impl BotPlayer for WeightedRandomPlayer {
 
 impl Default for WeightedRandomPlayer {
     fn default() -> Self {
-        Self::new(
-            "default".to_string(),
-            "Weighted Random Player".to_string(),
-            "red".to_string(),
-        )
+        Self::new()
     }
 }
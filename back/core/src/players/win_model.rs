@@ -0,0 +1,278 @@
+// Win probability model - a logistic regression from a few mid-game features to the
+// chance a seat goes on to win, fitted to simulated games (`simulate --fit-win-model`).
+// It is cheap enough to call per position, so it scores positions for the analysis bar
+// and stands in for the result of playouts cut off before anyone won.
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::State;
+
+pub const NUM_WIN_FEATURES: usize = 3;
+
+/// A seat's lead over its best opponent: victory points, production in dots (1/36 of a
+/// card per roll, robber included) and development cards bought
+pub type WinFeatures = [f64; NUM_WIN_FEATURES];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WinModel {
+    pub intercept: f64,
+    /// One per feature, in `WinFeatures` order
+    pub weights: WinFeatures,
+}
+
+/// Fitted on 2000 four-player games between value-function bots, sampled at the end of
+/// every turn after the opening (`simulate -p VVVV -n 2000 --seed 1 --fit-win-model`)
+pub const FITTED_WIN_MODEL: WinModel = WinModel {
+    intercept: -0.6376,
+    weights: [0.4750, 0.0078, 0.0415],
+};
+
+/// Newton iterations `fit` runs at most
+const FIT_ITERATIONS: usize = 50;
+/// Ridge term keeping the Hessian invertible on separable or degenerate samples
+const FIT_RIDGE: f64 = 1e-6;
+
+/// `color`'s lead over its best opponent; teammates are not opponents
+pub fn win_features(state: &State, color: u8) -> WinFeatures {
+    let seat = |c: u8| -> WinFeatures {
+        let dots: f64 = state.get_effective_production(c).iter().sum::<f64>() * 36.0;
+        // Victory point cards are never played
+        let held: u8 = (0..5).map(|card| state.get_dev_card_count(c, card)).sum();
+        let played: u8 = (0..4)
+            .map(|card| state.get_played_dev_card_count(c, card))
+            .sum();
+        [
+            state.get_visible_victory_points(color, c) as f64,
+            dots,
+            (held + played) as f64,
+        ]
+    };
+    let own = seat(color);
+    let mut best = [f64::NEG_INFINITY; NUM_WIN_FEATURES];
+    for other in (0..state.get_num_players()).filter(|&c| !state.are_teammates(color, c)) {
+        for (best, value) in best.iter_mut().zip(seat(other)) {
+            *best = best.max(value);
+        }
+    }
+    std::array::from_fn(|i| {
+        if best[i].is_finite() {
+            own[i] - best[i]
+        } else {
+            0.0
+        }
+    })
+}
+
+impl WinModel {
+    /// Chance a seat with `features` wins, on its own
+    pub fn probability(&self, features: &WinFeatures) -> f64 {
+        let z = self.intercept
+            + self
+                .weights
+                .iter()
+                .zip(features)
+                .map(|(w, x)| w * x)
+                .sum::<f64>();
+        1.0 / (1.0 + (-z).exp())
+    }
+
+    /// Win probability per color, summing to one over the table. A decided game gives
+    /// the winner's team everything.
+    pub fn win_probabilities(&self, state: &State) -> Vec<f64> {
+        let num_players = state.get_num_players();
+        if let Some(winner) = state.winner() {
+            let team_size = (0..num_players)
+                .filter(|&c| state.are_teammates(winner, c))
+                .count() as f64;
+            return (0..num_players)
+                .map(|c| {
+                    if state.are_teammates(winner, c) {
+                        1.0 / team_size
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+        }
+        let raw: Vec<f64> = (0..num_players)
+            .map(|c| self.probability(&win_features(state, c)))
+            .collect();
+        let total: f64 = raw.iter().sum();
+        raw.iter().map(|p| p / total).collect()
+    }
+
+    /// Chance `color`'s side wins: its own share plus its teammates'
+    pub fn team_win_probability(&self, state: &State, color: u8) -> f64 {
+        self.win_probabilities(state)
+            .iter()
+            .enumerate()
+            .filter(|&(c, _)| state.are_teammates(color, c as u8))
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    /// Maximum-likelihood fit to (features, won) samples by Newton's method; None
+    /// without samples
+    pub fn fit(samples: &[(WinFeatures, bool)]) -> Option<WinModel> {
+        const N: usize = NUM_WIN_FEATURES + 1;
+        if samples.is_empty() {
+            return None;
+        }
+        let mut beta = [0.0; N];
+        for _ in 0..FIT_ITERATIONS {
+            let mut gradient = [0.0; N];
+            let mut hessian = [[0.0; N]; N];
+            for (i, row) in hessian.iter_mut().enumerate() {
+                row[i] = FIT_RIDGE;
+            }
+            for (features, won) in samples {
+                let x: [f64; N] =
+                    std::array::from_fn(|i| if i == 0 { 1.0 } else { features[i - 1] });
+                let z: f64 = x.iter().zip(&beta).map(|(x, b)| x * b).sum();
+                let p = 1.0 / (1.0 + (-z).exp());
+                let y = if *won { 1.0 } else { 0.0 };
+                for i in 0..N {
+                    gradient[i] += (y - p) * x[i];
+                    for j in 0..N {
+                        hessian[i][j] += p * (1.0 - p) * x[i] * x[j];
+                    }
+                }
+            }
+            let step = solve(hessian, gradient)?;
+            for (b, s) in beta.iter_mut().zip(step) {
+                *b += s;
+            }
+            if step.iter().all(|s| s.abs() < 1e-9) {
+                break;
+            }
+        }
+        Some(WinModel {
+            intercept: beta[0],
+            weights: std::array::from_fn(|i| beta[i + 1]),
+        })
+    }
+
+    /// Predicted against observed win rate of `samples`, in `buckets` equal-width bins
+    /// of predicted probability. Empty bins are left out.
+    pub fn calibration(
+        &self,
+        samples: &[(WinFeatures, bool)],
+        buckets: usize,
+    ) -> Vec<CalibrationBucket> {
+        let mut bins = vec![CalibrationBucket::default(); buckets];
+        for (features, won) in samples {
+            let p = self.probability(features);
+            let bin = &mut bins[((p * buckets as f64) as usize).min(buckets - 1)];
+            bin.samples += 1;
+            bin.predicted += p;
+            bin.observed += *won as u32 as f64;
+        }
+        bins.into_iter()
+            .filter(|bin| bin.samples > 0)
+            .map(|bin| CalibrationBucket {
+                predicted: bin.predicted / bin.samples as f64,
+                observed: bin.observed / bin.samples as f64,
+                ..bin
+            })
+            .collect()
+    }
+}
+
+/// One bin of `WinModel::calibration`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CalibrationBucket {
+    pub samples: usize,
+    /// Mean predicted win probability
+    pub predicted: f64,
+    /// Share of the samples that won
+    pub observed: f64,
+}
+
+/// Solve `a x = b` by Gaussian elimination with partial pivoting; None when singular
+fn solve<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+    for col in 0..N {
+        let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..N {
+            let factor = a[row][col] / a[col][col];
+            let (above, below) = a.split_at_mut(row);
+            for (target, pivot) in below[0][col..].iter_mut().zip(&above[col][col..]) {
+                *target -= factor * pivot;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let tail: f64 = (row + 1..N).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_fit_recovers_the_generating_coefficients() {
+        let truth = WinModel {
+            intercept: -1.2,
+            weights: [0.6, 0.15, 0.3],
+        };
+        let mut rng = XorShiftRng::seed_from_u64(3);
+        let samples: Vec<(WinFeatures, bool)> = (0..20_000)
+            .map(|_| {
+                let features = [
+                    rng.gen_range(-6.0..6.0),
+                    rng.gen_range(-10.0..10.0),
+                    rng.gen_range(-4.0..4.0),
+                ];
+                (features, rng.gen_bool(truth.probability(&features)))
+            })
+            .collect();
+
+        let fitted = WinModel::fit(&samples).unwrap();
+        assert!(
+            (fitted.intercept - truth.intercept).abs() < 0.1,
+            "{fitted:?}"
+        );
+        for (fitted, truth) in fitted.weights.iter().zip(truth.weights) {
+            assert!((fitted - truth).abs() < 0.05, "{fitted} vs {truth}");
+        }
+        for bucket in fitted.calibration(&samples, 10) {
+            assert!(
+                (bucket.predicted - bucket.observed).abs() < 0.05,
+                "{bucket:?}"
+            );
+        }
+        assert_eq!(WinModel::fit(&[]), None);
+    }
+
+    #[test]
+    fn test_probabilities_favor_the_leader_and_sum_to_one() {
+        let mut state = State::new_base();
+        while state.is_initial_build_phase() {
+            let action = state.generate_playable_actions()[0];
+            state.apply_action(action);
+        }
+        let probabilities = FITTED_WIN_MODEL.win_probabilities(&state);
+        assert_eq!(probabilities.len(), 4);
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let dots = |c: u8| state.get_effective_production(c).iter().sum::<f64>();
+        let leader = (0..4).max_by(|&a, &b| dots(a).total_cmp(&dots(b))).unwrap();
+        let trailer = (0..4).min_by(|&a, &b| dots(a).total_cmp(&dots(b))).unwrap();
+        assert!(probabilities[leader as usize] >= probabilities[trailer as usize]);
+        assert_eq!(
+            FITTED_WIN_MODEL.team_win_probability(&state, leader),
+            probabilities[leader as usize]
+        );
+    }
+}
@@ -9,6 +9,7 @@ use crate::enums::{Action, ActionPrompt};
 use crate::players::nn::{candle_impl::CandleNet, types::PolicyValueNet};
 use crate::state::State;
 
+use super::win_model::FITTED_WIN_MODEL;
 use super::BotPlayer;
 
 // Hyperparameters
@@ -29,9 +30,6 @@ const ROOT_DIRICHLET_ALPHA_BASE: f64 = 10.0; // alpha ≈ 10/|A|
 /// Minimal AlphaZero-style player with pure MCTS (no NN yet)
 /// Single-threaded, from-scratch search per move for correctness and simplicity
 pub struct AlphaZeroPlayer {
-    pub id: String,
-    pub name: String,
-    pub color: String,
     simulations: usize,
     exploration_constant: f64,
     base_seed: u64,
@@ -49,14 +47,17 @@ pub struct AlphaZeroPlayer {
     root_temperature: f64,
 }
 
+impl Default for AlphaZeroPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AlphaZeroPlayer {
-    pub fn new(id: String, name: String, color: String) -> Self {
+    pub fn new() -> Self {
         let net: Box<dyn PolicyValueNet> =
             Box::new(CandleNet::new_default_device().expect("Candle device init"));
         Self {
-            id,
-            name,
-            color,
             simulations: ALPHAZERO_DEFAULT_SIMULATIONS,
             exploration_constant: ALPHAZERO_EXPLORATION_CONSTANT,
             base_seed: DEFAULT_BASE_SEED,
@@ -70,19 +71,10 @@ impl AlphaZeroPlayer {
         }
     }
 
-    pub fn with_parameters(
-        id: String,
-        name: String,
-        color: String,
-        simulations: usize,
-        exploration_constant: f64,
-    ) -> Self {
+    pub fn with_parameters(simulations: usize, exploration_constant: f64) -> Self {
         let net: Box<dyn PolicyValueNet> =
             Box::new(CandleNet::new_default_device().expect("Candle device init"));
         Self {
-            id,
-            name,
-            color,
             simulations,
             exploration_constant,
             base_seed: DEFAULT_BASE_SEED,
@@ -102,9 +94,6 @@ impl AlphaZeroPlayer {
 
     /// Construct with full parameter control (useful for training-time speed).
     pub fn with_parameters_full(
-        id: String,
-        name: String,
-        color: String,
         simulations: usize,
         exploration_constant: f64,
         decide_time_budget_ms: u64,
@@ -114,9 +103,6 @@ impl AlphaZeroPlayer {
         let net: Box<dyn PolicyValueNet> =
             Box::new(CandleNet::new_default_device().expect("Candle device init"));
         Self {
-            id,
-            name,
-            color,
             simulations,
             exploration_constant,
             base_seed: DEFAULT_BASE_SEED,
@@ -645,7 +631,11 @@ fn simulate_random_playout<R: Rng + ?Sized>(
     let mut state = start.clone();
     for _ in 0..max_steps {
         if let Some(winner) = state.winner() {
-            return if winner == root_player { 1.0 } else { 0.0 };
+            return if state.are_teammates(winner, root_player) {
+                1.0
+            } else {
+                0.0
+            };
         }
         let actions = state.generate_playable_actions();
         if actions.is_empty() {
@@ -654,7 +644,8 @@ fn simulate_random_playout<R: Rng + ?Sized>(
         let action = select_smart_rollout_action(&state, &actions, rng);
         state.apply_action(action);
     }
-    0.0
+    // Cut off before anyone won: score the final position instead of calling it a loss
+    FITTED_WIN_MODEL.team_win_probability(&state, root_player)
 }
 
 fn select_smart_rollout_action<R: Rng + ?Sized>(
@@ -681,7 +672,7 @@ fn select_smart_rollout_action<R: Rng + ?Sized>(
         best
     } else if coin < SMART_ROLLOUT_EXPLOIT_PCT + SMART_ROLLOUT_EXPLORE_PCT {
         // Explore: uniform random
-        return *actions.choose(rng).expect("Non-empty actions for explore");
+        *actions.choose(rng).expect("Non-empty actions for explore")
     } else {
         // Strategic nudge: prioritize growth actions slightly
         let mut best = actions[0];
@@ -718,7 +709,7 @@ fn heuristic_action_score(state: &State, action: Action) -> f64 {
 
     // Slight preference for building roads/settlements/cities vs. no-op
     let build_bias = match action {
-        Action::BuildRoad { .. } => 0.2,
+        Action::BuildRoad { .. } | Action::BuildShip { .. } => 0.2,
         Action::BuildSettlement { .. } => 0.6,
         Action::BuildCity { .. } => 0.8,
         Action::BuyDevelopmentCard { .. } => 0.3,
@@ -731,7 +722,7 @@ fn heuristic_action_score(state: &State, action: Action) -> f64 {
 
 fn strategic_bias(_state: &State, action: Action) -> f64 {
     match action {
-        Action::BuildRoad { .. } => 0.1,
+        Action::BuildRoad { .. } | Action::BuildShip { .. } => 0.1,
         Action::BuildSettlement { .. } => 0.2,
         Action::BuildCity { .. } => 0.25,
         Action::BuyDevelopmentCard { .. } => 0.05,
@@ -0,0 +1,56 @@
+//! The stable API for bot authors. Everything re-exported here follows semver: it only
+//! changes incompatibly with a major version bump. The other modules are internals that
+//! catan-server and the bundled tools build on, and may change in any release.
+//!
+//! A bot reads the position through `State`'s getters, picks one of the legal actions
+//! it is handed, and can look ahead by applying actions to a clone:
+//!
+//! ```
+//! use catan_core::prelude::*;
+//!
+//! /// Builds whenever it can, otherwise plays the first legal action
+//! struct Builder;
+//!
+//! impl BotPlayer for Builder {
+//!     fn decide(&self, state: &State, playable_actions: &[Action]) -> Action {
+//!         let points = |action: &Action| {
+//!             let mut next = state.clone();
+//!             next.apply_action(*action);
+//!             next.get_actual_victory_points(state.get_current_color())
+//!         };
+//!         playable_actions
+//!             .iter()
+//!             .copied()
+//!             .max_by_key(points)
+//!             .expect("the engine always offers an action")
+//!     }
+//! }
+//!
+//! let bots: Vec<SeatedBot> = (0..4)
+//!     .map(|seat| -> SeatedBot {
+//!         let engine: Box<dyn BotPlayer> = if seat == 0 {
+//!             Box::new(Builder)
+//!         } else {
+//!             Box::new(RandomPlayer::new())
+//!         };
+//!         SeatedBot::new(seat, engine)
+//!     })
+//!     .collect();
+//! let record = run_game(&bots, GameConfiguration::default(), 7);
+//! assert_eq!(record.lineup[0], "Builder");
+//! assert!(!record.actions.is_empty());
+//! ```
+
+pub use crate::enums::{Action, ActionPrompt, DevCard, GameConfiguration, MapType, Resource};
+pub use crate::errors::{CatanError, CatanResult};
+pub use crate::map_instance::{EdgeId, MapInstance, NodeId};
+pub use crate::map_template::Coordinate;
+pub use crate::players::{
+    AlphaBetaPlayer, BotPlayer, Deliberation, GreedyPlayer, RandomPlayer, SeatedBot,
+    ValueFunctionPlayer, WeightedRandomPlayer,
+};
+pub use crate::simulation::{
+    new_game_state, run_from, run_game, GameObserver, GameRecord, SimulationSettings, Termination,
+};
+pub use crate::state::State;
+pub use crate::{RULES_REVISION, VERSION};
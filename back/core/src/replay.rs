@@ -0,0 +1,381 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::enums::{Action, DiceMode, GameConfiguration, RulesVersion};
+use crate::errors::{CatanResult, GameError, InfrastructureError};
+use crate::map_instance::MapInstance;
+use crate::state::seating::OpeningRoll;
+use crate::state::State;
+use crate::{RULES_REVISION, VERSION};
+
+/// A recorded game: everything needed to rebuild the starting position plus the
+/// actions that were applied to it. Re-simulating a replay must land on `final_hash`,
+/// which makes replays usable as regression checks across engine refactors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    /// Engine that recorded the game; empty for replays older than this field
+    #[serde(default)]
+    pub engine_version: String,
+    /// `RULES_REVISION` the game was played under
+    #[serde(default = "first_rules_revision")]
+    pub rules_revision: u32,
+    pub config: GameConfiguration,
+    pub map_seed: u64,
+    /// Seed for in-game randomness (deck order, dice, steals)
+    pub seed: u64,
+    /// Development cards each color holds before the first action
+    #[serde(default)]
+    pub starting_dev_hands: Vec<Vec<u8>>,
+    /// Dice rolled to decide who went first, when the game was seated that way
+    #[serde(default)]
+    pub opening_rolls: Vec<OpeningRoll>,
+    pub actions: Vec<Action>,
+    /// Roll results in order, when the dice come from a deck instead of 2d6
+    #[serde(default)]
+    pub drawn_cards: Vec<(u8, u8)>,
+    pub final_hash: u64,
+}
+
+impl Replay {
+    /// Start recording from a fresh state (no actions applied yet)
+    pub fn record_start(state: &State, map_seed: u64) -> Self {
+        let starting_dev_hands = (0..state.get_num_players())
+            .map(|color| state.get_player_devhand(color).to_vec())
+            .collect();
+        Self {
+            engine_version: VERSION.to_string(),
+            rules_revision: state.rules().revision(),
+            config: state.get_config().as_ref().clone(),
+            map_seed,
+            seed: state.get_seed(),
+            starting_dev_hands,
+            opening_rolls: state.get_opening_rolls().to_vec(),
+            actions: Vec::new(),
+            drawn_cards: Vec::new(),
+            final_hash: state.compute_hash64(),
+        }
+    }
+
+    /// Where this game's rolls came from
+    pub fn dice_mode(&self) -> DiceMode {
+        self.config.variants.dice
+    }
+
+    /// Record an action. `state` is the position right after it was applied.
+    pub fn push(&mut self, action: Action, state: &State) {
+        self.actions.push(action);
+        if matches!(action, Action::Roll { .. }) && self.dice_mode() != DiceMode::Standard {
+            if let Some(dice) = state.get_last_dice_roll() {
+                self.drawn_cards.push(dice);
+            }
+        }
+    }
+
+    /// Record the hash of the position the game ended in
+    pub fn finish(&mut self, state: &State) {
+        self.final_hash = state.compute_hash64();
+    }
+
+    /// Rebuild the starting position described by this replay, under the rules it was
+    /// recorded with
+    pub fn initial_state(&self) -> State {
+        let map_instance = MapInstance::shared(&self.config.map_type, self.map_seed);
+        let config = GameConfiguration {
+            rules: RulesVersion::for_revision(self.rules_revision),
+            ..self.config.clone()
+        };
+        let mut state = State::new_with_seed(Arc::new(config), map_instance, self.seed);
+        for (color, hand) in self.starting_dev_hands.iter().enumerate() {
+            for (card_idx, &count) in hand.iter().enumerate() {
+                for _ in 0..count {
+                    state.add_dev_card(color as u8, card_idx);
+                }
+            }
+        }
+        state
+    }
+
+    /// Re-simulate the replay, checking every action is legal where it was recorded.
+    /// Returns the final state.
+    pub fn resimulate(&self) -> CatanResult<State> {
+        if self.rules_revision > RULES_REVISION {
+            return Err(GameError::InvalidStateTransition {
+                details: format!(
+                    "recorded under rules revision {} by engine {}, this engine plays revision {}",
+                    self.rules_revision, self.engine_version, RULES_REVISION
+                ),
+            }
+            .into());
+        }
+        let mut state = self.initial_state();
+        if state.get_opening_rolls() != self.opening_rolls {
+            return Err(GameError::InvalidStateTransition {
+                details: format!(
+                    "recorded opening rolls {:?}, replayed {:?}",
+                    self.opening_rolls,
+                    state.get_opening_rolls()
+                ),
+            }
+            .into());
+        }
+        let mut drawn_cards = self.drawn_cards.iter();
+        for (i, &action) in self.actions.iter().enumerate() {
+            // Custom discards, offers and counter-offers are legal without being generated
+            if let Err(rule) = state.validate_action(&action) {
+                return Err(GameError::InvalidStateTransition {
+                    details: format!(
+                        "action #{i} {action:?} is not legal in the replayed state: {rule}"
+                    ),
+                }
+                .into());
+            }
+            state.apply_action(action);
+            if matches!(action, Action::Roll { .. }) && !self.drawn_cards.is_empty() {
+                let recorded = drawn_cards.next().copied();
+                if recorded != state.get_last_dice_roll() {
+                    return Err(GameError::InvalidStateTransition {
+                        details: format!(
+                            "action #{i}: recorded draw {recorded:?}, replayed {:?}",
+                            state.get_last_dice_roll()
+                        ),
+                    }
+                    .into());
+                }
+            }
+        }
+        Ok(state)
+    }
+
+    /// Re-simulate and compare against the recorded final hash
+    pub fn verify(&self) -> CatanResult<()> {
+        let state = self.resimulate()?;
+        let hash = state.compute_hash64();
+        if hash != self.final_hash {
+            return Err(GameError::InvalidStateTransition {
+                details: format!(
+                    "final hash mismatch: recorded {:#x}, replayed {:#x}",
+                    self.final_hash, hash
+                ),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> CatanResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| persistence_error(path, e))?;
+        serde_json::from_str(&text).map_err(|e| persistence_error(path, e))
+    }
+
+    pub fn save(&self, path: &Path) -> CatanResult<()> {
+        let text = serde_json::to_string(self).map_err(|e| persistence_error(path, e))?;
+        std::fs::write(path, text).map_err(|e| persistence_error(path, e))
+    }
+}
+
+/// Replays recorded before the revision was stored
+fn first_rules_revision() -> u32 {
+    1
+}
+
+pub(crate) fn persistence_error(
+    path: &Path,
+    e: impl std::fmt::Display,
+) -> crate::errors::CatanError {
+    InfrastructureError::Persistence {
+        details: format!("{}: {e}", path.display()),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Seating, VariantConfig};
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn record_random_game(steps: usize) -> Replay {
+        record_random_game_with_dice(steps, DiceMode::Standard)
+    }
+
+    fn record_random_game_with_dice(steps: usize, dice: DiceMode) -> Replay {
+        record_random_game_under(steps, dice, RULES_REVISION)
+    }
+
+    fn record_random_game_under(steps: usize, dice: DiceMode, rules_revision: u32) -> Replay {
+        let config = GameConfiguration::builder()
+            .variants(VariantConfig {
+                dice,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        record_random_configured_game(steps, config, rules_revision)
+    }
+
+    fn record_random_configured_game(
+        steps: usize,
+        config: GameConfiguration,
+        rules_revision: u32,
+    ) -> Replay {
+        let mut state = Replay {
+            engine_version: VERSION.to_string(),
+            rules_revision,
+            config,
+            map_seed: 3,
+            seed: 42,
+            starting_dev_hands: Vec::new(),
+            opening_rolls: Vec::new(),
+            actions: Vec::new(),
+            drawn_cards: Vec::new(),
+            final_hash: 0,
+        }
+        .initial_state();
+        let mut replay = Replay::record_start(&state, 3);
+        let mut rng = XorShiftRng::seed_from_u64(7);
+        for _ in 0..steps {
+            if state.winner().is_some() {
+                break;
+            }
+            let actions = state.generate_playable_actions();
+            let action = *actions.choose(&mut rng).unwrap();
+            state.apply_action(action);
+            replay.push(action, &state);
+        }
+        replay.finish(&state);
+        replay
+    }
+
+    #[test]
+    fn test_replay_roundtrip_verifies() {
+        let replay = record_random_game(400);
+        let json = serde_json::to_string(&replay).unwrap();
+        let loaded: Replay = serde_json::from_str(&json).unwrap();
+        assert!(loaded.verify().is_ok());
+    }
+
+    #[test]
+    fn test_rules_revision_is_recorded() {
+        let replay = record_random_game(50);
+        assert_eq!(replay.rules_revision, RULES_REVISION);
+        assert_eq!(replay.engine_version, VERSION);
+
+        // Replays from before the fields existed count as the first revision
+        let mut json: serde_json::Value = serde_json::to_value(&replay).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("rules_revision");
+        fields.remove("engine_version");
+        let old: Replay = serde_json::from_value(json).unwrap();
+        assert_eq!(old.rules_revision, 1);
+        assert!(old.engine_version.is_empty());
+
+        let mut future = replay;
+        future.rules_revision = RULES_REVISION + 1;
+        assert!(future.verify().is_err());
+    }
+
+    #[test]
+    fn test_legacy_replay_verifies_under_legacy_rules() {
+        let replay = record_random_game_under(600, DiceMode::Standard, 1);
+        assert_eq!(replay.rules_revision, 1);
+        assert_eq!(replay.initial_state().rules(), RulesVersion::Legacy);
+        assert!(replay.verify().is_ok());
+    }
+
+    #[test]
+    fn test_revision_2_replay_plays_cards_the_turn_they_are_bought() {
+        let mut state = record_random_game_under(0, DiceMode::Standard, 2).initial_state();
+        assert_eq!(state.rules(), RulesVersion::Revision2);
+        let mut replay = Replay::record_start(&state, 3);
+        let mut rng = XorShiftRng::seed_from_u64(9);
+        for _ in 0..1000 {
+            if state.winner().is_some() {
+                break;
+            }
+            // Play any card the moment it can be, buying one whenever possible
+            let actions = state.generate_playable_actions();
+            let action = actions
+                .iter()
+                .find(|a| a.kind().starts_with("Play"))
+                .or_else(|| actions.iter().find(|a| a.kind() == "BuyDevelopmentCard"))
+                .copied()
+                .unwrap_or_else(|| *actions.choose(&mut rng).unwrap());
+            state.apply_action(action);
+            replay.push(action, &state);
+        }
+        replay.finish(&state);
+        assert_eq!(replay.rules_revision, 2);
+        assert!(replay.verify().is_ok());
+
+        // The current rules refuse the cards played the turn they were bought
+        replay.rules_revision = RULES_REVISION;
+        assert!(replay.verify().is_err());
+    }
+
+    #[test]
+    fn test_replay_with_counter_offers_verifies() {
+        let mut state = State::new_base();
+        let mut replay = Replay::record_start(&state, 0);
+        let mut rng = XorShiftRng::seed_from_u64(8);
+        let mut counters = 0;
+        for _ in 0..600 {
+            if state.winner().is_some() {
+                break;
+            }
+            let color = state.get_current_color();
+            let action = match state.counter_offer_candidates(color).first() {
+                Some(&trade) => {
+                    counters += 1;
+                    Action::CounterTrade { color, trade }
+                }
+                None => *state.generate_playable_actions().choose(&mut rng).unwrap(),
+            };
+            state.apply_action(action);
+            replay.push(action, &state);
+        }
+        replay.finish(&state);
+        assert!(counters > 0);
+        assert!(replay.verify().is_ok());
+    }
+
+    #[test]
+    fn test_tampered_replay_fails() {
+        let mut replay = record_random_game(400);
+        replay.final_hash ^= 1;
+        assert!(replay.verify().is_err());
+    }
+
+    #[test]
+    fn test_event_card_draws_are_recorded() {
+        let mut replay = record_random_game_with_dice(400, DiceMode::EventCards);
+        let rolls = replay
+            .actions
+            .iter()
+            .filter(|a| matches!(a, Action::Roll { .. }))
+            .count();
+        assert!(rolls > 0);
+        assert_eq!(replay.drawn_cards.len(), rolls);
+        assert!(replay.verify().is_ok());
+
+        replay.drawn_cards[0] = (replay.drawn_cards[0].1 % 6 + 1, 7);
+        assert!(replay.verify().is_err());
+    }
+
+    #[test]
+    fn test_opening_rolls_are_recorded() {
+        let config = GameConfiguration::builder()
+            .seating(Seating::RollForFirst)
+            .build()
+            .unwrap();
+        let mut replay = record_random_configured_game(200, config, RULES_REVISION);
+        assert!(replay.opening_rolls.len() >= 4);
+        assert!(replay.verify().is_ok());
+
+        replay.opening_rolls[0].1 = (6, 6);
+        replay.opening_rolls[1].1 = (1, 1);
+        assert!(replay.verify().is_err());
+    }
+}
@@ -0,0 +1,511 @@
+// Bot-vs-bot game loop. `simulate`, the ladder arena and playouts all drive games
+// through `run_from`, so loop detection, action limits and termination are decided in
+// one place. Callers that need more than the returned record (action statistics,
+// replays, verbose logs) hook in with a `GameObserver`.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::dice_stats::DiceHistogram;
+use crate::enums::{Action, GameConfiguration};
+use crate::errors::CatanResult;
+use crate::map_instance::MapInstance;
+use crate::players::{BotPlayer, SeatedBot};
+use crate::replay::persistence_error;
+use crate::state::loop_detection::LoopDetector;
+use crate::state::State;
+use crate::VERSION;
+
+/// Actions a game may run for before it is abandoned as unfinished
+pub const DEFAULT_MAX_ACTIONS: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct SimulationSettings {
+    pub max_actions: usize,
+    /// Force EndTurn when a position repeats within a turn (see `LoopDetector`)
+    pub break_loops: bool,
+}
+
+impl Default for SimulationSettings {
+    fn default() -> Self {
+        Self {
+            max_actions: DEFAULT_MAX_ACTIONS,
+            break_loops: true,
+        }
+    }
+}
+
+/// Why a simulated game stopped
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum Termination {
+    Victory,
+    /// `max_actions` reached without a winner
+    ActionLimit,
+    /// The side to move had no legal action; `prompt` is the state's action prompt
+    NoActions {
+        prompt: String,
+    },
+}
+
+/// Wall-clock time a game took, in milliseconds
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameTimings {
+    pub total_ms: f64,
+    /// Time each color's bot spent deciding
+    pub decision_ms: Vec<f64>,
+}
+
+/// One simulated game: how it was set up, every action, and how it ended. Serialized
+/// as JSON for training data and tournament results; games started with
+/// `new_game_state` can be rebuilt from `config` and `seed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub engine_version: String,
+    pub rules_revision: u32,
+    pub config: GameConfiguration,
+    pub seed: u64,
+    /// Bot name per color
+    pub lineup: Vec<String>,
+    pub winner: Option<u8>,
+    pub termination: Termination,
+    /// Every action applied, including EndTurns forced by loop detection
+    pub actions: Vec<Action>,
+    /// Actual victory points per color after each completed turn
+    pub vps_by_turn: Vec<Vec<u8>>,
+    /// Actual victory points per color at the end
+    pub final_vps: Vec<u8>,
+    pub loop_breaks: u32,
+    pub timings: GameTimings,
+    /// Totals rolled during the game
+    #[serde(default)]
+    pub dice: DiceHistogram,
+}
+
+impl GameRecord {
+    /// Turns completed (EndTurns applied)
+    pub fn turns(&self) -> usize {
+        self.vps_by_turn.len()
+    }
+
+    /// `color`'s lead over the best other color after each turn (negative when behind)
+    pub fn margin_by_turn(&self, color: u8) -> Vec<i16> {
+        self.vps_by_turn
+            .iter()
+            .map(|vps| {
+                let own = vps[color as usize] as i16;
+                let best_other = vps
+                    .iter()
+                    .enumerate()
+                    .filter(|&(c, _)| c != color as usize)
+                    .map(|(_, &vp)| vp as i16)
+                    .max()
+                    .unwrap_or(0);
+                own - best_other
+            })
+            .collect()
+    }
+
+    pub fn load(path: &Path) -> CatanResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| persistence_error(path, e))?;
+        serde_json::from_str(&text).map_err(|e| persistence_error(path, e))
+    }
+
+    pub fn save(&self, path: &Path) -> CatanResult<()> {
+        let text = serde_json::to_string(self).map_err(|e| persistence_error(path, e))?;
+        std::fs::write(path, text).map_err(|e| persistence_error(path, e))
+    }
+
+    /// Apply `action` and record it, with a VP snapshot when it ends a turn and the
+    /// total when it is a roll
+    fn apply(&mut self, state: &mut State, action: Action, observer: &mut dyn GameObserver) {
+        state.apply_action(action);
+        self.actions.push(action);
+        match action {
+            Action::EndTurn { .. } => self.vps_by_turn.push(actual_vps(state)),
+            Action::Roll { .. } => {
+                if let Some(dice) = state.get_last_dice_roll() {
+                    self.dice.record(dice);
+                }
+            }
+            _ => {}
+        }
+        observer.on_action(state, action);
+    }
+}
+
+/// Hooks into `run_from`. Every method defaults to doing nothing.
+pub trait GameObserver {
+    /// A bot chose `action` in `state`, before it is applied
+    fn on_decision(&mut self, _state: &State, _action: Action) {}
+    /// `action` was applied and `state` is the position right after it. Also called
+    /// for EndTurns forced by loop detection.
+    fn on_action(&mut self, _state: &State, _action: Action) {}
+    /// The game stopped in `state`
+    fn on_finish(&mut self, _state: &State, _record: &GameRecord) {}
+}
+
+impl GameObserver for () {}
+
+/// Fresh game for `config`, with the map and the in-game randomness both seeded by `seed`
+pub fn new_game_state(config: GameConfiguration, seed: u64) -> State {
+    let map_instance = MapInstance::shared(&config.map_type, seed);
+    State::new_with_seed(Arc::new(config), map_instance, seed)
+}
+
+/// Play a game from the start with default settings. `bots[c]` plays color `c`.
+pub fn run_game(bots: &[SeatedBot], config: GameConfiguration, seed: u64) -> GameRecord {
+    run_from(
+        new_game_state(config, seed),
+        bots,
+        &SimulationSettings::default(),
+        &mut (),
+    )
+}
+
+/// Play `state` out until someone wins, nobody can move or `max_actions` is reached
+pub fn run_from(
+    mut state: State,
+    bots: &[SeatedBot],
+    settings: &SimulationSettings,
+    observer: &mut dyn GameObserver,
+) -> GameRecord {
+    let num_players = state.get_num_players();
+    let mut record = GameRecord {
+        engine_version: VERSION.to_string(),
+        rules_revision: state.rules().revision(),
+        config: state.get_config().as_ref().clone(),
+        seed: state.get_seed(),
+        lineup: bots.iter().map(|bot| bot.name()).collect(),
+        winner: None,
+        termination: Termination::ActionLimit,
+        actions: Vec::new(),
+        vps_by_turn: Vec::new(),
+        final_vps: Vec::new(),
+        loop_breaks: 0,
+        dice: DiceHistogram::default(),
+        timings: GameTimings {
+            total_ms: 0.0,
+            decision_ms: vec![0.0; num_players as usize],
+        },
+    };
+    let started = Instant::now();
+    let mut loop_detector = LoopDetector::new();
+    let mut actions = Vec::new();
+
+    while record.actions.len() < settings.max_actions {
+        if state.winner().is_some() {
+            record.termination = Termination::Victory;
+            break;
+        }
+        state.generate_playable_actions_into(&mut actions);
+        if actions.is_empty() {
+            record.termination = Termination::NoActions {
+                prompt: format!("{:?}", state.get_action_prompt()),
+            };
+            break;
+        }
+        let color = state.get_current_color() as usize;
+        let deciding = Instant::now();
+        let action = match bots[color].decide(&state, &actions) {
+            Action::Discard { color: seat, .. } => Action::Discard {
+                color: seat,
+                resources: bots[color].choose_discard(&state, seat),
+            },
+            declined @ (Action::RejectTrade { color: seat }
+            | Action::CancelTrade { color: seat }) => bots[color]
+                .counter_offer(&state, seat)
+                .filter(|_| state.can_counter_trade(seat))
+                .map_or(declined, |trade| Action::CounterTrade {
+                    color: seat,
+                    trade,
+                }),
+            action => action,
+        };
+        record.timings.decision_ms[color] += deciding.elapsed().as_secs_f64() * 1000.0;
+        observer.on_decision(&state, action);
+
+        record.apply(&mut state, action, observer);
+
+        if !settings.break_loops {
+            continue;
+        }
+        if let Some(loop_break) = loop_detector.check(&state, action) {
+            record.loop_breaks += 1;
+            if loop_break.forced_end_turn {
+                let end_turn = Action::EndTurn {
+                    color: loop_break.color,
+                };
+                record.apply(&mut state, end_turn, observer);
+            }
+        }
+    }
+
+    if state.winner().is_some() {
+        record.termination = Termination::Victory;
+    }
+    record.winner = state.winner();
+    record.final_vps = actual_vps(&state);
+    record.timings.total_ms = started.elapsed().as_secs_f64() * 1000.0;
+    observer.on_finish(&state, &record);
+    record
+}
+
+/// Map seeds a tournament plays every matchup on. Each seed is played once per seat
+/// order, so every bot meets the same boards from both sides of the turn order and
+/// per-seed results show which maps a bot struggles on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedPool {
+    pub seeds: Vec<u64>,
+}
+
+/// Who sits where in a pool game, relative to the lineup as listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeatOrder {
+    /// Lineup entry `i` plays color `i`
+    AsListed,
+    /// Lineup entry `i` plays color `n - 1 - i`
+    Reversed,
+}
+
+impl SeatOrder {
+    pub const BOTH: [SeatOrder; 2] = [SeatOrder::AsListed, SeatOrder::Reversed];
+
+    /// Lineup entry playing `color` in a game of `num_players`
+    pub fn entry(self, color: u8, num_players: u8) -> usize {
+        match self {
+            SeatOrder::AsListed => color as usize,
+            SeatOrder::Reversed => (num_players - 1 - color) as usize,
+        }
+    }
+}
+
+impl SeedPool {
+    /// `count` consecutive seeds starting at `base`
+    pub fn consecutive(base: u64, count: usize) -> Self {
+        Self {
+            seeds: (0..count as u64).map(|i| base.wrapping_add(i)).collect(),
+        }
+    }
+
+    /// Parse a comma-separated seed list such as "3,17,42"
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let seeds = list
+            .split(',')
+            .map(|seed| {
+                seed.trim()
+                    .parse()
+                    .map_err(|_| format!("bad seed '{seed}' in seed pool '{list}'"))
+            })
+            .collect::<Result<Vec<u64>, _>>()?;
+        Ok(Self { seeds })
+    }
+
+    /// Every (seed, seat order) pair, in play order
+    pub fn games(&self) -> impl Iterator<Item = (u64, SeatOrder)> + '_ {
+        self.seeds
+            .iter()
+            .flat_map(|&seed| SeatOrder::BOTH.map(|order| (seed, order)))
+    }
+}
+
+/// Results on one pool seed, per lineup entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeedResult {
+    pub seed: u64,
+    pub games: u32,
+    pub unfinished: u32,
+    pub wins: Vec<u32>,
+    /// Victory points summed over the seed's games
+    pub vp_sum: Vec<u32>,
+}
+
+impl SeedResult {
+    pub fn mean_vp(&self, entry: usize) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.vp_sum[entry] as f64 / self.games as f64
+    }
+}
+
+/// Per-seed results of a seed pool tournament, in pool order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeedPoolResults {
+    pub seeds: Vec<SeedResult>,
+}
+
+impl SeedPoolResults {
+    /// Count a finished game played on `record.seed` in seat `order`
+    pub fn record(&mut self, order: SeatOrder, record: &GameRecord) {
+        let num_players = record.final_vps.len() as u8;
+        let index = match self.seeds.iter().position(|r| r.seed == record.seed) {
+            Some(index) => index,
+            None => {
+                self.seeds.push(SeedResult {
+                    seed: record.seed,
+                    wins: vec![0; num_players as usize],
+                    vp_sum: vec![0; num_players as usize],
+                    ..Default::default()
+                });
+                self.seeds.len() - 1
+            }
+        };
+        let result = &mut self.seeds[index];
+        result.games += 1;
+        for (color, &vp) in record.final_vps.iter().enumerate() {
+            result.vp_sum[order.entry(color as u8, num_players)] += vp as u32;
+        }
+        match record.winner {
+            Some(winner) => result.wins[order.entry(winner, num_players)] += 1,
+            None => result.unfinished += 1,
+        }
+    }
+}
+
+fn actual_vps(state: &State) -> Vec<u8> {
+    (0..state.get_num_players())
+        .map(|color| state.get_actual_victory_points(color))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::ValueFunctionPlayer;
+
+    fn value_bots(num_players: u8) -> Vec<SeatedBot> {
+        (0..num_players)
+            .map(|seat| SeatedBot::new(seat, Box::new(ValueFunctionPlayer::new())))
+            .collect()
+    }
+
+    fn two_player_config() -> GameConfiguration {
+        GameConfiguration::builder().num_players(2).build().unwrap()
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        decisions: usize,
+        applied: usize,
+        finished: bool,
+    }
+
+    impl GameObserver for Counter {
+        fn on_decision(&mut self, _state: &State, _action: Action) {
+            self.decisions += 1;
+        }
+        fn on_action(&mut self, _state: &State, _action: Action) {
+            self.applied += 1;
+        }
+        fn on_finish(&mut self, _state: &State, _record: &GameRecord) {
+            self.finished = true;
+        }
+    }
+
+    #[test]
+    fn test_run_game_records_outcome() {
+        let record = run_game(&value_bots(2), two_player_config(), 11);
+
+        assert_eq!(record.seed, 11);
+        assert_eq!(record.final_vps.len(), 2);
+        match record.winner {
+            Some(winner) => {
+                assert_eq!(record.termination, Termination::Victory);
+                assert!(record.final_vps[winner as usize] >= 10);
+            }
+            None => assert_eq!(record.termination, Termination::ActionLimit),
+        }
+    }
+
+    #[test]
+    fn test_action_limit_and_observer() {
+        let bots = value_bots(2);
+        let settings = SimulationSettings {
+            max_actions: 30,
+            ..Default::default()
+        };
+        let mut counter = Counter::default();
+        let record = run_from(
+            new_game_state(two_player_config(), 3),
+            &bots,
+            &settings,
+            &mut counter,
+        );
+
+        assert_eq!(record.termination, Termination::ActionLimit);
+        assert_eq!(record.winner, None);
+        assert!(record.actions.len() >= 30);
+        assert_eq!(counter.applied, record.actions.len());
+        assert!(counter.decisions <= counter.applied);
+        assert!(counter.finished);
+    }
+
+    #[test]
+    fn test_game_record_round_trips_through_json() {
+        let record = run_game(&value_bots(2), two_player_config(), 5);
+        let end_turns = record
+            .actions
+            .iter()
+            .filter(|a| matches!(a, Action::EndTurn { .. }))
+            .count();
+        assert_eq!(record.turns(), end_turns);
+        assert_eq!(record.lineup, vec!["ValueFunctionPlayer"; 2]);
+        assert_eq!(record.timings.decision_ms.len(), 2);
+        let rolls = record
+            .actions
+            .iter()
+            .filter(|a| matches!(a, Action::Roll { .. }))
+            .count();
+        assert_eq!(record.dice.rolls() as usize, rolls);
+        let margins = record.margin_by_turn(0);
+        assert_eq!(margins.len(), record.turns());
+        for (margin, vps) in margins.iter().zip(&record.vps_by_turn) {
+            assert_eq!(*margin, vps[0] as i16 - vps[1] as i16);
+        }
+
+        let path = std::env::temp_dir().join(format!("record_{}.json", std::process::id()));
+        record.save(&path).unwrap();
+        let loaded = GameRecord::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.actions, record.actions);
+        assert_eq!(loaded.vps_by_turn, record.vps_by_turn);
+        assert_eq!(loaded.dice, record.dice);
+        assert_eq!(loaded.termination, record.termination);
+        assert_eq!(loaded.config.num_players, 2);
+        assert_eq!(loaded.timings.decision_ms.len(), 2);
+    }
+
+    #[test]
+    fn test_seed_pool_credits_lineup_entries_in_both_seat_orders() {
+        let pool = SeedPool::parse("4, 9").unwrap();
+        assert_eq!(pool, SeedPool { seeds: vec![4, 9] });
+        assert!(SeedPool::parse("4,x").is_err());
+        assert_eq!(SeedPool::consecutive(7, 3).seeds, vec![7, 8, 9]);
+
+        let mut results = SeedPoolResults::default();
+        for (seed, order) in pool.games() {
+            let mut record = run_game(&value_bots(2), two_player_config(), seed);
+            // Entry 0 always wins, whichever color it played
+            record.winner = Some(order.entry(0, 2) as u8);
+            record.final_vps = match order {
+                SeatOrder::AsListed => vec![10, 4],
+                SeatOrder::Reversed => vec![3, 10],
+            };
+            results.record(order, &record);
+        }
+
+        assert_eq!(results.seeds.len(), 2);
+        for (result, seed) in results.seeds.iter().zip([4, 9]) {
+            assert_eq!(result.seed, seed);
+            assert_eq!(result.games, 2);
+            assert_eq!(result.wins, vec![2, 0]);
+            assert_eq!(result.vp_sum, vec![20, 7]);
+            assert_eq!(result.mean_vp(1), 3.5);
+        }
+    }
+}
@@ -0,0 +1,1168 @@
+use log::debug;
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use crate::{
+    deck_slices::{freqdeck_add, freqdeck_sub, DEVCARD_COST},
+    enums::DevCard,
+};
+use crate::{
+    enums::{ActionPrompt, GameConfiguration, RulesVersion},
+    map_instance::{EdgeId, MapInstance, NodeId},
+    state_vector::{
+        actual_victory_points_index, dev_deck_index, dev_deck_size, initialize_state,
+        pieces_left_start, player_bought_devhand_slice, player_devhand_slice, player_hand_slice,
+        player_pieces_left_slice, player_played_devhand_slice, seating_order_slice, StateVector,
+        BANK_RESOURCE_SLICE, CITIES_LEFT_OFFSET, CURRENT_TICK_SEAT_INDEX, DEV_BANK_PTR_INDEX,
+        FREE_ROADS_AVAILABLE_INDEX, HAS_PLAYED_DEV_CARD, HAS_ROLLED_INDEX, IS_DISCARDING_INDEX,
+        IS_INITIAL_BUILD_PHASE_INDEX, IS_MOVING_ROBBER_INDEX, NUM_RESOURCES, ROADS_LEFT_OFFSET,
+        ROBBER_TILE_INDEX, SETTLEMENTS_LEFT_OFFSET, TOTAL_CITIES_PER_PLAYER,
+        TOTAL_ROADS_PER_PLAYER, TOTAL_SETTLEMENTS_PER_PLAYER,
+    },
+};
+
+pub mod catch_up;
+pub mod debug_dump;
+pub mod diff;
+pub mod fen;
+pub mod handicaps;
+pub mod longest_road;
+pub mod loop_detection;
+pub mod move_application;
+pub mod move_generation;
+pub mod seating;
+pub mod ships;
+pub mod snapshot;
+pub mod teams;
+pub mod trading;
+pub mod validation;
+pub mod variants;
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Building {
+    Settlement(u8, NodeId), // Color, NodeId
+    City(u8, NodeId),       // Color, NodeId
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BuildingType {
+    Settlement,
+    City,
+}
+
+#[derive(Debug)]
+pub struct State {
+    // These two are immutable
+    config: Arc<GameConfiguration>,
+    map_instance: Arc<MapInstance>,
+
+    // This is mutable
+    vector: StateVector,
+
+    // These are caches for speeding up game state calculations
+    board_buildable_ids: HashSet<NodeId>,
+    buildings: HashMap<NodeId, Building>,
+    buildings_by_color: HashMap<u8, Vec<Building>>, // Color -> Buildings
+    roads: HashMap<EdgeId, u8>,                     // (Node1, Node2) -> Color
+    roads_by_color: Vec<u8>,                        // Color -> Count
+    connected_components: HashMap<u8, Vec<HashSet<NodeId>>>,
+    longest_road_color: Option<u8>,
+    longest_road_length: u8,
+    road_lengths: Vec<u8>, // Color -> Longest path, ships included
+    // Seafarers: ships by (low, high) edge, and count per color
+    ships: HashMap<EdgeId, u8>,
+    ships_by_color: Vec<u8>,
+    // Seafarers: ships built this turn, which may not move yet, and whether the player
+    // to move already moved one
+    ships_built_this_turn: Vec<EdgeId>,
+    ship_moved_this_turn: bool,
+    // Seafarers: gold field cards each color still has to pick after a roll, and the
+    // seat that rolled, which gets the turn back once they are picked
+    gold_owed: Vec<u8>,
+    gold_roller_seat: u8,
+    largest_army_color: Option<u8>,
+    largest_army_count: u8,
+    harbormaster_color: Option<u8>,
+    harbormaster_points: u8,
+    // Board stamps: fresh whenever a piece or the robber moves, for the whole board and
+    // for each color whose own production or reach may have changed. Clones keep them,
+    // so evaluation caches can key on a stamp instead of the board itself.
+    board_stamp: u64,
+    color_board_stamps: Vec<u64>,
+    // Production per color in dice probability per resource, in total and without the
+    // nodes the robber blocks. Updated as buildings go up and when the robber moves,
+    // since evaluation asks for it constantly.
+    production: Vec<[f64; NUM_RESOURCES]>,
+    effective_production: Vec<[f64; NUM_RESOURCES]>,
+    // Dice rolled to decide who goes first, when the game was seated that way
+    opening_rolls: Vec<seating::OpeningRoll>,
+
+    // Cached winner to avoid recalculating every time
+    cached_winner: Option<u8>,
+
+    // Store the last dice roll for logging purposes
+    last_dice_roll: Option<(u8, u8)>,
+
+    // Source of all in-game randomness (deck order, dice, steals). Seeded so that a
+    // game can be replayed exactly from its seed and action list.
+    seed: u64,
+    rng: XorShiftRng,
+
+    // Team variant: whether the current player already gifted a card this turn
+    gifted_this_turn: bool,
+
+    // Domestic trading: the offer on the table, and how many the current player made
+    domestic_trade: Option<trading::DomesticTrade>,
+    trade_offers_this_turn: u8,
+
+    // 5–6 players: the seat whose turn just ended, while the others take their Special
+    // Build Phase in seat order
+    special_build_seat: Option<u8>,
+
+    // Event-card dice: remaining deck, top card last. Empty until the first roll.
+    event_deck: Vec<variants::EventCard>,
+    // Balanced dice: outcomes left in the bag
+    dice_bag: Vec<(u8, u8)>,
+}
+
+impl State {
+    pub fn new(config: Arc<GameConfiguration>, map_instance: Arc<MapInstance>) -> Self {
+        Self::new_with_seed(config, map_instance, rand::random())
+    }
+
+    /// Like `new`, but with a fixed seed for the game's randomness (deck, dice, steals)
+    pub fn new_with_seed(
+        config: Arc<GameConfiguration>,
+        map_instance: Arc<MapInstance>,
+        seed: u64,
+    ) -> Self {
+        debug!(
+            "State::new: config={:?}, num_players={}, seed={}",
+            config, config.num_players, seed
+        );
+
+        let mut rng = XorShiftRng::seed_from_u64(seed);
+        let mut vector = initialize_state(config.num_players, &mut rng);
+        let (seating_order, opening_rolls) =
+            seating::seat_order(&config.seating, config.num_players, &mut rng);
+        vector[seating_order_slice(config.num_players as usize)].copy_from_slice(&seating_order);
+        debug!(
+            "State::new: vector initialized, length={}, seating_order={:?}",
+            vector.len(),
+            &vector[seating_order_slice(config.num_players as usize)]
+        );
+
+        // Ensure robber starts on the desert tile (the first one, on boards with two)
+        let desert_tile_id = map_instance
+            .land_tiles
+            .values()
+            .filter(|lt| lt.is_desert())
+            .map(|lt| lt.id)
+            .min()
+            .expect("Desert tile not found in map_instance");
+        vector[ROBBER_TILE_INDEX] = desert_tile_id;
+
+        let board_buildable_ids = map_instance.land_nodes().clone();
+        let buildings = HashMap::new();
+        let buildings_by_color = HashMap::new();
+        let roads = HashMap::new();
+        let roads_by_color = vec![0; config.num_players as usize];
+        let ships_by_color = vec![0; config.num_players as usize];
+        let gold_owed = vec![0; config.num_players as usize];
+        let production = vec![[0.0; NUM_RESOURCES]; config.num_players as usize];
+        let color_board_stamps = (0..config.num_players)
+            .map(|_| fresh_board_stamp())
+            .collect();
+        let mut connected_components = HashMap::new();
+        for color in 0..config.num_players {
+            connected_components.insert(color, Vec::new());
+        }
+        let longest_road_color = None;
+        let longest_road_length = 0;
+        let road_lengths = vec![0; config.num_players as usize];
+        let largest_army_color = None;
+        let largest_army_count = 0;
+
+        let mut state = Self {
+            config,
+            map_instance,
+            vector,
+            board_buildable_ids,
+            buildings,
+            buildings_by_color,
+            roads,
+            roads_by_color,
+            connected_components,
+            longest_road_color,
+            longest_road_length,
+            road_lengths,
+            ships: HashMap::new(),
+            ships_by_color,
+            ships_built_this_turn: Vec::new(),
+            ship_moved_this_turn: false,
+            gold_owed,
+            gold_roller_seat: 0,
+            largest_army_color,
+            largest_army_count,
+            harbormaster_color: None,
+            harbormaster_points: 0,
+            board_stamp: fresh_board_stamp(),
+            color_board_stamps,
+            effective_production: production.clone(),
+            production,
+            opening_rolls,
+            cached_winner: None,
+            last_dice_roll: None,
+            seed,
+            rng,
+            gifted_this_turn: false,
+            domestic_trade: None,
+            trade_offers_this_turn: 0,
+            special_build_seat: None,
+            event_deck: Vec::new(),
+            dice_bag: Vec::new(),
+        };
+        state.apply_handicaps();
+        state
+    }
+
+    pub fn new_base() -> Self {
+        let config = GameConfiguration::default();
+        let map_instance = MapInstance::shared(&config.map_type, 0);
+        State::new(Arc::new(config), map_instance)
+    }
+
+    pub fn get_num_players(&self) -> u8 {
+        self.config.num_players
+    }
+
+    // ===== Getters =====
+    pub fn is_initial_build_phase(&self) -> bool {
+        self.vector[IS_INITIAL_BUILD_PHASE_INDEX] == 1
+    }
+
+    pub fn is_moving_robber(&self) -> bool {
+        self.vector[IS_MOVING_ROBBER_INDEX] == 1
+    }
+
+    pub fn is_discarding(&self) -> bool {
+        self.vector[IS_DISCARDING_INDEX] == 1
+    }
+
+    /// Seafarers: gold field cards `color` still has to pick
+    pub fn get_gold_owed(&self, color: u8) -> u8 {
+        self.gold_owed[color as usize]
+    }
+
+    pub fn get_map_instance(&self) -> &Arc<MapInstance> {
+        &self.map_instance
+    }
+
+    fn is_road_building(&self) -> bool {
+        self.vector[FREE_ROADS_AVAILABLE_INDEX] > 0
+    }
+
+    fn reset_is_road_building(&mut self) {
+        self.vector[FREE_ROADS_AVAILABLE_INDEX] = 0;
+    }
+
+    /// Returns a slice of Colors in the order of seating
+    /// e.g. [2, 1, 0, 3] if Orange goes first, then Blue, then Red, and then White
+    pub fn get_seating_order(&self) -> &[u8] {
+        let slice = seating_order_slice(self.config.num_players as usize);
+        debug!(
+            "get_seating_order: num_players={}, slice={:?}, vector.len()={}",
+            self.config.num_players,
+            slice,
+            self.vector.len()
+        );
+
+        &self.vector[slice]
+    }
+
+    pub fn get_current_tick_seat(&self) -> u8 {
+        self.vector[CURRENT_TICK_SEAT_INDEX]
+    }
+
+    pub fn get_current_color(&self) -> u8 {
+        let seating_order = self.get_seating_order();
+        let current_tick_seat = self.get_current_tick_seat();
+        debug!(
+            "get_current_color: seating_order={:?}, current_tick_seat={}, seating_order.len()={}",
+            seating_order,
+            current_tick_seat,
+            seating_order.len()
+        );
+
+        if current_tick_seat as usize >= seating_order.len() {
+            debug!(
+                "ERROR: current_tick_seat {} is out of bounds for seating_order of length {}",
+                current_tick_seat,
+                seating_order.len()
+            );
+            // Return a default value to avoid panic
+            return 0;
+        }
+
+        seating_order[current_tick_seat as usize]
+    }
+
+    pub fn current_player_rolled(&self) -> bool {
+        self.vector[HAS_ROLLED_INDEX] == 1
+    }
+
+    pub fn can_play_dev(&self, dev_card: u8) -> bool {
+        let color = self.get_current_color();
+        let dev_card_index = dev_card as usize;
+        let unplayable = if self.rules().bought_dev_cards_wait_a_turn() {
+            self.get_bought_dev_card_count(color, dev_card_index)
+        } else {
+            0
+        };
+        let has_one = self.vector[player_devhand_slice(self.config.num_players, color)]
+            [dev_card_index]
+            > unplayable;
+        let has_played_in_turn = self.vector[HAS_PLAYED_DEV_CARD] == 1;
+        has_one && !has_played_in_turn
+    }
+
+    pub fn get_action_prompt(&self) -> ActionPrompt {
+        if self.is_initial_build_phase() {
+            let num_things_built = self.buildings.len() + self.roads.len();
+            let num_players = self.config.num_players as usize;
+
+            if num_things_built == 2 * num_players + self.total_initial_roads() {
+                return ActionPrompt::PlayTurn;
+            }
+
+            // Check the current player's state specifically
+            let current_color = self.get_current_color();
+            let current_player_settlements = self
+                .buildings_by_color
+                .get(&current_color)
+                .map(|buildings| {
+                    buildings
+                        .iter()
+                        .filter(|b| matches!(b, Building::Settlement(_, _)))
+                        .count()
+                })
+                .unwrap_or(0);
+            let current_player_roads = self.roads_by_color[current_color as usize];
+
+            // If player has equal settlements and roads, they need to build a settlement
+            // If player has more settlements than roads, they need to build a road
+            if current_player_settlements == current_player_roads as usize
+                && !self.owes_extra_road(current_color, current_player_settlements)
+            {
+                return ActionPrompt::BuildInitialSettlement;
+            } else {
+                return ActionPrompt::BuildInitialRoad;
+            }
+        } else if self.is_moving_robber() {
+            return ActionPrompt::MoveRobber;
+        } else if self.is_discarding() {
+            return ActionPrompt::Discard;
+        } else if self.gold_owed.iter().any(|&owed| owed > 0) {
+            return ActionPrompt::ChooseGold;
+        } else if let Some(trade) = &self.domestic_trade {
+            return match trade.responder() {
+                Some(_) => ActionPrompt::DecideTrade,
+                None => ActionPrompt::DecideAcceptees,
+            };
+        } else if self.special_build_seat.is_some() {
+            return ActionPrompt::SpecialBuild;
+        }
+        ActionPrompt::PlayTurn
+    }
+
+    // TODO: Maybe move to mutations(?)
+    pub(crate) fn get_mut_player_hand(&mut self, color: u8) -> &mut [u8] {
+        &mut self.vector[player_hand_slice(self.config.num_players, color)]
+    }
+
+    pub fn get_player_hand(&self, color: u8) -> &[u8] {
+        &self.vector[player_hand_slice(self.config.num_players, color)]
+    }
+
+    pub(crate) fn get_mut_player_devhand(&mut self, color: u8) -> &mut [u8] {
+        &mut self.vector[player_devhand_slice(self.config.num_players, color)]
+    }
+
+    pub fn get_player_devhand(&self, color: u8) -> &[u8] {
+        &self.vector[player_devhand_slice(self.config.num_players, color)]
+    }
+
+    pub fn winner(&self) -> Option<u8> {
+        // Return cached result if available
+        if let Some(winner) = self.cached_winner {
+            return Some(winner);
+        }
+
+        // Check ALL players for victory, not just the current player
+        let winner = self.find_winner();
+        if let Some(color) = winner {
+            log::info!(
+                "🎉 GAME WON! Player {} has {} victory points (>= {})",
+                color,
+                self.get_actual_victory_points(color),
+                self.config.vps_to_win
+            );
+        }
+        winner
+    }
+
+    /// Check for victory and update cached winner
+    /// Should be called whenever victory points change
+    pub(crate) fn check_for_victory(&mut self) {
+        if self.cached_winner.is_some() {
+            return; // Already won
+        }
+
+        if let Some(color) = self.find_winner() {
+            log::info!(
+                "🎉 VICTORY! Player {} has {} victory points (>= {})",
+                color,
+                self.get_actual_victory_points(color),
+                self.config.vps_to_win
+            );
+            self.cached_winner = Some(color);
+        }
+    }
+
+    pub fn get_actual_victory_points(&self, color: u8) -> u8 {
+        self.vector[actual_victory_points_index(self.config.num_players, color)]
+    }
+
+    /// Victory points everyone can see: buildings and awards, but not victory point
+    /// cards, which stay hidden in their owner's hand until the game ends
+    pub fn get_public_victory_points(&self, color: u8) -> u8 {
+        let buildings = self.count_buildings(color, BuildingType::Settlement) as usize
+            + 2 * self.count_buildings(color, BuildingType::City) as usize;
+        let awards = [
+            self.get_longest_road_color(),
+            self.get_largest_army_color(),
+            self.get_harbormaster_color(),
+        ]
+        .iter()
+        .filter(|&&holder| holder == Some(color))
+        .count();
+        (buildings + 2 * awards) as u8 + self.get_vp_offset(color)
+    }
+
+    /// Victory points of `color` as `viewer` knows them: its own victory point cards,
+    /// and everyone's once the game is over, but no opponent's before that
+    pub fn get_visible_victory_points(&self, viewer: u8, color: u8) -> u8 {
+        if viewer == color || self.winner().is_some() {
+            self.get_actual_victory_points(color)
+        } else {
+            self.get_public_victory_points(color)
+        }
+    }
+
+    /// Victory point cards `color` holds. They count toward victory as soon as they
+    /// are bought, but only their owner knows about them.
+    pub fn get_victory_point_cards(&self, color: u8) -> u8 {
+        self.get_actual_victory_points(color)
+            .saturating_sub(self.get_public_victory_points(color))
+    }
+
+    pub fn get_roads_by_color(&self) -> &[u8] {
+        &self.roads_by_color
+    }
+
+    /// Returns a list of canonical road edges owned by the given color
+    pub fn get_roads_for_color(&self, color: u8) -> Vec<EdgeId> {
+        self.roads
+            .iter()
+            .filter_map(|(&edge, &owner)| if owner == color { Some(edge) } else { None })
+            .collect()
+    }
+
+    /// Debug method to log current victory points for all players
+    /// Call this occasionally to track VP progression
+    pub fn log_victory_points(&self) {
+        for color in 0..self.get_num_players() {
+            let vp = self.get_actual_victory_points(color);
+            let settlements = self.get_settlements(color).len();
+            let cities = self.get_cities(color).len();
+            log::info!(
+                "🏆 Player {} VP: {} (settlements: {}, cities: {})",
+                color,
+                vp,
+                settlements,
+                cities
+            );
+        }
+    }
+
+    // ===== Board Getters =====
+    pub fn get_cities(&self, color: u8) -> Vec<Building> {
+        let buildings = self.buildings_by_color.get(&color);
+        match buildings {
+            Some(buildings) => buildings
+                .iter()
+                .filter(|building| matches!(building, Building::City(_, _)))
+                .cloned()
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn get_settlements(&self, color: u8) -> Vec<Building> {
+        let buildings = self.buildings_by_color.get(&color);
+        match buildings {
+            Some(buildings) => buildings
+                .iter()
+                .filter(|building| matches!(building, Building::Settlement(_, _)))
+                .cloned()
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn get_building_type(&self, node_id: NodeId) -> Option<BuildingType> {
+        self.buildings.get(&node_id).map(|building| match building {
+            Building::Settlement(_, _) => BuildingType::Settlement,
+            Building::City(_, _) => BuildingType::City,
+        })
+    }
+
+    pub fn board_buildable_edges(&self, color: u8) -> Vec<EdgeId> {
+        self.buildable_edges(color).collect()
+    }
+
+    /// Empty edges touching `color`'s road network, each once, without allocating
+    pub(crate) fn buildable_edges(&self, color: u8) -> impl Iterator<Item = EdgeId> + '_ {
+        let network = self.road_network_nodes(color);
+        let mut in_network = NodeMask::default();
+        for node in network.clone() {
+            in_network.insert(node);
+        }
+        network.flat_map(move |node| {
+            self.map_instance
+                .get_neighbor_edges(node)
+                .iter()
+                .filter_map(move |&(a, b)| {
+                    let other = if a == node { b } else { a };
+                    // An edge between two network nodes is reported from its lower end only
+                    if in_network.contains(other) && other < node {
+                        return None;
+                    }
+                    let canonical_edge = (a.min(b), a.max(b));
+                    (!self.roads.contains_key(&canonical_edge)
+                        && !self.ships.contains_key(&canonical_edge))
+                    .then_some(canonical_edge)
+                })
+        })
+    }
+
+    /// Whether a settlement could go on `node_id` under the distance rule, ignoring
+    /// road connectivity
+    pub fn is_board_buildable(&self, node_id: NodeId) -> bool {
+        self.board_buildable_ids.contains(&node_id)
+    }
+
+    pub fn buildable_node_ids(&self, color: u8) -> Vec<u8> {
+        self.buildable_nodes(color).collect()
+    }
+
+    /// Nodes on `color`'s roads or ships that pass the distance rule, without allocating
+    pub(crate) fn buildable_nodes(&self, color: u8) -> impl Iterator<Item = NodeId> + '_ {
+        let mut seen = NodeMask::default();
+        self.road_network_nodes(color)
+            .chain(self.ship_network_nodes(color))
+            .filter(move |&node| seen.insert(node) && self.board_buildable_ids.contains(&node))
+    }
+
+    /// Every node touched by `color`'s roads, each once
+    fn road_network_nodes(&self, color: u8) -> impl Iterator<Item = NodeId> + Clone + '_ {
+        let mut seen = NodeMask::default();
+        self.connected_components
+            .get(&color)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .filter(move |&node| seen.insert(node))
+    }
+
+    fn get_connected_component_index(&self, color: u8, a: u8) -> Option<usize> {
+        let components = self.connected_components.get(&color).unwrap();
+        for (i, component) in components.iter().enumerate() {
+            if component.contains(&a) {
+                return Some(i);
+            }
+        }
+        None
+    }
+
+    pub fn get_node_color(&self, node_id: NodeId) -> Option<u8> {
+        self.buildings.get(&node_id).map(|building| match building {
+            Building::Settlement(owner_color, _) => *owner_color,
+            Building::City(owner_color, _) => *owner_color,
+        })
+    }
+
+    pub fn is_enemy_node(&self, color: u8, node_id: NodeId) -> bool {
+        match self.get_node_color(node_id) {
+            None => false, // No building, so not an enemy node
+            Some(node_owner_color) => node_owner_color != color, // It's an enemy if the owner is different
+        }
+    }
+
+    pub(crate) fn add_dev_card(&mut self, color: u8, card_idx: usize) {
+        self.vector[player_devhand_slice(self.config.num_players, color)][card_idx] += 1;
+    }
+
+    pub fn get_dev_card_count(&self, color: u8, card_idx: usize) -> u8 {
+        self.vector[player_devhand_slice(self.config.num_players, color)][card_idx]
+    }
+
+    pub fn get_played_dev_card_count(&self, color: u8, card_idx: usize) -> u8 {
+        self.vector[player_played_devhand_slice(self.config.num_players, color)][card_idx]
+    }
+
+    /// Cards of `card_idx` that `color` bought this turn and so cannot play yet
+    pub fn get_bought_dev_card_count(&self, color: u8, card_idx: usize) -> u8 {
+        let bought = &self.vector[player_bought_devhand_slice(self.config.num_players, color)];
+        bought.get(card_idx).copied().unwrap_or(0)
+    }
+
+    /// Cards bought this turn become playable
+    pub(crate) fn clear_bought_dev_cards(&mut self) {
+        for color in 0..self.config.num_players {
+            self.vector[player_bought_devhand_slice(self.config.num_players, color)].fill(0);
+        }
+    }
+
+    pub(crate) fn add_played_dev_card(&mut self, color: u8, card_idx: usize) {
+        self.vector[player_played_devhand_slice(self.config.num_players, color)][card_idx] += 1;
+    }
+
+    pub(crate) fn remove_dev_card(&mut self, color: u8, card_idx: usize) {
+        self.vector[player_devhand_slice(self.config.num_players, color)][card_idx] -= 1;
+    }
+
+    pub(crate) fn set_has_played_dev_card(&mut self) {
+        self.vector[HAS_PLAYED_DEV_CARD] = 1;
+    }
+
+    pub(crate) fn set_is_moving_robber(&mut self) {
+        self.vector[IS_MOVING_ROBBER_INDEX] = 1;
+    }
+
+    pub(crate) fn clear_is_moving_robber(&mut self) {
+        self.vector[IS_MOVING_ROBBER_INDEX] = 0;
+    }
+
+    pub fn bank_has_resource(&self, resource: u8) -> bool {
+        self.vector[BANK_RESOURCE_SLICE][resource as usize] > 0
+    }
+
+    #[doc(hidden)]
+    pub fn from_bank_to_player(&mut self, color: u8, resource: u8) {
+        let resource_idx = resource as usize;
+        self.vector[BANK_RESOURCE_SLICE][resource_idx] -= 1;
+        self.get_mut_player_hand(color)[resource_idx] += 1;
+    }
+
+    #[doc(hidden)]
+    pub fn from_player_to_bank(&mut self, color: u8, resource: u8, amount: u8) {
+        let resource_idx = resource as usize;
+        self.get_mut_player_hand(color)[resource_idx] -= amount;
+        self.vector[BANK_RESOURCE_SLICE][resource_idx] += amount;
+    }
+
+    pub fn get_player_resource_count(&self, color: u8, resource: u8) -> u8 {
+        self.get_player_hand(color)[resource as usize]
+    }
+
+    #[doc(hidden)]
+    pub fn from_player_to_player(
+        &mut self,
+        from_color: u8,
+        to_color: u8,
+        resource: u8,
+        amount: u8,
+    ) {
+        let resource_idx = resource as usize;
+        self.get_mut_player_hand(from_color)[resource_idx] -= amount;
+        self.get_mut_player_hand(to_color)[resource_idx] += amount;
+    }
+
+    pub fn get_robber_tile(&self) -> u8 {
+        self.vector[ROBBER_TILE_INDEX]
+    }
+
+    pub(crate) fn set_robber_tile(&mut self, tile_id: u8) {
+        self.vector[ROBBER_TILE_INDEX] = tile_id;
+        self.mark_board_changed(None);
+        self.recount_effective_production();
+    }
+
+    /// Stamp naming the current placement of every piece and the robber
+    pub fn get_board_stamp(&self) -> u64 {
+        self.board_stamp
+    }
+
+    /// Stamp naming the board as far as `color`'s production and reach go: their own
+    /// pieces, the settlements that block their spots, and the robber
+    pub fn get_color_board_stamp(&self, color: u8) -> u64 {
+        self.color_board_stamps[color as usize]
+    }
+
+    /// Restamps the board after a piece or the robber moved. `Some(color)` when only
+    /// that color's view changed (their roads, ships and cities); `None` for everyone.
+    pub(crate) fn mark_board_changed(&mut self, color: Option<u8>) {
+        self.board_stamp = fresh_board_stamp();
+        match color {
+            Some(color) => self.color_board_stamps[color as usize] = fresh_board_stamp(),
+            None => self
+                .color_board_stamps
+                .iter_mut()
+                .for_each(|stamp| *stamp = fresh_board_stamp()),
+        }
+    }
+
+    /// Get the owner of a specific edge (road)
+    /// Returns Some(color) if a road exists on this edge, None otherwise
+    pub fn get_edge_owner(&self, edge_id: EdgeId) -> Option<u8> {
+        let canonical_edge = (edge_id.0.min(edge_id.1), edge_id.0.max(edge_id.1));
+        self.roads.get(&canonical_edge).copied()
+    }
+
+    /// Returns true if any road is present on this edge (order-agnostic)
+    pub fn has_road(&self, edge_id: EdgeId) -> bool {
+        self.get_edge_owner(edge_id).is_some()
+    }
+
+    /// Returns true if the given color owns this edge (order-agnostic)
+    pub fn owns_road(&self, color: u8, edge_id: EdgeId) -> bool {
+        self.get_edge_owner(edge_id) == Some(color)
+    }
+
+    pub fn get_bank_resources(&self) -> &[u8] {
+        &self.vector[BANK_RESOURCE_SLICE]
+    }
+
+    pub fn get_last_dice_roll(&self) -> Option<(u8, u8)> {
+        self.last_dice_roll
+    }
+
+    /// Seed the game's randomness was initialized with
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Draw all randomness from here on (dice, deck draws, steals) from a fresh seed, so
+    /// copies of one position can play out differently
+    pub(crate) fn reseed(&mut self, seed: u64) {
+        self.rng = XorShiftRng::seed_from_u64(seed);
+    }
+
+    pub fn get_config(&self) -> &Arc<GameConfiguration> {
+        &self.config
+    }
+
+    /// Rules behavior this game follows where fixes changed outcomes
+    pub fn rules(&self) -> RulesVersion {
+        self.config.rules
+    }
+
+    // Expose special awards for hashing and diagnostics
+    pub fn get_longest_road_color(&self) -> Option<u8> {
+        self.longest_road_color
+    }
+
+    pub fn get_largest_army_color(&self) -> Option<u8> {
+        self.largest_army_color
+    }
+
+    /// Length of the award-holding road, 0 while nobody holds Longest Road
+    pub fn get_longest_road_length(&self) -> u8 {
+        self.longest_road_length
+    }
+
+    /// Knights played by the Largest Army holder, 0 while nobody holds it
+    pub fn get_largest_army_count(&self) -> u8 {
+        self.largest_army_count
+    }
+
+    /// Longest continuous road this color has, whether or not it holds the award
+    pub fn get_road_length(&self, color: u8) -> u8 {
+        self.road_lengths[color as usize]
+    }
+
+    pub fn get_knights_played(&self, color: u8) -> u8 {
+        self.get_played_dev_card_count(color, DevCard::Knight as usize)
+    }
+
+    /// Settlements left in `color`'s supply; upgrading to a city returns one
+    pub fn get_settlements_left(&self, color: u8) -> u8 {
+        self.get_pieces_left(color)[SETTLEMENTS_LEFT_OFFSET]
+    }
+
+    /// Cities left in `color`'s supply
+    pub fn get_cities_left(&self, color: u8) -> u8 {
+        self.get_pieces_left(color)[CITIES_LEFT_OFFSET]
+    }
+
+    /// Roads left in `color`'s supply
+    pub fn get_roads_left(&self, color: u8) -> u8 {
+        self.get_pieces_left(color)[ROADS_LEFT_OFFSET]
+    }
+
+    fn get_pieces_left(&self, color: u8) -> &[u8] {
+        &self.vector[player_pieces_left_slice(self.config.num_players, color)]
+    }
+
+    pub(crate) fn get_mut_pieces_left(&mut self, color: u8) -> &mut [u8] {
+        &mut self.vector[player_pieces_left_slice(self.config.num_players, color)]
+    }
+
+    /// Count every color's pieces left from the board again
+    pub(crate) fn recount_pieces_left(&mut self) {
+        for color in 0..self.config.num_players {
+            let pieces_left = [
+                TOTAL_SETTLEMENTS_PER_PLAYER
+                    - self.count_buildings(color, BuildingType::Settlement),
+                TOTAL_CITIES_PER_PLAYER - self.count_buildings(color, BuildingType::City),
+                TOTAL_ROADS_PER_PLAYER - self.roads_by_color[color as usize],
+            ];
+            self.get_mut_pieces_left(color)
+                .copy_from_slice(&pieces_left);
+        }
+    }
+
+    fn count_buildings(&self, color: u8, kind: BuildingType) -> u8 {
+        self.buildings_by_color
+            .get(&color)
+            .into_iter()
+            .flatten()
+            .filter(|building| match kind {
+                BuildingType::Settlement => matches!(building, Building::Settlement(..)),
+                BuildingType::City => matches!(building, Building::City(..)),
+            })
+            .count() as u8
+    }
+
+    /// Compute a stable 64-bit hash of the full public state vector.
+    /// This is used by search transposition tables to recognize repeated states.
+    /// The pieces left follow from the board and stay out, so replays recorded before
+    /// the vector held them keep their hashes.
+    pub fn compute_hash64(&self) -> u64 {
+        // FNV-1a 64-bit over the state vector
+        let mut hash: u64 = 0xcbf29ce484222325; // offset basis
+        const FNV_PRIME: u64 = 0x100000001b3;
+        let mut mix = |byte: u8| {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        };
+        for &byte in &self.vector[..pieces_left_start(self.config.num_players)] {
+            mix(byte);
+        }
+        // Trading state lives outside the vector; mixed in only when present, so
+        // positions without it hash as before
+        if self.trade_offers_this_turn > 0 {
+            mix(self.trade_offers_this_turn);
+        }
+        if let Some(seat) = self.special_build_seat {
+            mix(seat);
+        }
+        if self.gold_owed.iter().any(|&owed| owed > 0) {
+            for &owed in &self.gold_owed {
+                mix(owed);
+            }
+            mix(self.gold_roller_seat);
+        }
+        if let Some(trade) = &self.domestic_trade {
+            for &byte in trade.give.iter().chain(&trade.take) {
+                mix(byte);
+            }
+            for &byte in trade.acceptees.iter().chain(&trade.pending) {
+                mix(byte);
+            }
+            mix(trade.pending.len() as u8);
+            mix(trade.counters);
+        }
+        hash
+    }
+
+    /// Returns counts of remaining development cards in the bank by type index 0..4.
+    /// This is used for chance modeling when buying a development card.
+    pub fn get_remaining_dev_counts(&self) -> [u8; 5] {
+        let mut counts = [0u8; 5];
+        let n = self.config.num_players;
+        let ptr = self.vector[DEV_BANK_PTR_INDEX] as usize;
+        for position in ptr..dev_deck_size(n) {
+            let card = self.vector[dev_deck_index(n, position)];
+            if (card as usize) < counts.len() {
+                counts[card as usize] = counts[card as usize].saturating_add(1);
+            }
+        }
+        counts
+    }
+
+    /// Simulate the outcome of buying a development card of specific type without
+    /// mutating the development card deck order. This is used for EV branches.
+    /// It spends the resource cost and applies the logical effect (VP for VictoryPoint, otherwise adds to dev hand).
+    pub(crate) fn simulate_buy_dev_card_outcome(&mut self, color: u8, card_idx: usize) {
+        // Spend resources and replenish bank
+        freqdeck_sub(self.get_mut_player_hand(color), DEVCARD_COST);
+        freqdeck_add(&mut self.vector[BANK_RESOURCE_SLICE], DEVCARD_COST);
+
+        match card_idx {
+            x if x == DevCard::VictoryPoint as usize => {
+                self.add_victory_points(color, 1);
+            }
+            x if x == DevCard::Knight as usize
+                || x == DevCard::YearOfPlenty as usize
+                || x == DevCard::Monopoly as usize
+                || x == DevCard::RoadBuilding as usize =>
+            {
+                // Add to dev hand
+                let devhand = self.get_mut_player_devhand(color);
+                devhand[card_idx] = devhand[card_idx].saturating_add(1);
+            }
+            _ => {}
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn set_bank_resource(&mut self, resource_index: usize, count: u8) {
+        self.vector[BANK_RESOURCE_SLICE.start + resource_index] = count;
+    }
+
+    /// Effective production (considering robber) for a player
+    pub fn get_effective_production(&self, color: u8) -> Vec<f64> {
+        self.effective_production[color as usize].to_vec()
+    }
+
+    /// Total production (ignoring robber) for a player
+    pub fn get_total_production(&self, color: u8) -> Vec<f64> {
+        self.production[color as usize].to_vec()
+    }
+
+    /// Whether the robber keeps `node_id` from producing
+    fn is_robbed_node(&self, node_id: NodeId) -> bool {
+        let robber_tile = self.get_robber_tile();
+        self.map_instance
+            .get_adjacent_tiles(node_id)
+            .is_some_and(|tiles| tiles.iter().any(|tile| tile.id == robber_tile))
+    }
+
+    /// Adds one payout of `node_id` to `color`'s production: a new settlement, or the
+    /// second payout of a city
+    pub(crate) fn add_node_production(&mut self, color: u8, node_id: NodeId) {
+        let robbed = self.is_robbed_node(node_id);
+        let row = self.map_instance.get_node_production_row(node_id);
+        let color = color as usize;
+        for (resource, &proba) in row.iter().enumerate() {
+            self.production[color][resource] += proba;
+            if !robbed {
+                self.effective_production[color][resource] += proba;
+            }
+        }
+    }
+
+    /// Recounts effective production after the robber moved
+    fn recount_effective_production(&mut self) {
+        for color in 0..self.get_num_players() {
+            let mut production = [0.0; NUM_RESOURCES];
+            for building in self.buildings_by_color.get(&color).into_iter().flatten() {
+                let (node_id, multiplier) = match *building {
+                    Building::Settlement(_, node) => (node, 1.0),
+                    Building::City(_, node) => (node, 2.0),
+                };
+                if self.is_robbed_node(node_id) {
+                    continue;
+                }
+                let row = self.map_instance.get_node_production_row(node_id);
+                for (total, proba) in production.iter_mut().zip(row) {
+                    *total += proba * multiplier;
+                }
+            }
+            self.effective_production[color as usize] = production;
+        }
+    }
+}
+
+// Implementing Clone for State
+/// Fixed-size set of node ids, for deduplicating in hot paths without a HashSet
+#[derive(Debug, Clone, Copy, Default)]
+struct NodeMask([u64; 4]);
+
+impl NodeMask {
+    /// Returns whether `node` was newly added
+    fn insert(&mut self, node: NodeId) -> bool {
+        let (word, bit) = ((node >> 6) as usize, 1u64 << (node & 63));
+        let added = self.0[word] & bit == 0;
+        self.0[word] |= bit;
+        added
+    }
+
+    fn contains(&self, node: NodeId) -> bool {
+        self.0[(node >> 6) as usize] & (1u64 << (node & 63)) != 0
+    }
+}
+
+/// Board stamps are never reused, not even across states, so a stamp names one board
+fn fresh_board_stamp() -> u64 {
+    static NEXT_BOARD_STAMP: AtomicU64 = AtomicU64::new(1);
+    NEXT_BOARD_STAMP.fetch_add(1, Ordering::Relaxed)
+}
+
+impl Clone for State {
+    fn clone(&self) -> Self {
+        State {
+            config: self.config.clone(),
+            map_instance: self.map_instance.clone(),
+            vector: self.vector.clone(),
+            board_buildable_ids: self.board_buildable_ids.clone(),
+            buildings: self.buildings.clone(),
+            buildings_by_color: self.buildings_by_color.clone(),
+            roads: self.roads.clone(),
+            roads_by_color: self.roads_by_color.clone(),
+            connected_components: self.connected_components.clone(),
+            longest_road_color: self.longest_road_color,
+            longest_road_length: self.longest_road_length,
+            road_lengths: self.road_lengths.clone(),
+            ships: self.ships.clone(),
+            ships_by_color: self.ships_by_color.clone(),
+            ships_built_this_turn: self.ships_built_this_turn.clone(),
+            ship_moved_this_turn: self.ship_moved_this_turn,
+            gold_owed: self.gold_owed.clone(),
+            gold_roller_seat: self.gold_roller_seat,
+            largest_army_color: self.largest_army_color,
+            largest_army_count: self.largest_army_count,
+            harbormaster_color: self.harbormaster_color,
+            harbormaster_points: self.harbormaster_points,
+            board_stamp: self.board_stamp,
+            color_board_stamps: self.color_board_stamps.clone(),
+            production: self.production.clone(),
+            effective_production: self.effective_production.clone(),
+            opening_rolls: self.opening_rolls.clone(),
+            cached_winner: self.cached_winner,
+            last_dice_roll: self.last_dice_roll,
+            seed: self.seed,
+            rng: self.rng.clone(),
+            gifted_this_turn: self.gifted_this_turn,
+            domestic_trade: self.domestic_trade.clone(),
+            trade_offers_this_turn: self.trade_offers_this_turn,
+            special_build_seat: self.special_build_seat,
+            event_deck: self.event_deck.clone(),
+            dice_bag: self.dice_bag.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_creation() {
+        let state = State::new_base();
+
+        assert_eq!(state.longest_road_color, None);
+    }
+
+    #[test]
+    fn test_initial_build_phase() {
+        let state = State::new_base();
+
+        assert!(state.is_initial_build_phase());
+        assert!(!state.is_moving_robber());
+        assert!(!state.is_discarding());
+    }
+
+    #[test]
+    fn test_longest_acyclic_path() {
+        let mut state = State::new_base();
+        let color = 0;
+
+        state.roads.insert((0, 1), color);
+        state.roads.insert((1, 2), color);
+        state.roads.insert((2, 3), color);
+        state.roads.insert((3, 4), color);
+        state.roads.insert((4, 5), color);
+        state.roads.insert((0, 5), color);
+        state.roads.insert((0, 20), color);
+        state.roads.insert((20, 19), color);
+        state.roads.insert((20, 22), color);
+        state.roads.insert((22, 23), color);
+        state.roads.insert((6, 23), color);
+
+        let all_nodes = HashSet::from([0, 1, 2, 3, 4, 5, 19, 20, 22, 23, 6]);
+        let path = state.longest_acyclic_path(&all_nodes, color);
+        assert_eq!(path.len(), 10);
+    }
+
+    #[test]
+    fn test_production_follows_buildings_and_the_robber() {
+        let mut state = State::new_base();
+        let row = state.map_instance.get_node_production_row(0).to_vec();
+        let around: Vec<u8> = state
+            .map_instance
+            .get_adjacent_tiles(0)
+            .unwrap()
+            .iter()
+            .map(|tile| tile.id)
+            .collect();
+        let tile = around[0];
+        let elsewhere = state
+            .map_instance
+            .get_land_tiles()
+            .values()
+            .map(|tile| tile.id)
+            .find(|id| !around.contains(id))
+            .unwrap();
+        state.set_robber_tile(elsewhere);
+
+        state.build_settlement(0, 0);
+        assert_eq!(state.get_total_production(0), row);
+        assert_eq!(state.get_effective_production(0), row);
+        assert_eq!(state.get_total_production(1), vec![0.0; 5]);
+
+        state.set_robber_tile(tile);
+        assert_eq!(state.get_total_production(0), row);
+        assert_eq!(state.get_effective_production(0), vec![0.0; 5]);
+
+        // A city under the robber still pays nothing, and twice the row once it leaves
+        state
+            .get_mut_player_hand(0)
+            .copy_from_slice(&[0, 0, 0, 2, 3]);
+        state.build_city(0, 0);
+        assert_eq!(state.get_effective_production(0), vec![0.0; 5]);
+        state.set_robber_tile(elsewhere);
+        let doubled: Vec<f64> = row.iter().map(|proba| proba * 2.0).collect();
+        assert_eq!(state.get_effective_production(0), doubled);
+        assert_eq!(state.get_total_production(0), doubled);
+    }
+
+    #[test]
+    fn test_victory_point_cards_stay_hidden_until_someone_wins() {
+        let mut state = State::new_base();
+        state.build_settlement(0, 0);
+        // A victory point card bought by seat 0
+        state.add_victory_points(0, 1);
+        assert_eq!(state.get_actual_victory_points(0), 2);
+        assert_eq!(state.get_public_victory_points(0), 1);
+        assert_eq!(state.get_visible_victory_points(0, 0), 2);
+        assert_eq!(state.get_visible_victory_points(1, 0), 1);
+
+        // The win counts the hidden points, and then everyone sees them
+        let vps_to_win = state.get_config().vps_to_win;
+        state.add_victory_points(0, vps_to_win - 2);
+        assert_eq!(state.winner(), Some(0));
+        assert_eq!(state.get_visible_victory_points(1, 0), vps_to_win);
+    }
+}
@@ -0,0 +1,199 @@
+use super::State;
+use crate::enums::FreqDeck;
+use crate::state_vector::BANK_RESOURCE_SLICE;
+
+impl State {
+    /// Most public victory points anyone has. Catch-up rules go by public points so
+    /// that they give nobody's victory point cards away.
+    pub fn get_leader_victory_points(&self) -> u8 {
+        (0..self.get_num_players())
+            .map(|color| self.get_public_victory_points(color))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Public points `color` is behind the leaders (0 for a leader)
+    pub fn points_behind_leader(&self, color: u8) -> u8 {
+        self.get_leader_victory_points() - self.get_public_victory_points(color)
+    }
+
+    /// Whether the catch-up rules keep the robber away from `color`
+    pub fn is_catch_up_immune(&self, color: u8) -> bool {
+        self.config
+            .variants
+            .catch_up
+            .robber_immunity_gap
+            .is_some_and(|gap| self.points_behind_leader(color) >= gap)
+    }
+
+    /// Leaders' hands before `roller`'s roll is paid out, when the leader roll bonus
+    /// could apply to it
+    pub(super) fn leader_hands_before_roll(&self, roller: u8) -> Vec<(u8, FreqDeck)> {
+        if !self.config.variants.catch_up.leader_roll_bonus
+            || self.points_behind_leader(roller) == 0
+        {
+            return Vec::new();
+        }
+        let leader_vps = self.get_leader_victory_points();
+        (0..self.get_num_players())
+            .filter(|&color| self.get_public_victory_points(color) == leader_vps)
+            .map(|color| {
+                let hand = self.get_player_hand(color).try_into().expect("five counts");
+                (color, hand)
+            })
+            .collect()
+    }
+
+    /// Give the roller one card of the resource the leaders collected most of, if the
+    /// bank has one
+    pub(super) fn grant_leader_roll_bonus(&mut self, roller: u8, before: &[(u8, FreqDeck)]) {
+        let mut collected = [0u8; 5];
+        for (color, hand) in before {
+            let now = self.get_player_hand(*color);
+            for (total, (&after, &had)) in collected.iter_mut().zip(now.iter().zip(hand)) {
+                *total += after.saturating_sub(had);
+            }
+        }
+        let Some(resource) = (0..5)
+            .filter(|&resource| collected[resource] > 0)
+            .max_by_key(|&resource| (collected[resource], std::cmp::Reverse(resource)))
+        else {
+            return;
+        };
+        if self.vector[BANK_RESOURCE_SLICE][resource] == 0 {
+            return;
+        }
+        log::info!("🎁 Catch-up: player {roller} takes resource {resource} off the leaders' roll");
+        self.vector[BANK_RESOURCE_SLICE][resource] -= 1;
+        self.get_mut_player_hand(roller)[resource] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, CatchUpRules, GameConfiguration, StateEdit, VariantConfig};
+    use crate::map_instance::{MapInstance, NodeId};
+    use crate::state::Building;
+    use std::sync::Arc;
+
+    fn catch_up_state(catch_up: CatchUpRules) -> State {
+        let config = GameConfiguration::builder()
+            .variants(VariantConfig {
+                catch_up,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        let map_instance = MapInstance::shared(&config.map_type, 0);
+        let mut state = State::new(Arc::new(config), map_instance);
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::InitialBuildPhase(false),
+        });
+        state
+    }
+
+    /// Build `piece` on the lowest corner of the lowest tile numbered `number` that
+    /// keeps the distance rule, and return the tile's resource
+    fn build_on_number(state: &mut State, number: u8, piece: impl Fn(NodeId) -> Building) -> usize {
+        let tiles = state.map_instance.get_tiles_by_number(number);
+        let tile = (*tiles.iter().min_by_key(|tile| tile.id).unwrap()).clone();
+        let mut corners: Vec<NodeId> = tile.hexagon.nodes.values().copied().collect();
+        corners.sort_unstable();
+        let node = corners
+            .into_iter()
+            .find(|&node| {
+                let neighbors = state.map_instance.get_neighbor_nodes(node);
+                !state.buildings.contains_key(&node)
+                    && !neighbors.iter().any(|n| state.buildings.contains_key(n))
+            })
+            .unwrap();
+        state
+            .place_pieces(&[piece(node)], Vec::new(), Vec::new())
+            .unwrap();
+        tile.resource.unwrap() as usize
+    }
+
+    #[test]
+    fn test_trailing_roller_shares_the_leaders_roll() {
+        let mut state = catch_up_state(CatchUpRules {
+            leader_roll_bonus: true,
+            ..Default::default()
+        });
+        let resource = build_on_number(&mut state, 6, |node| Building::Settlement(1, node));
+        assert_eq!(state.points_behind_leader(0), 1);
+
+        state.apply_action(Action::Roll {
+            color: 0,
+            dice_opt: Some((3, 3)),
+        });
+        assert_eq!(state.get_player_hand(0)[resource], 1);
+        assert_eq!(state.get_player_hand(1)[resource], 1);
+    }
+
+    #[test]
+    fn test_leader_roll_bonus_is_off_by_default() {
+        let mut state = catch_up_state(CatchUpRules::default());
+        let resource = build_on_number(&mut state, 6, |node| Building::Settlement(1, node));
+
+        state.apply_action(Action::Roll {
+            color: 0,
+            dice_opt: Some((3, 3)),
+        });
+        assert_eq!(state.get_player_hand(0)[resource], 0);
+    }
+
+    #[test]
+    fn test_robber_skips_players_far_behind() {
+        let mut state = catch_up_state(CatchUpRules {
+            robber_immunity_gap: Some(3),
+            ..Default::default()
+        });
+        build_on_number(&mut state, 6, |node| Building::Settlement(1, node));
+        build_on_number(&mut state, 8, |node| Building::City(2, node));
+        state.apply_action(Action::DebugSetState {
+            color: 1,
+            edit: StateEdit::Hand([1, 0, 0, 0, 0]),
+        });
+        // Hidden victory point cards don't widen the public gap
+        state.add_victory_points(2, 3);
+        assert!(!state.is_catch_up_immune(1));
+        build_on_number(&mut state, 5, |node| Building::City(2, node));
+        assert!(state.is_catch_up_immune(1));
+        assert!(!state.is_catch_up_immune(2));
+
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::HasRolled(true),
+        });
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::MovingRobber(true),
+        });
+        let actions = state.generate_playable_actions();
+        assert!(!actions.is_empty());
+        assert!(!actions.iter().any(|action| matches!(
+            action,
+            Action::MoveRobber {
+                victim_opt: Some(1),
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_zero_immunity_gap_is_rejected() {
+        let variants = VariantConfig {
+            catch_up: CatchUpRules {
+                robber_immunity_gap: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(GameConfiguration::builder()
+            .variants(variants)
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,133 @@
+// Raw view of a position for investigating production issues: the state vector plus every
+// cache kept alongside it, in a stable order so two dumps diff cleanly.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::panic::AssertUnwindSafe;
+
+use super::{Building, State};
+use crate::enums::Action;
+use crate::map_instance::{EdgeId, NodeId};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StateDump {
+    pub seed: u64,
+    pub prompt: String,
+    pub current_color: u8,
+    pub vector: Vec<u8>,
+    pub board_buildable_ids: Vec<NodeId>,
+    /// Settlement and city nodes per color, in build order
+    pub settlements: BTreeMap<u8, Vec<NodeId>>,
+    pub cities: BTreeMap<u8, Vec<NodeId>>,
+    pub roads: Vec<(EdgeId, u8)>,
+    pub roads_by_color: Vec<u8>,
+    /// Seafarers ships, empty on other boards
+    pub ships: Vec<(EdgeId, u8)>,
+    /// Seafarers gold field cards left to pick, per color
+    pub gold_owed: Vec<u8>,
+    pub connected_components: BTreeMap<u8, Vec<Vec<NodeId>>>,
+    pub road_lengths: Vec<u8>,
+    /// (holder, size) of each award, when held
+    pub longest_road: Option<(u8, u8)>,
+    pub largest_army: Option<(u8, u8)>,
+    pub harbormaster: Option<(u8, u8)>,
+    pub last_dice_roll: Option<(u8, u8)>,
+    /// `None` if generating them panics, as it may on a position left half-updated
+    pub playable_actions: Option<Vec<Action>>,
+}
+
+impl State {
+    pub fn debug_dump(&self) -> StateDump {
+        let mut settlements: BTreeMap<u8, Vec<NodeId>> = BTreeMap::new();
+        let mut cities: BTreeMap<u8, Vec<NodeId>> = BTreeMap::new();
+        for (&color, buildings) in &self.buildings_by_color {
+            for building in buildings {
+                match *building {
+                    Building::Settlement(_, node_id) => {
+                        settlements.entry(color).or_default().push(node_id)
+                    }
+                    Building::City(_, node_id) => cities.entry(color).or_default().push(node_id),
+                }
+            }
+        }
+
+        let mut board_buildable_ids: Vec<NodeId> =
+            self.board_buildable_ids.iter().copied().collect();
+        board_buildable_ids.sort_unstable();
+        let mut roads: Vec<(EdgeId, u8)> = self.roads.iter().map(|(&e, &c)| (e, c)).collect();
+        roads.sort_unstable();
+        let mut ships: Vec<(EdgeId, u8)> = self.ships.iter().map(|(&e, &c)| (e, c)).collect();
+        ships.sort_unstable();
+        let connected_components = self
+            .connected_components
+            .iter()
+            .map(|(&color, components)| {
+                let mut components: Vec<Vec<NodeId>> = components
+                    .iter()
+                    .map(|component| {
+                        let mut nodes: Vec<NodeId> = component.iter().copied().collect();
+                        nodes.sort_unstable();
+                        nodes
+                    })
+                    .collect();
+                components.sort();
+                (color, components)
+            })
+            .collect();
+        let award = |holder: Option<u8>, size: u8| holder.map(|color| (color, size));
+
+        StateDump {
+            seed: self.seed,
+            prompt: format!("{:?}", self.get_action_prompt()),
+            current_color: self.get_current_color(),
+            vector: self.vector.clone(),
+            board_buildable_ids,
+            settlements,
+            cities,
+            roads,
+            roads_by_color: self.roads_by_color.clone(),
+            ships,
+            gold_owed: self.gold_owed.clone(),
+            connected_components,
+            road_lengths: self.road_lengths.clone(),
+            longest_road: award(self.longest_road_color, self.longest_road_length),
+            largest_army: award(self.largest_army_color, self.largest_army_count),
+            harbormaster: award(self.harbormaster_color, self.harbormaster_points),
+            last_dice_roll: self.last_dice_roll,
+            playable_actions: std::panic::catch_unwind(AssertUnwindSafe(|| {
+                self.generate_playable_actions()
+            }))
+            .ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_tracks_the_caches() {
+        let mut state = State::new_base();
+        let dump = state.debug_dump();
+        assert_eq!(dump.board_buildable_ids.len(), 54);
+        assert_eq!(dump.playable_actions.map(|actions| actions.len()), Some(54));
+        assert_eq!(dump.prompt, "BuildInitialSettlement");
+
+        let color = state.get_current_color();
+        state.apply_action(Action::BuildSettlement { color, node_id: 0 });
+        let edge_id = state.map_instance.get_neighbor_edges(0)[0];
+        state.apply_action(Action::BuildRoad { color, edge_id });
+
+        let dump = state.debug_dump();
+        assert_eq!(dump.settlements[&color], vec![0]);
+        assert_eq!(dump.roads, vec![(edge_id, color)]);
+        assert_eq!(dump.connected_components[&color].len(), 1);
+        assert_eq!(dump.vector, state.vector);
+        let json = serde_json::to_value(&dump).unwrap();
+        assert_eq!(
+            json["settlements"][color.to_string()],
+            serde_json::json!([0])
+        );
+    }
+}
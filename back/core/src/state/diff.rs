@@ -0,0 +1,230 @@
+// Field-by-field comparison of two positions, for tracking down where two engines (or two
+// builds of this one) drifted apart after the same actions. Fields are rendered as text
+// so states with different player counts still compare.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::{Building, State};
+use crate::map_instance::{EdgeId, NodeId};
+use crate::state_vector::{
+    player_played_devhand_slice, CURRENT_TURN_SEAT_INDEX, FREE_ROADS_AVAILABLE_INDEX,
+    HAS_PLAYED_DEV_CARD, HAS_ROLLED_INDEX, IS_BUILDING_ROAD_INDEX,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum DiffSection {
+    /// Cards held by the players and the bank
+    Hands,
+    /// The map and the pieces on it
+    Board,
+    Awards,
+    /// Whose turn it is and where in the turn
+    Flags,
+}
+
+/// A field that differs between two states; `None` on the side that lacks it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDiff {
+    pub section: DiffSection,
+    pub field: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl State {
+    /// Fields where `self` and `other` disagree, by section and field name
+    pub fn diff(&self, other: &State) -> Vec<FieldDiff> {
+        let left = self.diff_fields();
+        let right = other.diff_fields();
+        let keys: BTreeSet<&(DiffSection, String)> = left.keys().chain(right.keys()).collect();
+        keys.into_iter()
+            .filter(|key| left.get(*key) != right.get(*key))
+            .map(|key| FieldDiff {
+                section: key.0,
+                field: key.1.clone(),
+                left: left.get(key).cloned(),
+                right: right.get(key).cloned(),
+            })
+            .collect()
+    }
+
+    fn diff_fields(&self) -> BTreeMap<(DiffSection, String), String> {
+        let mut fields = BTreeMap::new();
+        let mut field = |section: DiffSection, name: String, value: String| {
+            fields.insert((section, name), value);
+        };
+
+        field(
+            DiffSection::Hands,
+            "bank resources".to_string(),
+            format!("{:?}", self.get_bank_resources()),
+        );
+        field(
+            DiffSection::Hands,
+            "dev cards left".to_string(),
+            format!("{:?}", self.get_remaining_dev_counts()),
+        );
+        field(
+            DiffSection::Board,
+            "map".to_string(),
+            format!(
+                "{:?} seed {}",
+                self.config.map_type,
+                self.map_instance.get_seed()
+            ),
+        );
+        field(
+            DiffSection::Board,
+            "robber tile".to_string(),
+            self.get_robber_tile().to_string(),
+        );
+        let award = |holder: Option<u8>, size: u8| match holder {
+            Some(color) => format!("color {color} ({size})"),
+            None => "none".to_string(),
+        };
+        field(
+            DiffSection::Awards,
+            "longest road".to_string(),
+            award(self.longest_road_color, self.longest_road_length),
+        );
+        field(
+            DiffSection::Awards,
+            "largest army".to_string(),
+            award(self.largest_army_color, self.largest_army_count),
+        );
+        field(
+            DiffSection::Awards,
+            "harbormaster".to_string(),
+            award(self.harbormaster_color, self.harbormaster_points),
+        );
+        let flags = [
+            ("prompt", format!("{:?}", self.get_action_prompt())),
+            ("current color", self.get_current_color().to_string()),
+            (
+                "turn seat",
+                self.vector[CURRENT_TURN_SEAT_INDEX].to_string(),
+            ),
+            (
+                "initial build phase",
+                self.is_initial_build_phase().to_string(),
+            ),
+            (
+                "has rolled",
+                (self.vector[HAS_ROLLED_INDEX] == 1).to_string(),
+            ),
+            (
+                "has played dev card",
+                (self.vector[HAS_PLAYED_DEV_CARD] == 1).to_string(),
+            ),
+            ("discarding", self.is_discarding().to_string()),
+            ("moving robber", self.is_moving_robber().to_string()),
+            (
+                "building road",
+                (self.vector[IS_BUILDING_ROAD_INDEX] == 1).to_string(),
+            ),
+            (
+                "free roads",
+                self.vector[FREE_ROADS_AVAILABLE_INDEX].to_string(),
+            ),
+            ("last dice roll", format!("{:?}", self.last_dice_roll)),
+        ];
+        for (name, value) in flags {
+            field(DiffSection::Flags, name.to_string(), value);
+        }
+
+        for color in 0..self.get_num_players() {
+            let mut settlements: Vec<NodeId> = Vec::new();
+            let mut cities: Vec<NodeId> = Vec::new();
+            for building in self.buildings_by_color.get(&color).into_iter().flatten() {
+                match *building {
+                    Building::Settlement(_, node_id) => settlements.push(node_id),
+                    Building::City(_, node_id) => cities.push(node_id),
+                }
+            }
+            settlements.sort_unstable();
+            cities.sort_unstable();
+            let edges_of = |pieces: &HashMap<EdgeId, u8>| {
+                let mut edges: Vec<EdgeId> = pieces
+                    .iter()
+                    .filter(|(_, &owner)| owner == color)
+                    .map(|(&edge, _)| edge)
+                    .collect();
+                edges.sort_unstable();
+                format!("{edges:?}")
+            };
+
+            let player = |name: &str| format!("color {color} {name}");
+            field(
+                DiffSection::Hands,
+                player("resources"),
+                format!("{:?}", self.get_player_hand(color)),
+            );
+            field(
+                DiffSection::Hands,
+                player("dev cards"),
+                format!("{:?}", self.get_player_devhand(color)),
+            );
+            field(
+                DiffSection::Hands,
+                player("played dev cards"),
+                format!(
+                    "{:?}",
+                    &self.vector[player_played_devhand_slice(self.get_num_players(), color)]
+                ),
+            );
+            field(
+                DiffSection::Hands,
+                player("victory points"),
+                self.get_actual_victory_points(color).to_string(),
+            );
+            field(
+                DiffSection::Board,
+                player("settlements"),
+                format!("{settlements:?}"),
+            );
+            field(DiffSection::Board, player("cities"), format!("{cities:?}"));
+            field(DiffSection::Board, player("roads"), edges_of(&self.roads));
+            field(DiffSection::Board, player("ships"), edges_of(&self.ships));
+            field(
+                DiffSection::Awards,
+                player("road length"),
+                self.get_road_length(color).to_string(),
+            );
+            field(
+                DiffSection::Flags,
+                player("gold owed"),
+                self.get_gold_owed(color).to_string(),
+            );
+        }
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::Action;
+
+    #[test]
+    fn test_diff_names_the_fields_that_drifted() {
+        let mut left = State::new_base();
+        let right = left.clone();
+        assert!(left.diff(&right).is_empty());
+
+        let color = left.get_current_color();
+        left.apply_action(Action::BuildSettlement { color, node_id: 0 });
+        let diff = left.diff(&right);
+        let settlements = diff
+            .iter()
+            .find(|d| d.field == format!("color {color} settlements"))
+            .unwrap();
+        assert_eq!(settlements.section, DiffSection::Board);
+        assert_eq!(settlements.left.as_deref(), Some("[0]"));
+        assert_eq!(settlements.right.as_deref(), Some("[]"));
+        assert!(diff
+            .iter()
+            .any(|d| d.section == DiffSection::Flags && d.field == "prompt"));
+        assert!(diff.iter().all(|d| !d.field.contains("resources")));
+    }
+}
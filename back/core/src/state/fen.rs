@@ -0,0 +1,446 @@
+// Compact one-line position notation, in the spirit of chess FEN, for benchmark fixtures
+// and bug reports. Fields are space separated:
+//
+//   <map> <color><phase> <buildings> <roads> <hands> <dev hands> <played> <robber>
+//
+// - map: `B` (base), `M` (mini), `E` (extended) or `S` (Seafarers), a colon and the map
+//   seed, e.g. `B:7`
+// - color to move, then the phase: `r` before rolling, `p` after rolling, `m` moving
+//   the robber
+// - buildings: `s<color>@<node>` or `c<color>@<node>`, comma separated
+// - roads: `<color>:<a>-<b>`, comma separated, then Seafarers ships as `<color>~<a>-<b>`
+// - hands: one group per seat separated by `/`, each five `.`-separated counts
+//   (wood, brick, sheep, wheat, ore)
+// - dev hands: same layout (knight, year of plenty, monopoly, road building, victory
+//   point cards)
+// - played: per seat four counts (knight, year of plenty, monopoly, road building)
+// - robber: land tile id
+//
+// Empty lists are written as `-`. Ties for Longest Road or Largest Army go to the
+// lowest color, since the order the awards were won in is not recorded.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::{Building, State};
+use crate::deck_slices::{
+    freqdeck_add, freqdeck_sub, FreqDeck, CITY_COST, ROAD_COST, SETTLEMENT_COST, SHIP_COST,
+};
+use crate::enums::{DevCard, GameConfiguration, MapType, MAX_PLAYERS, MIN_PLAYERS};
+use crate::errors::{CatanError, CatanResult};
+use crate::map_instance::{EdgeId, MapInstance, NodeId};
+use crate::state_vector::{
+    player_devhand_slice, player_played_devhand_slice, BANK_RESOURCE_SLICE,
+    CURRENT_TICK_SEAT_INDEX, CURRENT_TURN_SEAT_INDEX, HAS_ROLLED_INDEX,
+    IS_INITIAL_BUILD_PHASE_INDEX, IS_MOVING_ROBBER_INDEX, PLAYER_PLAYED_DEVCARDS_SIZE,
+};
+
+fn fen_error(details: impl std::fmt::Display) -> CatanError {
+    CatanError::Validation(format!("bad position: {details}"))
+}
+
+fn join_or_dash(parts: Vec<String>) -> String {
+    if parts.is_empty() {
+        "-".to_string()
+    } else {
+        parts.join(",")
+    }
+}
+
+fn counts(values: &[u8]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn parse_counts(group: &str, expected: usize) -> CatanResult<Vec<u8>> {
+    let values = group
+        .split('.')
+        .map(|v| {
+            v.parse::<u8>()
+                .map_err(|_| fen_error(format!("count '{v}'")))
+        })
+        .collect::<CatanResult<Vec<u8>>>()?;
+    if values.len() != expected {
+        return Err(fen_error(format!("'{group}' needs {expected} counts")));
+    }
+    Ok(values)
+}
+
+fn parse_seats(field: &str, expected: usize) -> CatanResult<Vec<Vec<u8>>> {
+    field
+        .split('/')
+        .map(|group| parse_counts(group, expected))
+        .collect()
+}
+
+fn list(field: &str) -> Vec<&str> {
+    if field == "-" {
+        Vec::new()
+    } else {
+        field.split(',').collect()
+    }
+}
+
+impl State {
+    /// Encode this position in the one-line notation described at the top of this module.
+    /// Only positions on a turn boundary are representable (no pending discards, gold
+    /// picks, trades or free roads).
+    pub fn to_fen(&self) -> String {
+        let map = match self.config.map_type {
+            MapType::Mini => 'M',
+            MapType::Base | MapType::Tournament => 'B',
+            MapType::Extended => 'E',
+            MapType::Seafarers => 'S',
+        };
+        let phase = if self.is_moving_robber() {
+            'm'
+        } else if self.current_player_rolled() {
+            'p'
+        } else {
+            'r'
+        };
+
+        let mut buildings: Vec<(NodeId, String)> = self
+            .buildings
+            .values()
+            .map(|building| match *building {
+                Building::Settlement(color, node) => (node, format!("s{color}@{node}")),
+                Building::City(color, node) => (node, format!("c{color}@{node}")),
+            })
+            .collect();
+        buildings.sort();
+        let mut roads: Vec<(EdgeId, u8)> = self.roads.iter().map(|(&e, &c)| (e, c)).collect();
+        roads.sort();
+        let mut ships: Vec<(EdgeId, u8)> = self.ships.iter().map(|(&e, &c)| (e, c)).collect();
+        ships.sort();
+
+        let num_players = self.get_num_players();
+        let seats =
+            |f: &dyn Fn(u8) -> String| (0..num_players).map(f).collect::<Vec<_>>().join("/");
+        let hands = seats(&|color| counts(self.get_player_hand(color)));
+        let devs = seats(&|color| {
+            let mut hand = self.get_player_devhand(color).to_vec();
+            hand[DevCard::VictoryPoint as usize] = self.get_victory_point_cards(color);
+            counts(&hand)
+        });
+        let played = seats(&|color| {
+            counts(
+                &self.vector[player_played_devhand_slice(num_players, color)]
+                    [..PLAYER_PLAYED_DEVCARDS_SIZE],
+            )
+        });
+
+        format!(
+            "{map}:{} {}{phase} {} {} {hands} {devs} {played} {}",
+            self.map_instance.get_seed(),
+            self.get_current_color(),
+            join_or_dash(buildings.into_iter().map(|(_, b)| b).collect()),
+            join_or_dash(
+                roads
+                    .into_iter()
+                    .map(|((a, b), color)| format!("{color}:{a}-{b}"))
+                    .chain(
+                        ships
+                            .into_iter()
+                            .map(|((a, b), color)| format!("{color}~{a}-{b}"))
+                    )
+                    .collect()
+            ),
+            self.get_robber_tile(),
+        )
+    }
+
+    /// Rebuild a position from `to_fen` notation. Dice, deck order and steals after this
+    /// point come from a fixed seed.
+    pub fn from_fen(fen: &str) -> CatanResult<State> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        let [map, to_move, buildings, roads, hands, devs, played, robber] = fields[..] else {
+            return Err(fen_error(format!(
+                "expected 8 fields, got {}",
+                fields.len()
+            )));
+        };
+
+        let (map_type, map_seed) = match map.split_once(':') {
+            Some(("B", seed)) => (MapType::Base, seed),
+            Some(("M", seed)) => (MapType::Mini, seed),
+            Some(("E", seed)) => (MapType::Extended, seed),
+            Some(("S", seed)) => (MapType::Seafarers, seed),
+            _ => return Err(fen_error(format!("map '{map}'"))),
+        };
+        let map_seed: u64 = map_seed
+            .parse()
+            .map_err(|_| fen_error(format!("map seed '{map_seed}'")))?;
+        let hands = parse_seats(hands, 5)?;
+        let devs = parse_seats(devs, 5)?;
+        let played = parse_seats(played, PLAYER_PLAYED_DEVCARDS_SIZE)?;
+        let num_players = hands.len();
+        if devs.len() != num_players
+            || played.len() != num_players
+            || !(MIN_PLAYERS as usize..=MAX_PLAYERS as usize).contains(&num_players)
+        {
+            return Err(fen_error(
+                "hands, dev hands and played cards disagree on seats",
+            ));
+        }
+        let num_players = num_players as u8;
+
+        let map_instance = MapInstance::shared(&map_type, map_seed);
+        let config = GameConfiguration::builder()
+            .map_type(map_type)
+            .num_players(num_players)
+            .build()?;
+        let mut state = State::new_with_seed(Arc::new(config), map_instance, 0);
+        state.vector[IS_INITIAL_BUILD_PHASE_INDEX] = 0;
+
+        let check_color = |color: u8| {
+            if color < num_players {
+                Ok(color)
+            } else {
+                Err(fen_error(format!("color {color}")))
+            }
+        };
+        let check_node = |state: &State, node: NodeId| {
+            if state.map_instance.land_nodes().contains(&node) {
+                Ok(node)
+            } else {
+                Err(fen_error(format!("node {node}")))
+            }
+        };
+
+        let mut placed = Vec::new();
+        for entry in list(buildings) {
+            let (kind, rest) = entry.split_at(1);
+            let (color, node) = rest
+                .split_once('@')
+                .and_then(|(c, n)| Some((c.parse::<u8>().ok()?, n.parse::<NodeId>().ok()?)))
+                .ok_or_else(|| fen_error(format!("building '{entry}'")))?;
+            let color = check_color(color)?;
+            let node = check_node(&state, node)?;
+            placed.push(match kind {
+                "s" => Building::Settlement(color, node),
+                "c" => Building::City(color, node),
+                _ => return Err(fen_error(format!("building '{entry}'"))),
+            });
+        }
+
+        let mut laid = Vec::new();
+        let mut sailed = Vec::new();
+        for entry in list(roads) {
+            let is_ship = entry.contains('~');
+            let (color, a, b) = entry
+                .split_once([':', '~'])
+                .and_then(|(c, e)| {
+                    let (a, b) = e.split_once('-')?;
+                    Some((
+                        c.parse::<u8>().ok()?,
+                        a.parse::<NodeId>().ok()?,
+                        b.parse::<NodeId>().ok()?,
+                    ))
+                })
+                .ok_or_else(|| fen_error(format!("road '{entry}'")))?;
+            let color = check_color(color)?;
+            let edge = (a.min(b), a.max(b));
+            if is_ship {
+                if !state.map_instance.sea_edges.contains(&edge) {
+                    return Err(fen_error(format!("sea edge {a}-{b}")));
+                }
+                sailed.push((color, edge));
+                continue;
+            }
+            let land_edges = &state.map_instance.land_edges;
+            if !land_edges.contains(&(a, b)) && !land_edges.contains(&(b, a)) {
+                return Err(fen_error(format!("edge {a}-{b}")));
+            }
+            laid.push((color, edge));
+        }
+        state
+            .place_pieces(&placed, laid, sailed)
+            .map_err(fen_error)?;
+
+        for color in 0..num_players {
+            let color_index = color as usize;
+            let hand: FreqDeck = hands[color_index][..].try_into().expect("five counts");
+            let held: FreqDeck = state
+                .get_player_hand(color)
+                .try_into()
+                .expect("five counts");
+            freqdeck_add(&mut state.vector[BANK_RESOURCE_SLICE], held);
+            if state
+                .get_bank_resources()
+                .iter()
+                .zip(hand)
+                .any(|(&bank, want)| bank < want)
+            {
+                return Err(fen_error("more resources in hands than in the bank"));
+            }
+            freqdeck_sub(&mut state.vector[BANK_RESOURCE_SLICE], hand);
+            state.get_mut_player_hand(color).copy_from_slice(&hand);
+
+            let dev_slice = player_devhand_slice(num_players, color);
+            state.vector[dev_slice].copy_from_slice(&devs[color_index]);
+            let victory_cards = devs[color_index][DevCard::VictoryPoint as usize];
+            state.vector[player_devhand_slice(num_players, color)]
+                [DevCard::VictoryPoint as usize] = 0;
+            state.add_victory_points(color, victory_cards);
+
+            let played_slice = player_played_devhand_slice(num_players, color);
+            state.vector[played_slice][..PLAYER_PLAYED_DEVCARDS_SIZE]
+                .copy_from_slice(&played[color_index]);
+        }
+        state.maintain_largest_army();
+
+        let robber: u8 = robber
+            .parse()
+            .map_err(|_| fen_error(format!("robber '{robber}'")))?;
+        if !state
+            .map_instance
+            .land_tiles
+            .values()
+            .any(|t| t.id == robber)
+        {
+            return Err(fen_error(format!("robber tile {robber}")));
+        }
+        state.set_robber_tile(robber);
+
+        let (color, phase) = to_move.split_at(to_move.len().saturating_sub(1));
+        let color: u8 = color
+            .parse()
+            .map_err(|_| fen_error(format!("color to move '{to_move}'")))?;
+        let color = check_color(color)?;
+        let seat = state
+            .get_seating_order()
+            .iter()
+            .position(|&c| c == color)
+            .expect("every color has a seat") as u8;
+        state.vector[CURRENT_TICK_SEAT_INDEX] = seat;
+        state.vector[CURRENT_TURN_SEAT_INDEX] = seat;
+        match phase {
+            "r" => {}
+            "p" => state.vector[HAS_ROLLED_INDEX] = 1,
+            "m" => {
+                state.vector[HAS_ROLLED_INDEX] = 1;
+                state.vector[IS_MOVING_ROBBER_INDEX] = 1;
+            }
+            _ => return Err(fen_error(format!("phase '{phase}'"))),
+        }
+
+        Ok(state)
+    }
+
+    /// Put pieces on an empty board: settlements first, then roads and ships outwards
+    /// from each network so components build up like in a game, then the city upgrades.
+    /// Builds pay for themselves, so hands and the bank end up where they started.
+    pub(super) fn place_pieces(
+        &mut self,
+        buildings: &[Building],
+        mut roads: Vec<(u8, EdgeId)>,
+        mut ships: Vec<(u8, EdgeId)>,
+    ) -> Result<(), String> {
+        let bank: FreqDeck = self.get_bank_resources().try_into().expect("five counts");
+        let mut cities = Vec::new();
+        for &building in buildings {
+            let (color, node) = match building {
+                Building::Settlement(color, node) => (color, node),
+                Building::City(color, node) => {
+                    cities.push((color, node));
+                    (color, node)
+                }
+            };
+            if self.buildings.contains_key(&node) {
+                return Err(format!("node {node} is taken twice"));
+            }
+            self.credit(color, SETTLEMENT_COST);
+            self.build_settlement(color, node);
+            self.connected_components
+                .entry(color)
+                .or_default()
+                .push(HashSet::from([node]));
+        }
+
+        while !roads.is_empty() {
+            let next = roads.iter().position(|&(color, (a, b))| {
+                self.get_connected_component_index(color, a).is_some()
+                    || self.get_connected_component_index(color, b).is_some()
+            });
+            let Some(index) = next else {
+                return Err("road not connected to its owner's network".to_string());
+            };
+            let (color, edge) = roads.remove(index);
+            self.credit(color, ROAD_COST);
+            let (owner, length) = self.build_road(color, edge);
+            self.maintain_longest_road(owner, length);
+        }
+
+        while !ships.is_empty() {
+            let next = ships
+                .iter()
+                .position(|&(color, edge)| self.buildable_sea_edges(color, None).contains(&edge));
+            let Some(index) = next else {
+                return Err("ship not connected to its owner's network".to_string());
+            };
+            let (color, edge) = ships.remove(index);
+            self.credit(color, SHIP_COST);
+            let (owner, length) = self.build_ship(color, edge);
+            self.maintain_longest_road(owner, length);
+        }
+        // Ships on the board at the start count as built on earlier turns
+        self.ships_built_this_turn.clear();
+
+        for (color, node) in cities {
+            self.credit(color, CITY_COST);
+            self.build_city(color, node);
+        }
+        // A full board costs more than the bank holds, so the payments went to a
+        // scratch bank
+        self.vector[BANK_RESOURCE_SLICE].copy_from_slice(&bank);
+        Ok(())
+    }
+
+    /// Hand `color` the cards for a build so it can pay for itself
+    fn credit(&mut self, color: u8, cost: FreqDeck) {
+        freqdeck_add(self.get_mut_player_hand(color), cost);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, ActionPrompt};
+
+    const POSITION: &str = "B:3 1p s0@0,c1@10 0:0-1,0:1-2,1:10-11 1.0.2.1.3/0.2.0.2.3/0.0.0.0.0/0.0.0.0.0 1.0.0.0.1/0.0.0.0.0/0.0.0.0.0/0.0.0.0.0 2.0.0.0/0.0.0.0/0.0.0.0/0.0.0.0 4";
+
+    #[test]
+    fn test_fen_roundtrip() {
+        let state = State::from_fen(POSITION).unwrap();
+        assert_eq!(state.to_fen(), POSITION);
+        assert_eq!(state.get_current_color(), 1);
+        assert!(matches!(state.get_action_prompt(), ActionPrompt::PlayTurn));
+        assert_eq!(state.get_actual_victory_points(0), 2);
+        assert_eq!(state.get_actual_victory_points(1), 2);
+        // Seat 0's second point is a victory point card, hidden from the public score
+        assert_eq!(state.get_public_victory_points(0), 1);
+        assert_eq!(state.get_victory_point_cards(0), 1);
+        assert_eq!(state.get_victory_point_cards(1), 0);
+        assert_eq!(state.get_player_hand(1), &[0, 2, 0, 2, 3]);
+        // Already a city, so seat 1's ore and wheat can only go elsewhere
+        assert!(!state
+            .generate_playable_actions()
+            .contains(&Action::BuildCity {
+                color: 1,
+                node_id: 10
+            }));
+        assert_eq!(state.get_roads_for_color(0).len(), 2);
+    }
+
+    #[test]
+    fn test_fen_rejects_bad_input() {
+        assert!(State::from_fen("B:3 0r - -").is_err());
+        assert!(State::from_fen(&POSITION.replace("s0@0", "s7@0")).is_err());
+        assert!(State::from_fen(&POSITION.replace("1:10-11", "1:30-31")).is_err());
+        assert!(State::from_fen(&POSITION.replace(" 4", " 99")).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use super::State;
+use crate::deck_slices::{freqdeck_add, freqdeck_sub};
+use crate::enums::SeatHandicap;
+use crate::state_vector::{actual_victory_points_index, BANK_RESOURCE_SLICE};
+
+/// Roads every seat places during the initial build phase
+const INITIAL_ROADS: usize = 2;
+
+impl State {
+    /// `color`'s handicap, if the game gives out any
+    pub fn get_handicap(&self, color: u8) -> Option<&SeatHandicap> {
+        self.config.handicaps.get(color as usize)
+    }
+
+    /// Victory points `color` started the game with
+    pub fn get_vp_offset(&self, color: u8) -> u8 {
+        self.get_handicap(color)
+            .map_or(0, |handicap| handicap.vp_offset)
+    }
+
+    /// Roads `color` places during the initial build phase
+    pub fn initial_roads(&self, color: u8) -> usize {
+        let extra = self
+            .get_handicap(color)
+            .is_some_and(|handicap| handicap.extra_road);
+        INITIAL_ROADS + extra as usize
+    }
+
+    /// Roads on the board once the initial build phase is over
+    pub(super) fn total_initial_roads(&self) -> usize {
+        (0..self.get_num_players())
+            .map(|color| self.initial_roads(color))
+            .sum()
+    }
+
+    /// Whether `color` has placed both initial settlements but still owes the extra road
+    /// of its handicap
+    pub(super) fn owes_extra_road(&self, color: u8, settlements: usize) -> bool {
+        settlements == 2
+            && (self.roads_by_color[color as usize] as usize) < self.initial_roads(color)
+    }
+
+    /// Deal the starting cards and points. Validation made sure the bank covers the cards.
+    pub(super) fn apply_handicaps(&mut self) {
+        let num_players = self.get_num_players();
+        for color in 0..num_players {
+            let Some(handicap) = self.config.handicaps.get(color as usize) else {
+                continue;
+            };
+            let (resources, vp_offset) = (handicap.resources, handicap.vp_offset);
+            freqdeck_sub(&mut self.vector[BANK_RESOURCE_SLICE], resources);
+            freqdeck_add(self.get_mut_player_hand(color), resources);
+            self.vector[actual_victory_points_index(num_players, color)] += vp_offset;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, ActionPrompt, GameConfiguration};
+    use crate::map_instance::MapInstance;
+    use std::sync::Arc;
+
+    fn handicapped_state() -> State {
+        let config = GameConfiguration::builder()
+            .handicaps(vec![
+                SeatHandicap::default(),
+                SeatHandicap {
+                    resources: [1, 1, 0, 0, 2],
+                    vp_offset: 1,
+                    extra_road: true,
+                },
+                SeatHandicap::default(),
+                SeatHandicap::default(),
+            ])
+            .build()
+            .unwrap();
+        let map_instance = MapInstance::shared(&config.map_type, 0);
+        State::new(Arc::new(config), map_instance)
+    }
+
+    #[test]
+    fn test_handicap_deals_cards_and_points() {
+        let state = handicapped_state();
+        assert_eq!(state.get_player_hand(1), [1, 1, 0, 0, 2]);
+        assert_eq!(state.get_bank_resources(), [18, 18, 19, 19, 17]);
+        assert_eq!(state.get_actual_victory_points(1), 1);
+        assert_eq!(state.get_public_victory_points(1), 1);
+        assert_eq!(state.get_actual_victory_points(0), 0);
+    }
+
+    #[test]
+    fn test_extra_road_is_placed_after_the_second_road() {
+        let mut state = handicapped_state();
+        let mut extra_roads = 0;
+        while state.is_initial_build_phase() {
+            let color = state.get_current_color();
+            let prompt = state.get_action_prompt();
+            if matches!(prompt, ActionPrompt::BuildInitialRoad)
+                && state.roads_by_color[color as usize] == 2
+            {
+                assert_eq!(color, 1);
+                extra_roads += 1;
+            }
+            let action = state.generate_playable_actions()[0];
+            assert!(matches!(
+                action,
+                Action::BuildSettlement { .. } | Action::BuildRoad { .. }
+            ));
+            state.apply_action(action);
+        }
+        assert_eq!(extra_roads, 1);
+        assert_eq!(state.roads_by_color, vec![2, 3, 2, 2]);
+        assert_eq!(state.get_current_color(), 0);
+        assert!(matches!(state.get_action_prompt(), ActionPrompt::PlayTurn));
+    }
+
+    #[test]
+    fn test_handicaps_are_validated() {
+        let builder = || GameConfiguration::builder().num_players(2);
+        assert!(builder()
+            .handicaps(vec![SeatHandicap::default()])
+            .build()
+            .is_err());
+        let rich = SeatHandicap {
+            resources: [10, 0, 0, 0, 0],
+            ..Default::default()
+        };
+        assert!(builder()
+            .handicaps(vec![rich.clone(), rich])
+            .build()
+            .is_err());
+        let winner = SeatHandicap {
+            vp_offset: 10,
+            ..Default::default()
+        };
+        assert!(builder()
+            .handicaps(vec![winner, SeatHandicap::default()])
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,467 @@
+// Longest road - the longest trail of one color's roads that does not pass through an
+// opponent's building. Enemy buildings are split into one dead-end vertex per road
+// touching them, which leaves each road network as a set of independent pieces. A
+// piece without cycles is a tree, whose longest trail is its diameter (two BFS passes);
+// only pieces with cycles need the exhaustive search, and that search is capped.
+// Seafarers ships count like roads, but a road and a ship only join at a building of
+// their owner.
+//
+// Trails start on the color's own intersections, so a road running from one enemy
+// building to another does not count unless the game's rules version says it does.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use smallvec::SmallVec;
+
+use super::{NodeMask, State};
+use crate::map_instance::{EdgeId, NodeId};
+
+/// Search nodes the exhaustive fallback may visit per call before it settles for the
+/// best trail found so far. Far above what a 15-road network needs.
+pub const LONGEST_ROAD_NODE_LIMIT: usize = 1_000_000;
+
+impl State {
+    pub fn longest_acyclic_path(
+        &self,
+        connected_node_set: &HashSet<NodeId>,
+        color: u8,
+    ) -> Vec<EdgeId> {
+        self.longest_acyclic_path_limited(connected_node_set, color, LONGEST_ROAD_NODE_LIMIT)
+    }
+
+    /// Longest trail of `color`'s roads reachable from `connected_node_set`. Pieces
+    /// with cycles are searched exhaustively until `node_limit` search nodes have been
+    /// visited; past that the result may be shorter than the true longest road.
+    pub fn longest_acyclic_path_limited(
+        &self,
+        connected_node_set: &HashSet<NodeId>,
+        color: u8,
+        node_limit: usize,
+    ) -> Vec<EdgeId> {
+        let graph = RoadGraph::build(self, connected_node_set, color);
+        let between_opponents = self.rules().roads_between_opponents_count();
+        let mut budget = node_limit;
+        let mut best: Vec<usize> = Vec::new();
+        for piece in graph.pieces() {
+            let edge_count = piece
+                .iter()
+                .map(|&v| graph.adjacency[v].len())
+                .sum::<usize>()
+                / 2;
+            let path = match (edge_count + 1 == piece.len(), between_opponents) {
+                (true, true) => graph.diameter(piece[0]),
+                (true, false) => graph.longest_path_from_own_end(&piece),
+                (false, _) => graph.longest_trail(&piece, between_opponents, &mut budget),
+            };
+            if path.len() > best.len() {
+                best = path;
+            }
+        }
+        best.into_iter().map(|edge| graph.edges[edge]).collect()
+    }
+}
+
+/// One color's roads as a small graph of local vertex ids
+struct RoadGraph {
+    /// Canonical edge of each road
+    edges: Vec<EdgeId>,
+    /// vertex -> (neighbor vertex, road index)
+    adjacency: Vec<SmallVec<[(usize, usize); 3]>>,
+    /// Whether each vertex is a road's end at an enemy building
+    enemy_ends: Vec<bool>,
+}
+
+impl RoadGraph {
+    fn build(state: &State, seeds: &HashSet<NodeId>, color: u8) -> Self {
+        // Tolerate legacy unsorted road inserts
+        let owns = |a: NodeId, b: NodeId| {
+            state.roads.get(&(a.min(b), a.max(b))) == Some(&color)
+                || state.roads.get(&(a, b)) == Some(&color)
+                || state.roads.get(&(b, a)) == Some(&color)
+        };
+        let mut graph = RoadGraph {
+            edges: Vec::new(),
+            adjacency: Vec::new(),
+            enemy_ends: Vec::new(),
+        };
+        // Keyed by node and whether a ship ends there away from an own building
+        let mut vertices: HashMap<(NodeId, bool), usize> = HashMap::new();
+        let mut vertex = |graph: &mut RoadGraph, node: NodeId, ship: bool| {
+            // An enemy building ends the road, so every road into it gets its own end
+            if state.is_enemy_node(color, node) {
+                graph.adjacency.push(SmallVec::new());
+                graph.enemy_ends.push(true);
+                return graph.adjacency.len() - 1;
+            }
+            let at_sea = ship && state.get_node_color(node) != Some(color);
+            *vertices.entry((node, at_sea)).or_insert_with(|| {
+                graph.adjacency.push(SmallVec::new());
+                graph.enemy_ends.push(false);
+                graph.adjacency.len() - 1
+            })
+        };
+
+        // Walk outward from the seeds; roads can reach an enemy building but not pass it
+        let mut expanded = NodeMask::default();
+        let mut stack: Vec<NodeId> = seeds.iter().copied().collect();
+        while let Some(node) = stack.pop() {
+            if !expanded.insert(node) {
+                continue;
+            }
+            let roads = state
+                .map_instance
+                .get_neighbor_nodes(node)
+                .iter()
+                .filter(|&&neighbor| owns(node, neighbor))
+                .map(|&neighbor| (neighbor, false));
+            let ships = state
+                .map_instance
+                .get_sea_edges(node)
+                .iter()
+                .filter(|edge| state.ships.get(edge) == Some(&color))
+                .map(|&(a, b)| (if a == node { b } else { a }, true));
+            for (neighbor, ship) in roads.chain(ships) {
+                let edge = (node.min(neighbor), node.max(neighbor));
+                if graph.edges.contains(&edge) {
+                    continue;
+                }
+                let (a, b) = (
+                    vertex(&mut graph, node, ship),
+                    vertex(&mut graph, neighbor, ship),
+                );
+                let index = graph.edges.len();
+                graph.edges.push(edge);
+                graph.adjacency[a].push((b, index));
+                graph.adjacency[b].push((a, index));
+                if !state.is_enemy_node(color, neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Vertices of each connected piece
+    fn pieces(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.adjacency.len()];
+        let mut pieces = Vec::new();
+        for start in 0..self.adjacency.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut piece = vec![start];
+            let mut next = 0;
+            while next < piece.len() {
+                for &(neighbor, _) in &self.adjacency[piece[next]] {
+                    if !seen[neighbor] {
+                        seen[neighbor] = true;
+                        piece.push(neighbor);
+                    }
+                }
+                next += 1;
+            }
+            pieces.push(piece);
+        }
+        pieces
+    }
+
+    /// Longest path in the tree containing `start`: the farthest vertex from anywhere
+    /// is one end of a diameter, and the farthest vertex from that end is the other.
+    fn diameter(&self, start: usize) -> Vec<usize> {
+        let (end, _) = self.farthest_from(start);
+        let (other_end, parents) = self.farthest_from(end);
+        Self::path_to(&parents, other_end)
+    }
+
+    /// Longest path in a tree that starts on one of the color's own intersections, so
+    /// not from one enemy building to another. The farthest vertex from any vertex is
+    /// an end of the diameter, so each candidate start only needs both diameter ends.
+    fn longest_path_from_own_end(&self, piece: &[usize]) -> Vec<usize> {
+        let (end, _) = self.farthest_from(piece[0]);
+        let (other_end, from_end) = self.farthest_from(end);
+        let (_, from_other_end) = self.farthest_from(other_end);
+        let mut best = Vec::new();
+        for &vertex in piece.iter().filter(|&&v| !self.enemy_ends[v]) {
+            for parents in [&from_end, &from_other_end] {
+                let path = Self::path_to(parents, vertex);
+                if path.len() > best.len() {
+                    best = path;
+                }
+            }
+        }
+        best
+    }
+
+    /// Roads leading from the root of a BFS tree (vertex -> parent, road) to `vertex`
+    fn path_to(parents: &[Option<(usize, usize)>], vertex: usize) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut current = vertex;
+        while let Some((parent, edge)) = parents[current] {
+            path.push(edge);
+            current = parent;
+        }
+        path
+    }
+
+    /// Farthest vertex from `start` and the BFS tree (vertex -> parent, road) leading to it
+    fn farthest_from(&self, start: usize) -> (usize, Vec<Option<(usize, usize)>>) {
+        let mut parents = vec![None; self.adjacency.len()];
+        let mut visited = vec![false; self.adjacency.len()];
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+        let mut last = start;
+        while let Some(vertex) = queue.pop_front() {
+            last = vertex;
+            for &(neighbor, edge) in &self.adjacency[vertex] {
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    parents[neighbor] = Some((vertex, edge));
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        (last, parents)
+    }
+
+    /// Exhaustive longest trail (no road used twice) over a piece with cycles, starting
+    /// on the color's own intersections unless `from_enemy_ends`
+    fn longest_trail(
+        &self,
+        piece: &[usize],
+        from_enemy_ends: bool,
+        budget: &mut usize,
+    ) -> Vec<usize> {
+        let mut used = vec![false; self.edges.len()];
+        let mut current = Vec::new();
+        let mut best = Vec::new();
+        for &start in piece {
+            if self.enemy_ends[start] && !from_enemy_ends {
+                continue;
+            }
+            self.extend_trail(start, &mut used, &mut current, &mut best, budget);
+        }
+        best
+    }
+
+    fn extend_trail(
+        &self,
+        vertex: usize,
+        used: &mut [bool],
+        current: &mut Vec<usize>,
+        best: &mut Vec<usize>,
+        budget: &mut usize,
+    ) {
+        if *budget == 0 {
+            return;
+        }
+        *budget -= 1;
+        if current.len() > best.len() {
+            best.clone_from(current);
+        }
+        for &(neighbor, edge) in &self.adjacency[vertex] {
+            if used[edge] {
+                continue;
+            }
+            used[edge] = true;
+            current.push(edge);
+            self.extend_trail(neighbor, used, current, best, budget);
+            current.pop();
+            used[edge] = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{GameConfiguration, MapType, RulesVersion};
+    use crate::map_instance::MapInstance;
+    use crate::state::Building;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+    use std::sync::Arc;
+
+    fn state_with_roads(color: u8, roads: &[EdgeId]) -> State {
+        let mut state = State::new_base();
+        for &edge in roads {
+            state.roads.insert(edge, color);
+        }
+        state
+    }
+
+    // The exhaustive search the tree solver replaced: trails from every seed, ending at
+    // the first enemy building they reach
+    fn original_longest_path(state: &State, seeds: &HashSet<NodeId>, color: u8) -> usize {
+        fn dfs(state: &State, node: NodeId, color: u8, path: &mut Vec<EdgeId>) -> usize {
+            let mut best = path.len();
+            for &neighbor in state.map_instance.get_neighbor_nodes(node) {
+                let edge = (node.min(neighbor), node.max(neighbor));
+                if path.contains(&edge) || state.roads.get(&edge) != Some(&color) {
+                    continue;
+                }
+                path.push(edge);
+                best = best.max(if state.is_enemy_node(color, neighbor) {
+                    path.len()
+                } else {
+                    dfs(state, neighbor, color, path)
+                });
+                path.pop();
+            }
+            best
+        }
+        seeds
+            .iter()
+            .map(|&seed| dfs(state, seed, color, &mut Vec::new()))
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_matches_the_original_search_under_legacy_rules() {
+        let mut rng = XorShiftRng::seed_from_u64(7);
+        let mut land_nodes: Vec<NodeId> = State::new_base()
+            .map_instance
+            .land_nodes()
+            .iter()
+            .copied()
+            .collect();
+        land_nodes.sort();
+        for _ in 0..2000 {
+            let mut state = State::new_base();
+            let mut config = state.get_config().as_ref().clone();
+            config.rules = RulesVersion::Legacy;
+            state.config = Arc::new(config);
+
+            // A random network grown from one node, with enemy settlements along it
+            let start = *land_nodes.choose(&mut rng).unwrap();
+            let mut reached = vec![start];
+            for _ in 0..rng.gen_range(3..16) {
+                let from = *reached.choose(&mut rng).unwrap();
+                let to = *state
+                    .map_instance
+                    .get_neighbor_nodes(from)
+                    .choose(&mut rng)
+                    .unwrap();
+                state.roads.insert((from.min(to), from.max(to)), 0);
+                reached.push(to);
+            }
+            for &node in &reached {
+                if node != start && rng.gen_bool(0.2) {
+                    state.buildings.insert(node, Building::Settlement(1, node));
+                }
+            }
+
+            // The own intersections connected to the start, as road components hold them
+            let mut seeds = HashSet::from([start]);
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for &neighbor in state.map_instance.get_neighbor_nodes(node) {
+                    let edge = (node.min(neighbor), node.max(neighbor));
+                    if state.roads.get(&edge) == Some(&0)
+                        && !state.is_enemy_node(0, neighbor)
+                        && seeds.insert(neighbor)
+                    {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            assert_eq!(
+                state.longest_acyclic_path(&seeds, 0).len(),
+                original_longest_path(&state, &seeds, 0),
+                "roads {:?}, enemy buildings {:?}",
+                state.roads,
+                state.buildings.keys()
+            );
+        }
+    }
+
+    #[test]
+    fn test_tree_network_uses_diameter() {
+        let state = state_with_roads(0, &[(0, 20), (19, 20), (20, 22), (22, 23), (6, 23)]);
+        let nodes = HashSet::from([0, 19, 20, 22, 23, 6]);
+        let path = state.longest_acyclic_path(&nodes, 0);
+        assert_eq!(path.len(), 4);
+        assert!(path.contains(&(22, 23)) && path.contains(&(6, 23)));
+    }
+
+    #[test]
+    fn test_enemy_buildings_end_but_do_not_break_roads() {
+        let mut state = state_with_roads(0, &[(1, 2), (2, 3), (3, 4), (4, 5)]);
+        state.buildings.insert(1, Building::Settlement(1, 1));
+        state.buildings.insert(4, Building::Settlement(1, 4));
+        // 1-2-3-4 runs between two enemy settlements; 4-5 is cut off by the one on 4
+        let mut path = state.longest_acyclic_path(&HashSet::from([2, 3]), 0);
+        path.sort();
+        assert_eq!(path, vec![(1, 2), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn test_legacy_rules_keep_old_longest_road() {
+        let mut state = state_with_roads(0, &[(1, 2), (2, 3), (3, 4)]);
+        state.buildings.insert(1, Building::Settlement(1, 1));
+        state.buildings.insert(4, Building::Settlement(1, 4));
+        let nodes = HashSet::from([2, 3]);
+        assert_eq!(state.longest_acyclic_path(&nodes, 0).len(), 3);
+
+        let mut config = state.get_config().as_ref().clone();
+        config.rules = RulesVersion::Legacy;
+        state.config = std::sync::Arc::new(config);
+        assert_eq!(state.longest_acyclic_path(&nodes, 0).len(), 2);
+    }
+
+    #[test]
+    fn test_ships_join_roads_only_at_own_buildings() {
+        let config = GameConfiguration {
+            map_type: MapType::Seafarers,
+            ..GameConfiguration::default()
+        };
+        let map = MapInstance::shared(&config.map_type, 0);
+        let mut state = State::new_with_seed(Arc::new(config), map.clone(), 0);
+        // A coastal node with a road inland and two ships in a row out to sea
+        let (node, road, ship, next_ship) = map
+            .land_nodes()
+            .iter()
+            .find_map(|&node| {
+                let &(a, b) = map
+                    .get_neighbor_edges(node)
+                    .iter()
+                    .find(|&&(a, b)| !map.sea_edges.contains(&(a.min(b), a.max(b))))?;
+                let ship = *map.get_sea_edges(node).first()?;
+                let far = if ship.0 == node { ship.1 } else { ship.0 };
+                let next_ship = *map.get_sea_edges(far).iter().find(|&&e| e != ship)?;
+                Some((node, (a.min(b), a.max(b)), ship, next_ship))
+            })
+            .unwrap();
+        state.roads.insert(road, 0);
+        state.ships.insert(ship, 0);
+        state.ships.insert(next_ship, 0);
+        let seeds = HashSet::from([node]);
+        assert_eq!(state.longest_acyclic_path(&seeds, 0).len(), 2);
+
+        state.buildings.insert(node, Building::Settlement(0, node));
+        assert_eq!(state.longest_acyclic_path(&seeds, 0).len(), 3);
+    }
+
+    #[test]
+    fn test_node_limit_caps_cyclic_search() {
+        let state = state_with_roads(
+            0,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (0, 5),
+                (0, 20),
+                (19, 20),
+            ],
+        );
+        let nodes = HashSet::from([0, 1, 2, 3, 4, 5, 19, 20]);
+        assert_eq!(state.longest_acyclic_path(&nodes, 0).len(), 8);
+        let capped = state.longest_acyclic_path_limited(&nodes, 0, 3);
+        assert!(capped.len() <= 2);
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use super::State;
+use crate::enums::{Action, ActionPrompt};
+
+/// How many times the same state may be seen within one turn before it is treated as a loop
+pub const LOOP_REPETITION_LIMIT: u8 = 3;
+
+/// Diagnostic produced when a loop was detected and broken by forcing EndTurn
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopBreak {
+    pub color: u8,
+    pub state_hash: u64,
+    pub repetitions: u8,
+    /// Whether EndTurn could actually be applied (it is only legal once the player has rolled)
+    pub forced_end_turn: bool,
+}
+
+/// Tracks state hashes seen during the current turn.
+///
+/// Lives outside of `State` on purpose: search code clones states constantly and
+/// should not pay for this bookkeeping. Drivers that own a real game (Game, simulate)
+/// call `observe` after every applied action.
+#[derive(Debug, Clone, Default)]
+pub struct LoopDetector {
+    seen_this_turn: HashMap<u64, u8>,
+}
+
+impl LoopDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the state reached after applying `action`. When the same state recurs
+    /// `LOOP_REPETITION_LIMIT` times within a turn, EndTurn is applied (if legal) and
+    /// a diagnostic is returned.
+    pub fn observe(&mut self, state: &mut State, action: Action) -> Option<LoopBreak> {
+        let loop_break = self.check(state, action)?;
+        if loop_break.forced_end_turn {
+            state.apply_action(Action::EndTurn {
+                color: loop_break.color,
+            });
+        }
+        Some(loop_break)
+    }
+
+    /// Same as `observe`, but leaves applying EndTurn to the caller. Used by drivers
+    /// that need to record the forced action themselves (e.g. Game's replay log).
+    pub fn check(&mut self, state: &State, action: Action) -> Option<LoopBreak> {
+        if matches!(action, Action::EndTurn { .. }) {
+            self.seen_this_turn.clear();
+            return None;
+        }
+
+        let state_hash = state.compute_hash64();
+        let count = self.seen_this_turn.entry(state_hash).or_insert(0);
+        *count = count.saturating_add(1);
+        if *count < LOOP_REPETITION_LIMIT {
+            return None;
+        }
+        let repetitions = *count;
+        self.seen_this_turn.clear();
+
+        let color = state.get_current_color();
+        let forced_end_turn = matches!(state.get_action_prompt(), ActionPrompt::PlayTurn)
+            && state
+                .generate_playable_actions()
+                .contains(&Action::EndTurn { color });
+        log::warn!(
+            "🔁 Loop detected: state {state_hash:#x} seen {repetitions} times this turn for color {color} (forced EndTurn: {forced_end_turn})"
+        );
+
+        Some(LoopBreak {
+            color,
+            state_hash,
+            repetitions,
+            forced_end_turn,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::StateEdit;
+
+    #[test]
+    fn test_repeated_state_forces_end_turn() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::HasRolled(true),
+        ] {
+            state.apply_action(Action::DebugSetState { color, edit });
+        }
+        let action = Action::BuyDevelopmentCard { color };
+
+        let mut detector = LoopDetector::new();
+        for _ in 1..LOOP_REPETITION_LIMIT {
+            assert_eq!(detector.observe(&mut state, action), None);
+        }
+        let diagnostic = detector.observe(&mut state, action).expect("loop detected");
+
+        assert_eq!(diagnostic.color, color);
+        assert_eq!(diagnostic.repetitions, LOOP_REPETITION_LIMIT);
+        assert!(diagnostic.forced_end_turn);
+        assert_ne!(state.get_current_color(), color);
+    }
+
+    #[test]
+    fn test_end_turn_resets_tracking() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        let action = Action::BuyDevelopmentCard { color };
+
+        let mut detector = LoopDetector::new();
+        for _ in 0..10 {
+            assert_eq!(detector.observe(&mut state, action), None);
+            detector.observe(&mut state, Action::EndTurn { color });
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// Applying actions to a State. `apply_action` dispatches each action to the module
+// that implements it; the award helpers shared between them (victory points, Longest
+// Road, Largest Army) live here.
+
+mod building;
+#[cfg(any(test, feature = "debug-actions"))]
+mod debug;
+mod devcards;
+mod dice;
+mod robber;
+mod trade;
+mod turn;
+
+use std::collections::HashMap;
+
+use super::Building;
+use super::State;
+
+use crate::enums::{Action, DevCard};
+use crate::state_vector::*;
+
+impl State {
+    pub fn apply_action(&mut self, action: Action) {
+        let before_initial = self.is_initial_build_phase();
+        let before_settlements = self
+            .buildings_by_color
+            .values()
+            .map(|buildings| {
+                buildings
+                    .iter()
+                    .filter(|b| matches!(b, Building::Settlement(_, _)))
+                    .count()
+            })
+            .sum::<usize>();
+        let before_roads = self.roads.len();
+
+        match action {
+            Action::BuildSettlement { color, node_id } => {
+                let (new_owner, new_length) = self.build_settlement(color, node_id);
+                self.maintain_longest_road(new_owner, new_length);
+                self.maintain_harbormaster();
+            }
+            Action::BuildRoad { color, edge_id } => {
+                let (new_owner, new_length) = self.build_road(color, edge_id);
+                self.maintain_longest_road(new_owner, new_length);
+            }
+            Action::BuildShip { color, edge_id } => {
+                let (new_owner, new_length) = self.build_ship(color, edge_id);
+                self.maintain_longest_road(new_owner, new_length);
+            }
+            Action::MoveShip { color, from, to } => {
+                let (new_owner, new_length) = self.move_ship(color, from, to);
+                self.maintain_longest_road(new_owner, new_length);
+            }
+            Action::BuildCity { color, node_id } => {
+                self.build_city(color, node_id);
+                self.maintain_harbormaster();
+            }
+            Action::BuyDevelopmentCard { color } => {
+                self.buy_development_card(color);
+            }
+            Action::Roll { color, dice_opt } => {
+                self.roll_dice(color, dice_opt);
+            }
+            Action::Discard { color, resources } => {
+                self.discard(color, resources);
+            }
+            Action::ChooseGold { color, resource } => {
+                self.choose_gold(color, resource);
+            }
+            Action::MoveRobber {
+                color,
+                coordinate,
+                victim_opt,
+            } => {
+                self.move_robber(color, coordinate, victim_opt);
+            }
+            Action::PlayKnight { color } => {
+                self.play_knight(color);
+                self.maintain_largest_army();
+            }
+            Action::PlayYearOfPlenty { color, resources } => {
+                self.play_year_of_plenty(color, resources);
+            }
+            Action::PlayMonopoly { color, resource } => {
+                self.play_monopoly(color, resource);
+            }
+            Action::PlayRoadBuilding { color } => {
+                self.play_road_building(color);
+            }
+            Action::MaritimeTrade {
+                color,
+                give,
+                take,
+                ratio,
+            } => {
+                self.maritime_trade(color, give, take, ratio);
+            }
+            Action::OfferTrade {
+                color,
+                trade: (give, take),
+            } => {
+                self.offer_trade(color, give, take);
+            }
+            Action::AcceptTrade { color, .. } => {
+                self.answer_trade(color, true);
+            }
+            Action::RejectTrade { color } => {
+                self.answer_trade(color, false);
+            }
+            Action::CounterTrade {
+                color,
+                trade: (give, take),
+            } => {
+                self.counter_trade(color, give, take);
+            }
+            Action::ConfirmTrade {
+                trade: (_, _, acceptee),
+                ..
+            } => {
+                self.confirm_trade(acceptee);
+            }
+            Action::CancelTrade { .. } => {
+                self.close_trade();
+            }
+            Action::GiftResource {
+                color,
+                teammate,
+                resource,
+            } => {
+                self.gift_resource(color, teammate, resource);
+            }
+            Action::EndTurn { color } => {
+                self.reset_is_road_building();
+                self.end_turn(color);
+            }
+            Action::PassBuildPhase { color } => {
+                self.reset_is_road_building();
+                self.pass_build_phase(color);
+            }
+            #[cfg(any(test, feature = "debug-actions"))]
+            Action::DebugSetState { color, edit } => {
+                self.debug_set_state(color, edit);
+            }
+            #[cfg(test)]
+            Action::DebugPanic { .. } => {
+                panic!("Action not implemented: {action:?}");
+            }
+        }
+
+        // Log important state changes
+        let after_initial = self.is_initial_build_phase();
+        let after_settlements = self
+            .buildings_by_color
+            .values()
+            .map(|buildings| {
+                buildings
+                    .iter()
+                    .filter(|b| matches!(b, Building::Settlement(_, _)))
+                    .count()
+            })
+            .sum::<usize>();
+        let after_roads = self.roads.len();
+
+        // Log phase transitions and significant state changes
+        if before_initial != after_initial {
+            log::info!(
+                "🎯 PHASE TRANSITION: Initial build phase = {} → {}",
+                before_initial,
+                after_initial
+            );
+        }
+
+        if before_settlements != after_settlements || before_roads != after_roads {
+            log::info!(
+                "🏗️  Build progress: Settlements: {} → {}, Roads: {} → {}",
+                before_settlements,
+                after_settlements,
+                before_roads,
+                after_roads
+            );
+        }
+    }
+
+    pub fn add_victory_points(&mut self, color: u8, points: u8) {
+        let n = self.get_num_players();
+        self.vector[actual_victory_points_index(n, color)] += points;
+        self.check_for_victory(); // Check for win condition whenever VPs change
+    }
+
+    pub fn sub_victory_points(&mut self, color: u8, points: u8) {
+        let n = self.get_num_players();
+        self.vector[actual_victory_points_index(n, color)] -= points;
+        self.check_for_victory(); // Check for win condition whenever VPs change
+    }
+
+    pub(super) fn maintain_longest_road(&mut self, new_owner: Option<u8>, new_length: u8) {
+        let prev_owner = self.longest_road_color;
+        self.longest_road_color = new_owner;
+        self.longest_road_length = new_length;
+
+        if new_owner == prev_owner {
+            return;
+        }
+
+        if let Some(prev_owner) = prev_owner {
+            self.sub_victory_points(prev_owner, 2);
+        }
+
+        if let Some(new_owner) = new_owner {
+            self.add_victory_points(new_owner, 2);
+        }
+    }
+
+    /// Longest Road holder after a recount, given each color's longest road.
+    pub(super) fn longest_road_holder(&self, road_lengths: &HashMap<u8, u8>) -> Option<u8> {
+        let new_longest_road_length = road_lengths.values().max().unwrap_or(&0);
+
+        // Determine new longest road owner based on this implementation's rules:
+        // 1. Need >= 5 roads to initially claim longest road
+        // 2. Once you have it, you keep it unless someone else builds a longer road OR your road becomes < 5
+        // 3. If no one currently has it, need >= 5 to claim it
+        if let Some(current_holder) = self.longest_road_color {
+            // Someone currently holds longest road
+            let current_holder_length = road_lengths.get(&current_holder).unwrap_or(&0);
+
+            // Current holder loses it if their road becomes < 5
+            if *current_holder_length < 5 {
+                // Check if anyone else has >= 5 to claim it
+                if *new_longest_road_length >= 5 {
+                    // Sort for deterministic iteration
+                    let mut candidates: Vec<_> = road_lengths
+                        .iter()
+                        .filter(|(_, &length)| length == *new_longest_road_length)
+                        .collect();
+                    candidates.sort_by_key(|(&color, _)| color);
+                    candidates.first().map(|(&color, _)| color)
+                } else {
+                    None
+                }
+            } else {
+                // Current holder has >= 5, check if anyone else has longer
+                let someone_has_longer = road_lengths.iter().any(|(&color, &length)| {
+                    color != current_holder && length > *current_holder_length
+                });
+
+                if someone_has_longer {
+                    // Find who has the longest road now (deterministic)
+                    let max_length = road_lengths.values().max().unwrap_or(&0);
+                    let mut candidates: Vec<_> = road_lengths
+                        .iter()
+                        .filter(|(_, &length)| length == *max_length)
+                        .collect();
+                    candidates.sort_by_key(|(&color, _)| color);
+                    candidates.first().map(|(&color, _)| color)
+                } else {
+                    // Current holder keeps it
+                    Some(current_holder)
+                }
+            }
+        } else if *new_longest_road_length >= 5 {
+            // No one currently has longest road, but someone has >= 5 (deterministic)
+            let mut candidates: Vec<_> = road_lengths
+                .iter()
+                .filter(|(_, &length)| length == *new_longest_road_length)
+                .collect();
+            candidates.sort_by_key(|(&color, _)| color);
+            candidates.first().map(|(&color, _)| color)
+        } else {
+            // No one has longest road and no one has >= 5
+            None
+        }
+    }
+
+    pub(super) fn maintain_largest_army(&mut self) {
+        let prev_owner = self.largest_army_color;
+        let prev_count = self.largest_army_count;
+
+        // Find player with most knights (if any have 3 or more)
+        let mut max_knights = 0;
+        let mut max_knights_color = None;
+
+        for color in 0..self.get_num_players() {
+            let knights = self.get_played_dev_card_count(color, DevCard::Knight as usize);
+            if knights >= 3 && knights > max_knights {
+                max_knights = knights;
+                max_knights_color = Some(color);
+            }
+        }
+
+        // Case where playerB meets playerA's largest army -> no change
+        if max_knights == prev_count {
+            return;
+        }
+
+        self.largest_army_color = max_knights_color;
+        self.largest_army_count = max_knights;
+
+        // If playerA retains largest army -> no VP changes
+        if max_knights_color == prev_owner {
+            return;
+        }
+
+        if let Some(prev_owner) = prev_owner {
+            self.sub_victory_points(prev_owner, 2);
+        }
+
+        if let Some(new_owner) = max_knights_color {
+            self.add_victory_points(new_owner, 2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_road_holder() {
+        let mut state = State::new_base();
+        assert_eq!(
+            state.longest_road_holder(&HashMap::from([(0, 4), (1, 3)])),
+            None
+        );
+        assert_eq!(
+            state.longest_road_holder(&HashMap::from([(0, 5), (1, 5)])),
+            Some(0)
+        );
+
+        // The holder keeps it on a tie, loses it to a longer road or when cut below 5
+        state.longest_road_color = Some(1);
+        assert_eq!(
+            state.longest_road_holder(&HashMap::from([(0, 6), (1, 6)])),
+            Some(1)
+        );
+        assert_eq!(
+            state.longest_road_holder(&HashMap::from([(0, 7), (1, 6)])),
+            Some(0)
+        );
+        assert_eq!(
+            state.longest_road_holder(&HashMap::from([(0, 3), (1, 4)])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_largest_army_moves_with_victory_points() {
+        let mut state = State::new_base();
+        let n = state.get_num_players();
+        state.vector[player_played_devhand_slice(n, 0)][DevCard::Knight as usize] = 3;
+        state.maintain_largest_army();
+        assert_eq!(state.largest_army_color, Some(0));
+        assert_eq!(state.vector[actual_victory_points_index(n, 0)], 2);
+
+        state.vector[player_played_devhand_slice(n, 1)][DevCard::Knight as usize] = 4;
+        state.maintain_largest_army();
+        assert_eq!(state.largest_army_color, Some(1));
+        assert_eq!(state.vector[actual_victory_points_index(n, 0)], 0);
+        assert_eq!(state.vector[actual_victory_points_index(n, 1)], 2);
+    }
+}
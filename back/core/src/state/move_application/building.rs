@@ -0,0 +1,997 @@
+// Settlements, roads, ships and cities, and the road-network caches they maintain:
+// connected components per color and the Longest Road recount when a settlement
+// cuts a network or a ship moves.
+
+use std::collections::{HashMap, HashSet};
+
+use super::super::{Building, State};
+use crate::deck_slices::{
+    freqdeck_add, freqdeck_sub, CITY_COST, ROAD_COST, SETTLEMENT_COST, SHIP_COST,
+};
+use crate::map_instance::{EdgeId, NodeId};
+use crate::state_vector::*;
+
+impl State {
+    pub fn build_settlement(&mut self, placing_color: u8, node_id: u8) -> (Option<u8>, u8) {
+        self.buildings
+            .insert(node_id, Building::Settlement(placing_color, node_id));
+        self.buildings_by_color
+            .entry(placing_color)
+            .or_default()
+            .push(Building::Settlement(placing_color, node_id));
+        self.get_mut_pieces_left(placing_color)[SETTLEMENTS_LEFT_OFFSET] -= 1;
+        // A settlement also takes spots from everyone else
+        self.mark_board_changed(None);
+        self.add_node_production(placing_color, node_id);
+
+        let is_free = self.is_initial_build_phase();
+        if !is_free {
+            freqdeck_sub(self.get_mut_player_hand(placing_color), SETTLEMENT_COST);
+            freqdeck_add(&mut self.vector[BANK_RESOURCE_SLICE], SETTLEMENT_COST);
+        }
+
+        self.add_victory_points(placing_color, 1);
+
+        // Update board_buildable_ids cache - remove the settlement node and its neighbors
+        self.board_buildable_ids.remove(&node_id);
+        for neighbor_id in self.map_instance.get_neighbor_nodes(node_id) {
+            self.board_buildable_ids.remove(neighbor_id);
+        }
+
+        if is_free {
+            let owned_buildings = self.buildings_by_color.get(&placing_color).unwrap();
+            let owned_settlements = owned_buildings
+                .iter()
+                .filter(|b| matches!(b, Building::Settlement(_, _)))
+                .count();
+
+            // If second house, yield resources
+            if owned_settlements == 2 {
+                let adjacent_tiles = self.map_instance.get_adjacent_tiles(node_id);
+                if let Some(adjacent_tiles) = adjacent_tiles {
+                    let mut total_resources = [0; 5];
+                    for tile in adjacent_tiles {
+                        if let Some(resource) = tile.resource {
+                            total_resources[resource as usize] += 1;
+                        }
+                    }
+
+                    let bank = &mut self.vector[BANK_RESOURCE_SLICE];
+                    freqdeck_sub(bank, total_resources);
+
+                    let hand = self.get_mut_player_hand(placing_color);
+                    freqdeck_add(hand, total_resources);
+
+                    log::info!(
+                        "🎁 Player {} received resources from second settlement: {:?}",
+                        placing_color,
+                        total_resources
+                    );
+                }
+            }
+            // Maintain caches and longest road =====
+            //   - connected_components
+            let component = HashSet::from([node_id]);
+            self.connected_components
+                .entry(placing_color)
+                .or_default()
+                .push(component);
+
+            // During initial build phase, preserve existing longest road state
+            (self.longest_road_color, self.longest_road_length)
+        } else {
+            // Mantain connected_components
+            // Mantain longest_road_color and longest_road_length
+
+            let mut plowed_edges_by_color: HashMap<u8, Vec<EdgeId>> = HashMap::new();
+            for edge in self.map_instance.get_neighbor_edges(node_id) {
+                let canonical_edge = (edge.0.min(edge.1), edge.0.max(edge.1));
+                if let Some(&road_color) = self.roads.get(&canonical_edge) {
+                    plowed_edges_by_color
+                        .entry(road_color)
+                        .or_default()
+                        .push(canonical_edge);
+                }
+            }
+
+            for (plowed_color, plowed_edges) in plowed_edges_by_color {
+                if plowed_edges.len() != 2 || plowed_color == placing_color {
+                    continue; // Skip if no bisection/plow
+                }
+
+                if let Some(plowed_component_idx) =
+                    self.get_connected_component_index(plowed_color, node_id)
+                {
+                    let outer_nodes: Vec<NodeId> = plowed_edges
+                        .iter()
+                        .map(|&edge| if edge.0 == node_id { edge.1 } else { edge.0 })
+                        .collect();
+
+                    if outer_nodes.len() != 2 {
+                        continue; // Can't bisect
+                    }
+
+                    // The component will be split
+                    let original_component =
+                        self.connected_components[&plowed_color][plowed_component_idx].clone();
+                    self.connected_components
+                        .get_mut(&plowed_color)
+                        .unwrap()
+                        .remove(plowed_component_idx);
+
+                    // DFS to create new components
+                    let component_a = self.dfs_walk(outer_nodes[0], plowed_color);
+                    let mut remaining_nodes = original_component;
+                    for node in &component_a {
+                        remaining_nodes.remove(node);
+                    }
+                    remaining_nodes.remove(&node_id); // Remove settlement node
+
+                    if !component_a.is_empty() {
+                        self.connected_components
+                            .get_mut(&plowed_color)
+                            .unwrap()
+                            .push(component_a);
+                    }
+                    if !remaining_nodes.is_empty() {
+                        self.connected_components
+                            .get_mut(&plowed_color)
+                            .unwrap()
+                            .push(remaining_nodes);
+                    }
+                }
+            }
+
+            // A settlement reached by ship starts a road network of its own
+            if self.touches_own_ship(placing_color, node_id)
+                && self
+                    .get_connected_component_index(placing_color, node_id)
+                    .is_none()
+            {
+                self.connected_components
+                    .entry(placing_color)
+                    .or_default()
+                    .push(HashSet::from([node_id]));
+            }
+
+            // Return longest road information
+            let road_lengths = self.recount_road_lengths();
+            let new_longest_road_length = road_lengths.values().max().copied().unwrap_or(0);
+            (
+                self.longest_road_holder(&road_lengths),
+                new_longest_road_length,
+            )
+        }
+    }
+
+    /// Helper method to get the current state of initial placement phase
+    /// Returns (total_settlements, total_roads, is_phase_1_complete, is_phase_2_complete)
+    pub fn get_initial_placement_progress(&self) -> (usize, usize, bool, bool) {
+        let total_settlements = self
+            .buildings_by_color
+            .values()
+            .map(|buildings| {
+                buildings
+                    .iter()
+                    .filter(|b| matches!(b, Building::Settlement(_, _)))
+                    .count()
+            })
+            .sum::<usize>();
+        let total_roads = self.roads.len();
+        let num_players = self.config.num_players as usize;
+
+        let phase_1_complete = total_settlements >= num_players && total_roads >= num_players;
+        let phase_2_complete =
+            total_settlements >= 2 * num_players && total_roads >= self.total_initial_roads();
+
+        (
+            total_settlements,
+            total_roads,
+            phase_1_complete,
+            phase_2_complete,
+        )
+    }
+
+    pub(in crate::state) fn build_road(
+        &mut self,
+        placing_color: u8,
+        edge_id: EdgeId,
+    ) -> (Option<u8>, u8) {
+        let inverted_edge = (edge_id.1, edge_id.0);
+
+        // DEBUG: Log road building details
+        log::debug!(
+            "🛣️  Building road for player {} on edge ({}, {}) and inverted ({}, {})",
+            placing_color,
+            edge_id.0,
+            edge_id.1,
+            inverted_edge.0,
+            inverted_edge.1
+        );
+
+        // DEBUG: Log existing roads before insertion
+        let existing_roads_count = self.roads.len();
+        log::debug!(
+            "📊 Before insertion: {} roads in storage, inserting for player {}",
+            existing_roads_count,
+            placing_color
+        );
+
+        let canonical_edge = (edge_id.0.min(edge_id.1), edge_id.0.max(edge_id.1));
+        self.roads.insert(canonical_edge, placing_color);
+        self.roads_by_color[placing_color as usize] += 1;
+        self.get_mut_pieces_left(placing_color)[ROADS_LEFT_OFFSET] -= 1;
+        self.mark_board_changed(Some(placing_color));
+
+        // DEBUG: Log after insertion
+        log::debug!(
+            "📊 After insertion: {} roads in storage, player {} now has {} roads",
+            self.roads.len(),
+            placing_color,
+            self.roads_by_color[placing_color as usize]
+        );
+
+        let is_initial_build_phase = self.is_initial_build_phase();
+        let is_road_building = self.is_road_building();
+        let is_free = is_initial_build_phase || is_road_building;
+        if !is_free {
+            freqdeck_sub(self.get_mut_player_hand(placing_color), ROAD_COST);
+            freqdeck_add(&mut self.vector[BANK_RESOURCE_SLICE], ROAD_COST);
+        }
+
+        // If this is a free road from Road Building card, decrement the counter
+        if is_road_building {
+            self.vector[FREE_ROADS_AVAILABLE_INDEX] -= 1;
+        }
+
+        if is_initial_build_phase {
+            // Count only settlements, not all buildings
+            let num_settlements = self
+                .buildings_by_color
+                .values()
+                .map(|buildings| {
+                    buildings
+                        .iter()
+                        .filter(|b| matches!(b, Building::Settlement(_, _)))
+                        .count()
+                })
+                .sum::<usize>();
+            let num_roads = self.roads.len();
+            let num_players = self.config.num_players as usize;
+
+            log::info!(
+                "🏗️  Initial build: {} settlements, {} roads ({} players)",
+                num_settlements,
+                num_roads,
+                num_players
+            );
+
+            // Catan initial placement rules:
+            // Phase 1: Each player places 1 settlement + 1 road (forward order: 0,1,2,3)
+            // Phase 2: Each player places 1 settlement + 1 road (reverse order: 3,2,1,0)
+
+            let going_forward = num_settlements <= num_players;
+            let at_phase_transition = num_settlements == num_players && num_roads == num_players;
+            let initial_phase_complete =
+                num_settlements == 2 * num_players && num_roads == self.total_initial_roads();
+
+            if initial_phase_complete {
+                // All initial placements done - start normal gameplay
+                self.vector[IS_INITIAL_BUILD_PHASE_INDEX] = 0;
+                log::info!("🎯 Initial build phase COMPLETE → Normal gameplay");
+            } else if at_phase_transition {
+                // Transition from forward to reverse order - don't advance turn
+                // The last player to place in forward order places first in reverse order
+                log::info!("🔄 Phase transition: forward → reverse order");
+                // No turn advancement - current player continues
+            } else if !going_forward && self.owes_extra_road(placing_color, 2) {
+                // A handicap road follows the second road - current player continues
+            } else if going_forward {
+                // Phase 1: advance turn forward (0->1->2->3)
+                self.advance_turn(1);
+                log::info!("➡️  Phase 1: turn advanced forward");
+            } else {
+                // Phase 2: advance turn backward (3->2->1->0)
+                self.advance_turn(-1);
+                log::info!("⬅️  Phase 2: turn advanced backward");
+            }
+        }
+
+        // Maintain caches and longest road =====
+        // Extend or merge components
+        let (a, b) = edge_id;
+        let a_index = self.get_connected_component_index(placing_color, a);
+        let b_index = self.get_connected_component_index(placing_color, b);
+
+        // Make sure the connected_components for this color exists
+        self.connected_components.entry(placing_color).or_default();
+
+        // Update connected components based on the new road
+        let affected_component =
+            self.update_connected_components(placing_color, a, b, a_index, b_index);
+
+        let prev_road_color = self.longest_road_color;
+        let prev_road_length = self.longest_road_length;
+
+        // Calculate length for affected component
+        let path_length = self
+            .longest_acyclic_path(&affected_component, placing_color)
+            .len() as u8;
+        let color_length = &mut self.road_lengths[placing_color as usize];
+        *color_length = (*color_length).max(path_length);
+
+        let (new_road_color, new_road_length) =
+            if path_length >= 5 && path_length > prev_road_length {
+                (Some(placing_color), path_length)
+            } else {
+                (prev_road_color, prev_road_length)
+            };
+        (new_road_color, new_road_length)
+    }
+
+    /// Recomputes every color's longest road (ships included) and returns it by color
+    fn recount_road_lengths(&mut self) -> HashMap<u8, u8> {
+        let mut road_lengths: HashMap<u8, u8> = HashMap::new();
+        // Sort colors for deterministic order
+        let mut colors: Vec<_> = self.connected_components.keys().cloned().collect();
+        colors.sort();
+        for color in colors {
+            let ship_nodes: HashSet<NodeId> = self.ship_network_nodes(color).collect();
+            let components = self.connected_components.get(&color).into_iter().flatten();
+            for seeds in components.chain((!ship_nodes.is_empty()).then_some(&ship_nodes)) {
+                let length = self.longest_acyclic_path(seeds, color).len() as u8;
+                if length > *road_lengths.get(&color).unwrap_or(&0) {
+                    road_lengths.insert(color, length);
+                }
+            }
+        }
+        for (color, length) in self.road_lengths.iter_mut().enumerate() {
+            *length = road_lengths.get(&(color as u8)).copied().unwrap_or(0);
+        }
+        road_lengths
+    }
+
+    /// Seafarers: puts a ship on `edge_id`, paid for or free from Road Building
+    pub(in crate::state) fn build_ship(
+        &mut self,
+        placing_color: u8,
+        edge_id: EdgeId,
+    ) -> (Option<u8>, u8) {
+        let (a, b) = edge_id;
+        let canonical_edge = (a.min(b), a.max(b));
+        self.ships.insert(canonical_edge, placing_color);
+        self.ships_by_color[placing_color as usize] += 1;
+        self.ships_built_this_turn.push(canonical_edge);
+        self.mark_board_changed(Some(placing_color));
+
+        if self.is_road_building() {
+            self.vector[FREE_ROADS_AVAILABLE_INDEX] -= 1;
+        } else {
+            freqdeck_sub(self.get_mut_player_hand(placing_color), SHIP_COST);
+            freqdeck_add(&mut self.vector[BANK_RESOURCE_SLICE], SHIP_COST);
+        }
+
+        let path_length = self
+            .longest_acyclic_path(&HashSet::from([a, b]), placing_color)
+            .len() as u8;
+        let color_length = &mut self.road_lengths[placing_color as usize];
+        *color_length = (*color_length).max(path_length);
+
+        if path_length >= 5 && path_length > self.longest_road_length {
+            (Some(placing_color), path_length)
+        } else {
+            (self.longest_road_color, self.longest_road_length)
+        }
+    }
+
+    /// Seafarers: relocates the ship on `from` to `to`, which may shorten a route
+    pub(in crate::state) fn move_ship(
+        &mut self,
+        color: u8,
+        from: EdgeId,
+        to: EdgeId,
+    ) -> (Option<u8>, u8) {
+        self.ships.remove(&(from.0.min(from.1), from.0.max(from.1)));
+        self.ships.insert((to.0.min(to.1), to.0.max(to.1)), color);
+        self.ship_moved_this_turn = true;
+        self.mark_board_changed(Some(color));
+
+        let road_lengths = self.recount_road_lengths();
+        let new_longest_road_length = road_lengths.values().max().copied().unwrap_or(0);
+        (
+            self.longest_road_holder(&road_lengths),
+            new_longest_road_length,
+        )
+    }
+
+    /// Updates the road network when a new road is built
+    ///
+    /// This method maintains the connected components for a player's road network:
+    /// - Merges components when a road connects two previously separate networks
+    /// - Extends an existing component when a road connects to it
+    /// - Creates a new component for isolated roads
+    ///
+    /// The function also handles enemy settlements that would block connections.
+    ///
+    /// Returns the affected component that contains the new road.
+    fn update_connected_components(
+        &mut self,
+        placing_color: u8,
+        a: NodeId,
+        b: NodeId,
+        a_index: Option<usize>,
+        b_index: Option<usize>,
+    ) -> HashSet<NodeId> {
+        // Pre-compute node validity before mutable borrow
+        let a_valid = !self.is_enemy_node(placing_color, a);
+        let b_valid = !self.is_enemy_node(placing_color, b);
+
+        // Get the components list for this color, creating it if it doesn't exist
+        let components = self.connected_components.entry(placing_color).or_default();
+
+        // Case 1: Both nodes are in components
+        if let (Some(a_idx), Some(b_idx)) = (a_index, b_index) {
+            if a_idx == b_idx {
+                // Both in same component - no change needed
+                return components[a_idx].clone();
+            }
+
+            // Merge components - always merge into the component with smaller index
+            // to minimize shifts in the vector
+            let (keep_idx, remove_idx) = if a_idx < b_idx {
+                (a_idx, b_idx)
+            } else {
+                (b_idx, a_idx)
+            };
+
+            let removed = components.remove(remove_idx);
+            components[keep_idx].extend(removed);
+            return components[keep_idx].clone();
+        }
+
+        // Case 2: Only one node is in a component - extend that component
+        if let Some(idx) = a_index.or(b_index) {
+            let component = &mut components[idx];
+
+            // Add the node that isn't in a component if it's valid
+            let new_node = if a_index.is_some() { b } else { a };
+            let is_valid = if a_index.is_some() { b_valid } else { a_valid };
+
+            if is_valid {
+                component.insert(new_node);
+            }
+
+            return component.clone();
+        }
+
+        // Case 3: Neither node is in a component - create a new one with valid nodes
+        let mut new_component = HashSet::new();
+        if a_valid {
+            new_component.insert(a);
+        }
+        if b_valid {
+            new_component.insert(b);
+        }
+
+        if !new_component.is_empty() {
+            components.push(new_component.clone());
+        }
+
+        new_component
+    }
+
+    pub(in crate::state) fn build_city(&mut self, color: u8, node_id: u8) {
+        // Update the main buildings HashMap
+        self.buildings
+            .insert(node_id, Building::City(color, node_id));
+
+        // Update the buildings_by_color tracking
+        let buildings = self.buildings_by_color.entry(color).or_default();
+
+        // Remove the settlement from buildings_by_color
+        if let Some(pos) = buildings.iter().position(|b| {
+            if let Building::Settlement(_, n) = b {
+                *n == node_id
+            } else {
+                false
+            }
+        }) {
+            buildings.remove(pos);
+        }
+
+        // Add the new city to buildings_by_color
+        buildings.push(Building::City(color, node_id));
+        let pieces_left = self.get_mut_pieces_left(color);
+        pieces_left[SETTLEMENTS_LEFT_OFFSET] += 1;
+        pieces_left[CITIES_LEFT_OFFSET] -= 1;
+        self.mark_board_changed(Some(color));
+        self.add_node_production(color, node_id);
+
+        freqdeck_sub(self.get_mut_player_hand(color), CITY_COST);
+        freqdeck_add(&mut self.vector[BANK_RESOURCE_SLICE], CITY_COST);
+        self.add_victory_points(color, 1);
+    }
+
+    fn dfs_walk(&self, start_node: NodeId, color: u8) -> HashSet<NodeId> {
+        let mut agenda = vec![start_node];
+        let mut visited = HashSet::new();
+
+        while let Some(node) = agenda.pop() {
+            if visited.contains(&node) {
+                continue;
+            }
+            visited.insert(node);
+
+            if self.is_enemy_node(color, node) {
+                continue;
+            }
+
+            for &neighbor in self.map_instance.get_neighbor_nodes(node) {
+                if self.owns_road(color, (node, neighbor)) {
+                    agenda.push(neighbor);
+                }
+            }
+        }
+        visited
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, StateEdit};
+
+    #[test]
+    fn test_build_settlement_initial_build_phase() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        assert_eq!(state.buildings.get(&0), None);
+        assert_eq!(state.board_buildable_ids.len(), 54);
+        assert_eq!(state.get_actual_victory_points(color), 0);
+
+        let node_id = 0;
+        state.build_settlement(color, node_id);
+
+        assert_eq!(
+            state.buildings.get(&node_id),
+            Some(&Building::Settlement(color, node_id))
+        );
+        assert_eq!(state.board_buildable_ids.len(), 50);
+        assert_eq!(state.get_actual_victory_points(color), 1);
+    }
+
+    #[test]
+    fn test_build_settlement_spends_resources() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        assert_eq!(state.buildings.get(&0), None);
+        assert_eq!(state.board_buildable_ids.len(), 54);
+        assert_eq!(state.get_actual_victory_points(color), 0);
+
+        // Exit initial build phase, holding a settlement's worth of cards
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::Hand(SETTLEMENT_COST),
+        ] {
+            state.apply_action(Action::DebugSetState { color, edit });
+        }
+        let hand_before = state.get_player_hand(color).to_vec();
+
+        let node_id = 0;
+        state.build_settlement(color, node_id);
+
+        assert_eq!(
+            state.buildings.get(&node_id),
+            Some(&Building::Settlement(color, node_id))
+        );
+        assert_eq!(state.board_buildable_ids.len(), 50);
+        assert_eq!(state.get_actual_victory_points(color), 1);
+
+        let hand_after = state.get_player_hand(color);
+        for i in 0..5 {
+            assert_eq!(hand_after[i], hand_before[i] - SETTLEMENT_COST[i]);
+        }
+    }
+
+    #[test]
+    fn test_second_settlement_yields_resources() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        let first_node = 0;
+        let bank_before = state.vector[BANK_RESOURCE_SLICE].to_vec();
+        let hand_before = state.get_player_hand(color).to_vec();
+
+        state.build_settlement(color, first_node);
+
+        assert_eq!(state.get_player_hand(color), hand_before);
+        assert_eq!(state.vector[BANK_RESOURCE_SLICE], bank_before);
+
+        let second_node = 3;
+        let bank_before = state.vector[BANK_RESOURCE_SLICE].to_vec();
+        let hand_before = state.get_player_hand(color).to_vec();
+
+        state.build_settlement(color, second_node);
+
+        assert_ne!(state.get_player_hand(color), hand_before);
+        assert_ne!(state.vector[BANK_RESOURCE_SLICE], bank_before);
+
+        for i in 0..5 {
+            let bank_diff = bank_before[i] - state.vector[BANK_RESOURCE_SLICE][i];
+            let hand_diff = state.get_player_hand(color)[i] - hand_before[i];
+            assert_eq!(bank_diff, hand_diff);
+        }
+    }
+
+    /// A node for a first settlement well away from `node_id`
+    fn node_apart(state: &State, node_id: u8) -> u8 {
+        let map = &state.map_instance;
+        (0..54)
+            .find(|&other| {
+                other != node_id
+                    && !map.get_neighbor_nodes(node_id).contains(&other)
+                    && !map
+                        .get_neighbor_nodes(other)
+                        .iter()
+                        .any(|n| map.get_neighbor_nodes(node_id).contains(n))
+            })
+            .unwrap()
+    }
+
+    /// Resources the second settlement on `node_id` hands out, and what the bank paid
+    fn second_settlement_yield(node_id: u8) -> ([u8; 5], [u8; 5]) {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        state.build_settlement(color, node_apart(&state, node_id));
+        let hand_before: Vec<u8> = state.get_player_hand(color).to_vec();
+        let bank_before: Vec<u8> = state.vector[BANK_RESOURCE_SLICE].to_vec();
+        state.build_settlement(color, node_id);
+        let gained = std::array::from_fn(|i| state.get_player_hand(color)[i] - hand_before[i]);
+        let paid = std::array::from_fn(|i| bank_before[i] - state.vector[BANK_RESOURCE_SLICE][i]);
+        (gained, paid)
+    }
+
+    #[test]
+    fn test_port_settlement_blocks_its_partner_node() {
+        let state = State::new_base();
+        let port_nodes = state.map_instance.get_port_nodes();
+        for (&node_id, _) in port_nodes.iter() {
+            let mut state = state.clone();
+            let neighbors = state.map_instance.get_neighbor_nodes(node_id).to_vec();
+            // Every port spans two neighboring coastline nodes
+            assert!(neighbors.iter().any(|n| port_nodes.contains_key(n)));
+            assert!(state.is_board_buildable(node_id));
+
+            state.build_settlement(0, node_id);
+            assert_eq!(state.board_buildable_ids.len(), 54 - 1 - neighbors.len());
+            assert!(neighbors.iter().all(|&n| !state.is_board_buildable(n)));
+            assert!(state
+                .initial_placement_violation(&Action::BuildSettlement { color: 1, node_id })
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn test_single_tile_corners() {
+        let state = State::new_base();
+        let corners: Vec<u8> = (0..54)
+            .filter(|&node_id| {
+                state
+                    .map_instance
+                    .get_adjacent_tiles(node_id)
+                    .unwrap()
+                    .len()
+                    == 1
+            })
+            .collect();
+        assert!(!corners.is_empty());
+        for node_id in corners {
+            // Coastline corners only have two neighbors to block
+            let neighbors = state.map_instance.get_neighbor_nodes(node_id);
+            assert_eq!(neighbors.len(), 2);
+            let mut placed = state.clone();
+            placed.build_settlement(0, node_id);
+            assert_eq!(placed.board_buildable_ids.len(), 51);
+
+            let tile = &state.map_instance.get_adjacent_tiles(node_id).unwrap()[0];
+            let (gained, paid) = second_settlement_yield(node_id);
+            assert_eq!(gained, paid);
+            let expected = tile.resource.map_or(0, |_| 1);
+            assert_eq!(gained.iter().sum::<u8>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_second_settlement_next_to_the_desert() {
+        let state = State::new_base();
+        let desert_corners: Vec<u8> = (0..54)
+            .filter(|&node_id| {
+                state
+                    .map_instance
+                    .get_adjacent_tiles(node_id)
+                    .unwrap()
+                    .iter()
+                    .any(|tile| tile.resource.is_none())
+            })
+            .collect();
+        assert_eq!(desert_corners.len(), 6);
+        for node_id in desert_corners {
+            let tiles = state.map_instance.get_adjacent_tiles(node_id).unwrap();
+            let mut expected = [0; 5];
+            for resource in tiles.iter().filter_map(|tile| tile.resource) {
+                expected[resource as usize] += 1;
+            }
+            let (gained, paid) = second_settlement_yield(node_id);
+            assert_eq!(gained, expected, "node {node_id}");
+            assert_eq!(paid, expected);
+        }
+    }
+
+    #[test]
+    fn test_settlement_cuts_longest_road() {
+        let mut state = State::new_base();
+        let color1 = 1;
+        let color2 = 2;
+
+        // give color1 6 consecutive roads
+        state.apply_action(Action::BuildSettlement {
+            color: color1,
+            node_id: 0,
+        });
+        for edge in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 16)] {
+            state.apply_action(Action::BuildRoad {
+                color: color1,
+                edge_id: edge,
+            });
+        }
+
+        assert_eq!(state.longest_road_color, Some(color1));
+        assert_eq!(state.get_actual_victory_points(color1), 3);
+        assert_eq!(state.get_actual_victory_points(color2), 0);
+
+        // Give color2 a settlement at node 4 to bisect color1's Longest Road
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::Hand(SETTLEMENT_COST),
+        ] {
+            state.apply_action(Action::DebugSetState {
+                color: color2,
+                edit,
+            });
+        }
+        state.apply_action(Action::BuildSettlement {
+            color: color2,
+            node_id: 4,
+        });
+
+        assert_eq!(state.longest_road_color, None);
+        assert_eq!(state.get_actual_victory_points(color1), 1);
+        assert_eq!(state.get_actual_victory_points(color2), 1);
+    }
+
+    #[test]
+    fn test_build_road_maintains_connected_components() {
+        let mut state = State::new_base();
+        let color1 = 1;
+
+        state.build_settlement(color1, 0);
+        state.build_road(color1, (0, 1));
+
+        let components = state.connected_components.get(&color1).unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0], HashSet::from([0, 1]));
+
+        state.build_road(color1, (1, 2));
+
+        let components = state.connected_components.get(&color1).unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0], HashSet::from([0, 1, 2]));
+
+        state.build_settlement(color1, 4);
+        state.build_road(color1, (3, 4));
+
+        let components = state.connected_components.get(&color1).unwrap();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0], HashSet::from([0, 1, 2]));
+        assert_eq!(components[1], HashSet::from([3, 4]));
+
+        state.build_road(color1, (2, 3));
+
+        let components = state.connected_components.get(&color1).unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0], HashSet::from([0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_settlement_cuts_longest_road_and_transfers() {
+        let mut state = State::new_base();
+        let color1 = 1;
+        let color2 = 2;
+
+        // give color1 6 consecutive roads
+        state.apply_action(Action::BuildSettlement {
+            color: color1,
+            node_id: 0,
+        });
+        for edge in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 16)] {
+            state.apply_action(Action::BuildRoad {
+                color: color1,
+                edge_id: edge,
+            });
+        }
+        // Give color2 5 consecutive roads with potential to bisect/plow color1's road
+        state.apply_action(Action::BuildSettlement {
+            color: color2,
+            node_id: 11,
+        });
+        for edge in [(11, 12), (12, 13), (13, 14), (14, 15), (4, 15)] {
+            state.apply_action(Action::BuildRoad {
+                color: color2,
+                edge_id: edge,
+            });
+        }
+
+        assert_eq!(state.longest_road_color, Some(color1));
+        assert_eq!(state.get_actual_victory_points(color1), 3);
+        assert_eq!(state.get_actual_victory_points(color2), 1);
+
+        // Give color2 a settlement at node 4 to bisect color1's Longest Road
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::Hand(SETTLEMENT_COST),
+        ] {
+            state.apply_action(Action::DebugSetState {
+                color: color2,
+                edit,
+            });
+        }
+        state.apply_action(Action::BuildSettlement {
+            color: color2,
+            node_id: 4,
+        });
+
+        assert_eq!(state.longest_road_color, Some(color2));
+        assert_eq!(state.get_actual_victory_points(color1), 1);
+        assert_eq!(state.get_actual_victory_points(color2), 4);
+    }
+
+    #[test]
+    fn test_extend_own_longest_road() {
+        let mut state = State::new_base();
+        let color1 = 1;
+
+        state.apply_action(Action::BuildSettlement {
+            color: color1,
+            node_id: 0,
+        });
+        for edge in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)] {
+            state.apply_action(Action::BuildRoad {
+                color: color1,
+                edge_id: edge,
+            });
+        }
+
+        assert_eq!(state.longest_road_color, Some(color1));
+        assert_eq!(state.longest_road_length, 5);
+        assert_eq!(state.get_actual_victory_points(color1), 3);
+
+        state.apply_action(Action::BuildRoad {
+            color: color1,
+            edge_id: (5, 16),
+        });
+
+        assert_eq!(state.longest_road_color, Some(color1));
+        assert_eq!(state.longest_road_length, 6);
+        assert_eq!(state.get_actual_victory_points(color1), 3);
+    }
+
+    #[test]
+    fn test_bisection_counts_remaining_components() {
+        let mut state = State::new_base();
+        let color1 = 1;
+        let color2 = 2;
+
+        state.apply_action(Action::BuildSettlement {
+            color: color1,
+            node_id: 0,
+        });
+        for edge in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 16)] {
+            state.apply_action(Action::BuildRoad {
+                color: color1,
+                edge_id: edge,
+            });
+        }
+
+        assert_eq!(state.longest_road_color, Some(color1));
+        assert_eq!(state.longest_road_length, 6);
+        assert_eq!(state.get_road_length(color1), 6);
+        assert_eq!(state.get_actual_victory_points(color1), 3);
+
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::Hand(SETTLEMENT_COST),
+        ] {
+            state.apply_action(Action::DebugSetState {
+                color: color2,
+                edit,
+            });
+        }
+        state.apply_action(Action::BuildSettlement {
+            color: color2,
+            node_id: 5,
+        });
+
+        assert_eq!(state.longest_road_color, Some(color1));
+        assert_eq!(state.longest_road_length, 5);
+        assert_eq!(state.get_road_length(color1), 5);
+        assert_eq!(state.get_road_length(color2), 0);
+        assert_eq!(state.connected_components.get(&color1).unwrap().len(), 2);
+        assert_eq!(state.get_actual_victory_points(color1), 3);
+        assert_eq!(state.get_actual_victory_points(color2), 1);
+    }
+
+    #[test]
+    fn test_update_connected_components() {
+        // Create a test state
+        let mut state = State::new_base();
+        let color = 0; // Red player
+
+        // Create two separate components
+        let mut comp1 = HashSet::new();
+        comp1.insert(0);
+        comp1.insert(1);
+
+        let mut comp2 = HashSet::new();
+        comp2.insert(3);
+        comp2.insert(4);
+
+        state.connected_components.insert(color, vec![comp1, comp2]);
+
+        // Add roads to connect the components
+        state.roads.insert((1, 2), color);
+        state.roads.insert((2, 3), color);
+
+        // First update: Connect node 1 to node 2
+        // Node 1 is in component index 0, node 2 is not in any component
+        let _updated_component = state.update_connected_components(color, 1, 2, Some(0), None);
+
+        // Verify node 2 was added to the first component
+        let components = state.connected_components.get(&color).unwrap();
+        assert_eq!(components.len(), 2, "Should still have two components");
+        assert!(
+            components[0].contains(&2),
+            "First component should now contain node 2"
+        );
+
+        // Second update: Connect node 2 to node 3
+        // After the first update, node 2 is now in component index 0
+        // Node 3 is in component index 1
+        let _updated_component = state.update_connected_components(color, 2, 3, Some(0), Some(1));
+
+        // Verify that the components were merged
+        let components = state.connected_components.get(&color).unwrap();
+        assert_eq!(components.len(), 1, "Components should be merged into one");
+
+        // Verify the merged component contains all nodes
+        let merged = &components[0];
+        assert!(
+            merged.contains(&0),
+            "Merged component should contain node 0"
+        );
+        assert!(
+            merged.contains(&1),
+            "Merged component should contain node 1"
+        );
+        assert!(
+            merged.contains(&2),
+            "Merged component should contain node 2"
+        );
+        assert!(
+            merged.contains(&3),
+            "Merged component should contain node 3"
+        );
+        assert!(
+            merged.contains(&4),
+            "Merged component should contain node 4"
+        );
+    }
+}
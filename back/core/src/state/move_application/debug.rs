@@ -0,0 +1,139 @@
+// Test setups through the apply pipeline. `DebugSetState` edits one part of the position,
+// then checks the invariants every real action keeps, so a setup can't quietly build a
+// position no game could reach (cards out of thin air, a robber at sea).
+
+use crate::enums::StateEdit;
+use crate::state::State;
+use crate::state_vector::*;
+
+impl State {
+    pub(super) fn debug_set_state(&mut self, color: u8, edit: StateEdit) {
+        match edit {
+            StateEdit::Hand(target) => {
+                for (resource, &wanted) in target.iter().enumerate() {
+                    let held = self.get_player_hand(color)[resource];
+                    let bank = self.vector[BANK_RESOURCE_SLICE][resource];
+                    assert!(
+                        wanted <= held + bank,
+                        "bank can't cover a hand of {wanted} of resource {resource}"
+                    );
+                    self.vector[BANK_RESOURCE_SLICE][resource] = held + bank - wanted;
+                    self.get_mut_player_hand(color)[resource] = wanted;
+                }
+            }
+            StateEdit::Bank(bank) => self.vector[BANK_RESOURCE_SLICE].copy_from_slice(&bank),
+            StateEdit::Robber(coordinate) => {
+                let tile = self.map_instance.get_land_tile(coordinate);
+                let tile_id = tile.map(|tile| tile.id);
+                self.set_robber_tile(tile_id.expect("robber must go on a land tile"));
+            }
+            StateEdit::HasRolled(on) => self.vector[HAS_ROLLED_INDEX] = on as u8,
+            StateEdit::InitialBuildPhase(on) => {
+                self.vector[IS_INITIAL_BUILD_PHASE_INDEX] = on as u8
+            }
+            StateEdit::MovingRobber(on) => self.vector[IS_MOVING_ROBBER_INDEX] = on as u8,
+            StateEdit::PlayedDevCard(on) => self.vector[HAS_PLAYED_DEV_CARD] = on as u8,
+            StateEdit::FreeRoads(count) => self.vector[FREE_ROADS_AVAILABLE_INDEX] = count,
+        }
+
+        if let Err(violation) = self.check_invariants() {
+            panic!("DebugSetState {edit:?} broke the position: {violation}");
+        }
+    }
+
+    fn check_invariants(&self) -> Result<(), String> {
+        for resource in 0..NUM_RESOURCES {
+            let in_hands: u32 = (0..self.get_num_players())
+                .map(|color| self.get_player_hand(color)[resource] as u32)
+                .sum();
+            let total = in_hands + self.vector[BANK_RESOURCE_SLICE][resource] as u32;
+            if total > bank_resource_count(self.get_num_players()) as u32 {
+                return Err(format!("{total} cards of resource {resource} in play"));
+            }
+        }
+
+        let robber = self.get_robber_tile();
+        let land_tiles = self.map_instance.get_land_tiles();
+        if !land_tiles.values().any(|tile| tile.id == robber) {
+            return Err(format!("robber on tile {robber}, which is not land"));
+        }
+
+        let flags = [
+            HAS_ROLLED_INDEX,
+            IS_INITIAL_BUILD_PHASE_INDEX,
+            IS_MOVING_ROBBER_INDEX,
+            HAS_PLAYED_DEV_CARD,
+        ];
+        if let Some(&index) = flags.iter().find(|&&index| self.vector[index] > 1) {
+            return Err(format!("flag at index {index} is {}", self.vector[index]));
+        }
+        if get_free_roads_available(&self.vector) > FREE_ROADS_MAX {
+            return Err("more free roads than Road Building grants".to_string());
+        }
+        if self.is_initial_build_phase()
+            && (self.vector[HAS_ROLLED_INDEX] == 1 || self.vector[IS_MOVING_ROBBER_INDEX] == 1)
+        {
+            return Err("dice or robber in play during initial placement".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enums::{Action, StateEdit};
+    use crate::state::State;
+    use crate::state_vector::BANK_RESOURCE_SLICE;
+
+    #[test]
+    fn test_hand_edits_trade_with_the_bank() {
+        let mut state = State::new_base();
+        state.apply_action(Action::DebugSetState {
+            color: 1,
+            edit: StateEdit::Hand([3, 0, 1, 0, 0]),
+        });
+        assert_eq!(state.get_player_hand(1), &[3, 0, 1, 0, 0]);
+        assert_eq!(&state.vector[BANK_RESOURCE_SLICE], &[16, 19, 18, 19, 19]);
+
+        state.apply_action(Action::DebugSetState {
+            color: 1,
+            edit: StateEdit::Hand([1, 0, 0, 0, 0]),
+        });
+        assert_eq!(&state.vector[BANK_RESOURCE_SLICE], &[18, 19, 19, 19, 19]);
+
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::InitialBuildPhase(false),
+        });
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::Robber((0, 0, 0)),
+        });
+        let centre = state.map_instance.get_land_tile((0, 0, 0)).unwrap().id;
+        assert_eq!(state.get_robber_tile(), centre);
+    }
+
+    #[test]
+    #[should_panic(expected = "cards of resource 0 in play")]
+    fn test_bank_edit_cannot_mint_cards() {
+        let mut state = State::new_base();
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::Hand([2, 0, 0, 0, 0]),
+        });
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::Bank([19, 19, 19, 19, 19]),
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "dice or robber in play during initial placement")]
+    fn test_no_roll_during_initial_placement() {
+        let mut state = State::new_base();
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::HasRolled(true),
+        });
+    }
+}
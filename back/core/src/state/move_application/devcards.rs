@@ -0,0 +1,527 @@
+// Buying and playing development cards. Largest Army is settled by the dispatcher
+// after a knight.
+
+use super::super::State;
+use crate::deck_slices::{freqdeck_add, freqdeck_sub, DEVCARD_COST};
+use crate::enums::DevCard;
+use crate::state_vector::*;
+
+impl State {
+    pub(super) fn buy_development_card(&mut self, color: u8) -> Option<DevCard> {
+        // Get next card from deck
+        if let Some(card) = take_next_dev_card(&mut self.vector, self.config.num_players) {
+            // Pay for the card
+            freqdeck_sub(self.get_mut_player_hand(color), DEVCARD_COST);
+            freqdeck_add(&mut self.vector[BANK_RESOURCE_SLICE], DEVCARD_COST);
+
+            let dev_card = match card {
+                0 => DevCard::Knight,
+                1 => DevCard::YearOfPlenty,
+                2 => DevCard::Monopoly,
+                3 => DevCard::RoadBuilding,
+                4 => DevCard::VictoryPoint,
+                _ => panic!("Invalid dev card index"),
+            };
+
+            match dev_card {
+                DevCard::VictoryPoint => {
+                    self.add_victory_points(color, 1);
+                }
+                _ => {
+                    let n = self.config.num_players;
+                    self.vector[player_devhand_slice(n, color)][card as usize] += 1;
+                    self.vector[player_bought_devhand_slice(n, color)][card as usize] += 1;
+                }
+            }
+
+            Some(dev_card)
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn play_knight(&mut self, color: u8) {
+        // Mark card as played
+        self.remove_dev_card(color, DevCard::Knight as usize);
+        self.add_played_dev_card(color, DevCard::Knight as usize);
+        self.set_has_played_dev_card();
+
+        // Set state to move robber
+        self.set_is_moving_robber();
+    }
+
+    pub(super) fn play_year_of_plenty(&mut self, color: u8, resources: (u8, Option<u8>)) {
+        // Assume move_generation has already checked that player has year of plenty card
+        // and that bank has enough resources
+        self.remove_dev_card(color, DevCard::YearOfPlenty as usize);
+        self.add_played_dev_card(color, DevCard::YearOfPlenty as usize);
+        self.set_has_played_dev_card();
+
+        // Give first resource to player
+        self.from_bank_to_player(color, resources.0);
+
+        // Give second resource if specified
+        if let Some(resource2) = resources.1 {
+            self.from_bank_to_player(color, resource2);
+        }
+    }
+
+    pub(super) fn play_monopoly(&mut self, color: u8, resource: u8) {
+        // Assume move_generation has already checked that player has monopoly card.
+        self.remove_dev_card(color, DevCard::Monopoly as usize);
+        self.add_played_dev_card(color, DevCard::Monopoly as usize);
+        self.set_has_played_dev_card();
+
+        // Steal all resources of type from other players
+        for victim_color in 0..self.get_num_players() {
+            if victim_color != color {
+                let amount = self.get_player_resource_count(victim_color, resource);
+                if amount > 0 {
+                    self.from_player_to_player(victim_color, color, resource, amount);
+                }
+            }
+        }
+    }
+
+    pub(super) fn play_road_building(&mut self, color: u8) {
+        // Assume move_generation has already checked that player has road building card.
+        self.remove_dev_card(color, DevCard::RoadBuilding as usize);
+        self.add_played_dev_card(color, DevCard::RoadBuilding as usize);
+        self.set_has_played_dev_card();
+
+        // Set state for free roads
+        self.vector[IS_BUILDING_ROAD_INDEX] = 1;
+        self.vector[FREE_ROADS_AVAILABLE_INDEX] = 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, RulesVersion, StateEdit};
+
+    #[test]
+    fn test_buy_development_cards() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        let mut cards_drawn = 0;
+
+        while cards_drawn < 26 {
+            state.apply_action(Action::DebugSetState {
+                color,
+                edit: StateEdit::Hand(DEVCARD_COST),
+            });
+            let initial_hand: [u8; 5] = state.get_player_hand(color).try_into().unwrap();
+            let initial_devhand = state.get_player_devhand(color).to_vec();
+            let initial_bank = state.vector[BANK_RESOURCE_SLICE].to_vec();
+            let initial_vps = state.get_actual_victory_points(color);
+
+            let drawn_card = state.buy_development_card(color);
+            cards_drawn += 1;
+
+            log::debug!("Cards Drawn: {}, Drawn card: {:?}", cards_drawn, drawn_card);
+
+            if cards_drawn < 26 {
+                let hand_after = state.get_player_hand(color);
+                let bank_after = &state.vector[BANK_RESOURCE_SLICE];
+                for i in 0..5 {
+                    assert_eq!(hand_after[i], initial_hand[i] - DEVCARD_COST[i]);
+                    assert_eq!(bank_after[i], initial_bank[i] + DEVCARD_COST[i]);
+                }
+                let devhand_after = state.get_player_devhand(color);
+
+                if drawn_card == Some(DevCard::VictoryPoint) {
+                    // VP added but kept off the public score, devhand not incremented
+                    assert_eq!(state.get_actual_victory_points(color), initial_vps + 1);
+                    assert_eq!(state.get_public_victory_points(color), 0);
+                    assert_eq!(
+                        devhand_after[drawn_card.unwrap() as usize],
+                        initial_devhand[drawn_card.unwrap() as usize]
+                    );
+                } else {
+                    // VP not added, devhand incremented
+                    assert_eq!(state.get_actual_victory_points(color), initial_vps);
+                    assert_eq!(
+                        devhand_after[drawn_card.unwrap() as usize],
+                        initial_devhand[drawn_card.unwrap() as usize] + 1
+                    );
+                }
+            } else {
+                // 26th card should not be drawn
+                assert!(drawn_card.is_none());
+                assert_eq!(state.get_player_hand(color), initial_hand);
+                assert_eq!(&state.vector[BANK_RESOURCE_SLICE], initial_bank);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bought_dev_card_waits_a_turn() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::InitialBuildPhase(false),
+        });
+        let card = loop {
+            state.apply_action(Action::DebugSetState {
+                color,
+                edit: StateEdit::Hand(DEVCARD_COST),
+            });
+            match state.buy_development_card(color) {
+                Some(DevCard::VictoryPoint) => continue,
+                card => break card.unwrap(),
+            }
+        };
+        assert!(!state.can_play_dev(card as u8));
+
+        for _ in 0..state.get_num_players() {
+            state.end_turn(state.get_current_color());
+        }
+        assert_eq!(state.get_current_color(), color);
+        assert!(state.can_play_dev(card as u8));
+
+        // Under legacy rules a card can be played the turn it was bought
+        let bought = player_bought_devhand_slice(state.get_num_players(), color);
+        state.vector[bought][card as usize] = state.get_player_devhand(color)[card as usize];
+        assert!(!state.can_play_dev(card as u8));
+        let mut config = state.get_config().as_ref().clone();
+        config.rules = RulesVersion::Legacy;
+        state.config = std::sync::Arc::new(config);
+        assert!(state.can_play_dev(card as u8));
+    }
+
+    #[test]
+    fn test_play_knight() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        state.add_dev_card(color, DevCard::Knight as usize);
+        assert_eq!(state.get_dev_card_count(color, DevCard::Knight as usize), 1);
+        assert_eq!(
+            state.get_played_dev_card_count(color, DevCard::Knight as usize),
+            0
+        );
+        assert_eq!(state.vector[HAS_PLAYED_DEV_CARD], 0);
+        assert_eq!(state.vector[IS_MOVING_ROBBER_INDEX], 0);
+
+        state.play_knight(color);
+
+        assert_eq!(state.get_dev_card_count(color, DevCard::Knight as usize), 0);
+        assert_eq!(
+            state.get_played_dev_card_count(color, DevCard::Knight as usize),
+            1
+        );
+        assert_eq!(state.vector[HAS_PLAYED_DEV_CARD], 1);
+        assert_eq!(state.vector[IS_MOVING_ROBBER_INDEX], 1);
+    }
+
+    #[test]
+    fn test_play_knight_largest_army() {
+        let mut state = State::new_base();
+        let color1 = 1;
+        let color2 = 2;
+        state.apply_action(Action::DebugSetState {
+            color: color1,
+            edit: StateEdit::InitialBuildPhase(false),
+        });
+
+        // Give first player 3 knight cards
+        for _ in 0..3 {
+            state.add_dev_card(color1, DevCard::Knight as usize);
+        }
+
+        // Play knights and verify largest army
+        for i in 0..3 {
+            // Reset for each turn
+            state.apply_action(Action::DebugSetState {
+                color: color1,
+                edit: StateEdit::PlayedDevCard(false),
+            });
+            state.apply_action(Action::PlayKnight { color: color1 });
+
+            // Verify knight was removed and marked as played
+            assert_eq!(
+                state.get_dev_card_count(color1, DevCard::Knight as usize),
+                2 - i
+            );
+            assert_eq!(
+                state.get_played_dev_card_count(color1, DevCard::Knight as usize),
+                i + 1
+            );
+            assert_eq!(state.vector[HAS_PLAYED_DEV_CARD], 1);
+            assert_eq!(state.vector[IS_MOVING_ROBBER_INDEX], 1);
+
+            // Check largest army status
+            if i == 2 {
+                assert_eq!(state.largest_army_color, Some(color1));
+                assert_eq!(state.largest_army_count, 3);
+                assert_eq!(state.get_actual_victory_points(color1), 2);
+                assert_eq!(state.get_actual_victory_points(color2), 0);
+            } else {
+                assert_eq!(state.largest_army_color, None);
+                assert_eq!(state.largest_army_count, 0);
+                assert_eq!(state.get_actual_victory_points(color1), 0);
+                assert_eq!(state.get_actual_victory_points(color2), 0);
+            }
+        }
+
+        // Now give second player 4 knight cards and have them take largest army
+        for _ in 0..4 {
+            state.add_dev_card(color2, DevCard::Knight as usize);
+        }
+
+        // Play knights with second player
+        for i in 0..4 {
+            // Reset for each turn
+            state.apply_action(Action::DebugSetState {
+                color: color2,
+                edit: StateEdit::PlayedDevCard(false),
+            });
+            state.apply_action(Action::PlayKnight { color: color2 });
+
+            // Verify knight was removed and marked as played
+            assert_eq!(
+                state.get_dev_card_count(color2, DevCard::Knight as usize),
+                3 - i
+            );
+            assert_eq!(
+                state.get_played_dev_card_count(color2, DevCard::Knight as usize),
+                i + 1
+            );
+
+            // Check largest army status
+            if i == 3 {
+                // After 4th knight, should take largest army
+                assert_eq!(state.largest_army_color, Some(color2));
+                assert_eq!(state.largest_army_count, 4);
+                assert_eq!(state.get_actual_victory_points(color1), 0); // Lost 2 VPs
+                assert_eq!(state.get_actual_victory_points(color2), 2); // Gained 2 VPs
+            } else {
+                // Still held by first player
+                assert_eq!(state.largest_army_color, Some(color1));
+                assert_eq!(state.largest_army_count, 3);
+                assert_eq!(state.get_actual_victory_points(color1), 2);
+                assert_eq!(state.get_actual_victory_points(color2), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_play_year_of_plenty() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Give player a year of plenty card
+        state.add_dev_card(color, DevCard::YearOfPlenty as usize);
+
+        let bank_before = state.vector[BANK_RESOURCE_SLICE].to_vec();
+        let hand_before = state.get_player_hand(color).to_vec();
+
+        // Play year of plenty for wood and brick
+        state.play_year_of_plenty(color, (0, Some(1)));
+
+        // Verify card was removed from hand
+        assert_eq!(
+            state.get_dev_card_count(color, DevCard::YearOfPlenty as usize),
+            0
+        );
+
+        // Verify card was marked as played
+        assert_eq!(
+            state.get_played_dev_card_count(color, DevCard::YearOfPlenty as usize),
+            1
+        );
+        assert_eq!(state.vector[HAS_PLAYED_DEV_CARD], 1);
+
+        // Verify resources were transferred
+        assert_eq!(state.vector[BANK_RESOURCE_SLICE][0], bank_before[0] - 1);
+        assert_eq!(state.vector[BANK_RESOURCE_SLICE][1], bank_before[1] - 1);
+        assert_eq!(state.get_player_hand(color)[0], hand_before[0] + 1);
+        assert_eq!(state.get_player_hand(color)[1], hand_before[1] + 1);
+    }
+
+    #[test]
+    fn test_play_monopoly() {
+        let mut state = State::new_base();
+        let monopolist_color = state.get_current_color();
+
+        // Give player a monopoly card
+        state.add_dev_card(monopolist_color, DevCard::Monopoly as usize);
+
+        // Give other players some wood
+        for other_color in 0..state.get_num_players() {
+            if other_color != monopolist_color {
+                state.apply_action(Action::DebugSetState {
+                    color: other_color,
+                    edit: StateEdit::Hand([3, 0, 0, 0, 0]),
+                });
+            }
+        }
+
+        let initial_wood = state.get_player_hand(monopolist_color)[0];
+        let expected_stolen = 3 * (state.get_num_players() - 1); // 3 wood from each other player
+
+        // Play monopoly on wood (resource index 0)
+        state.play_monopoly(monopolist_color, 0);
+
+        assert_eq!(
+            state.get_dev_card_count(monopolist_color, DevCard::Monopoly as usize),
+            0
+        );
+        assert_eq!(
+            state.get_played_dev_card_count(monopolist_color, DevCard::Monopoly as usize),
+            1
+        );
+        assert_eq!(state.vector[HAS_PLAYED_DEV_CARD], 1);
+        assert_eq!(
+            state.get_player_hand(monopolist_color)[0],
+            initial_wood + expected_stolen
+        );
+
+        // Verify other players lost their wood
+        for other_color in 0..state.get_num_players() {
+            if other_color != monopolist_color {
+                assert_eq!(state.get_player_hand(other_color)[0], 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_play_road_building() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Give player a road building card
+        state.add_dev_card(color, DevCard::RoadBuilding as usize);
+        assert_eq!(
+            state.get_dev_card_count(color, DevCard::RoadBuilding as usize),
+            1
+        );
+        assert_eq!(
+            state.get_played_dev_card_count(color, DevCard::RoadBuilding as usize),
+            0
+        );
+        assert_eq!(state.vector[HAS_PLAYED_DEV_CARD], 0);
+
+        // Play road building card
+        state.play_road_building(color);
+
+        // Verify card was removed from hand
+        assert_eq!(
+            state.get_dev_card_count(color, DevCard::RoadBuilding as usize),
+            0
+        );
+
+        // Verify card was marked as played
+        assert_eq!(
+            state.get_played_dev_card_count(color, DevCard::RoadBuilding as usize),
+            1
+        );
+        assert_eq!(state.vector[HAS_PLAYED_DEV_CARD], 1);
+
+        // Verify state was set for free roads
+        assert_eq!(state.vector[IS_BUILDING_ROAD_INDEX], 1);
+        assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 2);
+    }
+
+    #[test]
+    fn test_road_building_full_flow() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Give player a road building card and build initial settlement
+        state.add_dev_card(color, DevCard::RoadBuilding as usize);
+        state.build_settlement(color, 0);
+
+        // Initial state
+        assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 0);
+        assert!(!state.is_road_building());
+
+        // Play road building card
+        state.play_road_building(color);
+        assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 2);
+        assert!(state.is_road_building());
+
+        // Build first free road
+        state.build_road(color, (0, 1));
+        assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 1);
+        assert!(state.is_road_building()); // Still in road building mode
+
+        // Build second free road
+        state.build_road(color, (1, 2));
+        assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 0);
+        assert!(!state.is_road_building()); // No longer in road building mode
+
+        // Verify roads were built and are owned by player
+        assert_eq!(state.roads.get(&(0, 1)), Some(&color));
+        assert_eq!(state.roads.get(&(1, 2)), Some(&color));
+        assert_eq!(state.roads_by_color[color as usize], 2);
+    }
+
+    #[test]
+    fn test_road_building_turn_does_not_advance() {
+        let mut state = State::new_base();
+        let starting_color = state.get_current_color();
+
+        // Set up a simple scenario: build settlement and give road building card
+        state.build_settlement(starting_color, 0);
+        state.add_dev_card(starting_color, DevCard::RoadBuilding as usize);
+
+        // Manually set state to post-initial phase for road building
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::HasRolled(true),
+        ] {
+            state.apply_action(Action::DebugSetState {
+                color: starting_color,
+                edit,
+            });
+        }
+
+        // Verify initial state
+        assert_eq!(state.get_current_color(), starting_color);
+
+        // Play road building card
+        state.apply_action(Action::PlayRoadBuilding {
+            color: starting_color,
+        });
+
+        // Verify turn hasn't advanced after playing Road Building
+        assert_eq!(state.get_current_color(), starting_color);
+        assert!(state.is_road_building());
+        assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 2);
+
+        // Build first road
+        let actions = state.generate_playable_actions();
+        if let Some(Action::BuildRoad { edge_id, .. }) = actions.first() {
+            state.apply_action(Action::BuildRoad {
+                color: starting_color,
+                edge_id: *edge_id,
+            });
+
+            // CRITICAL: Verify turn STILL hasn't advanced after building first road
+            assert_eq!(state.get_current_color(), starting_color);
+            assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 1);
+            assert!(state.is_road_building());
+
+            // Build second road
+            let actions = state.generate_playable_actions();
+            if let Some(Action::BuildRoad { edge_id, .. }) = actions.first() {
+                state.apply_action(Action::BuildRoad {
+                    color: starting_color,
+                    edge_id: *edge_id,
+                });
+
+                // CRITICAL: Verify turn STILL hasn't advanced after building second road
+                assert_eq!(state.get_current_color(), starting_color);
+                assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 0);
+                assert!(!state.is_road_building());
+
+                // Now player should be able to continue their turn normally
+                let actions = state.generate_playable_actions();
+                assert!(actions.iter().any(|a| matches!(a, Action::EndTurn { .. })));
+            }
+        }
+    }
+}
@@ -0,0 +1,603 @@
+// Rolling: resource distribution (with the bank shortage rule) or, on a 7, the
+// start of the discard/robber phase. Seafarers gold fields pay cards of the owner's
+// choice, picked in seat order from the roller before the turn goes on.
+
+use super::super::{Building, State};
+use crate::state_vector::*;
+
+impl State {
+    pub(super) fn roll_dice(&mut self, color: u8, dice_opt: Option<(u8, u8)>) {
+        self.vector[HAS_ROLLED_INDEX] = 1;
+        let (die1, die2) = dice_opt.unwrap_or_else(|| self.draw_dice());
+
+        // Store the dice roll for logging purposes
+        self.last_dice_roll = Some((die1, die2));
+
+        let total = die1 + die2;
+
+        log::info!("🎲 Player {} rolled {} + {} = {}", color, die1, die2, total);
+
+        if total == 7 {
+            log::info!("🎲 Rolling 7 → Discard/Robber phase");
+            self.handle_roll_seven(color);
+        } else {
+            log::info!("🎲 Rolling {} → Resource distribution", total);
+            let roller_seat = self.get_current_tick_seat();
+            let leader_hands = self.leader_hands_before_roll(color);
+            self.distribute_roll_yields(total);
+            self.grant_leader_roll_bonus(color, &leader_hands);
+            self.vector[CURRENT_TICK_SEAT_INDEX] = color;
+            if self.gold_owed.iter().any(|&owed| owed > 0) {
+                self.gold_roller_seat = roller_seat;
+                self.seat_next_gold_chooser(roller_seat);
+            }
+        }
+    }
+
+    /// Seafarers: one card of `resource` towards what `color` is owed from gold fields
+    pub(super) fn choose_gold(&mut self, color: u8, resource: u8) {
+        self.vector[BANK_RESOURCE_SLICE][resource as usize] -= 1;
+        self.get_mut_player_hand(color)[resource as usize] += 1;
+        self.gold_owed[color as usize] -= 1;
+        self.seat_next_gold_chooser(self.get_current_tick_seat());
+    }
+
+    /// Hands the prompt to the first seat from `from_seat` on that still owes gold picks,
+    /// or back to the roller once none does. Picks the bank can no longer cover lapse.
+    fn seat_next_gold_chooser(&mut self, from_seat: u8) {
+        if self.vector[BANK_RESOURCE_SLICE]
+            .iter()
+            .all(|&count| count == 0)
+        {
+            self.gold_owed.fill(0);
+        }
+        let seating_order = self.get_seating_order();
+        let num_seats = seating_order.len() as u8;
+        let next_seat = (0..num_seats)
+            .map(|step| (from_seat + step) % num_seats)
+            .find(|&seat| self.gold_owed[seating_order[seat as usize] as usize] > 0);
+        self.vector[CURRENT_TICK_SEAT_INDEX] = next_seat.unwrap_or(self.gold_roller_seat);
+    }
+
+    fn handle_roll_seven(&mut self, color: u8) {
+        // Check who needs to discard
+        let discarders: Vec<bool> = (0..self.get_num_players())
+            .map(|c| {
+                let player_hand = self.get_player_hand(c);
+                let total_cards: u8 = player_hand.iter().sum();
+                total_cards > self.config.discard_limit
+            })
+            .collect();
+
+        let should_enter_discard_phase = discarders.iter().any(|&x| x);
+        if should_enter_discard_phase {
+            self.vector[IS_DISCARDING_INDEX] = 1;
+            self.vector[CURRENT_TICK_SEAT_INDEX] = color;
+            log::info!(
+                "🎲 Rolling 7: Entering discard phase, original roller: {}",
+                color
+            );
+        } else {
+            self.vector[IS_MOVING_ROBBER_INDEX] = 1;
+            self.vector[CURRENT_TICK_SEAT_INDEX] = color;
+            log::info!("🎲 Rolling 7: No discards needed, moving to robber");
+        }
+    }
+
+    // Returns Vec of (color, resource_index, amount) tuples for what each player should receive
+    fn collect_roll_yields(&self, roll: u8) -> Vec<(u8, usize, u8)> {
+        let mut all_yields = Vec::new();
+        let matching_tiles = self.map_instance.get_tiles_by_number(roll);
+
+        for tile in matching_tiles {
+            // Skip robber tile
+            if self.get_robber_tile() == tile.id {
+                continue;
+            }
+
+            if let Some(resource) = tile.resource {
+                let resource_idx = resource as usize;
+                // Collect all yields for this tile
+                for &node_id in tile.hexagon.nodes.values() {
+                    if let Some(building) = self.buildings.get(&node_id) {
+                        match building {
+                            Building::Settlement(owner_color, _) => {
+                                all_yields.push((*owner_color, resource_idx, 1));
+                            }
+                            Building::City(owner_color, _) => {
+                                all_yields.push((*owner_color, resource_idx, 2));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        all_yields
+    }
+
+    /// Gold field cards owed for `roll`: one per settlement and two per city
+    fn collect_gold_owed(&self, roll: u8) -> Vec<u8> {
+        let mut owed = vec![0; self.get_num_players() as usize];
+        for tile in self.map_instance.get_tiles_by_number(roll) {
+            if !tile.gold || self.get_robber_tile() == tile.id {
+                continue;
+            }
+            for node_id in tile.hexagon.nodes.values() {
+                match self.buildings.get(node_id) {
+                    Some(Building::Settlement(owner_color, _)) => owed[*owner_color as usize] += 1,
+                    Some(Building::City(owner_color, _)) => owed[*owner_color as usize] += 2,
+                    None => {}
+                }
+            }
+        }
+        owed
+    }
+
+    fn distribute_roll_yields(&mut self, roll: u8) {
+        // Gold is picked after the fixed yields are paid out, see `choose_gold`
+        self.gold_owed = self.collect_gold_owed(roll);
+        let yields = self.collect_roll_yields(roll);
+        if yields.is_empty() {
+            log::info!("🎲 Roll {} yields NO resources", roll);
+            return;
+        }
+
+        log::info!("🎲 Roll {} yields: {:?}", roll, yields);
+
+        // Calculate total needed by resource type
+        let mut resource_needs = [0u8; 5];
+        for (_, resource_idx, amount) in &yields {
+            resource_needs[*resource_idx] += amount;
+        }
+
+        // Check what can be allocated from bank
+        let bank = &self.vector[BANK_RESOURCE_SLICE];
+        log::info!(
+            "🏦 Current bank: [Wood:{}, Brick:{}, Sheep:{}, Wheat:{}, Ore:{}]",
+            bank[0],
+            bank[1],
+            bank[2],
+            bank[3],
+            bank[4]
+        );
+        log::info!("📦 Total resource needs: {:?}", resource_needs);
+
+        // For each resource type, determine if multiple players need it
+        let mut resource_recipients = [Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+        for (color, resource_idx, _) in &yields {
+            if !resource_recipients[*resource_idx].contains(color) {
+                resource_recipients[*resource_idx].push(*color);
+            }
+        }
+
+        // Determine which resources can be distributed
+        let mut can_distribute = [true; 5];
+        for i in 0..5 {
+            if bank[i] < resource_needs[i] {
+                // Resource is insufficient
+                if resource_recipients[i].len() > 1 {
+                    // Multiple players need this resource - no one gets it
+                    log::info!(
+                        "❌ Resource {}: insufficient for multiple recipients ({}), no one gets it",
+                        i,
+                        resource_recipients[i].len()
+                    );
+                    can_distribute[i] = false;
+                } else {
+                    log::info!("⚠️  Resource {}: insufficient for single recipient, will distribute what's available", i);
+                    // Single player - they get what's available (handled during distribution)
+                }
+            }
+        }
+
+        // Make a copy of bank resources to track what's distributed
+        let mut remaining = [0u8; 5];
+        remaining.copy_from_slice(&bank[..5]);
+
+        // Distribute resources according to the rules
+        for (owner_color, resource_idx, amount) in yields {
+            if !can_distribute[resource_idx] {
+                // Skip resources that can't be distributed
+                continue;
+            }
+
+            // Calculate how much to give (either full amount or what's available)
+            let available = remaining[resource_idx].min(amount);
+            if available > 0 {
+                // Update tracking of what's left
+                remaining[resource_idx] -= available;
+
+                // Update actual game state
+                self.vector[BANK_RESOURCE_SLICE][resource_idx] -= available;
+                self.get_mut_player_hand(owner_color)[resource_idx] += available;
+
+                log::info!(
+                    "✅ Distributed {} of resource {} to player {}",
+                    available,
+                    resource_idx,
+                    owner_color
+                );
+            }
+        }
+
+        log::info!(
+            "🏦 Bank after distribution: [Wood:{}, Brick:{}, Sheep:{}, Wheat:{}, Ore:{}]",
+            &self.vector[BANK_RESOURCE_SLICE][0],
+            &self.vector[BANK_RESOURCE_SLICE][1],
+            &self.vector[BANK_RESOURCE_SLICE][2],
+            &self.vector[BANK_RESOURCE_SLICE][3],
+            &self.vector[BANK_RESOURCE_SLICE][4]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck_slices::CITY_COST;
+    use crate::enums::{Action, ActionPrompt, FreqDeck, GameConfiguration, MapType, StateEdit};
+    use crate::map_instance::{MapInstance, NodeRef};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_roll_seven_triggers_discard() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([8, 0, 0, 0, 0]),
+        });
+
+        state.roll_dice(color, Some((4, 3)));
+
+        assert_eq!(state.vector[HAS_ROLLED_INDEX], 1);
+        assert_eq!(state.vector[IS_DISCARDING_INDEX], 1);
+        assert_eq!(state.vector[CURRENT_TICK_SEAT_INDEX], color);
+        assert_eq!(state.vector[IS_MOVING_ROBBER_INDEX], 0);
+    }
+
+    #[test]
+    fn test_roll_seven_no_discard_needed() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        state.roll_dice(color, Some((4, 3)));
+
+        assert_eq!(state.vector[HAS_ROLLED_INDEX], 1);
+        assert_eq!(state.vector[IS_DISCARDING_INDEX], 0);
+        assert_eq!(state.vector[CURRENT_TICK_SEAT_INDEX], color);
+        assert_eq!(state.vector[IS_MOVING_ROBBER_INDEX], 1);
+    }
+
+    #[test]
+    fn test_roll_tracks_has_rolled() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        assert_eq!(state.vector[HAS_ROLLED_INDEX], 0);
+        state.roll_dice(color, Some((2, 3)));
+        assert_eq!(state.vector[HAS_ROLLED_INDEX], 1);
+    }
+
+    #[test]
+    fn test_roll_yields_resources() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        state.build_settlement(color, 0);
+
+        let adjacent_tiles = state.map_instance.get_adjacent_tiles(0).unwrap();
+
+        let mut chosen_roll = None;
+        let mut expected_resource_yields = [0; 5];
+
+        for tile in adjacent_tiles.iter() {
+            if let (Some(number), Some(resource)) = (tile.number, tile.resource) {
+                // First valid number we find will be our roll
+                // Don't pick robber tile
+                if tile.id != state.get_robber_tile() {
+                    if chosen_roll.is_none() {
+                        chosen_roll = Some(number);
+                    }
+
+                    if Some(number) == chosen_roll {
+                        expected_resource_yields[resource as usize] += 1;
+                    }
+                }
+            }
+        }
+
+        let initial_bank = state.vector[BANK_RESOURCE_SLICE].to_vec();
+        let initial_hand = state.get_player_hand(color).to_vec();
+        // Roll numbers should sum to chosen_roll
+        let roll_numbers = (chosen_roll.unwrap() / 2, chosen_roll.unwrap().div_ceil(2));
+
+        state.apply_action(Action::Roll {
+            color,
+            dice_opt: Some(roll_numbers),
+        });
+
+        for resource_idx in 0..5 {
+            assert_eq!(
+                state.vector[BANK_RESOURCE_SLICE][resource_idx],
+                initial_bank[resource_idx] - expected_resource_yields[resource_idx],
+                "Bank should have {} fewer resource of {:?}",
+                expected_resource_yields[resource_idx],
+                resource_idx
+            );
+            assert_eq!(
+                state.get_player_hand(color)[resource_idx],
+                initial_hand[resource_idx] + expected_resource_yields[resource_idx],
+                "Player should have {} more resource of {:?}",
+                expected_resource_yields[resource_idx],
+                resource_idx
+            )
+        }
+    }
+
+    #[test]
+    fn test_roll_city_yields_double() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand(CITY_COST),
+        });
+        state.build_settlement(color, 0);
+        state.build_city(color, 0);
+
+        let adjacent_tiles = state.map_instance.get_adjacent_tiles(0).unwrap();
+
+        let mut chosen_roll = None;
+        let mut expected_resource_yields = [0; 5];
+
+        for tile in adjacent_tiles.iter() {
+            if let (Some(number), Some(resource)) = (tile.number, tile.resource) {
+                // Don't pick robber tile
+                if tile.id != state.get_robber_tile() {
+                    if chosen_roll.is_none() {
+                        chosen_roll = Some(number);
+                    }
+
+                    if Some(number) == chosen_roll {
+                        expected_resource_yields[resource as usize] += 2;
+                    }
+                }
+            }
+        }
+
+        let initial_bank = state.vector[BANK_RESOURCE_SLICE].to_vec();
+        let initial_hand = state.get_player_hand(color).to_vec();
+        // Roll numbers should sum to chosen_roll
+        let roll_numbers = (chosen_roll.unwrap() / 2, chosen_roll.unwrap().div_ceil(2));
+
+        state.apply_action(Action::Roll {
+            color,
+            dice_opt: Some(roll_numbers),
+        });
+
+        for resource_idx in 0..5 {
+            assert_eq!(
+                state.vector[BANK_RESOURCE_SLICE][resource_idx],
+                initial_bank[resource_idx] - expected_resource_yields[resource_idx],
+                "Bank should have {} fewer resource of {:?}",
+                expected_resource_yields[resource_idx],
+                resource_idx
+            );
+            assert_eq!(
+                state.get_player_hand(color)[resource_idx],
+                initial_hand[resource_idx] + expected_resource_yields[resource_idx],
+                "Player should have {} more resource of {:?}",
+                expected_resource_yields[resource_idx],
+                resource_idx
+            );
+        }
+    }
+
+    #[test]
+    fn test_roll_single_player_partial_payment_when_insufficient_bank() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        let node_id = 0;
+        state.build_settlement(color, node_id);
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand(CITY_COST),
+        });
+        state.build_city(color, node_id);
+
+        let adjacent_tiles = state.map_instance.get_adjacent_tiles(node_id).unwrap();
+
+        let mut chosen_roll = None;
+        let mut chosen_resource = None;
+
+        for tile in adjacent_tiles.iter() {
+            if let (Some(number), Some(resource)) = (tile.number, tile.resource) {
+                if tile.id != state.get_robber_tile() && chosen_roll.is_none() {
+                    chosen_roll = Some(number);
+                    chosen_resource = Some(resource);
+                }
+            }
+        }
+        assert!(chosen_roll.is_some(), "Should find at least one valid tile");
+
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Bank([1; 5]),
+        });
+        let hand_before = state.get_player_hand(color).to_vec();
+
+        let roll = chosen_roll.unwrap();
+        let roll_numbers = (roll / 2, roll.div_ceil(2));
+        state.roll_dice(color, Some(roll_numbers));
+
+        let chosen_resource_idx = chosen_resource.unwrap() as usize;
+        assert_eq!(state.vector[BANK_RESOURCE_SLICE][chosen_resource_idx], 0);
+
+        assert_eq!(
+            state.get_player_hand(color)[chosen_resource_idx],
+            hand_before[chosen_resource_idx] + 1
+        );
+        assert_eq!(state.vector[BANK_RESOURCE_SLICE][chosen_resource_idx], 0)
+    }
+
+    #[test]
+    fn test_roll_multiple_player_no_payment_when_insufficient_bank() {
+        let mut state = State::new_base();
+        let color1 = 1;
+        let color2 = 2;
+
+        let (resource, number, node1, node2) = {
+            let tile = state
+                .map_instance
+                .get_land_tiles()
+                .values()
+                .find(|tile| {
+                    tile.resource.is_some() && // Not a desert
+                    tile.id != state.get_robber_tile() // Not under robber
+                })
+                .expect("Should be at least one valid tile");
+
+            let node_ids: Vec<_> = tile.hexagon.nodes.values().take(2).copied().collect();
+
+            (
+                tile.resource.unwrap(),
+                tile.number.unwrap(),
+                node_ids[0],
+                node_ids[1],
+            )
+        };
+
+        // Place two opposing cities on a shared tile with expected yields
+        state.build_settlement(color1, node1);
+        state.build_settlement(color2, node2);
+        state.apply_action(Action::DebugSetState {
+            color: color1,
+            edit: StateEdit::Hand(CITY_COST),
+        });
+        state.apply_action(Action::DebugSetState {
+            color: color2,
+            edit: StateEdit::Hand(CITY_COST),
+        });
+        state.build_city(color1, node1);
+        state.build_city(color2, node2);
+
+        // Set bank to have only 1 of the needed resource
+        let resource_idx = resource as usize;
+        let mut bank: FreqDeck = state.vector[BANK_RESOURCE_SLICE].try_into().unwrap();
+        bank[resource_idx] = 1;
+        state.apply_action(Action::DebugSetState {
+            color: color1,
+            edit: StateEdit::Bank(bank),
+        });
+
+        let bank_before = state.vector[BANK_RESOURCE_SLICE][resource_idx];
+        let hand1_before = state.get_player_hand(color1)[resource_idx];
+        let hand2_before = state.get_player_hand(color2)[resource_idx];
+
+        // Roll the shared tile's number
+        let roll_numbers = (number / 2, number.div_ceil(2));
+        state.roll_dice(color1, Some(roll_numbers));
+
+        assert_eq!(
+            state.vector[BANK_RESOURCE_SLICE][resource_idx], bank_before,
+            "Bank should be unchanged"
+        );
+        // Neither player should get any resources
+        assert_eq!(
+            state.get_player_hand(color1)[resource_idx],
+            hand1_before,
+            "Player 1 should not receive resources"
+        );
+        assert_eq!(
+            state.get_player_hand(color2)[resource_idx],
+            hand2_before,
+            "Player 2 should not receive resources"
+        );
+    }
+
+    #[test]
+    fn test_gold_field_owners_pick_in_seat_order_from_the_roller() {
+        let config = GameConfiguration {
+            map_type: MapType::Seafarers,
+            ..GameConfiguration::default()
+        };
+        let map = MapInstance::shared(&config.map_type, 0);
+        let mut state = State::new_with_seed(Arc::new(config), map, 0);
+        let seating_order = state.get_seating_order().to_vec();
+        let (roller, other) = (seating_order[0], seating_order[1]);
+        let gold = state
+            .map_instance
+            .get_land_tiles()
+            .values()
+            .find(|tile| tile.gold)
+            .unwrap()
+            .clone();
+
+        // A settlement for the roller and a city for the next seat on the gold field
+        state.build_settlement(roller, gold.hexagon.nodes[&NodeRef::North]);
+        state.build_settlement(other, gold.hexagon.nodes[&NodeRef::South]);
+        state.apply_action(Action::DebugSetState {
+            color: other,
+            edit: StateEdit::Hand(CITY_COST),
+        });
+        state.build_city(other, gold.hexagon.nodes[&NodeRef::South]);
+        state.apply_action(Action::DebugSetState {
+            color: roller,
+            edit: StateEdit::InitialBuildPhase(false),
+        });
+
+        let number = gold.number.unwrap();
+        state.apply_action(Action::Roll {
+            color: roller,
+            dice_opt: Some((number / 2, number.div_ceil(2))),
+        });
+        let hands: Vec<Vec<u8>> = [roller, other]
+            .iter()
+            .map(|&color| state.get_player_hand(color).to_vec())
+            .collect();
+
+        assert_eq!(state.get_action_prompt(), ActionPrompt::ChooseGold);
+        assert_eq!(state.get_current_color(), roller);
+        assert_eq!(
+            state.generate_playable_actions(),
+            (0..5)
+                .map(|resource| Action::ChooseGold {
+                    color: roller,
+                    resource,
+                })
+                .collect::<Vec<_>>()
+        );
+        state.apply_action(Action::ChooseGold {
+            color: roller,
+            resource: 4,
+        });
+
+        assert_eq!(state.get_current_color(), other);
+        assert_eq!(state.get_gold_owed(other), 2);
+        for resource in [0, 3] {
+            state.apply_action(Action::ChooseGold {
+                color: other,
+                resource,
+            });
+        }
+
+        assert_eq!(state.get_action_prompt(), ActionPrompt::PlayTurn);
+        assert_eq!(state.get_current_color(), roller);
+        assert_eq!(state.get_player_hand(roller)[4], hands[0][4] + 1);
+        assert_eq!(state.get_player_hand(other)[0], hands[1][0] + 1);
+        assert_eq!(state.get_player_hand(other)[3], hands[1][3] + 1);
+
+        // Nothing is owed while the robber sits on the field
+        state.vector[ROBBER_TILE_INDEX] = gold.id;
+        state.vector[HAS_ROLLED_INDEX] = 0;
+        state.apply_action(Action::Roll {
+            color: roller,
+            dice_opt: Some((number / 2, number.div_ceil(2))),
+        });
+        assert_eq!(state.get_action_prompt(), ActionPrompt::PlayTurn);
+    }
+}
@@ -0,0 +1,222 @@
+// The discard phase after a 7 and moving the robber.
+
+use rand::Rng;
+
+use super::super::State;
+use crate::deck_slices::{freqdeck_add, freqdeck_sub};
+use crate::enums::FreqDeck;
+use crate::state_vector::*;
+
+impl State {
+    pub(super) fn move_robber(
+        &mut self,
+        color: u8,
+        coordinate: (i8, i8, i8),
+        victim_opt: Option<u8>,
+    ) {
+        self.set_robber_tile(self.map_instance.get_land_tile(coordinate).unwrap().id);
+
+        if let Some(victim) = victim_opt {
+            let total_cards: u8 = self.get_player_hand(victim).iter().sum();
+
+            if total_cards > 0 {
+                // Randomly select card to steal
+                let selected_idx = self.rng.gen_range(0..total_cards);
+
+                let mut cumsum = 0;
+                let mut stolen_resource_idx = 0;
+                for (i, &count) in self.get_player_hand(victim).iter().enumerate() {
+                    cumsum += count;
+                    if selected_idx < cumsum {
+                        stolen_resource_idx = i;
+                        break;
+                    }
+                }
+
+                let mut stolen_freqdeck = [0; 5];
+                stolen_freqdeck[stolen_resource_idx] = 1;
+                freqdeck_sub(self.get_mut_player_hand(victim), stolen_freqdeck);
+                freqdeck_add(self.get_mut_player_hand(color), stolen_freqdeck);
+            }
+        }
+        self.vector[IS_MOVING_ROBBER_INDEX] = 0;
+    }
+
+    pub(super) fn discard(&mut self, color: u8, discarded: FreqDeck) {
+        // Assume validation has checked the count and that the cards are in hand
+        freqdeck_sub(self.get_mut_player_hand(color), discarded);
+        freqdeck_add(&mut self.vector[BANK_RESOURCE_SLICE], discarded);
+
+        log::info!(
+            "🗑️  Player {} discarded {} cards: {:?}",
+            color,
+            discarded.iter().sum::<u8>(),
+            discarded
+        );
+
+        // Handle proper discard turn advancement and state transitions
+        self.advance_discard_turn();
+    }
+
+    pub(super) fn advance_discard_turn(&mut self) {
+        // Fix the discard advancement logic to handle seating order properly
+
+        let current_tick_seat_index = self.vector[CURRENT_TICK_SEAT_INDEX] as usize;
+        let seating_order = self.get_seating_order();
+        let current_discarder_color = seating_order[current_tick_seat_index];
+        let num_players = self.get_num_players();
+
+        log::info!(
+            "🔍 Checking for remaining discarders (current: {} at index {}, limit: {})",
+            current_discarder_color,
+            current_tick_seat_index,
+            self.config.discard_limit
+        );
+
+        // Check if the CURRENT discarder still needs to discard FIRST
+        let current_hand = self.get_player_hand(current_discarder_color);
+        let current_total: u8 = current_hand.iter().sum();
+        log::info!(
+            "🔍 Current discarder {} has {} cards",
+            current_discarder_color,
+            current_total
+        );
+
+        if current_total > self.config.discard_limit {
+            log::info!(
+                "➡️  Current discarder still needs to discard: Player {} ({} cards > {})",
+                current_discarder_color,
+                current_total,
+                self.config.discard_limit
+            );
+            return; // Stay with current discarder - they still need to discard
+        }
+
+        // Current discarder is done, check remaining players by going through seating order
+        for i in 1..num_players {
+            let next_seating_index = (current_tick_seat_index + i as usize) % num_players as usize;
+            let next_player_color = seating_order[next_seating_index];
+            let player_hand = self.get_player_hand(next_player_color);
+            let total_cards: u8 = player_hand.iter().sum();
+
+            log::info!(
+                "🔍 Player {} (index {}) has {} cards (limit: {})",
+                next_player_color,
+                next_seating_index,
+                total_cards,
+                self.config.discard_limit
+            );
+
+            if total_cards > self.config.discard_limit {
+                // Found next discarder
+                self.vector[CURRENT_TICK_SEAT_INDEX] = next_seating_index as u8;
+                log::info!(
+                    "➡️  Next discarder: Player {} at index {} ({} cards > {})",
+                    next_player_color,
+                    next_seating_index,
+                    total_cards,
+                    self.config.discard_limit
+                );
+                return;
+            }
+        }
+
+        // No more discarders found - transition to robber movement
+        self.vector[IS_DISCARDING_INDEX] = 0;
+        self.vector[IS_MOVING_ROBBER_INDEX] = 1;
+        // Return to the player who originally rolled the 7
+        self.vector[CURRENT_TICK_SEAT_INDEX] = self.vector[CURRENT_TURN_SEAT_INDEX];
+        log::info!(
+            "🎯 All discards complete → Moving robber (Player {})",
+            self.vector[CURRENT_TURN_SEAT_INDEX]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, RulesVersion, StateEdit};
+
+    #[test]
+    fn test_discard() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Give the player a known distribution of 17 cards
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([3, 9, 1, 3, 1]),
+        });
+
+        let bank_before = state.vector[BANK_RESOURCE_SLICE].to_vec();
+
+        state.discard(color, state.suggested_discard(color));
+
+        // After discarding 17 / 2 = 8 cards, the player should have 9 left.
+        let total_after: u8 = state.get_player_hand(color).iter().sum();
+        assert_eq!(total_after, 9, "Player should have exactly 9 cards left.");
+
+        // Verify discard phase ended
+        assert_eq!(
+            state.vector[IS_DISCARDING_INDEX], 0,
+            "Discard phase should end."
+        );
+
+        // The bank should have received exactly 8 more cards in total
+        let bank_after = &state.vector[BANK_RESOURCE_SLICE];
+        let mut total_discarded = 0;
+        for i in 0..5 {
+            total_discarded += bank_after[i] - bank_before[i];
+        }
+        assert_eq!(
+            total_discarded, 8,
+            "Exactly 8 cards should have been added to the bank."
+        );
+
+        // Check the specific distribution after discard
+        let final_player_hand = state.get_player_hand(color);
+        assert_eq!(
+            final_player_hand,
+            &[2, 2, 1, 3, 1],
+            "Discard logic should spread discards across highest-frequency resources first."
+        );
+
+        // Rules revisions before 3 took the larger half of an odd hand
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([3, 9, 1, 3, 1]),
+        });
+        let mut config = state.get_config().as_ref().clone();
+        config.rules = RulesVersion::Revision2;
+        state.config = std::sync::Arc::new(config);
+        assert_eq!(state.discard_count(color), 9);
+    }
+
+    #[test]
+    fn test_move_robber_steals_one_card() {
+        let mut state = State::new_base();
+        let (thief, victim) = (0, 1);
+        for (color, edit) in [
+            (victim, StateEdit::Hand([0, 0, 3, 0, 0])),
+            (thief, StateEdit::InitialBuildPhase(false)),
+            (thief, StateEdit::MovingRobber(true)),
+        ] {
+            state.apply_action(Action::DebugSetState { color, edit });
+        }
+
+        let (&coordinate, tile) = state
+            .map_instance
+            .get_land_tiles()
+            .iter()
+            .find(|(_, tile)| tile.id != state.get_robber_tile())
+            .unwrap();
+        let tile_id = tile.id;
+        state.move_robber(thief, coordinate, Some(victim));
+
+        assert_eq!(state.get_robber_tile(), tile_id);
+        assert_eq!(state.get_player_hand(victim)[2], 2);
+        assert_eq!(state.get_player_hand(thief)[2], 1);
+        assert_eq!(state.vector[IS_MOVING_ROBBER_INDEX], 0);
+    }
+}
@@ -0,0 +1,88 @@
+// Domestic trades: putting an offer on the table, the answers, and the offerer's pick.
+
+use super::super::trading::DomesticTrade;
+use super::super::State;
+use crate::deck_slices::{freqdeck_add, freqdeck_sub};
+use crate::enums::FreqDeck;
+use crate::state_vector::*;
+
+impl State {
+    pub(super) fn offer_trade(&mut self, color: u8, give: FreqDeck, take: FreqDeck) {
+        let offerer_seat = self.get_current_tick_seat();
+        let seating_order = self.get_seating_order();
+        let pending: Vec<u8> = (1..seating_order.len())
+            .map(|i| seating_order[(offerer_seat as usize + i) % seating_order.len()])
+            .collect();
+        self.domestic_trade = Some(DomesticTrade {
+            offerer: color,
+            give,
+            take,
+            acceptees: Vec::new(),
+            pending,
+            offerer_seat,
+            counters: 0,
+        });
+        self.trade_offers_this_turn += 1;
+        self.next_trade_answer();
+    }
+
+    pub(super) fn answer_trade(&mut self, color: u8, accept: bool) {
+        // Assume move_generation has checked that an acceptee holds the cards asked for
+        let Some(trade) = &mut self.domestic_trade else {
+            return;
+        };
+        if accept {
+            trade.acceptees.push(color);
+        }
+        trade.pending.retain(|&pending| pending != color);
+        self.next_trade_answer();
+    }
+
+    /// Replace the offer's terms. A responder's counter goes straight to the offerer, with
+    /// the counterer as the one seat that accepted; the offerer's goes back to that seat.
+    pub(super) fn counter_trade(&mut self, color: u8, give: FreqDeck, take: FreqDeck) {
+        let Some(trade) = &mut self.domestic_trade else {
+            return;
+        };
+        trade.give = give;
+        trade.take = take;
+        trade.counters += 1;
+        if color == trade.offerer {
+            trade.pending = std::mem::take(&mut trade.acceptees);
+        } else {
+            trade.pending.clear();
+            trade.acceptees = vec![color];
+        }
+        self.next_trade_answer();
+    }
+
+    pub(super) fn confirm_trade(&mut self, acceptee: u8) {
+        if let Some(trade) = self.domestic_trade.clone() {
+            freqdeck_sub(self.get_mut_player_hand(trade.offerer), trade.give);
+            freqdeck_add(self.get_mut_player_hand(acceptee), trade.give);
+            freqdeck_sub(self.get_mut_player_hand(acceptee), trade.take);
+            freqdeck_add(self.get_mut_player_hand(trade.offerer), trade.take);
+        }
+        self.close_trade();
+    }
+
+    /// Take the offer off the table and hand play back to the offerer
+    pub(super) fn close_trade(&mut self) {
+        if let Some(trade) = self.domestic_trade.take() {
+            self.vector[CURRENT_TICK_SEAT_INDEX] = trade.offerer_seat;
+        }
+    }
+
+    /// Pass the offer to the next seat to answer. Once all have answered, the offerer
+    /// picks among the acceptees, or the offer lapses if there are none.
+    fn next_trade_answer(&mut self) {
+        let Some(trade) = &self.domestic_trade else {
+            return;
+        };
+        match trade.responder() {
+            Some(responder) => self.vector[CURRENT_TICK_SEAT_INDEX] = self.seat_of(responder),
+            None if trade.acceptees.is_empty() => self.close_trade(),
+            None => self.vector[CURRENT_TICK_SEAT_INDEX] = trade.offerer_seat,
+        }
+    }
+}
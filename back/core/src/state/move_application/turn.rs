@@ -0,0 +1,215 @@
+// Turn order, trades with the bank or a teammate, and ending the turn. Trades between
+// players are in trade.rs.
+
+use super::super::State;
+use crate::state_vector::*;
+
+impl State {
+    pub fn advance_turn(&mut self, step_size: i8) {
+        // We add an extra num_players to ensure next_index is positive (u8)
+        let num_players = self.get_num_players() as i8;
+        let next_index =
+            ((self.get_current_tick_seat() as i8 + step_size + num_players) % num_players) as u8;
+
+        self.vector[CURRENT_TICK_SEAT_INDEX] = next_index;
+    }
+
+    pub(super) fn maritime_trade(&mut self, color: u8, give: u8, take: u8, ratio: u8) {
+        // Assume move_generation has already checked that player has enough resources
+        // to give and that bank has enough resources to take
+        self.from_player_to_bank(color, give, ratio);
+        self.from_bank_to_player(color, take);
+    }
+
+    pub(super) fn gift_resource(&mut self, color: u8, teammate: u8, resource: u8) {
+        // Assume move_generation has checked the team rules and that the card exists
+        self.get_mut_player_hand(color)[resource as usize] -= 1;
+        self.get_mut_player_hand(teammate)[resource as usize] += 1;
+        self.gifted_this_turn = true;
+    }
+
+    pub(super) fn end_turn(&mut self, _color: u8) {
+        // Handle discard phase properly
+        if self.is_discarding() {
+            // During discard phase, EndTurn should advance discard turn, not regular turn
+            self.advance_discard_turn();
+        } else {
+            // Normal turn advancement
+            self.vector[HAS_PLAYED_DEV_CARD] = 0;
+            self.vector[HAS_ROLLED_INDEX] = 0;
+            self.gifted_this_turn = false;
+            self.trade_offers_this_turn = 0;
+            self.ships_built_this_turn.clear();
+            self.ship_moved_this_turn = false;
+            self.clear_bought_dev_cards();
+            if self.config.uses_extension() {
+                self.special_build_seat = Some(self.get_current_tick_seat());
+            }
+            self.advance_turn(1);
+        }
+    }
+
+    /// Like ending the turn, except the roll stays in effect for the next seat. In a
+    /// Special Build Phase, once every other seat has passed, the seat after the one whose
+    /// turn ended starts its turn.
+    pub(super) fn pass_build_phase(&mut self, _color: u8) {
+        self.vector[HAS_PLAYED_DEV_CARD] = 0;
+        self.gifted_this_turn = false;
+        self.trade_offers_this_turn = 0;
+        self.ships_built_this_turn.clear();
+        self.ship_moved_this_turn = false;
+        self.clear_bought_dev_cards();
+        self.advance_turn(1);
+        if self.special_build_seat == Some(self.get_current_tick_seat()) {
+            self.special_build_seat = None;
+            self.advance_turn(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, ActionPrompt, GameConfiguration, StateEdit};
+    use crate::map_instance::MapInstance;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_maritime_trade_basic_rate() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([4, 0, 0, 0, 0]),
+        });
+
+        let initial_bank_brick = state.vector[BANK_RESOURCE_SLICE][1];
+
+        state.apply_action(Action::MaritimeTrade {
+            color,
+            give: 0,
+            take: 1,
+            ratio: 4,
+        });
+
+        assert_eq!(state.get_player_hand(color)[0], 0);
+        assert_eq!(state.get_player_hand(color)[1], 1);
+        assert_eq!(state.vector[BANK_RESOURCE_SLICE][0], 19);
+        assert_eq!(state.vector[BANK_RESOURCE_SLICE][1], initial_bank_brick - 1);
+    }
+
+    #[test]
+    fn test_pass_build_phase_keeps_the_roll() {
+        let mut state = State::new_base();
+        let roller = state.get_current_color();
+        let seating_order = state.get_seating_order().to_vec();
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::HasRolled(true),
+        ] {
+            state.apply_action(Action::DebugSetState {
+                color: roller,
+                edit,
+            });
+        }
+        state.apply_action(Action::PassBuildPhase { color: roller });
+
+        assert_eq!(state.get_current_color(), seating_order[1]);
+        assert_eq!(state.vector[HAS_ROLLED_INDEX], 1);
+    }
+
+    #[test]
+    fn test_special_build_phase_runs_between_turns() {
+        let config = GameConfiguration::builder().num_players(5).build().unwrap();
+        let map_instance = MapInstance::shared(&config.map_type, 0);
+        let mut state = State::new(Arc::new(config), map_instance);
+        let seating_order = state.get_seating_order().to_vec();
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::HasRolled(true),
+        ] {
+            state.apply_action(Action::DebugSetState {
+                color: seating_order[0],
+                edit,
+            });
+        }
+        state.apply_action(Action::EndTurn {
+            color: seating_order[0],
+        });
+
+        for &color in &seating_order[1..] {
+            assert_eq!(state.get_action_prompt(), ActionPrompt::SpecialBuild);
+            assert_eq!(
+                state.generate_playable_actions(),
+                vec![Action::PassBuildPhase { color }]
+            );
+            state.apply_action(Action::PassBuildPhase { color });
+        }
+        assert_eq!(state.get_action_prompt(), ActionPrompt::PlayTurn);
+        assert_eq!(state.get_current_color(), seating_order[1]);
+        assert!(!state.current_player_rolled());
+    }
+
+    #[test]
+    fn test_end_turn() {
+        let mut state = State::new_base();
+        let starting_color = state.get_current_color();
+        let seating_order = state.get_seating_order().to_vec();
+
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::PlayedDevCard(true),
+            StateEdit::HasRolled(true),
+            StateEdit::FreeRoads(1),
+        ] {
+            state.apply_action(Action::DebugSetState {
+                color: starting_color,
+                edit,
+            });
+        }
+        state.apply_action(Action::EndTurn {
+            color: starting_color,
+        });
+
+        assert_eq!(state.vector[HAS_PLAYED_DEV_CARD], 0);
+        assert_eq!(state.vector[HAS_ROLLED_INDEX], 0);
+        assert_eq!(state.vector[FREE_ROADS_AVAILABLE_INDEX], 0);
+
+        assert_eq!(state.get_current_color(), seating_order[1]);
+
+        for _ in 0..(state.get_num_players() - 1) {
+            state.apply_action(Action::EndTurn {
+                color: state.get_current_color(),
+            });
+        }
+
+        assert_eq!(state.get_current_color(), starting_color);
+    }
+
+    #[test]
+    fn test_advance_turn_wraps_both_ways() {
+        let mut state = State::new_base();
+        let last_seat = state.get_num_players() - 1;
+
+        state.advance_turn(-1);
+        assert_eq!(state.get_current_tick_seat(), last_seat);
+        state.advance_turn(1);
+        assert_eq!(state.get_current_tick_seat(), 0);
+    }
+
+    #[test]
+    fn test_gift_resource_moves_one_card() {
+        let mut state = State::new_base();
+        state.apply_action(Action::DebugSetState {
+            color: 0,
+            edit: StateEdit::Hand([0, 0, 0, 2, 0]),
+        });
+
+        state.gift_resource(0, 2, 3);
+
+        assert_eq!(state.get_player_hand(0)[3], 1);
+        assert_eq!(state.get_player_hand(2)[3], 1);
+        assert!(state.gifted_this_turn);
+    }
+}
@@ -0,0 +1,1485 @@
+use crate::deck_slices::{
+    freqdeck_contains, CITY_COST, DEVCARD_COST, ROAD_COST, SETTLEMENT_COST, SHIP_COST,
+};
+use crate::state_vector::{dev_deck_size, DEV_BANK_PTR_INDEX};
+
+use super::ships::TOTAL_SHIPS_PER_PLAYER;
+use super::Building;
+use super::State;
+
+use crate::enums::{Action, ActionPrompt, DevCard, FreqDeck, Resource};
+use crate::map_template::Coordinate;
+use smallvec::SmallVec;
+
+/// Why a robber placement is (or is not) preferred, in order of preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum RobberTile {
+    Preferred,
+    Desert,
+    BlocksTeammate,
+    BlocksProtected,
+}
+
+/// A robber destination: its rank, the tile, and the seats that could be robbed there
+pub(super) type RobberTarget = (RobberTile, Coordinate, SmallVec<[u8; 4]>);
+
+impl State {
+    pub fn generate_playable_actions(&self) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.generate_playable_actions_into(&mut actions);
+        actions
+    }
+
+    /// Same as `generate_playable_actions`, but fills a caller-owned buffer (cleared
+    /// first) so hot loops like search can reuse one allocation across positions.
+    pub fn generate_playable_actions_into(&self, out: &mut Vec<Action>) {
+        out.clear();
+        let current_color = self.get_current_color();
+        let action_prompt = self.get_action_prompt();
+        match action_prompt {
+            ActionPrompt::BuildInitialSettlement => {
+                self.add_settlement_possibilities(current_color, true, out)
+            }
+            ActionPrompt::BuildInitialRoad => {
+                self.add_initial_road_possibilities(current_color, out)
+            }
+            ActionPrompt::MoveRobber => self.add_robber_possibilities(current_color, out),
+            ActionPrompt::PlayTurn => self.add_play_turn_possibilities(current_color, out),
+            ActionPrompt::Discard => self.add_discard_possibilities(current_color, out),
+            ActionPrompt::DecideTrade => self.add_trade_answer_possibilities(current_color, out),
+            ActionPrompt::DecideAcceptees => {
+                self.add_trade_acceptee_possibilities(current_color, out)
+            }
+            ActionPrompt::SpecialBuild => self.add_special_build_possibilities(current_color, out),
+            ActionPrompt::ChooseGold => self.add_gold_possibilities(current_color, out),
+        }
+    }
+
+    pub fn settlement_possibilities(&self, color: u8, is_initial_build_phase: bool) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_settlement_possibilities(color, is_initial_build_phase, &mut actions);
+        actions
+    }
+
+    pub fn initial_road_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_initial_road_possibilities(color, &mut actions);
+        actions
+    }
+
+    /// Why `action` would break the initial placement rules, if it would. Checked before
+    /// touching the board, since an unknown node id would otherwise panic in the map
+    /// lookups. Outside the initial build phase every action passes.
+    pub fn initial_placement_violation(&self, action: &Action) -> Option<String> {
+        let color = self.get_current_color();
+        match (self.get_action_prompt(), *action) {
+            (
+                ActionPrompt::BuildInitialSettlement,
+                Action::BuildSettlement { color: c, node_id },
+            ) => {
+                if c != color {
+                    Some(format!("color {color} places next, not color {c}"))
+                } else if !self.map_instance.land_nodes().contains(&node_id) {
+                    Some(format!("node {node_id} is not on the board"))
+                } else if !self.board_buildable_ids.contains(&node_id) {
+                    Some(format!("node {node_id} is taken or next to a settlement"))
+                } else {
+                    None
+                }
+            }
+            (ActionPrompt::BuildInitialSettlement, other) => {
+                Some(format!("an initial settlement is due, not {other:?}"))
+            }
+            (ActionPrompt::BuildInitialRoad, Action::BuildRoad { .. }) => {
+                if self.initial_road_possibilities(color).contains(action) {
+                    None
+                } else {
+                    Some(format!(
+                        "{action:?} does not leave color {color}'s newest settlement"
+                    ))
+                }
+            }
+            (ActionPrompt::BuildInitialRoad, other) => {
+                Some(format!("an initial road is due, not {other:?}"))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn road_possibilities(&self, color: u8, is_free: bool) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_road_possibilities(color, is_free, &mut actions);
+        actions
+    }
+
+    pub fn city_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_city_possibilities(color, &mut actions);
+        actions
+    }
+
+    pub fn buy_development_card_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_buy_development_card_possibilities(color, &mut actions);
+        actions
+    }
+
+    pub fn year_of_plenty_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_year_of_plenty_possibilities(color, &mut actions);
+        actions
+    }
+
+    pub fn play_turn_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_play_turn_possibilities(color, &mut actions);
+        actions
+    }
+
+    pub fn maritime_trade_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_maritime_trade_possibilities(color, &mut actions);
+        actions
+    }
+
+    /// Robber placements (and steal victims) allowed by the configured robber rules.
+    ///
+    /// Tiles that would hit a protected player (friendly robber) or a teammate are ruled
+    /// out. If nothing is left the robber goes back to the desert; only when that is
+    /// impossible too (robber already there) are the restrictions relaxed, in that order.
+    pub fn robber_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_robber_possibilities(color, &mut actions);
+        actions
+    }
+
+    /// Team variant: give one card of any held resource to a teammate, once per turn
+    pub fn gift_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_gift_possibilities(color, &mut actions);
+        actions
+    }
+
+    pub fn discard_possibilities(&self, color: u8) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.add_discard_possibilities(color, &mut actions);
+        actions
+    }
+
+    fn add_settlement_possibilities(
+        &self,
+        color: u8,
+        is_initial_build_phase: bool,
+        out: &mut Vec<Action>,
+    ) {
+        log::debug!(
+            "🏘️  DEBUG settlement_possibilities: color {}, initial_phase: {}",
+            color,
+            is_initial_build_phase
+        );
+
+        if is_initial_build_phase {
+            log::debug!(
+                "  - Using initial build phase logic, {} buildable nodes",
+                self.board_buildable_ids.len()
+            );
+
+            // Use the maintained board_buildable_ids cache for initial build phase
+            out.extend(
+                self.board_buildable_ids
+                    .iter()
+                    .map(|&node_id| Action::BuildSettlement { color, node_id }),
+            );
+        } else {
+            log::debug!("  - Using normal build phase logic");
+            let has_resources = freqdeck_contains(self.get_player_hand(color), &SETTLEMENT_COST);
+            let settlements_left = self.get_settlements_left(color);
+            let has_settlements_available = settlements_left > 0;
+
+            log::debug!(
+                "  - has_resources: {}, settlements_left: {}, has_settlements_available: {}",
+                has_resources,
+                settlements_left,
+                has_settlements_available
+            );
+
+            if has_resources && has_settlements_available {
+                // For non-initial phase, check road connectivity
+                out.extend(
+                    self.buildable_nodes(color)
+                        .filter(|&node_id| {
+                            // Must touch at least one road or ship owned by this player
+                            self.map_instance
+                                .get_neighbor_edges(node_id)
+                                .iter()
+                                .any(|&edge_id| self.owns_road(color, edge_id))
+                                || self.touches_own_ship(color, node_id)
+                        })
+                        .map(|node_id| Action::BuildSettlement { color, node_id }),
+                );
+            } else {
+                log::debug!("  - No resources or settlements available, returning empty");
+            }
+        }
+    }
+
+    fn add_initial_road_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        // A handicap's extra road may extend anywhere from the network
+        if self.roads_by_color[color as usize] >= 2 {
+            out.extend(
+                self.buildable_edges(color)
+                    .map(|edge_id| Action::BuildRoad { color, edge_id }),
+            );
+            return;
+        }
+
+        // Get the last settlement built by this player
+        let last_settlement_building = self
+            .buildings_by_color
+            .get(&color)
+            .and_then(|buildings| buildings.last());
+
+        let last_node_id = match last_settlement_building {
+            Some(Building::Settlement(_, node_id)) => *node_id,
+            Some(Building::City(_, node_id)) => *node_id,
+            None => {
+                // No buildings yet, return empty - shouldn't happen in proper initial build sequence
+                log::warn!(
+                    "initial_road_possibilities called for color {} with no buildings",
+                    color
+                );
+                return;
+            }
+        };
+
+        // Don't rely on board_buildable_edges which uses connected_components cache
+        // For initial build phase, just get edges adjacent to the last settlement
+        let before = out.len();
+        out.extend(
+            self.map_instance
+                .get_neighbor_edges(last_node_id)
+                .iter()
+                .copied()
+                .filter(|&edge_id| !self.has_road(edge_id))
+                .map(|edge_id| Action::BuildRoad { color, edge_id }),
+        );
+
+        log::debug!(
+            "🏗️  Initial road possibilities for player {}: last settlement at node {}, {} buildable edges",
+            color,
+            last_node_id,
+            out.len() - before
+        );
+    }
+
+    fn add_road_possibilities(&self, color: u8, is_free: bool, out: &mut Vec<Action>) {
+        if self.get_roads_left(color) == 0 {
+            return;
+        }
+
+        if is_free || freqdeck_contains(self.get_player_hand(color), &ROAD_COST) {
+            out.extend(
+                self.buildable_edges(color)
+                    .map(|edge_id| Action::BuildRoad { color, edge_id }),
+            );
+        }
+    }
+
+    fn add_ship_possibilities(&self, color: u8, is_free: bool, out: &mut Vec<Action>) {
+        if !self.has_ships() || self.ships_by_color[color as usize] >= TOTAL_SHIPS_PER_PLAYER {
+            return;
+        }
+
+        if is_free || freqdeck_contains(self.get_player_hand(color), &SHIP_COST) {
+            out.extend(
+                self.buildable_sea_edges(color, None)
+                    .into_iter()
+                    .map(|edge_id| Action::BuildShip { color, edge_id }),
+            );
+        }
+    }
+
+    fn add_move_ship_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        for from in self.movable_ships(color) {
+            out.extend(
+                self.buildable_sea_edges(color, Some(from))
+                    .into_iter()
+                    .map(|to| Action::MoveShip { color, from, to }),
+            );
+        }
+    }
+
+    fn add_city_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        let has_money = freqdeck_contains(self.get_player_hand(color), &CITY_COST);
+        if !has_money {
+            return;
+        }
+
+        if self.get_cities_left(color) == 0 {
+            return;
+        }
+
+        out.extend(
+            self.get_settlements(color)
+                .iter()
+                .map(|building| match building {
+                    Building::Settlement(color, node_id) => Action::BuildCity {
+                        color: *color,
+                        node_id: *node_id,
+                    },
+                    _ => panic!("Invalid building type"),
+                }),
+        );
+    }
+
+    fn add_buy_development_card_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        let has_money = freqdeck_contains(self.get_player_hand(color), &DEVCARD_COST);
+        if !has_money {
+            return;
+        }
+
+        // Check if there are development cards available in the deck
+        let dev_card_ptr = self.vector[DEV_BANK_PTR_INDEX] as usize;
+        if dev_card_ptr >= dev_deck_size(self.config.num_players) {
+            return;
+        }
+
+        out.push(Action::BuyDevelopmentCard { color });
+    }
+
+    fn add_year_of_plenty_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        let bank_resources = self.get_bank_resources();
+        let before = out.len();
+
+        // First try all same-resource pairs
+        for (resource, &count) in bank_resources.iter().enumerate() {
+            if count >= 2 {
+                out.push(Action::PlayYearOfPlenty {
+                    color,
+                    resources: (resource as u8, Some(resource as u8)),
+                });
+            }
+        }
+
+        // Then try different-resource pairs
+        for (resource1, &count1) in bank_resources.iter().enumerate() {
+            if count1 > 0 {
+                for (resource2, &count2) in bank_resources.iter().enumerate().skip(resource1 + 1) {
+                    if count2 > 0 {
+                        out.push(Action::PlayYearOfPlenty {
+                            color,
+                            resources: (resource1 as u8, Some(resource2 as u8)),
+                        });
+                    }
+                }
+            }
+        }
+
+        // If no two-resource actions possible, try single resources
+        if out.len() == before {
+            for (resource, &count) in bank_resources.iter().enumerate() {
+                if count > 0 {
+                    out.push(Action::PlayYearOfPlenty {
+                        color,
+                        resources: (resource as u8, None),
+                    });
+                    break; // Only need first available resource
+                }
+            }
+        }
+    }
+
+    fn add_play_turn_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        if self.is_road_building() {
+            let before = out.len();
+            self.add_road_possibilities(color, true, out);
+            self.add_ship_possibilities(color, true, out);
+            if out.len() > before {
+                return;
+            }
+        }
+        if !self.current_player_rolled() {
+            out.push(Action::Roll {
+                color,
+                dice_opt: None,
+            });
+            if self.can_play_dev(DevCard::Knight as u8) {
+                out.push(Action::PlayKnight { color });
+            }
+            return;
+        }
+
+        out.push(Action::EndTurn { color });
+        if self.config.variants.speed_rounds {
+            out.push(Action::PassBuildPhase { color });
+        }
+
+        // Add all possible actions
+        self.add_settlement_possibilities(color, false, out);
+        self.add_road_possibilities(color, false, out);
+        self.add_ship_possibilities(color, false, out);
+        self.add_move_ship_possibilities(color, out);
+        self.add_city_possibilities(color, out);
+        self.add_buy_development_card_possibilities(color, out);
+
+        if self.can_play_dev(DevCard::Knight as u8) {
+            out.push(Action::PlayKnight { color });
+        }
+        if self.can_play_dev(DevCard::YearOfPlenty as u8) {
+            self.add_year_of_plenty_possibilities(color, out);
+        }
+        if self.can_play_dev(DevCard::Monopoly as u8) {
+            for resource in 0..5 {
+                out.push(Action::PlayMonopoly { color, resource });
+            }
+        }
+        if self.can_play_dev(DevCard::RoadBuilding as u8) {
+            // TODO: What if user has no roads left? or is completely blocked?
+            out.push(Action::PlayRoadBuilding { color });
+        }
+
+        // Add maritime trade possibilities
+        self.add_maritime_trade_possibilities(color, out);
+        self.add_gift_possibilities(color, out);
+        self.add_trade_offer_possibilities(color, out);
+    }
+
+    /// Between two turns of a 5–6 player game: build, buy, or pass. No trading and no
+    /// development cards.
+    fn add_special_build_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        out.push(Action::PassBuildPhase { color });
+        self.add_settlement_possibilities(color, false, out);
+        self.add_road_possibilities(color, false, out);
+        self.add_ship_possibilities(color, false, out);
+        self.add_city_possibilities(color, out);
+        self.add_buy_development_card_possibilities(color, out);
+    }
+
+    /// Maritime trade rate per resource (4, 3 or 2) given the ports `color` has built on
+    pub fn calculate_port_rates(&self, color: u8) -> [u8; 5] {
+        let mut port_rates = [4; 5]; // Default 4:1 rate for all resources
+
+        let Some(player_buildings) = self.buildings_by_color.get(&color) else {
+            return port_rates;
+        };
+
+        // For each player building, check if it's on a port and update rates
+        for building in player_buildings {
+            let node_id = match building {
+                Building::Settlement(_, id) | Building::City(_, id) => id,
+            };
+
+            if let Some(&port_resource) = self.map_instance.get_port_nodes().get(node_id) {
+                match port_resource {
+                    Some(resource) => port_rates[resource as usize] = 2,
+                    None => port_rates
+                        .iter_mut()
+                        .for_each(|rate| *rate = (*rate).min(3)),
+                }
+            }
+        }
+
+        port_rates
+    }
+
+    /// Ports `color` has a settlement or city on, one entry per port type: `None` for the
+    /// 3:1 port first, then 2:1 ports in resource order
+    pub fn get_owned_ports(&self, color: u8) -> Vec<Option<Resource>> {
+        let port_nodes = self.map_instance.get_port_nodes();
+        let mut ports: Vec<Option<Resource>> = self
+            .buildings_by_color
+            .get(&color)
+            .into_iter()
+            .flatten()
+            .filter_map(|building| match building {
+                Building::Settlement(_, id) | Building::City(_, id) => port_nodes.get(id).copied(),
+            })
+            .collect();
+        ports.sort_by_key(|port| port.map_or(0, |resource| resource as usize + 1));
+        ports.dedup();
+        ports
+    }
+
+    fn add_maritime_trade_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        let hand = self.get_player_hand(color);
+        let bank = self.get_bank_resources();
+        let port_rates = self.calculate_port_rates(color);
+
+        for (give_idx, &give_count) in hand.iter().enumerate() {
+            let rate = port_rates[give_idx];
+            if give_count < rate {
+                continue;
+            }
+            // Ensure bank has enough resources and it's a different resource
+            out.extend(
+                (0..5)
+                    .filter(|&take_idx| take_idx != give_idx && bank[take_idx] > 0)
+                    .map(|take_idx| Action::MaritimeTrade {
+                        color,
+                        give: give_idx as u8,
+                        take: take_idx as u8,
+                        ratio: rate,
+                    }),
+            );
+        }
+    }
+
+    fn add_robber_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        let tiles = self.robber_tiles(color);
+        let Some(best) = tiles.iter().map(|&(kind, _, _)| kind).min() else {
+            return;
+        };
+        for (_, coordinate, victims) in tiles.into_iter().filter(|(kind, _, _)| *kind == best) {
+            if victims.is_empty() {
+                out.push(Action::MoveRobber {
+                    color,
+                    coordinate,
+                    victim_opt: None,
+                });
+            }
+            out.extend(victims.into_iter().map(|victim| Action::MoveRobber {
+                color,
+                coordinate,
+                victim_opt: Some(victim),
+            }));
+        }
+    }
+
+    /// Every tile the robber could move to, ranked, with who could be robbed there.
+    /// Only the best-ranked tiles are offered.
+    pub(super) fn robber_tiles(&self, color: u8) -> SmallVec<[RobberTarget; 20]> {
+        let rules = &self.config.robber;
+        let current_robber_tile = self.get_robber_tile();
+        // Public points only, so the legal tiles give no victory point cards away
+        let is_protected = |other: u8| {
+            rules
+                .protect_up_to_vps
+                .is_some_and(|limit| self.get_public_victory_points(other) <= limit)
+                || self.is_catch_up_immune(other)
+        };
+
+        let mut tiles: SmallVec<[_; 20]> = SmallVec::new();
+        for (coordinate, tile) in self.map_instance.get_land_tiles() {
+            // Skip current robber location
+            if tile.id == current_robber_tile {
+                continue;
+            }
+
+            // Find players to steal from at this tile
+            let mut victims: SmallVec<[u8; 4]> = SmallVec::new();
+            let mut blocks_protected = false;
+            let mut blocks_teammate = false;
+            for node_id in tile.hexagon.nodes.values() {
+                if let Some(building) = self.buildings.get(node_id) {
+                    match building {
+                        Building::Settlement(owner, _) | Building::City(owner, _) => {
+                            if *owner == color {
+                                continue;
+                            }
+                            if self.are_teammates(*owner, color) {
+                                // Never rob or block your own team
+                                blocks_teammate = true;
+                            } else if is_protected(*owner) {
+                                blocks_protected = true;
+                            } else if rules.allow_steal
+                                && self.get_player_hand(*owner).iter().sum::<u8>() > 0
+                                && !victims.contains(owner)
+                            {
+                                // Victim must have resources
+                                victims.push(*owner);
+                            }
+                        }
+                    }
+                }
+            }
+
+            let kind = if tile.is_desert() {
+                // The desert produces nothing, so it never hurts anyone
+                if rules.allow_desert {
+                    RobberTile::Preferred
+                } else {
+                    RobberTile::Desert
+                }
+            } else if blocks_protected {
+                RobberTile::BlocksProtected
+            } else if blocks_teammate {
+                RobberTile::BlocksTeammate
+            } else {
+                RobberTile::Preferred
+            };
+            tiles.push((kind, *coordinate, victims));
+        }
+        tiles
+    }
+
+    fn add_gift_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        if !self.can_gift(color) {
+            return;
+        }
+        let hand = self.get_player_hand(color);
+        for teammate in self.teammates(color) {
+            for (resource, &count) in hand.iter().enumerate() {
+                if count > 0 {
+                    out.push(Action::GiftResource {
+                        color,
+                        teammate,
+                        resource: resource as u8,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Offers of one held card for one card of another resource. Larger offers are legal
+    /// too, but listing them all would swamp the action space.
+    fn add_trade_offer_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        if !self.can_offer_trade(color) {
+            return;
+        }
+        let hand = self.get_player_hand(color);
+        for (give, &count) in hand.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            for take in (0..5).filter(|&take| take != give) {
+                let mut give_deck = [0; 5];
+                give_deck[give] = 1;
+                let mut take_deck = [0; 5];
+                take_deck[take] = 1;
+                out.push(Action::OfferTrade {
+                    color,
+                    trade: (give_deck, take_deck),
+                });
+            }
+        }
+    }
+
+    fn add_trade_answer_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        out.push(Action::RejectTrade { color });
+        if let Some(trade) = &self.domestic_trade {
+            if self.can_accept_trade(color) {
+                out.push(Action::AcceptTrade {
+                    color,
+                    trade: (trade.give, trade.take),
+                });
+            }
+        }
+    }
+
+    fn add_trade_acceptee_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        out.push(Action::CancelTrade { color });
+        if let Some(trade) = &self.domestic_trade {
+            out.extend(
+                trade
+                    .acceptees
+                    .iter()
+                    .map(|&acceptee| Action::ConfirmTrade {
+                        color,
+                        trade: (trade.give, trade.take, acceptee),
+                    }),
+            );
+        }
+    }
+
+    fn add_discard_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        let hand = self.get_player_hand(color);
+        let total_cards: u8 = hand.iter().sum();
+
+        // If player has fewer cards than the discard limit, they shouldn't be in discard phase
+        // But if they ARE in discard phase, they must discard (this can happen if they had >7 cards
+        // when 7 was rolled, but then other players discarded and affected their hand somehow)
+        if total_cards <= self.config.discard_limit {
+            // This shouldn't normally happen, but if it does, treat as no discard needed
+            log::debug!(
+                "Player {} in discard phase but only has {} cards (limit: {})",
+                color,
+                total_cards,
+                self.config.discard_limit
+            );
+            out.push(Action::EndTurn { color });
+            return;
+        }
+
+        // Any combination of the right size may be discarded; listing them all would blow
+        // up the search, so only the suggested one is offered
+        out.push(Action::Discard {
+            color,
+            resources: self.suggested_discard(color),
+        });
+    }
+
+    /// A gold field card of any resource the bank still has
+    fn add_gold_possibilities(&self, color: u8, out: &mut Vec<Action>) {
+        let bank = self.get_bank_resources();
+        out.extend(
+            (0..5)
+                .filter(|&resource| bank[resource as usize] > 0)
+                .map(|resource| Action::ChooseGold { color, resource }),
+        );
+    }
+
+    /// Cards `color` gives up on a 7: half the hand, rounded down in the player's favor.
+    /// Rules revisions before 3 rounded up.
+    pub fn discard_count(&self, color: u8) -> u8 {
+        let total_cards: u8 = self.get_player_hand(color).iter().sum();
+        if self.rules().discards_round_down() {
+            total_cards / 2
+        } else {
+            total_cards - total_cards / 2
+        }
+    }
+
+    /// The discard offered to `color`: one card at a time from whichever resources it
+    /// holds most of, keeping the hand as diverse as possible
+    pub fn suggested_discard(&self, color: u8) -> FreqDeck {
+        let mut remaining_hand: FreqDeck = [0; 5];
+        remaining_hand.copy_from_slice(self.get_player_hand(color));
+        let mut to_discard = self.discard_count(color);
+        let mut discarded = [0u8; 5];
+
+        while to_discard > 0 {
+            // Take one card from each highest frequency resource
+            let max_count = *remaining_hand.iter().max().unwrap();
+            for i in 0..5 {
+                if to_discard > 0 && remaining_hand[i] == max_count {
+                    remaining_hand[i] -= 1;
+                    discarded[i] += 1;
+                    to_discard -= 1;
+                }
+            }
+        }
+        discarded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Resource, StateEdit};
+    use rand::seq::SliceRandom;
+    use std::collections::HashSet;
+
+    fn find_port_node_by_type(state: &State, resource: Option<Resource>) -> Option<u8> {
+        state
+            .map_instance
+            .get_port_nodes()
+            .iter()
+            .find_map(|(&node_id, &port_resource)| {
+                if port_resource == resource {
+                    Some(node_id)
+                } else {
+                    None
+                }
+            })
+    }
+
+    #[test]
+    fn test_move_generation() {
+        let state = State::new_base();
+        let actions = state.generate_playable_actions();
+        assert_eq!(actions.len(), 54);
+        assert!(matches!(actions[0], Action::BuildSettlement { .. }));
+    }
+
+    #[test]
+    fn test_generate_into_reuses_buffer() {
+        let mut state = State::new_base();
+        let mut rng = rand::thread_rng();
+        let mut buffer = vec![Action::EndTurn { color: 9 }];
+        for _ in 0..300 {
+            if state.winner().is_some() {
+                break;
+            }
+            state.generate_playable_actions_into(&mut buffer);
+            let fresh = state.generate_playable_actions();
+            assert_eq!(buffer.len(), fresh.len());
+            assert!(fresh.iter().all(|action| buffer.contains(action)));
+
+            let color = state.get_current_color();
+            let edges = state.board_buildable_edges(color);
+            let unique: HashSet<_> = edges.iter().collect();
+            assert_eq!(unique.len(), edges.len());
+
+            let action = *fresh.choose(&mut rng).unwrap();
+            state.apply_action(action);
+        }
+    }
+
+    #[test]
+    fn test_settlement_possibilities() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Test initial build phase
+        let initial_build_phase_actions = state.settlement_possibilities(color, true);
+        assert_eq!(initial_build_phase_actions.len(), 54);
+
+        // Give player resources
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([5; 5]),
+        });
+
+        let action = Action::BuildSettlement { color, node_id: 0 };
+        state.apply_action(action);
+
+        let action = Action::BuildRoad {
+            color,
+            edge_id: (0, 1),
+        };
+        state.apply_action(action);
+
+        let actions = state.settlement_possibilities(0, false);
+        assert_eq!(actions.len(), 0);
+
+        let action = Action::BuildRoad {
+            color,
+            edge_id: (1, 2),
+        };
+        state.apply_action(action);
+
+        // Should be able to build at node 2
+        let actions = state.settlement_possibilities(color, false);
+        assert_eq!(actions.len(), 1);
+        assert!(actions.iter().any(|action| {
+            matches!(action, Action::BuildSettlement { color: c, node_id } if *c == color && *node_id == 2)
+        }));
+    }
+
+    #[test]
+    fn test_initial_settlement_possibilities() {
+        let state = State::new_base();
+        let curr_color = state.get_current_color();
+        let actions = state.settlement_possibilities(curr_color, true);
+        assert_eq!(actions.len(), 54); // All nodes should be buildable
+
+        for action in actions {
+            match action {
+                Action::BuildSettlement { color, .. } => assert_eq!(color, curr_color),
+                _ => panic!("Expected BuildSettlement action"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_initial_road_possibilities() {
+        let mut state = State::new_base();
+        let curr_color = state.get_current_color();
+
+        state.build_settlement(curr_color, 0);
+
+        let actions = state.initial_road_possibilities(curr_color);
+        assert_eq!(actions.len(), 3);
+
+        for action in actions {
+            match action {
+                Action::BuildRoad { color, edge_id } => {
+                    assert_eq!(color, curr_color);
+                    assert!(edge_id.0 == 0 || edge_id.1 == 0);
+                }
+                _ => panic!("Expected BuildRoad action"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_settlement_blocks_neighbors() {
+        let mut state = State::new_base();
+        let curr_color = state.get_current_color();
+
+        state.build_settlement(curr_color, 0);
+
+        let actions = state.settlement_possibilities(curr_color, true);
+        let neighbors = state.map_instance.get_neighbor_nodes(0);
+
+        for action in actions {
+            match action {
+                Action::BuildSettlement { color: _, node_id } => {
+                    assert_ne!(node_id, 0);
+                    assert!(!neighbors.contains(&node_id));
+                }
+                _ => panic!("Expected BuildSettlement action"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_play_turn_initial_possibilities() {
+        let mut state = State::new_base();
+
+        assert!(state.is_initial_build_phase());
+        assert!(matches!(
+            state.get_action_prompt(),
+            ActionPrompt::BuildInitialSettlement
+        ));
+
+        let actions = state.generate_playable_actions();
+        match &actions[0] {
+            Action::BuildSettlement { .. } => (),
+            _ => panic!("Expected BuildSettlement action to be first action"),
+        }
+        state.apply_action(actions[0]);
+
+        assert!(matches!(
+            state.get_action_prompt(),
+            ActionPrompt::BuildInitialRoad
+        ));
+    }
+
+    #[test]
+    fn test_robber_possibilities() {
+        let mut state = State::new_base();
+        let color1 = 1;
+        let color2 = 2;
+
+        state.build_settlement(color2, 0);
+
+        // Give resources to color2
+        state.apply_action(Action::DebugSetState {
+            color: color2,
+            edit: StateEdit::Hand([0, 1, 0, 0, 0]),
+        });
+
+        let actions = state.robber_possibilities(color1);
+
+        // Should be able to move robber to any land tile except current location
+        let num_land_tiles = state.map_instance.get_land_tiles().len();
+        assert_eq!(actions.len(), num_land_tiles - 1);
+
+        // Count how many actions involve stealing
+        let steal_actions_count = actions.iter().filter(|action| {
+            matches!(action, Action::MoveRobber { victim_opt: Some(v), .. } if *v == color2)
+        }).count();
+
+        // Node 0 is connected to 3 tiles, but one might be the robber's current location
+        assert!(
+            steal_actions_count == 2 || steal_actions_count == 3,
+            "Should have 2 or 3 tiles where stealing is possible"
+        );
+
+        // Verify can't steal from player with no resources
+        state.apply_action(Action::DebugSetState {
+            color: color2,
+            edit: StateEdit::Hand([0, 0, 0, 0, 0]),
+        });
+        let actions = state.robber_possibilities(color1);
+        assert_eq!(actions.len(), num_land_tiles - 1); // All tiles except current
+
+        // Verify no stealing actions when victim has no resources
+        let steal_actions_count = actions
+            .iter()
+            .filter(|action| {
+                matches!(
+                    action,
+                    Action::MoveRobber {
+                        victim_opt: Some(_),
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(
+            steal_actions_count, 0,
+            "Should have no stealing actions when victim has no resources"
+        );
+    }
+
+    fn state_with_robber_rules(rules: crate::enums::RobberRules) -> State {
+        let mut state = State::new_base();
+        let mut config = (*state.config).clone();
+        config.robber = rules;
+        state.config = std::sync::Arc::new(config);
+        state
+    }
+
+    fn robber_tiles_and_victims(actions: &[Action]) -> (HashSet<(i8, i8, i8)>, HashSet<u8>) {
+        let mut tiles = HashSet::new();
+        let mut victims = HashSet::new();
+        for action in actions {
+            if let Action::MoveRobber {
+                coordinate,
+                victim_opt,
+                ..
+            } = action
+            {
+                tiles.insert(*coordinate);
+                victims.extend(*victim_opt);
+            }
+        }
+        (tiles, victims)
+    }
+
+    #[test]
+    fn test_friendly_robber_protects_low_vp_players() {
+        let mut state = state_with_robber_rules(crate::enums::RobberRules::friendly());
+        state.build_settlement(2, 0);
+        state.apply_action(Action::DebugSetState {
+            color: 2,
+            edit: StateEdit::Hand([0, 1, 0, 0, 0]),
+        });
+        let protected_tiles: HashSet<_> = state
+            .map_instance
+            .get_land_tiles()
+            .iter()
+            .filter(|(_, tile)| tile.hexagon.nodes.values().any(|&n| n == 0))
+            .map(|(coordinate, _)| *coordinate)
+            .collect();
+
+        let (tiles, victims) = robber_tiles_and_victims(&state.robber_possibilities(1));
+        assert!(victims.is_empty());
+        assert!(tiles.is_disjoint(&protected_tiles));
+
+        // Hidden victory point cards don't lift the protection
+        state.add_victory_points(2, 2);
+        let (_, victims) = robber_tiles_and_victims(&state.robber_possibilities(1));
+        assert!(victims.is_empty());
+
+        // Above the threshold in public points the player is fair game again
+        state.build_settlement(2, 20);
+        state.build_settlement(2, 40);
+        let (_, victims) = robber_tiles_and_victims(&state.robber_possibilities(1));
+        assert!(victims.contains(&2));
+    }
+
+    #[test]
+    fn test_no_steal_robber_only_blocks() {
+        let mut state = state_with_robber_rules(crate::enums::RobberRules {
+            allow_steal: false,
+            ..Default::default()
+        });
+        state.build_settlement(2, 0);
+        state.apply_action(Action::DebugSetState {
+            color: 2,
+            edit: StateEdit::Hand([0, 3, 0, 0, 0]),
+        });
+
+        let actions = state.robber_possibilities(1);
+        let (tiles, victims) = robber_tiles_and_victims(&actions);
+        assert!(victims.is_empty());
+        assert_eq!(tiles.len(), state.map_instance.get_land_tiles().len() - 1);
+    }
+
+    #[test]
+    fn test_robber_falls_back_to_desert() {
+        let mut state = state_with_robber_rules(crate::enums::RobberRules {
+            protect_up_to_vps: Some(2),
+            allow_desert: false,
+            allow_steal: true,
+        });
+        // Move the robber off the desert, then surround every producing tile with a
+        // protected player's buildings so no regular placement remains
+        let desert = state
+            .map_instance
+            .get_land_tiles()
+            .iter()
+            .find(|(_, tile)| tile.resource.is_none())
+            .map(|(coordinate, tile)| (*coordinate, tile.id))
+            .unwrap();
+        let other_tile = state
+            .map_instance
+            .get_land_tiles()
+            .iter()
+            .find(|(_, tile)| tile.resource.is_some())
+            .map(|(coordinate, _)| *coordinate)
+            .unwrap();
+        state.apply_action(Action::DebugSetState {
+            color: 1,
+            edit: StateEdit::Robber(other_tile),
+        });
+        let producing: Vec<_> = state
+            .map_instance
+            .get_land_tiles()
+            .values()
+            .filter(|tile| tile.resource.is_some())
+            .map(|tile| tile.hexagon.nodes.values().copied().collect::<Vec<_>>())
+            .collect();
+        for nodes in producing {
+            for node_id in nodes {
+                state
+                    .buildings
+                    .insert(node_id, Building::Settlement(2, node_id));
+            }
+        }
+
+        let (tiles, victims) = robber_tiles_and_victims(&state.robber_possibilities(1));
+        assert_eq!(tiles, HashSet::from([desert.0]));
+        assert!(victims.is_empty());
+    }
+
+    #[test]
+    fn test_robber_cant_stay_in_place() {
+        let state = State::new_base();
+        let color = state.get_current_color();
+        let actions = state.robber_possibilities(color);
+
+        // Get current robber tile
+        let current_robber_tile = state.get_robber_tile();
+
+        // Verify no action tries to move robber to current location
+        assert!(actions.iter().all(|action| {
+            if let Action::MoveRobber { coordinate, .. } = action {
+                state.map_instance.get_land_tile(*coordinate).unwrap().id != current_robber_tile
+            } else {
+                false
+            }
+        }));
+    }
+
+    #[test]
+    fn test_year_of_plenty_possibilities() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Give player a Year of Plenty card
+        state.add_dev_card(color, DevCard::YearOfPlenty as usize);
+
+        // Test with full bank - should have 15 actions:
+        // - 5 same-resource actions (Wood+Wood, Brick+Brick, etc.)
+        // - 10 different-resource combinations (5 choose 2)
+        let actions = state.year_of_plenty_possibilities(color);
+        assert_eq!(actions.len(), 15);
+
+        // Test with empty bank - should have 0 actions
+        for i in 0..5 {
+            // 5 resource types
+            state.set_bank_resource(i, 0);
+        }
+        let actions = state.year_of_plenty_possibilities(color);
+        assert_eq!(actions.len(), 0);
+
+        // Test with only 2 ORE available
+        state.set_bank_resource(4, 2);
+        let actions = state.year_of_plenty_possibilities(color);
+        assert_eq!(actions.len(), 1); // Can only take ORE+ORE
+        assert!(matches!(
+            actions[0],
+            Action::PlayYearOfPlenty {
+                resources: (4, Some(4)),
+                ..
+            }
+        ));
+
+        // Test with only 1 WHEAT available
+        for i in 0..5 {
+            state.set_bank_resource(i, 0);
+        }
+        state.set_bank_resource(3, 1);
+        let actions = state.year_of_plenty_possibilities(color);
+        assert_eq!(actions.len(), 1); // Can take just one WHEAT when that's all that's available
+        assert!(matches!(
+            actions[0],
+            Action::PlayYearOfPlenty {
+                resources: (3, None),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_discard_possibilities() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Give player 8 cards (above discard limit)
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([8, 0, 0, 0, 0]),
+        });
+
+        let actions = state.discard_possibilities(color);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0],
+            Action::Discard {
+                color,
+                resources: [4, 0, 0, 0, 0]
+            }
+        );
+
+        // Test with 7 cards (at discard limit)
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([7, 0, 0, 0, 0]),
+        });
+        let actions = state.discard_possibilities(color);
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], Action::EndTurn { color: c } if c == color));
+    }
+
+    #[test]
+    fn test_maritime_trade_possibilities() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Give player 4 wood (enough for 4:1 trade)
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([4, 0, 0, 0, 0]),
+        });
+
+        let actions = state.maritime_trade_possibilities(color);
+
+        // Should be able to trade 4 wood for any other resource
+        assert_eq!(actions.len(), 4); // Can trade for brick, sheep, wheat, ore
+        assert!(actions.iter().all(|action| matches!(
+            action,
+            Action::MaritimeTrade {
+                color: c,
+                give,
+                take,
+                ratio
+            } if *c == color && *give == 0 && *ratio == 4 && *take != 0
+        )));
+
+        // Test with insufficient resources
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([3, 0, 0, 0, 0]),
+        });
+        let actions = state.maritime_trade_possibilities(color);
+        assert_eq!(actions.len(), 0);
+    }
+
+    #[test]
+    fn test_maritime_trade_with_empty_bank() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Give player resources for trading
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([4, 0, 0, 0, 0]),
+        });
+
+        // Empty the bank
+        for i in 0..5 {
+            state.set_bank_resource(i, 0);
+        }
+
+        let actions = state.maritime_trade_possibilities(color);
+        assert_eq!(
+            actions.len(),
+            0,
+            "Should not be able to trade with empty bank"
+        );
+    }
+
+    #[test]
+    fn test_maritime_trade_with_two_to_one_port() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Find a 2:1 wood port node
+        let wood_port_node = find_port_node_by_type(&state, Some(Resource::Wood)).unwrap();
+
+        // Build settlement at wood port
+        state.build_settlement(color, wood_port_node);
+
+        // Give player 2 wood (enough for 2:1 trade)
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([2, 0, 0, 0, 0]),
+        });
+
+        let actions = state.maritime_trade_possibilities(color);
+        assert!(
+            actions.iter().any(|action| matches!(
+                action,
+                Action::MaritimeTrade {
+                    color: c,
+                    give,
+                    take,
+                    ratio
+                } if *c == color && *give == 0 && *ratio == 2 && *take != 0
+            )),
+            "Should be able to use 2:1 wood port"
+        );
+    }
+
+    #[test]
+    fn test_maritime_trade_with_three_to_one_port() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+
+        // Find a 3:1 port node
+        let port_node = find_port_node_by_type(&state, None).unwrap();
+
+        // Build settlement at 3:1 port
+        state.build_settlement(color, port_node);
+
+        // Give player 3 brick (enough for 3:1 trade)
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([0, 3, 0, 0, 0]),
+        });
+
+        let actions = state.maritime_trade_possibilities(color);
+        assert!(
+            actions.iter().any(|action| matches!(
+                action,
+                Action::MaritimeTrade {
+                    color: c,
+                    give,
+                    take,
+                    ratio
+                } if *c == color && *give == 1 && *ratio == 3 && *take != 0
+            )),
+            "Should be able to use 3:1 port for brick"
+        );
+    }
+
+    #[test]
+    fn test_initial_placement_violations() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        let settle = |color, node_id| Action::BuildSettlement { color, node_id };
+        assert!(state
+            .initial_placement_violation(&settle(color, 0))
+            .is_none());
+        assert!(state
+            .initial_placement_violation(&settle(color, 200))
+            .is_some());
+        assert!(state
+            .initial_placement_violation(&settle((color + 1) % 4, 0))
+            .is_some());
+        assert!(state
+            .initial_placement_violation(&Action::EndTurn { color })
+            .is_some());
+
+        state.apply_action(settle(color, 0));
+        let far_edge = state
+            .map_instance
+            .get_neighbor_edges(30)
+            .first()
+            .copied()
+            .unwrap();
+        let own_edge = state.map_instance.get_neighbor_edges(0)[0];
+        let road = |edge_id| Action::BuildRoad { color, edge_id };
+        assert!(state.initial_placement_violation(&road(far_edge)).is_some());
+        assert!(state
+            .initial_placement_violation(&settle(color, 30))
+            .is_some());
+        assert!(state.initial_placement_violation(&road(own_edge)).is_none());
+
+        // The next seat may not crowd the first settlement
+        state.apply_action(road(own_edge));
+        let next = state.get_current_color();
+        let neighbor = state.map_instance.get_neighbor_nodes(0)[0];
+        assert!(state
+            .initial_placement_violation(&settle(next, 0))
+            .is_some());
+        assert!(state
+            .initial_placement_violation(&settle(next, neighbor))
+            .is_some());
+        assert!(state
+            .initial_placement_violation(&settle(next, 30))
+            .is_none());
+    }
+
+    #[test]
+    fn test_owned_ports_list_each_port_type_once() {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        assert!(state.get_owned_ports(color).is_empty());
+
+        // Both nodes of the wood port, plus a 3:1 port
+        let wood_nodes: Vec<u8> = state
+            .map_instance
+            .get_port_nodes()
+            .iter()
+            .filter(|(_, &resource)| resource == Some(Resource::Wood))
+            .map(|(&node_id, _)| node_id)
+            .collect();
+        for node_id in wood_nodes {
+            state.build_settlement(color, node_id);
+        }
+        state.build_settlement(color, find_port_node_by_type(&state, None).unwrap());
+
+        assert_eq!(
+            state.get_owned_ports(color),
+            vec![None, Some(Resource::Wood)]
+        );
+        assert!(state.get_owned_ports((color + 1) % 4).is_empty());
+    }
+
+    #[test]
+    fn test_road_connectivity_enforcement() {
+        let mut state = State::new_base();
+        let color = 0;
+
+        // Give player resources for road building
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([5; 5]),
+        });
+
+        // Initially, player has no settlements or roads, so no roads should be buildable
+        let actions = state.road_possibilities(color, false);
+        assert_eq!(
+            actions.len(),
+            0,
+            "No roads should be buildable without settlements"
+        );
+
+        // Build a settlement at node 0
+        state.apply_action(Action::BuildSettlement { color, node_id: 0 });
+
+        // Now roads adjacent to the settlement should be buildable
+        let actions = state.road_possibilities(color, false);
+        assert!(
+            !actions.is_empty(),
+            "Roads adjacent to settlement should be buildable"
+        );
+
+        // Verify all buildable roads are actually adjacent to node 0
+        let node_0_edges = state.map_instance.get_neighbor_edges(0).to_vec();
+        // Normalize edge ordering for comparison (since edges can be stored as (a,b) or (b,a))
+        let normalized_node_0_edges: std::collections::HashSet<_> = node_0_edges
+            .iter()
+            .map(|&edge| (edge.0.min(edge.1), edge.0.max(edge.1)))
+            .collect();
+
+        for action in &actions {
+            if let Action::BuildRoad { edge_id, .. } = action {
+                let normalized_edge = (edge_id.0.min(edge_id.1), edge_id.0.max(edge_id.1));
+                assert!(
+                    normalized_node_0_edges.contains(&normalized_edge),
+                    "Road {edge_id:?} should be adjacent to settlement at node 0"
+                );
+            }
+        }
+
+        // Build the first road
+        let first_road = actions[0];
+        if let Action::BuildRoad {
+            edge_id: first_edge,
+            ..
+        } = first_road
+        {
+            state.apply_action(first_road);
+
+            // Now roads should be buildable adjacent to existing roads OR settlements
+            let new_actions = state.road_possibilities(color, false);
+            assert!(
+                new_actions.len() >= actions.len(),
+                "Should have at least as many options after building first road"
+            );
+
+            // Verify that each new road is adjacent to either the settlement or the existing road
+            for action in &new_actions {
+                if let Action::BuildRoad { edge_id, .. } = action {
+                    let is_adjacent_to_settlement = node_0_edges.contains(edge_id);
+                    let is_adjacent_to_first_road = edge_id.0 == first_edge.0
+                        || edge_id.0 == first_edge.1
+                        || edge_id.1 == first_edge.0
+                        || edge_id.1 == first_edge.1;
+
+                    assert!(
+                        is_adjacent_to_settlement || is_adjacent_to_first_road,
+                        "Road {edge_id:?} should be adjacent to either settlement at node 0 or first road {first_edge:?}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Turn order at the start of a game (see `Seating`). Deciding it draws on the game's
+// seeded RNG, so rebuilding a game from its seed lands on the same order and rolls.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_xorshift::XorShiftRng;
+
+use super::State;
+use crate::enums::Seating;
+
+/// A color and the two dice it rolled while deciding who goes first
+pub type OpeningRoll = (u8, (u8, u8));
+
+/// Colors in turn order, plus the opening rolls that decided it (only when rolling for
+/// first). `Fixed` seating leaves the RNG untouched, so such games keep their dice.
+pub(super) fn seat_order(
+    seating: &Seating,
+    num_players: u8,
+    rng: &mut XorShiftRng,
+) -> (Vec<u8>, Vec<OpeningRoll>) {
+    let mut colors: Vec<u8> = (0..num_players).collect();
+    match seating {
+        Seating::Fixed => (colors, Vec::new()),
+        Seating::Shuffled => {
+            colors.shuffle(rng);
+            (colors, Vec::new())
+        }
+        Seating::Explicit(order) => (order.clone(), Vec::new()),
+        Seating::RollForFirst => {
+            let mut rolls = Vec::new();
+            let mut contenders = colors.clone();
+            while contenders.len() > 1 {
+                let round: Vec<OpeningRoll> = contenders
+                    .iter()
+                    .map(|&color| (color, (rng.gen_range(1..=6), rng.gen_range(1..=6))))
+                    .collect();
+                let best = round.iter().map(|&(_, (a, b))| a + b).max().unwrap_or(0);
+                contenders = round
+                    .iter()
+                    .filter(|&&(_, (a, b))| a + b == best)
+                    .map(|&(color, _)| color)
+                    .collect();
+                rolls.extend(round);
+            }
+            colors.rotate_left(contenders[0] as usize);
+            (colors, rolls)
+        }
+    }
+}
+
+impl State {
+    /// Dice rolled to decide who goes first, in the order they were rolled
+    pub fn get_opening_rolls(&self) -> &[OpeningRoll] {
+        &self.opening_rolls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::GameConfiguration;
+    use crate::map_instance::MapInstance;
+    use std::sync::Arc;
+
+    fn seated_state(seating: Seating, seed: u64) -> State {
+        let config = GameConfiguration::builder()
+            .seating(seating)
+            .build()
+            .unwrap();
+        let map_instance = MapInstance::shared(&config.map_type, 0);
+        State::new_with_seed(Arc::new(config), map_instance, seed)
+    }
+
+    #[test]
+    fn test_fixed_and_explicit_orders() {
+        let fixed = seated_state(Seating::Fixed, 7);
+        assert_eq!(fixed.get_seating_order(), &[0, 1, 2, 3]);
+        assert!(fixed.get_opening_rolls().is_empty());
+
+        let explicit = seated_state(Seating::Explicit(vec![2, 0, 3, 1]), 7);
+        assert_eq!(explicit.get_seating_order(), &[2, 0, 3, 1]);
+        assert_eq!(explicit.get_current_color(), 2);
+    }
+
+    #[test]
+    fn test_shuffle_follows_the_seed() {
+        let orders: Vec<Vec<u8>> = (0..20)
+            .map(|seed| {
+                seated_state(Seating::Shuffled, seed)
+                    .get_seating_order()
+                    .to_vec()
+            })
+            .collect();
+        assert!(orders.iter().any(|order| order != &[0, 1, 2, 3]));
+        assert_eq!(
+            seated_state(Seating::Shuffled, 3).get_seating_order(),
+            orders[3].as_slice()
+        );
+    }
+
+    #[test]
+    fn test_highest_opening_roll_goes_first() {
+        for seed in 0..20 {
+            let state = seated_state(Seating::RollForFirst, seed);
+            let rolls = state.get_opening_rolls();
+            let order = state.get_seating_order();
+            assert!(rolls.len() >= 4);
+
+            // Everyone rolls once; only a tie for the highest total rolls again
+            let total = |&(_, (a, b)): &OpeningRoll| a + b;
+            let starter = order[0];
+            let best = rolls[..4].iter().map(total).max().unwrap();
+            let leaders = rolls[..4].iter().filter(|roll| total(roll) == best).count();
+            let starter_first_roll = rolls.iter().find(|&&(color, _)| color == starter);
+            assert_eq!(starter_first_roll.map(total), Some(best));
+            assert_eq!(rolls.len() == 4, leaders == 1);
+            // Play continues in color order from the starter
+            assert!(order.windows(2).all(|pair| pair[1] == (pair[0] + 1) % 4));
+            assert_eq!(state.get_current_color(), starter);
+        }
+    }
+}
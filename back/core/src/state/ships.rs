@@ -0,0 +1,248 @@
+// Seafarers ships. A ship sits on a sea edge and extends its owner's network like a
+// road: ships chain from a coastal building or from another ship, and a settlement may
+// go where a ship ends. Roads and ships only join at a building of their owner. Once
+// per turn, a player may move the ship at the open end of a shipping route, unless it
+// was built that turn.
+
+use std::collections::HashMap;
+
+use super::State;
+use crate::map_instance::{EdgeId, NodeId};
+
+pub(super) const TOTAL_SHIPS_PER_PLAYER: u8 = 15;
+
+impl State {
+    /// Ships on the board, by (low, high) edge
+    pub fn get_ships(&self) -> &HashMap<EdgeId, u8> {
+        &self.ships
+    }
+
+    pub fn get_ship_owner(&self, edge_id: EdgeId) -> Option<u8> {
+        let (a, b) = edge_id;
+        self.ships.get(&(a.min(b), a.max(b))).copied()
+    }
+
+    /// Ships `color` has built, per color
+    pub fn get_ships_by_color(&self) -> &[u8] {
+        &self.ships_by_color
+    }
+
+    /// Ships left in `color`'s supply
+    pub fn get_ships_left(&self, color: u8) -> u8 {
+        TOTAL_SHIPS_PER_PLAYER - self.ships_by_color[color as usize]
+    }
+
+    pub fn get_ships_for_color(&self, color: u8) -> Vec<EdgeId> {
+        self.ships
+            .iter()
+            .filter_map(|(&edge, &owner)| (owner == color).then_some(edge))
+            .collect()
+    }
+
+    /// Whether the board has sea edges for ships
+    pub fn has_ships(&self) -> bool {
+        !self.map_instance.sea_edges.is_empty()
+    }
+
+    /// Whether `color` has a ship touching `node_id`
+    pub(super) fn touches_own_ship(&self, color: u8, node_id: NodeId) -> bool {
+        self.map_instance
+            .get_sea_edges(node_id)
+            .iter()
+            .any(|&edge| self.ships.get(&edge) == Some(&color))
+    }
+
+    /// Empty sea edges where `color` could put a ship, each once and in ascending order.
+    /// `without` leaves one of their ships out, for the destinations of a move.
+    pub(crate) fn buildable_sea_edges(&self, color: u8, without: Option<EdgeId>) -> Vec<EdgeId> {
+        let mut edges: Vec<EdgeId> = self
+            .ship_harbors(color, without)
+            .flat_map(|node| self.map_instance.get_sea_edges(node).iter().copied())
+            .filter(|&edge| {
+                Some(edge) != without
+                    && !self.ships.contains_key(&edge)
+                    && !self.roads.contains_key(&edge)
+            })
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+
+    /// Nodes a ship of `color` may leave from: their coastal buildings, and the ends of
+    /// their ships short of an opponent's building
+    fn ship_harbors(
+        &self,
+        color: u8,
+        without: Option<EdgeId>,
+    ) -> impl Iterator<Item = NodeId> + '_ {
+        let buildings = self
+            .buildings_by_color
+            .get(&color)
+            .into_iter()
+            .flatten()
+            .map(|building| match *building {
+                super::Building::Settlement(_, node) | super::Building::City(_, node) => node,
+            });
+        let ship_ends = self
+            .ships
+            .iter()
+            .filter(move |&(&edge, &owner)| owner == color && Some(edge) != without)
+            .flat_map(|(&(a, b), _)| [a, b])
+            .filter(move |&node| !self.is_enemy_node(color, node));
+        buildings.chain(ship_ends)
+    }
+
+    /// Nodes touched by `color`'s ships, where a settlement may connect
+    pub(super) fn ship_network_nodes(&self, color: u8) -> impl Iterator<Item = NodeId> + '_ {
+        self.ships
+            .iter()
+            .filter(move |&(_, &owner)| owner == color)
+            .flat_map(|(&(a, b), _)| [a, b])
+    }
+
+    /// Ships `color` may move this turn: at the open end of a route (no building of
+    /// theirs and no other ship of theirs at one end), and not built this turn
+    pub(crate) fn movable_ships(&self, color: u8) -> Vec<EdgeId> {
+        if self.ship_moved_this_turn {
+            return Vec::new();
+        }
+        let mut ships: Vec<EdgeId> = self
+            .ships
+            .iter()
+            .filter(|&(edge, &owner)| owner == color && !self.ships_built_this_turn.contains(edge))
+            .map(|(&edge, _)| edge)
+            .filter(|&(a, b)| {
+                [a, b].into_iter().any(|node| {
+                    self.get_node_color(node) != Some(color)
+                        && !self
+                            .map_instance
+                            .get_sea_edges(node)
+                            .iter()
+                            .any(|&other| other != (a, b) && self.ships.get(&other) == Some(&color))
+                })
+            })
+            .collect();
+        ships.sort_unstable();
+        ships
+    }
+
+    /// Whether `color` already moved a ship this turn
+    pub fn ship_moved_this_turn(&self) -> bool {
+        self.ship_moved_this_turn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, ActionPrompt, GameConfiguration, MapType, StateEdit};
+    use crate::map_instance::MapInstance;
+    use std::sync::Arc;
+
+    /// Seafarers game past the opening with one settlement of the player to move on the
+    /// east coast of the main island, rolled and with cards for a few ships
+    fn coastal_state() -> (State, u8, NodeId) {
+        let config = GameConfiguration {
+            map_type: MapType::Seafarers,
+            ..GameConfiguration::default()
+        };
+        let map = MapInstance::shared(&config.map_type, 0);
+        let mut state = State::new_with_seed(Arc::new(config), map, 0);
+        let color = state.get_current_color();
+        // Easternmost main-island node that touches the sea
+        let node = *state
+            .map_instance
+            .land_nodes()
+            .iter()
+            .filter(|&&node| node < 54 && !state.map_instance.get_sea_edges(node).is_empty())
+            .max_by_key(|&&node| {
+                let tiles = state.map_instance.get_adjacent_tiles(node).unwrap();
+                tiles
+                    .iter()
+                    .map(|tile| {
+                        state
+                            .map_instance
+                            .get_land_tiles()
+                            .iter()
+                            .find(|(_, other)| other.id == tile.id)
+                            .map_or(0, |(coordinate, _)| coordinate.0)
+                    })
+                    .max()
+            })
+            .unwrap();
+        state.apply_action(Action::BuildSettlement {
+            color,
+            node_id: node,
+        });
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::HasRolled(true),
+            StateEdit::Hand([5, 0, 5, 0, 0]),
+        ] {
+            state.apply_action(Action::DebugSetState { color, edit });
+        }
+        (state, color, node)
+    }
+
+    #[test]
+    fn test_ships_chain_from_the_coast_and_move_from_the_open_end() {
+        let (mut state, color, node) = coastal_state();
+        assert_eq!(state.get_action_prompt(), ActionPrompt::PlayTurn);
+        let first = state.buildable_sea_edges(color, None);
+        assert!(!first.is_empty());
+        assert!(first.iter().all(|&(a, b)| a == node || b == node));
+        let actions = state.generate_playable_actions();
+        for &edge_id in &first {
+            assert!(actions.contains(&Action::BuildShip { color, edge_id }));
+        }
+
+        let ship = first[0];
+        assert_eq!(state.get_ships_left(color), TOTAL_SHIPS_PER_PLAYER);
+        state.apply_action(Action::BuildShip {
+            color,
+            edge_id: ship,
+        });
+        assert_eq!(state.get_ship_owner(ship), Some(color));
+        assert_eq!(state.get_ships_left(color), TOTAL_SHIPS_PER_PLAYER - 1);
+        assert_eq!(state.get_player_hand(color), [4, 0, 4, 0, 0]);
+        let far_end = if ship.0 == node { ship.1 } else { ship.0 };
+        assert!(state
+            .buildable_sea_edges(color, None)
+            .iter()
+            .any(|&(a, b)| (a == far_end || b == far_end) && (a, b) != ship));
+        // Built this turn, so it stays put until the next one
+        assert!(state.movable_ships(color).is_empty());
+
+        state.ships_built_this_turn.clear();
+        assert_eq!(state.movable_ships(color), vec![ship]);
+        let to = *state
+            .buildable_sea_edges(color, Some(ship))
+            .iter()
+            .find(|&&edge| edge != ship)
+            .unwrap();
+        let move_ship = Action::MoveShip {
+            color,
+            from: ship,
+            to,
+        };
+        assert!(state.generate_playable_actions().contains(&move_ship));
+        assert_eq!(state.validate_action(&move_ship), Ok(()));
+        state.apply_action(move_ship);
+        assert_eq!(state.get_ship_owner(ship), None);
+        assert_eq!(state.get_ship_owner(to), Some(color));
+        assert_eq!(state.get_ships_left(color), TOTAL_SHIPS_PER_PLAYER - 1);
+        assert!(state.movable_ships(color).is_empty());
+        assert!(!state
+            .generate_playable_actions()
+            .iter()
+            .any(|action| matches!(action, Action::MoveShip { .. })));
+    }
+
+    #[test]
+    fn test_boards_without_sea_offer_no_ships() {
+        let state = State::new_base();
+        assert!(!state.has_ships());
+        assert!(state.buildable_sea_edges(0, None).is_empty());
+    }
+}
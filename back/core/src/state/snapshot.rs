@@ -0,0 +1,307 @@
+// Serde support for State, for persistence, remote bots and snapshot-based undo. Only
+// what can't be derived is written: the configuration by value, the map by its seed
+// (the map type is in the configuration), the vector, the pieces on the board, the award
+// holders and the RNG. Board caches (buildable nodes, road networks and lengths) are
+// rebuilt on load by placing the pieces again.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::Arc;
+
+use super::{seating::OpeningRoll, trading::DomesticTrade, variants::EventCard, Building, State};
+use crate::enums::GameConfiguration;
+use crate::map_instance::{EdgeId, MapInstance};
+use crate::state_vector::{
+    IS_INITIAL_BUILD_PHASE_INDEX, PLAYER_BOUGHT_DEVCARDS_SIZE, PLAYER_PIECES_LEFT_SIZE,
+};
+use rand_xorshift::XorShiftRng;
+
+#[derive(Serialize, Deserialize)]
+struct StateSnapshot {
+    config: GameConfiguration,
+    map_seed: u64,
+    vector: Vec<u8>,
+    /// Per color, in build order
+    buildings: Vec<Building>,
+    roads: Vec<(u8, EdgeId)>,
+    #[serde(default)]
+    ships: Vec<(u8, EdgeId)>,
+    #[serde(default)]
+    ships_built_this_turn: Vec<EdgeId>,
+    #[serde(default)]
+    ship_moved_this_turn: bool,
+    /// Per color; empty in snapshots from before gold fields
+    #[serde(default)]
+    gold_owed: Vec<u8>,
+    #[serde(default)]
+    gold_roller_seat: u8,
+    longest_road: (Option<u8>, u8),
+    largest_army: (Option<u8>, u8),
+    harbormaster: (Option<u8>, u8),
+    opening_rolls: Vec<OpeningRoll>,
+    last_dice_roll: Option<(u8, u8)>,
+    seed: u64,
+    rng: XorShiftRng,
+    gifted_this_turn: bool,
+    #[serde(default)]
+    domestic_trade: Option<DomesticTrade>,
+    #[serde(default)]
+    trade_offers_this_turn: u8,
+    #[serde(default)]
+    special_build_seat: Option<u8>,
+    event_deck: Vec<EventCard>,
+    dice_bag: Vec<(u8, u8)>,
+}
+
+impl Serialize for State {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut colors: Vec<&u8> = self.buildings_by_color.keys().collect();
+        colors.sort_unstable();
+        let mut roads: Vec<(u8, EdgeId)> = self.roads.iter().map(|(&e, &c)| (c, e)).collect();
+        roads.sort_unstable();
+        let mut ships: Vec<(u8, EdgeId)> = self.ships.iter().map(|(&e, &c)| (c, e)).collect();
+        ships.sort_unstable();
+
+        StateSnapshot {
+            config: (*self.config).clone(),
+            map_seed: self.map_instance.get_seed(),
+            vector: self.vector.clone(),
+            buildings: colors
+                .into_iter()
+                .flat_map(|color| self.buildings_by_color[color].iter().copied())
+                .collect(),
+            roads,
+            ships,
+            ships_built_this_turn: self.ships_built_this_turn.clone(),
+            ship_moved_this_turn: self.ship_moved_this_turn,
+            gold_owed: self.gold_owed.clone(),
+            gold_roller_seat: self.gold_roller_seat,
+            longest_road: (self.longest_road_color, self.longest_road_length),
+            largest_army: (self.largest_army_color, self.largest_army_count),
+            harbormaster: (self.harbormaster_color, self.harbormaster_points),
+            opening_rolls: self.opening_rolls.clone(),
+            last_dice_roll: self.last_dice_roll,
+            seed: self.seed,
+            rng: self.rng.clone(),
+            gifted_this_turn: self.gifted_this_turn,
+            domestic_trade: self.domestic_trade.clone(),
+            trade_offers_this_turn: self.trade_offers_this_turn,
+            special_build_seat: self.special_build_seat,
+            event_deck: self.event_deck.clone(),
+            dice_bag: self.dice_bag.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for State {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let snapshot = StateSnapshot::deserialize(deserializer)?;
+        State::from_snapshot(snapshot).map_err(serde::de::Error::custom)
+    }
+}
+
+impl State {
+    fn from_snapshot(snapshot: StateSnapshot) -> Result<State, String> {
+        let map_instance = MapInstance::shared(&snapshot.config.map_type, snapshot.map_seed);
+        let num_players = snapshot.config.num_players;
+        let mut state = State::new_with_seed(Arc::new(snapshot.config), map_instance, 0);
+        let mut vector = snapshot.vector;
+        // Snapshots from before the pieces-left block end just short of it, and those from
+        // before the bought-this-turn block lack that one too
+        let pieces_left = PLAYER_PIECES_LEFT_SIZE * num_players as usize;
+        let bought = PLAYER_BOUGHT_DEVCARDS_SIZE * num_players as usize;
+        let missing = state.vector.len().checked_sub(vector.len());
+        if missing.is_some_and(|missing| [pieces_left, pieces_left + bought].contains(&missing)) {
+            vector.resize(state.vector.len(), 0);
+        }
+        if vector.len() != state.vector.len() {
+            return Err(format!(
+                "state vector has {} entries, expected {} for {num_players} players",
+                vector.len(),
+                state.vector.len()
+            ));
+        }
+        if let Some(building) = snapshot.buildings.iter().find(|building| {
+            let (Building::Settlement(color, node) | Building::City(color, node)) = **building;
+            color >= num_players || !state.map_instance.land_nodes().contains(&node)
+        }) {
+            return Err(format!("{building:?} is off the board"));
+        }
+        if let Some((color, edge)) = snapshot.roads.iter().find(|&&(color, (a, b))| {
+            let land_edges = &state.map_instance.land_edges;
+            color >= num_players || !(land_edges.contains(&(a, b)) || land_edges.contains(&(b, a)))
+        }) {
+            return Err(format!("road {edge:?} of color {color} is off the board"));
+        }
+        if let Some((color, edge)) = snapshot.ships.iter().find(|&&(color, edge)| {
+            color >= num_players || !state.map_instance.sea_edges.contains(&edge)
+        }) {
+            return Err(format!("ship {edge:?} of color {color} is off the board"));
+        }
+        if !snapshot.gold_owed.is_empty() && snapshot.gold_owed.len() != num_players as usize {
+            return Err(format!(
+                "gold owed to {} colors, expected {num_players}",
+                snapshot.gold_owed.len()
+            ));
+        }
+
+        state.vector[IS_INITIAL_BUILD_PHASE_INDEX] = 0;
+        state.place_pieces(&snapshot.buildings, snapshot.roads, snapshot.ships)?;
+        state.buildings_by_color.clear();
+        for building in snapshot.buildings {
+            let (Building::Settlement(color, _) | Building::City(color, _)) = building;
+            state
+                .buildings_by_color
+                .entry(color)
+                .or_default()
+                .push(building);
+        }
+
+        state.vector = vector;
+        state.recount_pieces_left();
+        (state.longest_road_color, state.longest_road_length) = snapshot.longest_road;
+        (state.largest_army_color, state.largest_army_count) = snapshot.largest_army;
+        (state.harbormaster_color, state.harbormaster_points) = snapshot.harbormaster;
+        state.opening_rolls = snapshot.opening_rolls;
+        state.cached_winner = None;
+        state.last_dice_roll = snapshot.last_dice_roll;
+        state.seed = snapshot.seed;
+        state.rng = snapshot.rng;
+        state.ships_built_this_turn = snapshot.ships_built_this_turn;
+        state.ship_moved_this_turn = snapshot.ship_moved_this_turn;
+        if !snapshot.gold_owed.is_empty() {
+            state.gold_owed = snapshot.gold_owed;
+        }
+        state.gold_roller_seat = snapshot.gold_roller_seat;
+        state.gifted_this_turn = snapshot.gifted_this_turn;
+        state.domestic_trade = snapshot.domestic_trade;
+        state.trade_offers_this_turn = snapshot.trade_offers_this_turn;
+        state.special_build_seat = snapshot.special_build_seat;
+        state.event_deck = snapshot.event_deck;
+        state.dice_bag = snapshot.dice_bag;
+        // The pieces were placed with the robber still on its starting tile
+        state.recount_effective_production();
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, StateEdit};
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_roundtrip_keeps_playing_identically() {
+        let mut state = State::new_with_seed(
+            Arc::new(GameConfiguration::default()),
+            MapInstance::shared(&crate::enums::MapType::Base, 5),
+            11,
+        );
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        for _ in 0..300 {
+            if state.winner().is_some() {
+                break;
+            }
+            let action = *state.generate_playable_actions().choose(&mut rng).unwrap();
+            state.apply_action(action);
+        }
+        // Park the robber where it blocks someone's production
+        let robbed = state
+            .map_instance
+            .get_land_tiles()
+            .iter()
+            .find(|(_, tile)| {
+                tile.resource.is_some()
+                    && tile
+                        .hexagon
+                        .nodes
+                        .values()
+                        .any(|node_id| state.buildings.contains_key(node_id))
+            })
+            .map(|(coordinate, _)| *coordinate)
+            .unwrap();
+        state.apply_action(Action::DebugSetState {
+            color: state.get_current_color(),
+            edit: StateEdit::Robber(robbed),
+        });
+
+        let json = serde_json::to_string(&state).unwrap();
+        let mut restored: State = serde_json::from_str(&json).unwrap();
+        // Offered actions are compared as sets below
+        let dump = |state: &State| {
+            let mut dump = serde_json::to_value(state.debug_dump()).unwrap();
+            dump.as_object_mut().unwrap().remove("playable_actions");
+            dump
+        };
+        assert_eq!(dump(&restored), dump(&state));
+        assert_eq!(restored.to_fen(), state.to_fen());
+        // Cached production is summed in placement order, so compare it with a tolerance
+        let same = |a: Vec<f64>, b: Vec<f64>| a.iter().zip(&b).all(|(a, b)| (a - b).abs() < 1e-9);
+        for color in 0..state.get_num_players() {
+            assert!(same(
+                restored.get_total_production(color),
+                state.get_total_production(color)
+            ));
+            assert!(same(
+                restored.get_effective_production(color),
+                state.get_effective_production(color)
+            ));
+        }
+
+        // Same RNG position, so dice and steals keep agreeing. Rebuilt caches may list
+        // actions in another order.
+        let offered = |state: &State| -> HashSet<Action> {
+            state.generate_playable_actions().into_iter().collect()
+        };
+        for _ in 0..100 {
+            let actions = state.generate_playable_actions();
+            assert_eq!(offered(&restored), offered(&state));
+            let action = *actions.choose(&mut rng).unwrap();
+            state.apply_action(action);
+            restored.apply_action(action);
+            assert_eq!(restored.vector, state.vector);
+        }
+    }
+
+    #[test]
+    fn test_pieces_left_follow_builds_and_older_snapshots() {
+        let mut state = State::new_base();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        for _ in 0..400 {
+            if state.winner().is_some() {
+                break;
+            }
+            let action = *state.generate_playable_actions().choose(&mut rng).unwrap();
+            state.apply_action(action);
+        }
+        for color in 0..state.get_num_players() {
+            let pieces = (
+                state.get_settlements(color).len() as u8 + state.get_settlements_left(color),
+                state.get_cities(color).len() as u8 + state.get_cities_left(color),
+                state.roads_by_color[color as usize] + state.get_roads_left(color),
+            );
+            assert_eq!(pieces, (5, 4, 15));
+        }
+        assert!((0..4).any(|color| state.get_settlements_left(color) < 5));
+
+        // Snapshots written before the vector held the pieces left get them recounted
+        let mut json = serde_json::to_value(&state).unwrap();
+        let vector = json["vector"].as_array_mut().unwrap();
+        vector.truncate(vector.len() - PLAYER_PIECES_LEFT_SIZE * 4);
+        let restored: State = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.vector, state.vector);
+        assert_eq!(restored.compute_hash64(), state.compute_hash64());
+    }
+
+    #[test]
+    fn test_rejects_pieces_off_the_board() {
+        let state = State::new_base();
+        let mut json = serde_json::to_value(&state).unwrap();
+        json["buildings"] = serde_json::json!([{ "Settlement": [0, 200] }]);
+        let error = serde_json::from_value::<State>(json).err().unwrap();
+        assert!(error.to_string().contains("off the board"));
+    }
+}
@@ -0,0 +1,144 @@
+use super::State;
+use crate::enums::{TeamConfig, TeamVictory};
+
+impl State {
+    pub fn get_team_config(&self) -> Option<&TeamConfig> {
+        self.config.teams.as_ref()
+    }
+
+    /// Team a seat belongs to. Without a team config every seat is its own team.
+    pub fn team_of(&self, color: u8) -> u8 {
+        self.config
+            .teams
+            .as_ref()
+            .and_then(|teams| teams.team_of_seat.get(color as usize).copied())
+            .unwrap_or(color)
+    }
+
+    /// Whether two seats play on the same side (a seat is always its own teammate)
+    pub fn are_teammates(&self, a: u8, b: u8) -> bool {
+        a == b || (self.config.teams.is_some() && self.team_of(a) == self.team_of(b))
+    }
+
+    /// Other members of `color`'s team
+    pub fn teammates(&self, color: u8) -> Vec<u8> {
+        (0..self.get_num_players())
+            .filter(|&other| other != color && self.are_teammates(color, other))
+            .collect()
+    }
+
+    /// Sum of actual victory points over `color`'s team (just `color` without teams)
+    pub fn get_team_victory_points(&self, color: u8) -> u8 {
+        (0..self.get_num_players())
+            .filter(|&other| self.are_teammates(color, other))
+            .map(|other| self.get_actual_victory_points(other))
+            .sum()
+    }
+
+    /// Color credited with the win, if any. With combined team points this is the
+    /// best-scoring member of the winning team.
+    pub(super) fn find_winner(&self) -> Option<u8> {
+        let combined = matches!(
+            self.config.teams.as_ref().map(|teams| teams.victory),
+            Some(TeamVictory::CombinedPoints)
+        );
+        let target = self.config.vps_to_win;
+        let on_turn = |color: u8| {
+            self.rules().wins_on_any_turn() || self.are_teammates(color, self.get_current_color())
+        };
+
+        if !combined {
+            return (0..self.get_num_players())
+                .find(|&color| on_turn(color) && self.get_actual_victory_points(color) >= target);
+        }
+
+        let team_color = (0..self.get_num_players())
+            .find(|&color| on_turn(color) && self.get_team_victory_points(color) >= target)?;
+        (0..self.get_num_players())
+            .filter(|&color| self.are_teammates(team_color, color))
+            .max_by_key(|&color| {
+                (
+                    self.get_actual_victory_points(color),
+                    std::cmp::Reverse(color),
+                )
+            })
+    }
+
+    /// Whether `color` may still gift a card to a teammate this turn
+    pub fn can_gift(&self, color: u8) -> bool {
+        self.config
+            .teams
+            .as_ref()
+            .is_some_and(|teams| teams.allow_gifts)
+            && !self.gifted_this_turn
+            && self.get_player_hand(color).iter().any(|&count| count > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, GameConfiguration, StateEdit};
+    use crate::map_instance::MapInstance;
+    use crate::state_vector::actual_victory_points_index;
+    use std::sync::Arc;
+
+    fn team_state(victory: TeamVictory) -> State {
+        let config = GameConfiguration::builder()
+            .vps_to_win(15)
+            .teams(Some(TeamConfig::two_vs_two(victory)))
+            .build()
+            .unwrap();
+        let map_instance = MapInstance::shared(&config.map_type, 0);
+        State::new(Arc::new(config), map_instance)
+    }
+
+    #[test]
+    fn test_teammates_sit_opposite() {
+        let state = team_state(TeamVictory::CombinedPoints);
+        assert!(state.are_teammates(0, 2));
+        assert!(state.are_teammates(1, 3));
+        assert!(!state.are_teammates(0, 1));
+        assert_eq!(state.teammates(3), vec![1]);
+    }
+
+    #[test]
+    fn test_combined_points_victory() {
+        let mut state = team_state(TeamVictory::CombinedPoints);
+        state.vector[actual_victory_points_index(4, 0)] = 6;
+        state.vector[actual_victory_points_index(4, 2)] = 8;
+        state.vector[actual_victory_points_index(4, 1)] = 9;
+        assert_eq!(state.find_winner(), None);
+
+        state.vector[actual_victory_points_index(4, 0)] = 7;
+        assert_eq!(state.find_winner(), Some(2));
+    }
+
+    #[test]
+    fn test_gift_once_per_turn() {
+        let mut state = team_state(TeamVictory::FirstTeammate);
+        let color = state.get_current_color();
+        let teammate = state.teammates(color)[0];
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::HasRolled(true),
+            StateEdit::Hand([2, 0, 0, 0, 0]),
+        ] {
+            state.apply_action(Action::DebugSetState { color, edit });
+        }
+
+        let gift = Action::GiftResource {
+            color,
+            teammate,
+            resource: 0,
+        };
+        assert!(state.generate_playable_actions().contains(&gift));
+        state.apply_action(gift);
+        assert_eq!(state.get_player_hand(color)[0], 1);
+        assert_eq!(state.get_player_hand(teammate)[0], 1);
+        assert!(!state
+            .generate_playable_actions()
+            .iter()
+            .any(|a| matches!(a, Action::GiftResource { .. })));
+    }
+}
@@ -0,0 +1,320 @@
+// Domestic trading between players. The player to move offers cards for cards, every
+// other seat answers in turn order (DecideTrade), and if anyone accepted the offerer
+// picks whom to trade with or calls it off (DecideAcceptees).
+//
+// Instead of answering yes or no, a responder may counter with other terms. The counter
+// replaces the offer: seats yet to answer are skipped and the offerer decides on it
+// alone, and may counter back once, which the counterer then accepts or rejects.
+
+use serde::{Deserialize, Serialize};
+
+use super::State;
+use crate::deck_slices::freqdeck_contains;
+use crate::enums::FreqDeck;
+
+/// Offers a player may make per turn, so declined offers cannot repeat forever
+pub const MAX_TRADE_OFFERS_PER_TURN: u8 = 2;
+/// Counter-offers per offer, both sides together, so haggling cannot go on forever
+pub const MAX_COUNTER_OFFERS: u8 = 2;
+
+/// An offer on the table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomesticTrade {
+    pub offerer: u8,
+    /// Cards the offerer gives
+    pub give: FreqDeck,
+    /// Cards the offerer asks for in return
+    pub take: FreqDeck,
+    /// Seats that accepted, in the order they answered
+    pub acceptees: Vec<u8>,
+    /// Seats still to answer, the one answering first
+    pub(super) pending: Vec<u8>,
+    /// Tick seat of the offerer, where play returns once the trade is settled
+    pub(super) offerer_seat: u8,
+    /// Counter-offers made on this offer so far
+    #[serde(default)]
+    pub counters: u8,
+}
+
+impl DomesticTrade {
+    /// Seat answering the offer now; `None` once everyone has
+    pub fn responder(&self) -> Option<u8> {
+        self.pending.first().copied()
+    }
+}
+
+impl State {
+    /// The offer waiting on answers or on the offerer's pick, if any
+    pub fn get_domestic_trade(&self) -> Option<&DomesticTrade> {
+        self.domestic_trade.as_ref()
+    }
+
+    /// Whether `color` may put another offer on the table this turn
+    pub fn can_offer_trade(&self, color: u8) -> bool {
+        self.domestic_trade.is_none()
+            && self.trade_offers_this_turn < MAX_TRADE_OFFERS_PER_TURN
+            && self.get_player_hand(color).iter().any(|&count| count > 0)
+    }
+
+    /// Whether `color` holds the cards the open offer asks for
+    pub fn can_accept_trade(&self, color: u8) -> bool {
+        self.domestic_trade
+            .as_ref()
+            .is_some_and(|trade| freqdeck_contains(self.get_player_hand(color), &trade.take))
+    }
+
+    /// Whether `color` may counter the offer on the table: a responder while counters
+    /// are left, or the offerer answering a counter
+    pub fn can_counter_trade(&self, color: u8) -> bool {
+        let Some(trade) = &self.domestic_trade else {
+            return false;
+        };
+        let answering_counter = trade.offerer == color && trade.counters > 0;
+        trade.counters < MAX_COUNTER_OFFERS
+            && (trade.responder() == Some(color) || answering_counter)
+    }
+
+    /// Counters that move the offer on the table by one card in `color`'s favor: asking
+    /// the other side for one more card, or giving one fewer. Other terms are legal too;
+    /// these are the ones bots weigh. Written from the offerer's side like the offer.
+    pub fn counter_offer_candidates(&self, color: u8) -> Vec<(FreqDeck, FreqDeck)> {
+        let (Some(trade), Some((offerer, responder))) = (
+            self.domestic_trade
+                .as_ref()
+                .filter(|_| self.can_counter_trade(color)),
+            self.trade_sides(color),
+        ) else {
+            return Vec::new();
+        };
+        let mut candidates = Vec::new();
+        for resource in 0..5 {
+            let (mut give, mut take) = (trade.give, trade.take);
+            if color == offerer {
+                // Ask for one more, or give one fewer
+                if give[resource] == 0 {
+                    take[resource] += 1;
+                    candidates.push((trade.give, take));
+                }
+                if trade.give[resource] > 0 && trade.give.iter().sum::<u8>() > 1 {
+                    give[resource] -= 1;
+                    candidates.push((give, trade.take));
+                }
+            } else {
+                if take[resource] == 0 {
+                    give[resource] += 1;
+                    candidates.push((give, trade.take));
+                }
+                if trade.take[resource] > 0 && trade.take.iter().sum::<u8>() > 1 {
+                    take[resource] -= 1;
+                    candidates.push((trade.give, take));
+                }
+            }
+        }
+        candidates.retain(|(give, take)| {
+            freqdeck_contains(self.get_player_hand(offerer), give)
+                && freqdeck_contains(self.get_player_hand(responder), take)
+        });
+        candidates
+    }
+
+    /// Offerer and responder of the trade `color` is to answer: `color` and the seat that
+    /// countered when the offerer answers, `color` itself as the responder otherwise
+    pub(crate) fn trade_sides(&self, color: u8) -> Option<(u8, u8)> {
+        let trade = self.domestic_trade.as_ref()?;
+        if color != trade.offerer {
+            Some((trade.offerer, color))
+        } else {
+            trade.acceptees.first().map(|&responder| (color, responder))
+        }
+    }
+
+    /// Index of `color` in the seating order
+    pub(super) fn seat_of(&self, color: u8) -> u8 {
+        self.get_seating_order()
+            .iter()
+            .position(|&seated| seated == color)
+            .unwrap_or_default() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Action, ActionPrompt, StateEdit};
+    use crate::state::validation::Illegal;
+
+    /// Base game past the opening, the first seat having rolled, with hands given in
+    /// seating order
+    fn rolled_state(hands: [FreqDeck; 4]) -> (State, u8) {
+        let mut state = State::new_base();
+        let color = state.get_current_color();
+        for edit in [
+            StateEdit::InitialBuildPhase(false),
+            StateEdit::HasRolled(true),
+        ] {
+            state.apply_action(Action::DebugSetState { color, edit });
+        }
+        let seating = state.get_seating_order().to_vec();
+        for (seated, hand) in seating.into_iter().zip(hands) {
+            state.apply_action(Action::DebugSetState {
+                color: seated,
+                edit: StateEdit::Hand(hand),
+            });
+        }
+        (state, color)
+    }
+
+    #[test]
+    fn test_accepted_offer_swaps_cards_with_the_chosen_seat() {
+        let (mut state, offerer) = rolled_state([
+            [2, 0, 0, 0, 0],
+            [0, 1, 0, 0, 0],
+            [0, 0, 0, 0, 0],
+            [0, 1, 0, 0, 0],
+        ]);
+        let (give, take) = ([1, 0, 0, 0, 0], [0, 1, 0, 0, 0]);
+        let offer = Action::OfferTrade {
+            color: offerer,
+            trade: (give, take),
+        };
+        assert!(state.generate_playable_actions().contains(&offer));
+        state.apply_action(offer);
+
+        // Everyone else answers in turn order; seats without the cards can only decline
+        let responders: Vec<u8> = state.get_seating_order()[1..].to_vec();
+        let holders: Vec<u8> = responders
+            .iter()
+            .copied()
+            .filter(|&c| state.get_player_hand(c)[1] > 0)
+            .collect();
+        for &responder in &responders {
+            assert_eq!(state.get_action_prompt(), ActionPrompt::DecideTrade);
+            assert_eq!(state.get_current_color(), responder);
+            let accept = Action::AcceptTrade {
+                color: responder,
+                trade: (give, take),
+            };
+            let actions = state.generate_playable_actions();
+            assert!(actions.contains(&Action::RejectTrade { color: responder }));
+            assert_eq!(actions.contains(&accept), holders.contains(&responder));
+            if holders.contains(&responder) {
+                state.apply_action(accept);
+            } else {
+                state.apply_action(Action::RejectTrade { color: responder });
+            }
+        }
+
+        assert_eq!(state.get_action_prompt(), ActionPrompt::DecideAcceptees);
+        assert_eq!(state.get_current_color(), offerer);
+        let acceptees = state.get_domestic_trade().unwrap().acceptees.clone();
+        assert_eq!(acceptees, holders);
+        assert!(state
+            .generate_playable_actions()
+            .contains(&Action::CancelTrade { color: offerer }));
+        assert_eq!(holders.len(), 2);
+
+        let partner = acceptees[0];
+        state.apply_action(Action::ConfirmTrade {
+            color: offerer,
+            trade: (give, take, partner),
+        });
+        assert_eq!(state.get_domestic_trade(), None);
+        assert_eq!(state.get_action_prompt(), ActionPrompt::PlayTurn);
+        assert_eq!(state.get_current_color(), offerer);
+        assert_eq!(state.get_player_hand(offerer)[..2], [1, 1]);
+        assert_eq!(state.get_player_hand(partner)[..2], [1, 0]);
+    }
+
+    #[test]
+    fn test_declined_offers_are_limited_per_turn() {
+        let (mut state, offerer) = rolled_state([[3, 0, 0, 0, 0]; 4]);
+        for _ in 0..MAX_TRADE_OFFERS_PER_TURN {
+            assert!(state.can_offer_trade(offerer));
+            state.apply_action(Action::OfferTrade {
+                color: offerer,
+                trade: ([1, 0, 0, 0, 0], [0, 0, 0, 0, 1]),
+            });
+            while state.get_action_prompt() == ActionPrompt::DecideTrade {
+                let responder = state.get_current_color();
+                state.apply_action(Action::RejectTrade { color: responder });
+            }
+            // Nobody accepted, so the offer closes without asking the offerer
+            assert_eq!(state.get_action_prompt(), ActionPrompt::PlayTurn);
+            assert_eq!(state.get_current_color(), offerer);
+        }
+        assert!(!state.can_offer_trade(offerer));
+        assert!(!state
+            .generate_playable_actions()
+            .iter()
+            .any(|a| matches!(a, Action::OfferTrade { .. })));
+
+        state.apply_action(Action::EndTurn { color: offerer });
+        let next = state.get_current_color();
+        assert!(state.can_offer_trade(next));
+    }
+
+    #[test]
+    fn test_counter_offers_go_back_and_forth_until_the_limit() {
+        let (mut state, offerer) = rolled_state([
+            [3, 0, 0, 0, 0],
+            [0, 2, 1, 0, 0],
+            [0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0],
+        ]);
+        let responder = state.get_seating_order()[1];
+        state.apply_action(Action::OfferTrade {
+            color: offerer,
+            trade: ([1, 0, 0, 0, 0], [0, 2, 0, 0, 0]),
+        });
+        assert!(!state.can_counter_trade(offerer));
+        assert!(state.can_counter_trade(responder));
+
+        // The responder wants a second card for the pair; the other seats are not asked
+        let counter = Action::CounterTrade {
+            color: responder,
+            trade: ([2, 0, 0, 0, 0], [0, 2, 0, 0, 0]),
+        };
+        assert!(state
+            .counter_offer_candidates(responder)
+            .contains(&([2, 0, 0, 0, 0], [0, 2, 0, 0, 0])));
+        assert_eq!(state.validate_action(&counter), Ok(()));
+        state.apply_action(counter);
+        assert_eq!(state.get_action_prompt(), ActionPrompt::DecideAcceptees);
+        assert_eq!(state.get_current_color(), offerer);
+
+        // The offerer meets them halfway, and the responder is asked again
+        let unaffordable = Action::CounterTrade {
+            color: offerer,
+            trade: ([2, 0, 0, 0, 0], [0, 3, 0, 0, 0]),
+        };
+        assert!(matches!(
+            state.validate_action(&unaffordable),
+            Err(Illegal::OtherSideCannotAfford { color, .. }) if color == responder
+        ));
+        let terms = ([2, 0, 0, 0, 0], [0, 1, 1, 0, 0]);
+        state.apply_action(Action::CounterTrade {
+            color: offerer,
+            trade: terms,
+        });
+        assert_eq!(state.get_action_prompt(), ActionPrompt::DecideTrade);
+        assert_eq!(state.get_current_color(), responder);
+        assert_eq!(
+            state.validate_action(&Action::CounterTrade {
+                color: responder,
+                trade: ([3, 0, 0, 0, 0], [0, 1, 1, 0, 0]),
+            }),
+            Err(Illegal::TooManyCounters)
+        );
+
+        state.apply_action(Action::AcceptTrade {
+            color: responder,
+            trade: terms,
+        });
+        state.apply_action(Action::ConfirmTrade {
+            color: offerer,
+            trade: (terms.0, terms.1, responder),
+        });
+        assert_eq!(state.get_player_hand(offerer)[..3], [1, 1, 1]);
+        assert_eq!(state.get_player_hand(responder)[..3], [2, 1, 0]);
+    }
+}
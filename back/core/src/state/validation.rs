@@ -0,0 +1,1297 @@
+// Rule checks for a single proposed action, answering "why not?" where move generation
+// only lists what is allowed. `validate_action` accepts exactly the actions
+// `generate_playable_actions` offers, plus trade offers of more than one card a side,
+// counter-offers and discards other than the suggested one; anything else gets the rule
+// it breaks.
+
+use serde::Serialize;
+
+use super::move_generation::RobberTile;
+use super::ships::TOTAL_SHIPS_PER_PLAYER;
+use super::trading::{DomesticTrade, MAX_COUNTER_OFFERS, MAX_TRADE_OFFERS_PER_TURN};
+use super::{Building, State};
+use crate::deck_slices::{
+    freqdeck_contains, CITY_COST, DEVCARD_COST, ROAD_COST, SETTLEMENT_COST, SHIP_COST,
+};
+use crate::enums::{Action, ActionPrompt, DevCard, FreqDeck};
+use crate::map_instance::{EdgeId, NodeId};
+use crate::map_template::Coordinate;
+use crate::state_vector::{dev_deck_size, DEV_BANK_PTR_INDEX, HAS_PLAYED_DEV_CARD};
+
+/// The rule an action breaks. Serialized with the rule's name under `rule`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, thiserror::Error)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum Illegal {
+    #[error("the game is over")]
+    GameOver,
+    #[error("it is color {current}'s turn")]
+    NotYourTurn { current: u8 },
+    #[error("the game is waiting for {prompt:?}")]
+    WrongPrompt { prompt: ActionPrompt },
+    #[error("{kind} is not supported by the engine")]
+    NotSupported { kind: &'static str },
+    #[error("the dice must be rolled first")]
+    MustRollFirst,
+    #[error("the dice were already rolled this turn")]
+    AlreadyRolled,
+    #[error("dice are rolled by the server, not chosen")]
+    FixedDice,
+    #[error("the free roads from Road Building must be placed first")]
+    FreeRoadsPending,
+    #[error("the {variant} variant is off")]
+    VariantOff { variant: &'static str },
+    #[error("missing resources {missing:?} (wood, brick, sheep, wheat, ore)")]
+    CannotAfford { missing: FreqDeck },
+    #[error("no {piece} pieces left")]
+    NoPiecesLeft { piece: &'static str },
+    #[error("node {node_id} is not on the board")]
+    UnknownNode { node_id: NodeId },
+    #[error("edge {edge_id:?} is not on the board")]
+    UnknownEdge { edge_id: EdgeId },
+    #[error("{coordinate:?} is not a land tile")]
+    UnknownTile { coordinate: Coordinate },
+    #[error("resource {resource} does not exist")]
+    UnknownResource { resource: u8 },
+    #[error("node {node_id} already has color {owner}'s building")]
+    NodeTaken { node_id: NodeId, owner: u8 },
+    #[error("node {node_id} is next to a settlement or city")]
+    TooCloseToSettlement { node_id: NodeId },
+    #[error("not connected to any of the player's roads or ships")]
+    NotConnected,
+    #[error("edge {edge_id:?} already has color {owner}'s road")]
+    RoadTaken { edge_id: EdgeId, owner: u8 },
+    #[error("edge {edge_id:?} already has color {owner}'s ship")]
+    ShipTaken { edge_id: EdgeId, owner: u8 },
+    #[error("edge {edge_id:?} is not a sea edge")]
+    NotSeaEdge { edge_id: EdgeId },
+    #[error("the ship on {edge_id:?} is not the player's, is built in, or was built this turn")]
+    ShipNotMovable { edge_id: EdgeId },
+    #[error("a ship was already moved this turn")]
+    ShipAlreadyMoved,
+    #[error("node {node_id} is not one of the player's settlements")]
+    NotOwnSettlement { node_id: NodeId },
+    #[error("edge {edge_id:?} does not leave the newest settlement")]
+    AwayFromNewestSettlement { edge_id: EdgeId },
+    #[error("the development card deck is empty")]
+    DeckEmpty,
+    #[error("no playable {card:?} card in hand")]
+    NoDevCard { card: DevCard },
+    #[error("a development card was already played this turn")]
+    DevCardAlreadyPlayed,
+    #[error("every {card:?} in hand was bought this turn")]
+    DevCardBoughtThisTurn { card: DevCard },
+    #[error("the bank is short of resource {resource}")]
+    BankShort { resource: u8 },
+    #[error("Year of Plenty takes two cards while the bank has them")]
+    YearOfPlentyTakesTwo,
+    #[error("the player trades this resource at {ratio}:1")]
+    WrongRatio { ratio: u8 },
+    #[error("a trade must swap two different resources")]
+    SameResource,
+    #[error("the robber must move to another tile")]
+    RobberMustMove,
+    #[error("the robber may not go there: {reason}")]
+    RobberTileRuledOut { reason: &'static str },
+    #[error("a victim must be chosen on that tile")]
+    MustChooseVictim,
+    #[error("color {victim} cannot be robbed there: {reason}")]
+    InvalidVictim { victim: u8, reason: &'static str },
+    #[error("nothing to discard; end the turn instead")]
+    NothingToDiscard,
+    #[error("cards must be discarded first")]
+    MustDiscard,
+    #[error("exactly {count} cards from the hand must be discarded")]
+    WrongDiscard { count: u8 },
+    #[error("no gift is possible: {reason}")]
+    GiftNotAllowed { reason: &'static str },
+    #[error("color {teammate} is not a teammate")]
+    NotTeammate { teammate: u8 },
+    #[error("{MAX_TRADE_OFFERS_PER_TURN} trade offers were already made this turn")]
+    TooManyOffers,
+    #[error("an offer gives and asks for cards, never of the same resource")]
+    InvalidOffer,
+    #[error("the offer on the table is {give:?} for {take:?}")]
+    NotTheOpenTrade { give: FreqDeck, take: FreqDeck },
+    #[error("color {acceptee} did not accept the offer")]
+    NotAcceptee { acceptee: u8 },
+    #[error("{MAX_COUNTER_OFFERS} counter-offers were already made on this offer")]
+    TooManyCounters,
+    #[error("the offerer may only counter a counter-offer")]
+    NoCounterToAnswer,
+    #[error("a counter-offer must change the terms")]
+    UnchangedCounter,
+    #[error("color {color} is missing {missing:?} for those terms")]
+    OtherSideCannotAfford { color: u8, missing: FreqDeck },
+    #[error("the engine writes this action as {expected:?}")]
+    NonCanonical { expected: Action },
+}
+
+/// Answer to "may this action be taken now?"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Legality {
+    pub legal: bool,
+    /// The rule broken, with its details
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<Illegal>,
+    /// `reason` in words
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl From<Result<(), Illegal>> for Legality {
+    fn from(result: Result<(), Illegal>) -> Self {
+        match result {
+            Ok(()) => Self {
+                legal: true,
+                reason: None,
+                message: None,
+            },
+            Err(illegal) => Self {
+                legal: false,
+                message: Some(illegal.to_string()),
+                reason: Some(illegal),
+            },
+        }
+    }
+}
+
+impl State {
+    /// Whether the player to move may take `action` now, and if not, the rule it breaks.
+    /// Roads and Year of Plenty pairs must be written the way move generation writes
+    /// them; `canonical_form` rewrites them.
+    pub fn validate_action(&self, action: &Action) -> Result<(), Illegal> {
+        let color = self.get_current_color();
+        if action.color() != color {
+            return Err(Illegal::NotYourTurn { current: color });
+        }
+        if let Some(kind) = unsupported(action) {
+            return Err(Illegal::NotSupported { kind });
+        }
+
+        let prompt = self.get_action_prompt();
+        match (&prompt, *action) {
+            (ActionPrompt::BuildInitialSettlement, Action::BuildSettlement { node_id, .. }) => {
+                self.check_free_node(node_id)
+            }
+            (ActionPrompt::BuildInitialRoad, Action::BuildRoad { edge_id, .. }) => {
+                self.check_initial_road(color, edge_id)
+            }
+            (
+                ActionPrompt::MoveRobber,
+                Action::MoveRobber {
+                    coordinate,
+                    victim_opt,
+                    ..
+                },
+            ) => self.check_robber(color, coordinate, victim_opt),
+            (ActionPrompt::Discard, Action::Discard { resources, .. }) => {
+                if !self.must_discard(color) {
+                    return Err(Illegal::NothingToDiscard);
+                }
+                let count = self.discard_count(color);
+                if resources.iter().sum::<u8>() == count
+                    && freqdeck_contains(self.get_player_hand(color), &resources)
+                {
+                    Ok(())
+                } else {
+                    Err(Illegal::WrongDiscard { count })
+                }
+            }
+            (ActionPrompt::Discard, Action::EndTurn { .. }) if !self.must_discard(color) => Ok(()),
+            (ActionPrompt::Discard, _) => Err(Illegal::MustDiscard),
+            (ActionPrompt::ChooseGold, Action::ChooseGold { resource, .. }) => {
+                check_resource(resource)?;
+                if self.get_bank_resources()[resource as usize] == 0 {
+                    return Err(Illegal::BankShort { resource });
+                }
+                Ok(())
+            }
+            (ActionPrompt::PlayTurn, _) => self.check_play_turn(color, action),
+            (ActionPrompt::DecideTrade, Action::RejectTrade { .. }) => Ok(()),
+            (ActionPrompt::DecideTrade, Action::AcceptTrade { trade, .. }) => {
+                let open = self.check_open_trade(trade)?;
+                self.check_cost(color, &open.take)
+            }
+            (
+                ActionPrompt::DecideTrade | ActionPrompt::DecideAcceptees,
+                Action::CounterTrade { trade, .. },
+            ) => self.check_counter(color, trade),
+            (ActionPrompt::SpecialBuild, Action::PassBuildPhase { .. }) => Ok(()),
+            (
+                ActionPrompt::SpecialBuild,
+                Action::BuildSettlement { .. }
+                | Action::BuildCity { .. }
+                | Action::BuildRoad { .. }
+                | Action::BuildShip { .. }
+                | Action::BuyDevelopmentCard { .. },
+            ) => self.check_build(color, action),
+            (ActionPrompt::DecideAcceptees, Action::CancelTrade { .. }) => Ok(()),
+            (
+                ActionPrompt::DecideAcceptees,
+                Action::ConfirmTrade {
+                    trade: (give, take, acceptee),
+                    ..
+                },
+            ) => {
+                let open = self.check_open_trade((give, take))?;
+                if open.acceptees.contains(&acceptee) {
+                    Ok(())
+                } else {
+                    Err(Illegal::NotAcceptee { acceptee })
+                }
+            }
+            _ => Err(Illegal::WrongPrompt { prompt }),
+        }
+    }
+
+    /// `action` written the way move generation writes it: roads from the end the
+    /// engine lists first, ships low end first, Year of Plenty pairs in resource order
+    pub fn canonical_form(&self, action: Action) -> Action {
+        match action {
+            Action::BuildRoad {
+                color,
+                edge_id: (a, b),
+            } => {
+                let initial = matches!(self.get_action_prompt(), ActionPrompt::BuildInitialRoad)
+                    && self.roads_by_color[color as usize] < 2;
+                let edge_id = match self.newest_building_node(color) {
+                    Some(node) if initial => self
+                        .map_instance
+                        .get_neighbor_edges(node)
+                        .iter()
+                        .copied()
+                        .find(|&edge| edge == (a, b) || edge == (b, a))
+                        .unwrap_or((a, b)),
+                    _ => (a.min(b), a.max(b)),
+                };
+                Action::BuildRoad { color, edge_id }
+            }
+            Action::BuildShip {
+                color,
+                edge_id: (a, b),
+            } => Action::BuildShip {
+                color,
+                edge_id: (a.min(b), a.max(b)),
+            },
+            Action::MoveShip {
+                color,
+                from: (a, b),
+                to: (c, d),
+            } => Action::MoveShip {
+                color,
+                from: (a.min(b), a.max(b)),
+                to: (c.min(d), c.max(d)),
+            },
+            Action::PlayYearOfPlenty {
+                color,
+                resources: (a, Some(b)),
+            } => Action::PlayYearOfPlenty {
+                color,
+                resources: (a.min(b), Some(a.max(b))),
+            },
+            // A discard that leaves the cards out takes the suggested ones
+            Action::Discard {
+                color,
+                resources: [0, 0, 0, 0, 0],
+            } => Action::Discard {
+                color,
+                resources: self.suggested_discard(color),
+            },
+            // Answers to an offer may leave its terms out
+            Action::AcceptTrade { color, .. } => match &self.domestic_trade {
+                Some(trade) => Action::AcceptTrade {
+                    color,
+                    trade: (trade.give, trade.take),
+                },
+                None => action,
+            },
+            Action::ConfirmTrade {
+                color,
+                trade: (_, _, acceptee),
+            } => match &self.domestic_trade {
+                Some(trade) => Action::ConfirmTrade {
+                    color,
+                    trade: (trade.give, trade.take, acceptee),
+                },
+                None => action,
+            },
+            other => other,
+        }
+    }
+
+    fn check_play_turn(&self, color: u8, action: &Action) -> Result<(), Illegal> {
+        if self.is_road_building() && (self.can_place_road(color) || self.can_place_ship(color)) {
+            return match *action {
+                Action::BuildRoad { edge_id, .. } => self.check_road(color, edge_id, true),
+                Action::BuildShip { edge_id, .. } => self.check_ship(color, edge_id, true),
+                _ => Err(Illegal::FreeRoadsPending),
+            };
+        }
+
+        if !self.current_player_rolled() {
+            return match *action {
+                Action::Roll { dice_opt: None, .. } => Ok(()),
+                Action::Roll { .. } => Err(Illegal::FixedDice),
+                Action::PlayKnight { .. } => self.check_dev_card(DevCard::Knight),
+                Action::MoveRobber { .. } | Action::Discard { .. } => Err(Illegal::WrongPrompt {
+                    prompt: ActionPrompt::PlayTurn,
+                }),
+                _ => Err(Illegal::MustRollFirst),
+            };
+        }
+
+        match *action {
+            Action::Roll { .. } => Err(Illegal::AlreadyRolled),
+            Action::EndTurn { .. } => Ok(()),
+            Action::PassBuildPhase { .. } => {
+                if self.config.variants.speed_rounds {
+                    Ok(())
+                } else {
+                    Err(Illegal::VariantOff {
+                        variant: "speed rounds",
+                    })
+                }
+            }
+            Action::BuildSettlement { .. }
+            | Action::BuildCity { .. }
+            | Action::BuildRoad { .. }
+            | Action::BuildShip { .. }
+            | Action::BuyDevelopmentCard { .. } => self.check_build(color, action),
+            Action::MoveShip { from, to, .. } => self.check_move_ship(color, from, to),
+            Action::PlayKnight { .. } => self.check_dev_card(DevCard::Knight),
+            Action::PlayYearOfPlenty { resources, .. } => {
+                self.check_dev_card(DevCard::YearOfPlenty)?;
+                self.check_year_of_plenty(action, resources)
+            }
+            Action::PlayMonopoly { resource, .. } => {
+                self.check_dev_card(DevCard::Monopoly)?;
+                check_resource(resource)
+            }
+            Action::PlayRoadBuilding { .. } => self.check_dev_card(DevCard::RoadBuilding),
+            Action::MaritimeTrade {
+                give, take, ratio, ..
+            } => {
+                check_resource(give)?;
+                check_resource(take)?;
+                if give == take {
+                    return Err(Illegal::SameResource);
+                }
+                let rate = self.calculate_port_rates(color)[give as usize];
+                if ratio != rate {
+                    return Err(Illegal::WrongRatio { ratio: rate });
+                }
+                let mut cost = [0; 5];
+                cost[give as usize] = rate;
+                self.check_cost(color, &cost)?;
+                if self.get_bank_resources()[take as usize] == 0 {
+                    return Err(Illegal::BankShort { resource: take });
+                }
+                Ok(())
+            }
+            Action::GiftResource {
+                teammate, resource, ..
+            } => self.check_gift(color, teammate, resource),
+            Action::OfferTrade {
+                trade: (give, take),
+                ..
+            } => self.check_offer(color, give, take),
+            _ => Err(Illegal::WrongPrompt {
+                prompt: ActionPrompt::PlayTurn,
+            }),
+        }
+    }
+
+    /// Node on the board, empty, and clear of the distance rule
+    fn check_free_node(&self, node_id: NodeId) -> Result<(), Illegal> {
+        if !self.map_instance.land_nodes().contains(&node_id) {
+            return Err(Illegal::UnknownNode { node_id });
+        }
+        if let Some(owner) = self.get_node_color(node_id) {
+            return Err(Illegal::NodeTaken { node_id, owner });
+        }
+        if !self.board_buildable_ids.contains(&node_id) {
+            return Err(Illegal::TooCloseToSettlement { node_id });
+        }
+        Ok(())
+    }
+
+    fn check_edge(&self, edge_id: EdgeId) -> Result<(), Illegal> {
+        let (a, b) = edge_id;
+        if !self.map_instance.land_edges.contains(&(a, b))
+            && !self.map_instance.land_edges.contains(&(b, a))
+        {
+            return Err(Illegal::UnknownEdge { edge_id });
+        }
+        if let Some(owner) = self.get_edge_owner(edge_id) {
+            return Err(Illegal::RoadTaken { edge_id, owner });
+        }
+        if let Some(owner) = self.get_ship_owner(edge_id) {
+            return Err(Illegal::ShipTaken { edge_id, owner });
+        }
+        Ok(())
+    }
+
+    fn check_sea_edge(&self, edge_id: EdgeId) -> Result<(), Illegal> {
+        let (a, b) = edge_id;
+        if !self.map_instance.sea_edges.contains(&(a.min(b), a.max(b))) {
+            return Err(Illegal::NotSeaEdge { edge_id });
+        }
+        if let Some(owner) = self.get_edge_owner(edge_id) {
+            return Err(Illegal::RoadTaken { edge_id, owner });
+        }
+        if let Some(owner) = self.get_ship_owner(edge_id) {
+            return Err(Illegal::ShipTaken { edge_id, owner });
+        }
+        Ok(())
+    }
+
+    fn check_initial_road(&self, color: u8, edge_id: EdgeId) -> Result<(), Illegal> {
+        // A handicap's extra road may extend anywhere from the network
+        if self.roads_by_color[color as usize] >= 2 {
+            return self.check_road(color, edge_id, true);
+        }
+        self.check_edge(edge_id)?;
+        let Some(node) = self.newest_building_node(color) else {
+            return Err(Illegal::AwayFromNewestSettlement { edge_id });
+        };
+        if !self
+            .map_instance
+            .get_neighbor_edges(node)
+            .contains(&edge_id)
+        {
+            return match self.canonical_form(Action::BuildRoad { color, edge_id }) {
+                expected @ Action::BuildRoad { edge_id: other, .. } if other != edge_id => {
+                    Err(Illegal::NonCanonical { expected })
+                }
+                _ => Err(Illegal::AwayFromNewestSettlement { edge_id }),
+            };
+        }
+        Ok(())
+    }
+
+    /// A road extending `color`'s network; `free` skips the cost (Road Building)
+    fn check_road(&self, color: u8, edge_id: EdgeId, free: bool) -> Result<(), Illegal> {
+        if self.get_roads_left(color) == 0 {
+            return Err(Illegal::NoPiecesLeft { piece: "road" });
+        }
+        if !free {
+            self.check_cost(color, &ROAD_COST)?;
+        }
+        self.check_edge(edge_id)?;
+        let (a, b) = edge_id;
+        if !self
+            .buildable_edges(color)
+            .any(|edge| edge == (a.min(b), a.max(b)))
+        {
+            return Err(Illegal::NotConnected);
+        }
+        if a > b {
+            return Err(Illegal::NonCanonical {
+                expected: Action::BuildRoad {
+                    color,
+                    edge_id: (b, a),
+                },
+            });
+        }
+        Ok(())
+    }
+
+    fn can_place_road(&self, color: u8) -> bool {
+        self.get_roads_left(color) > 0 && self.buildable_edges(color).next().is_some()
+    }
+
+    /// A ship extending `color`'s coast or shipping routes; `free` skips the cost
+    fn check_ship(&self, color: u8, edge_id: EdgeId, free: bool) -> Result<(), Illegal> {
+        if self.ships_by_color[color as usize] >= TOTAL_SHIPS_PER_PLAYER {
+            return Err(Illegal::NoPiecesLeft { piece: "ship" });
+        }
+        if !free {
+            self.check_cost(color, &SHIP_COST)?;
+        }
+        self.check_sea_edge(edge_id)?;
+        let (a, b) = edge_id;
+        if !self
+            .buildable_sea_edges(color, None)
+            .contains(&(a.min(b), a.max(b)))
+        {
+            return Err(Illegal::NotConnected);
+        }
+        if a > b {
+            return Err(Illegal::NonCanonical {
+                expected: Action::BuildShip {
+                    color,
+                    edge_id: (b, a),
+                },
+            });
+        }
+        Ok(())
+    }
+
+    fn can_place_ship(&self, color: u8) -> bool {
+        self.has_ships()
+            && self.ships_by_color[color as usize] < TOTAL_SHIPS_PER_PLAYER
+            && !self.buildable_sea_edges(color, None).is_empty()
+    }
+
+    /// The open-end ship on `from` sailing to `to`, once per turn
+    fn check_move_ship(&self, color: u8, from: EdgeId, to: EdgeId) -> Result<(), Illegal> {
+        if self.ship_moved_this_turn {
+            return Err(Illegal::ShipAlreadyMoved);
+        }
+        if !self.movable_ships(color).contains(&from) {
+            return Err(Illegal::ShipNotMovable { edge_id: from });
+        }
+        self.check_sea_edge(to)?;
+        let (c, d) = to;
+        if !self
+            .buildable_sea_edges(color, Some(from))
+            .contains(&(c.min(d), c.max(d)))
+        {
+            return Err(Illegal::NotConnected);
+        }
+        let canonical = self.canonical_form(Action::MoveShip { color, from, to });
+        if canonical != (Action::MoveShip { color, from, to }) {
+            return Err(Illegal::NonCanonical {
+                expected: canonical,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_cost(&self, color: u8, cost: &FreqDeck) -> Result<(), Illegal> {
+        let hand = self.get_player_hand(color);
+        let missing: FreqDeck = std::array::from_fn(|i| cost[i].saturating_sub(hand[i]));
+        if missing.iter().any(|&count| count > 0) {
+            return Err(Illegal::CannotAfford { missing });
+        }
+        Ok(())
+    }
+
+    /// Building and buying, the part of a turn the Special Build Phase allows too
+    fn check_build(&self, color: u8, action: &Action) -> Result<(), Illegal> {
+        match *action {
+            Action::BuildSettlement { node_id, .. } => {
+                if self.get_settlements_left(color) == 0 {
+                    return Err(Illegal::NoPiecesLeft {
+                        piece: "settlement",
+                    });
+                }
+                self.check_cost(color, &SETTLEMENT_COST)?;
+                self.check_free_node(node_id)?;
+                let connected = self
+                    .map_instance
+                    .get_neighbor_edges(node_id)
+                    .iter()
+                    .any(|&edge_id| self.owns_road(color, edge_id))
+                    || self.touches_own_ship(color, node_id);
+                if connected {
+                    Ok(())
+                } else {
+                    Err(Illegal::NotConnected)
+                }
+            }
+            Action::BuildCity { node_id, .. } => {
+                self.check_cost(color, &CITY_COST)?;
+                if self.get_cities_left(color) == 0 {
+                    return Err(Illegal::NoPiecesLeft { piece: "city" });
+                }
+                match self.buildings.get(&node_id) {
+                    Some(Building::Settlement(owner, _)) if *owner == color => Ok(()),
+                    _ => Err(Illegal::NotOwnSettlement { node_id }),
+                }
+            }
+            Action::BuildRoad { edge_id, .. } => self.check_road(color, edge_id, false),
+            Action::BuildShip { edge_id, .. } => self.check_ship(color, edge_id, false),
+            Action::BuyDevelopmentCard { .. } => {
+                self.check_cost(color, &DEVCARD_COST)?;
+                if self.vector[DEV_BANK_PTR_INDEX] as usize
+                    >= dev_deck_size(self.config.num_players)
+                {
+                    return Err(Illegal::DeckEmpty);
+                }
+                Ok(())
+            }
+            _ => Err(Illegal::WrongPrompt {
+                prompt: self.get_action_prompt(),
+            }),
+        }
+    }
+
+    fn check_dev_card(&self, card: DevCard) -> Result<(), Illegal> {
+        if self.vector[HAS_PLAYED_DEV_CARD] == 1 {
+            return Err(Illegal::DevCardAlreadyPlayed);
+        }
+        if !self.can_play_dev(card as u8) {
+            let color = self.get_current_color();
+            return Err(if self.get_player_devhand(color)[card as usize] > 0 {
+                Illegal::DevCardBoughtThisTurn { card }
+            } else {
+                Illegal::NoDevCard { card }
+            });
+        }
+        Ok(())
+    }
+
+    fn check_year_of_plenty(
+        &self,
+        action: &Action,
+        resources: (u8, Option<u8>),
+    ) -> Result<(), Illegal> {
+        let bank = self.get_bank_resources();
+        let (a, b) = resources;
+        check_resource(a)?;
+        match b {
+            Some(b) => {
+                check_resource(b)?;
+                if a == b && bank[a as usize] < 2 {
+                    return Err(Illegal::BankShort { resource: a });
+                }
+                if let Some(&short) = [a, b].iter().find(|&&r| bank[r as usize] == 0) {
+                    return Err(Illegal::BankShort { resource: short });
+                }
+                if a > b {
+                    return Err(Illegal::NonCanonical {
+                        expected: self.canonical_form(*action),
+                    });
+                }
+                Ok(())
+            }
+            None => {
+                if bank[a as usize] == 0 {
+                    return Err(Illegal::BankShort { resource: a });
+                }
+                let available = bank.iter().filter(|&&count| count > 0).count();
+                if available >= 2 || bank[a as usize] >= 2 {
+                    return Err(Illegal::YearOfPlentyTakesTwo);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn check_robber(
+        &self,
+        color: u8,
+        coordinate: Coordinate,
+        victim_opt: Option<u8>,
+    ) -> Result<(), Illegal> {
+        let Some(tile) = self.map_instance.get_land_tile(coordinate) else {
+            return Err(Illegal::UnknownTile { coordinate });
+        };
+        if tile.id == self.get_robber_tile() {
+            return Err(Illegal::RobberMustMove);
+        }
+
+        let tiles = self.robber_tiles(color);
+        let best = tiles.iter().map(|&(kind, _, _)| kind).min();
+        let Some((kind, _, victims)) = tiles.iter().find(|(_, c, _)| *c == coordinate) else {
+            return Err(Illegal::UnknownTile { coordinate });
+        };
+        if Some(*kind) != best {
+            return Err(Illegal::RobberTileRuledOut {
+                reason: match kind {
+                    RobberTile::Desert => "the desert is only a fallback under these rules",
+                    RobberTile::BlocksTeammate => "it would block a teammate",
+                    RobberTile::BlocksProtected => "it would block a protected player",
+                    RobberTile::Preferred => "a better tile is available",
+                },
+            });
+        }
+
+        match victim_opt {
+            None if victims.is_empty() => Ok(()),
+            None => Err(Illegal::MustChooseVictim),
+            Some(victim) if victims.contains(&victim) => Ok(()),
+            Some(victim) => {
+                let on_tile = tile
+                    .hexagon
+                    .nodes
+                    .values()
+                    .any(|node| self.get_node_color(*node) == Some(victim));
+                let reason = if victim == color {
+                    "that is the player moving the robber"
+                } else if !on_tile {
+                    "no building of theirs touches the tile"
+                } else if !self.config.robber.allow_steal {
+                    "stealing is off"
+                } else if self.are_teammates(victim, color) {
+                    "they are a teammate"
+                } else if self.get_player_hand(victim).iter().sum::<u8>() == 0 {
+                    "they have no cards"
+                } else {
+                    "they are protected"
+                };
+                Err(Illegal::InvalidVictim { victim, reason })
+            }
+        }
+    }
+
+    fn check_gift(&self, color: u8, teammate: u8, resource: u8) -> Result<(), Illegal> {
+        check_resource(resource)?;
+        let reason = match &self.config.teams {
+            None => Some("the team variant is off"),
+            Some(teams) if !teams.allow_gifts => Some("gifts are off"),
+            Some(_) if self.gifted_this_turn => Some("a gift was already made this turn"),
+            Some(_) => None,
+        };
+        if let Some(reason) = reason {
+            return Err(Illegal::GiftNotAllowed { reason });
+        }
+        if teammate == color || !self.are_teammates(color, teammate) {
+            return Err(Illegal::NotTeammate { teammate });
+        }
+        let mut cost = [0; 5];
+        cost[resource as usize] = 1;
+        self.check_cost(color, &cost)
+    }
+
+    fn check_offer(&self, color: u8, give: FreqDeck, take: FreqDeck) -> Result<(), Illegal> {
+        if self.trade_offers_this_turn >= MAX_TRADE_OFFERS_PER_TURN {
+            return Err(Illegal::TooManyOffers);
+        }
+        check_terms(give, take)?;
+        self.check_cost(color, &give)
+    }
+
+    /// New terms for the open offer from `color`, who is to answer it. Both sides must
+    /// hold the cards they would hand over.
+    fn check_counter(&self, color: u8, (give, take): (FreqDeck, FreqDeck)) -> Result<(), Illegal> {
+        let trade = self.domestic_trade.as_ref().ok_or(Illegal::WrongPrompt {
+            prompt: self.get_action_prompt(),
+        })?;
+        if trade.offerer == color && trade.counters == 0 {
+            return Err(Illegal::NoCounterToAnswer);
+        }
+        if trade.counters >= MAX_COUNTER_OFFERS {
+            return Err(Illegal::TooManyCounters);
+        }
+        if (give, take) == (trade.give, trade.take) {
+            return Err(Illegal::UnchangedCounter);
+        }
+        check_terms(give, take)?;
+        let Some((offerer, responder)) = self.trade_sides(color) else {
+            return Err(Illegal::NoCounterToAnswer);
+        };
+        let (own, other, owed) = if offerer == color {
+            (give, responder, take)
+        } else {
+            (take, offerer, give)
+        };
+        self.check_cost(color, &own)?;
+        self.check_cost(other, &owed).map_err(|e| match e {
+            Illegal::CannotAfford { missing } => Illegal::OtherSideCannotAfford {
+                color: other,
+                missing,
+            },
+            e => e,
+        })
+    }
+
+    /// The offer on the table, if `terms` are its terms
+    fn check_open_trade(&self, terms: (FreqDeck, FreqDeck)) -> Result<&DomesticTrade, Illegal> {
+        let trade = self.domestic_trade.as_ref().ok_or(Illegal::WrongPrompt {
+            prompt: self.get_action_prompt(),
+        })?;
+        if (trade.give, trade.take) == terms {
+            Ok(trade)
+        } else {
+            Err(Illegal::NotTheOpenTrade {
+                give: trade.give,
+                take: trade.take,
+            })
+        }
+    }
+
+    fn must_discard(&self, color: u8) -> bool {
+        self.get_player_hand(color).iter().sum::<u8>() > self.config.discard_limit
+    }
+
+    /// Node of `color`'s most recent building
+    fn newest_building_node(&self, color: u8) -> Option<NodeId> {
+        match self.buildings_by_color.get(&color)?.last()? {
+            Building::Settlement(_, node_id) | Building::City(_, node_id) => Some(*node_id),
+        }
+    }
+}
+
+/// Cards given and asked for, never of the same resource
+fn check_terms(give: FreqDeck, take: FreqDeck) -> Result<(), Illegal> {
+    let overlaps = give.iter().zip(take).any(|(&g, t)| g > 0 && t > 0);
+    if give == [0; 5] || take == [0; 5] || overlaps {
+        return Err(Illegal::InvalidOffer);
+    }
+    Ok(())
+}
+
+fn check_resource(resource: u8) -> Result<(), Illegal> {
+    if resource < 5 {
+        Ok(())
+    } else {
+        Err(Illegal::UnknownResource { resource })
+    }
+}
+
+/// Action kinds the engine never offers
+fn unsupported(action: &Action) -> Option<&'static str> {
+    match action {
+        #[cfg(test)]
+        Action::DebugPanic { .. } => Some(action.kind()),
+        #[cfg(any(test, feature = "debug-actions"))]
+        Action::DebugSetState { .. } => Some(action.kind()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{
+        GameConfiguration, MapType, RobberRules, StateEdit, TeamConfig, TeamVictory, VariantConfig,
+    };
+    use crate::map_instance::MapInstance;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::collections::{BTreeMap, HashSet};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_initial_settlement_next_to_another_is_too_close() {
+        let mut state = State::new_base();
+        let node_id = *state.board_buildable_ids.iter().min().unwrap();
+        let color = state.get_current_color();
+        state.apply_action(Action::BuildSettlement { color, node_id });
+        assert_eq!(
+            state.validate_action(&Action::BuildSettlement { color, node_id: 40 }),
+            Err(Illegal::WrongPrompt {
+                prompt: ActionPrompt::BuildInitialRoad
+            })
+        );
+        let road = state.generate_playable_actions()[0];
+        state.apply_action(road);
+
+        let next = state.get_current_color();
+        let neighbor = state.map_instance.get_neighbor_nodes(node_id)[0];
+        assert_eq!(
+            state.validate_action(&Action::BuildSettlement {
+                color: next,
+                node_id: neighbor,
+            }),
+            Err(Illegal::TooCloseToSettlement { node_id: neighbor })
+        );
+        assert_eq!(
+            state.validate_action(&Action::BuildSettlement {
+                color: next,
+                node_id,
+            }),
+            Err(Illegal::NodeTaken {
+                node_id,
+                owner: color
+            })
+        );
+        assert_eq!(
+            state.validate_action(&Action::BuildSettlement { color, node_id: 40 }),
+            Err(Illegal::NotYourTurn { current: next })
+        );
+    }
+
+    #[test]
+    fn test_play_turn_reasons() {
+        let mut state = State::new_base();
+        while state.is_initial_build_phase() {
+            let action = state.generate_playable_actions()[0];
+            state.apply_action(action);
+        }
+        let color = state.get_current_color();
+        assert_eq!(
+            state.validate_action(&Action::BuyDevelopmentCard { color }),
+            Err(Illegal::MustRollFirst)
+        );
+        state.apply_action(Action::Roll {
+            color,
+            dice_opt: Some((3, 3)),
+        });
+
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([0, 0, 0, 1, 0]),
+        });
+        assert_eq!(
+            state.validate_action(&Action::BuildCity { color, node_id: 0 }),
+            Err(Illegal::CannotAfford {
+                missing: [0, 0, 0, 1, 3]
+            })
+        );
+        assert_eq!(
+            state.validate_action(&Action::PlayMonopoly { color, resource: 0 }),
+            Err(Illegal::NoDevCard {
+                card: DevCard::Monopoly
+            })
+        );
+        assert_eq!(
+            state.validate_action(&Action::MaritimeTrade {
+                color,
+                give: 3,
+                take: 3,
+                ratio: 4,
+            }),
+            Err(Illegal::SameResource)
+        );
+        assert_eq!(state.validate_action(&Action::EndTurn { color }), Ok(()));
+    }
+
+    #[test]
+    fn test_any_discard_of_the_right_size_is_accepted() {
+        let mut state = State::new_base();
+        while state.is_initial_build_phase() {
+            let action = state.generate_playable_actions()[0];
+            state.apply_action(action);
+        }
+        let color = state.get_current_color();
+        state.apply_action(Action::DebugSetState {
+            color,
+            edit: StateEdit::Hand([5, 4, 0, 0, 0]),
+        });
+        state.apply_action(Action::Roll {
+            color,
+            dice_opt: Some((3, 4)),
+        });
+        assert_eq!(state.get_action_prompt(), ActionPrompt::Discard);
+        assert_eq!(state.get_current_color(), color);
+
+        let discard = |resources| Action::Discard { color, resources };
+        assert_eq!(state.validate_action(&discard([0, 4, 0, 0, 0])), Ok(()));
+        assert_eq!(
+            state.validate_action(&discard([1, 4, 0, 0, 0])),
+            Err(Illegal::WrongDiscard { count: 4 })
+        );
+        assert_eq!(
+            state.validate_action(&discard([0, 0, 4, 0, 0])),
+            Err(Illegal::WrongDiscard { count: 4 })
+        );
+        // Leaving the cards out picks the suggested discard
+        assert_eq!(
+            state.canonical_form(discard([0; 5])),
+            discard(state.suggested_discard(color))
+        );
+
+        state.apply_action(discard([0, 4, 0, 0, 0]));
+        assert_eq!(state.get_player_hand(color), &[5, 0, 0, 0, 0]);
+    }
+
+    /// Every action a client could propose for the current color, legal or not: each
+    /// action kind over all nodes, both orientations of every edge, every tile and
+    /// victim, every resource combination and trade ratio, one-for-one offers and
+    /// counter-offers, answers to the offer on the table, and discards around the
+    /// suggested one
+    fn candidate_actions(state: &State) -> Vec<Action> {
+        let color = state.get_current_color();
+        let map = &state.map_instance;
+        let mut actions = vec![
+            Action::Roll {
+                color,
+                dice_opt: None,
+            },
+            Action::Discard {
+                color,
+                resources: state.suggested_discard(color),
+            },
+            Action::BuyDevelopmentCard { color },
+            Action::PlayKnight { color },
+            Action::PlayRoadBuilding { color },
+            Action::EndTurn { color },
+            Action::PassBuildPhase { color },
+            Action::RejectTrade { color },
+            Action::CancelTrade { color },
+        ];
+        let terms = state
+            .get_domestic_trade()
+            .map_or(([1, 0, 0, 0, 0], [0, 1, 0, 0, 0]), |trade| {
+                (trade.give, trade.take)
+            });
+        actions.push(Action::AcceptTrade {
+            color,
+            trade: terms,
+        });
+        actions.push(Action::CounterTrade {
+            color,
+            trade: terms,
+        });
+        for trade in state.counter_offer_candidates(color) {
+            actions.push(Action::CounterTrade { color, trade });
+        }
+        // Swapping one card of the suggested discard for another, or one card too many
+        // or too few
+        let suggested = state.suggested_discard(color);
+        for a in 0..5 {
+            let mut more = suggested;
+            more[a] += 1;
+            actions.push(Action::Discard {
+                color,
+                resources: more,
+            });
+            if suggested[a] == 0 {
+                continue;
+            }
+            let mut fewer = suggested;
+            fewer[a] -= 1;
+            actions.push(Action::Discard {
+                color,
+                resources: fewer,
+            });
+            for b in (0..5).filter(|&b| b != a) {
+                let mut swapped = fewer;
+                swapped[b] += 1;
+                actions.push(Action::Discard {
+                    color,
+                    resources: swapped,
+                });
+            }
+        }
+        for acceptee in 0..state.get_num_players() {
+            actions.push(Action::ConfirmTrade {
+                color,
+                trade: (terms.0, terms.1, acceptee),
+            });
+        }
+        for &node_id in &map.land_nodes {
+            actions.push(Action::BuildSettlement { color, node_id });
+            actions.push(Action::BuildCity { color, node_id });
+        }
+        for &(a, b) in &map.land_edges {
+            actions.push(Action::BuildRoad {
+                color,
+                edge_id: (a, b),
+            });
+            actions.push(Action::BuildRoad {
+                color,
+                edge_id: (b, a),
+            });
+        }
+        for &(a, b) in &map.sea_edges {
+            actions.push(Action::BuildShip {
+                color,
+                edge_id: (a, b),
+            });
+            actions.push(Action::BuildShip {
+                color,
+                edge_id: (b, a),
+            });
+            for from in state.get_ships_for_color(color) {
+                actions.push(Action::MoveShip {
+                    color,
+                    from,
+                    to: (a, b),
+                });
+            }
+        }
+        for a in 0..5 {
+            actions.push(Action::PlayMonopoly { color, resource: a });
+            actions.push(Action::ChooseGold { color, resource: a });
+            actions.push(Action::PlayYearOfPlenty {
+                color,
+                resources: (a, None),
+            });
+            for b in 0..5 {
+                actions.push(Action::PlayYearOfPlenty {
+                    color,
+                    resources: (a, Some(b)),
+                });
+                for ratio in 2..=4 {
+                    actions.push(Action::MaritimeTrade {
+                        color,
+                        give: a,
+                        take: b,
+                        ratio,
+                    });
+                }
+                let mut give = [0; 5];
+                give[a as usize] = 1;
+                let mut take = [0; 5];
+                take[b as usize] = 1;
+                actions.push(Action::OfferTrade {
+                    color,
+                    trade: (give, take),
+                });
+                actions.push(Action::CounterTrade {
+                    color,
+                    trade: (give, take),
+                });
+            }
+            for teammate in 0..state.get_num_players() {
+                actions.push(Action::GiftResource {
+                    color,
+                    teammate,
+                    resource: a,
+                });
+            }
+        }
+        for &coordinate in map.land_tiles.keys() {
+            actions.push(Action::MoveRobber {
+                color,
+                coordinate,
+                victim_opt: None,
+            });
+            for victim in 0..state.get_num_players() {
+                actions.push(Action::MoveRobber {
+                    color,
+                    coordinate,
+                    victim_opt: Some(victim),
+                });
+            }
+        }
+        actions
+    }
+
+    /// Actions validation accepts that generation deliberately leaves out: discards other
+    /// than the suggested one, and counter-offers, which bots build themselves from
+    /// counter_offer_candidates. Anything else accepted must also be generated.
+    fn is_validate_only(state: &State, action: &Action) -> bool {
+        match *action {
+            Action::Discard { color, resources } => resources != state.suggested_discard(color),
+            Action::CounterTrade { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Rule sets the fuzz walks: the standard game plus each variant that changes what
+    /// is legal
+    fn fuzz_configurations() -> Vec<GameConfiguration> {
+        let base = GameConfiguration::default;
+        vec![
+            base(),
+            GameConfiguration {
+                num_players: 2,
+                discard_limit: 4,
+                ..base()
+            },
+            GameConfiguration {
+                num_players: 3,
+                map_type: MapType::Mini,
+                ..base()
+            },
+            GameConfiguration {
+                teams: Some(TeamConfig::two_vs_two(TeamVictory::CombinedPoints)),
+                ..base()
+            },
+            GameConfiguration {
+                robber: RobberRules {
+                    protect_up_to_vps: Some(3),
+                    allow_desert: false,
+                    allow_steal: false,
+                },
+                ..base()
+            },
+            GameConfiguration {
+                variants: VariantConfig {
+                    speed_rounds: true,
+                    harbormaster: true,
+                    ..Default::default()
+                },
+                ..base()
+            },
+            GameConfiguration::builder().num_players(6).build().unwrap(),
+            GameConfiguration {
+                map_type: MapType::Seafarers,
+                ..base()
+            },
+        ]
+    }
+
+    /// Walks random games under every fuzz configuration and checks, in each position
+    /// reached, that validation accepts exactly the generated actions, apart from the
+    /// validate-only ones. Mismatches are reported grouped by prompt.
+    #[test]
+    fn test_validation_accepts_exactly_the_generated_actions() {
+        const GAMES_PER_CONFIGURATION: u64 = 3;
+        const MAX_MOVES: usize = 500;
+
+        let mut states_per_prompt: BTreeMap<String, usize> = BTreeMap::new();
+        let mut mismatches: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut validate_only: BTreeMap<&str, usize> = BTreeMap::new();
+        for (c, config) in fuzz_configurations().into_iter().enumerate() {
+            let config = Arc::new(config);
+            let map = MapInstance::shared(&config.map_type, 0);
+            for game in 0..GAMES_PER_CONFIGURATION {
+                let seed = c as u64 * 100 + game;
+                let mut state = State::new_with_seed(config.clone(), map.clone(), seed);
+                let mut rng = XorShiftRng::seed_from_u64(seed);
+                for _ in 0..MAX_MOVES {
+                    if state.winner().is_some() {
+                        break;
+                    }
+                    let prompt = format!("{:?}", state.get_action_prompt());
+                    *states_per_prompt.entry(prompt.clone()).or_default() += 1;
+
+                    let generated = state.generate_playable_actions();
+                    let candidates = candidate_actions(&state);
+                    let candidate_set: HashSet<_> = candidates.iter().copied().collect();
+                    let accepted: HashSet<_> = candidates
+                        .into_iter()
+                        .filter(|action| state.validate_action(action).is_ok())
+                        .collect();
+                    let generated_set: HashSet<_> = generated.iter().copied().collect();
+                    for action in generated_set.symmetric_difference(&accepted) {
+                        if !generated_set.contains(action) && is_validate_only(&state, action) {
+                            *validate_only.entry(action.kind()).or_default() += 1;
+                            continue;
+                        }
+                        let side = if !candidate_set.contains(action) {
+                            "generated, outside the candidates"
+                        } else if generated_set.contains(action) {
+                            "generated, refused"
+                        } else {
+                            "accepted, not generated"
+                        };
+                        mismatches.entry(prompt.clone()).or_default().push(format!(
+                            "config {c} seed {seed}: {side}: {action:?} ({:?})",
+                            state.validate_action(action)
+                        ));
+                    }
+
+                    // Some moves are generated in hash order, so pick from a fixed order
+                    // to walk the same games on every run
+                    let mut ordered = generated;
+                    ordered.sort_by_cached_key(|action| format!("{action:?}"));
+                    let action = *ordered.choose(&mut rng).unwrap();
+                    state.apply_action(action);
+                }
+            }
+        }
+
+        let report: Vec<String> = mismatches
+            .iter()
+            .map(|(prompt, found)| {
+                format!(
+                    "{prompt}: {} mismatches in {} states, e.g. {}",
+                    found.len(),
+                    states_per_prompt[prompt],
+                    found[..found.len().min(3)].join("; ")
+                )
+            })
+            .collect();
+        assert!(report.is_empty(), "{}", report.join("\n"));
+        for prompt in [
+            ActionPrompt::BuildInitialSettlement,
+            ActionPrompt::BuildInitialRoad,
+            ActionPrompt::PlayTurn,
+            ActionPrompt::Discard,
+            ActionPrompt::MoveRobber,
+            ActionPrompt::DecideTrade,
+            ActionPrompt::DecideAcceptees,
+            ActionPrompt::SpecialBuild,
+            ActionPrompt::ChooseGold,
+        ] {
+            assert!(
+                states_per_prompt.contains_key(&format!("{prompt:?}")),
+                "no {prompt:?} state reached"
+            );
+        }
+        assert!(states_per_prompt.values().sum::<usize>() > 5000);
+        // Both validate-only kinds were proposed and accepted somewhere
+        assert_eq!(
+            validate_only.keys().copied().collect::<Vec<_>>(),
+            ["CounterTrade", "Discard"]
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{Building, State};
+use crate::enums::DiceMode;
+
+/// Harbor points needed before anyone can hold the Harbormaster card
+const HARBORMASTER_MIN_POINTS: u8 = 3;
+const HARBORMASTER_VPS: u8 = 2;
+/// The New Year card is shuffled in among this many cards at the bottom of the deck
+const NEW_YEAR_DEPTH: usize = 5;
+
+/// A card of the event-card dice deck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventCard {
+    Roll(u8, u8),
+    NewYear,
+}
+
+impl State {
+    /// Harbor points for `color`: 1 per settlement and 2 per city on a harbor node
+    pub fn harbor_points(&self, color: u8) -> u8 {
+        let port_nodes = self.map_instance.get_port_nodes();
+        self.buildings_by_color
+            .get(&color)
+            .map(|buildings| {
+                buildings
+                    .iter()
+                    .map(|building| match building {
+                        Building::Settlement(_, node_id) if port_nodes.contains_key(node_id) => 1,
+                        Building::City(_, node_id) if port_nodes.contains_key(node_id) => 2,
+                        _ => 0,
+                    })
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn get_harbormaster_color(&self) -> Option<u8> {
+        self.harbormaster_color
+    }
+
+    /// Reassign the Harbormaster card after a build. Like Largest Army, a challenger
+    /// has to strictly exceed the holder to take it.
+    pub(super) fn maintain_harbormaster(&mut self) {
+        if !self.config.variants.harbormaster {
+            return;
+        }
+
+        let mut best_points = self.harbormaster_points;
+        let mut best_color = self.harbormaster_color;
+        if let Some(holder) = self.harbormaster_color {
+            best_points = self.harbor_points(holder);
+        }
+        for color in 0..self.get_num_players() {
+            let points = self.harbor_points(color);
+            if points >= HARBORMASTER_MIN_POINTS && points > best_points {
+                best_points = points;
+                best_color = Some(color);
+            }
+        }
+        self.harbormaster_points = best_points;
+
+        if best_color == self.harbormaster_color {
+            return;
+        }
+        if let Some(prev_owner) = self.harbormaster_color {
+            self.sub_victory_points(prev_owner, HARBORMASTER_VPS);
+        }
+        if let Some(new_owner) = best_color {
+            self.add_victory_points(new_owner, HARBORMASTER_VPS);
+        }
+        self.harbormaster_color = best_color;
+    }
+
+    /// Draw the next roll from the configured dice source
+    pub(super) fn draw_dice(&mut self) -> (u8, u8) {
+        match self.config.variants.dice {
+            DiceMode::Standard => (self.rng.gen_range(1..=6), self.rng.gen_range(1..=6)),
+            DiceMode::EventCards => self.draw_event_card(),
+            DiceMode::Balanced => {
+                if self.dice_bag.is_empty() {
+                    self.dice_bag = all_outcomes().collect();
+                    self.dice_bag.shuffle(&mut self.rng);
+                }
+                self.dice_bag.pop().expect("dice bag was just refilled")
+            }
+        }
+    }
+
+    /// Cards left in the event deck (before the next reshuffle)
+    pub fn get_event_deck(&self) -> &[EventCard] {
+        &self.event_deck
+    }
+
+    fn draw_event_card(&mut self) -> (u8, u8) {
+        loop {
+            match self.event_deck.pop() {
+                Some(EventCard::Roll(die1, die2)) => return (die1, die2),
+                Some(EventCard::NewYear) => {
+                    log::info!("🎴 New Year drawn → reshuffling event deck");
+                    self.shuffle_event_deck(false);
+                }
+                // Only empty before the very first roll: the New Year card always
+                // comes up before the deck runs out.
+                None => self.shuffle_event_deck(true),
+            }
+        }
+    }
+
+    /// Shuffle all 36 outcomes plus the New Year card. The opening deck keeps 7s out of
+    /// the first round so nobody gets robbed before everyone has rolled once.
+    fn shuffle_event_deck(&mut self, opening: bool) {
+        let mut deck: Vec<EventCard> = all_outcomes()
+            .map(|(die1, die2)| EventCard::Roll(die1, die2))
+            .collect();
+        deck.shuffle(&mut self.rng);
+
+        if opening {
+            let first_round = self.get_num_players() as usize;
+            let top = deck.len() - first_round;
+            for i in top..deck.len() {
+                if is_seven(deck[i]) {
+                    let swap_with = (NEW_YEAR_DEPTH..top)
+                        .filter(|&j| !is_seven(deck[j]))
+                        .collect::<Vec<_>>()
+                        .choose(&mut self.rng)
+                        .copied()
+                        .expect("deck has enough non-7 cards");
+                    deck.swap(i, swap_with);
+                }
+            }
+        }
+
+        let new_year_at = self.rng.gen_range(0..NEW_YEAR_DEPTH);
+        deck.insert(new_year_at, EventCard::NewYear);
+        self.event_deck = deck;
+    }
+}
+
+fn all_outcomes() -> impl Iterator<Item = (u8, u8)> {
+    (1..=6).flat_map(|die1| (1..=6).map(move |die2| (die1, die2)))
+}
+
+fn is_seven(card: EventCard) -> bool {
+    matches!(card, EventCard::Roll(die1, die2) if die1 + die2 == 7)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck_slices::CITY_COST;
+    use crate::enums::{Action, StateEdit, VariantConfig};
+    use std::sync::Arc;
+
+    fn harbormaster_state() -> State {
+        let mut state = State::new_base();
+        let mut config = (*state.config).clone();
+        config.variants = VariantConfig {
+            harbormaster: true,
+            ..Default::default()
+        };
+        state.config = Arc::new(config);
+        state
+    }
+
+    fn port_nodes_apart(state: &State, count: usize) -> Vec<u8> {
+        let mut chosen: Vec<u8> = Vec::new();
+        let mut port_nodes: Vec<u8> = state
+            .map_instance
+            .get_port_nodes()
+            .keys()
+            .copied()
+            .collect();
+        port_nodes.sort();
+        for node_id in port_nodes {
+            let neighbors = state.map_instance.get_neighbor_nodes(node_id);
+            if chosen
+                .iter()
+                .all(|c| *c != node_id && !neighbors.contains(c))
+            {
+                chosen.push(node_id);
+            }
+            if chosen.len() == count {
+                break;
+            }
+        }
+        chosen
+    }
+
+    #[test]
+    fn test_harbormaster_needs_three_points_and_a_strict_lead() {
+        let mut state = harbormaster_state();
+        let nodes = port_nodes_apart(&state, 5);
+        for (color, edit) in [
+            (0, StateEdit::InitialBuildPhase(false)),
+            (0, StateEdit::Hand([3, 3, 3, 3, 0])),
+            (1, StateEdit::Hand([2, 2, 2, 2, 0])),
+        ] {
+            state.apply_action(Action::DebugSetState { color, edit });
+        }
+
+        state.build_settlement(0, nodes[0]);
+        state.build_settlement(0, nodes[1]);
+        state.maintain_harbormaster();
+        assert_eq!(state.get_harbormaster_color(), None);
+
+        state.build_settlement(0, nodes[2]);
+        state.maintain_harbormaster();
+        assert_eq!(state.get_harbormaster_color(), Some(0));
+        assert_eq!(state.get_actual_victory_points(0), 3 + HARBORMASTER_VPS);
+
+        // Matching the holder is not enough; exceeding is
+        state.build_settlement(1, nodes[3]);
+        state.build_settlement(1, nodes[4]);
+        state.apply_action(Action::DebugSetState {
+            color: 1,
+            edit: StateEdit::Hand(CITY_COST),
+        });
+        state.apply_action(Action::BuildCity {
+            color: 1,
+            node_id: nodes[3],
+        });
+        assert_eq!(state.harbor_points(1), 3);
+        assert_eq!(state.get_harbormaster_color(), Some(0));
+
+        state.apply_action(Action::DebugSetState {
+            color: 1,
+            edit: StateEdit::Hand(CITY_COST),
+        });
+        state.apply_action(Action::BuildCity {
+            color: 1,
+            node_id: nodes[4],
+        });
+        assert_eq!(state.get_harbormaster_color(), Some(1));
+        assert_eq!(state.get_actual_victory_points(0), 3);
+    }
+
+    fn event_card_state() -> State {
+        let mut state = State::new_base();
+        let mut config = (*state.config).clone();
+        config.variants.dice = DiceMode::EventCards;
+        state.config = Arc::new(config);
+        state
+    }
+
+    #[test]
+    fn test_event_deck_covers_every_outcome_once_per_cycle() {
+        let mut state = event_card_state();
+        let first = state.draw_dice();
+        assert_ne!(first.0 + first.1, 7, "no 7 in the opening round");
+
+        let deck = state.get_event_deck();
+        let new_year_at = deck.iter().position(|&c| c == EventCard::NewYear);
+        assert!(new_year_at.is_some_and(|at| at < NEW_YEAR_DEPTH));
+        let mut outcomes: Vec<(u8, u8)> = deck
+            .iter()
+            .filter_map(|&card| match card {
+                EventCard::Roll(die1, die2) => Some((die1, die2)),
+                EventCard::NewYear => None,
+            })
+            .chain(std::iter::once(first))
+            .collect();
+        outcomes.sort();
+        outcomes.dedup();
+        assert_eq!(outcomes.len(), 36);
+
+        // Drawing through the New Year card reshuffles instead of running dry
+        for _ in 0..100 {
+            state.draw_dice();
+            assert!(!state.get_event_deck().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_balanced_dice_empty_the_bag_before_refilling() {
+        let mut state = State::new_base();
+        let mut config = (*state.config).clone();
+        config.variants.dice = DiceMode::Balanced;
+        state.config = Arc::new(config);
+
+        for _ in 0..2 {
+            let mut rolls: Vec<(u8, u8)> = (0..36).map(|_| state.draw_dice()).collect();
+            rolls.sort();
+            assert_eq!(rolls, all_outcomes().collect::<Vec<_>>());
+        }
+    }
+}
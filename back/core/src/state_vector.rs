@@ -1,6 +1,7 @@
 use rand::seq::SliceRandom;
+use rand::Rng;
 
-use crate::enums::COLORS;
+use crate::enums::{COLORS, EXTENSION_MIN_PLAYERS};
 
 pub type StateVector = Vec<u8>;
 
@@ -42,14 +43,32 @@ pub const PLAYER_DEVCARDS_OFFSET: usize = 6;
 pub const PLAYER_DEVCARDS_SIZE: usize = 5;
 pub const PLAYER_PLAYED_DEVCARDS_OFFSET: usize = 11;
 pub const PLAYER_PLAYED_DEVCARDS_SIZE: usize = 4;
+// Development cards bought this turn, which may not be played until the next one. Kept
+// after every player's block, so vectors written before it only need zeros appended.
+pub const PLAYER_BOUGHT_DEVCARDS_SIZE: usize = 4;
+// 5–6 player games deal from a bigger deck: the cards past the base deck's (six knights
+// and one of each progress card) come after the bought-this-turn block
+pub const EXTENSION_DEV_CARDS: usize = 9;
+// Settlements, cities and roads each player has left to build, kept last. They follow
+// from the board, so vectors written before this block get it recounted.
+pub const PLAYER_PIECES_LEFT_SIZE: usize = 3;
+pub const SETTLEMENTS_LEFT_OFFSET: usize = 0;
+pub const CITIES_LEFT_OFFSET: usize = 1;
+pub const ROADS_LEFT_OFFSET: usize = 2;
 
 // Resource constants
 pub const MAX_RESOURCE_COUNT: u8 = 19;
+pub const EXTENSION_RESOURCE_COUNT: u8 = 24;
 pub const MAX_DEV_CARDS: usize = 25;
 pub const MAX_VICTORY_POINTS: u8 = 12;
 pub const NUM_RESOURCES: usize = 5;
 pub const FREE_ROADS_MAX: u8 = 2;
 
+// Pieces in each player's supply
+pub const TOTAL_SETTLEMENTS_PER_PLAYER: u8 = 5;
+pub const TOTAL_CITIES_PER_PLAYER: u8 = 4;
+pub const TOTAL_ROADS_PER_PLAYER: u8 = 15;
+
 /// This is in theory not needed since we use a vector and we can
 /// .push() to it. But since we made it, leaving in here in case
 /// we want to switch to an array implementation and it serves
@@ -96,10 +115,41 @@ pub fn get_state_array_size(num_players: usize) -> usize {
     // Players state
     size += n; // Color seating order
     size += (1 + PLAYER_RESOURCES_SIZE + PLAYER_DEVCARDS_SIZE + PLAYER_PLAYED_DEVCARDS_SIZE) * n;
+    size += PLAYER_BOUGHT_DEVCARDS_SIZE * n; // Player<i>_<devcard>_Bought_This_Turn
+
+    size += dev_deck_size(n as u8) - MAX_DEV_CARDS; // Extension Development Cards
+    size += PLAYER_PIECES_LEFT_SIZE * n; // Player<i>_<piece>_Left
 
     size
 }
 
+/// Cards of each resource the bank starts with
+pub fn bank_resource_count(num_players: u8) -> u8 {
+    if num_players >= EXTENSION_MIN_PLAYERS {
+        EXTENSION_RESOURCE_COUNT
+    } else {
+        MAX_RESOURCE_COUNT
+    }
+}
+
+pub fn dev_deck_size(num_players: u8) -> usize {
+    if num_players >= EXTENSION_MIN_PLAYERS {
+        MAX_DEV_CARDS + EXTENSION_DEV_CARDS
+    } else {
+        MAX_DEV_CARDS
+    }
+}
+
+/// Vector index of the card `position` draws into the deck
+pub fn dev_deck_index(num_players: u8, position: usize) -> usize {
+    if position < MAX_DEV_CARDS {
+        return DEV_BANK_START_INDEX + position;
+    }
+    let n = num_players as usize;
+    PLAYER_STATE_START_INDEX + n + n * (PLAYER_STATE_SIZE + PLAYER_BOUGHT_DEVCARDS_SIZE) + position
+        - MAX_DEV_CARDS
+}
+
 pub fn bank_resource_index(resource: u8) -> usize {
     if resource > 4 {
         panic!("Invalid resource index");
@@ -146,17 +196,41 @@ pub fn player_played_devhand_slice(num_players: u8, color: u8) -> std::ops::Rang
     start..start + PLAYER_PLAYED_DEVCARDS_SIZE
 }
 
+pub fn player_bought_devhand_slice(num_players: u8, color: u8) -> std::ops::Range<usize> {
+    let n = num_players as usize;
+    let start = PLAYER_STATE_START_INDEX
+        + n
+        + n * PLAYER_STATE_SIZE
+        + color as usize * PLAYER_BOUGHT_DEVCARDS_SIZE;
+    start..start + PLAYER_BOUGHT_DEVCARDS_SIZE
+}
+
+/// Where the pieces-left block starts, right after the extension development cards
+pub fn pieces_left_start(num_players: u8) -> usize {
+    let n = num_players as usize;
+    PLAYER_STATE_START_INDEX
+        + n
+        + n * (PLAYER_STATE_SIZE + PLAYER_BOUGHT_DEVCARDS_SIZE)
+        + dev_deck_size(num_players)
+        - MAX_DEV_CARDS
+}
+
+pub fn player_pieces_left_slice(num_players: u8, color: u8) -> std::ops::Range<usize> {
+    let start = pieces_left_start(num_players) + color as usize * PLAYER_PIECES_LEFT_SIZE;
+    start..start + PLAYER_PIECES_LEFT_SIZE
+}
+
 pub fn get_free_roads_available(vector: &StateVector) -> u8 {
     vector[FREE_ROADS_AVAILABLE_INDEX]
 }
 
 // TODO: I'm not sure if it makes more sense to have this in state.rs?
-pub fn take_next_dev_card(vector: &mut StateVector) -> Option<u8> {
+pub fn take_next_dev_card(vector: &mut StateVector, num_players: u8) -> Option<u8> {
     let ptr = vector[DEV_BANK_PTR_INDEX] as usize;
-    if ptr >= MAX_DEV_CARDS {
+    if ptr >= dev_deck_size(num_players) {
         return None;
     }
-    let card = vector[DEV_BANK_START_INDEX + ptr];
+    let card = vector[dev_deck_index(num_players, ptr)];
     vector[DEV_BANK_PTR_INDEX] += 1;
     Some(card)
 }
@@ -177,8 +251,9 @@ pub fn take_next_dev_card(vector: &mut StateVector) -> Option<u8> {
 /// TODO: This is not the only Data Structure to do rollouts.
 /// We recommend additional caches and aux data structures for
 ///  faster rollouts. This one is compact optimized for copying.
-/// TODO: Accept a seed for deterministic tests
-pub fn initialize_state(num_players: u8) -> Vec<u8> {
+/// Randomness (the development card deck order) is drawn from `rng`, so a seeded
+/// rng yields a reproducible starting vector.
+pub fn initialize_state<R: Rng + ?Sized>(num_players: u8, rng: &mut R) -> Vec<u8> {
     log::debug!(
         "initialize_state: num_players={}, PLAYER_STATE_START_INDEX={}",
         num_players,
@@ -194,14 +269,15 @@ pub fn initialize_state(num_players: u8) -> Vec<u8> {
 
     // Initialize Bank Resources
     for i in BANK_RESOURCE_SLICE {
-        vector[i] = MAX_RESOURCE_COUNT;
+        vector[i] = bank_resource_count(num_players);
     }
 
     // Initialize Bank Development Cards
-    // TODO: Shuffle
-    let mut listdeck = starting_dev_listdeck();
-    listdeck.shuffle(&mut rand::thread_rng());
-    vector[DEV_BANK_START_INDEX..DEV_BANK_END_INDEX].copy_from_slice(&listdeck);
+    let mut listdeck = starting_dev_listdeck(num_players);
+    listdeck.shuffle(rng);
+    for (position, card) in listdeck.into_iter().enumerate() {
+        vector[dev_deck_index(num_players, position)] = card;
+    }
     vector[DEV_BANK_PTR_INDEX] = 0;
 
     // Initialize Game Controls
@@ -259,13 +335,22 @@ pub fn initialize_state(num_players: u8) -> Vec<u8> {
         player_state_start += PLAYER_STATE_SIZE;
     }
 
+    // Player<i>_<piece>_Left: everyone starts with the full supply
+    for color in 0..num_players {
+        vector[player_pieces_left_slice(num_players, color)].copy_from_slice(&[
+            TOTAL_SETTLEMENTS_PER_PLAYER,
+            TOTAL_CITIES_PER_PLAYER,
+            TOTAL_ROADS_PER_PLAYER,
+        ]);
+    }
+
     vector
 }
 
 // Simple implementation directly here to avoid import issues
-fn starting_dev_listdeck() -> Vec<u8> {
-    // Return a Vec with exactly 25 elements to match the DEV_BANK slice
-    let mut deck = Vec::with_capacity(25);
+fn starting_dev_listdeck(num_players: u8) -> Vec<u8> {
+    // 25 elements to match the DEV_BANK slice, plus the extension cards
+    let mut deck = Vec::with_capacity(dev_deck_size(num_players));
     // Knights (14)
     deck.extend(vec![0; 14]); // 0 = Knight
                               // Year of Plenty (2)
@@ -276,6 +361,9 @@ fn starting_dev_listdeck() -> Vec<u8> {
     deck.extend(vec![3; 2]); // 3 = Monopoly
                              // Victory Points (5)
     deck.extend(vec![4; 5]); // 4 = Victory Point
+    if num_players >= EXTENSION_MIN_PLAYERS {
+        deck.extend([0, 0, 0, 0, 0, 0, 1, 2, 3]);
+    }
 
     deck
 }
@@ -289,13 +377,27 @@ mod tests {
     fn test_initialize_state_vector() {
         let n: usize = 2;
         let result = get_state_array_size(n);
-        assert_eq!(result, 301);
+        assert_eq!(result, 315);
     }
 
     #[test]
     fn test_initialize_state() {
-        let state = initialize_state(2);
-        assert_eq!(state.len(), 301);
+        let state = initialize_state(2, &mut rand::thread_rng());
+        assert_eq!(state.len(), 315);
+        assert_eq!(state[player_pieces_left_slice(2, 1)], [5, 4, 15]);
+    }
+
+    #[test]
+    fn test_extension_deck_and_bank() {
+        let mut vector = initialize_state(6, &mut rand::thread_rng());
+        assert_eq!(vector.len(), get_state_array_size(6));
+        assert_eq!(vector[BANK_RESOURCE_SLICE], [EXTENSION_RESOURCE_COUNT; 5]);
+
+        let mut counts = [0; 5];
+        while let Some(card) = take_next_dev_card(&mut vector, 6) {
+            counts[card as usize] += 1;
+        }
+        assert_eq!(counts, [20, 3, 3, 3, 5]);
     }
 
     #[test]
@@ -0,0 +1,159 @@
+// Think time - how long each seat takes to answer a prompt. Measured on the server, from
+// the moment a prompt opens (the game starts or the previous action is applied) until the
+// seat's action is received, so network latency counts against the seat.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Default)]
+struct SeatClock {
+    prompts: u32,
+    total: Duration,
+    longest: Duration,
+}
+
+/// Running timer for the open prompt plus what each seat has used so far
+#[derive(Debug, Clone, Default)]
+pub struct ThinkClock {
+    prompt_opened: Option<Instant>,
+    seats: Vec<SeatClock>,
+}
+
+impl ThinkClock {
+    /// Open a new prompt now
+    pub fn start(&mut self) {
+        self.prompt_opened = Some(Instant::now());
+    }
+
+    /// Charge the time since the prompt opened to `seat`, then open the next prompt.
+    /// Returns the time charged, if a prompt was open.
+    pub fn record(&mut self, seat: usize) -> Option<Duration> {
+        let now = Instant::now();
+        let elapsed = self.prompt_opened.map(|opened| now - opened);
+        if let Some(elapsed) = elapsed {
+            self.charge(seat, elapsed);
+        }
+        self.prompt_opened = Some(now);
+        elapsed
+    }
+
+    fn charge(&mut self, seat: usize, elapsed: Duration) {
+        if self.seats.len() <= seat {
+            self.seats.resize(seat + 1, SeatClock::default());
+        }
+        let clock = &mut self.seats[seat];
+        clock.prompts += 1;
+        clock.total += elapsed;
+        clock.longest = clock.longest.max(elapsed);
+    }
+
+    /// Rebuild the clock from saved think times, as `(seat, time)` per timed action,
+    /// with `open` already spent on the prompt that is still open
+    pub fn restore(
+        &mut self,
+        charges: impl IntoIterator<Item = (usize, Duration)>,
+        open: Duration,
+    ) {
+        self.seats.clear();
+        for (seat, elapsed) in charges {
+            self.charge(seat, elapsed);
+        }
+        let now = Instant::now();
+        self.prompt_opened = Some(now.checked_sub(open).unwrap_or(now));
+    }
+
+    /// Time spent on the open prompt so far
+    pub fn elapsed(&self) -> Duration {
+        self.prompt_opened
+            .map(|opened| opened.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// Totals per seat, labelled with the seats' colors
+    pub fn summary(&self, colors: &[String]) -> Vec<SeatThinkTime> {
+        colors
+            .iter()
+            .enumerate()
+            .map(|(seat, color)| {
+                let clock = self.seats.get(seat).cloned().unwrap_or_default();
+                SeatThinkTime {
+                    color: color.clone(),
+                    prompts: clock.prompts,
+                    total_ms: clock.total.as_millis() as u64,
+                    average_ms: clock
+                        .total
+                        .checked_div(clock.prompts)
+                        .unwrap_or_default()
+                        .as_millis() as u64,
+                    longest_ms: clock.longest.as_millis() as u64,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Prompts answered by one seat and the time taken over them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatThinkTime {
+    pub color: String,
+    pub prompts: u32,
+    pub total_ms: u64,
+    pub average_ms: u64,
+    pub longest_ms: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_aggregates_per_seat() {
+        let mut clock = ThinkClock::default();
+        clock.charge(0, Duration::from_millis(300));
+        clock.charge(0, Duration::from_millis(900));
+        clock.charge(2, Duration::from_millis(50));
+
+        let colors = ["red", "blue", "white"].map(String::from);
+        let summary = clock.summary(&colors);
+        assert_eq!(
+            summary[0],
+            SeatThinkTime {
+                color: "red".into(),
+                prompts: 2,
+                total_ms: 1200,
+                average_ms: 600,
+                longest_ms: 900,
+            }
+        );
+        assert_eq!((summary[1].prompts, summary[1].average_ms), (0, 0));
+        assert_eq!(summary[2].longest_ms, 50);
+    }
+
+    #[test]
+    fn test_record_opens_the_next_prompt() {
+        let mut clock = ThinkClock::default();
+        assert_eq!(clock.elapsed(), Duration::ZERO);
+        // Nothing to charge before the first prompt opens
+        assert_eq!(clock.record(1), None);
+        assert!(clock.seats.is_empty());
+
+        assert!(clock.record(1).is_some());
+        assert_eq!(clock.seats[1].prompts, 1);
+    }
+
+    #[test]
+    fn test_restore_rebuilds_seat_totals() {
+        let mut clock = ThinkClock::default();
+        clock.charge(3, Duration::from_millis(10));
+        clock.restore(
+            [
+                (0, Duration::from_millis(400)),
+                (1, Duration::from_millis(200)),
+            ],
+            Duration::from_millis(250),
+        );
+        assert_eq!(clock.seats.len(), 2);
+        assert_eq!(clock.seats[0].total, Duration::from_millis(400));
+        assert!(clock.elapsed() >= Duration::from_millis(250));
+    }
+}
@@ -1,12 +1,131 @@
-use std::collections::HashMap;
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use crate::actions::{resource_to_u8, GameEvent, GameId, PlayerAction};
-use crate::errors::{CatanError, CatanResult, GameError, PlayerError};
-use crate::game::{Game, GameState};
+use crate::actions::{
+    resource_to_u8, resources_to_freqdeck, GameEvent, GameId, PartialAction, PlayerAction,
+};
+use crate::audit::{AuditReport, AuditSettings};
+use crate::auto_pass::AutoPass;
+use crate::clock::{Clock, SystemClock};
+use crate::commands::resolve_command;
+use crate::enums::{Action, ActionPrompt, GameConfigBuilder, MapType};
+use crate::errors::{
+    panic_message, CatanError, CatanResult, GameError, InfrastructureError, PlayerError,
+};
+use crate::game::{ActionPage, Game, GameDump, GameState, GameStats, SaveGame, VpHistory};
+use crate::hibernation::{HibernatedGame, HibernationStore};
+use crate::pace::Pace;
+use crate::palette::Palette;
 use crate::player_system::{Player, PlayerFactory};
+use crate::players::value::ValueWeights;
+use crate::players::EvalProfile;
+use crate::players::{analyze, AnalysisResult, DeliberationEntry, DeliberationLog};
+use crate::players::{BotPlayer as _, ValueFunctionPlayer};
+use crate::preferences::{PreferenceStore, UserPreferences};
+use crate::sharding::ShardRouter;
+use crate::speed::{self, SpeedOutcome, SpeedRound, SpeedWindow};
+use crate::state::validation::Legality;
+
+/// Bot mode for games whose moves are applied by an outside driver (e.g. `simulate --serve`).
+/// The service never decides moves for these games.
+pub const EXTERNAL_BOT_MODE: &str = "external";
+
+/// Sandbox ("what if") games kept at once; forking past this drops the oldest sandbox
+const MAX_SANDBOX_GAMES: usize = 32;
+
+/// Search limits for hint/analysis requests, kept short since they run on request
+const ANALYSIS_DEPTH: i32 = 4;
+const ANALYSIS_TIME_MS: u64 = 500;
+/// Search depth for the ladder champion ("human_hard" games)
+const HARD_BOT_DEPTH: i32 = 6;
+
+/// A named seat for games created with an explicit seating order
+#[derive(Debug, Clone)]
+pub struct Seat {
+    pub name: String,
+    pub is_bot: bool,
+}
+
+/// Who asks for the full history of a game, by exporting or forking it. The history
+/// carries the seed that decides every roll and card draw still to come, so a game in
+/// play goes only to admins and to the claimed account seated against bots in it.
+#[derive(Debug, Clone, Copy)]
+pub enum HistoryAccess<'a> {
+    Anyone,
+    Admin,
+    Account {
+        name: &'a str,
+        token: Option<&'a str>,
+    },
+}
+
+/// Table settings a client may pick when creating a game, on top of its mode and player
+/// count. Unset values keep the defaults of the configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableOptions {
+    pub vps_to_win: Option<u8>,
+    pub discard_limit: Option<u8>,
+    pub map_type: Option<MapType>,
+    /// Seed the board is generated from; the fixed board 0 when unset
+    pub map_seed: Option<u64>,
+    /// Bot type per seat ("random", "alphabeta" or "hard"); unset seats get the mode's bots
+    pub bots: Vec<Option<String>>,
+    /// Seat colors shown to players; the classic ones when unset
+    pub palette: Option<Palette>,
+}
+
+impl TableOptions {
+    /// Apply the chosen rule values to `config`
+    fn configure(&self, mut config: GameConfigBuilder) -> GameConfigBuilder {
+        if let Some(vps_to_win) = self.vps_to_win {
+            config = config.vps_to_win(vps_to_win);
+        }
+        if let Some(discard_limit) = self.discard_limit {
+            config = config.discard_limit(discard_limit);
+        }
+        if let Some(map_type) = &self.map_type {
+            config = config.map_type(map_type.clone());
+        }
+        config
+    }
+
+    /// Bot mode driving each seat, or `None` where the game's mode applies
+    fn seat_bot_modes(
+        &self,
+        num_players: u8,
+        human_seat: bool,
+    ) -> CatanResult<Vec<Option<String>>> {
+        if self.bots.len() > num_players as usize {
+            return Err(CatanError::Validation(format!(
+                "{} bot types for {num_players} players",
+                self.bots.len()
+            )));
+        }
+        self.bots
+            .iter()
+            .enumerate()
+            .map(|(seat, bot)| match bot.as_deref() {
+                None => Ok(None),
+                Some(_) if seat == 0 && human_seat => Err(CatanError::Validation(
+                    "Seat 0 is the human seat in this mode".to_string(),
+                )),
+                Some("random") => Ok(Some("random".to_string())),
+                Some("alphabeta") => Ok(Some("alphabeta".to_string())),
+                Some("hard") => Ok(Some("human_hard".to_string())),
+                Some(other) => Err(CatanError::Validation(format!(
+                    "Unknown bot type '{other}' for seat {seat}"
+                ))),
+            })
+            .collect()
+    }
+}
 
 /// Core application service for game management
 /// This is the main orchestration layer that coordinates between domain and infrastructure
@@ -23,6 +142,28 @@ pub struct GameService {
     games: Arc<RwLock<HashMap<GameId, Arc<RwLock<Game>>>>>,
     players: Arc<RwLock<HashMap<GameId, Vec<Player>>>>,
     bot_modes: Arc<RwLock<HashMap<GameId, String>>>,
+    /// Bot modes of the seats that override their game's mode
+    seat_bot_modes: Arc<RwLock<HashMap<GameId, Vec<Option<String>>>>>,
+    /// Sandbox games forked off other games, oldest first
+    sandboxes: Arc<RwLock<VecDeque<GameId>>>,
+    /// Open build windows of speed-round games
+    speed_rounds: Arc<RwLock<HashMap<GameId, SpeedRound>>>,
+    /// Saved per-account settings
+    preferences: Arc<PreferenceStore>,
+    /// Auto-pass settings of the seats that turned it on, per game
+    auto_pass: Arc<RwLock<HashMap<GameId, HashMap<u8, AutoPass>>>>,
+    /// Ladder champion weights for the "hard" bot (default weights when absent)
+    hard_bot_weights: Option<ValueWeights>,
+    /// Evaluation profile for the other alpha-beta bots (built-in settings when absent)
+    eval_profile: Option<EvalProfile>,
+    /// Where alpha-beta bots log their deliberation per game (not logged when absent)
+    deliberation_log: Option<DeliberationLog>,
+    /// Time for build windows and for the pauses of the bot loop
+    clock: Arc<dyn Clock>,
+    /// Where idle games are written when dropped from memory (kept in memory when absent)
+    hibernation: Option<HibernationStore>,
+    /// Which instance owns which games (this one owns all when absent)
+    router: Option<ShardRouter>,
 }
 
 impl GameService {
@@ -31,31 +172,262 @@ impl GameService {
             games: Arc::new(RwLock::new(HashMap::new())),
             players: Arc::new(RwLock::new(HashMap::new())),
             bot_modes: Arc::new(RwLock::new(HashMap::new())),
+            seat_bot_modes: Arc::new(RwLock::new(HashMap::new())),
+            sandboxes: Arc::new(RwLock::new(VecDeque::new())),
+            speed_rounds: Arc::new(RwLock::new(HashMap::new())),
+            preferences: Arc::new(PreferenceStore::in_memory()),
+            auto_pass: Arc::new(RwLock::new(HashMap::new())),
+            hard_bot_weights: None,
+            eval_profile: None,
+            deliberation_log: None,
+            clock: Arc::new(SystemClock),
+            hibernation: None,
+            router: None,
+        }
+    }
+
+    /// Use the given weights (typically the ladder champion) for "human_hard" bots
+    pub fn with_hard_bot_weights(mut self, weights: Option<ValueWeights>) -> Self {
+        self.hard_bot_weights = weights;
+        self
+    }
+
+    /// Use the given evaluation profile for the regular alpha-beta bots
+    pub fn with_eval_profile(mut self, profile: Option<EvalProfile>) -> Self {
+        self.eval_profile = profile;
+        self
+    }
+
+    /// Log every alpha-beta bot decision to a per-game file in `log`
+    pub fn with_deliberation_log(mut self, log: Option<DeliberationLog>) -> Self {
+        self.deliberation_log = log;
+        self
+    }
+
+    /// Read and wait on `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Write idle games to `store` and reload them on their next access
+    pub fn with_hibernation(mut self, store: Option<HibernationStore>) -> Self {
+        self.hibernation = store;
+        self
+    }
+
+    /// Create only games `router` assigns to this instance
+    pub fn with_router(mut self, router: Option<ShardRouter>) -> Self {
+        self.router = router;
+        self
+    }
+
+    pub fn router(&self) -> Option<&ShardRouter> {
+        self.router.as_ref()
+    }
+
+    /// Keep account preferences in `store` instead of in memory
+    pub fn with_preferences(mut self, store: PreferenceStore) -> Self {
+        self.preferences = Arc::new(store);
+        self
+    }
+
+    /// `account`'s saved preferences, for a caller presenting the account's `token`
+    pub async fn preferences(
+        &self,
+        account: &str,
+        token: Option<&str>,
+    ) -> CatanResult<UserPreferences> {
+        self.preferences.get_authorized(account, token).await
+    }
+
+    /// Save `account`'s preferences; games seated afterwards use them. Returns the
+    /// account's token when this save claimed it.
+    pub async fn set_preferences(
+        &self,
+        account: &str,
+        token: Option<&str>,
+        preferences: UserPreferences,
+    ) -> CatanResult<Option<String>> {
+        self.preferences.set(account, token, preferences).await
+    }
+
+    /// Seat `account` at the human seat of a fresh game against bots, applying its
+    /// preferences: it moves to its preferred color if it has one, and gets auto-pass
+    /// when auto-end-turn is on. The seat takes the account's name.
+    pub async fn seat_account(&self, game_id: &str, account: &str) -> CatanResult<()> {
+        let preferences = self.preferences.get(account).await;
+        let game_arc = self.game_handle(game_id).await?;
+        let mut game = game_arc.write().await;
+
+        let humans: Vec<usize> = (0..game.players.len())
+            .filter(|&i| !game.bot_colors.contains(&game.players[i].color))
+            .collect();
+        let [human] = humans[..] else {
+            return Err(CatanError::Game(GameError::rule_violation(
+                "accounts can only be seated in games with one human seat",
+            )));
+        };
+        if game.version > 0 {
+            return Err(CatanError::Game(GameError::InvalidStateTransition {
+                details: "seats are fixed once the game has started".to_string(),
+            }));
+        }
+
+        let seat = match preferences.preferred_color_index() {
+            Some(color) if (color as usize) < game.players.len() => color as usize,
+            _ => human,
+        };
+        let bot_name = std::mem::replace(&mut game.players[seat].name, account.to_string());
+        if seat != human {
+            game.players[human].name = bot_name;
+            let human_color = game.players[human].color.clone();
+            let seat_color = game.players[seat].color.clone();
+            for color in game.bot_colors.iter_mut() {
+                if *color == seat_color {
+                    *color = human_color.clone();
+                }
+            }
+        }
+        let players = Self::seat_players(&game);
+        self.players
+            .write()
+            .await
+            .insert(game_id.to_string(), players);
+
+        if preferences.auto_end_turn {
+            let seats = HashMap::from([(seat as u8, AutoPass::default())]);
+            self.auto_pass
+                .write()
+                .await
+                .insert(game_id.to_string(), seats);
+        }
+        log::info!(
+            "🪑 {account} seated as {} in game {game_id}",
+            game.players[seat].color
+        );
+        Ok(())
+    }
+
+    /// Turn auto-pass on (`Some`) or off for `player_id`'s seat
+    pub async fn set_auto_pass(
+        &self,
+        game_id: &str,
+        player_id: &str,
+        setting: Option<AutoPass>,
+    ) -> CatanResult<()> {
+        let game_arc = self.game_handle(game_id).await?;
+        let color = game_arc
+            .read()
+            .await
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or_else(|| {
+                CatanError::Player(PlayerError::PlayerNotInGame {
+                    player_id: player_id.to_string(),
+                    game_id: game_id.to_string(),
+                })
+            })? as u8;
+
+        let mut auto_pass = self.auto_pass.write().await;
+        let seats = auto_pass.entry(game_id.to_string()).or_default();
+        match setting {
+            Some(setting) => seats.insert(color, setting),
+            None => seats.remove(&color),
+        };
+        Ok(())
+    }
+
+    /// When the seat to move has auto-pass on and nothing worth doing is left, how long to
+    /// wait before passing and the game version the decision was made at
+    pub async fn pending_auto_pass(&self, game_id: &str) -> Option<(Duration, u64)> {
+        let game_arc = self.game_handle(game_id).await.ok()?;
+        let game = game_arc.read().await;
+        let state = game.state.as_ref().filter(|_| game.fault.is_none())?;
+        let color = state.get_current_color();
+        let setting = *self.auto_pass.read().await.get(game_id)?.get(&color)?;
+        setting
+            .should_pass(state, color)
+            .then(|| (setting.delay(), game.version))
+    }
+
+    /// End the turn of the seat to move, as decided by `pending_auto_pass` at `version`.
+    /// Returns `None` without doing anything if the game moved on in the meantime.
+    pub async fn auto_pass(
+        &self,
+        game_id: &str,
+        version: u64,
+    ) -> CatanResult<Option<Vec<GameEvent>>> {
+        let game_arc = self.game_handle(game_id).await?;
+        let mut game = game_arc.write().await;
+        if game.version != version {
+            return Ok(None);
+        }
+        let Some(state) = game.state.as_ref() else {
+            return Ok(None);
+        };
+        let color = state.get_current_color();
+        let action = if state.get_action_prompt() == ActionPrompt::SpecialBuild {
+            PlayerAction::PassBuildPhase
+        } else {
+            PlayerAction::EndTurn
+        };
+        let player_id = game.players[color as usize].id.clone();
+        log::info!("⏭️ Auto-passing for {player_id} in game {game_id}");
+        Self::apply_player_action(&mut game, game_id, &player_id, action).map(Some)
+    }
+
+    /// A game's deliberation log as NDJSON, one bot decision per line. Logs are read from
+    /// disk, so games that have since been dropped from memory can still be inspected.
+    pub fn deliberation_log(&self, game_id: &str) -> CatanResult<String> {
+        match &self.deliberation_log {
+            Some(log) => log.read(game_id),
+            None => Err(InfrastructureError::Configuration {
+                details: "deliberation logging is off".to_string(),
+            }
+            .into()),
         }
     }
 
-    /// Create a new game with the specified configuration
-    pub async fn create_game(&self, num_players: u8, bot_type: &str) -> CatanResult<GameId> {
+    /// Create a new game with the rules `config` describes
+    pub async fn create_game(
+        &self,
+        bot_type: &str,
+        config: GameConfigBuilder,
+        table: &TableOptions,
+        pace: Pace,
+    ) -> CatanResult<GameId> {
+        let config = table.configure(config).build()?;
+        let num_players = config.num_players;
+        let human_vs_bots = matches!(bot_type, "human_alphabeta" | "human_random" | "human_hard");
+        let seat_modes = table.seat_bot_modes(num_players, human_vs_bots)?;
+        let map_seed = table.map_seed.unwrap_or_default();
         log::info!("🏭 DEBUG GameService::create_game:");
-        log::info!("  - num_players: {num_players}");
         log::info!("  - bot_type: '{bot_type}'");
+        log::info!("  - config: {config:?}");
+        log::info!("  - pace: {pace:?}");
 
-        let game_id = Uuid::new_v4().to_string();
+        let game_id = self.new_game_id();
         log::info!("  - Generated game_id: {game_id}");
 
         // Create the game instance using the appropriate function
         let game = match bot_type {
-            "human_alphabeta" | "human_random" => {
+            "human_alphabeta" | "human_random" | "human_hard" => {
                 log::info!("  - Creating human vs bots game");
                 // For human vs bots mode, use the specialized function
-                crate::game::start_human_vs_catanatron("Human".to_string(), num_players - 1)
+                crate::game::start_human_vs_catanatron("Human".to_string(), config, map_seed)
             }
             _ => {
                 log::info!("  - Creating all-bot game");
                 // For other modes, use the regular Game::new
                 let player_names: Vec<String> =
                     (0..num_players).map(|i| format!("Bot {}", i + 1)).collect();
-                let mut game = Game::new(game_id.clone(), player_names);
+                let mut game = Game::new_on_map(game_id.clone(), player_names, config, map_seed);
 
                 // For all-bot games, all players are bots
                 if bot_type == "random" {
@@ -69,6 +441,8 @@ impl GameService {
         // Override the game ID with our generated one
         let mut game = game;
         game.id = game_id.clone();
+        game.pace = pace;
+        game.palette = table.palette.unwrap_or_default();
 
         log::info!("  - Game created with {} players", game.players.len());
         log::info!("  - Current color: {:?}", game.current_color);
@@ -80,15 +454,13 @@ impl GameService {
 
         // Create player instances using the simple player system
         let mut players = Vec::new();
-        let colors = ["red", "blue", "white", "orange"];
+        let colors = ["red", "blue", "white", "orange", "green", "brown"];
 
         for (i, player) in game.players.iter().enumerate() {
             let player_id = format!("player_{i}");
             let color = colors[i % colors.len()].to_string();
 
-            let player_obj = if (bot_type == "human_alphabeta" || bot_type == "human_random")
-                && i == 0
-            {
+            let player_obj = if human_vs_bots && i == 0 {
                 // First player is human in human vs bots mode
                 log::info!("  - Creating human player: {}", player.name);
                 PlayerFactory::create_human(player_id, player.name.clone(), color)
@@ -131,19 +503,133 @@ impl GameService {
             let mut modes = self.bot_modes.write().await;
             modes.insert(game_id.clone(), bot_type.to_string());
         }
+        if seat_modes.iter().any(Option::is_some) {
+            self.seat_bot_modes
+                .write()
+                .await
+                .insert(game_id.clone(), seat_modes);
+        }
 
         log::info!("🏭 END GameService::create_game debug\n");
 
         Ok(game_id)
     }
 
+    /// Create a game with an explicit seating order (seat 0 places first).
+    /// Bot seats are driven according to `bot_type`, like in `create_game`.
+    pub async fn create_seated_game(&self, seats: &[Seat], bot_type: &str) -> CatanResult<GameId> {
+        let game_id = self.new_game_id();
+        let player_names = seats.iter().map(|s| s.name.clone()).collect();
+        let mut game = Game::new(game_id.clone(), player_names);
+        game.bot_colors = game
+            .players
+            .iter()
+            .zip(seats)
+            .filter(|(_, seat)| seat.is_bot)
+            .map(|(p, _)| p.color.clone())
+            .collect();
+        game.update_metadata_from_state();
+
+        let players = game
+            .players
+            .iter()
+            .zip(seats)
+            .map(|(p, seat)| {
+                if seat.is_bot {
+                    PlayerFactory::create_random_bot(p.id.clone(), p.name.clone(), p.color.clone())
+                } else {
+                    PlayerFactory::create_human(p.id.clone(), p.name.clone(), p.color.clone())
+                }
+            })
+            .collect();
+
+        self.games
+            .write()
+            .await
+            .insert(game_id.clone(), Arc::new(RwLock::new(game)));
+        self.players.write().await.insert(game_id.clone(), players);
+        self.bot_modes
+            .write()
+            .await
+            .insert(game_id.clone(), bot_type.to_string());
+
+        Ok(game_id)
+    }
+
+    /// Register a game driven from outside the service (e.g. the simulator) so clients can
+    /// watch it through the regular endpoints. All seats are reported as bots.
+    pub async fn register_external_game(&self, mut game: Game) -> GameId {
+        let game_id = game.id.clone();
+        game.bot_colors = game.players.iter().map(|p| p.color.clone()).collect();
+        let players = game
+            .players
+            .iter()
+            .map(|p| {
+                PlayerFactory::create_random_bot(p.id.clone(), p.name.clone(), p.color.clone())
+            })
+            .collect();
+
+        self.games
+            .write()
+            .await
+            .insert(game_id.clone(), Arc::new(RwLock::new(game)));
+        self.players.write().await.insert(game_id.clone(), players);
+        self.bot_modes
+            .write()
+            .await
+            .insert(game_id.clone(), EXTERNAL_BOT_MODE.to_string());
+
+        game_id
+    }
+
+    /// Apply an internal engine action on behalf of the player currently prompted
+    pub async fn apply_internal_action(
+        &self,
+        game_id: &str,
+        action: crate::enums::Action,
+    ) -> CatanResult<Vec<GameEvent>> {
+        let game_arc = self.game_handle(game_id).await?;
+        let mut game = game_arc.write().await;
+
+        let color = game
+            .state
+            .as_ref()
+            .map(|s| s.get_current_color() as usize)
+            .unwrap_or(game.current_player_index);
+        let player_id = game
+            .players
+            .get(color)
+            .map(|p| p.id.clone())
+            .ok_or_else(|| {
+                CatanError::Player(PlayerError::not_in_game(format!("player_{color}"), game_id))
+            })?;
+
+        game.process_action(&player_id, action)
+            .map_err(|e| CatanError::Game(GameError::invalid_action(e, player_id.clone())))?;
+
+        let mut events = vec![GameEvent::ActionExecuted {
+            game_id: game_id.to_string(),
+            player_id: player_id.clone(),
+            action: action.into(),
+            success: true,
+            message: "Action processed successfully".to_string(),
+        }];
+        if let Some(loop_break) = game.take_loop_break() {
+            events.push(GameEvent::LoopDetected {
+                game_id: game_id.to_string(),
+                player_id,
+                repetitions: loop_break.repetitions,
+                forced_end_turn: loop_break.forced_end_turn,
+            });
+        }
+        Ok(events)
+    }
+
     /// Get a game by ID
     pub async fn get_game(&self, game_id: &str) -> CatanResult<Game> {
         log::info!("📖 DEBUG GameService::get_game for game_id: {game_id}");
 
-        let games = self.games.read().await;
-
-        if let Some(game_arc) = games.get(game_id) {
+        if let Ok(game_arc) = self.game_handle(game_id).await {
             let mut game = game_arc.write().await;
             log::debug!("  - Found game, updating metadata...");
             // Update metadata before returning
@@ -168,10 +654,303 @@ impl GameService {
         }
     }
 
+    /// Recommend an action for the player to move, with the chance outcomes behind it.
+    /// Returns None when the game has no internal state or nothing is playable.
+    pub async fn analyze_position(&self, game_id: &str) -> CatanResult<Option<AnalysisResult>> {
+        let game = self.get_game(game_id).await?;
+        let Some(state) = game.state.as_ref() else {
+            return Ok(None);
+        };
+
+        Ok(analyze(
+            state,
+            ANALYSIS_DEPTH,
+            Duration::from_millis(ANALYSIS_TIME_MS),
+        ))
+    }
+
+    /// Fair-play audit of a game's human seats, for admins. Sandboxes are practice and
+    /// not audited. Searches every timed decision again, so it runs off the async workers.
+    pub async fn audit_game(&self, game_id: &str) -> CatanResult<AuditReport> {
+        let game = self.game_handle(game_id).await?.read().await.clone();
+        if game.forked_from.is_some() {
+            return Err(CatanError::Validation(
+                "sandbox games are not audited".to_string(),
+            ));
+        }
+        // Same search as the hints a player could be copying
+        let settings = AuditSettings {
+            depth: ANALYSIS_DEPTH,
+            time_budget: Duration::from_millis(ANALYSIS_TIME_MS),
+            ..AuditSettings::default()
+        };
+        tokio::task::spawn_blocking(move || game.audit(&settings))
+            .await
+            .map_err(|e| CatanError::Internal(format!("audit stopped: {e}")))?
+            .map_err(CatanError::Internal)
+    }
+
+    /// Victory points after every completed turn of a game
+    pub async fn vp_history(&self, game_id: &str) -> CatanResult<VpHistory> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        Ok(game.vp_history())
+    }
+
+    /// Up to `limit` of a game's applied actions from index `from`, with what each changed
+    pub async fn action_page(
+        &self,
+        game_id: &str,
+        from: usize,
+        limit: usize,
+    ) -> CatanResult<ActionPage> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        Ok(game.action_page(from, limit))
+    }
+
+    /// Turn count and dice fairness of a game
+    pub async fn game_stats(&self, game_id: &str) -> CatanResult<GameStats> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        Ok(game.stats())
+    }
+
+    /// Whether the seat with `color` (the player to move if absent) may take `action` now,
+    /// and if not, which rule stops it
+    pub async fn check_action(
+        &self,
+        game_id: &str,
+        color: Option<&str>,
+        action: PlayerAction,
+    ) -> CatanResult<Legality> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        let color = match color {
+            Some(color) => game
+                .players
+                .iter()
+                .position(|p| p.color.eq_ignore_ascii_case(color))
+                .ok_or_else(|| CatanError::Validation(format!("no seat has color {color:?}")))?
+                as u8,
+            None => game.current_player_index as u8,
+        };
+        let result = game.check_action(Self::convert_player_action_to_internal(action, color));
+        Ok(result.into())
+    }
+
+    /// Every action the game can offer, in the order compact playable actions refer to
+    pub async fn action_table(&self, game_id: &str) -> CatanResult<Vec<PlayerAction>> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        let table = game
+            .action_table()
+            .ok_or_else(|| GameError::InvalidStateTransition {
+                details: "game has no engine state".to_string(),
+            })?;
+        Ok(table.entries().to_vec())
+    }
+
+    /// Debug snapshot of a game's internals
+    pub async fn debug_dump(&self, game_id: &str) -> CatanResult<GameDump> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        Ok(game.debug_dump())
+    }
+
+    /// Currently offered actions that complete a partially specified one
+    pub async fn complete_action(
+        &self,
+        game_id: &str,
+        partial: &PartialAction,
+    ) -> CatanResult<Vec<PlayerAction>> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        Ok(partial.completions(&game.current_playable_actions))
+    }
+
+    /// The offered action a text command such as "build settlement at 23" describes
+    pub async fn resolve_command(&self, game_id: &str, text: &str) -> CatanResult<PlayerAction> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        resolve_command(text, &game.current_playable_actions).map_err(CatanError::Validation)
+    }
+
+    /// Whether `access` may have the history of `game`; anyone may once it is finished
+    async fn may_see_history(&self, game: &Game, access: HistoryAccess<'_>) -> bool {
+        if matches!(game.game_state, GameState::Finished { .. }) {
+            return true;
+        }
+        match access {
+            HistoryAccess::Anyone => false,
+            HistoryAccess::Admin => true,
+            HistoryAccess::Account { name, token } => {
+                let humans: Vec<_> = game
+                    .players
+                    .iter()
+                    .filter(|p| !game.bot_colors.contains(&p.color))
+                    .collect();
+                matches!(humans[..], [human] if human.name == name)
+                    && self.preferences.is_owner(name, token).await
+            }
+        }
+    }
+
+    /// Export a game as a self-contained savegame, clock state included, so it can move
+    /// to another server. Games in play need `access` to their history.
+    pub async fn export_game(
+        &self,
+        game_id: &str,
+        access: HistoryAccess<'_>,
+    ) -> CatanResult<SaveGame> {
+        let game_arc = self.game_handle(game_id).await?;
+        let bot_mode = self
+            .bot_modes
+            .read()
+            .await
+            .get(game_id)
+            .cloned()
+            .unwrap_or_default();
+
+        let game = game_arc.read().await;
+        if !self.may_see_history(&game, access).await {
+            return Err(CatanError::Validation(
+                "Games in play are exported only for admins and their seated account".to_string(),
+            ));
+        }
+        game.to_savegame(bot_mode).map_err(CatanError::Internal)
+    }
+
+    /// Recreate an exported game under a new id by re-applying its recorded actions
+    pub async fn import_game(&self, save: SaveGame) -> CatanResult<GameId> {
+        let game_id = self.new_game_id();
+        let game = Game::from_savegame(game_id.clone(), &save).map_err(CatanError::Validation)?;
+        log::info!(
+            "📥 Imported game {game_id} ({} actions, mode '{}')",
+            save.replay.actions.len(),
+            save.bot_mode
+        );
+
+        let players = Self::seat_players(&game);
+
+        self.games
+            .write()
+            .await
+            .insert(game_id.clone(), Arc::new(RwLock::new(game)));
+        self.players.write().await.insert(game_id.clone(), players);
+        self.bot_modes
+            .write()
+            .await
+            .insert(game_id.clone(), save.bot_mode);
+
+        Ok(game_id)
+    }
+
+    /// Branch a sandbox game off `game_id` after its first `at` actions, to explore another
+    /// line from there. Seats keep their human or bot role, except `bot_seats` which are
+    /// handed to bots. Only the newest `MAX_SANDBOX_GAMES` sandboxes are kept. A sandbox
+    /// replays the original's seed, so games in play need `access` to their history.
+    pub async fn fork_game(
+        &self,
+        game_id: &str,
+        at: usize,
+        bot_seats: &[usize],
+        access: HistoryAccess<'_>,
+    ) -> CatanResult<GameId> {
+        let game_arc = self.game_handle(game_id).await?;
+        let fork_id = self.new_game_id();
+        let mut fork = {
+            let game = game_arc.read().await;
+            if !self.may_see_history(&game, access).await {
+                return Err(CatanError::Validation(
+                    "Games in play are forked only for admins and their seated account".to_string(),
+                ));
+            }
+            game.fork(fork_id.clone(), at)
+                .map_err(CatanError::Validation)?
+        };
+        if let Some(seat) = bot_seats.iter().find(|&&seat| seat >= fork.players.len()) {
+            return Err(CatanError::Validation(format!(
+                "No seat {seat} in this game"
+            )));
+        }
+
+        let original_bots: Vec<bool> = self
+            .get_players(game_id)
+            .await?
+            .iter()
+            .map(|p| p.info.is_bot)
+            .collect();
+        let is_bot = |seat: usize| original_bots[seat] || bot_seats.contains(&seat);
+        fork.bot_colors = fork
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(seat, _)| is_bot(*seat))
+            .map(|(_, p)| p.color.clone())
+            .collect();
+        fork.update_metadata_from_state();
+        let players = fork
+            .players
+            .iter()
+            .enumerate()
+            .map(|(seat, p)| {
+                if is_bot(seat) {
+                    PlayerFactory::create_random_bot(p.id.clone(), p.name.clone(), p.color.clone())
+                } else {
+                    PlayerFactory::create_human(p.id.clone(), p.name.clone(), p.color.clone())
+                }
+            })
+            .collect();
+
+        // Externally driven games have no bot logic here, so their sandboxes use AlphaBeta
+        let bot_mode = match self.bot_modes.read().await.get(game_id) {
+            Some(mode) if mode != EXTERNAL_BOT_MODE => mode.clone(),
+            _ => "alphabeta".to_string(),
+        };
+        log::info!("🌱 Forked sandbox {fork_id} off game {game_id} at action {at}");
+
+        self.games
+            .write()
+            .await
+            .insert(fork_id.clone(), Arc::new(RwLock::new(fork)));
+        self.players.write().await.insert(fork_id.clone(), players);
+        self.bot_modes
+            .write()
+            .await
+            .insert(fork_id.clone(), bot_mode);
+
+        let evicted = {
+            let mut sandboxes = self.sandboxes.write().await;
+            sandboxes.push_back(fork_id.clone());
+            if sandboxes.len() > MAX_SANDBOX_GAMES {
+                sandboxes.pop_front()
+            } else {
+                None
+            }
+        };
+        if let Some(evicted) = evicted {
+            log::info!("🧹 Dropping sandbox {evicted} to stay under {MAX_SANDBOX_GAMES}");
+            self.remove_game(&evicted).await?;
+        }
+
+        Ok(fork_id)
+    }
+
+    /// Speed preset of a game
+    pub async fn pace(&self, game_id: &str) -> CatanResult<Pace> {
+        let game_arc = self.game_handle(game_id).await?;
+        let pace = game_arc.read().await.pace;
+        Ok(pace)
+    }
+
     /// Check if a game exists
     pub async fn game_exists(&self, game_id: &str) -> bool {
-        let games = self.games.read().await;
-        games.contains_key(game_id)
+        self.games.read().await.contains_key(game_id)
+            || self
+                .hibernation
+                .as_ref()
+                .is_some_and(|store| store.contains(game_id))
     }
 
     /// Process a player action
@@ -181,16 +960,60 @@ impl GameService {
         player_id: &str,
         action: PlayerAction,
     ) -> CatanResult<Vec<GameEvent>> {
-        let games = self.games.read().await;
+        let game_arc = self.game_handle(game_id).await?;
 
-        let game_arc = games.get(game_id).ok_or_else(|| {
-            CatanError::Game(GameError::GameNotFound {
-                game_id: game_id.to_string(),
-            })
-        })?;
+        let mut game = game_arc.write().await;
+        Self::apply_player_action(&mut game, game_id, player_id, action)
+    }
+
+    /// Process a player action tagged with a client sequence number and the game version
+    /// the client saw. A number at or below the last accepted one is a resubmission (e.g. a
+    /// network retry) and is dropped, returning `None`; numbers only advance when an
+    /// action succeeds. An action made against an older version, such as one chosen before
+    /// a bot moved, is rejected with `GameError::StaleVersion`.
+    pub async fn process_sequenced_action(
+        &self,
+        game_id: &str,
+        player_id: &str,
+        seq: u64,
+        version: u64,
+        action: PlayerAction,
+    ) -> CatanResult<Option<Vec<GameEvent>>> {
+        let game_arc = self.game_handle(game_id).await?;
 
         let mut game = game_arc.write().await;
+        if seq <= game.last_action_seq {
+            log::info!(
+                "🔁 Dropping duplicate action #{seq} for game {game_id} (last accepted #{})",
+                game.last_action_seq
+            );
+            return Ok(None);
+        }
+        if version != game.version {
+            return Err(CatanError::Game(GameError::StaleVersion {
+                submitted: version,
+                current: game.version,
+            }));
+        }
+
+        let events = Self::apply_player_action(&mut game, game_id, player_id, action)?;
+        let succeeded = events.iter().all(|event| match event {
+            GameEvent::ActionExecuted { success, .. } => *success,
+            _ => true,
+        });
+        if succeeded {
+            game.last_action_seq = seq;
+        }
+        Ok(Some(events))
+    }
 
+    /// Validate and apply an action for `player_id` on an already locked game
+    fn apply_player_action(
+        game: &mut Game,
+        game_id: &str,
+        player_id: &str,
+        action: PlayerAction,
+    ) -> CatanResult<Vec<GameEvent>> {
         // Find the player's color index for proper action conversion
         let player_color_index = game
             .players
@@ -203,21 +1026,52 @@ impl GameService {
                 })
             })? as u8;
 
+        // Past the initial placement, speed-round games only move through build windows
+        if Self::speed_phase(game) {
+            return Err(CatanError::Game(GameError::rule_violation(
+                "speed rounds take actions through the build window",
+            )));
+        }
+
         // Convert PlayerAction to the internal Action type with correct color
         let internal_action =
             Self::convert_player_action_to_internal(action.clone(), player_color_index);
 
+        // The engine assumes legal input, so only accept what the player is offered.
+        // Only the suggested discard and one-for-one trade offers are listed, and no
+        // counter-offers, so those are checked against the rules instead.
+        let offered = match &action {
+            PlayerAction::Discard { .. }
+            | PlayerAction::OfferTrade { .. }
+            | PlayerAction::CounterTrade { .. } => game.check_action(internal_action).is_ok(),
+            _ => game.current_playable_actions.contains(&action),
+        };
+        if !offered {
+            return Err(CatanError::Game(GameError::invalid_action(
+                format!("{action:?}"),
+                player_id,
+            )));
+        }
+
         // Process the action
         match game.process_action(player_id, internal_action) {
             Ok(()) => {
                 // Generate events based on the action
-                let events = vec![GameEvent::ActionExecuted {
+                let mut events = vec![GameEvent::ActionExecuted {
                     game_id: game_id.to_string(),
                     player_id: player_id.to_string(),
                     action,
                     success: true,
                     message: "Action processed successfully".to_string(),
                 }];
+                if let Some(loop_break) = game.take_loop_break() {
+                    events.push(GameEvent::LoopDetected {
+                        game_id: game_id.to_string(),
+                        player_id: player_id.to_string(),
+                        repetitions: loop_break.repetitions,
+                        forced_end_turn: loop_break.forced_end_turn,
+                    });
+                }
 
                 Ok(events)
             }
@@ -249,6 +1103,8 @@ impl GameService {
                 EnumAction::BuildSettlement { color, node_id }
             }
             PlayerAction::BuildCity { node_id } => EnumAction::BuildCity { color, node_id },
+            PlayerAction::BuildShip { edge_id } => EnumAction::BuildShip { color, edge_id },
+            PlayerAction::MoveShip { from, to } => EnumAction::MoveShip { color, from, to },
             PlayerAction::BuyDevelopmentCard => EnumAction::BuyDevelopmentCard { color },
             PlayerAction::PlayKnight => EnumAction::PlayKnight { color },
             PlayerAction::PlayYearOfPlenty { resources } => EnumAction::PlayYearOfPlenty {
@@ -267,6 +1123,7 @@ impl GameService {
                 ratio,
             },
             PlayerAction::EndTurn => EnumAction::EndTurn { color },
+            PlayerAction::PassBuildPhase => EnumAction::PassBuildPhase { color },
             PlayerAction::MoveRobber { coordinate, victim } => {
                 let victim_opt = victim.and_then(|v| {
                     // Extract color index from "player_X" format
@@ -278,8 +1135,49 @@ impl GameService {
                     victim_opt,
                 }
             }
-            PlayerAction::Discard { .. } => EnumAction::Discard { color },
-            _ => EnumAction::EndTurn { color }, // Default for unhandled actions
+            PlayerAction::Discard { resources } => EnumAction::Discard {
+                color,
+                resources: resources_to_freqdeck(&resources),
+            },
+            PlayerAction::ChooseGold { resource } => EnumAction::ChooseGold {
+                color,
+                resource: resource_to_u8(resource),
+            },
+            PlayerAction::GiftResource { teammate, resource } => EnumAction::GiftResource {
+                color,
+                // Extract color index from "player_X" format
+                teammate: teammate
+                    .strip_prefix("player_")
+                    .and_then(|s| s.parse::<u8>().ok())
+                    .unwrap_or(color),
+                resource: resource_to_u8(resource),
+            },
+            PlayerAction::OfferTrade { give, take } => EnumAction::OfferTrade {
+                color,
+                trade: (resources_to_freqdeck(&give), resources_to_freqdeck(&take)),
+            },
+            // Answers leave out the offer's terms; the game fills them in
+            PlayerAction::AcceptTrade => EnumAction::AcceptTrade {
+                color,
+                trade: ([0; 5], [0; 5]),
+            },
+            PlayerAction::RejectTrade => EnumAction::RejectTrade { color },
+            PlayerAction::CounterTrade { give, take } => EnumAction::CounterTrade {
+                color,
+                trade: (resources_to_freqdeck(&give), resources_to_freqdeck(&take)),
+            },
+            PlayerAction::ConfirmTrade { with } => EnumAction::ConfirmTrade {
+                color,
+                // Extract color index from "player_X" format
+                trade: (
+                    [0; 5],
+                    [0; 5],
+                    with.strip_prefix("player_")
+                        .and_then(|s| s.parse::<u8>().ok())
+                        .unwrap_or(color),
+                ),
+            },
+            PlayerAction::CancelTrade => EnumAction::CancelTrade { color },
         }
     }
 
@@ -291,6 +1189,8 @@ impl GameService {
 
     /// Get players for a game
     pub async fn get_players(&self, game_id: &str) -> CatanResult<Vec<Player>> {
+        // Wakes the game if it is hibernated
+        self.game_handle(game_id).await?;
         let players = self.players.read().await;
 
         if let Some(game_players) = players.get(game_id) {
@@ -317,8 +1217,12 @@ impl GameService {
         let game = self.get_game(game_id).await?;
         let players = self.get_players(game_id).await?;
 
-        // Validate game state before processing
-        if !matches!(game.game_state, GameState::Active | GameState::Setup) {
+        // Validate game state before processing; halted games take no more moves, and
+        // bots in speed rounds submit through the build window
+        if !matches!(game.game_state, GameState::Active | GameState::Setup)
+            || game.fault.is_some()
+            || Self::speed_phase(&game)
+        {
             return Ok(None);
         }
 
@@ -339,15 +1243,29 @@ impl GameService {
             return Ok(None);
         }
 
-        // Determine bot mode for this game
-        let bot_mode = {
-            let modes = self.bot_modes.read().await;
-            modes
-                .get(game_id)
-                .cloned()
-                .unwrap_or_else(|| "random".to_string())
+        // Determine bot mode for this seat, falling back to the game's
+        let seat_mode = self
+            .seat_bot_modes
+            .read()
+            .await
+            .get(game_id)
+            .and_then(|seats| seats.get(game.current_player_index).cloned().flatten());
+        let bot_mode = match seat_mode {
+            Some(mode) => mode,
+            None => {
+                let modes = self.bot_modes.read().await;
+                modes
+                    .get(game_id)
+                    .cloned()
+                    .unwrap_or_else(|| "random".to_string())
+            }
         };
 
+        // Externally driven games (e.g. simulate --serve) get their moves from their owner
+        if bot_mode == EXTERNAL_BOT_MODE {
+            return Ok(None);
+        }
+
         // Get available actions with proper validation and error handling
         let available_actions: Vec<PlayerAction> = if let Some(ref state) = game.state {
             let state_actions = state.generate_playable_actions();
@@ -405,31 +1323,86 @@ impl GameService {
         }
 
         // Let the bot decide what action to take with timeout protection
-        let decision_result = if bot_mode == "human_alphabeta" || bot_mode == "alphabeta" {
+        let decision_result = if matches!(
+            bot_mode.as_str(),
+            "human_alphabeta" | "alphabeta" | "human_hard"
+        ) {
             // Use backend AlphaBetaPlayer on the internal state for bots
             use crate::enums::Action as EnumAction;
-            use crate::players::{minimax::AlphaBetaPlayer, BotPlayer as _};
+            use crate::players::{AlphaBetaPlayer, BotPlayer as _, SeatedBot};
 
             if let Some(ref state) = game.state {
                 let state_actions: Vec<EnumAction> = state.generate_playable_actions();
-                let ab = AlphaBetaPlayer::new(
-                    "alphabeta_bot".to_string(),
-                    "AlphaBeta Bot".to_string(),
-                    "gray".to_string(),
-                );
-                // Run synchronously within timeout wrapper
-                let decided_internal = ab.decide(state, &state_actions);
-                let decided_player_action: PlayerAction = decided_internal.into();
-                Ok(Ok(decided_player_action))
+                let mut engine = match (&self.hard_bot_weights, bot_mode.as_str()) {
+                    (Some(weights), "human_hard") => {
+                        AlphaBetaPlayer::with_config(HARD_BOT_DEPTH, weights.clone(), None)
+                    }
+                    _ => match &self.eval_profile {
+                        Some(profile) => profile.alpha_beta_player(),
+                        None => AlphaBetaPlayer::new(),
+                    },
+                };
+                if let Some(search_ms) = game.pace.settings().bot_search_ms {
+                    engine.set_time_profile(search_ms, search_ms, usize::MAX);
+                }
+                engine.set_opponent_model(game.opponent_model().clone());
+                let bot = SeatedBot::new(state.get_current_color(), Box::new(engine));
+                // Run synchronously; a panicking search ends the turn instead of the task
+                let decided =
+                    std::panic::catch_unwind(AssertUnwindSafe(|| match &self.deliberation_log {
+                        Some(deliberation_log) => {
+                            let started = std::time::Instant::now();
+                            let deliberation = bot.deliberate(state, &state_actions);
+                            let chosen = deliberation.chosen;
+                            let entry = DeliberationEntry::new(
+                                state,
+                                &bot,
+                                deliberation,
+                                started.elapsed(),
+                            );
+                            if let Err(e) = deliberation_log.append(game_id, &entry) {
+                                log::warn!("Could not log deliberation for game {game_id}: {e}");
+                            }
+                            chosen
+                        }
+                        None => bot.decide(state, &state_actions),
+                    }))
+                    .map(|chosen| match chosen {
+                        EnumAction::Discard { color, .. } => EnumAction::Discard {
+                            color,
+                            resources: bot.choose_discard(state, color),
+                        },
+                        declined @ (EnumAction::RejectTrade { color }
+                        | EnumAction::CancelTrade { color }) => bot
+                            .counter_offer(state, color)
+                            .filter(|_| state.can_counter_trade(color))
+                            .map_or(declined, |trade| EnumAction::CounterTrade { color, trade }),
+                        chosen => chosen,
+                    });
+                Ok(decided
+                    .map(PlayerAction::from)
+                    .map_err(|payload| PlayerError::StrategyError {
+                        details: format!("search panicked: {}", panic_message(payload.as_ref())),
+                    }))
             } else {
                 Ok(Ok(PlayerAction::EndTurn))
             }
         } else {
             tokio::time::timeout(
-                tokio::time::Duration::from_secs(5),
-                current_player.decide_action(&game.game_state, &available_actions),
+                tokio::time::Duration::from_millis(game.pace.settings().bot_timeout_ms),
+                AssertUnwindSafe(
+                    current_player.decide_action(&game.game_state, &available_actions),
+                )
+                .catch_unwind(),
             )
             .await
+            .map(|decided| {
+                decided.unwrap_or_else(|payload| {
+                    Err(PlayerError::StrategyError {
+                        details: format!("strategy panicked: {}", panic_message(payload.as_ref())),
+                    })
+                })
+            })
         };
 
         match decision_result {
@@ -475,6 +1448,367 @@ impl GameService {
         }
     }
 
+    /// Whether `game_id` is past its initial placement in a speed-rounds game
+    pub async fn in_speed_phase(&self, game_id: &str) -> bool {
+        match self.game_handle(game_id).await {
+            Ok(game_arc) => Self::speed_phase(&*game_arc.read().await),
+            Err(_) => false,
+        }
+    }
+
+    fn speed_phase(game: &Game) -> bool {
+        game.fault.is_none() && game.state.as_ref().is_some_and(speed::in_speed_phase)
+    }
+
+    /// Roll for the seat to move and open a build window on the roll; bots submit right
+    /// away. A 7 is settled before the window opens: discards as usual, and the robber
+    /// goes where a value-function bot would put it for the roller. Returns the window
+    /// already open if there is one, and `None` outside the speed phase.
+    pub async fn open_speed_round(&self, game_id: &str) -> CatanResult<Option<SpeedWindow>> {
+        let game_arc = self.game_handle(game_id).await?;
+        let mut rounds = self.speed_rounds.write().await;
+        let mut game = game_arc.write().await;
+        let color_name = |color: u8| game.players[color as usize].color.clone();
+        if let Some(round) = rounds.get(game_id) {
+            return Ok(Some(round.window(self.clock.now(), color_name)));
+        }
+        if !Self::speed_phase(&game) {
+            return Ok(None);
+        }
+
+        let roller = game
+            .state
+            .as_ref()
+            .map_or(0, |state| state.get_current_color());
+        Self::apply_engine_action(
+            &mut game,
+            Action::Roll {
+                color: roller,
+                dice_opt: None,
+            },
+        )?;
+        while let Some(state) = game.state.as_ref().filter(|state| state.winner().is_none()) {
+            if !matches!(
+                state.get_action_prompt(),
+                ActionPrompt::Discard | ActionPrompt::MoveRobber | ActionPrompt::ChooseGold
+            ) {
+                break;
+            }
+            let action =
+                ValueFunctionPlayer::new().decide(state, &state.generate_playable_actions());
+            Self::apply_engine_action(&mut game, action)?;
+        }
+        let Some(state) = game.state.as_ref().filter(|_| Self::speed_phase(&game)) else {
+            return Ok(None);
+        };
+
+        let mut round = SpeedRound::new(
+            game.vp_history().turns.len() as u32 + 1,
+            roller,
+            state.get_last_dice_roll().map(|(die1, die2)| [die1, die2]),
+            speed::priority_order(state, roller),
+            self.clock.now(),
+        );
+        for color in round.priority.clone() {
+            let name = game.players[color as usize].color.clone();
+            if game.bot_colors.contains(&name) {
+                round.submit(color, speed::plan_bot_submission(state, color))?;
+            } else {
+                round.expect_human(color);
+            }
+        }
+        let color_name = |color: u8| game.players[color as usize].color.clone();
+        let window = round.window(self.clock.now(), color_name);
+        rounds.insert(game_id.to_string(), round);
+        Ok(Some(window))
+    }
+
+    /// Queue `player_id`'s actions for the open build window, replacing any earlier
+    /// submission of theirs
+    pub async fn submit_speed_actions(
+        &self,
+        game_id: &str,
+        player_id: &str,
+        actions: Vec<PlayerAction>,
+    ) -> CatanResult<SpeedWindow> {
+        let game_arc = self.game_handle(game_id).await?;
+        let game = game_arc.read().await;
+        let color = game
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or_else(|| {
+                CatanError::Player(PlayerError::PlayerNotInGame {
+                    player_id: player_id.to_string(),
+                    game_id: game_id.to_string(),
+                })
+            })? as u8;
+
+        let mut rounds = self.speed_rounds.write().await;
+        let round = rounds.get_mut(game_id).ok_or_else(|| {
+            CatanError::Game(GameError::InvalidStateTransition {
+                details: "no speed round is open".to_string(),
+            })
+        })?;
+        round.submit(color, actions)?;
+        Ok(round.window(self.clock.now(), |color| {
+            game.players[color as usize].color.clone()
+        }))
+    }
+
+    /// Whether the open build window can close (time is up or every human submitted).
+    /// False when no window is open.
+    pub async fn speed_round_ready(&self, game_id: &str) -> bool {
+        self.speed_rounds
+            .read()
+            .await
+            .get(game_id)
+            .is_some_and(|round| round.is_ready(self.clock.now()))
+    }
+
+    /// Close the build window and apply the submissions seat by seat in priority order,
+    /// passing the roll along after each seat and ending the roller's turn at the end
+    pub async fn resolve_speed_round(&self, game_id: &str) -> CatanResult<Vec<SpeedOutcome>> {
+        let Some(round) = self.speed_rounds.write().await.remove(game_id) else {
+            return Ok(Vec::new());
+        };
+        let game_arc = self.game_handle(game_id).await?;
+        let mut game = game_arc.write().await;
+        let roller = round.roller;
+
+        let mut outcomes = Vec::new();
+        for (color, actions) in round.into_queue() {
+            for action in actions {
+                let internal = Self::convert_player_action_to_internal(action.clone(), color);
+                let reason = if Self::is_legal_now(&game, internal) {
+                    Self::apply_engine_action(&mut game, internal)
+                        .err()
+                        .map(|e| e.to_string())
+                } else {
+                    Some("no longer legal when its turn came".to_string())
+                };
+                outcomes.push(SpeedOutcome {
+                    color: game.players[color as usize].color.clone(),
+                    action,
+                    applied: reason.is_none(),
+                    reason,
+                });
+            }
+            let pass = Action::PassBuildPhase { color };
+            if !Self::is_legal_now(&game, pass) {
+                // The game is over, or a loop break already ended the turn
+                return Ok(outcomes);
+            }
+            Self::apply_engine_action(&mut game, pass)?;
+        }
+        let end_turn = Action::EndTurn { color: roller };
+        if Self::is_legal_now(&game, end_turn) {
+            Self::apply_engine_action(&mut game, end_turn)?;
+        }
+        Ok(outcomes)
+    }
+
+    fn is_legal_now(game: &Game, action: Action) -> bool {
+        game.state.as_ref().is_some_and(|state| {
+            state.winner().is_none() && state.generate_playable_actions().contains(&action)
+        })
+    }
+
+    /// Apply an engine action for the seat it names, on an already locked game
+    fn apply_engine_action(game: &mut Game, action: Action) -> CatanResult<()> {
+        let player_id = game.players[action.color() as usize].id.clone();
+        game.process_action(&player_id, action)
+            .map_err(|details| CatanError::Game(GameError::InvalidStateTransition { details }))
+    }
+
+    /// Fresh id for a game created here, drawn until it routes to this instance. An
+    /// instance being drained owns no ids and takes the first draw.
+    fn new_game_id(&self) -> GameId {
+        loop {
+            let game_id = Uuid::new_v4().to_string();
+            match &self.router {
+                Some(router) if router.owns_games() && !router.is_local(&game_id) => {}
+                _ => return game_id,
+            }
+        }
+    }
+
+    /// Hibernate every game in memory that the router assigns to another instance, so
+    /// its owner picks it up from the shared store. Used when instances join or leave.
+    /// Returns the games handed off; games in use stay until asked again.
+    pub async fn hand_off_games(&self) -> CatanResult<Vec<GameId>> {
+        let Some(router) = &self.router else {
+            return Ok(Vec::new());
+        };
+        let foreign: Vec<GameId> = self
+            .games
+            .read()
+            .await
+            .keys()
+            .filter(|game_id| !router.is_local(game_id))
+            .cloned()
+            .collect();
+        let mut handed_off = Vec::new();
+        for game_id in foreign {
+            if self.hibernate_game(&game_id).await? {
+                handed_off.push(game_id);
+            }
+        }
+        if !handed_off.is_empty() {
+            log::info!("🔀 Handed off {} games to their owners", handed_off.len());
+        }
+        Ok(handed_off)
+    }
+
+    /// The game with `game_id`, woken from hibernation if it was put to disk
+    async fn game_handle(&self, game_id: &str) -> CatanResult<Arc<RwLock<Game>>> {
+        if let Some(game_arc) = self.games.read().await.get(game_id).cloned() {
+            return Ok(game_arc);
+        }
+        let woken = match &self.hibernation {
+            Some(store) if store.contains(game_id) => self.wake_game(store, game_id).await?,
+            _ => None,
+        };
+        woken.ok_or_else(|| {
+            CatanError::Game(GameError::GameNotFound {
+                game_id: game_id.to_string(),
+            })
+        })
+    }
+
+    /// Write an idle game to disk and drop it from memory, if hibernation is on. Games
+    /// someone is using right now, externally driven ones, faulted ones and speed-round
+    /// games stay in memory. Returns whether the game was hibernated.
+    pub async fn hibernate_game(&self, game_id: &str) -> CatanResult<bool> {
+        let Some(store) = &self.hibernation else {
+            return Ok(false);
+        };
+        let bot_mode = self
+            .bot_modes
+            .read()
+            .await
+            .get(game_id)
+            .cloned()
+            .unwrap_or_default();
+        if bot_mode == EXTERNAL_BOT_MODE || self.speed_rounds.read().await.contains_key(game_id) {
+            return Ok(false);
+        }
+        let seat_bot_modes = self.seat_bot_modes.read().await.get(game_id).cloned();
+        let auto_pass = self
+            .auto_pass
+            .read()
+            .await
+            .get(game_id)
+            .cloned()
+            .unwrap_or_default();
+
+        let mut games = self.games.write().await;
+        let Some(game_arc) = games.get(game_id) else {
+            return Ok(false);
+        };
+        // A holder of another handle could still change the game after it is written
+        if Arc::strong_count(game_arc) > 1 {
+            return Ok(false);
+        }
+        let hibernated = {
+            let game = game_arc.read().await;
+            if game.fault.is_some() {
+                return Ok(false);
+            }
+            let Ok(save) = game.to_savegame(bot_mode) else {
+                return Ok(false);
+            };
+            HibernatedGame {
+                save,
+                palette: game.palette,
+                version: game.version,
+                last_action_seq: game.last_action_seq,
+                forked_from: game.forked_from.clone(),
+                seat_bot_modes,
+                auto_pass,
+            }
+        };
+        store.save(game_id, &hibernated)?;
+        games.remove(game_id);
+        drop(games);
+
+        self.players.write().await.remove(game_id);
+        self.bot_modes.write().await.remove(game_id);
+        self.seat_bot_modes.write().await.remove(game_id);
+        self.auto_pass.write().await.remove(game_id);
+        log::info!(
+            "💤 Hibernated game {game_id} ({} actions)",
+            hibernated.save.replay.actions.len()
+        );
+        Ok(true)
+    }
+
+    /// Bring a hibernated game back into memory. `None` if it is not hibernated.
+    async fn wake_game(
+        &self,
+        store: &HibernationStore,
+        game_id: &str,
+    ) -> CatanResult<Option<Arc<RwLock<Game>>>> {
+        // Held until the game is fully registered, so concurrent requests wake it once
+        let mut games = self.games.write().await;
+        if let Some(game_arc) = games.get(game_id) {
+            return Ok(Some(game_arc.clone()));
+        }
+        let Some(hibernated) = store.load(game_id)? else {
+            return Ok(None);
+        };
+        let mut game = Game::from_savegame(game_id.to_string(), &hibernated.save)
+            .map_err(CatanError::Internal)?;
+        game.palette = hibernated.palette;
+        game.version = hibernated.version;
+        game.last_action_seq = hibernated.last_action_seq;
+        game.forked_from = hibernated.forked_from;
+        let players = Self::seat_players(&game);
+
+        let game_arc = Arc::new(RwLock::new(game));
+        games.insert(game_id.to_string(), game_arc.clone());
+        self.players
+            .write()
+            .await
+            .insert(game_id.to_string(), players);
+        self.bot_modes
+            .write()
+            .await
+            .insert(game_id.to_string(), hibernated.save.bot_mode);
+        if let Some(seat_modes) = hibernated.seat_bot_modes {
+            self.seat_bot_modes
+                .write()
+                .await
+                .insert(game_id.to_string(), seat_modes);
+        }
+        if !hibernated.auto_pass.is_empty() {
+            self.auto_pass
+                .write()
+                .await
+                .insert(game_id.to_string(), hibernated.auto_pass);
+        }
+        drop(games);
+
+        store.remove(game_id)?;
+        log::info!("⏰ Woke game {game_id} from hibernation");
+        Ok(Some(game_arc))
+    }
+
+    /// Player instances for a game's seats, bots where the game lists their color
+    fn seat_players(game: &Game) -> Vec<Player> {
+        game.players
+            .iter()
+            .map(|p| {
+                let (id, name, color) = (p.id.clone(), p.name.clone(), p.color.clone());
+                if game.bot_colors.contains(&color) {
+                    PlayerFactory::create_random_bot(id, name, color)
+                } else {
+                    PlayerFactory::create_human(id, name, color)
+                }
+            })
+            .collect()
+    }
+
     /// Remove a game (cleanup)
     pub async fn remove_game(&self, game_id: &str) -> CatanResult<()> {
         {
@@ -487,6 +1821,15 @@ impl GameService {
             players.remove(game_id);
         }
 
+        self.bot_modes.write().await.remove(game_id);
+        self.seat_bot_modes.write().await.remove(game_id);
+        self.sandboxes.write().await.retain(|id| id != game_id);
+        self.speed_rounds.write().await.remove(game_id);
+        self.auto_pass.write().await.remove(game_id);
+        if let Some(store) = self.hibernation.as_ref().filter(|s| s.contains(game_id)) {
+            store.remove(game_id)?;
+        }
+
         Ok(())
     }
 
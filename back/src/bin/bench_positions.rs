@@ -0,0 +1,149 @@
+use catan::players::bench::{load_suite, BenchPosition, DEFAULT_SUITE_PATH};
+use catan::players::{
+    AlphaBetaPlayer, BotPlayer, EvalProfile, GreedyPlayer, RandomPlayer, ValueFunctionPlayer,
+    WeightedRandomPlayer,
+};
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// Asks each engine configuration for its move in every benchmark position and reports
+/// how many it solves, per tag and overall. Much faster than an arena run, so it is a
+/// quick check that a change to search or evaluation has not made an engine worse.
+///
+/// Usage: bench_positions [-e ENGINES] [--suite PATH] [--profile PATH] [-v]
+///   ENGINES uses simulate's letters (A, S, V, G, W, R), default AVG
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().collect();
+    let mut engines = "AVG".to_string();
+    let mut suite_path = PathBuf::from(DEFAULT_SUITE_PATH);
+    let mut profile: Option<EvalProfile> = None;
+    let mut verbose = false;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-e" | "--engines" if i + 1 < args.len() => {
+                engines = args[i + 1].clone();
+                i += 1;
+            }
+            "--suite" if i + 1 < args.len() => {
+                suite_path = PathBuf::from(&args[i + 1]);
+                i += 1;
+            }
+            "--profile" if i + 1 < args.len() => {
+                match EvalProfile::load(Path::new(&args[i + 1])) {
+                    Ok(loaded) => profile = Some(loaded),
+                    Err(e) => {
+                        eprintln!("{e}");
+                        return ExitCode::FAILURE;
+                    }
+                }
+                i += 1;
+            }
+            "-v" | "--verbose" => verbose = true,
+            other => {
+                eprintln!("Unknown argument: {other}");
+                return ExitCode::FAILURE;
+            }
+        }
+        i += 1;
+    }
+
+    let suite = match load_suite(&suite_path) {
+        Ok(suite) => suite,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("{} positions from {}\n", suite.len(), suite_path.display());
+
+    for c in engines.chars() {
+        let (engine, label) = build_engine(c, profile.as_ref());
+        let score = score_engine(engine.as_ref(), &suite, verbose);
+        score.print(label);
+    }
+    ExitCode::SUCCESS
+}
+
+fn build_engine(c: char, profile: Option<&EvalProfile>) -> (Box<dyn BotPlayer>, &'static str) {
+    match (c, profile) {
+        ('V' | 'v', Some(profile)) => (Box::new(profile.value_player()), "ProfiledValue"),
+        ('V' | 'v', None) => (Box::new(ValueFunctionPlayer::new()), "Value"),
+        ('G' | 'g', Some(profile)) => (Box::new(profile.greedy_player()), "ProfiledGreedy"),
+        ('G' | 'g', None) => (Box::new(GreedyPlayer::new()), "Greedy"),
+        ('W' | 'w', _) => (Box::new(WeightedRandomPlayer::new()), "WeightedRandom"),
+        ('A' | 'a', Some(profile)) => (Box::new(profile.alpha_beta_player()), "ProfiledAlphaBeta"),
+        ('A' | 'a', None) => (Box::new(AlphaBetaPlayer::new()), "AlphaBeta"),
+        ('S' | 's', profile) => {
+            let mut engine = profile
+                .map(EvalProfile::alpha_beta_player)
+                .unwrap_or_default();
+            engine.set_spiteful_robber(true);
+            (Box::new(engine), "SpitefulAlphaBeta")
+        }
+        _ => (Box::new(RandomPlayer::new()), "Random"),
+    }
+}
+
+#[derive(Default)]
+struct Score {
+    /// tag -> (solved, total)
+    by_tag: BTreeMap<String, (usize, usize)>,
+    elapsed: Duration,
+    errors: usize,
+}
+
+fn score_engine(engine: &dyn BotPlayer, suite: &[BenchPosition], verbose: bool) -> Score {
+    let mut score = Score::default();
+    for position in suite {
+        let state = match position.state() {
+            Ok(state) => state,
+            Err(e) => {
+                eprintln!("line {}: {e}", position.line);
+                score.errors += 1;
+                continue;
+            }
+        };
+        let actions = state.generate_playable_actions();
+        let start = Instant::now();
+        let action = engine.decide(&state, &actions);
+        score.elapsed += start.elapsed();
+
+        let solved = position.is_solved_by(&state, action);
+        let entry = score.by_tag.entry(position.tag.clone()).or_default();
+        entry.1 += 1;
+        if solved {
+            entry.0 += 1;
+        } else if verbose {
+            println!(
+                "  ✗ line {} ({}): played {action:?}",
+                position.line, position.tag
+            );
+        }
+    }
+    score
+}
+
+impl Score {
+    fn print(&self, label: &str) {
+        let (solved, total) = self
+            .by_tag
+            .values()
+            .fold((0, 0), |(s, t), &(solved, total)| (s + solved, t + total));
+        let per_move = self.elapsed.as_secs_f64() * 1000.0 / total.max(1) as f64;
+        println!("{label}: {solved}/{total} solved, {per_move:.1} ms per move");
+        for (tag, (solved, total)) in &self.by_tag {
+            println!("  {tag:<24} {solved:>3}/{total}");
+        }
+        if self.errors > 0 {
+            println!("  {} positions failed to load", self.errors);
+        }
+        println!();
+    }
+}
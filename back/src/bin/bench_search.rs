@@ -0,0 +1,120 @@
+use catan::enums::Action;
+use catan::players::bench::{load_suite, DEFAULT_SUITE_PATH};
+use catan::players::{AlphaBetaPlayer, BotPlayer};
+use catan::state::State;
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// Throughput benchmark over the benchmark positions: how fast move generation runs
+/// with a fresh list per call versus a reused buffer, and how many nodes per second
+/// AlphaBeta searches at a fixed time budget. Use a release build.
+///
+/// Usage: bench_search [--suite PATH] [-n MOVEGEN_PASSES] [--ms BUDGET] [--rounds N]
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().collect();
+    let mut suite_path = PathBuf::from(DEFAULT_SUITE_PATH);
+    let mut passes = 2000;
+    let mut budget_ms = 100;
+    let mut rounds = 3;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--suite" if i + 1 < args.len() => {
+                suite_path = PathBuf::from(&args[i + 1]);
+                i += 1;
+            }
+            "-n" | "--passes" if i + 1 < args.len() => {
+                passes = args[i + 1].parse().unwrap_or(passes);
+                i += 1;
+            }
+            "--ms" if i + 1 < args.len() => {
+                budget_ms = args[i + 1].parse().unwrap_or(budget_ms);
+                i += 1;
+            }
+            "--rounds" if i + 1 < args.len() => {
+                rounds = args[i + 1].parse().unwrap_or(rounds);
+                i += 1;
+            }
+            other => {
+                eprintln!("Unknown argument: {other}");
+                return ExitCode::FAILURE;
+            }
+        }
+        i += 1;
+    }
+
+    let suite = match load_suite(&suite_path) {
+        Ok(suite) => suite,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let states: Vec<State> = suite.iter().filter_map(|p| p.state().ok()).collect();
+    println!("{} positions from {}\n", states.len(), suite_path.display());
+
+    let generated = passes * states.len();
+    let fresh = time_movegen(passes, &states, |state, _| {
+        state.generate_playable_actions().len()
+    });
+    let reused = time_movegen(passes, &states, |state, buffer| {
+        state.generate_playable_actions_into(buffer);
+        buffer.len()
+    });
+    println!("move generation ({generated} positions)");
+    println!(
+        "  fresh Vec       {:>10.0} positions/s",
+        rate(generated, fresh)
+    );
+    println!(
+        "  reused buffer   {:>10.0} positions/s\n",
+        rate(generated, reused)
+    );
+
+    let mut nodes = 0;
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..rounds {
+        for state in &states {
+            let mut engine = AlphaBetaPlayer::new();
+            engine.set_time_profile(budget_ms, budget_ms, usize::MAX);
+            let actions = state.generate_playable_actions();
+            let start = Instant::now();
+            engine.decide(state, &actions);
+            elapsed += start.elapsed();
+            nodes += engine.last_search_nodes();
+        }
+    }
+    println!("AlphaBeta search ({budget_ms} ms budget, {rounds} rounds)");
+    println!(
+        "  {nodes} nodes in {elapsed:.1?}, {:.0} nodes/s",
+        rate(nodes, elapsed)
+    );
+    ExitCode::SUCCESS
+}
+
+fn time_movegen(
+    passes: usize,
+    states: &[State],
+    generate: impl Fn(&State, &mut Vec<Action>) -> usize,
+) -> Duration {
+    let mut buffer = Vec::new();
+    let mut total = 0;
+    let start = Instant::now();
+    for _ in 0..passes {
+        for state in states {
+            total += generate(state, &mut buffer);
+        }
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(total);
+    elapsed
+}
+
+fn rate(count: usize, elapsed: Duration) -> f64 {
+    count as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+}
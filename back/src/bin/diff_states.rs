@@ -0,0 +1,75 @@
+use catan::state::diff::DiffSection;
+use catan::state::State;
+use std::env;
+use std::process::ExitCode;
+
+/// Compares two serialized states field by field and prints what differs in hands, board,
+/// awards and flags, e.g. this engine's state against another engine's after replaying
+/// the same action list. Each file holds a state as JSON or as a FEN line.
+///
+/// Usage: diff_states LEFT RIGHT [--json]
+///   Exits with 1 when the states differ, 2 when either cannot be read
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let json = args.iter().any(|arg| arg == "--json");
+    let paths: Vec<&String> = args.iter().filter(|arg| *arg != "--json").collect();
+    let [left_path, right_path] = paths[..] else {
+        eprintln!("Usage: diff_states LEFT RIGHT [--json]");
+        return ExitCode::from(2);
+    };
+
+    let (left, right) = match (load(left_path), load(right_path)) {
+        (Ok(left), Ok(right)) => (left, right),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let diff = left.diff(&right);
+    if json {
+        match serde_json::to_string_pretty(&diff) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("Failed to serialize the diff: {e}");
+                return ExitCode::from(2);
+            }
+        }
+    } else if diff.is_empty() {
+        println!("✅ States match");
+    } else {
+        let mut section: Option<DiffSection> = None;
+        for field in &diff {
+            if section != Some(field.section) {
+                section = Some(field.section);
+                println!("{:?}:", field.section);
+            }
+            let side = |value: &Option<String>| value.clone().unwrap_or_else(|| "—".to_string());
+            println!(
+                "  {}: {} ≠ {}",
+                field.field,
+                side(&field.left),
+                side(&field.right)
+            );
+        }
+        println!("❌ {} fields differ", diff.len());
+    }
+
+    if diff.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// A state from a JSON snapshot, or from FEN when the file is not JSON
+fn load(path: &str) -> Result<State, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    if text.trim_start().starts_with('{') {
+        serde_json::from_str(&text).map_err(|e| format!("Invalid state JSON in {path}: {e}"))
+    } else {
+        State::from_fen(text.trim()).map_err(|e| format!("Invalid FEN in {path}: {e}"))
+    }
+}
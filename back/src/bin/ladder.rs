@@ -0,0 +1,103 @@
+use catan::players::ladder::{Ladder, DEFAULT_ARENA_GAMES, DEFAULT_PROMOTION_MARGIN};
+use catan::players::value::ValueWeights;
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Maintains the champion lineage of tuned value weights. A candidate weights file (JSON,
+/// same fields as ValueWeights) plays an arena against the current champion and is
+/// appended as the next version only if it wins by the required margin.
+///
+/// Usage: ladder LINEAGE [CANDIDATE] [--games N] [--margin F] [--seed S]
+///        (without CANDIDATE, prints the lineage)
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut positional: Vec<&str> = Vec::new();
+    let mut games = DEFAULT_ARENA_GAMES;
+    let mut margin = DEFAULT_PROMOTION_MARGIN;
+    let mut seed: u64 = 0;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--games" if i + 1 < args.len() => {
+                games = args[i + 1].parse().unwrap_or(games);
+                i += 1;
+            }
+            "--margin" if i + 1 < args.len() => {
+                margin = args[i + 1].parse().unwrap_or(margin);
+                i += 1;
+            }
+            "--seed" if i + 1 < args.len() => {
+                seed = args[i + 1].parse().unwrap_or(seed);
+                i += 1;
+            }
+            other => positional.push(other),
+        }
+        i += 1;
+    }
+
+    let Some(lineage_path) = positional.first().map(PathBuf::from) else {
+        eprintln!("Usage: ladder LINEAGE [CANDIDATE] [--games N] [--margin F] [--seed S]");
+        return ExitCode::FAILURE;
+    };
+    let mut ladder = match Ladder::load(&lineage_path) {
+        Ok(ladder) => ladder,
+        Err(e) => {
+            eprintln!("Failed to load lineage: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(candidate_path) = positional.get(1).map(PathBuf::from) else {
+        for checkpoint in &ladder.checkpoints {
+            match checkpoint.arena {
+                Some(arena) => println!(
+                    "{}: won {}/{} against the previous champion",
+                    checkpoint.tag,
+                    arena.candidate_wins,
+                    arena.games()
+                ),
+                None => println!("{}: initial champion", checkpoint.tag),
+            }
+        }
+        return ExitCode::SUCCESS;
+    };
+    let candidate: ValueWeights = match std::fs::read_to_string(&candidate_path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+    {
+        Ok(weights) => weights,
+        Err(e) => {
+            eprintln!("Failed to read {}: {e}", candidate_path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let challenge = ladder.challenge(candidate, games, margin, seed);
+    if let Some(arena) = challenge.arena {
+        println!(
+            "Arena: candidate {} - {} champion ({} unfinished), win rate {:.1}% (needs {:.1}%)",
+            arena.candidate_wins,
+            arena.champion_wins,
+            arena.unfinished,
+            arena.candidate_win_rate() * 100.0,
+            (0.5 + margin) * 100.0
+        );
+    }
+    match challenge.promoted_as {
+        Some(tag) => {
+            if let Err(e) = ladder.save(&lineage_path) {
+                eprintln!("Failed to save lineage: {e}");
+                return ExitCode::FAILURE;
+            }
+            println!("🏆 Promoted candidate as {tag}");
+        }
+        None => println!(
+            "Candidate rejected; champion stays {}",
+            ladder.champion().map_or("-", |c| c.tag.as_str())
+        ),
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,196 @@
+use catan::players::bench::load_suite;
+use catan::players::playout::{playout_win_rates, ActionWinRate, BotFactory, PlayoutSettings};
+use catan::players::{
+    AlphaBetaPlayer, BotPlayer, EvalProfile, GreedyPlayer, RandomPlayer, ValueFunctionPlayer,
+    WeightedRandomPlayer,
+};
+use catan::state::State;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+/// Plays every legal action of a position out to the end with configured bots and
+/// prints empirical win rates for the side to move. With `--suite` it does this for
+/// every benchmark position and flags those whose annotation disagrees with the best
+/// playout result.
+///
+/// Usage: playout [-p BOTS] [-n GAMES] [--threads N] [--max-actions N] [--seed S]
+///                [--profile PATH] [--top K] (FEN | --suite PATH)
+///   BOTS uses simulate's letters, one per seat (default VVVV)
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().collect();
+    let mut bots = "VVVV".to_string();
+    let mut settings = PlayoutSettings::default();
+    let mut threads: Option<usize> = None;
+    let mut profile: Option<EvalProfile> = None;
+    let mut top = 5;
+    let mut suite_path: Option<PathBuf> = None;
+    let mut fen: Option<String> = None;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-p" | "--players" if i + 1 < args.len() => {
+                bots = args[i + 1].clone();
+                i += 1;
+            }
+            "-n" | "--games" if i + 1 < args.len() => {
+                settings.games_per_action = args[i + 1].parse().unwrap_or(100);
+                i += 1;
+            }
+            "--threads" if i + 1 < args.len() => {
+                threads = args[i + 1].parse().ok();
+                i += 1;
+            }
+            "--max-actions" if i + 1 < args.len() => {
+                settings.max_actions = args[i + 1].parse().unwrap_or(settings.max_actions);
+                i += 1;
+            }
+            "--seed" if i + 1 < args.len() => {
+                settings.seed = args[i + 1].parse().unwrap_or(0);
+                i += 1;
+            }
+            "--top" if i + 1 < args.len() => {
+                top = args[i + 1].parse().unwrap_or(top);
+                i += 1;
+            }
+            "--profile" if i + 1 < args.len() => {
+                match EvalProfile::load(Path::new(&args[i + 1])) {
+                    Ok(loaded) => profile = Some(loaded),
+                    Err(e) => {
+                        eprintln!("{e}");
+                        return ExitCode::FAILURE;
+                    }
+                }
+                i += 1;
+            }
+            "--suite" if i + 1 < args.len() => {
+                suite_path = Some(PathBuf::from(&args[i + 1]));
+                i += 1;
+            }
+            other if !other.starts_with('-') => fen = Some(other.to_string()),
+            other => {
+                eprintln!("Unknown argument: {other}");
+                return ExitCode::FAILURE;
+            }
+        }
+        i += 1;
+    }
+
+    if let Some(threads) = threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .expect("thread pool is configured once");
+    }
+    let letters: Vec<char> = bots.chars().collect();
+    let factory = move |color: u8| -> Box<dyn BotPlayer> {
+        let letter = letters.get(color as usize).copied().unwrap_or('R');
+        build_bot(letter, profile.as_ref())
+    };
+
+    match (suite_path, fen) {
+        (Some(path), _) => {
+            let suite = match load_suite(&path) {
+                Ok(suite) => suite,
+                Err(e) => {
+                    eprintln!("{e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let mut disagreements = 0;
+            for position in &suite {
+                let Ok(state) = position.state() else {
+                    eprintln!("line {}: bad position", position.line);
+                    continue;
+                };
+                let rates = report(&state, &factory, &settings, top);
+                let agrees = rates
+                    .first()
+                    .is_some_and(|best| position.is_solved_by(&state, best.action));
+                if !agrees {
+                    disagreements += 1;
+                }
+                println!(
+                    "line {} ({}): annotation {}\n",
+                    position.line,
+                    position.tag,
+                    if agrees { "agrees" } else { "DISAGREES" }
+                );
+            }
+            println!(
+                "{} of {} annotations disagree with playouts",
+                disagreements,
+                suite.len()
+            );
+        }
+        (None, Some(fen)) => match State::from_fen(&fen) {
+            Ok(state) => {
+                report(&state, &factory, &settings, top);
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        (None, None) => {
+            eprintln!("Give a position (FEN) or --suite PATH");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn report(
+    state: &State,
+    factory: &BotFactory,
+    settings: &PlayoutSettings,
+    top: usize,
+) -> Vec<ActionWinRate> {
+    let start = Instant::now();
+    let rates = playout_win_rates(state, factory, settings);
+    println!(
+        "{} actions x {} games in {:.1?}",
+        rates.len(),
+        settings.games_per_action,
+        start.elapsed()
+    );
+    for rate in rates.iter().take(top) {
+        println!(
+            "  {:>5.1}% ±{:>4.1}  {:>6.0} actions  {:?}{}",
+            rate.win_rate() * 100.0,
+            rate.std_error() * 100.0,
+            rate.mean_win_length(),
+            rate.action,
+            if rate.unfinished > 0 {
+                format!("  ({} unfinished)", rate.unfinished)
+            } else {
+                String::new()
+            }
+        );
+    }
+    rates
+}
+
+fn build_bot(letter: char, profile: Option<&EvalProfile>) -> Box<dyn BotPlayer> {
+    match (letter, profile) {
+        ('V' | 'v', Some(profile)) => Box::new(profile.value_player()),
+        ('V' | 'v', None) => Box::new(ValueFunctionPlayer::new()),
+        ('G' | 'g', Some(profile)) => Box::new(profile.greedy_player()),
+        ('G' | 'g', None) => Box::new(GreedyPlayer::new()),
+        ('W' | 'w', _) => Box::new(WeightedRandomPlayer::new()),
+        ('A' | 'a', Some(profile)) => Box::new(profile.alpha_beta_player()),
+        ('A' | 'a', None) => Box::new(AlphaBetaPlayer::new()),
+        ('S' | 's', profile) => {
+            let mut engine = profile
+                .map(EvalProfile::alpha_beta_player)
+                .unwrap_or_default();
+            engine.set_spiteful_robber(true);
+            Box::new(engine)
+        }
+        _ => Box::new(RandomPlayer::new()),
+    }
+}
@@ -1,12 +1,38 @@
-use catan::enums::Action;
+use axum::{
+    extract::{Path, State as AxumState, WebSocketUpgrade},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use catan::application::GameService;
+use catan::dice_stats::DiceHistogram;
+use catan::enums::{
+    Action, DiceMode, GameConfiguration, RobberRules, RulesVersion, TeamConfig, TeamVictory,
+    VariantConfig,
+};
 use catan::game::*;
+use catan::placement_stats::PlacementHeatmap;
+use catan::players::win_model::{win_features, WinFeatures, WinModel};
 use catan::players::{
-    AlphaBetaPlayer, AlphaZeroPlayer, BotPlayer, GreedyPlayer, RandomPlayer, ValueFunctionPlayer,
-    WeightedRandomPlayer,
+    AlphaBetaPlayer, AlphaZeroPlayer, BotPlayer, EvalProfile, GreedyPlayer, RandomPlayer,
+    SeatedBot, ValueFunctionPlayer, WeightedRandomPlayer,
 };
-use std::collections::HashMap;
+use catan::replay::Replay;
+use catan::simulation::{
+    new_game_state, run_from, GameObserver, GameRecord, SeatOrder, SeedPool, SeedPoolResults,
+    SimulationSettings, Termination,
+};
+use catan::state::State;
+use catan::websocket::{WebSocketService, WsMessage};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
-use std::time::Instant;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower_http::cors::{Any, CorsLayer};
 
 fn main() {
     env_logger::init();
@@ -16,22 +42,34 @@ fn main() {
     let mut verbose = false;
     let mut players_config = "RRRR".to_string(); // Default: 4 random players
     let mut dump_timeout = false;
+    let mut action_stats_csv: Option<String> = None;
+    let mut serve = false;
+    let mut port: u16 = 8000;
+    let mut delay_ms: u64 = 500;
+    let mut replay_dir: Option<PathBuf> = None;
+    let mut records_path: Option<PathBuf> = None;
+    let mut heatmap_path: Option<PathBuf> = None;
+    let mut fit_win_model = false;
+    let mut team_victory: Option<TeamVictory> = None;
+    let mut robber_rules = RobberRules::default();
+    let mut variants = VariantConfig::default();
+    let mut rules = RulesVersion::default();
+    let mut profile_args: Vec<String> = Vec::new();
+    let mut seed: Option<u64> = None;
+    let mut seed_pool: Option<SeedPool> = None;
+    let mut seed_pool_size: Option<usize> = None;
 
     // Parse command line arguments
     let mut i = 1;
     while i < args.len() {
         match args[i].as_str() {
-            "-n" | "--num_games" => {
-                if i + 1 < args.len() {
-                    num_games = args[i + 1].parse().unwrap_or(1);
-                    i += 1;
-                }
+            "-n" | "--num_games" if i + 1 < args.len() => {
+                num_games = args[i + 1].parse().unwrap_or(1);
+                i += 1;
             }
-            "-p" | "--players" => {
-                if i + 1 < args.len() {
-                    players_config = args[i + 1].clone();
-                    i += 1;
-                }
+            "-p" | "--players" if i + 1 < args.len() => {
+                players_config = args[i + 1].clone();
+                i += 1;
             }
             "-v" | "--verbose" => {
                 verbose = true;
@@ -39,6 +77,97 @@ fn main() {
             "-t" | "--dump-timeout" => {
                 dump_timeout = true;
             }
+            "--action-stats-csv" if i + 1 < args.len() => {
+                action_stats_csv = Some(args[i + 1].clone());
+                i += 1;
+            }
+            "--record-replays" if i + 1 < args.len() => {
+                replay_dir = Some(PathBuf::from(&args[i + 1]));
+                i += 1;
+            }
+            "--game-records" if i + 1 < args.len() => {
+                records_path = Some(PathBuf::from(&args[i + 1]));
+                i += 1;
+            }
+            "--placement-heatmap" if i + 1 < args.len() => {
+                heatmap_path = Some(PathBuf::from(&args[i + 1]));
+                i += 1;
+            }
+            "--fit-win-model" => {
+                fit_win_model = true;
+            }
+            "--serve" => {
+                serve = true;
+            }
+            "--port" if i + 1 < args.len() => {
+                port = args[i + 1].parse().unwrap_or(port);
+                i += 1;
+            }
+            "--delay-ms" if i + 1 < args.len() => {
+                delay_ms = args[i + 1].parse().unwrap_or(delay_ms);
+                i += 1;
+            }
+            "--seed" if i + 1 < args.len() => {
+                seed = args[i + 1].parse().ok();
+                i += 1;
+            }
+            "--seed-pool" if i + 1 < args.len() => {
+                match SeedPool::parse(&args[i + 1]) {
+                    Ok(pool) => seed_pool = Some(pool),
+                    Err(e) => {
+                        eprintln!("{e}");
+                        return;
+                    }
+                }
+                i += 1;
+            }
+            "--seed-pool-size" if i + 1 < args.len() => {
+                seed_pool_size = args[i + 1].parse().ok();
+                i += 1;
+            }
+            "--profile" if i + 1 < args.len() => {
+                profile_args.push(args[i + 1].clone());
+                i += 1;
+            }
+            "--harbormaster" => {
+                variants.harbormaster = true;
+            }
+            "--event-cards" => {
+                variants.dice = DiceMode::EventCards;
+            }
+            "--balanced-dice" => {
+                variants.dice = DiceMode::Balanced;
+            }
+            "--catch-up-bonus" => {
+                variants.catch_up.leader_roll_bonus = true;
+            }
+            "--catch-up-immunity" if i + 1 < args.len() => {
+                variants.catch_up.robber_immunity_gap = args[i + 1].parse().ok();
+                i += 1;
+            }
+            "--legacy-rules" => {
+                rules = RulesVersion::Legacy;
+            }
+            "--friendly-robber" => {
+                robber_rules.protect_up_to_vps = RobberRules::friendly().protect_up_to_vps;
+            }
+            "--no-steal-robber" => {
+                robber_rules.allow_steal = false;
+            }
+            "--no-desert-robber" => {
+                robber_rules.allow_desert = false;
+            }
+            "--teams" if i + 1 < args.len() => {
+                team_victory = match args[i + 1].as_str() {
+                    "combined" => Some(TeamVictory::CombinedPoints),
+                    "first" => Some(TeamVictory::FirstTeammate),
+                    other => {
+                        eprintln!("Unknown --teams mode '{other}' (expected combined or first)");
+                        return;
+                    }
+                };
+                i += 1;
+            }
             _ => {}
         }
         i += 1;
@@ -62,7 +191,6 @@ fn main() {
     // Termination reasons
     let mut timeout_games: u32 = 0;
     let mut no_actions_games: u32 = 0;
-    let mut no_state_games: u32 = 0;
     let mut no_actions_by_prompt: HashMap<String, u32> = HashMap::new();
     let mut timeout_turns: u64 = 0;
     let mut timeout_bank_zero_sum: u64 = 0;
@@ -71,48 +199,170 @@ fn main() {
     let mut timeout_vp_sum: u64 = 0; // sum of total VP across players at timeout
     let mut no_actions_vp_sum: u64 = 0; // sum of total VP across players at no-actions
 
+    let profiles = match load_seat_profiles(&profile_args, num_players) {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            eprintln!("{e}");
+            return;
+        }
+    };
+
     // Build bot lineup from players_config (R,G,W,A,Z)
-    let (bots, bot_labels) = build_bots_from_config(&players_config);
+    let (bots, bot_labels) = build_bots_from_config(&players_config, &profiles);
+    // The same lineup seated back to front, for the second game on each pool seed
+    let reversed_config: String = players_config.chars().rev().collect();
+    let reversed_profiles: Vec<_> = profiles.iter().rev().cloned().collect();
+    let (reversed_bots, reversed_labels) =
+        build_bots_from_config(&reversed_config, &reversed_profiles);
+    let mut action_stats = ActionStats::default();
+    let mut loop_breaks: u32 = 0;
+    let mut dice = DiceHistogram::default();
+
+    if team_victory.is_some() && bots.len() != 4 {
+        eprintln!("--teams needs exactly 4 players (2v2)");
+        return;
+    }
+
+    if let Some(dir) = &replay_dir {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            eprintln!("Failed to create replay directory {}: {e}", dir.display());
+            return;
+        }
+    }
+
+    let mut records_file = match &records_path {
+        Some(path) => match File::create(path) {
+            Ok(file) => Some(io::BufWriter::new(file)),
+            Err(e) => {
+                eprintln!("Failed to create {}: {e}", path.display());
+                return;
+            }
+        },
+        None => None,
+    };
 
-    for game_num in 0..num_games {
+    if serve {
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to start tokio runtime");
+        let num_players = bots.len() as u8;
+        let lineup = move || build_bots_from_config(&players_config, &profiles).0;
+        runtime.block_on(serve_single_game(lineup, num_players, port, delay_ms));
+        return;
+    }
+
+    let base_seed = seed.unwrap_or_else(rand::random);
+    if let Some(size) = seed_pool_size {
+        seed_pool.get_or_insert_with(|| SeedPool::consecutive(base_seed, size));
+    }
+    let games: Vec<(u64, SeatOrder)> = match &seed_pool {
+        Some(pool) => {
+            println!(
+                "Seed pool: {:?}, each played with the lineup as listed and reversed",
+                pool.seeds
+            );
+            pool.games().collect()
+        }
+        None => {
+            println!("Seed: {base_seed} (game i uses seed + i)");
+            (0..num_games as u64)
+                .map(|game_num| (base_seed.wrapping_add(game_num), SeatOrder::AsListed))
+                .collect()
+        }
+    };
+    num_games = games.len();
+    let mut pool_results = SeedPoolResults::default();
+    let mut heatmap = PlacementHeatmap::default();
+    let mut win_samples: Vec<(WinFeatures, bool)> = Vec::new();
+    for (game_num, &(game_seed, order)) in games.iter().enumerate() {
         if num_games > 1 {
             log::info!("\n🎯 Game {} of {}", game_num + 1, num_games);
         }
 
-        let result = simulate_single_game(&bots, verbose, dump_timeout);
-        match result {
-            SimOutcome::Completed { winner, turns, vps } => {
-                wins[winner as usize] += 1;
-                total_turns += turns as u64;
+        let (bots, labels) = match order {
+            SeatOrder::AsListed => (&bots, &bot_labels),
+            SeatOrder::Reversed => (&reversed_bots, &reversed_labels),
+        };
+        let entry = |color: u8| order.entry(color, bots.len() as u8);
+        let config = configured_game(
+            bots.len() as u8,
+            team_victory,
+            robber_rules.clone(),
+            variants.clone(),
+            rules,
+        );
+        let state = new_game_state(config, game_seed);
+        let replay = replay_dir
+            .as_ref()
+            .map(|_| Replay::record_start(&state, game_seed));
+        let mut observer =
+            SimObserver::new(labels, &mut action_stats, replay, verbose, dump_timeout);
+        observer.collect_win_samples = fit_win_model;
+        let mut record = run_from(state, bots, &SimulationSettings::default(), &mut observer);
+        let (bank_zeroes, actions_len) = (observer.bank_zeroes, observer.actions_len);
+        win_samples.append(&mut observer.win_samples);
+        if let (Some(dir), Some(replay)) = (&replay_dir, &observer.replay) {
+            let path = dir.join(format!("game_{:04}.json", game_num + 1));
+            if let Err(e) = replay.save(&path) {
+                eprintln!("Failed to save replay: {e}");
+            }
+        }
+        record.lineup = labels.clone();
+        if heatmap_path.is_some() {
+            heatmap.record(&record);
+        }
+        if let Some(file) = records_file.as_mut() {
+            let written = serde_json::to_string(&record)
+                .map_err(io::Error::from)
+                .and_then(|line| writeln!(file, "{line}"));
+            if let Err(e) = written {
+                eprintln!("Failed to write game record: {e}");
+            }
+        }
+        loop_breaks += record.loop_breaks;
+        dice.merge(&record.dice);
+        let turns = record.actions.len() as u64;
+        let vp_total = record.final_vps.iter().map(|&v| v as u64).sum::<u64>();
+        if seed_pool.is_some() {
+            pool_results.record(order, &record);
+        }
+        match (record.termination, record.winner) {
+            (Termination::Victory, Some(winner)) => {
+                wins[entry(winner)] += 1;
+                total_turns += turns;
                 completed_games += 1;
-                for (i, &vp) in vps.iter().enumerate() {
-                    vp_sum[i] += vp as u64;
-                    vp_sum_sq[i] += (vp as u128) * (vp as u128);
+                for (color, &vp) in record.final_vps.iter().enumerate() {
+                    vp_sum[entry(color as u8)] += vp as u64;
+                    vp_sum_sq[entry(color as u8)] += (vp as u128) * (vp as u128);
                 }
                 if num_games > 1 {
-                    let label = bot_labels[winner as usize].as_str();
+                    let label = labels[winner as usize].as_str();
                     log::info!("  Winner: Player {winner} ({label}) in {turns} turns");
                 }
             }
-            SimOutcome::Timeout {
-                turns,
-                vps,
-                bank_zeroes,
-                actions_len,
-            } => {
+            (Termination::NoActions { prompt }, _) => {
+                no_actions_games += 1;
+                no_actions_turns += turns;
+                no_actions_vp_sum += vp_total;
+                *no_actions_by_prompt.entry(prompt).or_insert(0) += 1;
+            }
+            _ => {
                 timeout_games += 1;
-                timeout_turns += turns as u64;
-                timeout_vp_sum += vps.iter().map(|&v| v as u64).sum::<u64>();
+                timeout_turns += turns;
+                timeout_vp_sum += vp_total;
                 timeout_bank_zero_sum += bank_zeroes as u64;
                 timeout_actions_sum += actions_len as u64;
             }
-            SimOutcome::NoActions { turns, prompt, vps } => {
-                no_actions_games += 1;
-                no_actions_turns += turns as u64;
-                no_actions_vp_sum += vps.iter().map(|&v| v as u64).sum::<u64>();
-                *no_actions_by_prompt.entry(prompt).or_insert(0) += 1;
-            }
-            SimOutcome::NoState => no_state_games += 1,
+        }
+    }
+
+    if let Some(file) = records_file.as_mut() {
+        if let Err(e) = file.flush() {
+            eprintln!("Failed to write game records: {e}");
+        }
+    }
+
+    if let Some(path) = &heatmap_path {
+        if let Err(e) = heatmap.save(path) {
+            eprintln!("Failed to write placement heatmap: {e}");
         }
     }
 
@@ -142,11 +392,20 @@ fn main() {
             "Player {i} ({label}): {win_count} wins ({win_rate:.1}%), mean VP: {mean_vp:.2} ± {std_vp:.2}"
         );
     }
+    if team_victory.is_some() {
+        for team in 0..2 {
+            let team_wins = wins[team] + wins[team + 2];
+            println!(
+                "Team {team} (players {team} & {}): {team_wins} wins",
+                team + 2
+            );
+        }
+    }
     println!("Completed games: {completed_games}/{num_games}");
     let incomplete = num_games as u32 - completed_games;
     if incomplete > 0 {
         println!(
-            "Incomplete: {incomplete} (timeouts: {timeout_games}, no_actions: {no_actions_games}, no_state: {no_state_games})"
+            "Incomplete: {incomplete} (timeouts: {timeout_games}, no_actions: {no_actions_games})"
         );
         if timeout_games > 0 {
             let avg = timeout_turns as f64 / timeout_games as f64;
@@ -163,12 +422,21 @@ fn main() {
             println!("  - NoActions: avg turns {avg:.1}, mean total VP {mean_vp_sum:.2}");
             // Print top 3 prompts causing no_actions
             let mut items: Vec<(String, u32)> = no_actions_by_prompt.into_iter().collect();
-            items.sort_by(|a, b| b.1.cmp(&a.1));
+            items.sort_by_key(|item| std::cmp::Reverse(item.1));
             for (i, (prompt, count)) in items.into_iter().take(3).enumerate() {
                 println!("    {}. {}: {}", i + 1, prompt, count);
             }
         }
     }
+    if seed_pool.is_some() {
+        print_seed_pool_results(&pool_results, &bot_labels);
+    }
+    if dice.rolls() > 0 {
+        println!("Dice: {}", dice.fairness());
+    }
+    if loop_breaks > 0 {
+        println!("Loop breaks (repeated state within a turn): {loop_breaks}");
+    }
     if completed_games > 0 {
         println!(
             "Average turns per game: {:.1}",
@@ -186,426 +454,647 @@ fn main() {
     };
     let per_game_secs = if denom > 0.0 { total_secs / denom } else { 0.0 };
     println!("Total time: {total_secs:.3}s | Avg time per game: {per_game_secs:.3}s");
+
+    if fit_win_model {
+        print_win_model_fit(&win_samples);
+    }
+
+    action_stats.print_summary();
+    if let Some(path) = action_stats_csv {
+        match action_stats.write_csv(&path) {
+            Ok(()) => println!("Action stats written to {path}"),
+            Err(e) => eprintln!("Failed to write action stats to {path}: {e}"),
+        }
+    }
 }
 
-enum SimOutcome {
-    Completed {
-        winner: u8,
-        turns: u32,
-        vps: Vec<u8>,
-    },
-    Timeout {
-        turns: u32,
-        vps: Vec<u8>,
-        bank_zeroes: u8,
-        actions_len: usize,
-    },
-    NoActions {
-        turns: u32,
-        prompt: String,
-        vps: Vec<u8>,
-    },
-    NoState,
+/// One line per pool seed: each lineup entry's wins and mean VP over both seat orders
+fn print_seed_pool_results(results: &SeedPoolResults, labels: &[String]) {
+    println!("Per-seed results (wins / mean VP, lineup order):");
+    for result in &results.seeds {
+        let entries: Vec<String> = labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| format!("{label} {}/{:.1}", result.wins[i], result.mean_vp(i)))
+            .collect();
+        let unfinished = if result.unfinished > 0 {
+            format!(" ({} unfinished)", result.unfinished)
+        } else {
+            String::new()
+        };
+        println!("  seed {}: {}{unfinished}", result.seed, entries.join(", "));
+    }
 }
 
-fn simulate_single_game(
-    bots: &[Box<dyn BotPlayer>],
-    verbose: bool,
-    dump_timeout: bool,
-) -> SimOutcome {
-    // Create a real game with actual game logic
-    let mut game = simulate_bot_game(bots.len() as u8);
-
-    if verbose {
-        log::debug!(
-            "✅ Created game with real State and {} players",
-            game.players.len()
+/// Fit the win probability model to the sampled positions and print its coefficients
+/// with a calibration table
+fn print_win_model_fit(samples: &[(WinFeatures, bool)]) {
+    let Some(model) = WinModel::fit(samples) else {
+        println!("Win model: no finished games to fit");
+        return;
+    };
+    println!("Win model fitted on {} positions:", samples.len());
+    println!(
+        "  intercept {:.4}, VP lead {:.4}, production lead (dots) {:.4}, dev card lead {:.4}",
+        model.intercept, model.weights[0], model.weights[1], model.weights[2]
+    );
+    println!("  predicted -> observed win rate:");
+    for bucket in model.calibration(samples, 10) {
+        println!(
+            "    {:5.1}% -> {:5.1}% ({} positions)",
+            bucket.predicted * 100.0,
+            bucket.observed * 100.0,
+            bucket.samples
         );
     }
+}
 
-    // Print initial state
-    if let Some(ref state) = game.state {
-        if verbose {
-            log::debug!("🏁 Initial state:");
-            log::debug!(
-                "   - Is initial build phase: {}",
-                state.is_initial_build_phase()
-            );
-            log::debug!("   - Current player: {}", state.get_current_color());
-            log::debug!(
-                "   - Players: {:?}",
-                game.players.iter().map(|p| &p.name).collect::<Vec<_>>()
-            );
-            log::debug!("📊 Initial Victory Points:");
-            // Show initial victory points breakdown
-            for color in 0..state.get_num_players() {
-                let vp = state.get_actual_victory_points(color);
-                let settlements = state.get_settlements(color).len();
-                let cities = state.get_cities(color).len();
-                let roads = state.get_roads_by_color()[color as usize];
-                log::debug!(
-                    "   🏆 Player {color}: {vp} VP (settlements: {settlements}, cities: {cities}, roads: {roads})"
-                );
+/// Histogram of chosen action kinds, keyed by (bot label, prompt, action kind).
+/// Seats sharing a bot type are aggregated together.
+#[derive(Default)]
+struct ActionStats {
+    counts: BTreeMap<(String, String, &'static str), u64>,
+}
+
+impl ActionStats {
+    fn record(&mut self, bot_label: &str, prompt: String, action: &Action) {
+        *self
+            .counts
+            .entry((bot_label.to_string(), prompt, action.kind()))
+            .or_insert(0) += 1;
+    }
+
+    fn print_summary(&self) {
+        if self.counts.is_empty() {
+            return;
+        }
+        println!("\n🎲 Action distribution per bot:\n==============================");
+        // Totals per (bot, prompt) so each row can be shown as a share of its prompt
+        let mut prompt_totals: BTreeMap<(&str, &str), u64> = BTreeMap::new();
+        for ((bot, prompt, _), &count) in &self.counts {
+            *prompt_totals.entry((bot, prompt)).or_insert(0) += count;
+        }
+        let mut last_bot = "";
+        let mut last_prompt = "";
+        for ((bot, prompt, kind), &count) in &self.counts {
+            if bot != last_bot {
+                println!("{bot}:");
+                last_bot = bot;
+                last_prompt = "";
             }
+            if prompt != last_prompt {
+                let total = prompt_totals[&(bot.as_str(), prompt.as_str())];
+                println!("  {prompt} ({total} decisions)");
+                last_prompt = prompt;
+            }
+            let total = prompt_totals[&(bot.as_str(), prompt.as_str())];
+            let pct = count as f64 / total as f64 * 100.0;
+            println!("    {kind:<20} {count:>8} ({pct:5.1}%)");
         }
     }
 
-    // Simulate turns with real actions
-    let mut turn_count = 0;
-    const MAX_TURNS: u32 = 10000; // Higher limit for real games - increased for thorough testing
-    let mut last_vp_log = 0;
+    fn write_csv(&self, path: &str) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        writeln!(file, "bot,prompt,action,count")?;
+        for ((bot, prompt, kind), count) in &self.counts {
+            writeln!(file, "{bot},{prompt},{kind},{count}")?;
+        }
+        Ok(())
+    }
+}
 
-    if verbose {
-        log::info!("🎯 Starting simulation with MAX_TURNS = {MAX_TURNS}");
+// Watch mode: the simulated game is registered with a GameService and exposed through the
+// same HTTP/WebSocket routes as the main server, so the frontend can spectate it live.
+
+async fn get_served_game(
+    AxumState(service): AxumState<Arc<WebSocketService>>,
+    Path(game_id): Path<String>,
+) -> Result<Json<Game>, StatusCode> {
+    match service.game_service().get_game(&game_id).await {
+        Ok(game) => Ok(Json(game)),
+        Err(_) => Err(StatusCode::NOT_FOUND),
     }
+}
 
-    while turn_count < MAX_TURNS {
-        // Check for winner
-        if let Some(ref state) = game.state {
-            if let Some(winner) = state.winner() {
-                if verbose {
-                    log::info!("🎉 GAME WON! Player {winner} is the winner!");
-                    log::info!("📊 Final Victory Points:");
-                    // Show final victory points breakdown
-                    for color in 0..state.get_num_players() {
-                        let vp = state.get_actual_victory_points(color);
-                        let settlements = state.get_settlements(color).len();
-                        let cities = state.get_cities(color).len();
-                        let roads = state.get_roads_by_color()[color as usize];
-                        log::info!(
-                            "   🏆 Player {color}: {vp} VP (settlements: {settlements}, cities: {cities}, roads: {roads})"
-                        );
-                    }
-                }
-                let final_vps = collect_final_vps(state);
-                log::info!("✅ Game completed in {turn_count} turns");
-                return SimOutcome::Completed {
-                    winner,
-                    turns: turn_count,
-                    vps: final_vps,
+async fn served_ws_handler(
+    ws: WebSocketUpgrade,
+    Path(game_id): Path<String>,
+    AxumState(service): AxumState<Arc<WebSocketService>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| async move {
+        service
+            .handle_connection(socket, Some(game_id), Default::default())
+            .await
+    })
+}
+
+/// Combined team points are checked against this target instead of the usual 10
+const COMBINED_TEAM_VPS_TO_WIN: u8 = 15;
+
+/// Bot game configuration with optional house rules. Teams are 2v2 with partners in
+/// seats 0/2 and 1/3.
+fn configured_game(
+    num_players: u8,
+    team_victory: Option<TeamVictory>,
+    robber: RobberRules,
+    variants: VariantConfig,
+    rules: RulesVersion,
+) -> GameConfiguration {
+    let mut config = GameConfiguration {
+        num_players,
+        teams: team_victory.map(TeamConfig::two_vs_two),
+        robber,
+        variants,
+        rules,
+        ..Default::default()
+    };
+    if team_victory == Some(TeamVictory::CombinedPoints) {
+        config.vps_to_win = COMBINED_TEAM_VPS_TO_WIN;
+    }
+    config
+}
+
+/// A position the served game's bots should move in, with the channel for their answer
+type BotDecision = (State, Vec<Action>, tokio::sync::oneshot::Sender<Action>);
+
+async fn serve_single_game(
+    lineup: impl FnOnce() -> Vec<SeatedBot> + Send + 'static,
+    num_players: u8,
+    port: u16,
+    delay_ms: u64,
+) {
+    let game_service = Arc::new(GameService::new());
+    let websocket_service = Arc::new(WebSocketService::new(game_service.clone()));
+    let game_id = game_service
+        .register_external_game(simulate_bot_game(num_players))
+        .await;
+
+    let cors = CorsLayer::new()
+        .allow_methods(Any)
+        .allow_headers(Any)
+        .allow_origin(Any);
+    let app = Router::new()
+        .route("/games/{game_id}", get(get_served_game))
+        .route("/ws/games/{game_id}", get(served_ws_handler))
+        .with_state(websocket_service.clone())
+        .layer(cors);
+    let listener = match tokio::net::TcpListener::bind(("0.0.0.0", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Failed to bind port {port}: {e}");
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            log::error!("Watch server stopped: {e}");
+        }
+    });
+
+    println!("👀 Serving simulated game {game_id} on port {port}");
+    println!(
+        "   Open the frontend at /game/{game_id} to watch; the game starts once a client connects."
+    );
+    while !websocket_service.has_active_connections(&game_id).await {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    // Bots search on a blocking thread so their decisions do not stall the watch server.
+    // They are built there too, since not every engine can move between threads.
+    let (decisions, requests) = std::sync::mpsc::channel::<BotDecision>();
+    tokio::task::spawn_blocking(move || {
+        let bots = lineup();
+        for (state, actions, reply) in requests {
+            let action = match bots.get(state.get_current_color() as usize) {
+                Some(bot) => bot.decide(&state, &actions),
+                None => actions[0],
+            };
+            let _ = reply.send(action);
+        }
+    });
+
+    let broadcaster = websocket_service.broadcaster();
+    // Turns ended so far
+    let mut turns: u32 = 0;
+    loop {
+        let Ok(game) = game_service.get_game(&game_id).await else {
+            break;
+        };
+        let Some(state) = game.state else {
+            break;
+        };
+        if let Some(winner) = state.winner() {
+            println!("🎉 Player {winner} won on turn {}", turns + 1);
+            break;
+        }
+        let actions = state.generate_playable_actions();
+        if actions.is_empty() {
+            println!("❌ No actions available at {:?}", state.get_action_prompt());
+            break;
+        }
+        let (reply, decided) = tokio::sync::oneshot::channel();
+        if decisions.send((state, actions, reply)).is_err() {
+            break;
+        }
+        let Ok(action) = decided.await else {
+            log::error!("The bots stopped deciding");
+            break;
+        };
+
+        match game_service.apply_internal_action(&game_id, action).await {
+            Ok(events) => {
+                let result_msg = WsMessage::ActionResult {
+                    success: true,
+                    message: "Action processed".to_string(),
+                    events,
                 };
+                let _ = broadcaster.send((game_id.clone(), result_msg));
+            }
+            Err(e) => {
+                log::error!("Failed to apply {action:?}: {e}");
+                break;
             }
         }
+        if let Ok(updated_game) = game_service.get_game(&game_id).await {
+            let _ = broadcaster.send((game_id.clone(), WsMessage::game_updated(updated_game)));
+        }
 
-        // Get available actions and current player info
-        let (available_actions, current_player) = if let Some(ref state) = game.state {
-            let actions = state.generate_playable_actions();
-            let player = state.get_current_color();
-            (actions, player)
-        } else {
-            if verbose {
-                log::error!("❌ No game state available!");
-            }
-            return SimOutcome::NoState;
-        };
+        if matches!(action, Action::EndTurn { .. }) {
+            turns += 1;
+        }
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+    }
+
+    println!("Game over; still serving the final position. Press Ctrl+C to exit.");
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Hooks `simulate` adds to the shared game loop: action statistics, replay recording,
+/// verbose progress logs and the timeout diagnostics.
+struct SimObserver<'a> {
+    bot_labels: &'a [String],
+    action_stats: &'a mut ActionStats,
+    replay: Option<Replay>,
+    verbose: bool,
+    dump_timeout: bool,
+    decisions: u32,
+    last_vp_log: u32,
+    /// Resource types the bank had run out of when the game stopped
+    bank_zeroes: u8,
+    /// Legal actions in the final position
+    actions_len: usize,
+    /// Sample win model features at the end of every turn after the opening
+    collect_win_samples: bool,
+    /// Features per color of the game so far, labelled once it is won
+    pending_win_samples: Vec<(u8, WinFeatures)>,
+    /// Labelled samples of the finished game; empty when it was not won
+    win_samples: Vec<(WinFeatures, bool)>,
+}
+
+impl<'a> SimObserver<'a> {
+    fn new(
+        bot_labels: &'a [String],
+        action_stats: &'a mut ActionStats,
+        replay: Option<Replay>,
+        verbose: bool,
+        dump_timeout: bool,
+    ) -> Self {
+        Self {
+            bot_labels,
+            action_stats,
+            replay,
+            verbose,
+            dump_timeout,
+            decisions: 0,
+            last_vp_log: 0,
+            bank_zeroes: 0,
+            actions_len: 0,
+            collect_win_samples: false,
+            pending_win_samples: Vec::new(),
+            win_samples: Vec::new(),
+        }
+    }
+}
 
-        // Log turn info every 10 turns or for debugging
-        if verbose && (turn_count % 10 == 0 || turn_count < 5) {
+impl GameObserver for SimObserver<'_> {
+    fn on_decision(&mut self, state: &State, action: Action) {
+        let turn = self.decisions;
+        let color = state.get_current_color();
+        if self.verbose && turn == 0 {
+            log::debug!("🏁 Initial state:");
             log::debug!(
-                "\n🎯 Turn {}: Player {} has {} actions",
-                turn_count + 1,
-                current_player,
-                available_actions.len()
+                "   - Is initial build phase: {}",
+                state.is_initial_build_phase()
             );
+            log::debug!("   - Current player: {color}");
+            log_victory_points(state, log::Level::Debug);
         }
-
-        if available_actions.is_empty() {
-            if verbose {
-                log::error!("❌ No actions available! This is a bug.");
-                if let Some(ref state) = game.state {
-                    log::debug!("🔍 Debug info:");
-                    log::debug!("   - Phase: {:?}", state.get_action_prompt());
-                    log::debug!("   - Is initial: {}", state.is_initial_build_phase());
-                    log::debug!("   - Is discarding: {}", state.is_discarding());
-                    log::debug!("   - Is moving robber: {}", state.is_moving_robber());
-                    log::debug!("   - Rolled this turn: {}", state.current_player_rolled());
-                    let color = state.get_current_color();
-                    let hand = state.get_player_hand(color);
-                    log::debug!(
-                        "   - Current player {} hand: Wd={} Br={} Sh={} Wh={} Or={}",
-                        color,
-                        hand[0],
-                        hand[1],
-                        hand[2],
-                        hand[3],
-                        hand[4]
-                    );
-                    let dev = state.get_player_devhand(color);
-                    log::debug!(
-                        "   - Dev hand: K={} YOP={} Mono={} RB={} VP={}",
-                        dev[0],
-                        dev[1],
-                        dev[2],
-                        dev[3],
-                        dev[4]
-                    );
-                    let bank = state.get_bank_resources();
-                    log::debug!(
-                        "   - Bank: Wd={} Br={} Sh={} Wh={} Or={}",
-                        bank[0],
-                        bank[1],
-                        bank[2],
-                        bank[3],
-                        bank[4]
-                    );
-                    // Also dump candidate possibilities by category for PlayTurn
-                    if matches!(
-                        state.get_action_prompt(),
-                        catan::enums::ActionPrompt::PlayTurn
-                    ) {
-                        let color = state.get_current_color();
-                        let cats = vec![
-                            (
-                                "settlement",
-                                state.settlement_possibilities(color, false).len(),
-                            ),
-                            ("road", state.road_possibilities(color, false).len()),
-                            ("city", state.city_possibilities(color).len()),
-                            (
-                                "buy_dev",
-                                state.buy_development_card_possibilities(color).len(),
-                            ),
-                            ("maritime", state.maritime_trade_possibilities(color).len()),
-                        ];
-                        log::debug!("   - Category sizes: {:?}", cats);
-                    }
-                    state.log_victory_points();
+        if self.verbose && (turn.is_multiple_of(10) || turn < 5) {
+            let actions = state.generate_playable_actions();
+            log::debug!(
+                "\n🎯 Turn {}: Player {color} has {} actions",
+                turn + 1,
+                actions.len()
+            );
+            if turn < 5 {
+                if actions.len() <= 5 {
+                    log::debug!("   Available actions: {actions:?}");
+                } else {
+                    log::debug!("   First 3 actions: {:?}", &actions[..3]);
+                    log::debug!("   ... and {} more", actions.len() - 3);
                 }
             }
-            if let Some(ref state) = game.state {
-                let prompt = format!("{:?}", state.get_action_prompt());
-                let vps = collect_final_vps(state);
-                return SimOutcome::NoActions {
-                    turns: turn_count,
-                    prompt,
-                    vps,
-                };
-            } else {
-                return SimOutcome::NoState;
-            }
+            let hand = state.get_player_hand(color);
+            log::debug!(
+                "   📊 Player {color} status: {} VP (settlements: {}, cities: {}, roads: {})",
+                state.get_actual_victory_points(color),
+                state.get_settlements(color).len(),
+                state.get_cities(color).len(),
+                state.get_roads_by_color()[color as usize]
+            );
+            log::debug!(
+                "   💰 Resources: Wood={}, Brick={}, Sheep={}, Wheat={}, Ore={}",
+                hand[0],
+                hand[1],
+                hand[2],
+                hand[3],
+                hand[4]
+            );
+            log::debug!("🤖 Player {color} action: {action:?}");
         }
 
-        // Show first few available actions for debugging (only early turns)
-        if verbose && turn_count < 5 {
-            if available_actions.len() <= 5 {
-                log::debug!("   Available actions: {available_actions:?}");
-            } else {
-                log::debug!("   First 3 actions: {:?}", &available_actions[..3]);
-                log::debug!("   ... and {} more", available_actions.len() - 3);
-            }
+        if let Some(label) = self.bot_labels.get(color as usize) {
+            let prompt = format!("{:?}", state.get_action_prompt());
+            self.action_stats.record(label, prompt, &action);
         }
+        self.decisions += 1;
+    }
 
-        // Show current player's detailed status for building/VP analysis
-        if verbose && (turn_count % 10 == 0 || turn_count < 5) {
-            if let Some(ref state) = game.state {
-                let vp = state.get_actual_victory_points(current_player);
-                let settlements = state.get_settlements(current_player).len();
-                let cities = state.get_cities(current_player).len();
-                let roads = state.get_roads_by_color()[current_player as usize];
-
-                // Log player's resources
-                let hand = state.get_player_hand(current_player);
-                log::debug!(
-                    "   📊 Player {current_player} status: {vp} VP (settlements: {settlements}, cities: {cities}, roads: {roads})"
-                );
-                log::debug!(
-                    "   💰 Resources: Wood={}, Brick={}, Sheep={}, Wheat={}, Ore={}",
-                    hand[0],
-                    hand[1],
-                    hand[2],
-                    hand[3],
-                    hand[4]
-                );
+    fn on_action(&mut self, state: &State, action: Action) {
+        if let Some(replay) = self.replay.as_mut() {
+            replay.push(action, state);
+        }
+        if self.collect_win_samples
+            && matches!(action, Action::EndTurn { .. })
+            && !state.is_initial_build_phase()
+        {
+            for color in 0..state.get_num_players() {
+                self.pending_win_samples
+                    .push((color, win_features(state, color)));
             }
         }
+        if !self.verbose {
+            return;
+        }
 
-        // Choose action via configured bot for the current player
-        let bot_idx = current_player as usize;
-        let action = if bot_idx < bots.len() {
-            bots[bot_idx].decide(game.state.as_ref().unwrap(), &available_actions)
-        } else {
-            // Fallback: first action
-            available_actions[0]
-        };
-
-        if verbose && (turn_count % 10 == 0 || turn_count < 5) {
-            log::debug!("🤖 Player {current_player} action: {action:?}");
+        // Log victory points every 20 turns or when something interesting happens
+        let turn = self.decisions.saturating_sub(1);
+        let interesting = matches!(
+            action,
+            Action::BuildSettlement { .. } | Action::BuildCity { .. }
+        );
+        if (turn.is_multiple_of(20) || interesting) && turn != self.last_vp_log {
+            log::info!("📊 Victory Points Status (Turn {}):", turn + 1);
+            log_victory_points(state, log::Level::Info);
+            self.last_vp_log = turn;
         }
 
-        // Apply the action using real game logic
-        if let Some(ref mut state) = game.state {
-            state.apply_action(action);
+        // Small delay for readability (only for early turns)
+        if self.decisions < 20 {
+            std::thread::sleep(Duration::from_millis(50));
         }
+    }
 
-        // Log victory points every 20 turns or when something interesting happens
-        let should_log_vp = verbose
-            && (turn_count % 20 == 0
-                || matches!(
-                    action,
-                    Action::BuildSettlement { .. } | Action::BuildCity { .. }
-                ));
-
-        if should_log_vp && turn_count != last_vp_log {
-            log::info!("📊 Victory Points Status (Turn {}):", turn_count + 1);
-            if let Some(ref state) = game.state {
-                // Custom victory points logging that actually shows the values
-                for color in 0..state.get_num_players() {
-                    let vp = state.get_actual_victory_points(color);
-                    let settlements = state.get_settlements(color).len();
-                    let cities = state.get_cities(color).len();
-                    let roads = state.get_roads_by_color()[color as usize];
-                    log::info!(
-                        "   🏆 Player {color}: {vp} VP (settlements: {settlements}, cities: {cities}, roads: {roads})"
-                    );
+    fn on_finish(&mut self, state: &State, record: &GameRecord) {
+        if let Some(replay) = self.replay.as_mut() {
+            replay.finish(state);
+        }
+        if let Some(winner) = record.winner {
+            self.win_samples = self
+                .pending_win_samples
+                .drain(..)
+                .map(|(color, features)| (features, state.are_teammates(winner, color)))
+                .collect();
+        }
+        let turns = record.actions.len();
+        match &record.termination {
+            Termination::Victory => {
+                if self.verbose {
+                    if let Some(winner) = record.winner {
+                        log::info!("🎉 GAME WON! Player {winner} is the winner!");
+                    }
+                    log::info!("📊 Final Victory Points:");
+                    log_victory_points(state, log::Level::Info);
+                }
+                log::info!("✅ Game completed in {turns} turns");
+            }
+            Termination::NoActions { .. } => {
+                if self.verbose {
+                    log::error!("❌ No actions available! This is a bug.");
+                    log_stuck_position(state);
+                }
+            }
+            Termination::ActionLimit => {
+                if self.verbose {
+                    log::info!("⏰ Simulation ended after {turns} turns (max reached)");
+                    log::info!("📊 Final Victory Points:");
+                    log_victory_points(state, log::Level::Info);
+                }
+                let bank = state.get_bank_resources();
+                self.bank_zeroes = bank.iter().filter(|&&c| c == 0).count() as u8;
+                self.actions_len = state.generate_playable_actions().len();
+                if self.dump_timeout {
+                    dump_timeout_state(state, turns, self.bank_zeroes, self.actions_len);
                 }
             }
-            last_vp_log = turn_count;
         }
+    }
+}
 
-        turn_count += 1;
-
-        // Small delay for readability (only for early turns and verbose mode)
-        if verbose && turn_count < 20 {
-            std::thread::sleep(std::time::Duration::from_millis(50));
-        }
+fn log_victory_points(state: &State, level: log::Level) {
+    for color in 0..state.get_num_players() {
+        let vp = state.get_actual_victory_points(color);
+        let settlements = state.get_settlements(color).len();
+        let cities = state.get_cities(color).len();
+        let roads = state.get_roads_by_color()[color as usize];
+        log::log!(
+            level,
+            "   🏆 Player {color}: {vp} VP (settlements: {settlements}, cities: {cities}, roads: {roads})"
+        );
     }
+}
 
-    if turn_count >= MAX_TURNS && verbose {
-        log::info!("⏰ Simulation ended after {MAX_TURNS} turns (max reached)");
-        if let Some(ref state) = game.state {
-            log::info!("📊 Final Victory Points:");
-            // Show final victory points breakdown
-            for color in 0..state.get_num_players() {
-                let vp = state.get_actual_victory_points(color);
-                let settlements = state.get_settlements(color).len();
-                let cities = state.get_cities(color).len();
-                let roads = state.get_roads_by_color()[color as usize];
-                log::info!(
-                        "   🏆 Player {color}: {vp} VP (settlements: {settlements}, cities: {cities}, roads: {roads})"
-                    );
-            }
-        }
-    }
-
-    // If we reach here, no winner within MAX_TURNS
-    if let Some(ref state) = game.state {
-        let vps = collect_final_vps(state);
-        // Quick diagnostics at timeout
-        let bank = state.get_bank_resources();
-        let bank_zeroes = bank.iter().filter(|&&c| c == 0).count() as u8;
-        let actions_len = state.generate_playable_actions().len();
-        if dump_timeout {
-            println!("\n⏰ Timeout dump:");
-            println!("  - Turn: {turn_count}");
-            println!("  - Current player: {}", state.get_current_color());
-            println!("  - Action prompt: {:?}", state.get_action_prompt());
-            println!("  - Rolled this turn: {}", state.current_player_rolled());
-            println!(
-                "  - Bank: wood={} brick={} sheep={} wheat={} ore={} (zero-types={})",
-                bank[0], bank[1], bank[2], bank[3], bank[4], bank_zeroes
-            );
-            for color in 0..state.get_num_players() {
-                let hand = state.get_player_hand(color);
-                let dev = state.get_player_devhand(color);
-                println!(
-                    "  - P{}: VP={} | hand [w={},b={},s={},w={},o={}] | dev [K={},YOP={},M={},RB={},VP={}]",
-                    color,
-                    state.get_actual_victory_points(color),
-                    hand[0], hand[1], hand[2], hand[3], hand[4],
-                    dev[0], dev[1], dev[2], dev[3], dev[4]
-                );
-            }
-            let color = state.get_current_color();
-            println!(
-                "  - Legal actions now: {} (settle={}, road={}, city={}, buy_dev={}, maritime={})",
-                actions_len,
+fn log_stuck_position(state: &State) {
+    log::debug!("🔍 Debug info:");
+    log::debug!("   - Phase: {:?}", state.get_action_prompt());
+    log::debug!("   - Is initial: {}", state.is_initial_build_phase());
+    log::debug!("   - Is discarding: {}", state.is_discarding());
+    log::debug!("   - Is moving robber: {}", state.is_moving_robber());
+    log::debug!("   - Rolled this turn: {}", state.current_player_rolled());
+    let color = state.get_current_color();
+    let hand = state.get_player_hand(color);
+    log::debug!(
+        "   - Current player {} hand: Wd={} Br={} Sh={} Wh={} Or={}",
+        color,
+        hand[0],
+        hand[1],
+        hand[2],
+        hand[3],
+        hand[4]
+    );
+    let dev = state.get_player_devhand(color);
+    log::debug!(
+        "   - Dev hand: K={} YOP={} Mono={} RB={} VP={}",
+        dev[0],
+        dev[1],
+        dev[2],
+        dev[3],
+        dev[4]
+    );
+    let bank = state.get_bank_resources();
+    log::debug!(
+        "   - Bank: Wd={} Br={} Sh={} Wh={} Or={}",
+        bank[0],
+        bank[1],
+        bank[2],
+        bank[3],
+        bank[4]
+    );
+    // Also dump candidate possibilities by category for PlayTurn
+    if matches!(
+        state.get_action_prompt(),
+        catan::enums::ActionPrompt::PlayTurn
+    ) {
+        let cats = vec![
+            (
+                "settlement",
                 state.settlement_possibilities(color, false).len(),
-                state.road_possibilities(color, false).len(),
-                state.city_possibilities(color).len(),
+            ),
+            ("road", state.road_possibilities(color, false).len()),
+            ("city", state.city_possibilities(color).len()),
+            (
+                "buy_dev",
                 state.buy_development_card_possibilities(color).len(),
-                state.maritime_trade_possibilities(color).len()
-            );
-            let acts = state.generate_playable_actions();
-            let preview = acts.iter().take(5).collect::<Vec<_>>();
-            println!("  - Actions (up to 5): {preview:?}");
-        }
-        return SimOutcome::Timeout {
-            turns: turn_count,
-            vps,
-            bank_zeroes,
-            actions_len,
-        };
+            ),
+            ("maritime", state.maritime_trade_possibilities(color).len()),
+        ];
+        log::debug!("   - Category sizes: {:?}", cats);
     }
-    SimOutcome::NoState
+    state.log_victory_points();
+}
+
+fn dump_timeout_state(state: &State, turns: usize, bank_zeroes: u8, actions_len: usize) {
+    let bank = state.get_bank_resources();
+    println!("\n⏰ Timeout dump:");
+    println!("  - Turn: {turns}");
+    println!("  - Current player: {}", state.get_current_color());
+    println!("  - Action prompt: {:?}", state.get_action_prompt());
+    println!("  - Rolled this turn: {}", state.current_player_rolled());
+    println!(
+        "  - Bank: wood={} brick={} sheep={} wheat={} ore={} (zero-types={})",
+        bank[0], bank[1], bank[2], bank[3], bank[4], bank_zeroes
+    );
+    for color in 0..state.get_num_players() {
+        let hand = state.get_player_hand(color);
+        let dev = state.get_player_devhand(color);
+        println!(
+            "  - P{}: VP={} | hand [w={},b={},s={},w={},o={}] | dev [K={},YOP={},M={},RB={},VP={}]",
+            color,
+            state.get_actual_victory_points(color),
+            hand[0],
+            hand[1],
+            hand[2],
+            hand[3],
+            hand[4],
+            dev[0],
+            dev[1],
+            dev[2],
+            dev[3],
+            dev[4]
+        );
+    }
+    let color = state.get_current_color();
+    println!(
+        "  - Legal actions now: {} (settle={}, road={}, city={}, buy_dev={}, maritime={})",
+        actions_len,
+        state.settlement_possibilities(color, false).len(),
+        state.road_possibilities(color, false).len(),
+        state.city_possibilities(color).len(),
+        state.buy_development_card_possibilities(color).len(),
+        state.maritime_trade_possibilities(color).len()
+    );
+    let acts = state.generate_playable_actions();
+    let preview = acts.iter().take(5).collect::<Vec<_>>();
+    println!("  - Actions (up to 5): {preview:?}");
 }
 
 // Helper for building the bot lineup from config string
 
-fn build_bots_from_config(config: &str) -> (Vec<Box<dyn BotPlayer>>, Vec<String>) {
-    let colors = ["red", "blue", "white", "orange"]; // cosmetic only
-    let mut bots: Vec<Box<dyn BotPlayer>> = Vec::new();
+/// Parse `--profile [SEAT=]PATH` arguments into one optional profile per seat. A bare
+/// PATH applies to every seat without a seat-specific profile.
+fn load_seat_profiles(
+    profile_args: &[String],
+    num_players: usize,
+) -> Result<Vec<Option<EvalProfile>>, String> {
+    let mut shared: Option<EvalProfile> = None;
+    let mut seats: Vec<Option<EvalProfile>> = vec![None; num_players];
+    for arg in profile_args {
+        let (seat, path) = match arg.split_once('=') {
+            Some((seat, path)) => {
+                let seat: usize = seat
+                    .parse()
+                    .map_err(|_| format!("Bad --profile seat in '{arg}'"))?;
+                if seat >= num_players {
+                    return Err(format!("--profile seat {seat} is out of range"));
+                }
+                (Some(seat), path)
+            }
+            None => (None, arg.as_str()),
+        };
+        let profile = EvalProfile::load(std::path::Path::new(path)).map_err(|e| e.to_string())?;
+        match seat {
+            Some(seat) => seats[seat] = Some(profile),
+            None => shared = Some(profile),
+        }
+    }
+    Ok(seats
+        .into_iter()
+        .map(|seat| seat.or_else(|| shared.clone()))
+        .collect())
+}
+
+fn build_bots_from_config(
+    config: &str,
+    profiles: &[Option<EvalProfile>],
+) -> (Vec<SeatedBot>, Vec<String>) {
+    let mut bots: Vec<SeatedBot> = Vec::new();
     let mut labels: Vec<String> = Vec::new();
 
     for (i, c) in config.chars().enumerate() {
-        match c {
-            'V' | 'v' => {
-                bots.push(Box::new(ValueFunctionPlayer::new(
-                    format!("player_{i}"),
-                    format!("Value {i}"),
-                    colors[i % colors.len()].to_string(),
-                    i as u8,
-                )));
-                labels.push("Value".to_string());
-            }
-            'Z' | 'z' => {
-                bots.push(Box::new(AlphaZeroPlayer::new(
-                    format!("player_{i}"),
-                    format!("AlphaZero {i}"),
-                    colors[i % colors.len()].to_string(),
-                )));
-                labels.push("AlphaZero".to_string());
-            }
-            'G' | 'g' => {
-                bots.push(Box::new(GreedyPlayer::new(
-                    format!("player_{i}"),
-                    format!("Greedy {i}"),
-                    colors[i % colors.len()].to_string(),
-                )));
-                labels.push("Greedy".to_string());
-            }
-            'W' | 'w' => {
-                bots.push(Box::new(WeightedRandomPlayer::new(
-                    format!("player_{i}"),
-                    format!("Weighted {i}"),
-                    colors[i % colors.len()].to_string(),
-                )));
-                labels.push("WeightedRandom".to_string());
-            }
-            'A' | 'a' => {
-                bots.push(Box::new(AlphaBetaPlayer::new(
-                    format!("player_{i}"),
-                    format!("AlphaBeta {i}"),
-                    colors[i % colors.len()].to_string(),
-                )));
-                labels.push("AlphaBeta".to_string());
+        let profile = profiles.get(i).and_then(|p| p.as_ref());
+        let (engine, label): (Box<dyn BotPlayer>, &str) = match (c, profile) {
+            ('V' | 'v', Some(profile)) => (Box::new(profile.value_player()), "ProfiledValue"),
+            ('V' | 'v', None) => (Box::new(ValueFunctionPlayer::new()), "Value"),
+            ('Z' | 'z', _) => (Box::new(AlphaZeroPlayer::new()), "AlphaZero"),
+            ('G' | 'g', Some(profile)) => (Box::new(profile.greedy_player()), "ProfiledGreedy"),
+            ('G' | 'g', None) => (Box::new(GreedyPlayer::new()), "Greedy"),
+            ('W' | 'w', _) => (Box::new(WeightedRandomPlayer::new()), "WeightedRandom"),
+            ('A' | 'a', Some(profile)) => {
+                (Box::new(profile.alpha_beta_player()), "ProfiledAlphaBeta")
             }
-            _ => {
-                bots.push(Box::new(RandomPlayer::new(
-                    format!("player_{i}"),
-                    format!("Random {i}"),
-                    colors[i % colors.len()].to_string(),
-                )));
-                labels.push("Random".to_string());
+            ('A' | 'a', None) => (Box::new(AlphaBetaPlayer::new()), "AlphaBeta"),
+            ('S' | 's', profile) => {
+                let mut engine = profile
+                    .map(EvalProfile::alpha_beta_player)
+                    .unwrap_or_default();
+                engine.set_spiteful_robber(true);
+                (Box::new(engine), "SpitefulAlphaBeta")
             }
-        }
+            _ => (Box::new(RandomPlayer::new()), "Random"),
+        };
+        bots.push(SeatedBot::new(i as u8, engine));
+        labels.push(label.to_string());
     }
 
     (bots, labels)
 }
-
-fn collect_final_vps(state: &catan::state::State) -> Vec<u8> {
-    let num_players = state.get_num_players();
-    (0..num_players)
-        .map(|c| state.get_actual_victory_points(c))
-        .collect()
-}
@@ -0,0 +1,74 @@
+use catan::enums::DiceMode;
+use catan::replay::Replay;
+use catan::RULES_REVISION;
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Re-simulates every recorded replay (*.json) in a directory and checks that the
+/// final state hash matches the recorded one. Run it across engine refactors to make
+/// sure rules behavior is preserved.
+///
+/// Usage: verify [DIR]   (default: replays)
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let dir = env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("replays"));
+
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect(),
+        Err(e) => {
+            eprintln!("Failed to read replay directory {}: {e}", dir.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    paths.sort();
+
+    if paths.is_empty() {
+        println!("No replays found in {}", dir.display());
+        return ExitCode::SUCCESS;
+    }
+
+    let mut failures = 0;
+    for path in &paths {
+        let result = Replay::load(path).and_then(|replay| replay.verify().map(|_| replay));
+        match result {
+            Ok(replay) => {
+                let mut notes = Vec::new();
+                if replay.dice_mode() != DiceMode::Standard {
+                    notes.push(format!("{:?} dice", replay.dice_mode()));
+                }
+                if replay.rules_revision != RULES_REVISION {
+                    notes.push(format!("rules revision {}", replay.rules_revision));
+                }
+                if notes.is_empty() {
+                    println!("✅ {}", path.display());
+                } else {
+                    println!("✅ {} ({})", path.display(), notes.join(", "));
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                println!("❌ {}: {e}", path.display());
+            }
+        }
+    }
+
+    println!(
+        "\nVerified {} replays: {} passed, {} failed",
+        paths.len(),
+        paths.len() - failures,
+        failures
+    );
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,375 @@
+use catan::actions::PlayerAction;
+use catan::errors::ErrorCode;
+use catan::game::Game;
+use catan::websocket::WsMessage;
+use catan::RULES_REVISION;
+use futures::{SinkExt, StreamExt};
+use std::env;
+use std::process::ExitCode;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+/// WebSocket protocol conformance run against a live server. Creates a human-vs-bot
+/// game over HTTP, then connects a player and a spectator and checks the server's
+/// answers to: the connection handshake, an illegal action, a legal action, a state
+/// request, a reconnect and a connection to an unknown game. It doubles as reference
+/// client code for the message flow the frontend implements.
+///
+/// Usage: wsclient [--server URL] [--timeout-ms N]   (default: http://127.0.0.1:8000, 10000)
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().collect();
+    let mut server = "http://127.0.0.1:8000".to_string();
+    let mut timeout_ms = 10_000;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--server" if i + 1 < args.len() => {
+                server = args[i + 1].trim_end_matches('/').to_string();
+                i += 1;
+            }
+            "--timeout-ms" if i + 1 < args.len() => {
+                timeout_ms = args[i + 1].parse().unwrap_or(timeout_ms);
+                i += 1;
+            }
+            other => {
+                eprintln!("Unknown argument: {other}");
+                return ExitCode::FAILURE;
+            }
+        }
+        i += 1;
+    }
+
+    let client = ProtocolClient {
+        server,
+        timeout: Duration::from_millis(timeout_ms),
+    };
+    match client.run().await {
+        Ok(checks) => {
+            println!("\nAll {checks} protocol checks passed");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("❌ {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct ProtocolClient {
+    /// HTTP base URL; the WebSocket URL is derived from it
+    server: String,
+    /// How long to wait for each expected message
+    timeout: Duration,
+}
+
+impl ProtocolClient {
+    /// Run every check in order, stopping at the first failure. Returns the number of
+    /// checks passed.
+    async fn run(&self) -> Result<usize, String> {
+        let mut checks = 0;
+        let mut pass = |name: &str| {
+            checks += 1;
+            println!("✅ {name}");
+        };
+
+        let game = self.create_game().await?;
+        pass(&format!("created game {} over HTTP", game.id));
+
+        let mut player = self.connect(&game.id, "player").await?;
+        let game = player.handshake(&game.id).await?;
+        pass("player receives greeting and game_state");
+
+        let mut spectator = self.connect(&game.id, "spectator").await?;
+        spectator.handshake(&game.id).await?;
+        pass("spectator receives greeting and game_state");
+
+        // Bots move on their own once someone is connected; wait for the human's turn
+        let game = if is_human_turn(&game) {
+            game
+        } else {
+            player
+                .expect(
+                    "game_updated on the human's turn",
+                    |message| match message {
+                        WsMessage::GameUpdated { game, .. } if is_human_turn(&game) => Some(game),
+                        _ => None,
+                    },
+                )
+                .await?
+        };
+        pass("bots play until the human is to move");
+
+        // Rejected actions do not use up a sequence number
+        let seq = game.last_action_seq + 1;
+        let illegal = illegal_action(&game);
+        player
+            .send(&WsMessage::PlayerAction {
+                action: illegal.clone(),
+                seq,
+                version: game.version,
+            })
+            .await?;
+        player
+            .expect(
+                "ILLEGAL_ACTION error for an illegal action",
+                |message| match message {
+                    WsMessage::Error {
+                        code: ErrorCode::IllegalAction,
+                        message,
+                        ..
+                    } => Some(message),
+                    _ => None,
+                },
+            )
+            .await?;
+        pass(&format!("illegal {illegal:?} is rejected with an error"));
+
+        let legal = game
+            .current_playable_actions
+            .first()
+            .cloned()
+            .ok_or("no playable action on the human's turn")?;
+        player
+            .send(&WsMessage::PlayerAction {
+                action: legal.clone(),
+                seq,
+                // Any version but the current one; the human may be first to move
+                version: game.version + 1,
+            })
+            .await?;
+        player
+            .expect(
+                "stale_action for another version",
+                |message| match message {
+                    WsMessage::StaleAction { version, .. } if version == game.version => Some(()),
+                    _ => None,
+                },
+            )
+            .await?;
+        pass("an action made against another game version is refused");
+
+        let actions_before = game.actions.len();
+        player
+            .send(&WsMessage::PlayerAction {
+                action: legal.clone(),
+                seq,
+                version: game.version,
+            })
+            .await?;
+        player
+            .expect("successful action_result", |message| match message {
+                WsMessage::ActionResult { success: true, .. } => Some(()),
+                _ => None,
+            })
+            .await?;
+        let (updated, buildable) = player
+            .expect("game_updated after the action", |message| match message {
+                WsMessage::GameUpdated { game, buildable } => Some((game, buildable)),
+                _ => None,
+            })
+            .await?;
+        if updated.actions.len() <= actions_before {
+            return Err(format!(
+                "game log did not grow after {legal:?} ({} actions)",
+                updated.actions.len()
+            ));
+        }
+        if updated.last_action_seq != seq {
+            return Err(format!(
+                "last_action_seq is {} after accepting #{seq}",
+                updated.last_action_seq
+            ));
+        }
+        pass(&format!("legal {legal:?} is applied and broadcast"));
+        match buildable {
+            Some(masks) if Some(&masks.color) == updated.current_color.as_ref() => {}
+            other => return Err(format!("game_updated marks {other:?} as buildable")),
+        }
+        pass("game_updated marks the buildable spots of the player to move");
+
+        spectator
+            .expect("the player's action_result", |message| match message {
+                WsMessage::ActionResult { success: true, .. } => Some(()),
+                _ => None,
+            })
+            .await?;
+        pass("spectator sees the player's action");
+
+        player.send(&WsMessage::GetGameState).await?;
+        player
+            .expect("game_state on request", |message| match message {
+                WsMessage::GameState { game: requested } if requested.id == game.id => Some(()),
+                _ => None,
+            })
+            .await?;
+        pass("get_game_state answers with game_state");
+
+        player.close().await?;
+        let mut player = self.connect(&game.id, "reconnected player").await?;
+        let resumed = player.handshake(&game.id).await?;
+        if resumed.actions.len() <= actions_before {
+            return Err("reconnected player got a stale game_state".to_string());
+        }
+        pass("reconnect resumes the game where it was");
+
+        player.close().await?;
+        spectator.close().await?;
+
+        let mut stranger = self.connect("no_such_game", "stranger").await?;
+        stranger
+            .expect("greeting", |message| match message {
+                WsMessage::Greeting { .. } => Some(()),
+                _ => None,
+            })
+            .await?;
+        stranger
+            .expect(
+                "GAME_NOT_FOUND error for an unknown game",
+                |message| match message {
+                    WsMessage::Error {
+                        code: ErrorCode::GameNotFound,
+                        message,
+                        ..
+                    } => Some(message),
+                    _ => None,
+                },
+            )
+            .await?;
+        pass("unknown game id is answered with an error");
+
+        Ok(checks)
+    }
+
+    async fn create_game(&self) -> Result<Game, String> {
+        let url = format!("{}/games", self.server);
+        let body = serde_json::json!({ "mode": "HUMAN_VS_RANDOM", "num_players": 2 });
+        let response = reqwest::Client::new()
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("POST {url}: {e}"))?;
+        response
+            .json::<Game>()
+            .await
+            .map_err(|e| format!("POST {url}: {e}"))
+    }
+
+    async fn connect(&self, game_id: &str, name: &'static str) -> Result<Connection, String> {
+        let base = self
+            .server
+            .replacen("https://", "wss://", 1)
+            .replacen("http://", "ws://", 1);
+        let url = format!("{base}/ws/games/{game_id}");
+        let (socket, _) = connect_async(&url)
+            .await
+            .map_err(|e| format!("{name}: connecting to {url}: {e}"))?;
+        Ok(Connection {
+            name,
+            socket,
+            timeout: self.timeout,
+        })
+    }
+}
+
+/// One WebSocket connection to a game channel
+struct Connection {
+    name: &'static str,
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    timeout: Duration,
+}
+
+impl Connection {
+    async fn send(&mut self, message: &WsMessage) -> Result<(), String> {
+        let text = serde_json::to_string(message).map_err(|e| e.to_string())?;
+        self.socket
+            .send(Message::text(text))
+            .await
+            .map_err(|e| format!("{}: send failed: {e}", self.name))
+    }
+
+    async fn close(mut self) -> Result<(), String> {
+        self.socket
+            .close(None)
+            .await
+            .map_err(|e| format!("{}: close failed: {e}", self.name))
+    }
+
+    /// Read messages until `pick` accepts one, skipping the others (bot moves, state
+    /// updates). Fails on timeout or when the server closes the connection.
+    async fn expect<T>(
+        &mut self,
+        what: &str,
+        mut pick: impl FnMut(WsMessage) -> Option<T>,
+    ) -> Result<T, String> {
+        let name = self.name;
+        let wait = async {
+            while let Some(frame) = self.socket.next().await {
+                let text = match frame.map_err(|e| format!("{name}: read failed: {e}"))? {
+                    Message::Text(text) => text,
+                    Message::Close(_) => break,
+                    _ => continue,
+                };
+                let message: WsMessage = serde_json::from_str(text.as_str())
+                    .map_err(|e| format!("{name}: unparseable message {text}: {e}"))?;
+                if let Some(found) = pick(message) {
+                    return Ok(found);
+                }
+            }
+            Err(format!(
+                "{name}: connection closed while waiting for {what}"
+            ))
+        };
+        tokio::time::timeout(self.timeout, wait)
+            .await
+            .map_err(|_| format!("{name}: timed out waiting for {what}"))?
+    }
+
+    /// The greeting (checked against this build's rules revision) and the game's state
+    async fn handshake(&mut self, game_id: &str) -> Result<Game, String> {
+        let revision = self
+            .expect("greeting", |message| match message {
+                WsMessage::Greeting { rules_revision, .. } => Some(rules_revision),
+                _ => None,
+            })
+            .await?;
+        if revision != RULES_REVISION {
+            return Err(format!(
+                "{}: server plays rules revision {revision}, client expects {RULES_REVISION}",
+                self.name
+            ));
+        }
+        let game = self
+            .expect("initial game_state", |message| match message {
+                WsMessage::GameState { game } => Some(game),
+                _ => None,
+            })
+            .await?;
+        if game.id != game_id {
+            return Err(format!("{}: game_state for {} instead", self.name, game.id));
+        }
+        Ok(game)
+    }
+}
+
+fn is_human_turn(game: &Game) -> bool {
+    game.current_color
+        .as_ref()
+        .is_some_and(|color| !game.bot_colors.contains(color))
+        && !game.current_playable_actions.is_empty()
+}
+
+/// A city on a node where the current player cannot build one
+fn illegal_action(game: &Game) -> PlayerAction {
+    (0..54)
+        .map(|node_id| PlayerAction::BuildCity { node_id })
+        .find(|action| !game.current_playable_actions.contains(action))
+        .unwrap_or(PlayerAction::BuildCity { node_id: 0 })
+}
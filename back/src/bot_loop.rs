@@ -0,0 +1,164 @@
+// One step of a game's bot loop: play the bot to move (or auto-pass a seat that asked
+// for it) and broadcast the result. The WebSocket service runs these steps in a
+// background task per game; with `BotScheduling::Stepped` nothing runs on its own and
+// tests call `WebSocketService::step_bots`, so "bot move, broadcast, human action"
+// sequences play out in a fixed order, with the pauses taken on the service's clock.
+
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::actions::GameId;
+use crate::application::GameService;
+use crate::websocket::WsMessage;
+
+/// Pause after a failed bot turn before the loop tries again
+pub const RETRY_DELAY: Duration = Duration::from_millis(2000);
+
+/// How bot turns are driven
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BotScheduling {
+    /// A background task per game plays bot turns as they come up
+    #[default]
+    Background,
+    /// Bots only move when `WebSocketService::step_bots` is called
+    Stepped,
+}
+
+/// What a step did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotStep {
+    /// A bot moved and the new state was broadcast
+    Moved,
+    /// The seat to move had auto-pass on; its turn was ended and broadcast
+    AutoPassed,
+    /// Speed-round play, where rolls and build windows drive the game instead
+    SpeedRound,
+    /// Nothing for bots to do until a human acts
+    Idle,
+    /// The bot turn failed; the step waited `RETRY_DELAY`
+    Failed,
+}
+
+/// Play one bot step of `game_id`, broadcasting what happened
+pub async fn step(
+    game_service: &GameService,
+    broadcaster: &broadcast::Sender<(GameId, WsMessage)>,
+    game_id: &str,
+) -> BotStep {
+    let clock = game_service.clock();
+    let broadcast_game = || async {
+        if let Ok(game) = game_service.get_game(game_id).await {
+            if let Err(e) = broadcaster.send((game_id.to_string(), WsMessage::game_updated(game))) {
+                log::error!("Failed to broadcast game update for game {game_id}: {e:?}");
+            }
+        }
+    };
+
+    match game_service.process_bot_turn(game_id).await {
+        Ok(Some(_events)) => {
+            let thinking_msg = WsMessage::BotThinking {
+                player_id: "current_bot".to_string(), // Simplified
+            };
+            let _ = broadcaster.send((game_id.to_string(), thinking_msg));
+
+            // Pause so bot moves are visible, as long as the game's pace asks for
+            let pace = game_service.pace(game_id).await.unwrap_or_default();
+            clock
+                .sleep(Duration::from_millis(pace.settings().bot_delay_ms))
+                .await;
+            broadcast_game().await;
+            BotStep::Moved
+        }
+        Ok(None) if game_service.in_speed_phase(game_id).await => BotStep::SpeedRound,
+        Ok(None) => {
+            let Some((delay, version)) = game_service.pending_auto_pass(game_id).await else {
+                return BotStep::Idle;
+            };
+            clock.sleep(delay).await;
+            match game_service.auto_pass(game_id, version).await {
+                Ok(Some(events)) => {
+                    let result_msg = WsMessage::ActionResult {
+                        success: true,
+                        message: "Turn passed automatically".to_string(),
+                        events,
+                    };
+                    let _ = broadcaster.send((game_id.to_string(), result_msg));
+                    broadcast_game().await;
+                    BotStep::AutoPassed
+                }
+                _ => BotStep::Idle,
+            }
+        }
+        Err(e) => {
+            log::error!("Bot processing error for game {game_id}: {e}");
+            clock.sleep(RETRY_DELAY).await;
+            BotStep::Failed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::PlayerAction;
+    use crate::application::TableOptions;
+    use crate::clock::FakeClock;
+    use crate::enums::GameConfiguration;
+    use crate::pace::Pace;
+    use crate::websocket::WebSocketService;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_stepped_bots_move_in_order_on_the_fake_clock() {
+        let clock = Arc::new(FakeClock::default());
+        let game_service = Arc::new(GameService::new().with_clock(clock.clone()));
+        let websocket =
+            WebSocketService::new(game_service.clone()).with_scheduling(BotScheduling::Stepped);
+        let mut messages = websocket.broadcaster().subscribe();
+        let game_id = game_service
+            .create_game(
+                "human_random",
+                GameConfiguration::builder().num_players(2),
+                &TableOptions::default(),
+                Pace::Blitz,
+            )
+            .await
+            .unwrap();
+
+        // The human opens, so bots wait for it
+        assert_eq!(websocket.step_bots(&game_id).await, BotStep::Idle);
+        let game = game_service.get_game(&game_id).await.unwrap();
+        let human = game.players[game.current_player_index].id.clone();
+        for _ in 0..2 {
+            let game = game_service.get_game(&game_id).await.unwrap();
+            let action: PlayerAction = game.current_playable_actions[0].clone();
+            game_service
+                .process_action(&game_id, &human, action)
+                .await
+                .unwrap();
+        }
+
+        // The bot places its two settlements and roads, one broadcast pair per move,
+        // then it is the human's turn again
+        let mut moves = 0;
+        while websocket.step_bots(&game_id).await == BotStep::Moved {
+            assert!(matches!(
+                messages.try_recv().unwrap().1,
+                WsMessage::BotThinking { .. }
+            ));
+            assert!(matches!(
+                messages.try_recv().unwrap().1,
+                WsMessage::GameUpdated { .. }
+            ));
+            moves += 1;
+        }
+        assert_eq!(moves, 4);
+        assert!(messages.try_recv().is_err());
+        let blitz_delay = Duration::from_millis(Pace::Blitz.settings().bot_delay_ms);
+        assert_eq!(clock.sleeps(), vec![blitz_delay; 4]);
+        assert_eq!(clock.elapsed(), blitz_delay * 4);
+
+        let game = game_service.get_game(&game_id).await.unwrap();
+        assert_eq!(game.players[game.current_player_index].id, human);
+    }
+}
@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::actions::GameId;
+use crate::application::{GameService, Seat};
+use crate::errors::{CatanError, CatanResult, GameError};
+use crate::game::{Game, GameState};
+
+/// Campaign points awarded to the winner of a game on top of their victory points
+pub const CAMPAIGN_WIN_BONUS: u32 = 2;
+
+/// Cumulative campaign result for one participant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampaignStanding {
+    pub name: String,
+    pub points: u32,
+    pub wins: u32,
+}
+
+/// Outcome of a single finished game within a campaign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignGameResult {
+    pub game_id: GameId,
+    pub winner: Option<String>,
+    /// (name, victory points) in the seat order the game was played in
+    pub victory_points: Vec<(String, u32)>,
+}
+
+/// Standings snapshot sent to clients after each campaign game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignSummary {
+    pub campaign_id: String,
+    pub games_played: u32,
+    pub num_games: u32,
+    /// Sorted by points, best first
+    pub standings: Vec<CampaignStanding>,
+    pub current_game_id: Option<GameId>,
+    pub finished: bool,
+}
+
+/// A series of games where results carry over: campaign points accumulate, and each
+/// game is seated by the previous result so the winner gets the last placement pick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Campaign {
+    pub id: String,
+    pub bot_type: String,
+    pub num_games: u32,
+    pub human_names: Vec<String>,
+    /// Seat order of the current (or next) game
+    pub seat_order: Vec<String>,
+    pub current_game_id: Option<GameId>,
+    pub results: Vec<CampaignGameResult>,
+    pub standings: Vec<CampaignStanding>,
+}
+
+impl Campaign {
+    pub fn new(id: String, bot_type: &str, num_games: u32, seat_order: Vec<String>) -> Self {
+        let human_names = if bot_type.starts_with("human_") {
+            seat_order.iter().take(1).cloned().collect()
+        } else {
+            Vec::new()
+        };
+        let standings = seat_order
+            .iter()
+            .map(|name| CampaignStanding {
+                name: name.clone(),
+                points: 0,
+                wins: 0,
+            })
+            .collect();
+        Self {
+            id,
+            bot_type: bot_type.to_string(),
+            num_games,
+            human_names,
+            seat_order,
+            current_game_id: None,
+            results: Vec::new(),
+            standings,
+        }
+    }
+
+    pub fn games_played(&self) -> u32 {
+        self.results.len() as u32
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.games_played() >= self.num_games
+    }
+
+    fn seats(&self) -> Vec<Seat> {
+        self.seat_order
+            .iter()
+            .map(|name| Seat {
+                name: name.clone(),
+                is_bot: !self.human_names.contains(name),
+            })
+            .collect()
+    }
+
+    /// Fold a finished game into the standings and reseat for the next game
+    pub fn record_result(&mut self, result: CampaignGameResult) {
+        for (name, vps) in &result.victory_points {
+            if let Some(standing) = self.standings.iter_mut().find(|s| &s.name == name) {
+                standing.points += vps;
+                if result.winner.as_ref() == Some(name) {
+                    standing.points += CAMPAIGN_WIN_BONUS;
+                    standing.wins += 1;
+                }
+            }
+        }
+        self.seat_order = next_seat_order(&result);
+        self.results.push(result);
+    }
+
+    pub fn summary(&self) -> CampaignSummary {
+        let mut standings = self.standings.clone();
+        standings.sort_by(|a, b| b.points.cmp(&a.points).then(b.wins.cmp(&a.wins)));
+        CampaignSummary {
+            campaign_id: self.id.clone(),
+            games_played: self.games_played(),
+            num_games: self.num_games,
+            standings,
+            current_game_id: self.current_game_id.clone(),
+            finished: self.is_finished(),
+        }
+    }
+}
+
+/// Seat order for the following game: fewest victory points places first, the winner last.
+/// Ties keep the previous relative order.
+pub fn next_seat_order(result: &CampaignGameResult) -> Vec<String> {
+    let mut ranked = result.victory_points.clone();
+    ranked.sort_by_key(|(name, vps)| (result.winner.as_ref() == Some(name), *vps));
+    ranked.into_iter().map(|(name, _)| name).collect()
+}
+
+/// Read the outcome of a finished game
+fn game_result(game: &Game) -> CatanResult<CampaignGameResult> {
+    let winner = match &game.game_state {
+        GameState::Finished { winner } => Some(winner.clone()),
+        _ => {
+            return Err(GameError::InvalidStateTransition {
+                details: format!("game {} is not finished yet", game.id),
+            }
+            .into())
+        }
+    };
+    let victory_points = game
+        .players
+        .iter()
+        .enumerate()
+        .map(|(color, player)| {
+            let vps = game
+                .state
+                .as_ref()
+                .map(|s| s.get_actual_victory_points(color as u8) as u32)
+                .unwrap_or(player.victory_points);
+            (player.name.clone(), vps)
+        })
+        .collect();
+    Ok(CampaignGameResult {
+        game_id: game.id.clone(),
+        winner,
+        victory_points,
+    })
+}
+
+/// Manages campaigns on top of the regular GameService; every campaign game is an
+/// ordinary game that clients play and watch through the usual endpoints.
+#[derive(Clone)]
+pub struct CampaignService {
+    game_service: Arc<GameService>,
+    campaigns: Arc<RwLock<HashMap<String, Campaign>>>,
+}
+
+impl CampaignService {
+    pub fn new(game_service: Arc<GameService>) -> Self {
+        Self {
+            game_service,
+            campaigns: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Create a campaign and start its first game
+    pub async fn create_campaign(
+        &self,
+        num_players: u8,
+        bot_type: &str,
+        num_games: u32,
+    ) -> CatanResult<CampaignSummary> {
+        if num_games == 0 {
+            return Err(CatanError::Validation(
+                "A campaign needs at least one game".to_string(),
+            ));
+        }
+        let seat_order = (0..num_players)
+            .map(|i| {
+                if i == 0 && bot_type.starts_with("human_") {
+                    "Human".to_string()
+                } else {
+                    format!("Bot {}", i + 1)
+                }
+            })
+            .collect();
+        let mut campaign =
+            Campaign::new(Uuid::new_v4().to_string(), bot_type, num_games, seat_order);
+
+        let game_id = self
+            .game_service
+            .create_seated_game(&campaign.seats(), &campaign.bot_type)
+            .await?;
+        campaign.current_game_id = Some(game_id);
+        log::info!(
+            "🏆 Campaign {} created: {} games, mode '{}'",
+            campaign.id,
+            num_games,
+            bot_type
+        );
+
+        let summary = campaign.summary();
+        self.campaigns
+            .write()
+            .await
+            .insert(campaign.id.clone(), campaign);
+        Ok(summary)
+    }
+
+    pub async fn get_summary(&self, campaign_id: &str) -> CatanResult<CampaignSummary> {
+        self.campaigns
+            .read()
+            .await
+            .get(campaign_id)
+            .map(Campaign::summary)
+            .ok_or_else(|| campaign_not_found(campaign_id))
+    }
+
+    /// Record the current game's result and start the next game, if any remain.
+    /// Fails if the current game has not finished.
+    pub async fn advance(&self, campaign_id: &str) -> CatanResult<CampaignSummary> {
+        let mut campaigns = self.campaigns.write().await;
+        let campaign = campaigns
+            .get_mut(campaign_id)
+            .ok_or_else(|| campaign_not_found(campaign_id))?;
+        let current_game_id = campaign.current_game_id.clone().ok_or_else(|| {
+            CatanError::Validation(format!("Campaign {campaign_id} is already finished"))
+        })?;
+
+        let game = self.game_service.get_game(&current_game_id).await?;
+        campaign.record_result(game_result(&game)?);
+        campaign.current_game_id = None;
+
+        if !campaign.is_finished() {
+            let game_id = self
+                .game_service
+                .create_seated_game(&campaign.seats(), &campaign.bot_type)
+                .await?;
+            campaign.current_game_id = Some(game_id);
+        }
+        log::info!(
+            "🏆 Campaign {}: {}/{} games played",
+            campaign.id,
+            campaign.games_played(),
+            campaign.num_games
+        );
+        Ok(campaign.summary())
+    }
+}
+
+fn campaign_not_found(campaign_id: &str) -> CatanError {
+    GameError::CampaignNotFound {
+        campaign_id: campaign_id.to_string(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(winner: &str, vps: &[(&str, u32)]) -> CampaignGameResult {
+        CampaignGameResult {
+            game_id: "g".to_string(),
+            winner: Some(winner.to_string()),
+            victory_points: vps.iter().map(|(n, v)| (n.to_string(), *v)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_winner_places_last_in_next_game() {
+        let r = result("A", &[("A", 10), ("B", 4), ("C", 7), ("D", 4)]);
+        assert_eq!(next_seat_order(&r), vec!["B", "D", "C", "A"]);
+    }
+
+    #[test]
+    fn test_points_accumulate_across_games() {
+        let seats = ["A", "B", "C"].iter().map(|s| s.to_string()).collect();
+        let mut campaign = Campaign::new("c".into(), "random", 2, seats);
+        campaign.record_result(result("A", &[("A", 10), ("B", 5), ("C", 3)]));
+        campaign.record_result(result("B", &[("B", 10), ("C", 6), ("A", 8)]));
+
+        let summary = campaign.summary();
+        assert!(summary.finished);
+        let points: Vec<_> = summary
+            .standings
+            .iter()
+            .map(|s| (s.name.as_str(), s.points, s.wins))
+            .collect();
+        assert_eq!(
+            points,
+            vec![
+                ("A", 18 + CAMPAIGN_WIN_BONUS, 1),
+                ("B", 15 + CAMPAIGN_WIN_BONUS, 1),
+                ("C", 9, 0)
+            ]
+        );
+        assert_eq!(campaign.seat_order, vec!["C", "A", "B"]);
+    }
+}
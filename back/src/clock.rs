@@ -0,0 +1,75 @@
+// Time as the server sees it. Build windows, bot pacing and auto-pass delays read the
+// time and sleep through a `Clock`, so tests can swap in a `FakeClock` whose sleeps
+// return at once and move its time forward instead.
+
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[async_trait]
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Wall-clock time and tokio timers
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Time that only moves when slept through or advanced. Every sleep is recorded.
+#[derive(Debug)]
+pub struct FakeClock {
+    start: Instant,
+    elapsed: Mutex<Duration>,
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+            sleeps: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl FakeClock {
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    /// Time passed since the clock was made
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+
+    /// Every sleep so far, in order
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        self.sleeps.lock().unwrap().push(duration);
+        self.advance(duration);
+        tokio::task::yield_now().await;
+    }
+}
@@ -0,0 +1,34 @@
+// Frontend served from the binary itself (cargo feature `embedded-frontend`). The built
+// Angular app is compiled in with rust-embed, so one executable serves both the API and
+// the game UI. Build the app first with `npm run build:embedded` in front/.
+
+use axum::http::{header, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "../front/dist/catan/browser/browser/"]
+struct Assets;
+
+/// Router fallback: built files by path, and the app shell for anything else without a
+/// file extension so client-side routes like /game/{id} survive a reload
+pub async fn serve_asset(uri: Uri) -> Response {
+    let path = uri.path().trim_start_matches('/');
+    let path = if path.is_empty() { "index.html" } else { path };
+    if let Some(file) = Assets::get(path) {
+        let mime = file.metadata.mimetype().to_string();
+        return ([(header::CONTENT_TYPE, mime)], file.data).into_response();
+    }
+    let is_file = path
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| name.contains('.'));
+    match Assets::get("index.html") {
+        Some(index) if !is_file => (
+            [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+            index.data,
+        )
+            .into_response(),
+        _ => StatusCode::NOT_FOUND.into_response(),
+    }
+}
@@ -0,0 +1,98 @@
+// Idle games written to disk so they stop holding memory. A game is hibernated once its
+// last WebSocket connection closes and its bot loop has wound down; GameService brings it
+// back the next time anything asks for it, over REST or WebSocket. Games come back by
+// re-applying their recorded actions, the same way savegames are imported.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::auto_pass::AutoPass;
+use crate::errors::{CatanError, CatanResult};
+use crate::game::{ForkOrigin, SaveGame};
+use crate::palette::Palette;
+use crate::preferences::persistence_error;
+
+/// Environment variable naming the hibernation directory; games stay in memory when unset
+pub const HIBERNATION_DIR_ENV: &str = "CATAN_HIBERNATION_DIR";
+
+/// Everything the service keeps about a game that its savegame does not cover
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HibernatedGame {
+    pub save: SaveGame,
+    #[serde(default)]
+    pub palette: Palette,
+    pub version: u64,
+    pub last_action_seq: u64,
+    #[serde(default)]
+    pub forked_from: Option<ForkOrigin>,
+    /// Bot modes of the seats that override the game's mode
+    #[serde(default)]
+    pub seat_bot_modes: Option<Vec<Option<String>>>,
+    #[serde(default)]
+    pub auto_pass: HashMap<u8, AutoPass>,
+}
+
+/// One JSON file per hibernated game under one directory
+#[derive(Debug, Clone)]
+pub struct HibernationStore {
+    dir: PathBuf,
+}
+
+impl HibernationStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Hibernate into the directory named by `CATAN_HIBERNATION_DIR`, if the variable is set
+    pub fn from_env() -> Option<Self> {
+        std::env::var(HIBERNATION_DIR_ENV).ok().map(Self::new)
+    }
+
+    /// File for a game. Game ids are generated names; anything that could leave the
+    /// directory is rejected.
+    fn path(&self, game_id: &str) -> CatanResult<PathBuf> {
+        let valid = !game_id.is_empty()
+            && game_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(CatanError::Validation(format!(
+                "invalid game id '{game_id}'"
+            )));
+        }
+        Ok(self.dir.join(format!("{game_id}.json")))
+    }
+
+    pub fn contains(&self, game_id: &str) -> bool {
+        self.path(game_id).is_ok_and(|path| path.is_file())
+    }
+
+    pub fn save(&self, game_id: &str, game: &HibernatedGame) -> CatanResult<()> {
+        let path = self.path(game_id)?;
+        std::fs::create_dir_all(&self.dir).map_err(|e| persistence_error(&self.dir, e))?;
+        let text = serde_json::to_string(game).map_err(|e| persistence_error(&path, e))?;
+        std::fs::write(&path, text).map_err(|e| persistence_error(&path, e))
+    }
+
+    /// The hibernated game, or `None` if `game_id` is not hibernated
+    pub fn load(&self, game_id: &str) -> CatanResult<Option<HibernatedGame>> {
+        let path = self.path(game_id)?;
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map(Some)
+                .map_err(|e| persistence_error(&path, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(persistence_error(&path, e)),
+        }
+    }
+
+    /// Forget a hibernated game; nothing happens if it is not hibernated
+    pub fn remove(&self, game_id: &str) -> CatanResult<()> {
+        let path = self.path(game_id)?;
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(persistence_error(&path, e)),
+            _ => Ok(()),
+        }
+    }
+}
@@ -2,31 +2,34 @@
 //
 // This file serves as the central organization point for the Catan game server,
 // exporting all the necessary modules and types in a clean, structured manner.
+// The engine itself lives in catan-core; its modules are re-exported here under the
+// paths they always had.
 
 // New unified architecture modules
-pub mod actions;
-pub mod errors;
 pub mod player_system;
 
 // Clean architecture layers
 pub mod application;
+pub mod bot_loop;
+pub mod campaign;
+pub mod clock;
+pub mod hibernation;
+pub mod preferences;
+pub mod sharding;
+pub mod speed;
 pub mod websocket;
 
-// Core game data structures and enums
-pub mod enums;
-pub mod game;
-pub mod state;
-pub mod state_vector;
+// Game UI compiled into the binary (single-binary local play)
+#[cfg(feature = "embedded-frontend")]
+pub mod frontend;
 
-// Game logic implementation
-pub mod deck_slices;
-pub mod global_state;
-pub mod map_instance;
-pub mod map_template;
-
-pub mod ordered_hashmap;
-pub mod player;
-pub mod players;
+// Engine modules from catan-core
+pub use catan_core::{
+    actions, audit, auto_pass, commands, compact_actions, conformance, deck_slices, dice_stats,
+    enums, errors, game, global_state, map_instance, map_template, ordered_hashmap, pace, palette,
+    placement_stats, player, players, replay, simulation, state, state_vector, think_time,
+    RULES_REVISION, VERSION,
+};
 
 // Server implementation - using modern GameService in application.rs
 
@@ -47,13 +50,12 @@ pub use crate::websocket::{WebSocketService, WsMessage};
 // Common types used throughout the application
 pub type PlayerColor = String;
 
-// Version information
-pub const VERSION: &str = env!("CARGO_PKG_VERSION");
-
 // Create a new game instance with the given configuration
 pub fn create_game(config: GameConfiguration) -> CatanResult<Game> {
-    Ok(Game::new(
+    config.validate()?;
+    Ok(Game::new_with_config(
         uuid::Uuid::new_v4().to_string(),
         vec!["Player".to_string(); config.num_players as usize],
+        config,
     ))
 }
@@ -1,8 +1,9 @@
 use axum::http::Method;
 use axum::{
-    extract::{Path, State, WebSocketUpgrade},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{Path, Query, Request, State, WebSocketUpgrade},
+    http::{header, HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Redirect, Response},
     routing::{get, post},
     Json, Router,
 };
@@ -10,9 +11,26 @@ use serde::Deserialize;
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 
-use catan::application::GameService;
-use catan::game::Game;
-use catan::websocket::WebSocketService;
+use catan::actions::{PartialAction, PlayerAction};
+use catan::application::{GameService, HistoryAccess, TableOptions};
+use catan::audit::AuditReport;
+use catan::campaign::{CampaignService, CampaignSummary};
+use catan::compact_actions::ActionsFormat;
+use catan::enums::{GameConfiguration, RobberRules, SeatHandicap, Seating, VariantConfig};
+use catan::errors::CatanError;
+use catan::game::{ActionPage, Game, GameDump, GameStats, SaveGame, VpHistory};
+use catan::hibernation::{HibernationStore, HIBERNATION_DIR_ENV};
+use catan::pace::Pace;
+use catan::players::deliberation::DELIBERATION_DIR_ENV;
+use catan::players::ladder::{Ladder, DEFAULT_LADDER_PATH};
+use catan::players::profile::{EvalProfile, EVAL_PROFILE_ENV};
+use catan::players::DeliberationLog;
+use catan::preferences::{
+    PreferenceStore, UserPreferences, DEFAULT_PREFERENCES_PATH, PREFERENCES_PATH_ENV,
+};
+use catan::sharding::{ShardRouter, SHARD_NODES_ENV};
+use catan::state::validation::Legality;
+use catan::websocket::{WebSocketService, WsMessage};
 
 // Game configuration
 #[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
@@ -20,6 +38,7 @@ use catan::websocket::WebSocketService;
 enum GameMode {
     HumanVsCatanatron,
     HumanVsRandom,
+    HumanVsHard,
     RandomBots,
     CatanatronBots,
 }
@@ -28,34 +47,131 @@ enum GameMode {
 struct GameConfig {
     mode: GameMode,
     num_players: u8,
+    #[serde(default)]
+    seating: Seating,
+    /// One entry per color, or none for an even game
+    #[serde(default)]
+    handicaps: Vec<SeatHandicap>,
+    #[serde(default)]
+    variants: VariantConfig,
+    /// House rules for the robber, such as the friendly robber
+    #[serde(default)]
+    robber: RobberRules,
+    /// Rule values, board and per-seat bots
+    #[serde(flatten)]
+    table: TableOptions,
+    /// Falls back to the account's default pace, then the standard one
+    #[serde(default)]
+    pace: Option<Pace>,
+    /// Account taking the human seat; its saved preferences apply to the game
+    #[serde(default)]
+    account: Option<String>,
+}
+
+/// Page of `/games/{id}/actions`
+#[derive(Debug, Deserialize)]
+struct ActionPageQuery {
+    #[serde(default)]
+    from: usize,
+    limit: Option<usize>,
+}
+
+/// Actions per page when the request gives no limit, and the most one page can hold
+const DEFAULT_ACTION_PAGE: usize = 50;
+const MAX_ACTION_PAGE: usize = 500;
+
+/// Question for `/games/{id}/legality`: may `color` (the player to move if absent) take
+/// `action` now?
+#[derive(Debug, Deserialize)]
+struct LegalityQuery {
+    color: Option<String>,
+    action: PlayerAction,
+}
+
+/// Options of a WebSocket connection, e.g. `?actions=mask` for compact playable actions
+#[derive(Debug, Deserialize)]
+struct WsQuery {
+    #[serde(default)]
+    actions: ActionsFormat,
+}
+
+/// Fork point of `/games/{id}/fork`, plus seats to hand to bots (e.g. `bots=1,2`)
+#[derive(Debug, Deserialize)]
+struct ForkQuery {
+    at: usize,
+    bots: Option<String>,
+    /// Account seated in the game, for forking it while in play
+    account: Option<String>,
+}
+
+/// Account seated in the game, for exporting it while in play
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    account: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CampaignConfig {
+    mode: GameMode,
+    num_players: u8,
+    num_games: u32,
+}
+
+// We pass a descriptive string through to GameService so it can select bot logic per mode
+fn bot_type_for(mode: &GameMode) -> &'static str {
+    match mode {
+        GameMode::RandomBots => "random",
+        GameMode::HumanVsCatanatron => "human_alphabeta", // First player human, bots use AlphaBeta
+        GameMode::HumanVsRandom => "human_random",        // First player human, bots random
+        GameMode::HumanVsHard => "human_hard", // First player human, bots use the ladder champion
+        GameMode::CatanatronBots => "alphabeta", // All bots use AlphaBeta
+    }
 }
 
 // Clean application state - single dependency injection point
 struct AppState {
     game_service: Arc<GameService>,
+    campaign_service: Arc<CampaignService>,
     websocket_service: Arc<WebSocketService>,
+    /// Token required by the admin routes (/admin/*, /games/{id}/debug), which are
+    /// disabled when unset
+    admin_token: Option<String>,
 }
 
+/// Environment variable holding the admin token
+const ADMIN_TOKEN_ENV: &str = "CATAN_ADMIN_TOKEN";
+/// Request header carrying the admin token
+const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+/// Header carrying an account's token, issued in the response to its first save
+const ACCOUNT_TOKEN_HEADER: &str = "x-account-token";
+
 // API Routes
 
 // Get hello world
+#[cfg(not(feature = "embedded-frontend"))]
 async fn hello_world() -> &'static str {
     "Hello from Catan backend!"
 }
 
-// Simple MCTS analysis placeholder endpoint
+// Position analysis: game status plus the recommended action and its chance outcomes
 async fn analyze_game(
     State(state): State<Arc<AppState>>,
     Path(game_id): Path<String>,
 ) -> impl IntoResponse {
-    // For now, return a minimal JSON with available actions count and status
     match state.game_service.get_game(&game_id).await {
         Ok(game) => {
             let actions = game.current_playable_actions.len();
+            let recommendation = state
+                .game_service
+                .analyze_position(&game_id)
+                .await
+                .ok()
+                .flatten();
             let payload = serde_json::json!({
                 "game_id": game_id,
                 "status": game.game_state,
                 "available_actions": actions,
+                "recommendation": recommendation,
             });
             (StatusCode::OK, axum::Json(payload))
         }
@@ -69,7 +185,8 @@ async fn analyze_game(
 // Create a new game
 async fn create_game(
     State(state): State<Arc<AppState>>,
-    Json(config): Json<GameConfig>,
+    headers: HeaderMap,
+    Json(mut config): Json<GameConfig>,
 ) -> Result<Json<Game>, StatusCode> {
     log::info!(
         "Creating game with mode: {:?}, players: {}",
@@ -78,20 +195,46 @@ async fn create_game(
     );
 
     // Determine bot type from config
-    // We pass a descriptive string through to GameService so it can select bot logic per mode
-    let bot_type = match config.mode {
-        GameMode::RandomBots => "random",
-        GameMode::HumanVsCatanatron => "human_alphabeta", // First player human, bots use AlphaBeta
-        GameMode::HumanVsRandom => "human_random",        // First player human, bots random
-        GameMode::CatanatronBots => "alphabeta",          // All bots use AlphaBeta
-    };
+    let bot_type = bot_type_for(&config.mode);
+    let mut pace = config.pace.unwrap_or_default();
+    if let Some(account) = &config.account {
+        let preferences = state
+            .game_service
+            .preferences(account, account_token(&headers))
+            .await
+            .map_err(preferences_status)?;
+        pace = config.pace.unwrap_or(preferences.default_pace);
+        config.table.palette.get_or_insert(preferences.palette);
+    }
 
     // Delegate to game service (clean separation)
-    match state
+    let created = state
         .game_service
-        .create_game(config.num_players, bot_type)
-        .await
-    {
+        .create_game(
+            bot_type,
+            GameConfiguration::builder()
+                .num_players(config.num_players)
+                .seating(config.seating)
+                .handicaps(config.handicaps)
+                .variants(config.variants)
+                .robber(config.robber),
+            &config.table,
+            pace,
+        )
+        .await;
+    let seated = match (created, &config.account) {
+        (Ok(game_id), Some(account)) => {
+            match state.game_service.seat_account(&game_id, account).await {
+                Ok(()) => Ok(game_id),
+                Err(e) => {
+                    let _ = state.game_service.remove_game(&game_id).await;
+                    Err(e)
+                }
+            }
+        }
+        (created, _) => created,
+    };
+    match seated {
         Ok(game_id) => {
             // Return the full game object
             match state.game_service.get_game(&game_id).await {
@@ -99,10 +242,63 @@ async fn create_game(
                 Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
             }
         }
+        Err(CatanError::Validation(_) | CatanError::Game(_)) => Err(StatusCode::BAD_REQUEST),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
 
+// The account token a request presents, if any
+fn account_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(ACCOUNT_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+}
+
+fn preferences_status(e: CatanError) -> StatusCode {
+    match e {
+        CatanError::Player(_) => StatusCode::UNAUTHORIZED,
+        CatanError::Validation(_) => StatusCode::BAD_REQUEST,
+        e => {
+            log::error!("Failed to access preferences: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+// Saved preferences of an account (defaults if it has none); needs the account's token
+async fn get_preferences(
+    State(state): State<Arc<AppState>>,
+    Path(account): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<UserPreferences>, StatusCode> {
+    state
+        .game_service
+        .preferences(&account, account_token(&headers))
+        .await
+        .map(Json)
+        .map_err(preferences_status)
+}
+
+// Replace an account's preferences; the first save claims the account and answers with
+// its token in the x-account-token header, which every later request must present
+async fn set_preferences(
+    State(state): State<Arc<AppState>>,
+    Path(account): Path<String>,
+    headers: HeaderMap,
+    Json(preferences): Json<UserPreferences>,
+) -> Result<Response, StatusCode> {
+    let issued = state
+        .game_service
+        .set_preferences(&account, account_token(&headers), preferences.clone())
+        .await
+        .map_err(preferences_status)?;
+    let mut response = Json(preferences).into_response();
+    if let Some(token) = issued.and_then(|token| token.parse().ok()) {
+        response.headers_mut().insert(ACCOUNT_TOKEN_HEADER, token);
+    }
+    Ok(response)
+}
+
 // Get a game
 async fn get_game(
     State(state): State<Arc<AppState>>,
@@ -117,56 +313,528 @@ async fn get_game(
     }
 }
 
+// Who asks for a game's history: an admin, or the account named in the query if it
+// presents the account's token
+fn history_access<'a>(
+    state: &AppState,
+    headers: &'a HeaderMap,
+    account: Option<&'a str>,
+) -> HistoryAccess<'a> {
+    if require_admin(state, headers).is_ok() {
+        return HistoryAccess::Admin;
+    }
+    match account {
+        Some(name) => HistoryAccess::Account {
+            name,
+            token: account_token(headers),
+        },
+        None => HistoryAccess::Anyone,
+    }
+}
+
+// Export a game as a savegame blob; games still in play only for admins and the account
+// seated in them
+async fn export_game(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    Query(export): Query<ExportQuery>,
+    headers: HeaderMap,
+) -> Result<Json<SaveGame>, StatusCode> {
+    let access = history_access(&state, &headers, export.account.as_deref());
+    match state.game_service.export_game(&game_id, access).await {
+        Ok(save) => Ok(Json(save)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(CatanError::Validation(_)) => Err(StatusCode::FORBIDDEN),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Victory points after every completed turn, for VP-over-time charts
+async fn get_vp_history(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+) -> Result<Json<VpHistory>, StatusCode> {
+    match state.game_service.vp_history(&game_id).await {
+        Ok(history) => Ok(Json(history)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Rules oracle: checks a hypothetical action and names the rule it breaks, if any
+async fn check_legality(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    Json(query): Json<LegalityQuery>,
+) -> Result<Json<Legality>, StatusCode> {
+    match state
+        .game_service
+        .check_action(&game_id, query.color.as_deref(), query.action)
+        .await
+    {
+        Ok(legality) => Ok(Json(legality)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(CatanError::Validation(_)) => Err(StatusCode::BAD_REQUEST),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Table that compact playable actions index into; fixed for the whole game
+async fn get_action_table(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+) -> Result<Json<Vec<PlayerAction>>, StatusCode> {
+    match state.game_service.action_table(&game_id).await {
+        Ok(table) => Ok(Json(table)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Paged action history with per-action deltas, so replay viewers can scrub long games
+async fn get_actions(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    Query(page): Query<ActionPageQuery>,
+) -> Result<Json<ActionPage>, StatusCode> {
+    let limit = page
+        .limit
+        .unwrap_or(DEFAULT_ACTION_PAGE)
+        .min(MAX_ACTION_PAGE);
+    match state
+        .game_service
+        .action_page(&game_id, page.from, limit)
+        .await
+    {
+        Ok(page) => Ok(Json(page)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Turn count and dice fairness (roll histogram with a chi-square test against fair dice)
+async fn get_game_stats(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+) -> Result<Json<GameStats>, StatusCode> {
+    match state.game_service.game_stats(&game_id).await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Legal actions matching a partially specified one (e.g. a road touching a clicked node)
+async fn complete_action(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    Json(partial): Json<PartialAction>,
+) -> Result<Json<Vec<PlayerAction>>, StatusCode> {
+    match state.game_service.complete_action(&game_id, &partial).await {
+        Ok(completions) => Ok(Json(completions)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Admin routes answer 404 while no admin token is configured, so they look absent
+fn require_admin(state: &AppState, headers: &HeaderMap) -> Result<(), StatusCode> {
+    let Some(token) = &state.admin_token else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+    let supplied = headers
+        .get(ADMIN_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok());
+    if supplied != Some(token.as_str()) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(())
+}
+
+// Admin: download a game's bot deliberation log (NDJSON, one decision per line)
+async fn get_deliberation_log(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, StatusCode> {
+    require_admin(&state, &headers)?;
+    match state.game_service.deliberation_log(&game_id) {
+        Ok(log) => Ok(([(header::CONTENT_TYPE, "application/x-ndjson")], log)),
+        Err(CatanError::Validation(_)) => Err(StatusCode::BAD_REQUEST),
+        Err(CatanError::Infrastructure(_)) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Admin: hibernate the games in memory that other instances own, for their owners to
+// pick up from the shared store (e.g. after the shard nodes changed in a deploy)
+async fn hand_off_games(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<String>>, StatusCode> {
+    require_admin(&state, &headers)?;
+    match state.game_service.hand_off_games().await {
+        Ok(handed_off) => Ok(Json(handed_off)),
+        Err(e) => {
+            log::error!("Hand-off failed: {e}");
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+// Admin: how closely each human seat's decisions and think times track the engine
+async fn get_game_audit(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<AuditReport>, StatusCode> {
+    require_admin(&state, &headers)?;
+    match state.game_service.audit_game(&game_id).await {
+        Ok(report) => Ok(Json(report)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(CatanError::Validation(_)) => Err(StatusCode::BAD_REQUEST),
+        Err(e) => {
+            log::error!("Audit of game {game_id} failed: {e}");
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+// Admin: raw state vector, caches, prompt and recent actions of a game
+async fn get_game_debug(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<GameDump>, StatusCode> {
+    require_admin(&state, &headers)?;
+    match state.game_service.debug_dump(&game_id).await {
+        Ok(dump) => Ok(Json(dump)),
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Recreate an exported game under a new id
+async fn import_game(
+    State(state): State<Arc<AppState>>,
+    Json(save): Json<SaveGame>,
+) -> Result<Json<Game>, StatusCode> {
+    match state.game_service.import_game(save).await {
+        Ok(game_id) => match state.game_service.get_game(&game_id).await {
+            Ok(game) => Ok(Json(game)),
+            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        },
+        Err(e) => {
+            log::warn!("Rejected savegame import: {e}");
+            Err(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+// Branch a sandbox game off any point of a finished game's history; games still in play
+// only for admins and the account seated in them
+async fn fork_game(
+    State(state): State<Arc<AppState>>,
+    Path(game_id): Path<String>,
+    Query(fork): Query<ForkQuery>,
+    headers: HeaderMap,
+) -> Result<Json<Game>, StatusCode> {
+    let bot_seats: Vec<usize> = match fork.bots.as_deref() {
+        None | Some("") => Vec::new(),
+        Some(seats) => seats
+            .split(',')
+            .map(|seat| seat.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| StatusCode::BAD_REQUEST)?,
+    };
+    let access = history_access(&state, &headers, fork.account.as_deref());
+    match state
+        .game_service
+        .fork_game(&game_id, fork.at, &bot_seats, access)
+        .await
+    {
+        Ok(fork_id) => match state.game_service.get_game(&fork_id).await {
+            Ok(game) => Ok(Json(game)),
+            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        },
+        Err(CatanError::Game(_)) => Err(StatusCode::NOT_FOUND),
+        Err(CatanError::Validation(e)) => {
+            log::warn!("Rejected fork of game {game_id}: {e}");
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Create a campaign and start its first game
+async fn create_campaign(
+    State(state): State<Arc<AppState>>,
+    Json(config): Json<CampaignConfig>,
+) -> Result<Json<CampaignSummary>, StatusCode> {
+    log::info!(
+        "Creating campaign with mode: {:?}, players: {}, games: {}",
+        config.mode,
+        config.num_players,
+        config.num_games
+    );
+    match state
+        .campaign_service
+        .create_campaign(
+            config.num_players,
+            bot_type_for(&config.mode),
+            config.num_games,
+        )
+        .await
+    {
+        Ok(summary) => Ok(Json(summary)),
+        Err(CatanError::Validation(_)) => Err(StatusCode::BAD_REQUEST),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+// Get campaign standings
+async fn get_campaign(
+    State(state): State<Arc<AppState>>,
+    Path(campaign_id): Path<String>,
+) -> Result<Json<CampaignSummary>, StatusCode> {
+    match state.campaign_service.get_summary(&campaign_id).await {
+        Ok(summary) => Ok(Json(summary)),
+        Err(_) => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+// Record the finished campaign game and start the next one
+async fn advance_campaign(
+    State(state): State<Arc<AppState>>,
+    Path(campaign_id): Path<String>,
+) -> Result<Json<CampaignSummary>, StatusCode> {
+    let finished_game_id = match state.campaign_service.get_summary(&campaign_id).await {
+        Ok(summary) => summary.current_game_id,
+        Err(_) => return Err(StatusCode::NOT_FOUND),
+    };
+    match state.campaign_service.advance(&campaign_id).await {
+        Ok(summary) => {
+            // Let everyone still watching the finished game see the standings
+            if let Some(game_id) = finished_game_id {
+                let _ = state.websocket_service.broadcaster().send((
+                    game_id,
+                    WsMessage::CampaignStandings {
+                        summary: summary.clone(),
+                    },
+                ));
+            }
+            Ok(Json(summary))
+        }
+        Err(e) => {
+            log::warn!("Cannot advance campaign {campaign_id}: {e}");
+            Err(StatusCode::CONFLICT)
+        }
+    }
+}
+
 // WebSocket handler for game updates
 async fn ws_handler(
     ws: WebSocketUpgrade,
     Path(game_id): Path<String>,
+    Query(options): Query<WsQuery>,
     State(state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
     // Delegate to WebSocket service (clean separation)
     ws.on_upgrade(move |socket| async move {
         state
             .websocket_service
-            .handle_connection(socket, game_id)
+            .handle_connection(socket, Some(game_id), options.actions)
             .await
     })
 }
 
+// WebSocket not tied to one game; games are followed with `subscribe` messages
+async fn ws_lobby_handler(
+    ws: WebSocketUpgrade,
+    Query(options): Query<WsQuery>,
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| async move {
+        state
+            .websocket_service
+            .handle_connection(socket, None, options.actions)
+            .await
+    })
+}
+
+// Requests about another instance's game are sent there; 307 keeps the method and body
+async fn route_to_owner(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let owner_url = state.game_service.router().and_then(|router| {
+        let path_and_query = request.uri().path_and_query()?;
+        router.redirect(path_and_query.as_str())
+    });
+    match owner_url {
+        Some(url) => Redirect::temporary(&url).into_response(),
+        None => next.run(request).await,
+    }
+}
+
+/// Listen address of the standalone (`embedded-frontend`) binary
+#[cfg(feature = "embedded-frontend")]
+const LISTEN_ADDR_ENV: &str = "CATAN_ADDR";
+#[cfg(feature = "embedded-frontend")]
+const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:8000";
+
+#[cfg(not(feature = "embedded-frontend"))]
 #[shuttle_runtime::main]
 async fn main() -> shuttle_axum::ShuttleAxum {
+    init_logging();
+    let app = api_router().route("/", get(hello_world)).layer(cors());
+
+    log::info!("Starting Catan backend server");
+
+    Ok(app.into())
+}
+
+// Single binary for local play: the API plus the game UI on one port, no Shuttle needed
+#[cfg(feature = "embedded-frontend")]
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    init_logging();
+    let app = api_router()
+        .fallback(catan::frontend::serve_asset)
+        .layer(cors());
+
+    let addr = std::env::var(LISTEN_ADDR_ENV).unwrap_or_else(|_| DEFAULT_LISTEN_ADDR.to_string());
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    log::info!("Serving Catan at http://{addr}");
+    axum::serve(listener, app).await
+}
+
+fn init_logging() {
     // Initialize logger (only if not already initialized by shuttle)
     if std::env::var("RUST_LOG").is_err() {
         std::env::set_var("RUST_LOG", "info");
     }
     let _ = env_logger::try_init(); // Use try_init to avoid double initialization
+}
+
+fn cors() -> CorsLayer {
+    CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers(Any)
+        .expose_headers([header::HeaderName::from_static(ACCOUNT_TOKEN_HEADER)])
+        .allow_origin(Any)
+}
 
+// Services and API routes, shared by both entry points
+fn api_router() -> Router {
     // Create clean service layer architecture
-    let game_service = Arc::new(GameService::new());
+    let ladder_path =
+        std::env::var("CATAN_LADDER_PATH").unwrap_or_else(|_| DEFAULT_LADDER_PATH.to_string());
+    let champion = match Ladder::load(std::path::Path::new(&ladder_path)) {
+        Ok(ladder) => ladder.champion().cloned(),
+        Err(e) => {
+            log::warn!("Could not load champion lineage {ladder_path}: {e}");
+            None
+        }
+    };
+    if let Some(champion) = &champion {
+        log::info!(
+            "🏆 Hard bot uses champion {} from {ladder_path}",
+            champion.tag
+        );
+    }
+    let eval_profile = match EvalProfile::from_env() {
+        Ok(profile) => profile,
+        Err(e) => {
+            log::warn!("Could not load {EVAL_PROFILE_ENV}: {e}");
+            None
+        }
+    };
+    if eval_profile.is_some() {
+        log::info!("🎛️ Bots use the evaluation profile from {EVAL_PROFILE_ENV}");
+    }
+    let deliberation_log = DeliberationLog::from_env();
+    if deliberation_log.is_some() {
+        log::info!("📝 Bot deliberation is logged per game under {DELIBERATION_DIR_ENV}");
+    }
+    let hibernation = HibernationStore::from_env();
+    if hibernation.is_some() {
+        log::info!("💤 Idle games are hibernated under {HIBERNATION_DIR_ENV}");
+    }
+    let router = match ShardRouter::from_env() {
+        Ok(router) => router,
+        Err(e) => {
+            log::warn!("Could not read {SHARD_NODES_ENV}, owning every game: {e}");
+            None
+        }
+    };
+    if router.is_some() {
+        log::info!("🔀 Games are sharded over the instances in {SHARD_NODES_ENV}");
+    }
+    let preferences_path = std::env::var(PREFERENCES_PATH_ENV)
+        .unwrap_or_else(|_| DEFAULT_PREFERENCES_PATH.to_string());
+    let preferences = match PreferenceStore::load(std::path::Path::new(&preferences_path)) {
+        Ok(store) => store,
+        Err(e) => {
+            log::warn!(
+                "Could not load preferences {preferences_path}, keeping them in memory: {e}"
+            );
+            PreferenceStore::in_memory()
+        }
+    };
+    let game_service = Arc::new(
+        GameService::new()
+            .with_hard_bot_weights(champion.map(|c| c.weights))
+            .with_eval_profile(eval_profile)
+            .with_deliberation_log(deliberation_log)
+            .with_hibernation(hibernation)
+            .with_router(router)
+            .with_preferences(preferences),
+    );
+    let campaign_service = Arc::new(CampaignService::new(game_service.clone()));
     let websocket_service = Arc::new(WebSocketService::new(game_service.clone()));
 
     // Create shared application state with dependency injection
     let state = Arc::new(AppState {
         game_service,
+        campaign_service,
         websocket_service,
+        admin_token: std::env::var(ADMIN_TOKEN_ENV).ok(),
     });
 
-    // Configure CORS
-    let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-        .allow_headers(Any)
-        .allow_origin(Any);
-
-    // Create router with routes
-    let app = Router::new()
-        .route("/", get(hello_world))
+    Router::new()
         .route("/mcts/analyze/{game_id}", get(analyze_game))
         .route("/games", post(create_game))
+        .route("/games/import", post(import_game))
         .route("/games/{game_id}", get(get_game))
+        .route("/games/{game_id}/export", get(export_game))
+        .route("/games/{game_id}/vp-history", get(get_vp_history))
+        .route("/games/{game_id}/stats", get(get_game_stats))
+        .route("/games/{game_id}/debug", get(get_game_debug))
+        .route("/games/{game_id}/actions", get(get_actions))
+        .route("/games/{game_id}/action-table", get(get_action_table))
+        .route("/games/{game_id}/legality", post(check_legality))
+        .route("/games/{game_id}/fork", post(fork_game))
+        .route("/games/{game_id}/actions/complete", post(complete_action))
+        .route(
+            "/admin/games/{game_id}/deliberation",
+            get(get_deliberation_log),
+        )
+        .route("/admin/games/{game_id}/audit", get(get_game_audit))
+        .route("/admin/handoff", post(hand_off_games))
+        .route(
+            "/users/{account}/preferences",
+            get(get_preferences).post(set_preferences),
+        )
+        .route("/campaigns", post(create_campaign))
+        .route("/campaigns/{campaign_id}", get(get_campaign))
+        .route("/campaigns/{campaign_id}/next", post(advance_campaign))
+        .route("/ws", get(ws_lobby_handler))
         .route("/ws/games/{game_id}", get(ws_handler))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            route_to_owner,
+        ))
         .with_state(state)
-        .layer(cors);
-
-    log::info!("Starting Catan backend server");
-
-    Ok(app.into())
 }
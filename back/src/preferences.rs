@@ -0,0 +1,317 @@
+// Per-account settings, kept in a JSON file so they outlive the server process. Clients
+// set them once instead of sending them with every game; GameService reads them when an
+// account takes a seat. The first save claims an account and issues its token, which
+// every later read or write of the account must present.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::RwLock;
+
+use crate::errors::{CatanError, CatanResult, InfrastructureError, PlayerError};
+use crate::pace::Pace;
+use crate::palette::Palette;
+
+/// Environment variable naming the preferences file
+pub const PREFERENCES_PATH_ENV: &str = "CATAN_PREFERENCES_PATH";
+/// Where preferences are kept unless CATAN_PREFERENCES_PATH is set
+pub const DEFAULT_PREFERENCES_PATH: &str = "data/preferences.json";
+
+/// Seat colors, by color index
+const SEAT_COLORS: [&str; 6] = ["red", "blue", "white", "orange", "green", "brown"];
+/// Longest account name accepted
+const MAX_ACCOUNT_LEN: usize = 64;
+
+/// How much help the client offers during play
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HintLevel {
+    #[default]
+    Off,
+    /// Highlight where the player can build
+    Basic,
+    /// Also suggest the move analysis rates best
+    Full,
+}
+
+/// What an account has chosen; anything left out keeps its default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserPreferences {
+    /// Color to sit at in games against bots, e.g. "blue"
+    pub preferred_color: Option<String>,
+    /// Seat the account with auto-pass on, ending its turns once EndTurn is the only
+    /// legal action
+    pub auto_end_turn: bool,
+    /// Pace of new games that do not pick one
+    pub default_pace: Pace,
+    /// Seat colors of new games that do not pick any
+    pub palette: Palette,
+    pub hint_level: HintLevel,
+}
+
+impl UserPreferences {
+    /// Color index of the preferred color, if one is set
+    pub fn preferred_color_index(&self) -> Option<u8> {
+        let preferred = self.preferred_color.as_deref()?;
+        SEAT_COLORS
+            .iter()
+            .position(|color| color.eq_ignore_ascii_case(preferred))
+            .map(|index| index as u8)
+    }
+
+    pub fn validate(&self) -> CatanResult<()> {
+        match &self.preferred_color {
+            Some(color) if self.preferred_color_index().is_none() => Err(CatanError::Validation(
+                format!("unknown color {color:?}, expected one of {SEAT_COLORS:?}"),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// What is stored per account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Account {
+    #[serde(flatten)]
+    preferences: UserPreferences,
+    /// Proof of ownership, issued on the account's first save. Accounts saved before
+    /// tokens existed have none until their next save claims them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+}
+
+/// Preferences of every account, written through to disk on each change
+pub struct PreferenceStore {
+    /// File the preferences are saved to; kept in memory only when absent
+    path: Option<PathBuf>,
+    accounts: RwLock<HashMap<String, Account>>,
+}
+
+impl PreferenceStore {
+    /// A store that forgets everything on restart
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            accounts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Load the store saved at `path`; a missing file is an empty store
+    pub fn load(path: &Path) -> CatanResult<Self> {
+        let accounts = match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| persistence_error(path, e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(persistence_error(path, e)),
+        };
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            accounts: RwLock::new(accounts),
+        })
+    }
+
+    /// `account`'s preferences, or the defaults if it never saved any
+    pub async fn get(&self, account: &str) -> UserPreferences {
+        self.accounts
+            .read()
+            .await
+            .get(account)
+            .map(|stored| stored.preferences.clone())
+            .unwrap_or_default()
+    }
+
+    /// `account`'s preferences for a caller presenting `token`. Accounts that never saved
+    /// any have nothing to protect and answer with the defaults.
+    pub async fn get_authorized(
+        &self,
+        account: &str,
+        token: Option<&str>,
+    ) -> CatanResult<UserPreferences> {
+        match self.accounts.read().await.get(account) {
+            None => Ok(UserPreferences::default()),
+            Some(stored) if stored.token.is_some() && stored.token.as_deref() == token => {
+                Ok(stored.preferences.clone())
+            }
+            Some(_) => Err(authentication_failed(account)),
+        }
+    }
+
+    /// Whether `token` proves the caller owns `account`, which must have been claimed
+    pub async fn is_owner(&self, account: &str, token: Option<&str>) -> bool {
+        matches!(
+            self.accounts.read().await.get(account),
+            Some(stored) if stored.token.is_some() && stored.token.as_deref() == token
+        )
+    }
+
+    /// Replace `account`'s preferences and save the store. A claimed account needs its
+    /// `token`; saving an unclaimed one claims it and returns the token issued for it.
+    pub async fn set(
+        &self,
+        account: &str,
+        token: Option<&str>,
+        preferences: UserPreferences,
+    ) -> CatanResult<Option<String>> {
+        if account.is_empty() || account.len() > MAX_ACCOUNT_LEN {
+            return Err(CatanError::Validation(format!(
+                "account names are 1 to {MAX_ACCOUNT_LEN} bytes long"
+            )));
+        }
+        preferences.validate()?;
+
+        let mut accounts = self.accounts.write().await;
+        let claimed = accounts
+            .get(account)
+            .and_then(|stored| stored.token.clone());
+        let issued = match &claimed {
+            Some(expected) if Some(expected.as_str()) != token => {
+                return Err(authentication_failed(account));
+            }
+            Some(_) => None,
+            None => Some(uuid::Uuid::new_v4().simple().to_string()),
+        };
+        let stored = Account {
+            preferences,
+            token: claimed.or_else(|| issued.clone()),
+        };
+        let previous = accounts.insert(account.to_string(), stored);
+        if let Err(e) = self.save(&accounts) {
+            // Keep memory and disk in agreement
+            match previous {
+                Some(previous) => accounts.insert(account.to_string(), previous),
+                None => accounts.remove(account),
+            };
+            return Err(e);
+        }
+        Ok(issued)
+    }
+
+    fn save(&self, accounts: &HashMap<String, Account>) -> CatanResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| persistence_error(parent, e))?;
+        }
+        let text =
+            serde_json::to_string_pretty(accounts).map_err(|e| persistence_error(path, e))?;
+        std::fs::write(path, text).map_err(|e| persistence_error(path, e))
+    }
+}
+
+fn authentication_failed(account: &str) -> CatanError {
+    PlayerError::AuthenticationFailed {
+        player_id: account.to_string(),
+    }
+    .into()
+}
+
+pub(crate) fn persistence_error(path: &Path, e: impl std::fmt::Display) -> CatanError {
+    InfrastructureError::Persistence {
+        details: format!("{}: {e}", path.display()),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_preferences_survive_reload() {
+        let dir = std::env::temp_dir().join(format!("catan-prefs-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("preferences.json");
+        let preferences = UserPreferences {
+            preferred_color: Some("Blue".to_string()),
+            auto_end_turn: true,
+            default_pace: Pace::Blitz,
+            palette: Palette::ColorblindSafe,
+            hint_level: HintLevel::Full,
+        };
+
+        let store = PreferenceStore::load(&path).unwrap();
+        assert_eq!(store.get("ada").await, UserPreferences::default());
+        let token = store.set("ada", None, preferences.clone()).await.unwrap();
+
+        let reloaded = PreferenceStore::load(&path).unwrap();
+        assert_eq!(reloaded.get("ada").await, preferences);
+        let authorized = reloaded.get_authorized("ada", token.as_deref()).await;
+        assert_eq!(authorized.unwrap(), preferences);
+        assert_eq!(preferences.preferred_color_index(), Some(1));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_account_sits_at_its_preferred_color() {
+        use crate::application::{GameService, TableOptions};
+        use crate::enums::GameConfiguration;
+
+        let service = GameService::new();
+        let preferences = UserPreferences {
+            preferred_color: Some("white".to_string()),
+            ..Default::default()
+        };
+        service
+            .set_preferences("ada", None, preferences)
+            .await
+            .unwrap();
+        let game_id = service
+            .create_game(
+                "human_random",
+                GameConfiguration::builder().num_players(4),
+                &TableOptions::default(),
+                Pace::default(),
+            )
+            .await
+            .unwrap();
+        service.seat_account(&game_id, "ada").await.unwrap();
+
+        let game = service.get_game(&game_id).await.unwrap();
+        assert_eq!(game.players[2].name, "ada");
+        assert!(!game.bot_colors.contains(&"white".to_string()));
+        assert!(game.bot_colors.contains(&"red".to_string()));
+        assert_eq!(game.bot_colors.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_preferences_are_refused() {
+        let store = PreferenceStore::in_memory();
+        let purple = UserPreferences {
+            preferred_color: Some("purple".to_string()),
+            ..Default::default()
+        };
+        assert!(store.set("ada", None, purple).await.is_err());
+        assert!(store
+            .set("", None, UserPreferences::default())
+            .await
+            .is_err());
+        assert_eq!(store.get("ada").await, UserPreferences::default());
+    }
+
+    #[tokio::test]
+    async fn test_only_the_token_holder_reads_or_changes_an_account() {
+        let store = PreferenceStore::in_memory();
+        let blitz = UserPreferences {
+            default_pace: Pace::Blitz,
+            ..Default::default()
+        };
+        let token = store.set("ada", None, blitz.clone()).await.unwrap();
+        let token = token.expect("the first save claims the account");
+
+        let stranger = store.get_authorized("ada", None).await;
+        assert!(matches!(stranger, Err(CatanError::Player(_))));
+        let forged = store.get_authorized("ada", Some("guess")).await;
+        assert!(matches!(forged, Err(CatanError::Player(_))));
+        let overwrite = store.set("ada", Some("guess"), UserPreferences::default());
+        assert!(matches!(overwrite.await, Err(CatanError::Player(_))));
+        assert_eq!(store.get("ada").await, blitz);
+
+        let owner = store.get_authorized("ada", Some(&token)).await.unwrap();
+        assert_eq!(owner, blitz);
+        let reissued = store.set("ada", Some(&token), UserPreferences::default());
+        assert_eq!(reissued.await.unwrap(), None);
+        assert_eq!(store.get("ada").await, UserPreferences::default());
+        let unclaimed = store.get_authorized("grace", None).await;
+        assert_eq!(unclaimed.unwrap(), UserPreferences::default());
+    }
+}
@@ -0,0 +1,226 @@
+// Game ownership across server instances. Each instance owns the game ids that a
+// consistent-hash ring assigns to it, creates only games it owns, and redirects requests
+// for other games to their owner. Adding or removing an instance moves only the games
+// the ring hands to or takes from it; those are handed over through the shared
+// hibernation store.
+
+use crate::errors::{CatanError, CatanResult};
+
+/// Environment variable listing the instances, as `id=url` pairs separated by commas
+pub const SHARD_NODES_ENV: &str = "CATAN_SHARD_NODES";
+/// Environment variable naming this instance among `CATAN_SHARD_NODES`
+pub const SHARD_ID_ENV: &str = "CATAN_SHARD_ID";
+
+/// Points each instance gets on the ring, so games spread evenly between few instances
+const VIRTUAL_NODES: u32 = 64;
+
+/// A server instance and the base URL clients reach it at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardNode {
+    pub id: String,
+    pub url: String,
+}
+
+/// Consistent-hash ring over the instances, seen from one of them
+#[derive(Debug, Clone)]
+pub struct ShardRouter {
+    local: String,
+    nodes: Vec<ShardNode>,
+    /// Ring points and the index of the node owning the arc ending at each, by point
+    ring: Vec<(u64, usize)>,
+}
+
+impl ShardRouter {
+    /// Router for the instance `local`. An instance missing from `nodes` owns no games,
+    /// which is how one is drained before a deploy takes it down.
+    pub fn new(local: impl Into<String>, nodes: Vec<ShardNode>) -> CatanResult<Self> {
+        if nodes.is_empty() {
+            return Err(CatanError::Validation("no shard nodes given".to_string()));
+        }
+        let mut ring = Vec::with_capacity(nodes.len() * VIRTUAL_NODES as usize);
+        for (index, node) in nodes.iter().enumerate() {
+            if nodes[..index].iter().any(|other| other.id == node.id) {
+                return Err(CatanError::Validation(format!(
+                    "shard node '{}' is listed twice",
+                    node.id
+                )));
+            }
+            for replica in 0..VIRTUAL_NODES {
+                ring.push((stable_hash(&format!("{}#{replica}", node.id)), index));
+            }
+        }
+        ring.sort_unstable();
+        Ok(Self {
+            local: local.into(),
+            nodes,
+            ring,
+        })
+    }
+
+    /// Router described by `CATAN_SHARD_NODES` and `CATAN_SHARD_ID`; `None` when the
+    /// nodes are unset, so a single instance owns every game
+    pub fn from_env() -> CatanResult<Option<Self>> {
+        let Ok(spec) = std::env::var(SHARD_NODES_ENV) else {
+            return Ok(None);
+        };
+        let local = std::env::var(SHARD_ID_ENV).map_err(|_| {
+            CatanError::Validation(format!(
+                "{SHARD_NODES_ENV} is set but {SHARD_ID_ENV} is not"
+            ))
+        })?;
+        Self::new(local, parse_nodes(&spec)?).map(Some)
+    }
+
+    /// Instance that owns `game_id`
+    pub fn owner(&self, game_id: &str) -> &ShardNode {
+        let point = stable_hash(game_id);
+        let at = self.ring.partition_point(|&(p, _)| p < point);
+        let (_, index) = self.ring[at % self.ring.len()];
+        &self.nodes[index]
+    }
+
+    /// Whether this instance owns `game_id`
+    pub fn is_local(&self, game_id: &str) -> bool {
+        self.owner(game_id).id == self.local
+    }
+
+    /// Whether this instance is on the ring at all
+    pub fn owns_games(&self) -> bool {
+        self.nodes.iter().any(|node| node.id == self.local)
+    }
+
+    /// Where a request for `path_and_query` goes when it concerns another instance's game;
+    /// `None` if it is served here
+    pub fn redirect(&self, path_and_query: &str) -> Option<String> {
+        let path = path_and_query.split('?').next().unwrap_or_default();
+        let game_id = game_id_in_path(path)?;
+        if self.is_local(game_id) {
+            return None;
+        }
+        let owner = self.owner(game_id);
+        Some(format!(
+            "{}{path_and_query}",
+            owner.url.trim_end_matches('/')
+        ))
+    }
+}
+
+/// `id=url` pairs separated by commas
+fn parse_nodes(spec: &str) -> CatanResult<Vec<ShardNode>> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once('=') {
+            Some((id, url)) if !id.trim().is_empty() && !url.trim().is_empty() => Ok(ShardNode {
+                id: id.trim().to_string(),
+                url: url.trim().to_string(),
+            }),
+            _ => Err(CatanError::Validation(format!(
+                "invalid shard node '{entry}', expected id=url"
+            ))),
+        })
+        .collect()
+}
+
+/// Game a request path is about: `/games/{id}/...`, `/ws/games/{id}`,
+/// `/admin/games/{id}/...` or `/mcts/analyze/{id}`
+fn game_id_in_path(path: &str) -> Option<&str> {
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let game_id = match segments[..] {
+        ["games", "import", ..] => return None,
+        ["games", game_id, ..]
+        | ["ws" | "admin", "games", game_id, ..]
+        | ["mcts", "analyze", game_id] => game_id,
+        _ => return None,
+    };
+    (!game_id.is_empty()).then_some(game_id)
+}
+
+/// FNV-1a, which unlike the std hasher is the same on every build and instance, with
+/// the MurmurHash3 finalizer so similar keys such as `a#1` and `a#2` spread over the ring
+fn stable_hash(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in text.as_bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^ (hash >> 33)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(ids: &[&str]) -> Vec<ShardNode> {
+        ids.iter()
+            .map(|id| ShardNode {
+                id: id.to_string(),
+                url: format!("http://{id}.internal:8000"),
+            })
+            .collect()
+    }
+
+    fn game_ids() -> Vec<String> {
+        (0..1000).map(|i| format!("game-{i}")).collect()
+    }
+
+    #[test]
+    fn test_games_spread_over_the_nodes_and_move_only_to_a_new_one() {
+        let before = ShardRouter::new("a", nodes(&["a", "b", "c"])).unwrap();
+        let after = ShardRouter::new("a", nodes(&["a", "b", "c", "d"])).unwrap();
+
+        let ids = game_ids();
+        for id in ["a", "b", "c"] {
+            let owned = ids.iter().filter(|g| before.owner(g).id == id).count();
+            assert!((200..470).contains(&owned), "{id} owns {owned} games");
+        }
+        let mut moved = 0;
+        for game_id in &ids {
+            let (old, new) = (before.owner(game_id), after.owner(game_id));
+            if old != new {
+                assert_eq!(new.id, "d", "{game_id} moved between existing nodes");
+                moved += 1;
+            }
+        }
+        assert!((150..350).contains(&moved), "{moved} games moved");
+    }
+
+    #[test]
+    fn test_requests_for_other_nodes_games_redirect_to_their_owner() {
+        let router = ShardRouter::new("a", nodes(&["a", "b"])).unwrap();
+        let ids = game_ids();
+        let local = ids.iter().find(|g| router.is_local(g)).unwrap();
+        let remote = ids.iter().find(|g| !router.is_local(g)).unwrap();
+
+        assert_eq!(router.redirect(&format!("/games/{local}")), None);
+        assert_eq!(
+            router.redirect(&format!("/ws/games/{remote}?actions=mask")),
+            Some(format!(
+                "http://b.internal:8000/ws/games/{remote}?actions=mask"
+            ))
+        );
+        assert!(router
+            .redirect(&format!("/games/{remote}/actions"))
+            .is_some());
+        for path in ["/games", "/games/import", "/ws", "/campaigns/1"] {
+            assert_eq!(router.redirect(path), None, "{path}");
+        }
+
+        let draining = ShardRouter::new("c", nodes(&["a", "b"])).unwrap();
+        assert!(!draining.owns_games());
+        assert!(draining.redirect(&format!("/games/{local}")).is_some());
+    }
+
+    #[test]
+    fn test_node_lists_parse_from_id_url_pairs() {
+        let parsed = parse_nodes(" a=http://a.internal:8000, b=http://b.internal:8000 ");
+        assert_eq!(parsed.unwrap(), nodes(&["a", "b"]));
+        assert!(parse_nodes("a").is_err());
+        assert!(ShardRouter::new("a", nodes(&["a", "a"])).is_err());
+        assert!(ShardRouter::new("a", Vec::new()).is_err());
+    }
+}
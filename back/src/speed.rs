@@ -0,0 +1,280 @@
+// Experimental "speed Catan" rounds. Once the initial placement is done, rolls come on a
+// server timer and every seat builds on the same roll: a shared window opens after each
+// roll, all seats submit the builds and trades they want, and when the window closes the
+// submissions are applied one seat at a time in priority order. Priority starts with the
+// roller and rotates with it, so every seat gets to go first once per lap.
+//
+// A submitted action that is no longer legal when its seat comes up (a spot an earlier
+// seat built on, cards spent by an earlier action) is rejected; the seat's remaining
+// actions still get their chance. In the engine a round is the roller's turn, with
+// `PassBuildPhase` handing the roll to each following seat and the roller ending it.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::actions::PlayerAction;
+use crate::enums::Action;
+use crate::errors::{CatanError, CatanResult, GameError};
+use crate::players::{BotPlayer, ValueFunctionPlayer};
+use crate::state::State;
+
+/// How long seats have to submit after a roll; the window closes early once every
+/// human has submitted
+pub const BUILD_WINDOW: Duration = Duration::from_secs(20);
+/// Pause between resolving a round and the next automatic roll
+pub const ROLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Most actions one seat may queue per round
+pub const MAX_SUBMITTED_ACTIONS: usize = 12;
+
+/// Actions a seat may queue during the build window
+pub fn is_speed_action(action: &PlayerAction) -> bool {
+    matches!(
+        action,
+        PlayerAction::BuildRoad { .. }
+            | PlayerAction::BuildShip { .. }
+            | PlayerAction::BuildSettlement { .. }
+            | PlayerAction::BuildCity { .. }
+            | PlayerAction::BuyDevelopmentCard
+            | PlayerAction::MaritimeTrade { .. }
+    )
+}
+
+/// Whether `state` plays speed rounds right now: the variant is on and the initial
+/// placement, which stays sequential, is over
+pub fn in_speed_phase(state: &State) -> bool {
+    state.get_config().variants.speed_rounds
+        && !state.is_initial_build_phase()
+        && state.winner().is_none()
+}
+
+/// Seats in the order their submissions are applied: the roller, then the table in turn
+/// order
+pub fn priority_order(state: &State, roller: u8) -> Vec<u8> {
+    let seating = state.get_seating_order();
+    let first = seating
+        .iter()
+        .position(|&color| color == roller)
+        .unwrap_or_default();
+    seating[first..]
+        .iter()
+        .chain(&seating[..first])
+        .copied()
+        .collect()
+}
+
+/// What a bot at `color` wants to do with the current roll, planned as if it had the
+/// build phase to itself. Other seats' submissions are not known yet, so some of these
+/// may lose out at resolution.
+pub fn plan_bot_submission(state: &State, color: u8) -> Vec<PlayerAction> {
+    let mut plan = state.clone();
+    for _ in 0..state.get_num_players() {
+        if plan.get_current_color() == color {
+            break;
+        }
+        let current = plan.get_current_color();
+        plan.apply_action(Action::PassBuildPhase { color: current });
+    }
+
+    let bot = ValueFunctionPlayer::new();
+    let mut submitted = Vec::new();
+    while submitted.len() < MAX_SUBMITTED_ACTIONS && plan.winner().is_none() {
+        let options: Vec<Action> = plan
+            .generate_playable_actions()
+            .into_iter()
+            .filter(|action| {
+                matches!(action, Action::EndTurn { .. })
+                    || is_speed_action(&PlayerAction::from(*action))
+            })
+            .collect();
+        let chosen = bot.decide(&plan, &options);
+        if matches!(chosen, Action::EndTurn { .. }) {
+            break;
+        }
+        plan.apply_action(chosen);
+        submitted.push(PlayerAction::from(chosen));
+    }
+    submitted
+}
+
+/// A round as clients see it while its window is open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedWindow {
+    /// Rounds played in this game, counting this one
+    pub round: u32,
+    pub roller: String,
+    pub dice: Option<[u8; 2]>,
+    /// Colors in the order their submissions will be applied
+    pub priority: Vec<String>,
+    /// Colors that have submitted so far
+    pub submitted: Vec<String>,
+    pub closes_in_ms: u64,
+}
+
+/// What became of one submitted action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedOutcome {
+    pub color: String,
+    pub action: PlayerAction,
+    pub applied: bool,
+    /// Why the action was not applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An open build window
+#[derive(Debug, Clone)]
+pub struct SpeedRound {
+    pub round: u32,
+    pub roller: u8,
+    pub dice: Option<[u8; 2]>,
+    pub priority: Vec<u8>,
+    closes_at: Instant,
+    /// Seats the window waits for; bots submit as soon as it opens
+    humans: Vec<u8>,
+    submissions: HashMap<u8, Vec<PlayerAction>>,
+}
+
+impl SpeedRound {
+    /// A window opening at `now`, open for `BUILD_WINDOW`
+    pub fn new(
+        round: u32,
+        roller: u8,
+        dice: Option<[u8; 2]>,
+        priority: Vec<u8>,
+        now: Instant,
+    ) -> Self {
+        Self {
+            round,
+            roller,
+            dice,
+            priority,
+            closes_at: now + BUILD_WINDOW,
+            humans: Vec::new(),
+            submissions: HashMap::new(),
+        }
+    }
+
+    /// Wait for `color` to submit before closing the window early
+    pub fn expect_human(&mut self, color: u8) {
+        self.humans.push(color);
+    }
+
+    /// Queue `actions` for `color`, replacing anything it submitted before
+    pub fn submit(&mut self, color: u8, actions: Vec<PlayerAction>) -> CatanResult<()> {
+        if let Some(action) = actions.iter().find(|action| !is_speed_action(action)) {
+            return Err(CatanError::Game(GameError::rule_violation(format!(
+                "{} cannot be queued in a speed round",
+                action.kind()
+            ))));
+        }
+        if actions.len() > MAX_SUBMITTED_ACTIONS {
+            return Err(CatanError::Validation(format!(
+                "{} actions submitted, at most {MAX_SUBMITTED_ACTIONS} per round",
+                actions.len()
+            )));
+        }
+        self.submissions.insert(color, actions);
+        Ok(())
+    }
+
+    /// Whether the window can close: time is up or every human has submitted
+    pub fn is_ready(&self, now: Instant) -> bool {
+        now >= self.closes_at
+            || self
+                .humans
+                .iter()
+                .all(|color| self.submissions.contains_key(color))
+    }
+
+    /// Submissions per seat, in priority order; seats that sent nothing get an empty list
+    pub fn into_queue(mut self) -> Vec<(u8, Vec<PlayerAction>)> {
+        self.priority
+            .iter()
+            .map(|color| (*color, self.submissions.remove(color).unwrap_or_default()))
+            .collect()
+    }
+
+    pub fn window(&self, now: Instant, color_name: impl Fn(u8) -> String) -> SpeedWindow {
+        let mut submitted: Vec<u8> = self.submissions.keys().copied().collect();
+        submitted.sort_unstable();
+        SpeedWindow {
+            round: self.round,
+            roller: color_name(self.roller),
+            dice: self.dice,
+            priority: self
+                .priority
+                .iter()
+                .map(|&color| color_name(color))
+                .collect(),
+            submitted: submitted.into_iter().map(&color_name).collect(),
+            closes_in_ms: self.closes_at.saturating_duration_since(now).as_millis() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{GameConfiguration, Seating, VariantConfig};
+    use crate::map_instance::MapInstance;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_priority_starts_with_the_roller() {
+        let config = GameConfiguration::builder()
+            .seating(Seating::Explicit(vec![2, 0, 3, 1]))
+            .build()
+            .unwrap();
+        let map_instance = MapInstance::shared(&config.map_type, 0);
+        let state = State::new(Arc::new(config), map_instance);
+        assert_eq!(priority_order(&state, 3), vec![3, 1, 2, 0]);
+        assert_eq!(priority_order(&state, 2), vec![2, 0, 3, 1]);
+    }
+
+    #[test]
+    fn test_round_waits_for_humans_and_refuses_other_actions() {
+        let opened = Instant::now();
+        let mut round = SpeedRound::new(1, 0, Some([3, 4]), vec![0, 1], opened);
+        round.expect_human(1);
+        round.submit(0, Vec::new()).unwrap();
+        assert!(!round.is_ready(opened));
+        assert!(round.is_ready(opened + BUILD_WINDOW));
+
+        assert!(round.submit(1, vec![PlayerAction::Roll]).is_err());
+        let build = PlayerAction::BuildSettlement { node_id: 7 };
+        round.submit(1, vec![build.clone()]).unwrap();
+        assert!(round.is_ready(opened));
+        assert_eq!(round.into_queue(), vec![(0, vec![]), (1, vec![build])]);
+    }
+
+    #[test]
+    fn test_bots_plan_only_speed_actions() {
+        let config = GameConfiguration::builder()
+            .num_players(2)
+            .variants(VariantConfig {
+                speed_rounds: true,
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        let map_instance = MapInstance::shared(&config.map_type, 0);
+        let mut state = State::new_with_seed(Arc::new(config), map_instance, 4);
+        let bot = ValueFunctionPlayer::new();
+        while state.is_initial_build_phase() {
+            let action = bot.decide(&state, &state.generate_playable_actions());
+            state.apply_action(action);
+        }
+        assert!(in_speed_phase(&state));
+        let roller = state.get_current_color();
+        state.apply_action(Action::Roll {
+            color: roller,
+            dice_opt: Some((3, 3)),
+        });
+
+        let other = 1 - roller;
+        let plan = plan_bot_submission(&state, other);
+        assert!(plan.iter().all(is_speed_action));
+        assert!(plan.len() <= MAX_SUBMITTED_ACTIONS);
+    }
+}
@@ -4,17 +4,43 @@ use log;
 use log::LevelFilter;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 
 use crate::actions::{GameEvent, GameId, PlayerAction};
-use crate::application::GameService;
-use crate::errors::CatanResult;
-use crate::game::Game;
+use crate::application::{GameService, TableOptions};
+use crate::auto_pass::AutoPass;
+use crate::bot_loop::{self, BotScheduling, BotStep};
+use crate::campaign::CampaignSummary;
+use crate::compact_actions::{ActionsFormat, CompactActions};
+use crate::enums::GameConfiguration;
+use crate::errors::{CatanError, CatanResult, ErrorCode, GameError};
+use crate::game::{BuildableMasks, Game};
+use crate::pace::Pace;
+use crate::players::win_model::FITTED_WIN_MODEL;
+use crate::speed::{self, SpeedOutcome, SpeedWindow};
 use crate::state::State;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 
+/// Games a connection follows at most
+const MAX_SUBSCRIPTIONS: usize = 16;
+
+/// Games one connection follows
+type Subscriptions = Arc<RwLock<HashSet<GameId>>>;
+/// Replies meant for one connection only, with the game they are about
+type DirectSender = mpsc::UnboundedSender<(Option<GameId>, WsMessage)>;
+
+/// The fields of an incoming message needed to route it. Every message may carry a
+/// `game_id`; outgoing ones always say which game they are about.
+#[derive(Debug, Default, Deserialize)]
+struct Envelope {
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    game_id: Option<GameId>,
+}
+
 /// WebSocket message types for client-server communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -22,17 +48,36 @@ pub enum WsMessage {
     #[serde(rename = "game_state")]
     GameState { game: Game },
 
+    // `buildable` marks where the player to move may build, so clients need not derive it
     #[serde(rename = "game_updated")]
-    GameUpdated { game: Game },
+    GameUpdated {
+        game: Game,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        buildable: Option<BuildableMasks>,
+    },
 
+    // `seq` is a client-chosen number above the game's `last_action_seq`; resending a
+    // message with the same number (e.g. after a network retry) applies it at most once.
+    // `version` is the game version the action was chosen against; stale ones are refused.
     #[serde(rename = "player_action")]
     PlayerAction {
         action: PlayerAction, // Accept enum format directly: {Roll: {}}
+        seq: u64,
+        version: u64,
     },
 
+    // A player_action chosen against an outdated game; `version` is the current one
+    #[serde(rename = "stale_action")]
+    StaleAction { seq: u64, version: u64 },
+
     #[serde(rename = "get_game_state")]
     GetGameState,
 
+    // Text command for chat-driven play ("build settlement at 23"), resolved to the one
+    // legal action it describes
+    #[serde(rename = "command")]
+    Command { text: String },
+
     // ✅ REMOVED: BotAction - Bot actions are now automatic, not triggered by frontend
     #[serde(rename = "action_result")]
     ActionResult {
@@ -41,11 +86,21 @@ pub enum WsMessage {
         events: Vec<GameEvent>,
     },
 
+    // `code` says what kind of failure this is; `details` carries the error's fields
     #[serde(rename = "error")]
-    Error { message: String },
+    Error {
+        code: ErrorCode,
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        details: Option<serde_json::Value>,
+    },
 
     #[serde(rename = "greeting")]
-    Greeting { message: String },
+    Greeting {
+        message: String,
+        engine_version: String,
+        rules_revision: u32,
+    },
 
     #[serde(rename = "bot_thinking")]
     BotThinking { player_id: String },
@@ -54,6 +109,8 @@ pub enum WsMessage {
     CreateGame {
         mode: String, // 'HUMAN_VS_CATANATRON' | 'RANDOM_BOTS' | 'CATANATRON_BOTS'
         num_players: u8,
+        #[serde(flatten)]
+        table: TableOptions,
     },
 
     #[serde(rename = "game_created")]
@@ -76,6 +133,42 @@ pub enum WsMessage {
         #[serde(skip_serializing_if = "Option::is_none")]
         request_id: Option<String>,
     },
+
+    // Follow another game on this connection; its state comes back as game_state
+    #[serde(rename = "subscribe")]
+    Subscribe { game_id: GameId },
+
+    #[serde(rename = "unsubscribe")]
+    Unsubscribe { game_id: GameId },
+
+    // A subscribed game lives on another server instance; follow it at `url` instead
+    #[serde(rename = "redirect")]
+    Redirect { game_id: GameId, url: String },
+
+    // Campaign standings after a campaign game finished, sent on that game's channel
+    #[serde(rename = "campaign_standings")]
+    CampaignStandings { summary: CampaignSummary },
+
+    // Speed rounds: the human seat's builds for the open window, replacing earlier ones
+    #[serde(rename = "speed_submit")]
+    SpeedSubmit { actions: Vec<PlayerAction> },
+
+    // Auto-pass for the human seat: the setting to use, or null to turn it off
+    #[serde(rename = "set_auto_pass")]
+    SetAutoPass { auto_pass: Option<AutoPass> },
+
+    #[serde(rename = "speed_round_opened")]
+    SpeedRoundOpened { window: SpeedWindow },
+
+    // Every submitted action in the order it was tried, with the ones that lost out
+    #[serde(rename = "speed_round_resolved")]
+    SpeedRoundResolved { outcomes: Vec<SpeedOutcome> },
+}
+
+/// A game's running bot loop
+struct BotTask {
+    cancel: broadcast::Sender<()>,
+    handle: tokio::task::JoinHandle<()>,
 }
 
 // Convert array action format to PlayerAction enum
@@ -91,7 +184,54 @@ pub struct WebSocketService {
     // Track active connections per game
     active_connections: Arc<RwLock<HashMap<GameId, HashSet<String>>>>,
     // Track bot simulation tasks that can be cancelled
-    bot_tasks: Arc<RwLock<HashMap<GameId, tokio::sync::broadcast::Sender<()>>>>,
+    bot_tasks: Arc<RwLock<HashMap<GameId, BotTask>>>,
+    scheduling: BotScheduling,
+}
+
+impl WsMessage {
+    /// Update message for `game`, with the buildable spots of the player to move
+    pub fn game_updated(game: Game) -> Self {
+        if board_check_enabled() {
+            check_board(&game);
+        }
+        let buildable = game.buildable_masks();
+        WsMessage::GameUpdated { game, buildable }
+    }
+
+    /// Error message for a failed request; `context` says what was being attempted
+    pub fn failure(context: &str, error: &CatanError) -> Self {
+        WsMessage::Error {
+            code: error.code(),
+            message: format!("{context}: {error}"),
+            details: error.details(),
+        }
+    }
+}
+
+/// Environment variable that turns on the board check: with it set, every game_updated
+/// also rebuilds the board from the game's action log and logs where the board being
+/// sent disagrees. Costs a full replay per update, so debugging only.
+pub const BOARD_CHECK_ENV: &str = "CATAN_BOARD_CHECK";
+
+fn board_check_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var_os(BOARD_CHECK_ENV).is_some())
+}
+
+fn check_board(game: &Game) {
+    match game.board_divergence() {
+        Ok(Some(differences)) if !differences.is_empty() => {
+            log::error!(
+                "🧩 Board of game {} (version {}) diverges from its action log in {} places: {:?}",
+                game.id,
+                game.version,
+                differences.len(),
+                &differences[..differences.len().min(20)]
+            );
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("🧩 Could not rebuild the board of game {}: {}", game.id, e),
+    }
 }
 
 impl WebSocketService {
@@ -103,130 +243,135 @@ impl WebSocketService {
             broadcaster,
             active_connections: Arc::new(RwLock::new(HashMap::new())),
             bot_tasks: Arc::new(RwLock::new(HashMap::new())),
+            scheduling: BotScheduling::default(),
         }
     }
 
-    /// Handle a new WebSocket connection
-    pub async fn handle_connection(&self, socket: WebSocket, game_id: String) {
+    /// Drive bot turns with `scheduling` instead of a background task per game
+    pub fn with_scheduling(mut self, scheduling: BotScheduling) -> Self {
+        self.scheduling = scheduling;
+        self
+    }
+
+    /// Play one bot step of `game_id` and broadcast it. The way bots move under
+    /// `BotScheduling::Stepped`; speed rounds are left to the background loop.
+    pub async fn step_bots(&self, game_id: &str) -> BotStep {
+        bot_loop::step(&self.game_service, &self.broadcaster, game_id).await
+    }
+
+    /// Handle a new WebSocket connection. `game_id` is the game in the connection's URL,
+    /// followed from the start; `subscribe` follows more games on the same socket.
+    /// `actions_format` is how playable actions are sent in this connection's snapshots.
+    pub async fn handle_connection(
+        &self,
+        socket: WebSocket,
+        game_id: Option<GameId>,
+        actions_format: ActionsFormat,
+    ) {
         // Generate a unique connection ID
         let connection_id = format!("conn_{}", uuid::Uuid::new_v4());
         log::info!(
-            "🔌 WebSocket connected: {} (game {})",
+            "🔌 WebSocket connected: {} (game {:?})",
             connection_id,
             game_id
         );
 
-        // Add this connection to our tracking
-        self.add_connection(&game_id, &connection_id).await;
-
         // Split socket for concurrent read/write
         let (mut sender, mut receiver) = socket.split();
 
         // Send greeting
         let greeting = WsMessage::Greeting {
             message: "Connected to Catan game".to_string(),
+            engine_version: crate::VERSION.to_string(),
+            rules_revision: crate::RULES_REVISION,
         };
 
         if let Err(e) = self.send_message(&mut sender, &greeting).await {
             log::error!("❌ Failed to send greeting: {}", e);
-            self.remove_connection(&game_id, &connection_id).await;
-            return;
-        }
-
-        // Check if game exists and send initial state
-        if !self.game_service.game_exists(&game_id).await {
-            let error = WsMessage::Error {
-                message: format!("Game {game_id} not found"),
-            };
-            let _ = self.send_message(&mut sender, &error).await;
-            self.remove_connection(&game_id, &connection_id).await;
             return;
         }
 
-        // Send initial game state
-        match self.game_service.get_game(&game_id).await {
-            Ok(game) => {
-                let state_msg = WsMessage::GameState { game };
-                if let Err(e) = self.send_message(&mut sender, &state_msg).await {
-                    log::error!("❌ Failed to send initial game state: {}", e);
-                    self.remove_connection(&game_id, &connection_id).await;
-                    return;
-                }
-            }
-            Err(e) => {
-                log::error!("❌ Failed to get initial game state: {}", e);
-                self.remove_connection(&game_id, &connection_id).await;
-                return;
-            }
-        }
-
         // Subscribe to game updates FIRST
         let mut game_updates = self.broadcaster.subscribe();
-
-        // Start bot gameplay only if this is the first connection for this game
-        let should_start_bots = {
-            let connections = self.active_connections.read().await;
-            connections
-                .get(&game_id)
-                .is_some_and(|conns| conns.len() == 1)
-        };
-
-        if should_start_bots {
-            log::info!("🤖 Starting bots for game {}", game_id);
-            self.start_bot_simulation(&game_id).await;
+        let subscriptions: Subscriptions = Arc::new(RwLock::new(HashSet::new()));
+        let (direct_tx, mut direct_rx) = mpsc::unbounded_channel();
+
+        // The URL's game must exist; the connection is closed otherwise
+        if let Some(game_id) = &game_id {
+            if let Err(e) = self
+                .subscribe(&connection_id, &subscriptions, game_id, &direct_tx)
+                .await
+            {
+                log::error!("❌ Failed to follow game {}: {}", game_id, e);
+                let error = Self::with_game_id(
+                    Some(game_id),
+                    &WsMessage::failure("Failed to open game", &e),
+                );
+                let _ = Self::send_value_static(&mut sender, &error).await;
+                return;
+            }
         }
 
-        // Task to forward game updates to this client
-        let game_id_for_updates = game_id.clone();
+        // Task to forward updates of followed games, and replies to this connection
+        let subscriptions_for_updates = subscriptions.clone();
         let connection_id_for_updates = connection_id.clone();
         let mut update_task = tokio::spawn(async move {
-            while let Ok((update_game_id, message)) = game_updates.recv().await {
-                if update_game_id == game_id_for_updates {
-                    if let Err(e) = Self::send_message_static(&mut sender, &message).await {
-                        log::error!(
-                            "Failed to send message to connection {}: {:?}",
-                            connection_id_for_updates,
-                            e
-                        );
-                        break; // Client disconnected
+            loop {
+                let (update_game_id, mut message) = tokio::select! {
+                    update = game_updates.recv() => {
+                        let Ok((update_game_id, message)) = update else {
+                            break;
+                        };
+                        if !subscriptions_for_updates.read().await.contains(&update_game_id) {
+                            continue;
+                        }
+                        (Some(update_game_id), message)
                     }
+                    Some(direct) = direct_rx.recv() => direct,
+                };
+                let compact = Self::compact_playable_actions(&mut message, actions_format);
+                let mut value = Self::with_game_id(update_game_id.as_deref(), &message);
+                if let (Some(compact), Some(game)) = (
+                    compact,
+                    value
+                        .get_mut("game")
+                        .and_then(serde_json::Value::as_object_mut),
+                ) {
+                    let compact = serde_json::to_value(compact).unwrap_or_default();
+                    game.insert("compact_actions".to_string(), compact);
+                }
+                if let Err(e) = Self::send_value_static(&mut sender, &value).await {
+                    log::error!(
+                        "Failed to send message to connection {}: {:?}",
+                        connection_id_for_updates,
+                        e
+                    );
+                    break; // Client disconnected
                 }
             }
         });
 
         // Task to handle incoming messages
-        let game_service = self.game_service.clone();
-        let broadcaster = self.broadcaster.clone();
-        let game_id_for_messages = game_id.clone();
         let connection_id_for_messages = connection_id.clone();
+        let subscriptions_for_messages = subscriptions.clone();
         let service_for_messages = self.clone();
 
         let mut message_task = tokio::spawn(async move {
             while let Some(Ok(message)) = receiver.next().await {
                 match message {
                     Message::Text(text) => {
-                        if let Err(e) = Self::handle_text_message(
-                            &game_service,
-                            &broadcaster,
-                            &game_id_for_messages,
-                            text.to_string(),
-                            &service_for_messages,
-                        )
-                        .await
-                        {
-                            log::error!(
-                                "Error handling message from connection {}: {}",
-                                connection_id_for_messages,
-                                e
-                            );
-                        }
+                        service_for_messages
+                            .route_text_message(
+                                &connection_id_for_messages,
+                                &subscriptions_for_messages,
+                                game_id.as_deref(),
+                                &direct_tx,
+                                text.to_string(),
+                            )
+                            .await;
                     }
                     Message::Close(_) => {
-                        log::info!(
-                            "WebSocket connection {} closed for game {}",
-                            connection_id_for_messages,
-                            game_id_for_messages
-                        );
+                        log::info!("WebSocket connection {} closed", connection_id_for_messages);
                         break;
                     }
                     _ => {
@@ -247,14 +392,162 @@ impl WebSocketService {
         }
 
         // Connection cleanup
-        self.remove_connection(&game_id, &connection_id).await;
+        let followed: Vec<GameId> = subscriptions.read().await.iter().cloned().collect();
+        for game_id in &followed {
+            self.remove_connection(game_id, &connection_id).await;
+        }
         log::info!(
-            "WebSocket connection {} terminated for game {}",
+            "WebSocket connection {} terminated (games {:?})",
             connection_id,
-            game_id
+            followed
         );
     }
 
+    /// Dispatch a client message to the game it names, or by default to the game in the
+    /// connection's URL (or its only followed game). `subscribe` and `unsubscribe` are
+    /// handled here; anything else needs a followed game, except `create_game`.
+    async fn route_text_message(
+        &self,
+        connection_id: &str,
+        subscriptions: &Subscriptions,
+        url_game_id: Option<&str>,
+        direct_tx: &DirectSender,
+        text: String,
+    ) {
+        // Malformed messages fall through to handle_text_message, which reports them
+        let envelope: Envelope = serde_json::from_str(&text).unwrap_or_default();
+        match (envelope.kind.as_str(), &envelope.game_id) {
+            ("subscribe", Some(game_id)) => {
+                let owner_url = self
+                    .game_service
+                    .router()
+                    .and_then(|router| router.redirect(&format!("/ws/games/{game_id}")));
+                if let Some(url) = owner_url {
+                    let redirect = WsMessage::Redirect {
+                        game_id: game_id.clone(),
+                        url,
+                    };
+                    let _ = direct_tx.send((Some(game_id.clone()), redirect));
+                } else if let Err(e) = self
+                    .subscribe(connection_id, subscriptions, game_id, direct_tx)
+                    .await
+                {
+                    let error = WsMessage::failure("Subscribe failed", &e);
+                    let _ = direct_tx.send((Some(game_id.clone()), error));
+                }
+                return;
+            }
+            ("unsubscribe", Some(game_id)) => {
+                if subscriptions.write().await.remove(game_id) {
+                    self.remove_connection(game_id, connection_id).await;
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        let followed = subscriptions.read().await;
+        let only_followed = (followed.len() == 1)
+            .then(|| followed.iter().next().cloned())
+            .flatten();
+        let target = envelope
+            .game_id
+            .or_else(|| url_game_id.map(str::to_string))
+            .or(only_followed);
+        let allowed = envelope.kind == "create_game"
+            || target.as_ref().is_some_and(|id| followed.contains(id));
+        drop(followed);
+        if !allowed {
+            let error = CatanError::Validation(match &target {
+                Some(id) => format!("this connection does not follow game {id}"),
+                None => "messages need a game_id on this connection".to_string(),
+            });
+            let _ = direct_tx.send((target, WsMessage::failure("Message refused", &error)));
+            return;
+        }
+
+        let game_id = target.unwrap_or_default();
+        if let Err(e) =
+            Self::handle_text_message(&self.game_service, &self.broadcaster, &game_id, text, self)
+                .await
+        {
+            log::error!(
+                "Error handling message from connection {}: {}",
+                connection_id,
+                e
+            );
+        }
+    }
+
+    /// Follow `game_id` on a connection: send its state and start its bots if this is
+    /// the game's first connection
+    async fn subscribe(
+        &self,
+        connection_id: &str,
+        subscriptions: &Subscriptions,
+        game_id: &str,
+        direct_tx: &DirectSender,
+    ) -> CatanResult<()> {
+        let game = self.game_service.get_game(game_id).await?;
+        {
+            let mut followed = subscriptions.write().await;
+            if !followed.contains(game_id) && followed.len() >= MAX_SUBSCRIPTIONS {
+                return Err(CatanError::Validation(format!(
+                    "a connection follows at most {MAX_SUBSCRIPTIONS} games"
+                )));
+            }
+            if !followed.insert(game_id.to_string()) {
+                // Already followed; just resend the state
+                let _ = direct_tx.send((Some(game_id.to_string()), WsMessage::GameState { game }));
+                return Ok(());
+            }
+        }
+        self.add_connection(game_id, connection_id).await;
+        let _ = direct_tx.send((Some(game_id.to_string()), WsMessage::GameState { game }));
+
+        // Start bot gameplay only if this is the first connection for this game
+        let should_start_bots = {
+            let connections = self.active_connections.read().await;
+            connections
+                .get(game_id)
+                .is_some_and(|conns| conns.len() == 1)
+        };
+        if should_start_bots {
+            log::info!("🤖 Starting bots for game {}", game_id);
+            self.start_bot_simulation(game_id).await;
+        }
+        Ok(())
+    }
+
+    /// Take the playable actions out of a snapshot's game and return them in table form,
+    /// for connections that asked for a compact format
+    fn compact_playable_actions(
+        message: &mut WsMessage,
+        format: ActionsFormat,
+    ) -> Option<CompactActions> {
+        let (WsMessage::GameState { game }
+        | WsMessage::GameUpdated { game, .. }
+        | WsMessage::GameCreated { game, .. }) = message
+        else {
+            return None;
+        };
+        let compact = game.compact_actions(format)?;
+        game.current_playable_actions.clear();
+        Some(compact)
+    }
+
+    /// `message` as JSON with the game it is about in `game_id`, unless it names one
+    /// itself
+    fn with_game_id(game_id: Option<&str>, message: &WsMessage) -> serde_json::Value {
+        let mut value = serde_json::to_value(message).unwrap_or_default();
+        if let (Some(game_id), Some(fields)) = (game_id, value.as_object_mut()) {
+            fields
+                .entry("game_id")
+                .or_insert_with(|| game_id.to_string().into());
+        }
+        value
+    }
+
     /// Add a connection to tracking
     async fn add_connection(&self, game_id: &str, connection_id: &str) {
         let mut connections = self.active_connections.write().await;
@@ -288,20 +581,39 @@ impl WebSocketService {
         };
 
         if should_stop_bots {
-            self.stop_bot_simulation(game_id).await;
+            let bot_task = self.stop_bot_simulation(game_id).await;
+            self.hibernate_when_idle(game_id, bot_task);
         }
     }
 
+    /// Hibernate a game nobody follows any more, once its bot loop has wound down so no
+    /// bot move lands after the game is written
+    fn hibernate_when_idle(&self, game_id: &str, bot_task: Option<tokio::task::JoinHandle<()>>) {
+        let service = self.clone();
+        let game_id = game_id.to_string();
+        tokio::spawn(async move {
+            if let Some(bot_task) = bot_task {
+                let _ = bot_task.await;
+            }
+            // Holding the connections lock keeps new followers out until the game is written;
+            // one arriving later wakes it again
+            let connections = service.active_connections.read().await;
+            if connections.contains_key(&game_id) {
+                return;
+            }
+            if let Err(e) = service.game_service.hibernate_game(&game_id).await {
+                log::error!("Failed to hibernate game {game_id}: {e}");
+            }
+        });
+    }
+
     /// Start bot simulation for a game
     async fn start_bot_simulation(&self, game_id: &str) {
-        // Create a cancellation channel for this game's bots
-        let (cancel_tx, _) = broadcast::channel(1);
-
-        // Store the cancellation sender
-        {
-            let mut bot_tasks = self.bot_tasks.write().await;
-            bot_tasks.insert(game_id.to_string(), cancel_tx.clone());
+        if self.scheduling == BotScheduling::Stepped {
+            return;
         }
+        // Create a cancellation channel for this game's bots
+        let (cancel_tx, mut cancel_rx) = broadcast::channel(1);
 
         // Start the bot processing task
         let game_service = self.game_service.clone();
@@ -309,11 +621,12 @@ impl WebSocketService {
         let game_id_owned = game_id.to_string();
         let active_connections = self.active_connections.clone();
 
-        tokio::spawn(async move {
-            let mut cancel_rx = cancel_tx.subscribe();
-
+        let handle = tokio::spawn(async move {
             // Small delay to ensure WebSocket subscription is fully established
-            tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+            game_service
+                .clock()
+                .sleep(tokio::time::Duration::from_millis(1000))
+                .await;
 
             // Process bot turns with cancellation support
             Self::process_bot_turns_with_cancellation(
@@ -325,15 +638,136 @@ impl WebSocketService {
             )
             .await;
         });
+
+        // Store the cancellation sender
+        let task = BotTask {
+            cancel: cancel_tx,
+            handle,
+        };
+        self.bot_tasks
+            .write()
+            .await
+            .insert(game_id.to_string(), task);
     }
 
-    /// Stop bot simulation for a game
-    async fn stop_bot_simulation(&self, game_id: &str) {
+    /// Stop bot simulation for a game, returning its task to wait on if it had one
+    async fn stop_bot_simulation(&self, game_id: &str) -> Option<tokio::task::JoinHandle<()>> {
         let mut bot_tasks = self.bot_tasks.write().await;
-        if let Some(cancel_tx) = bot_tasks.remove(game_id) {
-            // Send cancellation signal (ignore if no receivers)
-            let _ = cancel_tx.send(());
-            log::info!("🛑 Stopped bot simulation for game {}", game_id);
+        let task = bot_tasks.remove(game_id)?;
+        // Send cancellation signal (ignore if no receivers)
+        let _ = task.cancel.send(());
+        log::info!("🛑 Stopped bot simulation for game {}", game_id);
+        Some(task.handle)
+    }
+
+    /// Apply a human player's action and broadcast the result. `stamp` is the message's
+    /// (sequence number, game version), if it has one. Actions carrying a sequence number
+    /// the game already accepted, or made against an outdated version, are not applied
+    /// and are answered with the current state so the client can resync.
+    async fn handle_player_action(
+        game_service: &GameService,
+        broadcaster: &broadcast::Sender<(GameId, WsMessage)>,
+        game_id: &str,
+        action: PlayerAction,
+        stamp: Option<(u64, u64)>,
+        service: &WebSocketService,
+    ) {
+        log::info!("🎯 Processing action for game {}: {:?}", game_id, action);
+
+        // Use the PlayerAction enum directly - no conversion needed!
+        log::info!("✅ Received PlayerAction enum: {:?}", action);
+
+        // Resolve the acting player to the current player at the moment of receipt
+        let (acting_player_id, bot_to_move) = match game_service.get_game(game_id).await {
+            Ok(game) => {
+                let idx = game.current_player_index;
+                let bot_to_move = game
+                    .current_color
+                    .as_ref()
+                    .is_some_and(|color| game.bot_colors.contains(color));
+                let player_id = game
+                    .players
+                    .get(idx)
+                    .map(|p| p.id.clone())
+                    .unwrap_or_else(|| "player_0".to_string());
+                (player_id, bot_to_move)
+            }
+            Err(_) => ("player_0".to_string(), false),
+        };
+
+        // Bots move on their own; a human's action on a bot's turn would be played for it
+        if bot_to_move {
+            let error = CatanError::Game(GameError::not_player_turn(&acting_player_id, "human"));
+            let _ = broadcaster.send((
+                game_id.to_string(),
+                WsMessage::failure("Action refused", &error),
+            ));
+            return;
+        }
+
+        // Process the action through the game service
+        let result = match stamp {
+            Some((seq, version)) => {
+                game_service
+                    .process_sequenced_action(game_id, &acting_player_id, seq, version, action)
+                    .await
+            }
+            None => game_service
+                .process_action(game_id, &acting_player_id, action)
+                .await
+                .map(Some),
+        };
+        match result {
+            Ok(None) => {
+                if let Ok(game) = game_service.get_game(game_id).await {
+                    let update_msg = WsMessage::game_updated(game);
+                    let _ = broadcaster.send((game_id.to_string(), update_msg));
+                }
+            }
+            Ok(Some(events)) => {
+                log::info!("✅ Action processed successfully");
+
+                // Send action result
+                let result_msg = WsMessage::ActionResult {
+                    success: true,
+                    message: "Action processed".to_string(),
+                    events: events.clone(),
+                };
+                let _ = broadcaster.send((game_id.to_string(), result_msg));
+
+                // Send updated game state
+                if let Ok(updated_game) = game_service.get_game(game_id).await {
+                    let update_msg = WsMessage::game_updated(updated_game);
+                    let _ = broadcaster.send((game_id.to_string(), update_msg));
+                }
+
+                // Restart bot simulation after human action (only if connections exist)
+                if service.has_active_connections(game_id).await {
+                    log::debug!(
+                        "🔄 Restarting bot simulation after human action for game {}",
+                        game_id
+                    );
+                    service.start_bot_simulation(game_id).await;
+                }
+            }
+            Err(CatanError::Game(GameError::StaleVersion { current, .. })) => {
+                let seq = stamp.map(|(seq, _)| seq).unwrap_or_default();
+                log::info!("⏳ Stale action #{seq} for game {game_id} (now version {current})");
+                let stale_msg = WsMessage::StaleAction {
+                    seq,
+                    version: current,
+                };
+                let _ = broadcaster.send((game_id.to_string(), stale_msg));
+                if let Ok(game) = game_service.get_game(game_id).await {
+                    let update_msg = WsMessage::game_updated(game);
+                    let _ = broadcaster.send((game_id.to_string(), update_msg));
+                }
+            }
+            Err(e) => {
+                log::error!("❌ Action processing failed: {}", e);
+                let error_msg = WsMessage::failure("Action failed", &e);
+                let _ = broadcaster.send((game_id.to_string(), error_msg));
+            }
         }
     }
 
@@ -375,60 +809,65 @@ impl WebSocketService {
         })?;
 
         match ws_message {
-            WsMessage::PlayerAction { action } => {
-                log::info!("🎯 Processing action for game {}: {:?}", game_id, action);
-
-                // Use the PlayerAction enum directly - no conversion needed!
-                log::info!("✅ Received PlayerAction enum: {:?}", action);
-
-                // Resolve the acting player to the current player at the moment of receipt
-                let acting_player_id = match game_service.get_game(game_id).await {
-                    Ok(game) => {
-                        let idx = game.current_player_index;
-                        game.players
-                            .get(idx)
-                            .map(|p| p.id.clone())
-                            .unwrap_or_else(|| "player_0".to_string())
+            WsMessage::PlayerAction {
+                action,
+                seq,
+                version,
+            } => {
+                Self::handle_player_action(
+                    game_service,
+                    broadcaster,
+                    game_id,
+                    action,
+                    Some((seq, version)),
+                    service,
+                )
+                .await;
+            }
+            WsMessage::SpeedSubmit { actions } => {
+                let result = match Self::human_player_id(game_service, game_id).await {
+                    Ok(human) => {
+                        game_service
+                            .submit_speed_actions(game_id, &human, actions)
+                            .await
                     }
-                    Err(_) => "player_0".to_string(),
+                    Err(e) => Err(e),
                 };
-
-                // Process the action through the game service
-                match game_service
-                    .process_action(game_id, &acting_player_id, action)
-                    .await
-                {
-                    Ok(events) => {
-                        log::info!("✅ Action processed successfully");
-
-                        // Send action result
-                        let result_msg = WsMessage::ActionResult {
-                            success: true,
-                            message: "Action processed".to_string(),
-                            events: events.clone(),
-                        };
-                        let _ = broadcaster.send((game_id.to_string(), result_msg));
-
-                        // Send updated game state
-                        if let Ok(updated_game) = game_service.get_game(game_id).await {
-                            let update_msg = WsMessage::GameUpdated { game: updated_game };
-                            let _ = broadcaster.send((game_id.to_string(), update_msg));
-                        }
-
-                        // Restart bot simulation after human action (only if connections exist)
-                        if service.has_active_connections(game_id).await {
-                            log::debug!(
-                                "🔄 Restarting bot simulation after human action for game {}",
-                                game_id
-                            );
-                            service.start_bot_simulation(game_id).await;
-                        }
+                let msg = match result {
+                    Ok(window) => WsMessage::SpeedRoundOpened { window },
+                    Err(e) => WsMessage::failure("Speed submission refused", &e),
+                };
+                let _ = broadcaster.send((game_id.to_string(), msg));
+            }
+            WsMessage::SetAutoPass { auto_pass } => {
+                let result = match Self::human_player_id(game_service, game_id).await {
+                    Ok(human) => game_service.set_auto_pass(game_id, &human, auto_pass).await,
+                    Err(e) => Err(e),
+                };
+                match result {
+                    // A pass may be due right away
+                    Ok(()) => service.start_bot_simulation(game_id).await,
+                    Err(e) => {
+                        let error_msg = WsMessage::failure("Auto-pass not changed", &e);
+                        let _ = broadcaster.send((game_id.to_string(), error_msg));
+                    }
+                }
+            }
+            WsMessage::Command { text } => {
+                match game_service.resolve_command(game_id, &text).await {
+                    Ok(action) => {
+                        Self::handle_player_action(
+                            game_service,
+                            broadcaster,
+                            game_id,
+                            action,
+                            None,
+                            service,
+                        )
+                        .await;
                     }
                     Err(e) => {
-                        log::error!("❌ Action processing failed: {}", e);
-                        let error_msg = WsMessage::Error {
-                            message: format!("Action failed: {e}"),
-                        };
+                        let error_msg = WsMessage::failure("Command failed", &e);
                         let _ = broadcaster.send((game_id.to_string(), error_msg));
                     }
                 }
@@ -473,15 +912,17 @@ impl WebSocketService {
                     }
                     Err(e) => {
                         log::error!("❌ Failed to get game state: {}", e);
-                        let error_msg = WsMessage::Error {
-                            message: format!("Failed to get game: {e}"),
-                        };
+                        let error_msg = WsMessage::failure("Failed to get game", &e);
                         let _ = broadcaster.send((game_id.to_string(), error_msg));
                     }
                 }
             }
             // ✅ REMOVED: BotAction handler - Bot actions are now automatic
-            WsMessage::CreateGame { mode, num_players } => {
+            WsMessage::CreateGame {
+                mode,
+                num_players,
+                table,
+            } => {
                 log::info!(
                     "🎮 Creating new game: mode={}, players={}",
                     mode,
@@ -491,12 +932,21 @@ impl WebSocketService {
                 // Determine bot type from mode
                 let bot_type = match mode.as_str() {
                     "HUMAN_VS_CATANATRON" => "mcts",
+                    "HUMAN_VS_HARD" => "human_hard",
                     "RANDOM_BOTS" => "random",
                     "CATANATRON_BOTS" => "mcts",
                     _ => "random",
                 };
 
-                match game_service.create_game(num_players, bot_type).await {
+                match game_service
+                    .create_game(
+                        bot_type,
+                        GameConfiguration::builder().num_players(num_players),
+                        &table,
+                        Pace::default(),
+                    )
+                    .await
+                {
                     Ok(new_game_id) => {
                         log::info!("✅ Game created successfully: {}", new_game_id);
 
@@ -511,18 +961,15 @@ impl WebSocketService {
                             }
                             Err(e) => {
                                 log::error!("❌ Failed to get created game: {}", e);
-                                let error_msg = WsMessage::Error {
-                                    message: format!("Failed to get created game: {e}"),
-                                };
+                                let error_msg =
+                                    WsMessage::failure("Failed to get created game", &e);
                                 let _ = broadcaster.send((game_id.to_string(), error_msg));
                             }
                         }
                     }
                     Err(e) => {
                         log::error!("❌ Game creation failed: {}", e);
-                        let error_msg = WsMessage::Error {
-                            message: format!("Game creation failed: {e}"),
-                        };
+                        let error_msg = WsMessage::failure("Game creation failed", &e);
                         let _ = broadcaster.send((game_id.to_string(), error_msg));
                     }
                 }
@@ -536,7 +983,7 @@ impl WebSocketService {
     }
 
     /// Check if a game has active connections
-    async fn has_active_connections(&self, game_id: &str) -> bool {
+    pub async fn has_active_connections(&self, game_id: &str) -> bool {
         let connections = self.active_connections.read().await;
         connections
             .get(game_id)
@@ -576,31 +1023,24 @@ impl WebSocketService {
                 break;
             }
 
-            // Try to process a bot turn
-            match game_service.process_bot_turn(game_id).await {
-                Ok(Some(_events)) => {
-                    // Send bot thinking indicator
-                    let thinking_msg = WsMessage::BotThinking {
-                        player_id: "current_bot".to_string(), // Simplified
-                    };
-                    let _ = broadcaster.send((game_id.to_string(), thinking_msg));
-
-                    // Small delay to make bot moves visible
-                    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-                    // Send updated game state after bot move
-                    if let Ok(updated_game) = game_service.get_game(game_id).await {
-                        let update_msg = WsMessage::GameUpdated { game: updated_game };
-                        if let Err(e) = broadcaster.send((game_id.to_string(), update_msg)) {
-                            log::error!(
-                                "Failed to broadcast game update for game {}: {:?}",
-                                game_id,
-                                e
-                            );
-                        }
+            match bot_loop::step(game_service, broadcaster, game_id).await {
+                BotStep::Moved | BotStep::AutoPassed | BotStep::Failed => {}
+                BotStep::SpeedRound => {
+                    Self::run_speed_round(
+                        game_service,
+                        broadcaster,
+                        game_id,
+                        active_connections,
+                        cancel_rx,
+                    )
+                    .await;
+                    if cancel_rx.try_recv().is_ok() {
+                        log::info!("🛑 Speed rounds cancelled for game {}", game_id);
+                        break;
                     }
+                    game_service.clock().sleep(speed::ROLL_INTERVAL).await;
                 }
-                Ok(None) => {
+                BotStep::Idle => {
                     // No more bot moves needed - exit the loop instead of continuous polling
                     log::debug!(
                         "🤖 No bot actions needed for game {}, ending bot simulation loop",
@@ -608,16 +1048,80 @@ impl WebSocketService {
                     );
                     break;
                 }
-                Err(e) => {
-                    log::error!("Bot processing error for game {}: {}", game_id, e);
-                    tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
-                }
             }
         }
 
         log::info!("🏁 Bot simulation ended for game {}", game_id);
     }
 
+    /// Id of the game's first human seat, the one this connection plays
+    async fn human_player_id(game_service: &GameService, game_id: &str) -> CatanResult<String> {
+        let game = game_service.get_game(game_id).await?;
+        Ok(game
+            .players
+            .iter()
+            .find(|p| !game.bot_colors.contains(&p.color))
+            .map(|p| p.id.clone())
+            .unwrap_or_else(|| "player_0".to_string()))
+    }
+
+    /// Roll, hold the build window open until it is ready, then resolve it. Gives up
+    /// early (leaving the window open) when the game loses its connections or the
+    /// simulation is cancelled.
+    async fn run_speed_round(
+        game_service: &GameService,
+        broadcaster: &broadcast::Sender<(GameId, WsMessage)>,
+        game_id: &str,
+        active_connections: &Arc<RwLock<HashMap<GameId, HashSet<String>>>>,
+        cancel_rx: &mut broadcast::Receiver<()>,
+    ) {
+        let broadcast_game = || async {
+            if let Ok(game) = game_service.get_game(game_id).await {
+                let _ = broadcaster.send((game_id.to_string(), WsMessage::game_updated(game)));
+            }
+        };
+
+        match game_service.open_speed_round(game_id).await {
+            Ok(Some(window)) => {
+                let _ =
+                    broadcaster.send((game_id.to_string(), WsMessage::SpeedRoundOpened { window }));
+                broadcast_game().await;
+            }
+            Ok(None) => return,
+            Err(e) => {
+                log::error!("Speed round failed to open for game {}: {}", game_id, e);
+                game_service.clock().sleep(bot_loop::RETRY_DELAY).await;
+                return;
+            }
+        }
+
+        while !game_service.speed_round_ready(game_id).await {
+            let has_connections = active_connections
+                .read()
+                .await
+                .get(game_id)
+                .is_some_and(|conns| !conns.is_empty());
+            if !has_connections || !cancel_rx.is_empty() {
+                return;
+            }
+            game_service
+                .clock()
+                .sleep(tokio::time::Duration::from_millis(250))
+                .await;
+        }
+
+        match game_service.resolve_speed_round(game_id).await {
+            Ok(outcomes) => {
+                let _ = broadcaster.send((
+                    game_id.to_string(),
+                    WsMessage::SpeedRoundResolved { outcomes },
+                ));
+            }
+            Err(e) => log::error!("Speed round failed to resolve for game {}: {}", game_id, e),
+        }
+        broadcast_game().await;
+    }
+
     /// Send a message to a WebSocket sender
     async fn send_message(
         &self,
@@ -627,6 +1131,17 @@ impl WebSocketService {
         Self::send_message_static(sender, message).await
     }
 
+    /// Send an already serialized message
+    async fn send_value_static(
+        sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+        value: &serde_json::Value,
+    ) -> Result<(), axum::Error> {
+        sender
+            .send(Message::Text(value.to_string().into()))
+            .await
+            .map_err(axum::Error::new)
+    }
+
     /// Static version of send_message for use in spawned tasks
     async fn send_message_static(
         sender: &mut futures::stream::SplitSink<WebSocket, Message>,
@@ -640,6 +1155,11 @@ impl WebSocketService {
             .map_err(axum::Error::new)
     }
 
+    /// Get the game service backing this WebSocket layer
+    pub fn game_service(&self) -> &Arc<GameService> {
+        &self.game_service
+    }
+
     /// Get the broadcaster for sending messages to all clients
     pub fn broadcaster(&self) -> broadcast::Sender<(GameId, WsMessage)> {
         self.broadcaster.clone()
@@ -653,15 +1173,14 @@ impl WebSocketService {
         simulations: usize,
     ) -> std::collections::HashMap<String, f32> {
         let num_players = root_state.get_num_players() as usize;
-        let mut win_counts = vec![0usize; num_players];
+        let mut win_counts = vec![0f64; num_players];
 
         for _ in 0..simulations {
             let mut state = root_state.clone();
 
             // Run a random rollout capped at 1000 steps
             for _ in 0..1000 {
-                if let Some(winner) = state.winner() {
-                    win_counts[winner as usize] += 1;
+                if state.winner().is_some() {
                     break;
                 }
                 let actions = state.generate_playable_actions();
@@ -675,6 +1194,14 @@ impl WebSocketService {
                     break;
                 }
             }
+            // A won rollout credits the winner; one cut off at the cap is split by the
+            // win model's estimate of its final position
+            for (count, p) in win_counts
+                .iter_mut()
+                .zip(FITTED_WIN_MODEL.win_probabilities(&state))
+            {
+                *count += p;
+            }
         }
 
         // Convert counts to percentages keyed by player color string
@@ -691,3 +1218,419 @@ impl WebSocketService {
         probs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::HistoryAccess;
+    use crate::clock::FakeClock;
+    use crate::enums::MapType;
+    use crate::hibernation::HibernationStore;
+    use crate::pace::Pace;
+    use crate::sharding::{ShardNode, ShardRouter};
+    use axum::extract::{Path, State as AxumState, WebSocketUpgrade};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::serve::Listener;
+    use axum::Router;
+    use std::time::Duration;
+    use tokio::io::DuplexStream;
+    use tokio_tungstenite::tungstenite::Message as ClientMessage;
+    use tokio_tungstenite::{client_async, WebSocketStream};
+
+    type Client = WebSocketStream<DuplexStream>;
+
+    /// Hands the server ends of in-memory pipes to axum, standing in for a socket
+    struct PipeListener(mpsc::UnboundedReceiver<DuplexStream>);
+
+    impl Listener for PipeListener {
+        type Io = DuplexStream;
+        type Addr = ();
+
+        async fn accept(&mut self) -> (DuplexStream, ()) {
+            match self.0.recv().await {
+                Some(pipe) => (pipe, ()),
+                None => std::future::pending().await,
+            }
+        }
+
+        fn local_addr(&self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A server for `websocket` routed like the real one, and a way to dial it
+    fn serve(websocket: WebSocketService) -> mpsc::UnboundedSender<DuplexStream> {
+        async fn ws_handler(
+            ws: WebSocketUpgrade,
+            Path(game_id): Path<String>,
+            AxumState(websocket): AxumState<WebSocketService>,
+        ) -> impl IntoResponse {
+            ws.on_upgrade(move |socket| async move {
+                websocket
+                    .handle_connection(socket, Some(game_id), ActionsFormat::Full)
+                    .await
+            })
+        }
+
+        let router = Router::new()
+            .route("/ws/{game_id}", get(ws_handler))
+            .with_state(websocket);
+        let (dial, pipes) = mpsc::unbounded_channel();
+        tokio::spawn(async move { axum::serve(PipeListener(pipes), router).await });
+        dial
+    }
+
+    async fn connect(dial: &mpsc::UnboundedSender<DuplexStream>, game_id: &str) -> Client {
+        let (client_end, server_end) = tokio::io::duplex(1 << 16);
+        dial.send(server_end).unwrap();
+        let url = format!("ws://catan.test/ws/{game_id}");
+        client_async(url, client_end).await.unwrap().0
+    }
+
+    /// Next text message, skipping pings and the like
+    async fn receive(client: &mut Client) -> serde_json::Value {
+        loop {
+            let message = tokio::time::timeout(Duration::from_secs(5), client.next())
+                .await
+                .expect("no message within 5s")
+                .expect("connection closed")
+                .unwrap();
+            if let ClientMessage::Text(text) = message {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    /// A two-seat game where the human opens, behind a service on a fake clock
+    async fn service_with_game(scheduling: BotScheduling) -> (WebSocketService, GameId) {
+        let game_service = Arc::new(GameService::new().with_clock(Arc::new(FakeClock::default())));
+        let game_id = game_service
+            .create_game(
+                "human_random",
+                GameConfiguration::builder().num_players(2),
+                &TableOptions::default(),
+                Pace::Blitz,
+            )
+            .await
+            .unwrap();
+        let websocket = WebSocketService::new(game_service).with_scheduling(scheduling);
+        (websocket, game_id)
+    }
+
+    #[tokio::test]
+    async fn test_actions_reach_every_connected_client() {
+        let (websocket, game_id) = service_with_game(BotScheduling::Stepped).await;
+        let dial = serve(websocket);
+
+        let mut player = connect(&dial, &game_id).await;
+        assert_eq!(receive(&mut player).await["type"], "greeting");
+        let state = receive(&mut player).await;
+        assert_eq!(state["type"], "game_state");
+        assert_eq!(state["game_id"], game_id.as_str());
+        let mut spectator = connect(&dial, &game_id).await;
+        assert_eq!(receive(&mut spectator).await["type"], "greeting");
+        assert_eq!(receive(&mut spectator).await["type"], "game_state");
+
+        let action = serde_json::json!({
+            "type": "player_action",
+            "action": state["game"]["current_playable_actions"][0],
+            "seq": 1,
+            "version": state["game"]["version"],
+        });
+        player
+            .send(ClientMessage::Text(action.to_string().into()))
+            .await
+            .unwrap();
+        for client in [&mut player, &mut spectator] {
+            let result = receive(client).await;
+            assert_eq!(result["type"], "action_result");
+            assert_eq!(result["success"], true);
+            let update = receive(client).await;
+            assert_eq!(update["type"], "game_updated");
+            assert_eq!(update["game_id"], game_id.as_str());
+            assert_eq!(update["game"]["last_action_seq"], 1);
+        }
+    }
+
+    /// Wait for `condition` on the service's bookkeeping, which lags behind the sockets
+    async fn eventually(
+        websocket: &WebSocketService,
+        condition: impl Fn(&WebSocketService) -> bool,
+    ) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !condition(websocket) {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("condition not reached within 5s");
+    }
+
+    #[tokio::test]
+    async fn test_last_disconnect_stops_the_bots() {
+        let (websocket, game_id) = service_with_game(BotScheduling::Background).await;
+        let dial = serve(websocket.clone());
+        let connections = |websocket: &WebSocketService| {
+            let connections = websocket.active_connections.try_read();
+            connections.map_or(usize::MAX, |connections| {
+                connections.get(&game_id).map_or(0, HashSet::len)
+            })
+        };
+        let bots_running = |websocket: &WebSocketService| {
+            let bot_tasks = websocket.bot_tasks.try_read();
+            bot_tasks.map_or(true, |bot_tasks| bot_tasks.contains_key(&game_id))
+        };
+
+        let mut clients = Vec::new();
+        for _ in 0..2 {
+            let mut client = connect(&dial, &game_id).await;
+            receive(&mut client).await;
+            receive(&mut client).await;
+            clients.push(client);
+        }
+        assert_eq!(connections(&websocket), 2);
+        assert!(bots_running(&websocket));
+
+        // Bots keep going while anyone is still watching
+        clients.pop().unwrap().close(None).await.unwrap();
+        eventually(&websocket, |websocket| connections(websocket) == 1).await;
+        assert!(bots_running(&websocket));
+
+        clients.pop().unwrap().close(None).await.unwrap();
+        eventually(&websocket, |websocket| !bots_running(websocket)).await;
+        assert_eq!(connections(&websocket), 0);
+    }
+
+    #[tokio::test]
+    async fn test_idle_game_hibernates_until_its_next_access() {
+        let dir = std::env::temp_dir().join(format!("catan-hibernation-{}", uuid::Uuid::new_v4()));
+        let store = HibernationStore::new(&dir);
+        let game_service = Arc::new(GameService::new().with_hibernation(Some(store.clone())));
+        let game_id = game_service
+            .create_game(
+                "human_random",
+                GameConfiguration::builder().num_players(2),
+                &TableOptions::default(),
+                Pace::Blitz,
+            )
+            .await
+            .unwrap();
+        let game = game_service.get_game(&game_id).await.unwrap();
+        let action = game.current_playable_actions[0].clone();
+        let human = game.players[game.current_player_index].id.clone();
+        game_service
+            .process_sequenced_action(&game_id, &human, 3, game.version, action)
+            .await
+            .unwrap();
+        let websocket =
+            WebSocketService::new(game_service.clone()).with_scheduling(BotScheduling::Stepped);
+        let dial = serve(websocket);
+
+        let mut client = connect(&dial, &game_id).await;
+        receive(&mut client).await;
+        receive(&mut client).await;
+        client.close(None).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while game_service.list_games().await.contains(&game_id) {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("game not hibernated within 5s");
+        assert!(store.contains(&game_id));
+        assert!(game_service.game_exists(&game_id).await);
+
+        // The next connection wakes the game where it was left
+        let mut client = connect(&dial, &game_id).await;
+        receive(&mut client).await;
+        let state = receive(&mut client).await;
+        assert_eq!(state["type"], "game_state");
+        assert_eq!(state["game"]["version"], 1);
+        assert_eq!(state["game"]["last_action_seq"], 3);
+        assert_eq!(state["game"]["pace"], "blitz");
+        assert!(!store.contains(&game_id));
+        let page = game_service.action_page(&game_id, 0, 1).await.unwrap();
+        assert!(page.actions[0].think_ms.is_some());
+        assert_eq!(game_service.get_players(&game_id).await.unwrap().len(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_games_are_redirected_to_and_handed_off_to_their_owner() {
+        let dir = std::env::temp_dir().join(format!("catan-handoff-{}", uuid::Uuid::new_v4()));
+        let store = HibernationStore::new(&dir);
+        let ring = |local: &str, ids: &[&str]| {
+            let nodes = ids
+                .iter()
+                .map(|id| ShardNode {
+                    id: id.to_string(),
+                    url: format!("http://{id}.test"),
+                })
+                .collect();
+            Some(ShardRouter::new(local, nodes).unwrap())
+        };
+        let instance = |local: &str| {
+            GameService::new()
+                .with_hibernation(Some(store.clone()))
+                .with_router(ring(local, &["a", "b"]))
+        };
+        let (a, b) = (instance("a"), Arc::new(instance("b")));
+        let mut game_ids = Vec::new();
+        for service in [&a, &b] {
+            let game_id = service
+                .create_game(
+                    "human_random",
+                    GameConfiguration::builder().num_players(2),
+                    &TableOptions::default(),
+                    Pace::Blitz,
+                )
+                .await
+                .unwrap();
+            assert!(service.router().unwrap().is_local(&game_id));
+            game_ids.push(game_id);
+        }
+        let [on_a, on_b] = &game_ids[..] else {
+            unreachable!()
+        };
+
+        // Following a's game from b points the client at a
+        let websocket = WebSocketService::new(b.clone()).with_scheduling(BotScheduling::Stepped);
+        let dial = serve(websocket);
+        let mut client = connect(&dial, on_b).await;
+        receive(&mut client).await;
+        receive(&mut client).await;
+        let subscribe = serde_json::json!({"type": "subscribe", "game_id": on_a});
+        client
+            .send(ClientMessage::Text(subscribe.to_string().into()))
+            .await
+            .unwrap();
+        let redirect = receive(&mut client).await;
+        assert_eq!(redirect["type"], "redirect");
+        assert_eq!(redirect["url"], format!("http://a.test/ws/games/{on_a}"));
+
+        // Once a leaves the ring, its game goes to b through the shared store
+        let drained = a.with_router(ring("a", &["b"]));
+        assert_eq!(drained.hand_off_games().await.unwrap(), vec![on_a.clone()]);
+        assert!(!drained.list_games().await.contains(on_a));
+        assert_eq!(b.get_game(on_a).await.unwrap().id, *on_a);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_live_games_fork_only_for_admins_and_their_account() {
+        let game_service = GameService::new();
+        let game_id = game_service
+            .create_game(
+                "random",
+                GameConfiguration::builder().num_players(2),
+                &TableOptions::default(),
+                Pace::default(),
+            )
+            .await
+            .unwrap();
+
+        let forked = game_service
+            .fork_game(&game_id, 0, &[], HistoryAccess::Anyone)
+            .await;
+        assert!(matches!(forked, Err(CatanError::Validation(_))));
+        let fork_id = game_service
+            .fork_game(&game_id, 0, &[], HistoryAccess::Admin)
+            .await
+            .unwrap();
+        assert!(game_service.game_exists(&fork_id).await);
+
+        // The account seated against bots may move its game in play, given its token
+        let token = game_service
+            .set_preferences("ada", None, Default::default())
+            .await
+            .unwrap();
+        let game_id = game_service
+            .create_game(
+                "human_random",
+                GameConfiguration::builder().num_players(2),
+                &TableOptions::default(),
+                Pace::Blitz,
+            )
+            .await
+            .unwrap();
+        game_service.seat_account(&game_id, "ada").await.unwrap();
+        for token in [None, Some("guess")] {
+            let access = HistoryAccess::Account { name: "ada", token };
+            let export = game_service.export_game(&game_id, access).await;
+            assert!(matches!(export, Err(CatanError::Validation(_))));
+        }
+        let access = HistoryAccess::Account {
+            name: "ada",
+            token: token.as_deref(),
+        };
+        let save = game_service.export_game(&game_id, access).await.unwrap();
+        assert_eq!(save.pace, Pace::Blitz);
+        let imported = game_service.import_game(save).await.unwrap();
+        assert_eq!(game_service.pace(&imported).await.unwrap(), Pace::Blitz);
+        let forked = game_service.fork_game(&game_id, 0, &[], access).await;
+        assert!(forked.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_game_applies_the_table_options() {
+        let message = r#"{"type": "create_game", "mode": "RANDOM_BOTS", "num_players": 3,
+            "vps_to_win": 8, "discard_limit": 9, "map_type": "Mini", "map_seed": 7,
+            "bots": [null, "hard"], "palette": "colorblind_safe"}"#;
+        let Ok(WsMessage::CreateGame {
+            num_players, table, ..
+        }) = serde_json::from_str(message)
+        else {
+            panic!("create_game did not parse");
+        };
+        let game_service = GameService::new();
+        let game_id = game_service
+            .create_game(
+                "random",
+                GameConfiguration::builder().num_players(num_players),
+                &table,
+                Pace::default(),
+            )
+            .await
+            .unwrap();
+
+        let game = game_service.get_game(&game_id).await.unwrap();
+        let config = game.state.as_ref().unwrap().get_config();
+        assert_eq!((config.vps_to_win, config.discard_limit), (8, 9));
+        assert!(matches!(config.map_type, MapType::Mini));
+        let live_export = game_service
+            .export_game(&game_id, HistoryAccess::Anyone)
+            .await;
+        assert!(matches!(live_export, Err(CatanError::Validation(_))));
+        let save = game_service
+            .export_game(&game_id, HistoryAccess::Admin)
+            .await
+            .unwrap();
+        assert_eq!(save.replay.map_seed, 7);
+        let snapshot = serde_json::to_value(&game).unwrap();
+        assert_eq!(snapshot["palette"], "colorblind_safe");
+        assert_eq!(snapshot["seat_colors"].as_array().unwrap().len(), 3);
+        assert_eq!(snapshot["seat_colors"][0]["seat"], game.players[0].color);
+        assert_eq!(snapshot["seat_colors"][0]["hex"], "#d55e00");
+
+        // The human seat cannot be handed to a bot, nor can a seat get an unknown bot
+        for bots in [vec![Some("hard")], vec![None, Some("genius")]] {
+            let table = TableOptions {
+                bots: bots
+                    .into_iter()
+                    .map(|bot| bot.map(str::to_string))
+                    .collect(),
+                ..Default::default()
+            };
+            let created = game_service
+                .create_game(
+                    "human_random",
+                    GameConfiguration::builder().num_players(2),
+                    &table,
+                    Pace::default(),
+                )
+                .await;
+            assert!(matches!(created, Err(CatanError::Validation(_))));
+        }
+    }
+}
@@ -156,8 +156,9 @@ export class ActionsToolbarComponent {
     // Hide action buttons during bot turns
     if (this.isBotTurn || this.isBotThinking) return false;
     
-    // Show action buttons during regular play and discard, but hide during robber movement (direct tile clicking)
+    // Show action buttons during regular play, the Special Build Phase and discard, but hide during robber movement (direct tile clicking)
     return this.gameState.current_prompt === 'PLAY_TURN' || 
+           this.gameState.current_prompt === 'SPECIAL_BUILD' ||
            this.gameState.current_prompt === 'DISCARD';
   }
 
@@ -91,6 +91,7 @@ export class GameComponent implements OnInit, OnDestroy, AfterViewInit {
   resourceSelectorOpen = false;
   resourceSelectorOptions: ResourceOption[] = [];
   resourceSelectorMode: 'monopoly' | 'yearOfPlenty' | 'discard' | 'trade' = 'monopoly';
+  discardCount = 0;
 
   // Available trades
   // trades property removed - ActionToolbar now handles trade detection
@@ -178,7 +179,8 @@ export class GameComponent implements OnInit, OnDestroy, AfterViewInit {
         // Load game state successfully - node positioning is now handled by hex math calculations
 
         // Clear building mode if it's no longer player's turn or actions changed
-        if (this.buildingMode !== 'none' && (this.isBotTurn || this.gameState.current_prompt !== 'PLAY_TURN')) {
+        const canBuild = ['PLAY_TURN', 'SPECIAL_BUILD'].includes(this.gameState.current_prompt ?? '');
+        if (this.buildingMode !== 'none' && (this.isBotTurn || !canBuild)) {
           this.buildingMode = 'none';
           console.log('🚫 Building mode cleared due to game state change');
         }
@@ -553,6 +555,12 @@ export class GameComponent implements OnInit, OnDestroy, AfterViewInit {
           console.error('Error using year of plenty card:', err);
         },
       });
+    } else if (this.resourceSelectorMode === 'discard') {
+      // Expand { Wood: 2, Ore: 1 } into one entry per card
+      const counts: Record<string, number> = resources.resources;
+      const discarded = Object.entries(counts).flatMap(([type, count]) => Array(count).fill(type));
+      this.websocketService.sendPlayerAction(this.gameId, { Discard: { resources: discarded } });
+      this.resourceSelectorOpen = false;
     }
   }
 
@@ -639,21 +647,15 @@ export class GameComponent implements OnInit, OnDestroy, AfterViewInit {
   proceedWithDiscard(): void {
     if (!this.gameId || this.isWatchOnlyMode || this.isBotTurn || this.isBotThinking) return;
 
-    console.log('🗂️ Looking for DISCARD action with proper resources field...');
-
-    // Find the actual DISCARD action from current_playable_actions (includes required resources field)
-    const discardAction = this.gameState?.current_playable_actions?.find((action: any) => {
-      return action.hasOwnProperty('Discard');
-    });
-
-    if (discardAction) {
-      console.log('🗂️ Found DISCARD action with resources:', discardAction);
-      this.websocketService.sendPlayerAction(this.gameId, discardAction);
-    } else {
-      console.error(
-        '❌ No DISCARD action found in current_playable_actions - backend should provide this'
-      );
-    }
+    // Let the player pick which half of the hand to give up
+    const hand = this.getCurrentPlayer()?.resources || {};
+    const total = Object.values(hand).reduce((sum, count) => sum + count, 0);
+    this.discardCount = total - Math.floor(total / 2);
+    this.resourceSelectorMode = 'discard';
+    this.resourceSelectorOptions = ['Wood', 'Brick', 'Sheep', 'Wheat', 'Ore']
+      .map(type => ({ type, label: type, count: hand[type.toLowerCase()] || 0 }))
+      .filter(option => option.count > 0);
+    this.resourceSelectorOpen = true;
   }
 
   rollDice(): void {
@@ -667,9 +669,11 @@ export class GameComponent implements OnInit, OnDestroy, AfterViewInit {
   endTurn(): void {
     if (!this.gameId || this.isWatchOnlyMode || this.isBotTurn || this.isBotThinking) return;
 
-    console.log('⏭️ Sending EndTurn action via WebSocket');
+    // Between turns of a 5-6 player game the main button passes the Special Build Phase
+    const action = this.gameState?.current_prompt === 'SPECIAL_BUILD' ? { PassBuildPhase: {} } : { EndTurn: {} };
+    console.log('⏭️ Sending', Object.keys(action)[0], 'action via WebSocket');
     // Use WebSocket instead of HTTP for consistent action handling
-    this.websocketService.sendPlayerAction(this.gameId, { EndTurn: {} });
+    this.websocketService.sendPlayerAction(this.gameId, action);
   }
 
   getCurrentPlayer(): ExtendedPlayer | null {
@@ -835,7 +839,7 @@ export class GameComponent implements OnInit, OnDestroy, AfterViewInit {
       if (typeof action === 'string') {
         const enumMap: { [key: string]: string[] } = {
           ROLL: ['Roll'],
-          END_TURN: ['EndTurn'],
+          END_TURN: ['EndTurn', 'PassBuildPhase'],
           BUY_DEVELOPMENT_CARD: ['BuyDevelopmentCard'],
           PLAY_MONOPOLY: ['PlayMonopoly'],
           PLAY_YEAR_OF_PLENTY: ['PlayYearOfPlenty'],
@@ -10,6 +10,7 @@ import { MatProgressSpinnerModule } from '@angular/material/progress-spinner';
 enum GameMode {
   HUMAN_VS_CATANATRON = 'HUMAN_VS_CATANATRON',
   HUMAN_VS_RANDOM = 'HUMAN_VS_RANDOM',
+  HUMAN_VS_HARD = 'HUMAN_VS_HARD',
   RANDOM_BOTS = 'RANDOM_BOTS',
   CATANATRON_BOTS = 'CATANATRON_BOTS',
 }
@@ -59,6 +60,9 @@ enum GameMode {
               <button class="action-button primary" (click)="startGame(GameMode.HUMAN_VS_CATANATRON)">
                 PLAY AGAINST CATANATRON
               </button>
+              <button class="action-button secondary" (click)="startGame(GameMode.HUMAN_VS_HARD)">
+                PLAY AGAINST CHAMPION
+              </button>
               <button class="action-button secondary" (click)="startGame(GameMode.HUMAN_VS_RANDOM)">
                 PLAY AGAINST RANDOM
               </button>
@@ -323,12 +327,14 @@ export class HomeComponent implements OnInit {
   }
 
   // Map local GameMode enum to service string literals
-  private mapGameMode(mode: GameMode): 'HUMAN_VS_CATANATRON' | 'HUMAN_VS_RANDOM' | 'RANDOM_BOTS' | 'CATANATRON_BOTS' {
+  private mapGameMode(mode: GameMode): 'HUMAN_VS_CATANATRON' | 'HUMAN_VS_RANDOM' | 'HUMAN_VS_HARD' | 'RANDOM_BOTS' | 'CATANATRON_BOTS' {
     switch (mode) {
       case GameMode.HUMAN_VS_CATANATRON:
         return 'HUMAN_VS_CATANATRON';
       case GameMode.HUMAN_VS_RANDOM:
         return 'HUMAN_VS_RANDOM';
+      case GameMode.HUMAN_VS_HARD:
+        return 'HUMAN_VS_HARD';
       case GameMode.RANDOM_BOTS:
         return 'RANDOM_BOTS';
       case GameMode.CATANATRON_BOTS:
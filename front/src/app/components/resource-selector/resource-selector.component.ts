@@ -103,7 +103,7 @@ export interface ResourceOption {
               </div>
             }
             @if (mode === 'discard') {
-              <p>Select resources to discard</p>
+              <p>Select {{ discardCount }} resources to discard</p>
               <div class="resource-grid">
                 @for (option of options; track option.type) {
                   <button
@@ -156,6 +156,8 @@ export class ResourceSelectorComponent {
   @Input() open: boolean = false;
   @Input() options: ResourceOption[] = [];
   @Input() mode: 'monopoly' | 'yearOfPlenty' | 'discard' | 'trade' = 'monopoly';
+  // Cards to pick in discard mode
+  @Input() discardCount: number = 0;
 
   @Output() onClose = new EventEmitter<void>();
   @Output() onSelect = new EventEmitter<any>();
@@ -287,8 +289,8 @@ export class ResourceSelectorComponent {
     if (this.mode === 'yearOfPlenty') {
       return this.selectedResources.length === 2;
     } else if (this.mode === 'discard') {
-      // Implement discard validation logic
-      return this.selectedResources.length > 0;
+      const selected = this.selectedResources.reduce((sum, r) => sum + (r.count || 1), 0);
+      return selected === this.discardCount;
     }
     return false;
   }
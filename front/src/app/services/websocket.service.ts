@@ -8,21 +8,76 @@ export type WsMessageType =
   | 'error'
   | 'greeting'
   | 'player_action'
+  | 'stale_action'
   | 'get_game_state'
   | 'bot_thinking'
   | 'action_result'
   | 'create_game'
-  | 'game_created';
+  | 'game_created'
+  | 'campaign_standings'
+  | 'speed_submit'
+  | 'set_auto_pass'
+  | 'subscribe'
+  | 'unsubscribe'
+  | 'speed_round_opened'
+  | 'speed_round_resolved';
+
+// Ends the seat's turn once nothing worth doing is left
+export interface AutoPass {
+  delay_ms?: number;
+  // Also pass when the only other options are maritime trades that enable no build
+  skip_dominated_trades?: boolean;
+  // Trades are only skipped below this hand size
+  trade_hand_limit?: number;
+}
+
+// Open build window of a speed round
+export interface SpeedWindow {
+  round: number;
+  roller: string;
+  dice: [number, number] | null;
+  priority: string[];
+  submitted: string[];
+  closes_in_ms: number;
+}
+
+export interface SpeedOutcome {
+  color: string;
+  action: any;
+  applied: boolean;
+  reason?: string;
+}
+
+// Category of an `error` message, stable across server wording changes
+export type WsErrorCode =
+  | 'GAME_NOT_FOUND'
+  | 'NOT_YOUR_TURN'
+  | 'ILLEGAL_ACTION'
+  | 'RATE_LIMITED'
+  | 'VERSION_CONFLICT'
+  | 'INTERNAL';
 
 export interface WsMessage {
   type: WsMessageType;
   data?: any;
   game?: any;
+  buildable?: any; // game_updated
   game_id?: string;
   action?: any;
+  seq?: number; // player_action, stale_action
+  version?: number; // player_action, stale_action
   message?: string;
+  code?: WsErrorCode; // error
+  details?: Record<string, unknown>; // error
   success?: boolean;
   events?: any[];
+  summary?: any; // campaign_standings
+  actions?: any[]; // speed_submit
+  auto_pass?: AutoPass | null; // set_auto_pass
+  window?: SpeedWindow; // speed_round_opened
+  outcomes?: SpeedOutcome[]; // speed_round_resolved
+  engine_version?: string; // greeting
+  rules_revision?: number; // greeting
 }
 
 @Injectable({
@@ -33,6 +88,12 @@ export class WebsocketService {
   private messagesSubject = new Subject<WsMessage>();
   private connectionStatusSubject = new BehaviorSubject<boolean>(false);
   private lastGreeting = new BehaviorSubject<string>('');
+  // Sequence number of the last action sent; the server drops resubmitted numbers
+  private actionSeq = 0;
+  // Version of the latest game snapshot received; actions are chosen against it
+  private gameVersion = 0;
+  // Game the socket was opened for; other followed games do not move its counters
+  private gameId: string | null = null;
 
   public messages$ = this.messagesSubject.asObservable();
   public connectionStatus$ = this.connectionStatusSubject.asObservable();
@@ -44,6 +105,9 @@ export class WebsocketService {
   public connect(gameId: string): Observable<boolean> {
     // Close existing connection if any
     this.disconnect();
+    this.actionSeq = 0;
+    this.gameVersion = 0;
+    this.gameId = gameId;
 
     const wsUrl = `${environment.wsUrl}/games/${gameId}`;
 
@@ -64,6 +128,15 @@ export class WebsocketService {
           this.lastGreeting.next(message.message || message.data);
         }
 
+        // Resync so the next action is numbered above the last one the server accepted
+        const ownGame = !message.game_id || message.game_id === this.gameId;
+        if (ownGame && typeof message.game?.last_action_seq === 'number') {
+          this.actionSeq = Math.max(this.actionSeq, message.game.last_action_seq);
+        }
+        if (ownGame && typeof message.game?.version === 'number') {
+          this.gameVersion = message.game.version;
+        }
+
         this.messagesSubject.next(message);
       } catch (error) {
         console.error('❌ Error parsing WebSocket message:', error);
@@ -105,6 +178,8 @@ export class WebsocketService {
     this.sendMessage({
       type: 'player_action',
       action: action,
+      seq: ++this.actionSeq,
+      version: this.gameVersion,
     });
   }
 
@@ -117,6 +192,15 @@ export class WebsocketService {
     });
   }
 
+  // Follow another game on this socket; its messages carry its game_id
+  public subscribe(gameId: string): void {
+    this.sendMessage({ type: 'subscribe', game_id: gameId });
+  }
+
+  public unsubscribe(gameId: string): void {
+    this.sendMessage({ type: 'unsubscribe', game_id: gameId });
+  }
+
   public createGame(mode: string, numPlayers: number): void {
     this.sendMessage({
       type: 'create_game',
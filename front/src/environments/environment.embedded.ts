@@ -0,0 +1,7 @@
+// Served by the backend itself (`cargo run --features embedded-frontend`), so the API
+// lives on whatever host and port the page came from
+export const environment = {
+  production: true,
+  apiUrl: '',
+  wsUrl: `${location.protocol === 'https:' ? 'wss' : 'ws'}://${location.host}/ws`,
+};